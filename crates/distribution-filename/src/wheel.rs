@@ -62,6 +62,30 @@ impl WheelFilename {
         compatible_tags.compatibility(&self.python_tag, &self.abi_tag, &self.platform_tag)
     }
 
+    /// Select the wheel that uv would install for the given tags from a set of candidates.
+    ///
+    /// Returns the compatible wheel with the highest [`TagCompatibility`], preferring the earliest
+    /// candidate in the event of a tie, or `None` if none of the candidates are compatible.
+    pub fn best_match<'a>(
+        candidates: impl IntoIterator<Item = &'a WheelFilename>,
+        compatible_tags: &Tags,
+    ) -> Option<&'a WheelFilename> {
+        let mut best: Option<(&WheelFilename, TagCompatibility)> = None;
+        for candidate in candidates {
+            let compatibility = candidate.compatibility(compatible_tags);
+            if !compatibility.is_compatible() {
+                continue;
+            }
+            if best
+                .as_ref()
+                .map_or(true, |(_, existing)| compatibility > *existing)
+            {
+                best = Some((candidate, compatibility));
+            }
+        }
+        best.map(|(wheel, _)| wheel)
+    }
+
     /// Get the tag for this wheel.
     pub fn get_tag(&self) -> String {
         format!(
@@ -319,4 +343,42 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn best_match() {
+        let tags = Tags::new(vec![
+            (
+                "cp312".to_string(),
+                "cp312".to_string(),
+                "manylinux_2_17_x86_64".to_string(),
+            ),
+            (
+                "cp312".to_string(),
+                "abi3".to_string(),
+                "manylinux_2_17_x86_64".to_string(),
+            ),
+            ("py3".to_string(), "none".to_string(), "any".to_string()),
+        ]);
+        let candidates = [
+            "foo-1.0-py3-none-any.whl",
+            "foo-1.0-cp312-abi3-manylinux_2_17_x86_64.whl",
+            "foo-1.0-cp312-cp312-manylinux_2_17_x86_64.whl",
+            "foo-1.0-cp311-cp311-manylinux_2_17_x86_64.whl",
+        ]
+        .map(|filename| WheelFilename::from_str(filename).unwrap());
+
+        let best = WheelFilename::best_match(&candidates, &tags).unwrap();
+        assert_eq!(
+            best.to_string(),
+            "foo-1.0-cp312-cp312-manylinux_2_17_x86_64.whl"
+        );
+
+        let best = WheelFilename::best_match(&candidates[..2], &tags).unwrap();
+        assert_eq!(
+            best.to_string(),
+            "foo-1.0-cp312-abi3-manylinux_2_17_x86_64.whl"
+        );
+
+        assert!(WheelFilename::best_match(&candidates[3..], &tags).is_none());
+    }
 }
@@ -66,10 +66,14 @@ impl TryFrom<&Url> for DirectGitUrl {
 
 impl From<&Url> for DirectArchiveUrl {
     fn from(url: &Url) -> Self {
-        Self {
-            url: url.clone(),
-            subdirectory: get_subdirectory(url),
-        }
+        let subdirectory = get_subdirectory(url);
+
+        // Remove the fragment (e.g., `#subdirectory=pkg_dir`), which isn't part of the location of
+        // the archive itself.
+        let mut url = url.clone();
+        url.set_fragment(None);
+
+        Self { url, subdirectory }
     }
 }
 
@@ -238,6 +242,16 @@ mod tests {
         let actual = Url::from(DirectUrl::try_from(&expected)?);
         assert_eq!(expected, actual);
 
+        let expected = Url::parse("https://github.com/foo-labs/foo/archive/master.zip")?;
+        let actual = Url::from(DirectUrl::try_from(&expected)?);
+        assert_eq!(expected, actual);
+
+        let expected = Url::parse(
+            "https://github.com/foo-labs/foo/archive/master.zip#subdirectory=packages/bar",
+        )?;
+        let actual = Url::from(DirectUrl::try_from(&expected)?);
+        assert_eq!(expected, actual);
+
         // TODO(charlie): Preserve other fragments.
         let expected =
             Url::parse("git+https://github.com/pallets/flask.git#egg=flask&subdirectory=pkg_dir")?;
@@ -161,7 +161,7 @@ impl Display for FlatIndexLocation {
 /// caches.
 ///
 /// From a pip perspective, this type merges `--index-url`, `--extra-index-url`, and `--find-links`.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct IndexLocations {
     index: Option<IndexUrl>,
     extra_index: Vec<IndexUrl>,
@@ -251,6 +251,17 @@ impl<'a> IndexLocations {
         self.index().into_iter().chain(self.extra_index())
     }
 
+    /// Return an iterator over all [`IndexUrl`] entries from which cached distributions may be
+    /// reused.
+    ///
+    /// Unlike [`IndexLocations::indexes`], this includes the configured indexes even if
+    /// `--no-index` is set, since reading a distribution from the cache doesn't require any index
+    /// lookups.
+    pub fn cached_indexes(&'a self) -> impl Iterator<Item = &'a IndexUrl> + 'a {
+        std::iter::once(self.index.as_ref().unwrap_or(&DEFAULT_INDEX_URL))
+            .chain(self.extra_index.iter())
+    }
+
     /// Return an iterator over the [`FlatIndexLocation`] entries.
     pub fn flat_index(&'a self) -> impl Iterator<Item = &'a FlatIndexLocation> + 'a {
         self.flat_index.iter()
@@ -32,6 +32,7 @@ pub struct InstalledRegistryDist {
 pub struct InstalledDirectUrlDist {
     pub name: PackageName,
     pub version: Version,
+    pub direct_url: Box<pypi_types::DirectUrl>,
     pub url: Url,
     pub editable: bool,
     pub path: PathBuf,
@@ -55,12 +56,13 @@ impl InstalledDist {
 
             let name = PackageName::from_str(name)?;
             let version = Version::from_str(version).map_err(|err| anyhow!(err))?;
-            return if let Some(direct_url) = Self::direct_url(path)? {
+            return if let Some(direct_url) = Self::read_direct_url(path)? {
                 match Url::try_from(&direct_url) {
                     Ok(url) => Ok(Some(Self::Url(InstalledDirectUrlDist {
                         name,
                         version,
                         editable: matches!(&direct_url, pypi_types::DirectUrl::LocalDirectory { dir_info, .. } if dir_info.editable == Some(true)),
+                        direct_url: Box::new(direct_url),
                         url,
                         path: path.to_path_buf(),
                     }))),
@@ -100,8 +102,17 @@ impl InstalledDist {
         }
     }
 
+    /// Return the [`pypi_types::DirectUrl`] from which the distribution was installed, if it was
+    /// installed from a URL (as recorded in `direct_url.json`).
+    pub fn direct_url(&self) -> Option<&pypi_types::DirectUrl> {
+        match self {
+            Self::Registry(_) => None,
+            Self::Url(dist) => Some(&dist.direct_url),
+        }
+    }
+
     /// Read the `direct_url.json` file from a `.dist-info` directory.
-    fn direct_url(path: &Path) -> Result<Option<pypi_types::DirectUrl>> {
+    fn read_direct_url(path: &Path) -> Result<Option<pypi_types::DirectUrl>> {
         let path = path.join("direct_url.json");
         let Ok(file) = fs_err::File::open(path) else {
             return Ok(None);
@@ -122,6 +133,56 @@ impl InstalledDist {
         })
     }
 
+    /// Read the licensing information from the `METADATA` file in a `.dist-info` directory.
+    pub fn license_metadata(&self) -> Result<pypi_types::LicenseMetadata> {
+        let path = self.path().join("METADATA");
+        let contents = fs::read(&path)?;
+        pypi_types::LicenseMetadata::parse(&contents).with_context(|| {
+            format!(
+                "Failed to parse METADATA file at: {}",
+                path.simplified_display()
+            )
+        })
+    }
+
+    /// Read the entries of the `RECORD` file in a `.dist-info` directory, i.e., the files that
+    /// were installed as part of the distribution.
+    ///
+    /// See: <https://packaging.python.org/en/latest/specifications/recording-installed-packages/#the-record-file>
+    pub fn record(&self) -> Result<Vec<pypi_types::RecordEntry>> {
+        let path = self.path().join("RECORD");
+        let file = fs::File::open(&path)?;
+        pypi_types::RecordEntry::read_all(file).with_context(|| {
+            format!(
+                "Failed to parse RECORD file at: {}",
+                path.simplified_display()
+            )
+        })
+    }
+
+    /// Read the entry points declared in the `entry_points.txt` file in a `.dist-info` directory,
+    /// e.g., the distribution's `console_scripts` or plugins.
+    ///
+    /// Returns an empty set of entry points if the distribution doesn't declare any.
+    ///
+    /// See: <https://packaging.python.org/en/latest/specifications/entry-points/>
+    pub fn entry_points(&self) -> Result<pypi_types::EntryPoints> {
+        let path = self.path().join("entry_points.txt");
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(pypi_types::EntryPoints::default());
+            }
+            Err(err) => return Err(err.into()),
+        };
+        pypi_types::EntryPoints::from_str(&contents).with_context(|| {
+            format!(
+                "Failed to parse entry_points.txt file at: {}",
+                path.simplified_display()
+            )
+        })
+    }
+
     /// Return the `INSTALLER` of the distribution.
     pub fn installer(&self) -> Result<Option<String>> {
         let path = self.path().join("INSTALLER");
@@ -132,6 +193,14 @@ impl InstalledDist {
         }
     }
 
+    /// Return true if the distribution was requested directly by the user, as opposed to installed
+    /// as a dependency, as indicated by the presence of a `REQUESTED` file.
+    ///
+    /// See: <https://packaging.python.org/en/latest/specifications/recording-installed-packages/#the-requested-file>
+    pub fn is_requested(&self) -> bool {
+        self.path().join("REQUESTED").is_file()
+    }
+
     /// Return true if the distribution is editable.
     pub fn is_editable(&self) -> bool {
         match self {
@@ -149,6 +218,48 @@ impl InstalledDist {
     }
 }
 
+/// An iterator over the distributions installed in a `site-packages` directory.
+///
+/// Each `.dist-info` directory yields an [`InstalledDist`]; any other entries are skipped.
+#[derive(Debug)]
+pub struct InstalledDists {
+    entries: fs::ReadDir,
+}
+
+impl InstalledDists {
+    /// Read the distributions installed in the given `site-packages` directory.
+    pub fn from_site_packages(site_packages: &Path) -> Result<Self> {
+        Ok(Self {
+            entries: fs::read_dir(site_packages)?,
+        })
+    }
+}
+
+impl Iterator for InstalledDists {
+    type Item = Result<InstalledDist>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for entry in self.entries.by_ref() {
+            let path = match entry.and_then(|entry| Ok((entry.file_type()?, entry.path()))) {
+                Ok((file_type, path)) if file_type.is_dir() => path,
+                Ok(_) => continue,
+                Err(err) => return Some(Err(err.into())),
+            };
+            match InstalledDist::try_from_path(&path) {
+                Ok(Some(dist)) => return Some(Ok(dist)),
+                Ok(None) => continue,
+                Err(err) => {
+                    return Some(Err(err.context(format!(
+                        "Failed to read metadata: from {}",
+                        path.display()
+                    ))))
+                }
+            }
+        }
+        None
+    }
+}
+
 impl Name for InstalledRegistryDist {
     fn name(&self) -> &PackageName {
         &self.name
@@ -190,3 +301,135 @@ impl InstalledMetadata for InstalledDist {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use anyhow::Result;
+    use fs_err as fs;
+
+    use pypi_types::{DirectUrl, RecordEntry};
+
+    use crate::{InstalledDist, InstalledDists, Name};
+
+    /// Create a `.dist-info` directory in the given `site-packages` with the given files.
+    fn dist_info(site_packages: &Path, name: &str, files: &[(&str, &str)]) -> Result<()> {
+        let dist_info = site_packages.join(name);
+        fs::create_dir(&dist_info)?;
+        for (file, contents) in files {
+            fs::write(dist_info.join(file), contents)?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn installed_dists() -> Result<()> {
+        let site_packages = tempfile::tempdir()?;
+        dist_info(site_packages.path(), "anyio-4.3.0.dist-info", &[])?;
+        dist_info(
+            site_packages.path(),
+            "project-0.1.0.dist-info",
+            &[(
+                "direct_url.json",
+                r#"{"url": "file:///home/user/project", "dir_info": {"editable": true}}"#,
+            )],
+        )?;
+        // Non-distribution entries are skipped.
+        fs::create_dir(site_packages.path().join("anyio"))?;
+        fs::create_dir(site_packages.path().join("__pycache__"))?;
+        fs::write(site_packages.path().join("distutils-precedence.pth"), "")?;
+
+        let mut dists = InstalledDists::from_site_packages(site_packages.path())?
+            .collect::<Result<Vec<_>>>()?;
+        dists.sort_unstable_by(|a, b| a.name().cmp(b.name()));
+
+        let [anyio, project] = dists.as_slice() else {
+            panic!("Expected two distributions, found: {dists:?}");
+        };
+        assert_eq!(anyio.name().as_ref(), "anyio");
+        assert_eq!(anyio.version().to_string(), "4.3.0");
+        assert!(matches!(anyio, InstalledDist::Registry(_)));
+        assert_eq!(anyio.direct_url(), None);
+
+        assert_eq!(project.name().as_ref(), "project");
+        assert!(matches!(
+            project,
+            InstalledDist::Url(dist) if dist.editable
+        ));
+        assert!(matches!(
+            project.direct_url(),
+            Some(DirectUrl::LocalDirectory { url, .. }) if url == "file:///home/user/project"
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn installed_dists_invalid() -> Result<()> {
+        let site_packages = tempfile::tempdir()?;
+        dist_info(site_packages.path(), "anyio-4.3.0.dist-info", &[])?;
+        dist_info(site_packages.path(), "anyio-latest.dist-info", &[])?;
+
+        let dists = InstalledDists::from_site_packages(site_packages.path())?.collect::<Vec<_>>();
+        assert_eq!(dists.len(), 2);
+        assert_eq!(dists.iter().filter(|dist| dist.is_err()).count(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn record() -> Result<()> {
+        let site_packages = tempfile::tempdir()?;
+        dist_info(
+            site_packages.path(),
+            "tqdm-4.62.3.dist-info",
+            &[(
+                "RECORD",
+                "tqdm/cli.py,sha256=x_c8nmc4Huc-lKEsAXj78ZiyqSJ9hJ71j7vltY67icw,10509\n\
+                 /tqdm/__init__.py,,\n\
+                 tqdm-4.62.3.dist-info/RECORD,,\n",
+            )],
+        )?;
+
+        let dist =
+            InstalledDist::try_from_path(&site_packages.path().join("tqdm-4.62.3.dist-info"))?
+                .expect("Expected a distribution");
+        assert_eq!(
+            dist.record()?,
+            vec![
+                RecordEntry {
+                    path: "tqdm/cli.py".to_string(),
+                    hash: Some("sha256=x_c8nmc4Huc-lKEsAXj78ZiyqSJ9hJ71j7vltY67icw".to_string()),
+                    size: Some(10509),
+                },
+                // Leading slashes are stripped.
+                RecordEntry {
+                    path: "tqdm/__init__.py".to_string(),
+                    hash: None,
+                    size: None,
+                },
+                RecordEntry {
+                    path: "tqdm-4.62.3.dist-info/RECORD".to_string(),
+                    hash: None,
+                    size: None,
+                },
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn record_missing() -> Result<()> {
+        let site_packages = tempfile::tempdir()?;
+        dist_info(site_packages.path(), "tqdm-4.62.3.dist-info", &[])?;
+
+        let dist =
+            InstalledDist::try_from_path(&site_packages.path().join("tqdm-4.62.3.dist-info"))?
+                .expect("Expected a distribution");
+        assert!(dist.record().is_err());
+
+        Ok(())
+    }
+}
@@ -32,6 +32,10 @@
 //! * [`InstalledDirectUrlDist`]
 //!
 //! Since we read this information from [`direct_url.json`](https://packaging.python.org/en/latest/specifications/direct-url-data-structure/), it doesn't match the information [`Dist`] exactly.
+//!
+//! The distributions installed in a `site-packages` directory can be enumerated with
+//! [`InstalledDists`], and each [`InstalledDist`] exposes its typed `METADATA`, `RECORD`, and
+//! `direct_url.json` contents.
 use std::borrow::Cow;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
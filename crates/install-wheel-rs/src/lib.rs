@@ -13,15 +13,23 @@ use zip::ZipArchive;
 use distribution_filename::WheelFilename;
 use pep440_rs::Version;
 use platform_host::{Arch, Os};
-pub use uninstall::{uninstall_wheel, Uninstall};
+pub use script::ScriptNaming;
+pub use size::{disk_usage, DiskUsage};
+pub use startup::{
+    find_startup_hook, find_startup_hooks, install_startup_hook, set_startup_hook_enabled,
+    StartupHook,
+};
+pub use uninstall::{uninstall_wheel, uninstall_wheel_dry_run, Uninstall};
 use uv_fs::Simplified;
 use uv_normalize::PackageName;
 
 pub mod linker;
-mod record;
 mod script;
+mod size;
+mod startup;
 mod uninstall;
 mod wheel;
+mod zipped;
 
 /// The layout of the target environment into which a wheel can be installed.
 #[derive(Debug, Clone)]
@@ -32,7 +40,10 @@ pub struct Layout {
     pub purelib: PathBuf,
     /// The `platlib` directory, as returned by `sysconfig.get_paths()`.
     pub platlib: PathBuf,
-    /// The `include` directory, as returned by `sysconfig.get_paths()`.
+    /// The `include` directory, as returned by `sysconfig.get_paths()`, into which headers are
+    /// installed (under a subdirectory named after the distribution).
+    ///
+    /// In a virtual environment, this is `{prefix}/include/site/pythonX.Y`, to match pip.
     pub include: PathBuf,
     /// The `scripts` directory, as returned by `sysconfig.get_paths()`.
     pub scripts: PathBuf,
@@ -79,7 +90,7 @@ pub enum Error {
     RecordCsv(#[from] csv::Error),
     #[error("Broken virtualenv: {0}")]
     BrokenVenv(String),
-    #[error("Unable to create Windows launch for {0} (only x64_64 is supported)")]
+    #[error("Unable to create Windows launcher for {0} (only x86_64 and aarch64 are supported)")]
     UnsupportedWindowsArch(&'static str),
     #[error("Unable to create Windows launcher on non-Windows platform")]
     NotWindows,
@@ -105,6 +116,10 @@ pub enum Error {
     MismatchedName(PackageName, PackageName),
     #[error("Wheel version does not match filename: {0} != {1}")]
     MismatchedVersion(Version, Version),
+    #[error("Editable project directory not found at: {}", _0.simplified_display())]
+    MissingEditableProject(PathBuf),
+    #[error("Invalid startup hook: {0}")]
+    InvalidStartupHook(String),
 }
 
 /// Returns `true` if the file is a `METADATA` file in a `dist-info` directory that matches the
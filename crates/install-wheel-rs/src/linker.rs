@@ -1,6 +1,9 @@
 //! Like `wheel.rs`, but for installing wheels that have already been unzipped, rather than
-//! reading from a zip file.
+//! reading from a zip file (with the exception of [`install_wheel_archive`], which extracts an
+//! archive directly into the target environment).
 
+use std::io;
+use std::io::{Read, Seek};
 use std::path::Path;
 use std::str::FromStr;
 
@@ -12,48 +15,45 @@ use tracing::{debug, instrument};
 
 use distribution_filename::WheelFilename;
 use pep440_rs::Version;
-use pypi_types::DirectUrl;
+use pypi_types::{DirInfo, DirectUrl, RecordEntry};
+use url::Url;
+use uv_fs::normalize_path;
 use uv_normalize::PackageName;
+use zip::ZipArchive;
 
 use crate::script::{scripts_from_ini, Script};
 use crate::wheel::{
     extra_dist_info, install_data, parse_metadata, parse_wheel_file, read_record_file,
-    write_script_entrypoints, LibKind,
+    verify_record, write_script_entrypoints, LibKind,
 };
-use crate::{Error, Layout};
+use crate::{zipped, Error, Layout, ScriptNaming};
 
 /// Install the given wheel to the given venv
 ///
 /// The caller must ensure that the wheel is compatible to the environment.
 ///
+/// If `script_naming` is `None`, no launchers are generated for the wheel's entrypoints (e.g.,
+/// when installing for a different platform, for which the launchers would be invalid).
+///
 /// <https://packaging.python.org/en/latest/specifications/binary-distribution-format/#installing-a-wheel-distribution-1-0-py32-none-any-whl>
 ///
 /// Wheel 1.0: <https://www.python.org/dev/peps/pep-0427/>
 #[instrument(skip_all, fields(wheel = % wheel.as_ref().display()))]
+#[allow(clippy::too_many_arguments)]
 pub fn install_wheel(
     layout: &Layout,
     wheel: impl AsRef<Path>,
     filename: &WheelFilename,
     direct_url: Option<&DirectUrl>,
     installer: Option<&str>,
+    requested: bool,
     link_mode: LinkMode,
+    script_naming: Option<&ScriptNaming>,
+    verify_hashes: bool,
 ) -> Result<(), Error> {
     let dist_info_prefix = find_dist_info(&wheel)?;
     let metadata = dist_info_metadata(&dist_info_prefix, &wheel)?;
-    let (name, version) = parse_metadata(&dist_info_prefix, &metadata)?;
-
-    // Validate the wheel name and version.
-    {
-        let name = PackageName::from_str(&name)?;
-        if name != filename.name {
-            return Err(Error::MismatchedName(name, filename.name.clone()));
-        }
-
-        let version = Version::from_str(&version)?;
-        if version != filename.version {
-            return Err(Error::MismatchedVersion(version, filename.version.clone()));
-        }
-    }
+    let name = validate_metadata(&dist_info_prefix, &metadata, filename)?;
 
     // We're going step by step though
     // https://packaging.python.org/en/latest/specifications/binary-distribution-format/#installing-a-wheel-distribution-1-0-py32-none-any-whl
@@ -72,7 +72,14 @@ pub fn install_wheel(
         LibKind::Pure => &layout.purelib,
         LibKind::Plat => &layout.platlib,
     };
-    let num_unpacked = link_mode.link_wheel_files(site_packages, &wheel)?;
+    let zipped = matches!(link_mode, LinkMode::Zip)
+        && matches!(lib_kind, LibKind::Pure)
+        && zipped::is_zippable(wheel.as_ref(), &dist_info_prefix)?;
+    let num_unpacked = if zipped {
+        zipped::zip_wheel_files(site_packages, wheel.as_ref(), &dist_info_prefix)?
+    } else {
+        link_mode.link_wheel_files(site_packages, &wheel)?
+    };
     debug!(name, "Extracted {num_unpacked} files");
 
     // Read the RECORD file.
@@ -83,11 +90,134 @@ pub fn install_wheel(
     )?;
     let mut record = read_record_file(&mut record_file)?;
 
-    debug!(name, "Writing entrypoints");
+    if zipped {
+        zipped::zipped_record(site_packages, &dist_info_prefix, &mut record)?;
+    }
+
+    finish_install(
+        layout,
+        site_packages,
+        wheel.as_ref(),
+        &dist_info_prefix,
+        &name,
+        record,
+        direct_url,
+        installer,
+        requested,
+        script_naming,
+        verify_hashes,
+    )
+}
+
+/// Install a wheel from a zip archive, such as a wheel that was downloaded into memory.
+///
+/// Unlike [`install_wheel`], the wheel doesn't need to be unzipped on disk ahead of time: its
+/// files are extracted directly into the site packages. Any `Read + Seek` source is supported
+/// (e.g., a [`std::io::Cursor`] over an in-memory buffer, or a [`std::fs::File`]).
+///
+/// The caller must ensure that the wheel is compatible to the environment.
+#[instrument(skip_all, fields(wheel = %filename))]
+#[allow(clippy::too_many_arguments)]
+pub fn install_wheel_archive(
+    layout: &Layout,
+    reader: impl Read + Seek,
+    filename: &WheelFilename,
+    direct_url: Option<&DirectUrl>,
+    installer: Option<&str>,
+    requested: bool,
+    script_naming: Option<&ScriptNaming>,
+    verify_hashes: bool,
+) -> Result<(), Error> {
+    let mut archive =
+        ZipArchive::new(reader).map_err(|err| Error::Zip(filename.to_string(), err))?;
+
+    let dist_info_prefix =
+        crate::find_dist_info(filename, archive.file_names().map(|name| (name, name)))?
+            .1
+            .to_string();
+    let metadata = read_archive_file(
+        &mut archive,
+        filename,
+        &format!("{dist_info_prefix}.dist-info/METADATA"),
+    )?;
+    let name = validate_metadata(&dist_info_prefix, &metadata, filename)?;
+
+    let wheel_text = read_archive_file(
+        &mut archive,
+        filename,
+        &format!("{dist_info_prefix}.dist-info/WHEEL"),
+    )?;
+    let wheel_text = String::from_utf8(wheel_text)
+        .map_err(|err| Error::InvalidWheel(format!("WHEEL file is not valid UTF-8: {err}")))?;
+    let lib_kind = parse_wheel_file(&wheel_text)?;
+
+    debug!(name, "Extracting file");
+    let site_packages = match lib_kind {
+        LibKind::Pure => &layout.purelib,
+        LibKind::Plat => &layout.platlib,
+    };
+    let num_unpacked = extract_wheel_files(site_packages, &mut archive, filename)?;
+    debug!(name, "Extracted {num_unpacked} files");
+
+    // Read the RECORD file, which was extracted along with the rest of the `.dist-info`.
+    let mut record_file =
+        File::open(site_packages.join(format!("{dist_info_prefix}.dist-info/RECORD")))?;
+    let record = read_record_file(&mut record_file)?;
+
+    finish_install(
+        layout,
+        site_packages,
+        site_packages,
+        &dist_info_prefix,
+        &name,
+        record,
+        direct_url,
+        installer,
+        requested,
+        script_naming,
+        verify_hashes,
+    )
+}
+
+/// Complete the installation of a wheel whose files have been placed in the site packages, by
+/// writing its entrypoints, installing its data files, and writing its `RECORD`.
+///
+/// The entrypoints are read from the `.dist-info` directory in `wheel`.
+#[allow(clippy::too_many_arguments)]
+fn finish_install(
+    layout: &Layout,
+    site_packages: &Path,
+    wheel: &Path,
+    dist_info_prefix: &str,
+    name: &str,
+    mut record: Vec<RecordEntry>,
+    direct_url: Option<&DirectUrl>,
+    installer: Option<&str>,
+    requested: bool,
+    script_naming: Option<&ScriptNaming>,
+    verify_hashes: bool,
+) -> Result<(), Error> {
     let (console_scripts, gui_scripts) =
-        parse_scripts(&wheel, &dist_info_prefix, None, layout.python_version.1)?;
-    write_script_entrypoints(layout, site_packages, &console_scripts, &mut record, false)?;
-    write_script_entrypoints(layout, site_packages, &gui_scripts, &mut record, true)?;
+        parse_scripts(wheel, dist_info_prefix, None, layout.python_version.1)?;
+    if let Some(script_naming) = script_naming {
+        debug!(name, "Writing entrypoints");
+        write_script_entrypoints(
+            layout,
+            site_packages,
+            &console_scripts,
+            script_naming,
+            &mut record,
+            false,
+        )?;
+        write_script_entrypoints(
+            layout,
+            site_packages,
+            &gui_scripts,
+            script_naming,
+            &mut record,
+            true,
+        )?;
+    }
 
     // 2.a Unpacked archive includes distribution-1.0.dist-info/ and (if there is data) distribution-1.0.data/.
     // 2.b Move each subtree of distribution-1.0.data/ onto its destination path. Each subdirectory of distribution-1.0.data/ is a key into a dict of destination directories, such as distribution-1.0.data/(purelib|platlib|headers|scripts|data). The initially supported paths are taken from distutils.command.install.
@@ -98,7 +228,7 @@ pub fn install_wheel(
             layout,
             site_packages,
             &data_dir,
-            &name,
+            name,
             &console_scripts,
             &gui_scripts,
             &mut record,
@@ -114,13 +244,21 @@ pub fn install_wheel(
     debug!(name, "Writing extra metadata");
     extra_dist_info(
         site_packages,
-        &dist_info_prefix,
-        true,
+        dist_info_prefix,
+        requested,
         direct_url,
         installer,
         &mut record,
     )?;
 
+    // The files are installed unchanged from the wheel, so the wheel's own `RECORD` hashes are
+    // reused rather than recomputed. If requested, verify them against the installed files, once
+    // every file is in its final location.
+    if verify_hashes {
+        debug!(name, "Verifying installed files");
+        verify_record(site_packages, &record)?;
+    }
+
     debug!(name, "Writing record");
     let mut record_writer = csv::WriterBuilder::new()
         .has_headers(false)
@@ -134,6 +272,61 @@ pub fn install_wheel(
     Ok(())
 }
 
+/// Install a pre-built editable wheel (see PEP 660) for the project in the given directory.
+///
+/// The wheel is expected to have been built (and unzipped) by an external build orchestrator,
+/// like Bazel or Pants, such that only the linking, `RECORD`, and `direct_url.json` are handled
+/// here. The `direct_url.json` points to the project directory and marks the distribution as
+/// editable, as if it had been installed with `uv pip install -e`.
+///
+/// Editable wheels are never installed as zip archives, since they typically rely on a `.pth`
+/// file or import hook to expose the project's source tree.
+#[allow(clippy::too_many_arguments)]
+pub fn install_editable_wheel(
+    layout: &Layout,
+    wheel: impl AsRef<Path>,
+    filename: &WheelFilename,
+    project: impl AsRef<Path>,
+    installer: Option<&str>,
+    requested: bool,
+    link_mode: LinkMode,
+    script_naming: Option<&ScriptNaming>,
+    verify_hashes: bool,
+) -> Result<(), Error> {
+    let project = if project.as_ref().is_absolute() {
+        normalize_path(project.as_ref())
+    } else {
+        normalize_path(std::env::current_dir()?.join(project.as_ref()))
+    };
+    if !project.is_dir() {
+        return Err(Error::MissingEditableProject(project));
+    }
+    let url = Url::from_file_path(&project).expect("path is absolute");
+    let direct_url = DirectUrl::LocalDirectory {
+        url: url.to_string(),
+        dir_info: DirInfo {
+            editable: Some(true),
+        },
+    };
+
+    let link_mode = match link_mode {
+        LinkMode::Zip => LinkMode::default(),
+        link_mode => link_mode,
+    };
+
+    install_wheel(
+        layout,
+        wheel,
+        filename,
+        Some(&direct_url),
+        installer,
+        requested,
+        link_mode,
+        script_naming,
+        verify_hashes,
+    )
+}
+
 /// Find the `dist-info` directory in an unzipped wheel.
 ///
 /// See: <https://github.com/PyO3/python-pkginfo-rs>
@@ -175,6 +368,104 @@ fn dist_info_metadata(dist_info_prefix: &str, wheel: impl AsRef<Path>) -> Result
     Ok(fs::read(metadata_file)?)
 }
 
+/// Parse the `dist-info` metadata, and validate that the name and version match the wheel
+/// filename.
+///
+/// Returns the distribution name, as written in the metadata.
+fn validate_metadata(
+    dist_info_prefix: &str,
+    metadata: &[u8],
+    filename: &WheelFilename,
+) -> Result<String, Error> {
+    let (name, version) = parse_metadata(dist_info_prefix, metadata)?;
+
+    {
+        let name = PackageName::from_str(&name)?;
+        if name != filename.name {
+            return Err(Error::MismatchedName(name, filename.name.clone()));
+        }
+
+        let version = Version::from_str(&version)?;
+        if version != filename.version {
+            return Err(Error::MismatchedVersion(version, filename.version.clone()));
+        }
+    }
+
+    Ok(name)
+}
+
+/// Read a single file from a wheel archive into a buffer.
+fn read_archive_file(
+    archive: &mut ZipArchive<impl Read + Seek>,
+    filename: &WheelFilename,
+    path: &str,
+) -> Result<Vec<u8>, Error> {
+    let mut file = archive
+        .by_name(path)
+        .map_err(|err| Error::Zip(filename.to_string(), err))?;
+
+    #[allow(clippy::cast_possible_truncation)]
+    let mut buffer = Vec::with_capacity(file.size() as usize);
+    file.read_to_end(&mut buffer)?;
+
+    Ok(buffer)
+}
+
+/// Extract all files in a wheel archive into site packages.
+///
+/// Entries with paths that would escape the site packages (e.g., absolute paths, or paths
+/// containing `..`) are rejected.
+fn extract_wheel_files(
+    site_packages: &Path,
+    archive: &mut ZipArchive<impl Read + Seek>,
+    filename: &WheelFilename,
+) -> Result<usize, Error> {
+    let mut count = 0usize;
+
+    for index in 0..archive.len() {
+        let mut file = archive
+            .by_index(index)
+            .map_err(|err| Error::Zip(filename.to_string(), err))?;
+
+        let Some(relative) = file.enclosed_name().map(Path::to_path_buf) else {
+            return Err(Error::InvalidWheel(format!(
+                "Unsafe path in archive: {}",
+                file.name()
+            )));
+        };
+        let path = site_packages.join(relative);
+
+        if file.is_dir() {
+            fs::create_dir_all(&path)?;
+            continue;
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut outfile = File::create(&path)?;
+        io::copy(&mut file, &mut outfile)?;
+
+        // Preserve the executable bit, as in `uv_extract::unzip`.
+        #[cfg(unix)]
+        {
+            use std::fs::Permissions;
+            use std::os::unix::fs::PermissionsExt;
+
+            if let Some(mode) = file.unix_mode() {
+                if mode & 0o111 != 0 {
+                    let permissions = fs::metadata(&path)?.permissions();
+                    fs::set_permissions(&path, Permissions::from_mode(permissions.mode() | 0o111))?;
+                }
+            }
+        }
+
+        count += 1;
+    }
+
+    Ok(count)
+}
+
 /// Parses the `entry_points.txt` entry in the wheel for console scripts
 ///
 /// Returns (`script_name`, module, function)
@@ -198,15 +489,34 @@ fn parse_scripts(
     scripts_from_ini(extras, python_minor, ini)
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
 pub enum LinkMode {
     /// Clone (i.e., copy-on-write) packages from the wheel into the site packages.
+    ///
+    /// If the filesystem doesn't support cloning, falls back to hard linking, and then to copying.
     Clone,
     /// Copy packages from the wheel into the site packages.
     Copy,
     /// Hard link packages from the wheel into the site packages.
+    ///
+    /// If the filesystem doesn't support hard links (or the cache and the site packages are on
+    /// different filesystems), falls back to copying.
     Hardlink,
+    /// Symbolically link packages from the wheel into the site packages.
+    ///
+    /// WARNING: The use of symlinks is discouraged, as they create tight coupling between the cache
+    /// and the target environment. For example, clearing the cache (`uv clean`) will break all
+    /// installed packages by way of removing the underlying source files. Use symlinks with
+    /// caution.
+    Symlink,
+    /// Install small, pure-Python packages as zip archives that are added to `sys.path` via a
+    /// `.pth` file, rather than extracting them into the site packages. Packages that can't be
+    /// imported from a zip archive fall back to the default link mode.
+    ///
+    /// WARNING: This mode is experimental. Packages that read their own files from disk (e.g., via
+    /// `__file__`) may not work when installed as zip archives.
+    Zip,
 }
 
 impl Default for LinkMode {
@@ -231,10 +541,108 @@ impl LinkMode {
             Self::Clone => clone_wheel_files(site_packages, wheel),
             Self::Copy => copy_wheel_files(site_packages, wheel),
             Self::Hardlink => hardlink_wheel_files(site_packages, wheel),
+            Self::Symlink => symlink_wheel_files(site_packages, wheel),
+            Self::Zip => Self::default().link_wheel_files(site_packages, wheel),
+        }
+    }
+
+    /// Determine the link mode that the filesystems backing `wheel` and `site_packages` support,
+    /// falling back from cloning to hard linking to copying.
+    ///
+    /// The probe links a single file from the wheel into a temporary directory within
+    /// `site_packages`, so it's intended to be run once per install, with the resolved mode used
+    /// for every wheel in that install.
+    #[must_use]
+    pub fn resolve(self, site_packages: impl AsRef<Path>, wheel: impl AsRef<Path>) -> Self {
+        let site_packages = site_packages.as_ref();
+        if !matches!(self, Self::Clone | Self::Hardlink) {
+            return self;
+        }
+
+        let mode = self.probe(site_packages, wheel.as_ref());
+        if mode == self {
+            debug!(
+                "Using link mode `{mode:?}` for: {}",
+                site_packages.display()
+            );
+        } else {
+            debug!(
+                "Link mode `{self:?}` is not supported for {}; using `{mode:?}` instead",
+                site_packages.display()
+            );
+        }
+        mode
+    }
+
+    /// Test whether this link mode is supported, by linking a single file from `wheel` into a
+    /// temporary directory within `site_packages`.
+    fn probe(self, site_packages: &Path, wheel: &Path) -> Self {
+        // If there's nothing to probe with, assume the requested mode works; any failures will
+        // still fall back to copying on a per-wheel basis.
+        let Some(file) = walkdir::WalkDir::new(wheel)
+            .into_iter()
+            .filter_map(Result::ok)
+            .find(|entry| entry.file_type().is_file())
+        else {
+            return self;
+        };
+        let Ok(tempdir) = tempdir_in(site_packages) else {
+            return self;
+        };
+        let target = tempdir.path().join("probe");
+
+        if self == Self::Clone {
+            match reflink::reflink(file.path(), &target) {
+                Ok(()) => return Self::Clone,
+                Err(err) if is_cross_device(&err) => {
+                    debug!(
+                        "Cache and {} are on different filesystems; falling back to copying",
+                        site_packages.display()
+                    );
+                    return Self::Copy;
+                }
+                Err(err) => {
+                    debug!(
+                        "Failed to clone into {} ({err}); falling back to hard linking",
+                        site_packages.display()
+                    );
+                    // Remove any partial clone, so that the hard link can take its place.
+                    let _ = fs::remove_file(&target);
+                }
+            }
+        }
+
+        match fs::hard_link(file.path(), &target) {
+            Ok(()) => Self::Hardlink,
+            Err(err) if is_cross_device(&err) => {
+                debug!(
+                    "Cache and {} are on different filesystems; falling back to copying",
+                    site_packages.display()
+                );
+                Self::Copy
+            }
+            Err(err) => {
+                debug!(
+                    "Failed to hard link into {} ({err}); falling back to copying",
+                    site_packages.display()
+                );
+                Self::Copy
+            }
         }
     }
 }
 
+/// Returns `true` if the error indicates that the source and destination of a link are on
+/// different filesystems.
+fn is_cross_device(err: &io::Error) -> bool {
+    // `EXDEV` on Unix (18 on Linux, macOS, and the BSDs), and `ERROR_NOT_SAME_DEVICE` on Windows.
+    if cfg!(windows) {
+        err.raw_os_error() == Some(17)
+    } else {
+        err.raw_os_error() == Some(18)
+    }
+}
+
 /// Extract a wheel by cloning all of its files into site packages. The files will be cloned
 /// via copy-on-write, which is similar to a hard link, but allows the files to be modified
 /// independently (that is, the file is copied upon modification).
@@ -435,10 +843,20 @@ fn hardlink_wheel_files(
                         if fs::hard_link(path, &tempfile).is_ok() {
                             fs_err::rename(&tempfile, &out_path)?;
                         } else {
+                            debug!(
+                                "Failed to hard link {} to temporary location {} - attempting to copy files as a fallback",
+                                path.display(),
+                                tempfile.display()
+                            );
                             fs::copy(path, &out_path)?;
                             attempt = Attempt::UseCopyFallback;
                         }
                     } else {
+                        debug!(
+                            "Failed to hard link {} to {} ({err}) - attempting to copy files as a fallback",
+                            path.display(),
+                            out_path.display()
+                        );
                         fs::copy(path, &out_path)?;
                         attempt = Attempt::UseCopyFallback;
                     }
@@ -472,3 +890,659 @@ fn hardlink_wheel_files(
 
     Ok(count)
 }
+
+/// Extract a wheel by symbolically linking all of its files into site packages.
+///
+/// Falls back to copying if symlinks aren't supported for this installation (e.g., on Windows,
+/// without the necessary privileges).
+fn symlink_wheel_files(
+    site_packages: impl AsRef<Path>,
+    wheel: impl AsRef<Path>,
+) -> Result<usize, Error> {
+    let mut attempt = Attempt::default();
+    let mut count = 0usize;
+
+    // Walk over the directory.
+    for entry in walkdir::WalkDir::new(&wheel) {
+        let entry = entry?;
+        let path = entry.path();
+
+        let relative = path.strip_prefix(&wheel).unwrap();
+        let out_path = site_packages.as_ref().join(relative);
+
+        if entry.file_type().is_dir() {
+            fs::create_dir_all(&out_path)?;
+            continue;
+        }
+
+        // The `RECORD` file is modified during installation, so we copy it instead of symlinking.
+        if path.ends_with("RECORD") {
+            fs::copy(path, &out_path)?;
+            count += 1;
+            continue;
+        }
+
+        // Fallback to copying if symlinks aren't supported for this installation.
+        match attempt {
+            Attempt::Initial => {
+                attempt = Attempt::Subsequent;
+                if let Err(err) = create_symlink(path, &out_path) {
+                    // If the file already exists, remove it and try again.
+                    if err.kind() == std::io::ErrorKind::AlreadyExists {
+                        debug!(
+                            "File already exists (initial attempt), overwriting: {}",
+                            out_path.display()
+                        );
+                        // Removing and recreating would lead to race conditions.
+                        let tempdir = tempdir_in(&site_packages)?;
+                        let tempfile = tempdir.path().join(entry.file_name());
+                        if create_symlink(path, &tempfile).is_ok() {
+                            fs::rename(&tempfile, &out_path)?;
+                        } else {
+                            debug!(
+                                "Failed to symlink {} to temporary location {} - attempting to copy files as a fallback",
+                                path.display(),
+                                tempfile.display()
+                            );
+                            fs::copy(path, &out_path)?;
+                            attempt = Attempt::UseCopyFallback;
+                        }
+                    } else {
+                        debug!(
+                            "Failed to symlink {} to {} ({err}) - attempting to copy files as a fallback",
+                            path.display(),
+                            out_path.display()
+                        );
+                        fs::copy(path, &out_path)?;
+                        attempt = Attempt::UseCopyFallback;
+                    }
+                }
+            }
+            Attempt::Subsequent => {
+                if let Err(err) = create_symlink(path, &out_path) {
+                    // If the file already exists, remove it and try again.
+                    if err.kind() == std::io::ErrorKind::AlreadyExists {
+                        debug!(
+                            "File already exists (subsequent attempt), overwriting: {}",
+                            out_path.display()
+                        );
+                        // Removing and recreating would lead to race conditions.
+                        let tempdir = tempdir_in(&site_packages)?;
+                        let tempfile = tempdir.path().join(entry.file_name());
+                        create_symlink(path, &tempfile)?;
+                        fs::rename(&tempfile, &out_path)?;
+                    } else {
+                        return Err(err.into());
+                    }
+                }
+            }
+            Attempt::UseCopyFallback => {
+                fs::copy(path, &out_path)?;
+            }
+        }
+
+        count += 1;
+    }
+
+    Ok(count)
+}
+
+/// Create a symbolic link to the file at `src`, located at `dst`.
+#[cfg(unix)]
+fn create_symlink(src: &Path, dst: &Path) -> std::io::Result<()> {
+    fs_err::os::unix::fs::symlink(src, dst)
+}
+
+/// Create a symbolic link to the file at `src`, located at `dst`.
+#[cfg(windows)]
+fn create_symlink(src: &Path, dst: &Path) -> std::io::Result<()> {
+    fs_err::os::windows::fs::symlink_file(src, dst)
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::{Cursor, Write};
+    use std::str::FromStr;
+
+    use fs_err as fs;
+    use tempfile::TempDir;
+    use zip::write::FileOptions;
+    use zip::ZipWriter;
+
+    use distribution_filename::WheelFilename;
+    use pypi_types::{DirInfo, DirectUrl};
+
+    use super::{
+        install_editable_wheel, install_wheel, install_wheel_archive, is_cross_device, LinkMode,
+    };
+    use crate::{Error, Layout, ScriptNaming};
+
+    #[test]
+    fn editable_wheel() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+
+        // An unzipped editable wheel, as built by the project's build backend.
+        let wheel = root.join("wheel");
+        fs::create_dir_all(wheel.join("foo-1.0.dist-info")).unwrap();
+        fs::write(
+            wheel.join("foo-1.0.dist-info/METADATA"),
+            "Metadata-Version: 2.1\nName: foo\nVersion: 1.0\n",
+        )
+        .unwrap();
+        fs::write(
+            wheel.join("foo-1.0.dist-info/WHEEL"),
+            "Wheel-Version: 1.0\nRoot-Is-Purelib: true\nTag: py3-none-any\n",
+        )
+        .unwrap();
+        fs::write(wheel.join("__editable__.foo-1.0.pth"), "/src/foo\n").unwrap();
+        fs::write(
+            wheel.join("foo-1.0.dist-info/RECORD"),
+            "__editable__.foo-1.0.pth,,\nfoo-1.0.dist-info/METADATA,,\nfoo-1.0.dist-info/WHEEL,,\nfoo-1.0.dist-info/RECORD,,\n",
+        )
+        .unwrap();
+
+        let project = root.join("project");
+        fs::create_dir_all(&project).unwrap();
+
+        let site_packages = root.join("site-packages");
+        fs::create_dir_all(&site_packages).unwrap();
+        let layout = Layout {
+            sys_executable: root.join("bin/python"),
+            purelib: site_packages.clone(),
+            platlib: site_packages.clone(),
+            include: root.join("include"),
+            scripts: root.join("bin"),
+            data: root.to_path_buf(),
+            python_version: (3, 12),
+            os_name: "posix".to_string(),
+        };
+        let filename = WheelFilename::from_str("foo-1.0-py3-none-any.whl").unwrap();
+
+        install_editable_wheel(
+            &layout,
+            &wheel,
+            &filename,
+            &project,
+            Some("bazel"),
+            true,
+            LinkMode::Zip,
+            Some(&ScriptNaming::default()),
+            false,
+        )
+        .unwrap();
+
+        // The wheel is linked (rather than zipped), and marked as an editable install of the
+        // project.
+        assert!(site_packages.join("__editable__.foo-1.0.pth").is_file());
+        let direct_url: DirectUrl = serde_json::from_str(
+            &fs::read_to_string(site_packages.join("foo-1.0.dist-info/direct_url.json")).unwrap(),
+        )
+        .unwrap();
+        let DirectUrl::LocalDirectory { url, dir_info } = direct_url else {
+            panic!("Expected a local directory: {direct_url:?}");
+        };
+        assert_eq!(url, url::Url::from_file_path(&project).unwrap().to_string());
+        assert_eq!(
+            dir_info,
+            DirInfo {
+                editable: Some(true)
+            }
+        );
+        assert_eq!(
+            fs::read_to_string(site_packages.join("foo-1.0.dist-info/INSTALLER")).unwrap(),
+            "bazel"
+        );
+        let record = fs::read_to_string(site_packages.join("foo-1.0.dist-info/RECORD")).unwrap();
+        assert!(record.contains("foo-1.0.dist-info/direct_url.json"));
+
+        // The project directory must exist.
+        let err = install_editable_wheel(
+            &layout,
+            &wheel,
+            &filename,
+            root.join("missing"),
+            None,
+            false,
+            LinkMode::Copy,
+            Some(&ScriptNaming::default()),
+            false,
+        )
+        .unwrap_err();
+        assert!(matches!(err, Error::MissingEditableProject(_)));
+    }
+
+    #[test]
+    fn link_mode_fallback() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel = temp_dir.path().join("wheel");
+        fs::create_dir_all(wheel.join("foo")).unwrap();
+        fs::write(wheel.join("foo/__init__.py"), "").unwrap();
+        let site_packages = temp_dir.path().join("site-packages");
+        fs::create_dir_all(&site_packages).unwrap();
+
+        // Within a single filesystem, hard links are always supported.
+        assert_eq!(
+            LinkMode::Hardlink.resolve(&site_packages, &wheel),
+            LinkMode::Hardlink
+        );
+
+        // Cloning is filesystem-dependent, but should never fall back past hard linking.
+        assert_ne!(
+            LinkMode::Clone.resolve(&site_packages, &wheel),
+            LinkMode::Copy
+        );
+
+        // Copying is always supported, and isn't probed.
+        assert_eq!(
+            LinkMode::Copy.resolve(&site_packages, &wheel),
+            LinkMode::Copy
+        );
+
+        // The probe doesn't leave anything behind.
+        assert_eq!(fs::read_dir(&site_packages).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn cross_device() {
+        let code = if cfg!(windows) { 17 } else { 18 };
+        assert!(is_cross_device(&std::io::Error::from_raw_os_error(code)));
+        assert!(!is_cross_device(&std::io::Error::from(
+            std::io::ErrorKind::PermissionDenied
+        )));
+    }
+
+    /// Install a wheel with `headers` and `data` trees, which are moved out of `site-packages`
+    /// into the corresponding scheme directories.
+    #[test]
+    fn data_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+
+        // An unzipped wheel, with a header and a data file.
+        let wheel = root.join("wheel");
+        fs::create_dir_all(wheel.join("foo-1.0.dist-info")).unwrap();
+        fs::create_dir_all(wheel.join("foo-1.0.data/headers")).unwrap();
+        fs::create_dir_all(wheel.join("foo-1.0.data/data/share/foo")).unwrap();
+        fs::write(
+            wheel.join("foo-1.0.dist-info/METADATA"),
+            "Metadata-Version: 2.1\nName: foo\nVersion: 1.0\n",
+        )
+        .unwrap();
+        fs::write(
+            wheel.join("foo-1.0.dist-info/WHEEL"),
+            "Wheel-Version: 1.0\nRoot-Is-Purelib: false\nTag: cp312-cp312-linux_x86_64\n",
+        )
+        .unwrap();
+        fs::write(wheel.join("foo-1.0.data/headers/foo.h"), "int foo(void);\n").unwrap();
+        fs::write(wheel.join("foo-1.0.data/data/share/foo/foo.txt"), "foo\n").unwrap();
+        fs::write(
+            wheel.join("foo-1.0.dist-info/RECORD"),
+            "foo-1.0.data/headers/foo.h,,\nfoo-1.0.data/data/share/foo/foo.txt,,\nfoo-1.0.dist-info/METADATA,,\nfoo-1.0.dist-info/WHEEL,,\nfoo-1.0.dist-info/RECORD,,\n",
+        )
+        .unwrap();
+
+        let site_packages = root.join("lib/python3.12/site-packages");
+        fs::create_dir_all(&site_packages).unwrap();
+        let layout = Layout {
+            sys_executable: root.join("bin/python"),
+            purelib: site_packages.clone(),
+            platlib: site_packages.clone(),
+            include: root.join("include/site/python3.12"),
+            scripts: root.join("bin"),
+            data: root.to_path_buf(),
+            python_version: (3, 12),
+            os_name: "posix".to_string(),
+        };
+        let filename = WheelFilename::from_str("foo-1.0-cp312-cp312-linux_x86_64.whl").unwrap();
+
+        install_wheel(
+            &layout,
+            &wheel,
+            &filename,
+            None,
+            None,
+            false,
+            LinkMode::Copy,
+            Some(&ScriptNaming::default()),
+            false,
+        )
+        .unwrap();
+
+        // Headers are installed into a directory named after the distribution, and data files
+        // are installed relative to the environment root.
+        assert!(root.join("include/site/python3.12/foo/foo.h").is_file());
+        assert!(root.join("share/foo/foo.txt").is_file());
+        assert!(!site_packages.join("foo-1.0.data").exists());
+
+        // The `RECORD` tracks the files at their installed locations.
+        let record = fs::read_to_string(site_packages.join("foo-1.0.dist-info/RECORD")).unwrap();
+        assert!(record.contains("../../../include/site/python3.12/foo/foo.h"));
+        assert!(record.contains("../../../share/foo/foo.txt"));
+        assert!(!record.contains("foo-1.0.data"));
+    }
+
+    /// With `verify_hashes`, reject a wheel whose installed files don't match its `RECORD`.
+    #[test]
+    fn verify_hashes() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+
+        // An unzipped wheel, whose `RECORD` lists a file that's missing, and a file that's
+        // truncated.
+        let wheel = root.join("wheel");
+        fs::create_dir_all(wheel.join("foo-1.0.dist-info")).unwrap();
+        fs::create_dir_all(wheel.join("foo")).unwrap();
+        fs::write(
+            wheel.join("foo-1.0.dist-info/METADATA"),
+            "Metadata-Version: 2.1\nName: foo\nVersion: 1.0\n",
+        )
+        .unwrap();
+        fs::write(
+            wheel.join("foo-1.0.dist-info/WHEEL"),
+            "Wheel-Version: 1.0\nRoot-Is-Purelib: true\nTag: py3-none-any\n",
+        )
+        .unwrap();
+        fs::write(wheel.join("foo/__init__.py"), "foo").unwrap();
+        fs::write(
+            wheel.join("foo-1.0.dist-info/RECORD"),
+            "foo/__init__.py,,10\nfoo-1.0.dist-info/METADATA,,\nfoo-1.0.dist-info/WHEEL,,\nfoo-1.0.dist-info/RECORD,,\n",
+        )
+        .unwrap();
+
+        let site_packages = root.join("site-packages");
+        fs::create_dir_all(&site_packages).unwrap();
+        let layout = Layout {
+            sys_executable: root.join("bin/python"),
+            purelib: site_packages.clone(),
+            platlib: site_packages.clone(),
+            include: root.join("include"),
+            scripts: root.join("bin"),
+            data: root.to_path_buf(),
+            python_version: (3, 12),
+            os_name: "posix".to_string(),
+        };
+        let filename = WheelFilename::from_str("foo-1.0-py3-none-any.whl").unwrap();
+
+        let err = install_wheel(
+            &layout,
+            &wheel,
+            &filename,
+            None,
+            None,
+            false,
+            LinkMode::Copy,
+            Some(&ScriptNaming::default()),
+            true,
+        )
+        .unwrap_err();
+        let Error::RecordFile(message) = err else {
+            panic!("Expected a RECORD error: {err:?}");
+        };
+        assert_eq!(
+            message,
+            "Size mismatch for `foo/__init__.py`: expected 10, found 3"
+        );
+    }
+
+    /// Install a wheel from an in-memory archive, without unzipping it to disk first.
+    #[test]
+    fn archive() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+
+        // A wheel, built in memory.
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        for (path, contents) in [
+            ("foo/__init__.py", "print('foo')\n"),
+            (
+                "foo-1.0.dist-info/METADATA",
+                "Metadata-Version: 2.1\nName: foo\nVersion: 1.0\n",
+            ),
+            (
+                "foo-1.0.dist-info/WHEEL",
+                "Wheel-Version: 1.0\nRoot-Is-Purelib: true\nTag: py3-none-any\n",
+            ),
+            (
+                "foo-1.0.dist-info/RECORD",
+                "foo/__init__.py,,\nfoo-1.0.dist-info/METADATA,,\nfoo-1.0.dist-info/WHEEL,,\nfoo-1.0.dist-info/RECORD,,\n",
+            ),
+        ] {
+            writer.start_file(path, FileOptions::default()).unwrap();
+            writer.write_all(contents.as_bytes()).unwrap();
+        }
+        let archive = writer.finish().unwrap();
+
+        let site_packages = root.join("site-packages");
+        fs::create_dir_all(&site_packages).unwrap();
+        let layout = Layout {
+            sys_executable: root.join("bin/python"),
+            purelib: site_packages.clone(),
+            platlib: site_packages.clone(),
+            include: root.join("include"),
+            scripts: root.join("bin"),
+            data: root.to_path_buf(),
+            python_version: (3, 12),
+            os_name: "posix".to_string(),
+        };
+        let filename = WheelFilename::from_str("foo-1.0-py3-none-any.whl").unwrap();
+
+        install_wheel_archive(
+            &layout,
+            Cursor::new(archive.into_inner()),
+            &filename,
+            None,
+            Some("embedder"),
+            true,
+            Some(&ScriptNaming::default()),
+            true,
+        )
+        .unwrap();
+
+        assert_eq!(
+            fs::read_to_string(site_packages.join("foo/__init__.py")).unwrap(),
+            "print('foo')\n"
+        );
+        assert_eq!(
+            fs::read_to_string(site_packages.join("foo-1.0.dist-info/INSTALLER")).unwrap(),
+            "embedder"
+        );
+        let record = fs::read_to_string(site_packages.join("foo-1.0.dist-info/RECORD")).unwrap();
+        assert!(record.contains("foo-1.0.dist-info/INSTALLER"));
+        assert!(record.contains("foo-1.0.dist-info/REQUESTED"));
+    }
+
+    /// Build an in-memory wheel for `foo` 1.0 with the given additional files and Unix modes.
+    fn foo_archive(files: &[(&str, &str, u32)]) -> Vec<u8> {
+        let mut record = files
+            .iter()
+            .map(|(path, ..)| format!("{path},,\n"))
+            .collect::<String>();
+        record.push_str(
+            "foo-1.0.dist-info/METADATA,,\nfoo-1.0.dist-info/WHEEL,,\nfoo-1.0.dist-info/RECORD,,\n",
+        );
+
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        for (path, contents, mode) in files.iter().copied().chain([
+            (
+                "foo-1.0.dist-info/METADATA",
+                "Metadata-Version: 2.1\nName: foo\nVersion: 1.0\n",
+                0o644,
+            ),
+            (
+                "foo-1.0.dist-info/WHEEL",
+                "Wheel-Version: 1.0\nRoot-Is-Purelib: true\nTag: py3-none-any\n",
+                0o644,
+            ),
+            ("foo-1.0.dist-info/RECORD", record.as_str(), 0o644),
+        ]) {
+            writer
+                .start_file(path, FileOptions::default().unix_permissions(mode))
+                .unwrap();
+            writer.write_all(contents.as_bytes()).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    /// A virtual environment layout rooted at the given directory.
+    fn layout(root: &std::path::Path) -> Layout {
+        let site_packages = root.join("site-packages");
+        fs::create_dir_all(&site_packages).unwrap();
+        Layout {
+            sys_executable: root.join("bin/python"),
+            purelib: site_packages.clone(),
+            platlib: site_packages,
+            include: root.join("include"),
+            scripts: root.join("bin"),
+            data: root.to_path_buf(),
+            python_version: (3, 12),
+            os_name: "posix".to_string(),
+        }
+    }
+
+    /// Reject archive entries that would be extracted outside of `site-packages`.
+    #[test]
+    fn archive_unsafe_path() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().join("venv");
+        let layout = layout(&root);
+        let filename = WheelFilename::from_str("foo-1.0-py3-none-any.whl").unwrap();
+
+        let archive = foo_archive(&[("../evil.py", "print('evil')\n", 0o644)]);
+        let err = install_wheel_archive(
+            &layout,
+            Cursor::new(archive),
+            &filename,
+            None,
+            None,
+            false,
+            Some(&ScriptNaming::default()),
+            false,
+        )
+        .unwrap_err();
+        let Error::InvalidWheel(message) = err else {
+            panic!("Expected an invalid wheel error: {err:?}");
+        };
+        assert_eq!(message, "Unsafe path in archive: ../evil.py");
+        assert!(!root.join("evil.py").exists());
+    }
+
+    /// Preserve the executable bit of files extracted from an archive.
+    #[test]
+    #[cfg(unix)]
+    fn archive_executable() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let layout = layout(temp_dir.path());
+        let filename = WheelFilename::from_str("foo-1.0-py3-none-any.whl").unwrap();
+
+        let archive = foo_archive(&[
+            ("foo/__init__.py", "", 0o644),
+            ("foo/run.sh", "#!/bin/sh\n", 0o755),
+        ]);
+        install_wheel_archive(
+            &layout,
+            Cursor::new(archive),
+            &filename,
+            None,
+            None,
+            false,
+            Some(&ScriptNaming::default()),
+            false,
+        )
+        .unwrap();
+
+        let mode = |path: &str| {
+            fs::metadata(layout.purelib.join(path))
+                .unwrap()
+                .permissions()
+                .mode()
+        };
+        assert_ne!(mode("foo/run.sh") & 0o111, 0);
+        assert_eq!(mode("foo/__init__.py") & 0o111, 0);
+    }
+
+    /// Installing from an archive produces the same `RECORD` as installing the unzipped wheel.
+    #[test]
+    fn archive_record() {
+        let temp_dir = TempDir::new().unwrap();
+        let filename = WheelFilename::from_str("foo-1.0-py3-none-any.whl").unwrap();
+        let archive = foo_archive(&[
+            ("foo/__init__.py", "print('foo')\n", 0o644),
+            ("foo/bar.py", "print('bar')\n", 0o644),
+        ]);
+
+        // Install from the archive.
+        let archived = layout(&temp_dir.path().join("archived"));
+        install_wheel_archive(
+            &archived,
+            Cursor::new(archive.clone()),
+            &filename,
+            None,
+            Some("uv"),
+            true,
+            Some(&ScriptNaming::default()),
+            false,
+        )
+        .unwrap();
+
+        // Install from the unzipped wheel.
+        let wheel = temp_dir.path().join("wheel");
+        zip::ZipArchive::new(Cursor::new(archive))
+            .unwrap()
+            .extract(&wheel)
+            .unwrap();
+        let unzipped = layout(&temp_dir.path().join("unzipped"));
+        install_wheel(
+            &unzipped,
+            &wheel,
+            &filename,
+            None,
+            Some("uv"),
+            true,
+            LinkMode::Copy,
+            Some(&ScriptNaming::default()),
+            false,
+        )
+        .unwrap();
+
+        let record = |layout: &Layout| {
+            fs::read_to_string(layout.purelib.join("foo-1.0.dist-info/RECORD")).unwrap()
+        };
+        assert_eq!(record(&archived), record(&unzipped));
+    }
+
+    /// Install headers to the environment's include directory, and skip any stray files in the
+    /// data directory rather than rejecting the wheel.
+    #[test]
+    fn data_headers() {
+        let temp_dir = TempDir::new().unwrap();
+        let layout = layout(temp_dir.path());
+        let filename = WheelFilename::from_str("foo-1.0-py3-none-any.whl").unwrap();
+
+        let archive = foo_archive(&[
+            ("foo/__init__.py", "", 0o644),
+            ("foo-1.0.data/headers/foo.h", "int foo(void);\n", 0o644),
+            ("foo-1.0.data/README", "Not part of the scheme.\n", 0o644),
+        ]);
+        install_wheel_archive(
+            &layout,
+            Cursor::new(archive),
+            &filename,
+            None,
+            None,
+            false,
+            Some(&ScriptNaming::default()),
+            false,
+        )
+        .unwrap();
+
+        assert_eq!(
+            fs::read_to_string(layout.include.join("foo").join("foo.h")).unwrap(),
+            "int foo(void);\n"
+        );
+        assert!(!layout.purelib.join("foo-1.0.data").exists());
+        assert!(!layout.data.join("README").exists());
+    }
+}
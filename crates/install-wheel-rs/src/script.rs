@@ -1,10 +1,8 @@
-use configparser::ini::Ini;
-use once_cell::sync::Lazy;
-use regex::Regex;
-use rustc_hash::FxHashSet;
 use serde::Serialize;
 
-use crate::{wheel, Error};
+use pypi_types::{EntryPoint, EntryPoints};
+
+use crate::Error;
 
 /// A script defining the name of the runnable entrypoint and the module and function that should be
 /// run.
@@ -21,39 +19,44 @@ impl Script {
     /// <https://packaging.python.org/en/latest/specifications/entry-points/>
     ///
     /// Extras are supposed to be ignored, which happens if you pass None for extras
+    #[cfg(test)]
     pub(crate) fn from_value(
         script_name: &str,
         value: &str,
         extras: Option<&[String]>,
     ) -> Result<Option<Self>, Error> {
-        // "Within a value, readers must accept and ignore spaces (including multiple consecutive spaces) before or after the colon,
-        //  between the object reference and the left square bracket, between the extra names and the square brackets and colons delimiting them,
-        //  and after the right square bracket."
-        // – https://packaging.python.org/en/latest/specifications/entry-points/#file-format
-        static SCRIPT_REGEX: Lazy<Regex> = Lazy::new(|| {
-            Regex::new(r"^(?P<module>[\w\d_\-.]+)\s*:\s*(?P<function>[\w\d_\-.]+)(?:\s*\[\s*(?P<extras>(?:[^,]+,?\s*)+)\])?\s*$").unwrap()
-        });
+        let entry_point = EntryPoint::from_value(script_name, value)
+            .map_err(|err| Error::InvalidWheel(err.to_string()))?;
+        Self::from_entry_point(&entry_point, extras)
+    }
 
-        let captures = SCRIPT_REGEX
-            .captures(value)
-            .ok_or_else(|| Error::InvalidWheel(format!("invalid console script: '{value}'")))?;
-        if let Some(script_extras) = captures.name("extras") {
-            if let Some(extras) = extras {
-                let script_extras = script_extras
-                    .as_str()
-                    .split(',')
-                    .map(|extra| extra.trim().to_string())
-                    .collect::<FxHashSet<String>>();
-                if !script_extras.is_subset(&extras.iter().cloned().collect()) {
-                    return Ok(None);
-                }
+    /// Create a script from an entry point, like `foomod:main_bar [bar,baz]`.
+    ///
+    /// Returns `None` if the entry point requires extras that aren't enabled. Extras are supposed
+    /// to be ignored, which happens if you pass None for extras.
+    pub(crate) fn from_entry_point(
+        entry_point: &EntryPoint,
+        extras: Option<&[String]>,
+    ) -> Result<Option<Self>, Error> {
+        let Some(function) = entry_point.object.as_ref() else {
+            return Err(Error::InvalidWheel(format!(
+                "invalid console script: `{}` doesn't refer to a function",
+                entry_point.name
+            )));
+        };
+        if let Some(extras) = extras {
+            if !entry_point
+                .extras
+                .iter()
+                .all(|extra| extras.contains(extra))
+            {
+                return Ok(None);
             }
         }
-
         Ok(Some(Self {
-            name: script_name.to_string(),
-            module: captures.name("module").unwrap().as_str().to_string(),
-            function: captures.name("function").unwrap().as_str().to_string(),
+            name: entry_point.name.clone(),
+            module: entry_point.module.clone(),
+            function: function.clone(),
         }))
     }
 
@@ -64,26 +67,42 @@ impl Script {
     }
 }
 
+/// A prefix and suffix to apply to the names of the entrypoint scripts generated for a wheel, e.g.,
+/// to install `black` as `proj-black` or `black-3.12`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScriptNaming {
+    pub prefix: Option<String>,
+    pub suffix: Option<String>,
+}
+
+impl ScriptNaming {
+    /// Apply the prefix and suffix to the given script name.
+    pub(crate) fn apply(&self, name: &str) -> String {
+        format!(
+            "{}{name}{}",
+            self.prefix.as_deref().unwrap_or_default(),
+            self.suffix.as_deref().unwrap_or_default()
+        )
+    }
+}
+
 pub(crate) fn scripts_from_ini(
     extras: Option<&[String]>,
     python_minor: u8,
     ini: String,
 ) -> Result<(Vec<Script>, Vec<Script>), Error> {
-    let entry_points_mapping = Ini::new_cs()
-        .read(ini)
-        .map_err(|err| Error::InvalidWheel(format!("entry_points.txt is invalid: {err}")))?;
-
-    // TODO: handle extras
-    let mut console_scripts = match entry_points_mapping.get("console_scripts") {
-        Some(console_scripts) => {
-            wheel::read_scripts_from_section(console_scripts, "console_scripts", extras)?
+    let entry_points = EntryPoints::from_groups(&ini, &["console_scripts", "gui_scripts"])
+        .map_err(|err| Error::InvalidWheel(err.to_string()))?;
+
+    let scripts = |entry_points: &[EntryPoint]| -> Result<Vec<Script>, Error> {
+        let mut scripts = Vec::new();
+        for entry_point in entry_points {
+            scripts.extend(Script::from_entry_point(entry_point, extras)?);
         }
-        None => Vec::new(),
-    };
-    let gui_scripts = match entry_points_mapping.get("gui_scripts") {
-        Some(gui_scripts) => wheel::read_scripts_from_section(gui_scripts, "gui_scripts", extras)?,
-        None => Vec::new(),
+        Ok(scripts)
     };
+    let mut console_scripts = scripts(entry_points.console_scripts())?;
+    let gui_scripts = scripts(entry_points.gui_scripts())?;
 
     // Special case to generate versioned pip launchers.
     // https://github.com/pypa/pip/blob/3898741e29b7279e7bffe044ecfbe20f6a438b1e/src/pip/_internal/operations/install/wheel.py#L283
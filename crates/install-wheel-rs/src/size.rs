@@ -0,0 +1,238 @@
+//! Measure the disk usage of installed distributions, based on the files listed in their `RECORD`.
+
+use std::path::Path;
+
+use fs_err as fs;
+use rustc_hash::FxHashMap;
+
+use crate::wheel::read_record_file;
+use crate::Error;
+
+/// The disk usage of an installed distribution.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DiskUsage {
+    /// The size of the files installed by the distribution, in bytes.
+    ///
+    /// If a file is shared between multiple distributions in the same set (e.g., via hardlinks),
+    /// its size is divided evenly among them, such that the sizes sum to the total disk usage.
+    pub size: u64,
+    /// The portion of [`DiskUsage::size`] attributable to files that are also linked from outside
+    /// the environment (e.g., from the uv cache), and so don't consume additional disk space.
+    pub linked: u64,
+    /// The number of files installed by the distribution.
+    pub files: usize,
+}
+
+/// A file listed in a `RECORD`.
+struct RecordedFile {
+    id: FileId,
+    size: u64,
+    links: u64,
+}
+
+/// Compute the [`DiskUsage`] of each of the given `.dist-info` directories, in order.
+///
+/// The size of a file that's shared between distributions is divided evenly among them, and each
+/// distribution's total is rounded to the nearest byte.
+///
+/// Files that don't exist on disk (e.g., because they were removed after installation) are
+/// ignored, as are any files that aren't listed in the `RECORD` (like compiled bytecode).
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+pub fn disk_usage<'a>(
+    dist_infos: impl IntoIterator<Item = &'a Path>,
+) -> Result<Vec<DiskUsage>, Error> {
+    // Collect the files installed by each distribution, along with the number of distributions that
+    // refer to each file.
+    let mut owners: FxHashMap<FileId, u64> = FxHashMap::default();
+    let mut distributions = Vec::new();
+    for dist_info in dist_infos {
+        let files = recorded_files(dist_info)?;
+        for file in &files {
+            *owners.entry(file.id.clone()).or_default() += 1;
+        }
+        distributions.push(files);
+    }
+
+    Ok(distributions
+        .into_iter()
+        .map(|files| {
+            // Accumulate the shares of shared files as fractions, to avoid losing the remainder of
+            // each division.
+            let mut size = 0.0;
+            let mut linked = 0.0;
+            for file in &files {
+                let owners = owners.get(&file.id).copied().unwrap_or(1);
+                let share = file.size as f64 / owners as f64;
+                size += share;
+
+                // If the file has more links than owners, it's shared with something outside of
+                // the environment.
+                if file.links > owners {
+                    linked += share;
+                }
+            }
+            DiskUsage {
+                size: size.round() as u64,
+                linked: linked.round() as u64,
+                files: files.len(),
+            }
+        })
+        .collect())
+}
+
+/// Read the `RECORD` for the given `.dist-info` directory, and stat each file that it lists.
+fn recorded_files(dist_info: &Path) -> Result<Vec<RecordedFile>, Error> {
+    let Some(site_packages) = dist_info.parent() else {
+        return Err(Error::BrokenVenv(
+            "dist-info directory is not in a site-packages directory".to_string(),
+        ));
+    };
+
+    let record_path = dist_info.join("RECORD");
+    let mut record_file = match fs::File::open(&record_path) {
+        Ok(record_file) => record_file,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            return Err(Error::MissingRecord(record_path));
+        }
+        Err(err) => return Err(err.into()),
+    };
+    let record = read_record_file(&mut record_file)?;
+
+    let mut files = Vec::with_capacity(record.len());
+    for entry in record {
+        let path = site_packages.join(&entry.path);
+        let metadata = match fs::symlink_metadata(&path) {
+            Ok(metadata) => metadata,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(err) => return Err(err.into()),
+        };
+        if !metadata.is_file() {
+            continue;
+        }
+        files.push(RecordedFile {
+            id: file_id(&path, &metadata),
+            size: metadata.len(),
+            links: link_count(&metadata),
+        });
+    }
+    Ok(files)
+}
+
+/// A unique identifier for a file on disk, such that hardlinks to the same file share an ID.
+#[cfg(unix)]
+type FileId = (u64, u64);
+
+/// A unique identifier for a file on disk. Hardlinks can't be detected on stable Rust on this
+/// platform, so each path is considered distinct.
+#[cfg(not(unix))]
+type FileId = std::path::PathBuf;
+
+#[cfg(unix)]
+fn file_id(_path: &Path, metadata: &std::fs::Metadata) -> FileId {
+    use std::os::unix::fs::MetadataExt;
+    (metadata.dev(), metadata.ino())
+}
+
+#[cfg(not(unix))]
+fn file_id(path: &Path, _metadata: &std::fs::Metadata) -> FileId {
+    crate::uninstall::normalize_path(path)
+}
+
+#[cfg(unix)]
+fn link_count(metadata: &std::fs::Metadata) -> u64 {
+    use std::os::unix::fs::MetadataExt;
+    metadata.nlink()
+}
+
+#[cfg(not(unix))]
+fn link_count(_metadata: &std::fs::Metadata) -> u64 {
+    1
+}
+
+#[cfg(test)]
+mod test {
+    use fs_err as fs;
+    use tempfile::TempDir;
+
+    use super::{disk_usage, DiskUsage};
+
+    #[test]
+    fn shared_files() {
+        let site_packages = TempDir::new().unwrap();
+        let root = site_packages.path();
+
+        fs::create_dir_all(root.join("foo")).unwrap();
+        fs::write(root.join("foo/__init__.py"), "x".repeat(100)).unwrap();
+        fs::write(root.join("shared.py"), "x".repeat(50)).unwrap();
+
+        fs::create_dir_all(root.join("foo-1.0.dist-info")).unwrap();
+        fs::write(
+            root.join("foo-1.0.dist-info/RECORD"),
+            "foo/__init__.py,,100\nshared.py,,50\nfoo/missing.py,,10\nfoo-1.0.dist-info/RECORD,,\n",
+        )
+        .unwrap();
+
+        fs::create_dir_all(root.join("bar-1.0.dist-info")).unwrap();
+        fs::write(
+            root.join("bar-1.0.dist-info/RECORD"),
+            "shared.py,,50\nbar-1.0.dist-info/RECORD,,\n",
+        )
+        .unwrap();
+
+        let foo_record = fs::metadata(root.join("foo-1.0.dist-info/RECORD"))
+            .unwrap()
+            .len();
+        let bar_record = fs::metadata(root.join("bar-1.0.dist-info/RECORD"))
+            .unwrap()
+            .len();
+
+        let usage = disk_usage([
+            root.join("foo-1.0.dist-info").as_path(),
+            root.join("bar-1.0.dist-info").as_path(),
+        ])
+        .unwrap();
+        assert_eq!(
+            usage,
+            [
+                DiskUsage {
+                    size: 100 + 25 + foo_record,
+                    linked: 0,
+                    files: 3,
+                },
+                DiskUsage {
+                    size: 25 + bar_record,
+                    linked: 0,
+                    files: 2,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn fractional_shares() {
+        let site_packages = TempDir::new().unwrap();
+        let root = site_packages.path();
+
+        // Two files of an odd size, each shared between two distributions.
+        fs::write(root.join("a.py"), "x".repeat(51)).unwrap();
+        fs::write(root.join("b.py"), "x".repeat(51)).unwrap();
+        for name in ["foo-1.0.dist-info", "bar-1.0.dist-info"] {
+            fs::create_dir_all(root.join(name)).unwrap();
+            fs::write(root.join(name).join("RECORD"), "a.py,,51\nb.py,,51\n").unwrap();
+        }
+
+        let usage = disk_usage([
+            root.join("foo-1.0.dist-info").as_path(),
+            root.join("bar-1.0.dist-info").as_path(),
+        ])
+        .unwrap();
+        assert_eq!(
+            usage.iter().map(|usage| usage.size).collect::<Vec<_>>(),
+            [51, 51]
+        );
+    }
+}
@@ -0,0 +1,313 @@
+//! Manage environment-level startup hooks, installed as `.pth` files.
+//!
+//! Each hook is owned by a synthetic `uv-startup-<name>` distribution, whose `RECORD` lists the
+//! `.pth` file. As a result, hooks show up alongside other installed packages, and are removed
+//! cleanly by the standard uninstall machinery.
+
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use fs_err as fs;
+use fs_err::File;
+use tracing::debug;
+
+use pypi_types::RecordEntry;
+use uv_normalize::PackageName;
+
+use crate::uninstall::uninstall_wheel;
+use crate::wheel::{read_record_file, record_path, write_file_recorded};
+use crate::Error;
+
+/// The prefix for the `.dist-info` directory and `.pth` file of a startup hook.
+const STARTUP_PREFIX: &str = "uv_startup_";
+
+/// The version of the synthetic distribution that owns a startup hook.
+const STARTUP_VERSION: &str = "0.0.0";
+
+/// A startup hook installed into a `site-packages` directory.
+#[derive(Debug, Clone)]
+pub struct StartupHook {
+    /// The name of the hook.
+    pub name: PackageName,
+    /// The code executed when the interpreter starts.
+    pub code: String,
+    /// Whether the hook is enabled, i.e., whether its `.pth` file is active.
+    pub enabled: bool,
+    /// The `.dist-info` directory of the distribution that owns the hook.
+    pub dist_info: PathBuf,
+}
+
+impl StartupHook {
+    /// The name of the distribution that owns the hook, e.g., `uv-startup-coverage`.
+    pub fn dist_name(&self) -> String {
+        format!("uv-startup-{}", self.name)
+    }
+}
+
+/// Install a startup hook that runs the given code whenever the interpreter starts, replacing any
+/// existing hook of the same name.
+///
+/// Python only executes `.pth` lines that begin with `import`, so every non-empty line of `code`
+/// must do so.
+pub fn install_startup_hook(
+    site_packages: &Path,
+    name: &PackageName,
+    code: &str,
+    installer: &str,
+) -> Result<StartupHook, Error> {
+    let lines = code
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>();
+    if lines.is_empty() {
+        return Err(Error::InvalidStartupHook(
+            "the hook must contain at least one line of code".to_string(),
+        ));
+    }
+    if let Some(line) = lines
+        .iter()
+        .find(|line| !(line.starts_with("import ") || line.starts_with("import\t")))
+    {
+        return Err(Error::InvalidStartupHook(format!(
+            "`{line}` does not begin with `import`, so it would not be executed at startup"
+        )));
+    }
+
+    // Replace any existing hook of the same name.
+    if let Some(existing) = find_startup_hook(site_packages, name)? {
+        debug!("Replacing existing startup hook: {}", existing.name);
+        uninstall_wheel(&existing.dist_info, false)?;
+    }
+
+    let stem = stem(name);
+    let dist_info_dir = PathBuf::from(format!("{stem}-{STARTUP_VERSION}.dist-info"));
+    fs::create_dir_all(site_packages.join(&dist_info_dir))?;
+
+    let mut record = Vec::new();
+    let mut contents = lines.join("\n");
+    contents.push('\n');
+    write_file_recorded(
+        site_packages,
+        Path::new(&format!("{stem}.pth")),
+        &contents,
+        &mut record,
+    )?;
+    write_file_recorded(
+        site_packages,
+        &dist_info_dir.join("METADATA"),
+        format!(
+            "Metadata-Version: 2.1\nName: uv-startup-{name}\nVersion: {STARTUP_VERSION}\nSummary: Startup hook managed by {installer}\n"
+        ),
+        &mut record,
+    )?;
+    write_file_recorded(
+        site_packages,
+        &dist_info_dir.join("INSTALLER"),
+        installer,
+        &mut record,
+    )?;
+    write_record(site_packages, &dist_info_dir, record)?;
+
+    Ok(StartupHook {
+        name: name.clone(),
+        code: contents,
+        enabled: true,
+        dist_info: site_packages.join(dist_info_dir),
+    })
+}
+
+/// Enable or disable the startup hook with the given name, by renaming its `.pth` file.
+///
+/// Returns `None` if no such hook is installed.
+pub fn set_startup_hook_enabled(
+    site_packages: &Path,
+    name: &PackageName,
+    enabled: bool,
+) -> Result<Option<StartupHook>, Error> {
+    let Some(hook) = find_startup_hook(site_packages, name)? else {
+        return Ok(None);
+    };
+    if hook.enabled == enabled {
+        return Ok(Some(hook));
+    }
+
+    let stem = stem(name);
+    let active = format!("{stem}.pth");
+    let inactive = format!("{stem}.pth.disabled");
+    let (from, to) = if enabled {
+        (inactive, active)
+    } else {
+        (active, inactive)
+    };
+    fs::rename(site_packages.join(&from), site_packages.join(&to))?;
+
+    // Update the `RECORD`, such that uninstalling the hook removes the renamed file.
+    let Some(dist_info_dir) = hook.dist_info.file_name().map(PathBuf::from) else {
+        return Err(Error::InvalidStartupHook(format!(
+            "invalid dist-info directory: {}",
+            hook.dist_info.display()
+        )));
+    };
+    let mut record = read_record_file(&mut File::open(hook.dist_info.join("RECORD"))?)?;
+    record.retain(|entry| !entry.path.ends_with("RECORD"));
+    for entry in &mut record {
+        if entry.path == from {
+            entry.path.clone_from(&to);
+        }
+    }
+    write_record(site_packages, &dist_info_dir, record)?;
+
+    Ok(Some(StartupHook { enabled, ..hook }))
+}
+
+/// Find the startup hook with the given name, if it's installed.
+pub fn find_startup_hook(
+    site_packages: &Path,
+    name: &PackageName,
+) -> Result<Option<StartupHook>, Error> {
+    let stem = stem(name);
+    let dist_info = site_packages.join(format!("{stem}-{STARTUP_VERSION}.dist-info"));
+    if !dist_info.is_dir() {
+        return Ok(None);
+    }
+
+    let active = site_packages.join(format!("{stem}.pth"));
+    let inactive = site_packages.join(format!("{stem}.pth.disabled"));
+    let (code, enabled) = match fs::read_to_string(&active) {
+        Ok(code) => (code, true),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            match fs::read_to_string(&inactive) {
+                Ok(code) => (code, false),
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => (String::new(), false),
+                Err(err) => return Err(err.into()),
+            }
+        }
+        Err(err) => return Err(err.into()),
+    };
+
+    Ok(Some(StartupHook {
+        name: name.clone(),
+        code,
+        enabled,
+        dist_info,
+    }))
+}
+
+/// Find all startup hooks installed into the `site-packages` directory, sorted by name.
+pub fn find_startup_hooks(site_packages: &Path) -> Result<Vec<StartupHook>, Error> {
+    let read_dir = match fs::read_dir(site_packages) {
+        Ok(read_dir) => read_dir,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err.into()),
+    };
+
+    let mut hooks = Vec::new();
+    for entry in read_dir {
+        let file_name = entry?.file_name();
+        let Some(name) = file_name
+            .to_str()
+            .and_then(|name| name.strip_prefix(STARTUP_PREFIX))
+            .and_then(|name| name.strip_suffix(&format!("-{STARTUP_VERSION}.dist-info")))
+            .and_then(|name| PackageName::from_str(name).ok())
+        else {
+            continue;
+        };
+        if let Some(hook) = find_startup_hook(site_packages, &name)? {
+            hooks.push(hook);
+        }
+    }
+    hooks.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(hooks)
+}
+
+/// The file stem shared by the `.pth` file and `.dist-info` directory of a hook.
+fn stem(name: &PackageName) -> String {
+    format!("{STARTUP_PREFIX}{}", name.as_dist_info_name())
+}
+
+/// Write the `RECORD` file for a startup hook, including an entry for the `RECORD` itself.
+fn write_record(
+    site_packages: &Path,
+    dist_info_dir: &Path,
+    mut record: Vec<RecordEntry>,
+) -> Result<(), Error> {
+    let record_file = dist_info_dir.join("RECORD");
+    record.push(RecordEntry {
+        path: record_path(&record_file),
+        hash: None,
+        size: None,
+    });
+    record.sort();
+
+    let mut record_writer = csv::WriterBuilder::new()
+        .has_headers(false)
+        .escape(b'"')
+        .from_path(site_packages.join(record_file))?;
+    for entry in record {
+        record_writer.serialize(entry)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use fs_err as fs;
+    use tempfile::TempDir;
+
+    use uv_normalize::PackageName;
+
+    use super::{find_startup_hooks, install_startup_hook, set_startup_hook_enabled};
+    use crate::uninstall_wheel;
+
+    #[test]
+    fn startup_hook_lifecycle() {
+        let site_packages = TempDir::new().unwrap();
+        let name = PackageName::from_str("coverage").unwrap();
+
+        let hook = install_startup_hook(
+            site_packages.path(),
+            &name,
+            "import coverage; coverage.process_startup()",
+            "uv",
+        )
+        .unwrap();
+        let pth = site_packages.path().join("uv_startup_coverage.pth");
+        assert!(pth.is_file());
+        assert_eq!(
+            fs::read_to_string(hook.dist_info.join("INSTALLER")).unwrap(),
+            "uv"
+        );
+
+        // Disabling the hook renames the `.pth` file, and updates the `RECORD` to match.
+        let hook = set_startup_hook_enabled(site_packages.path(), &name, false)
+            .unwrap()
+            .unwrap();
+        assert!(!hook.enabled);
+        assert!(!pth.exists());
+        let record = fs::read_to_string(hook.dist_info.join("RECORD")).unwrap();
+        assert!(record.contains("uv_startup_coverage.pth.disabled,"));
+
+        // `RECORD` paths use forward slashes on every platform.
+        assert!(record.contains("uv_startup_coverage-0.0.0.dist-info/METADATA,"));
+        assert!(record.contains("uv_startup_coverage-0.0.0.dist-info/RECORD,,"));
+
+        let hooks = find_startup_hooks(site_packages.path()).unwrap();
+        assert_eq!(hooks.len(), 1);
+        assert_eq!(hooks[0].name, name);
+
+        // Uninstalling the owning distribution removes everything.
+        uninstall_wheel(&hook.dist_info, false).unwrap();
+        assert_eq!(fs::read_dir(site_packages.path()).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn startup_hook_requires_import() {
+        let site_packages = TempDir::new().unwrap();
+        let name = PackageName::from_str("warnings").unwrap();
+        assert!(install_startup_hook(site_packages.path(), &name, "print('hi')", "uv").is_err());
+        assert!(install_startup_hook(site_packages.path(), &name, "", "uv").is_err());
+    }
+}
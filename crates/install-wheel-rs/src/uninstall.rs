@@ -4,44 +4,81 @@ use std::path::{Component, Path, PathBuf};
 use fs_err as fs;
 use tracing::debug;
 
-use crate::wheel::read_record_file;
+use pypi_types::RecordEntry;
+
+use crate::wheel::{hash_files, read_record_file};
 use crate::Error;
 
 /// Uninstall the wheel represented by the given `dist_info` directory.
-pub fn uninstall_wheel(dist_info: &Path) -> Result<Uninstall, Error> {
-    let Some(site_packages) = dist_info.parent() else {
-        return Err(Error::BrokenVenv(
-            "dist-info directory is not in a site-packages directory".to_string(),
-        ));
-    };
-
-    // Read the RECORD file.
-    let record_path = dist_info.join("RECORD");
-    let mut record_file = match fs::File::open(&record_path) {
-        Ok(record_file) => record_file,
-        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
-            return Err(Error::MissingRecord(record_path));
-        }
-        Err(err) => return Err(err.into()),
-    };
-    let record = read_record_file(&mut record_file)?;
+///
+/// Files that were already absent are reported as missing. If `check_modified` is set, every file
+/// is first hashed and compared against the `RECORD`; files whose contents no longer match are
+/// still removed, but are reported as modified.
+pub fn uninstall_wheel(dist_info: &Path, check_modified: bool) -> Result<Uninstall, Error> {
+    let (site_packages, record) = read_record(dist_info)?;
 
     let mut file_count = 0usize;
     let mut dir_count = 0usize;
+    let mut script_count = 0usize;
+    let mut pth_count = 0usize;
+    let mut bytecode_count = 0usize;
+    let mut modified = Vec::new();
+    let mut missing = Vec::new();
+
+    // If requested, compare the files against their recorded hashes prior to removing them. Files
+    // without a recorded hash (like the `RECORD` itself) are skipped.
+    let hashed = if check_modified {
+        record
+            .iter()
+            .filter(|entry| entry.hash.is_some())
+            .map(|entry| site_packages.join(&entry.path))
+            .collect::<Vec<_>>()
+    } else {
+        Vec::new()
+    };
+    let mut hashes = hash_files(&hashed).into_iter();
 
     // Uninstall the files, keeping track of any directories that are left empty.
     let mut visited = BTreeSet::new();
     for entry in &record {
         let path = site_packages.join(&entry.path);
+
+        if let Some(expected) = entry.hash.as_deref().filter(|_| check_modified) {
+            if let Some(Ok((_, actual))) = hashes.next() {
+                if actual != expected {
+                    debug!("File was modified since installation: {}", path.display());
+                    modified.push(normalize_path(&path));
+                }
+            }
+        }
+
         match fs::remove_file(&path) {
             Ok(()) => {
                 debug!("Removed file: {}", path.display());
                 file_count += 1;
+
+                let path = normalize_path(&path);
+                if !path.starts_with(site_packages) {
+                    // Files outside of `site-packages` are scripts (like console-script launchers
+                    // in `bin`).
+                    script_count += 1;
+                } else if path.extension().is_some_and(|ext| ext == "pth") {
+                    pth_count += 1;
+                } else if path.extension().is_some_and(|ext| ext == "py") {
+                    // Remove any bytecode compiled from the module, which may or may not be listed
+                    // in the RECORD.
+                    bytecode_count += remove_bytecode(&path)?;
+                }
+
                 if let Some(parent) = path.parent() {
-                    visited.insert(normalize_path(parent));
+                    visited.insert(parent.join("__pycache__"));
+                    visited.insert(parent.to_path_buf());
                 }
             }
-            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                debug!("File was already missing: {}", path.display());
+                missing.push(normalize_path(&path));
+            }
             Err(err) => match fs::remove_dir_all(&path) {
                 Ok(()) => {
                     debug!("Removed directory: {}", path.display());
@@ -71,31 +108,29 @@ pub fn uninstall_wheel(dist_info: &Path) -> Result<Uninstall, Error> {
                 break;
             }
 
-            // If the directory contains a `__pycache__` directory, always remove it. `__pycache__`
-            // may or may not be listed in the RECORD, but installers are expected to be smart
-            // enough to remove it either way.
-            let pycache = path.join("__pycache__");
-            match fs::remove_dir_all(&pycache) {
-                Ok(()) => {
-                    debug!("Removed directory: {}", pycache.display());
-                    dir_count += 1;
-                }
-                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
-                Err(err) => return Err(err.into()),
-            }
-
             // Try to read from the directory. If it doesn't exist, assume we deleted it in a
             // previous iteration.
-            let mut read_dir = match fs::read_dir(path) {
-                Ok(read_dir) => read_dir,
+            let entries = match fs::read_dir(path) {
+                Ok(read_dir) => read_dir
+                    .map(|entry| entry.map(|entry| entry.file_name()))
+                    .collect::<Result<Vec<_>, _>>()?,
                 Err(err) if err.kind() == std::io::ErrorKind::NotFound => break,
                 Err(err) => return Err(err.into()),
             };
 
-            // If the directory is not empty, we're done.
-            if read_dir.next().is_some() {
+            // If the directory contains anything other than a `__pycache__` directory, we're done.
+            // Otherwise, remove the `__pycache__` directory, which may contain bytecode that isn't
+            // listed in the RECORD (e.g., compiled by the interpreter at runtime), but which
+            // installers are expected to be smart enough to remove either way.
+            if entries.iter().any(|name| name != "__pycache__") {
                 break;
             }
+            if !entries.is_empty() {
+                let pycache = path.join("__pycache__");
+                fs::remove_dir_all(&pycache)?;
+                debug!("Removed directory: {}", pycache.display());
+                dir_count += 1;
+            }
 
             fs::remove_dir(path)?;
 
@@ -113,21 +148,120 @@ pub fn uninstall_wheel(dist_info: &Path) -> Result<Uninstall, Error> {
     Ok(Uninstall {
         file_count,
         dir_count,
+        script_count,
+        pth_count,
+        bytecode_count,
+        modified,
+        missing,
     })
 }
 
+/// List the files that would be removed by uninstalling the wheel represented by the given
+/// `dist_info` directory, without removing anything.
+///
+/// Only files that currently exist are included. Directories that would be left empty (and
+/// `__pycache__` directories) are not included.
+pub fn uninstall_wheel_dry_run(dist_info: &Path) -> Result<Vec<PathBuf>, Error> {
+    let (site_packages, record) = read_record(dist_info)?;
+
+    let mut paths = record
+        .iter()
+        .map(|entry| normalize_path(&site_packages.join(&entry.path)))
+        .filter(|path| path.exists())
+        .collect::<Vec<_>>();
+    paths.sort();
+    paths.dedup();
+    Ok(paths)
+}
+
+/// Read the `RECORD` file from the given `dist_info` directory, returning it along with the
+/// enclosing `site-packages` directory.
+fn read_record(dist_info: &Path) -> Result<(&Path, Vec<RecordEntry>), Error> {
+    let Some(site_packages) = dist_info.parent() else {
+        return Err(Error::BrokenVenv(
+            "dist-info directory is not in a site-packages directory".to_string(),
+        ));
+    };
+
+    let record_path = dist_info.join("RECORD");
+    let mut record_file = match fs::File::open(&record_path) {
+        Ok(record_file) => record_file,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            return Err(Error::MissingRecord(record_path));
+        }
+        Err(err) => return Err(err.into()),
+    };
+    let record = read_record_file(&mut record_file)?;
+
+    Ok((site_packages, record))
+}
+
+/// Remove any bytecode compiled from the given Python module (i.e., any
+/// `__pycache__/{module}.{tag}.pyc` files alongside it), returning the number of files removed.
+fn remove_bytecode(module: &Path) -> Result<usize, Error> {
+    let (Some(parent), Some(stem)) = (module.parent(), module.file_stem()) else {
+        return Ok(0);
+    };
+    let pycache = parent.join("__pycache__");
+    let read_dir = match fs::read_dir(&pycache) {
+        Ok(read_dir) => read_dir,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(err) => return Err(err.into()),
+    };
+
+    let stem = stem.to_string_lossy();
+    let mut count = 0;
+    for entry in read_dir {
+        let entry = entry?;
+        let file_name = entry.file_name();
+        let Some(file_name) = file_name.to_str() else {
+            continue;
+        };
+        // Ex) `module.cpython-312.pyc` or `module.cpython-312.opt-1.pyc`
+        let is_bytecode = file_name
+            .strip_prefix(stem.as_ref())
+            .and_then(|rest| rest.strip_prefix('.'))
+            .is_some_and(|rest| rest.ends_with(".pyc"));
+        if !is_bytecode {
+            continue;
+        }
+        match fs::remove_file(entry.path()) {
+            Ok(()) => {
+                debug!("Removed file: {}", entry.path().display());
+                count += 1;
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err.into()),
+        }
+    }
+    Ok(count)
+}
+
 #[derive(Debug, Default)]
 pub struct Uninstall {
     /// The number of files that were removed during the uninstallation.
     pub file_count: usize,
     /// The number of directories that were removed during the uninstallation.
     pub dir_count: usize,
+    /// The number of removed files that were scripts outside of `site-packages` (e.g., console
+    /// script launchers). Included in `file_count`.
+    pub script_count: usize,
+    /// The number of removed files that were `.pth` files. Included in `file_count`.
+    pub pth_count: usize,
+    /// The number of bytecode files removed from `__pycache__` directories for removed modules.
+    /// Not included in `file_count`.
+    pub bytecode_count: usize,
+    /// The files that were removed, but whose contents no longer matched the `RECORD`. Only
+    /// populated if modifications were checked.
+    pub modified: Vec<PathBuf>,
+    /// The files that were listed in the `RECORD`, but were already missing.
+    pub missing: Vec<PathBuf>,
 }
 
 /// Normalize a path, removing things like `.` and `..`.
 ///
 /// Source: <https://github.com/rust-lang/cargo/blob/b48c41aedbd69ee3990d62a0e2006edbb506a480/crates/cargo-util/src/paths.rs#L76C1-L109C2>
-fn normalize_path(path: &Path) -> PathBuf {
+pub(crate) fn normalize_path(path: &Path) -> PathBuf {
     let mut components = path.components().peekable();
     let mut ret = if let Some(c @ Component::Prefix(..)) = components.peek().copied() {
         components.next();
@@ -153,3 +287,53 @@ fn normalize_path(path: &Path) -> PathBuf {
     }
     ret
 }
+
+#[cfg(test)]
+mod test {
+    use fs_err as fs;
+    use tempfile::TempDir;
+
+    use super::uninstall_wheel;
+
+    #[test]
+    fn uninstall_categories() {
+        let root = TempDir::new().unwrap();
+        let site_packages = root.path().join("lib").join("site-packages");
+        let bin = root.path().join("bin");
+        let package = site_packages.join("pkg");
+        let pycache = package.join("__pycache__");
+        let dist_info = site_packages.join("pkg-1.0.0.dist-info");
+        for dir in [&bin, &pycache, &dist_info] {
+            fs::create_dir_all(dir).unwrap();
+        }
+
+        for file in [
+            package.join("__init__.py"),
+            package.join("module.py"),
+            pycache.join("__init__.cpython-312.pyc"),
+            pycache.join("module.cpython-312.opt-1.pyc"),
+            site_packages.join("pkg.pth"),
+            bin.join("pkg"),
+        ] {
+            fs::write(file, "").unwrap();
+        }
+        fs::write(
+            dist_info.join("RECORD"),
+            "pkg/__init__.py,,\npkg/module.py,,\npkg.pth,,\n../../bin/pkg,,\npkg-1.0.0.dist-info/RECORD,,\n",
+        )
+        .unwrap();
+
+        // Files that aren't listed in the RECORD (and bytecode for their modules) should be
+        // retained, along with their directories.
+        fs::write(package.join("other.py"), "").unwrap();
+        fs::write(pycache.join("other.cpython-312.pyc"), "").unwrap();
+        let summary = uninstall_wheel(&dist_info, false).unwrap();
+        assert_eq!(summary.file_count, 5);
+        assert_eq!(summary.script_count, 1);
+        assert_eq!(summary.pth_count, 1);
+        assert_eq!(summary.bytecode_count, 2);
+        assert_eq!(summary.dir_count, 1);
+        assert!(pycache.join("other.cpython-312.pyc").is_file());
+        assert!(!bin.join("pkg").exists());
+    }
+}
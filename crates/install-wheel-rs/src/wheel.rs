@@ -1,4 +1,3 @@
-use std::collections::HashMap;
 use std::io::{BufRead, BufReader, Cursor, Read, Write};
 use std::path::{Path, PathBuf};
 use std::{env, io, iter};
@@ -7,6 +6,7 @@ use data_encoding::BASE64URL_NOPAD;
 use fs_err as fs;
 use fs_err::{DirEntry, File};
 use mailparse::MailHeaderMap;
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 use rustc_hash::FxHashMap;
 use sha2::{Digest, Sha256};
 use tracing::{instrument, warn};
@@ -14,11 +14,11 @@ use walkdir::WalkDir;
 use zip::write::FileOptions;
 use zip::ZipWriter;
 
-use pypi_types::DirectUrl;
+use pypi_types::{DirectUrl, RecordEntry};
 use uv_fs::Simplified;
+use uv_warnings::{warn_user, warn_user_code, WarningCode};
 
-use crate::record::RecordEntry;
-use crate::script::Script;
+use crate::script::{Script, ScriptNaming};
 use crate::{Error, Layout};
 
 const LAUNCHER_MAGIC_NUMBER: [u8; 4] = [b'U', b'V', b'U', b'V'];
@@ -62,35 +62,14 @@ if __name__ == "__main__":
     )
 }
 
-/// Part of entrypoints parsing
-pub(crate) fn read_scripts_from_section(
-    scripts_section: &HashMap<String, Option<String>>,
-    section_name: &str,
-    extras: Option<&[String]>,
-) -> Result<Vec<Script>, Error> {
-    let mut scripts = Vec::new();
-    for (script_name, python_location) in scripts_section {
-        match python_location {
-            Some(value) => {
-                if let Some(script) = Script::from_value(script_name, value, extras)? {
-                    scripts.push(script);
-                }
-            }
-            None => {
-                return Err(Error::InvalidWheel(format!(
-                    "[{section_name}] key {script_name} must have a value"
-                )));
-            }
-        }
-    }
-    Ok(scripts)
-}
-
 /// Shamelessly stolen (and updated for recent sha2)
 /// <https://github.com/richo/hashing-copy/blob/d8dd2fdb63c6faf198de0c9e5713d6249cbb5323/src/lib.rs#L10-L52>
 /// which in turn got it from std
 /// <https://doc.rust-lang.org/1.58.0/src/std/io/copy.rs.html#128-156>
-fn copy_and_hash(reader: &mut impl Read, writer: &mut impl Write) -> io::Result<(u64, String)> {
+pub(crate) fn copy_and_hash(
+    reader: &mut impl Read,
+    writer: &mut impl Write,
+) -> io::Result<(u64, String)> {
     // TODO: Do we need to support anything besides sha256?
     let mut hasher = Sha256::new();
     // Same buf size as std. Note that this number is important for performance
@@ -114,6 +93,68 @@ fn copy_and_hash(reader: &mut impl Read, writer: &mut impl Write) -> io::Result<
     ))
 }
 
+/// Compute the size and hash of each of the given files, in the format used by `RECORD` files
+/// (e.g., `sha256=...`).
+///
+/// The files are hashed in parallel, since hashing dominates for wheels with many (or large)
+/// files on fast disks. Results are returned in the same order as the input.
+pub(crate) fn hash_files<P: AsRef<Path> + Sync>(paths: &[P]) -> Vec<io::Result<(u64, String)>> {
+    paths
+        .par_iter()
+        .map(|path| copy_and_hash(&mut File::open(path.as_ref())?, &mut io::sink()))
+        .collect()
+}
+
+/// Verify that every file listed in the `RECORD` exists, and matches its recorded size and hash.
+///
+/// Entries with a SHA-256 hash are hashed, which also yields their size; any other entries (like
+/// the `RECORD` itself) are only checked for existence and size. As such, each file is read at
+/// most once.
+pub(crate) fn verify_record(site_packages: &Path, record: &[RecordEntry]) -> Result<(), Error> {
+    let (hashed, unhashed): (Vec<_>, Vec<_>) = record.iter().partition(|entry| {
+        entry
+            .hash
+            .as_deref()
+            .is_some_and(|hash| hash.starts_with("sha256="))
+    });
+
+    for entry in unhashed {
+        let metadata = fs::metadata(site_packages.join(&entry.path)).map_err(|err| {
+            Error::RecordFile(format!("Missing installed file `{}`: {err}", entry.path))
+        })?;
+        if let Some(expected) = entry.size.filter(|expected| *expected != metadata.len()) {
+            return Err(Error::RecordFile(format!(
+                "Size mismatch for `{}`: expected {expected}, found {}",
+                entry.path,
+                metadata.len(),
+            )));
+        }
+    }
+
+    let paths = hashed
+        .iter()
+        .map(|entry| site_packages.join(&entry.path))
+        .collect::<Vec<_>>();
+    for (entry, result) in hashed.iter().zip(hash_files(&paths)) {
+        let (size, hash) = result
+            .map_err(|err| Error::RecordFile(format!("Failed to hash `{}`: {err}", entry.path)))?;
+        if entry.hash.as_deref() != Some(hash.as_str()) {
+            return Err(Error::RecordFile(format!(
+                "Hash mismatch for `{}`: expected `{}`, found `{hash}`",
+                entry.path,
+                entry.hash.as_deref().unwrap_or_default(),
+            )));
+        }
+        if let Some(expected) = entry.size.filter(|expected| *expected != size) {
+            return Err(Error::RecordFile(format!(
+                "Size mismatch for `{}`: expected {expected}, found {size}",
+                entry.path,
+            )));
+        }
+    }
+    Ok(())
+}
+
 /// Format the shebang for a given Python executable.
 ///
 /// Like pip, if a shebang is non-simple (too long or contains spaces), we use `/bin/sh` as the
@@ -143,6 +184,25 @@ fn format_shebang(executable: impl AsRef<Path>, os_name: &str) -> String {
     format!("#!{executable}")
 }
 
+/// Returns the Python executable that should run a script.
+///
+/// On Windows, GUI scripts are run with `pythonw.exe` (if present alongside the interpreter), to
+/// avoid opening a console window. Otherwise, the interpreter itself is used.
+fn get_script_executable(python_executable: &Path, is_gui: bool) -> PathBuf {
+    if cfg!(windows) && is_gui {
+        python_executable
+            .file_name()
+            .map(|name| {
+                let name = name.to_string_lossy().replace("python", "pythonw");
+                python_executable.with_file_name(name)
+            })
+            .filter(|path| path.is_file())
+            .unwrap_or_else(|| python_executable.to_path_buf())
+    } else {
+        python_executable.to_path_buf()
+    }
+}
+
 /// A Windows script is a minimal .exe launcher binary with the python entrypoint script appended as
 /// stored zip file. The launcher will look for `python[w].exe` adjacent to it in the same directory
 /// to start the embedded script.
@@ -222,23 +282,24 @@ pub(crate) fn write_script_entrypoints(
     layout: &Layout,
     site_packages: &Path,
     entrypoints: &[Script],
+    naming: &ScriptNaming,
     record: &mut Vec<RecordEntry>,
     is_gui: bool,
 ) -> Result<(), Error> {
     for entrypoint in entrypoints {
         let entrypoint_absolute = if cfg!(windows) {
             // On windows we actually build an .exe wrapper
-            let script_name = entrypoint
-                .name
-                // FIXME: What are the in-reality rules here for names?
-                .strip_suffix(".py")
-                .unwrap_or(&entrypoint.name)
-                .to_string()
-                + ".exe";
+            let script_name = naming.apply(
+                entrypoint
+                    .name
+                    // FIXME: What are the in-reality rules here for names?
+                    .strip_suffix(".py")
+                    .unwrap_or(&entrypoint.name),
+            ) + ".exe";
 
             layout.scripts.join(script_name)
         } else {
-            layout.scripts.join(&entrypoint.name)
+            layout.scripts.join(naming.apply(&entrypoint.name))
         };
 
         let entrypoint_relative = pathdiff::diff_paths(&entrypoint_absolute, site_packages)
@@ -252,10 +313,21 @@ pub(crate) fn write_script_entrypoints(
                 ))
             })?;
 
+        // Any existing script was installed by another package, since a reinstalled package is
+        // removed before it's installed again.
+        if entrypoint_absolute.exists() {
+            warn_user_code!(
+                WarningCode::ScriptCollision,
+                "Overwriting existing script `{}`, which may belong to another package",
+                entrypoint_absolute.simplified_display()
+            );
+        }
+
         // Generate the launcher script.
+        let launcher_executable = get_script_executable(&layout.sys_executable, is_gui);
         let launcher_python_script = get_script_launcher(
             entrypoint,
-            &format_shebang(&layout.sys_executable, &layout.os_name),
+            &format_shebang(&launcher_executable, &layout.os_name),
         );
 
         // If necessary, wrap the launcher script in a Windows launcher binary.
@@ -263,7 +335,7 @@ pub(crate) fn write_script_entrypoints(
             write_file_recorded(
                 site_packages,
                 &entrypoint_relative,
-                &windows_script_launcher(&launcher_python_script, is_gui, &layout.sys_executable)?,
+                &windows_script_launcher(&launcher_python_script, is_gui, &launcher_executable)?,
                 record,
             )?;
         } else {
@@ -418,7 +490,12 @@ pub(crate) fn move_folder_recorded(
                         src.simplified_display()
                     ))
                 })?;
-            entry.path = relative_to(&target, site_packages)?.display().to_string();
+            // Like pip, record the absolute path if the target can't be expressed relative to
+            // `site-packages` (e.g., it's on a different drive on Windows).
+            entry.path = relative_to(&target, site_packages)
+                .unwrap_or_else(|_| target.clone())
+                .display()
+                .to_string();
         }
     }
     Ok(())
@@ -458,11 +535,22 @@ fn install_script(
     let mut start = vec![0; placeholder_python.len()];
     script.read_exact(&mut start)?;
     let size_and_encoded_hash = if start == placeholder_python {
-        let start = format_shebang(&layout.sys_executable, &layout.os_name)
-            .as_bytes()
-            .to_vec();
+        // Check for the `#!pythonw` variant, retaining the next byte if it isn't part of the
+        // placeholder.
+        let mut buf = [0; 1];
+        let read = script.read(&mut buf)?;
+        let is_gui = read == 1 && buf[0] == b'w';
+        let next: &[u8] = if is_gui { &[] } else { &buf[..read] };
+
+        let start = format_shebang(
+            get_script_executable(&layout.sys_executable, is_gui),
+            &layout.os_name,
+        )
+        .as_bytes()
+        .to_vec();
         let mut target = File::create(&target_path)?;
-        let size_and_encoded_hash = copy_and_hash(&mut start.chain(script), &mut target)?;
+        let size_and_encoded_hash =
+            copy_and_hash(&mut start.chain(next).chain(script), &mut target)?;
         fs::remove_file(&path)?;
         Some(size_and_encoded_hash)
     } else {
@@ -517,6 +605,17 @@ pub(crate) fn install_data(
         let entry = entry?;
         let path = entry.path();
 
+        // Each entry is a key into the installation scheme, and should be a directory whose
+        // contents are moved to the corresponding location. Some wheels in the wild include stray
+        // files alongside them, which have nowhere to go.
+        if !entry.file_type()?.is_dir() {
+            warn_user!(
+                "Ignoring file in the data directory of `{dist_name}`: {}",
+                entry.file_name().to_string_lossy()
+            );
+            continue;
+        }
+
         match path.file_name().and_then(|name| name.to_str()) {
             Some("data") => {
                 // Move the content of the folder to the root of the venv
@@ -546,6 +645,9 @@ pub(crate) fn install_data(
                 }
             }
             Some("headers") => {
+                // Headers are installed into a directory named after the distribution, e.g.,
+                // `include/site/python3.12/<dist_name>` in a virtual environment, or
+                // `include/python3.12/<dist_name>` otherwise.
                 let target_path = layout.include.join(dist_name);
                 move_folder_recorded(&path, &target_path, site_packages, record)?;
             }
@@ -586,13 +688,24 @@ pub(crate) fn write_file_recorded(
     let hash = Sha256::new().chain_update(content.as_ref()).finalize();
     let encoded_hash = format!("sha256={}", BASE64URL_NOPAD.encode(&hash));
     record.push(RecordEntry {
-        path: relative_path.display().to_string(),
+        path: record_path(relative_path),
         hash: Some(encoded_hash),
         size: Some(content.as_ref().len() as u64),
     });
     Ok(())
 }
 
+/// Format a path, relative to the `site-packages` directory, for a `RECORD` entry.
+///
+/// `RECORD` paths always use forward slashes as separators, regardless of the platform.
+pub(crate) fn record_path(relative_path: &Path) -> String {
+    relative_path
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
 /// Adds `INSTALLER`, `REQUESTED` and `direct_url.json` to the .dist-info dir
 pub(crate) fn extra_dist_info(
     site_packages: &Path,
@@ -606,7 +719,7 @@ pub(crate) fn extra_dist_info(
     write_file_recorded(
         site_packages,
         &dist_info_dir.join("INSTALLER"),
-        env!("CARGO_PKG_NAME"),
+        installer.unwrap_or(env!("CARGO_PKG_NAME")),
         record,
     )?;
     if requested {
@@ -620,34 +733,13 @@ pub(crate) fn extra_dist_info(
             record,
         )?;
     }
-    if let Some(installer) = installer {
-        write_file_recorded(
-            site_packages,
-            &dist_info_dir.join("INSTALLER"),
-            installer,
-            record,
-        )?;
-    }
     Ok(())
 }
 
 /// Reads the record file
 /// <https://www.python.org/dev/peps/pep-0376/#record>
 pub(crate) fn read_record_file(record: &mut impl Read) -> Result<Vec<RecordEntry>, Error> {
-    csv::ReaderBuilder::new()
-        .has_headers(false)
-        .escape(Some(b'"'))
-        .from_reader(record)
-        .deserialize()
-        .map(|entry| {
-            let entry: RecordEntry = entry?;
-            Ok(RecordEntry {
-                // selenium uses absolute paths for some reason
-                path: entry.path.trim_start_matches('/').to_string(),
-                ..entry
-            })
-        })
-        .collect()
+    Ok(RecordEntry::read_all(record)?)
 }
 
 /// Parse a file with `Key: value` entries such as WHEEL and METADATA
@@ -726,7 +818,7 @@ mod test {
 
     use indoc::{formatdoc, indoc};
 
-    use crate::wheel::format_shebang;
+    use crate::wheel::{format_shebang, get_script_executable, hash_files, verify_record};
 
     use super::{parse_key_value_file, parse_wheel_file, read_record_file, relative_to, Script};
 
@@ -785,6 +877,50 @@ mod test {
         assert_eq!(expected, actual);
     }
 
+    #[test]
+    fn test_hash_files() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let empty = temp_dir.path().join("empty.py");
+        fs_err::write(&empty, "").unwrap();
+        let content = temp_dir.path().join("content.py");
+        fs_err::write(&content, "hello").unwrap();
+        let missing = temp_dir.path().join("missing.py");
+
+        let hashes = hash_files(&[&content, &missing, &empty]);
+        assert_eq!(
+            hashes[0].as_ref().unwrap(),
+            &(
+                5,
+                "sha256=LPJNul-wow4m6DsqxbninhsWHlwfp0JecwQzYpOLmCQ".to_string()
+            )
+        );
+        assert!(hashes[1].is_err());
+        assert_eq!(
+            hashes[2].as_ref().unwrap(),
+            &(
+                0,
+                "sha256=47DEQpj8HBSa-_TImW-5JCeuQeRkm5NMpJWZG3hSuFU".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_verify_record() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        fs_err::write(temp_dir.path().join("content.py"), "hello").unwrap();
+
+        let record = read_record_file(
+            &mut "content.py,sha256=LPJNul-wow4m6DsqxbninhsWHlwfp0JecwQzYpOLmCQ,5\nRECORD,,\n"
+                .as_bytes(),
+        )
+        .unwrap();
+        verify_record(temp_dir.path(), &record).unwrap();
+
+        fs_err::write(temp_dir.path().join("content.py"), "world").unwrap();
+        let err = verify_record(temp_dir.path(), &record).unwrap_err();
+        assert!(err.to_string().contains("Hash mismatch for `content.py`"));
+    }
+
     #[test]
     fn test_relative_to() {
         assert_eq!(
@@ -855,6 +991,23 @@ mod test {
         );
     }
 
+    #[test]
+    #[cfg(windows)]
+    fn test_script_executable() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let python = temp_dir.path().join("python.exe");
+        let pythonw = temp_dir.path().join("pythonw.exe");
+        fs_err::write(&python, "").unwrap();
+
+        // Without `pythonw.exe`, fall back to `python.exe`.
+        assert_eq!(get_script_executable(&python, true), python);
+
+        // GUI scripts should use `pythonw.exe`, if available.
+        fs_err::write(&pythonw, "").unwrap();
+        assert_eq!(get_script_executable(&python, true), pythonw);
+        assert_eq!(get_script_executable(&python, false), python);
+    }
+
     #[test]
     fn test_shebang() {
         // By default, use a simple shebang.
@@ -883,7 +1036,7 @@ mod test {
         let os_name = "posix";
         assert_eq!(format_shebang(executable, os_name), "#!/usr/bin/'python3'");
 
-        // If the path is too long, we should not use the `exec` trick.
+        // If the path is too long, we should use the `exec` trick.
         let executable = Path::new("/usr/bin/path/to/a/very/long/executable/executable/executable/executable/executable/executable/executable/executable/name/python3");
         let os_name = "posix";
         assert_eq!(format_shebang(executable, os_name), "#!/bin/sh\n'''exec' '/usr/bin/path/to/a/very/long/executable/executable/executable/executable/executable/executable/executable/executable/name/python3' \"$0\" \"$@\"\n' '''");
@@ -0,0 +1,157 @@
+//! Install small, pure-Python wheels as zip archives that are imported via `zipimport`, rather
+//! than linking each of their files into `site-packages`.
+//!
+//! The `.dist-info` directory is still installed as a directory (such that the distribution can be
+//! discovered and uninstalled), alongside a `{dist-info prefix}.zip` archive containing the
+//! remaining files, and a `{dist-info prefix}.pth` file that adds the archive to `sys.path`. Both
+//! are recorded in the `RECORD`, replacing the entries for the archived files.
+
+use std::io::Write;
+use std::path::Path;
+
+use fs_err as fs;
+use fs_err::File;
+use tracing::debug;
+use walkdir::WalkDir;
+use zip::write::FileOptions;
+use zip::ZipWriter;
+
+use pypi_types::RecordEntry;
+
+use crate::wheel::hash_files;
+use crate::Error;
+
+/// The maximum total size of the files in a wheel, in bytes, for it to be installed as a zip
+/// archive. Larger wheels gain little from avoiding extraction.
+const MAX_ZIPPED_SIZE: u64 = 1024 * 1024;
+
+/// Returns `true` if the unzipped wheel can be installed as a zip archive.
+///
+/// The wheel must consist of (a modest amount of) pure Python, without any extension modules
+/// (which can't be imported from a zip archive), data directory, or `.pth` files of its own.
+pub(crate) fn is_zippable(wheel: &Path, dist_info_prefix: &str) -> Result<bool, Error> {
+    if wheel.join(format!("{dist_info_prefix}.data")).exists() {
+        return Ok(false);
+    }
+
+    let dist_info = wheel.join(format!("{dist_info_prefix}.dist-info"));
+    let mut size = 0;
+    for entry in WalkDir::new(wheel)
+        .into_iter()
+        .filter_entry(|entry| entry.path() != dist_info)
+    {
+        let entry = entry.map_err(|err| Error::Io(err.into()))?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        if entry.path().extension().is_some_and(|ext| {
+            ["so", "pyd", "dylib", "dll", "pth"]
+                .iter()
+                .any(|unsupported| ext == *unsupported)
+        }) {
+            return Ok(false);
+        }
+        size += entry.metadata().map_err(|err| Error::Io(err.into()))?.len();
+        if size > MAX_ZIPPED_SIZE {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+/// Install the unzipped wheel into site packages as a zip archive, returning the number of files
+/// installed.
+pub(crate) fn zip_wheel_files(
+    site_packages: &Path,
+    wheel: &Path,
+    dist_info_prefix: &str,
+) -> Result<usize, Error> {
+    let dist_info = wheel.join(format!("{dist_info_prefix}.dist-info"));
+    let mut count = 0usize;
+
+    // Copy the `.dist-info` directory.
+    for entry in WalkDir::new(&dist_info) {
+        let entry = entry.map_err(|err| Error::Io(err.into()))?;
+        let relative = entry.path().strip_prefix(wheel).unwrap();
+        let out_path = site_packages.join(relative);
+        if entry.file_type().is_dir() {
+            fs::create_dir_all(&out_path)?;
+        } else {
+            fs::copy(entry.path(), &out_path)?;
+            count += 1;
+        }
+    }
+
+    // Archive everything else.
+    let archive = site_packages.join(format!("{dist_info_prefix}.zip"));
+    debug!("Writing zip archive: {}", archive.display());
+    let mut writer = ZipWriter::new(File::create(&archive)?);
+    let options = FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    for entry in WalkDir::new(wheel)
+        .min_depth(1)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| entry.path() != dist_info)
+    {
+        let entry = entry.map_err(|err| Error::Io(err.into()))?;
+        let relative = entry.path().strip_prefix(wheel).unwrap();
+        // Zip archives always use forward slashes.
+        let name = relative
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        if entry.file_type().is_dir() {
+            writer
+                .add_directory(name.as_str(), options)
+                .map_err(|err| Error::Zip(name, err))?;
+        } else {
+            writer
+                .start_file(name.as_str(), options)
+                .map_err(|err| Error::Zip(name, err))?;
+            std::io::copy(&mut File::open(entry.path())?, &mut writer)?;
+            count += 1;
+        }
+    }
+    writer
+        .finish()
+        .map_err(|err| Error::Zip(archive.display().to_string(), err))?;
+
+    // Add the archive to `sys.path`. Relative paths in `.pth` files are resolved against the
+    // `site-packages` directory.
+    let mut pth = File::create(site_packages.join(format!("{dist_info_prefix}.pth")))?;
+    writeln!(pth, "{dist_info_prefix}.zip")?;
+
+    Ok(count)
+}
+
+/// Replace the `RECORD` entries for the archived files with entries for the zip archive and the
+/// `.pth` file that imports it.
+pub(crate) fn zipped_record(
+    site_packages: &Path,
+    dist_info_prefix: &str,
+    record: &mut Vec<RecordEntry>,
+) -> Result<(), Error> {
+    let dist_info = format!("{dist_info_prefix}.dist-info/");
+    record.retain(|entry| entry.path.starts_with(&dist_info));
+
+    let files = [
+        format!("{dist_info_prefix}.zip"),
+        format!("{dist_info_prefix}.pth"),
+    ];
+    let paths = files
+        .iter()
+        .map(|file| site_packages.join(file))
+        .collect::<Vec<_>>();
+    for (file, result) in files.into_iter().zip(hash_files(&paths)) {
+        let (size, hash) = result?;
+        record.push(RecordEntry {
+            path: file,
+            hash: Some(hash),
+            size: Some(size),
+        });
+    }
+
+    Ok(())
+}
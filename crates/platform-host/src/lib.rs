@@ -3,6 +3,7 @@
 use std::{fmt, io};
 
 use platform_info::{PlatformInfo, PlatformInfoAPI, UNameAPI};
+use serde::Serialize;
 use thiserror::Error;
 
 use crate::linux::detect_linux_libc;
@@ -19,7 +20,7 @@ pub enum PlatformError {
     OsVersionDetectionError(String),
 }
 
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
 pub struct Platform {
     os: Os,
     arch: Arch,
@@ -50,7 +51,7 @@ impl Platform {
 }
 
 /// All supported operating systems.
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
 pub enum Os {
     Manylinux { major: u16, minor: u16 },
     Musllinux { major: u16, minor: u16 },
@@ -147,7 +148,7 @@ impl fmt::Display for Os {
 }
 
 /// All supported CPU architectures
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
 pub enum Arch {
     Aarch64,
     Armv7L,
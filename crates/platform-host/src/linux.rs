@@ -11,9 +11,32 @@ use regex::Regex;
 
 use crate::{Os, PlatformError};
 
+/// The environment variable used to override the detected glibc version, and thus the accepted
+/// `manylinux` tags, e.g., on systems where detection fails (like NixOS) or where the detected
+/// version is misleading (like older CentOS releases with backported symbols).
+const UV_MANYLINUX_COMPATIBLE: &str = "UV_MANYLINUX_COMPATIBLE";
+
 pub(crate) fn detect_linux_libc() -> Result<Os, PlatformError> {
+    if let Some(value) = std::env::var_os(UV_MANYLINUX_COMPATIBLE) {
+        let value = value.to_string_lossy();
+        let Some(os) = parse_manylinux_compatible(&value) else {
+            return Err(PlatformError::OsVersionDetectionError(format!(
+                "invalid `{UV_MANYLINUX_COMPATIBLE}` value: `{value}` (expected a glibc version, \
+                 like `2.17`, or a manylinux tag, like `manylinux_2_17` or `manylinux2014`)"
+            )));
+        };
+        tracing::debug!("Using glibc version from `{UV_MANYLINUX_COMPATIBLE}`: {value}");
+        return Ok(os);
+    }
+
     let ld_path = find_ld_path()?;
 
+    // If the dynamic linker is musl's (e.g., `/lib/ld-musl-x86_64.so.1` on Alpine), the system
+    // uses musl, so falling back to the glibc strategies below would misidentify it.
+    if is_musl_ld(&ld_path) {
+        return detect_musl(&ld_path);
+    }
+
     tracing::trace!("trying to detect musl version by running `{ld_path:?}`");
     match detect_musl_version(&ld_path) {
         Ok(os) => return Ok(os),
@@ -40,6 +63,88 @@ pub(crate) fn detect_linux_libc() -> Result<Os, PlatformError> {
     Err(PlatformError::OsVersionDetectionError(msg.to_string()))
 }
 
+/// Parse a `manylinux` compatibility override, as in `2.17`, `manylinux_2_17`, or `manylinux2014`.
+fn parse_manylinux_compatible(value: &str) -> Option<Os> {
+    let value = value.trim();
+    match value {
+        "manylinux1" => return Some(Os::Manylinux { major: 2, minor: 5 }),
+        "manylinux2010" => {
+            return Some(Os::Manylinux {
+                major: 2,
+                minor: 12,
+            })
+        }
+        "manylinux2014" => {
+            return Some(Os::Manylinux {
+                major: 2,
+                minor: 17,
+            })
+        }
+        _ => {}
+    }
+    let version = value.strip_prefix("manylinux_").unwrap_or(value);
+    let (major, minor) = version.split_once(['.', '_'])?;
+    Some(Os::Manylinux {
+        major: major.parse().ok()?,
+        minor: minor.parse().ok()?,
+    })
+}
+
+/// Returns `true` if the dynamic linker at the given path is musl's, as in
+/// `/lib/ld-musl-x86_64.so.1`.
+fn is_musl_ld(ld_path: &Path) -> bool {
+    ld_path
+        .file_name()
+        .is_some_and(|filename| filename.to_string_lossy().starts_with("ld-musl-"))
+}
+
+/// Detect the musl version on a system that's known to use musl.
+fn detect_musl(ld_path: &Path) -> Result<Os, PlatformError> {
+    tracing::trace!("trying to detect musl version by running `{ld_path:?}`");
+    match detect_musl_version(ld_path) {
+        Ok(os) => return Ok(os),
+        Err(err) => tracing::trace!("tried to find musl version, but failed: {err}"),
+    }
+    // On Alpine, `ldd` is a wrapper around the musl dynamic linker, so it reports the same
+    // version, even if the linker can't be invoked directly.
+    tracing::trace!("trying to run `ldd --version` to detect musl version");
+    match detect_musl_version_from_ldd() {
+        Ok(os) => return Ok(os),
+        Err(err) => {
+            tracing::trace!("tried to find musl version from `ldd --version`, but failed: {err}");
+        }
+    }
+    Err(PlatformError::OsVersionDetectionError(format!(
+        "found the musl dynamic linker at {ld_path:?}, but could not detect the musl version",
+    )))
+}
+
+fn detect_musl_version_from_ldd() -> Result<Os, PlatformError> {
+    let output = Command::new("ldd")
+        .args(["--version"])
+        .output()
+        .map_err(|err| {
+            PlatformError::OsVersionDetectionError(format!(
+                "failed to execute `ldd --version` for musl: {err}"
+            ))
+        })?;
+    match musl_ld_output_to_version("stdout", &output.stdout) {
+        Ok(os) => return Ok(os),
+        Err(err) => {
+            tracing::trace!("failed to parse musl version from stdout of `ldd --version`: {err}");
+        }
+    }
+    match musl_ld_output_to_version("stderr", &output.stderr) {
+        Ok(os) => return Ok(os),
+        Err(err) => {
+            tracing::trace!("failed to parse musl version from stderr of `ldd --version`: {err}");
+        }
+    }
+    Err(PlatformError::OsVersionDetectionError(
+        "could not find musl version from stdout or stderr of `ldd --version`".to_string(),
+    ))
+}
+
 // glibc version is taken from std/sys/unix/os.rs
 fn detect_glibc_version_from_ldd() -> Result<Os, PlatformError> {
     let output = Command::new("ldd")
@@ -290,4 +395,40 @@ Usage: /lib/ld-musl-x86_64.so.1 [options] [--] pathname [args]\
         let got = musl_ld_output_to_version("stderr", output).unwrap();
         assert_eq!(got, Os::Musllinux { major: 1, minor: 2 });
     }
+
+    #[test]
+    fn manylinux_compatible() {
+        assert_eq!(
+            parse_manylinux_compatible("2.17"),
+            Some(Os::Manylinux {
+                major: 2,
+                minor: 17
+            })
+        );
+        assert_eq!(
+            parse_manylinux_compatible("manylinux_2_28"),
+            Some(Os::Manylinux {
+                major: 2,
+                minor: 28
+            })
+        );
+        assert_eq!(
+            parse_manylinux_compatible("manylinux2014"),
+            Some(Os::Manylinux {
+                major: 2,
+                minor: 17
+            })
+        );
+        assert_eq!(parse_manylinux_compatible("2"), None);
+        assert_eq!(parse_manylinux_compatible("2.17.1"), None);
+        assert_eq!(parse_manylinux_compatible("musllinux_1_2"), None);
+    }
+
+    #[test]
+    fn musl_ld_path() {
+        assert!(is_musl_ld(Path::new("/lib/ld-musl-x86_64.so.1")));
+        assert!(is_musl_ld(Path::new("/lib/ld-musl-aarch64.so.1")));
+        assert!(!is_musl_ld(Path::new("/lib64/ld-linux-x86-64.so.2")));
+        assert!(!is_musl_ld(Path::new("/lib/ld-linux-aarch64.so.1")));
+    }
 }
@@ -1,3 +1,31 @@
+//! Wheel compatibility tags, as defined in the [platform compatibility tags specification].
+//!
+//! [`Tags`] holds the set of tags supported by a given interpreter and platform, ranked by
+//! priority. Given the tags of a wheel, [`Tags::compatibility`] determines whether the wheel can be
+//! installed and, if so, how preferable it is relative to other compatible wheels: when multiple
+//! wheels are compatible, uv selects the one with the highest [`TagPriority`].
+//!
+//! ```
+//! use platform_tags::{TagCompatibility, Tags};
+//!
+//! let tags = Tags::new(vec![
+//!     ("cp312".to_string(), "cp312".to_string(), "manylinux_2_17_x86_64".to_string()),
+//!     ("py3".to_string(), "none".to_string(), "any".to_string()),
+//! ]);
+//!
+//! let binary = tags.compatibility(
+//!     &["cp312".to_string()],
+//!     &["cp312".to_string()],
+//!     &["manylinux_2_17_x86_64".to_string()],
+//! );
+//! let pure = tags.compatibility(&["py3".to_string()], &["none".to_string()], &["any".to_string()]);
+//! assert!(binary.is_compatible() && pure.is_compatible());
+//! assert!(binary > pure);
+//! ```
+//!
+//! [platform compatibility tags specification]: https://packaging.python.org/en/latest/specifications/platform-compatibility-tags/
+
+use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 use std::sync::Arc;
 use std::{cmp, num::NonZeroU32};
@@ -18,17 +46,96 @@ pub enum TagsError {
     InvalidPriority(usize, #[source] std::num::TryFromIntError),
 }
 
+/// The reason a set of wheel tags is incompatible, ordered from the least to the most specific
+/// match (i.e., a wheel whose platform tag is incompatible matched on its Python and ABI tags).
 #[derive(Debug, Eq, Ord, PartialEq, PartialOrd, Clone)]
 pub enum IncompatibleTag {
+    /// The wheel has no tags.
     Invalid,
+    /// None of the wheel's Python tags are supported.
     Python,
+    /// None of the wheel's ABI tags are supported for its supported Python tags.
     Abi,
+    /// None of the wheel's platform tags are supported for its supported Python and ABI tags.
     Platform,
+    /// The wheel's platform tags require a newer C standard library than the target platform
+    /// provides (e.g., a `manylinux_2_28` wheel on a system with glibc 2.17).
+    Libc {
+        /// The oldest version of the C standard library supported by the wheel.
+        required: LibcVersion,
+        /// The version of the C standard library detected on the target platform.
+        detected: LibcVersion,
+    },
 }
 
+/// A version of the C standard library on Linux, as encoded in `manylinux` and `musllinux`
+/// platform tags.
+#[derive(Debug, Copy, Clone, Eq, Ord, PartialEq, PartialOrd)]
+pub enum LibcVersion {
+    /// glibc, as targeted by `manylinux` tags, with its major and minor version.
+    Glibc(u16, u16),
+    /// musl, as targeted by `musllinux` tags, with its major and minor version.
+    Musl(u16, u16),
+}
+
+impl LibcVersion {
+    /// Parse the C standard library version and architecture from a platform tag (e.g.,
+    /// `manylinux_2_28_x86_64` or `manylinux2014_aarch64`).
+    fn from_platform_tag(platform_tag: &str) -> Option<(Self, &str)> {
+        // Ex) `manylinux1_x86_64`, `manylinux2010_x86_64`, `manylinux2014_x86_64`
+        for (legacy, minor) in [
+            ("manylinux1_", 5),
+            ("manylinux2010_", 12),
+            ("manylinux2014_", 17),
+        ] {
+            if let Some(arch) = platform_tag.strip_prefix(legacy) {
+                return Some((Self::Glibc(2, minor), arch));
+            }
+        }
+
+        // Ex) `manylinux_2_28_x86_64` or `musllinux_1_2_aarch64`
+        let (rest, libc): (&str, fn(u16, u16) -> Self) =
+            if let Some(rest) = platform_tag.strip_prefix("manylinux_") {
+                (rest, Self::Glibc)
+            } else if let Some(rest) = platform_tag.strip_prefix("musllinux_") {
+                (rest, Self::Musl)
+            } else {
+                return None;
+            };
+        let mut parts = rest.splitn(3, '_');
+        let major = parts.next()?.parse().ok()?;
+        let minor = parts.next()?.parse().ok()?;
+        let arch = parts.next()?;
+        Some((libc(major, minor), arch))
+    }
+
+    /// Returns `true` if both versions refer to the same C standard library.
+    fn is_same_libc(self, other: Self) -> bool {
+        matches!(
+            (self, other),
+            (Self::Glibc(..), Self::Glibc(..)) | (Self::Musl(..), Self::Musl(..))
+        )
+    }
+}
+
+impl Display for LibcVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Glibc(major, minor) => write!(f, "glibc {major}.{minor}"),
+            Self::Musl(major, minor) => write!(f, "musl {major}.{minor}"),
+        }
+    }
+}
+
+/// The compatibility of a set of wheel tags with a [`Tags`] set.
+///
+/// Compatible tags are ordered by their [`TagPriority`], and always compare greater than
+/// incompatible tags, such that the "best" wheel among a set of candidates is the maximum.
 #[derive(Debug, PartialEq, Eq)]
 pub enum TagCompatibility {
+    /// The tags are incompatible, for the given reason.
     Incompatible(IncompatibleTag),
+    /// The tags are compatible, with the priority of the best-matching tag.
     Compatible(TagPriority),
 }
 
@@ -50,6 +157,7 @@ impl PartialOrd for TagCompatibility {
 }
 
 impl TagCompatibility {
+    /// Returns `true` if the tags are compatible.
     pub fn is_compatible(&self) -> bool {
         matches!(self, Self::Compatible(_))
     }
@@ -64,6 +172,8 @@ pub struct Tags {
     /// python_tag |--> abi_tag |--> platform_tag |--> priority
     #[allow(clippy::type_complexity)]
     map: Arc<FxHashMap<String, FxHashMap<String, FxHashMap<String, TagPriority>>>>,
+    /// The C standard library version and architecture of the target platform, if it's Linux.
+    libc: Option<(LibcVersion, String)>,
 }
 
 impl Tags {
@@ -81,7 +191,18 @@ impl Tags {
                 .entry(platform.to_string())
                 .or_insert(TagPriority::try_from(index).expect("valid tag priority"));
         }
-        Self { map: Arc::new(map) }
+        Self {
+            map: Arc::new(map),
+            libc: None,
+        }
+    }
+
+    /// Set the C standard library version and architecture of the target platform, used to explain
+    /// why wheels that target a newer C standard library are incompatible.
+    #[must_use]
+    pub fn with_libc(mut self, libc: LibcVersion, arch: impl Into<String>) -> Self {
+        self.libc = Some((libc, arch.into()));
+        self
     }
 
     /// Returns the compatible tags for the given Python implementation (e.g., `cpython`), version,
@@ -101,7 +222,7 @@ impl Tags {
         for platform_tag in &platform_tags {
             tags.push((
                 implementation.language_tag(python_version),
-                implementation.abi_tag(python_version, implementation_version),
+                implementation.abi_tag(python_version, implementation_version, platform),
                 platform_tag.clone(),
             ));
             tags.push((
@@ -154,7 +275,18 @@ impl Tags {
             "none".to_string(),
             "any".to_string(),
         ));
-        Ok(Self::new(tags))
+        let tags = Self::new(tags);
+        Ok(match platform.os() {
+            Os::Manylinux { major, minor } => tags.with_libc(
+                LibcVersion::Glibc(*major, *minor),
+                platform.arch().to_string(),
+            ),
+            Os::Musllinux { major, minor } => tags.with_libc(
+                LibcVersion::Musl(*major, *minor),
+                platform.arch().to_string(),
+            ),
+            _ => tags,
+        })
     }
 
     /// Returns true when there exists at least one tag for this platform
@@ -193,6 +325,40 @@ impl Tags {
         false
     }
 
+    /// Returns the [`TagPriority`] of a single tag triple, or `None` if the tag isn't supported.
+    pub fn priority(
+        &self,
+        python_tag: &str,
+        abi_tag: &str,
+        platform_tag: &str,
+    ) -> Option<TagPriority> {
+        self.map
+            .get(python_tag)?
+            .get(abi_tag)?
+            .get(platform_tag)
+            .copied()
+    }
+
+    /// Returns the supported tags, as `(python_tag, abi_tag, platform_tag)` triples, from the most
+    /// to the least preferred.
+    pub fn ranked(&self) -> Vec<(&str, &str, &str)> {
+        let mut tags = self
+            .map
+            .iter()
+            .flat_map(|(py, abis)| {
+                abis.iter().flat_map(move |(abi, platforms)| {
+                    platforms
+                        .iter()
+                        .map(move |(platform, priority)| (*priority, py, abi, platform))
+                })
+            })
+            .collect::<Vec<_>>();
+        tags.sort_unstable_by(|a, b| b.0.cmp(&a.0));
+        tags.into_iter()
+            .map(|(_, py, abi, platform)| (py.as_str(), abi.as_str(), platform.as_str()))
+            .collect()
+    }
+
     /// Returns the [`TagCompatibility`] of the given tags.
     ///
     /// If compatible, includes the score of the most-compatible platform tag.
@@ -229,6 +395,30 @@ impl Tags {
                 }
             }
         }
+
+        // If the wheel is only incompatible because it requires a newer C standard library than
+        // the target platform provides, say so.
+        if max_compatibility == TagCompatibility::Incompatible(IncompatibleTag::Platform) {
+            if let Some((detected, arch)) = &self.libc {
+                if let Some(required) = wheel_platform_tags
+                    .iter()
+                    .filter_map(|tag| LibcVersion::from_platform_tag(tag))
+                    .filter(|(required, wheel_arch)| {
+                        *wheel_arch == arch.as_str()
+                            && required.is_same_libc(*detected)
+                            && required > detected
+                    })
+                    .map(|(required, _)| required)
+                    .min()
+                {
+                    max_compatibility = TagCompatibility::Incompatible(IncompatibleTag::Libc {
+                        required,
+                        detected: *detected,
+                    });
+                }
+            }
+        }
+
         max_compatibility
     }
 }
@@ -239,6 +429,13 @@ impl Tags {
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub struct TagPriority(NonZeroU32);
 
+impl TagPriority {
+    /// Returns the priority as an integer, where higher values indicate higher priority.
+    pub fn get(self) -> u32 {
+        self.0.get()
+    }
+}
+
 impl TryFrom<usize> for TagPriority {
     type Error = TagsError;
 
@@ -256,6 +453,8 @@ impl TryFrom<usize> for TagPriority {
 pub enum Implementation {
     CPython,
     PyPy,
+    GraalPy,
+    Pyston,
 }
 
 impl Implementation {
@@ -267,10 +466,21 @@ impl Implementation {
             Self::CPython => format!("cp{}{}", python_version.0, python_version.1),
             // Ex) `pp39`
             Self::PyPy => format!("pp{}{}", python_version.0, python_version.1),
+            // Ex) `graalpy310`
+            Self::GraalPy => format!("graalpy{}{}", python_version.0, python_version.1),
+            // Ex) `pt38`
+            Self::Pyston => format!("pt{}{}", python_version.0, python_version.1),
         }
     }
 
-    pub fn abi_tag(&self, python_version: (u8, u8), implementation_version: (u8, u8)) -> String {
+    /// Returns the ABI tag for the current implementation, Python version, and implementation
+    /// version (e.g., `cp39`, `pypy39_pp73`, or `graalpy240_310_native`).
+    pub fn abi_tag(
+        &self,
+        python_version: (u8, u8),
+        implementation_version: (u8, u8),
+        platform: &Platform,
+    ) -> String {
         match self {
             // Ex) `cp39`
             Self::CPython => {
@@ -288,6 +498,21 @@ impl Implementation {
                 implementation_version.0,
                 implementation_version.1
             ),
+            // Ex) `graalpy240_310_native`
+            Self::GraalPy => format!(
+                "graalpy{}{}_{}{}_native",
+                implementation_version.0,
+                implementation_version.1,
+                python_version.0,
+                python_version.1
+            ),
+            // Ex) `pyston_23_x86_64_linux_gnu`
+            Self::Pyston => format!(
+                "pyston_{}{}_{}_linux_gnu",
+                implementation_version.0,
+                implementation_version.1,
+                platform.arch()
+            ),
         }
     }
 }
@@ -300,6 +525,8 @@ impl FromStr for Implementation {
             // Known and supported implementations.
             "cpython" => Ok(Self::CPython),
             "pypy" => Ok(Self::PyPy),
+            "graalpy" => Ok(Self::GraalPy),
+            "pyston" => Ok(Self::Pyston),
             // Known but unsupported implementations.
             "python" => Err(TagsError::UnsupportedImplementation(s.to_string())),
             "ironpython" => Err(TagsError::UnsupportedImplementation(s.to_string())),
@@ -341,9 +568,11 @@ fn compatible_tags(platform: &Platform) -> Result<Vec<String>, PlatformError> {
         }
         (Os::Musllinux { major, minor }, _) => {
             let mut platform_tags = vec![format!("linux_{}", arch)];
-            // musl 1.1 is the lowest supported version in musllinux
+            // Like `packaging`, accept every `musllinux` tag for the detected major version, down
+            // to `musllinux_{major}_0`.
+            // See: https://github.com/pypa/packaging/blob/fd4f11139d1c884a637be8aa26bb60a31fbc9411/packaging/_musllinux.py
             platform_tags
-                .extend((1..=*minor).map(|minor| format!("musllinux_{major}_{minor}_{arch}")));
+                .extend((0..=*minor).map(|minor| format!("musllinux_{major}_{minor}_{arch}")));
             platform_tags
         }
         (Os::Macos { major, minor }, Arch::X86_64) => {
@@ -484,3 +713,136 @@ fn get_mac_binary_formats(major: u16, minor: u16, arch: Arch) -> Vec<String> {
 
     formats
 }
+
+#[cfg(test)]
+mod tests {
+    use platform_host::{Arch, Os, Platform};
+
+    use super::{IncompatibleTag, LibcVersion, TagCompatibility, Tags};
+
+    #[test]
+    fn alternative_implementations() {
+        let platform = Platform::new(
+            Os::Manylinux {
+                major: 2,
+                minor: 28,
+            },
+            Arch::X86_64,
+        );
+
+        let tags = Tags::from_env(&platform, (3, 10), "graalpy", (24, 0)).unwrap();
+        assert!(tags
+            .priority(
+                "graalpy310",
+                "graalpy240_310_native",
+                "manylinux_2_17_x86_64"
+            )
+            .is_some());
+        assert!(tags
+            .priority("graalpy310", "none", "manylinux_2_17_x86_64")
+            .is_some());
+        assert!(tags
+            .priority("cp310", "abi3", "manylinux_2_17_x86_64")
+            .is_none());
+
+        let tags = Tags::from_env(&platform, (3, 8), "pyston", (2, 3)).unwrap();
+        assert!(tags
+            .priority(
+                "pt38",
+                "pyston_23_x86_64_linux_gnu",
+                "manylinux_2_17_x86_64"
+            )
+            .is_some());
+        assert!(tags.priority("py3", "none", "any").is_some());
+    }
+
+    #[test]
+    fn libc_incompatibility() {
+        let platform = Platform::new(
+            Os::Manylinux {
+                major: 2,
+                minor: 17,
+            },
+            Arch::X86_64,
+        );
+        let tags = Tags::from_env(&platform, (3, 12), "cpython", (3, 12)).unwrap();
+
+        // A wheel that requires a newer glibc.
+        assert_eq!(
+            tags.compatibility(
+                &["cp312".to_string()],
+                &["cp312".to_string()],
+                &[
+                    "manylinux_2_28_x86_64".to_string(),
+                    "manylinux_2_34_x86_64".to_string()
+                ],
+            ),
+            TagCompatibility::Incompatible(IncompatibleTag::Libc {
+                required: LibcVersion::Glibc(2, 28),
+                detected: LibcVersion::Glibc(2, 17),
+            })
+        );
+
+        // A wheel for a different architecture is merely incompatible with the platform.
+        assert_eq!(
+            tags.compatibility(
+                &["cp312".to_string()],
+                &["cp312".to_string()],
+                &["manylinux_2_28_aarch64".to_string()],
+            ),
+            TagCompatibility::Incompatible(IncompatibleTag::Platform)
+        );
+
+        // As is a wheel for musl.
+        assert_eq!(
+            tags.compatibility(
+                &["cp312".to_string()],
+                &["cp312".to_string()],
+                &["musllinux_1_2_x86_64".to_string()],
+            ),
+            TagCompatibility::Incompatible(IncompatibleTag::Platform)
+        );
+    }
+
+    #[test]
+    fn musllinux() {
+        let platform = Platform::new(Os::Musllinux { major: 1, minor: 2 }, Arch::X86_64);
+        let tags = Tags::from_env(&platform, (3, 12), "cpython", (3, 12)).unwrap();
+
+        // Wheels built for the same or an older musl are compatible.
+        for platform_tag in [
+            "musllinux_1_0_x86_64",
+            "musllinux_1_1_x86_64",
+            "musllinux_1_2_x86_64",
+            "linux_x86_64",
+        ] {
+            assert!(
+                tags.priority("cp312", "cp312", platform_tag).is_some(),
+                "{platform_tag}"
+            );
+        }
+
+        // Wheels that require a newer musl are not.
+        assert_eq!(
+            tags.compatibility(
+                &["cp312".to_string()],
+                &["cp312".to_string()],
+                &["musllinux_1_3_x86_64".to_string()],
+            ),
+            TagCompatibility::Incompatible(IncompatibleTag::Libc {
+                required: LibcVersion::Musl(1, 3),
+                detected: LibcVersion::Musl(1, 2),
+            })
+        );
+
+        // Nor are wheels built for glibc.
+        assert_eq!(
+            tags.compatibility(
+                &["cp312".to_string()],
+                &["cp312".to_string()],
+                &["manylinux_2_17_x86_64".to_string()],
+            ),
+            TagCompatibility::Incompatible(IncompatibleTag::Platform)
+        );
+    }
+}
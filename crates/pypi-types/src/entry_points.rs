@@ -0,0 +1,271 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use configparser::ini::Ini;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// The entry points declared by a distribution, as parsed from its `entry_points.txt`.
+///
+/// Entry points are organized into groups, like `console_scripts`, `gui_scripts`, or any
+/// plugin-specific group (e.g., `pytest11`).
+///
+/// See: <https://packaging.python.org/en/latest/specifications/entry-points/>
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EntryPoints(BTreeMap<String, Vec<EntryPoint>>);
+
+/// A single entry point, like `black = black:patched_main [d]`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EntryPoint {
+    /// The name of the entry point (e.g., `black`).
+    pub name: String,
+    /// The module to import (e.g., `black`).
+    pub module: String,
+    /// The object within the module, if any (e.g., `patched_main`). Attribute access is
+    /// represented with dots (e.g., `cli.main`).
+    pub object: Option<String>,
+    /// The extras required by the entry point, if any. Tools should generally ignore these.
+    pub extras: Vec<String>,
+}
+
+#[derive(Error, Debug)]
+pub enum EntryPointsError {
+    #[error("entry_points.txt is invalid: {0}")]
+    Ini(String),
+    #[error("Entry point `{name}` in group `{group}` must have a value")]
+    MissingValue { group: String, name: String },
+    #[error("Invalid object reference for entry point `{name}`: `{value}`")]
+    InvalidObjectReference { name: String, value: String },
+}
+
+impl EntryPoints {
+    /// Return the entry points in the given group, e.g., `console_scripts`.
+    pub fn group(&self, group: &str) -> &[EntryPoint] {
+        self.0.get(group).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Return the `console_scripts` entry points.
+    pub fn console_scripts(&self) -> &[EntryPoint] {
+        self.group("console_scripts")
+    }
+
+    /// Return the `gui_scripts` entry points.
+    pub fn gui_scripts(&self) -> &[EntryPoint] {
+        self.group("gui_scripts")
+    }
+
+    /// Return all groups, along with their entry points, in alphabetical order.
+    pub fn groups(&self) -> impl Iterator<Item = (&str, &[EntryPoint])> {
+        self.0
+            .iter()
+            .map(|(group, entry_points)| (group.as_str(), entry_points.as_slice()))
+    }
+
+    /// Return `true` if the distribution doesn't declare any entry points.
+    pub fn is_empty(&self) -> bool {
+        self.0.values().all(Vec::is_empty)
+    }
+}
+
+impl EntryPoints {
+    /// Parse the given groups from the contents of an `entry_points.txt` file, ignoring (and
+    /// skipping validation of) any other groups.
+    ///
+    /// For example, an installer only needs the `console_scripts` and `gui_scripts` groups, and
+    /// shouldn't reject a distribution due to a malformed plugin declaration.
+    pub fn from_groups(contents: &str, groups: &[&str]) -> Result<Self, EntryPointsError> {
+        Self::parse(contents, |group| groups.contains(&group))
+    }
+
+    /// Parse the contents of an `entry_points.txt` file, which uses the INI format (as read by
+    /// `configparser`), with case-sensitive names. Within each group, later definitions take
+    /// precedence, and entry points are ordered by name.
+    fn parse(contents: &str, include: impl Fn(&str) -> bool) -> Result<Self, EntryPointsError> {
+        let sections = Ini::new_cs()
+            .read(contents.to_string())
+            .map_err(EntryPointsError::Ini)?;
+
+        let mut groups = BTreeMap::new();
+        for (group, section) in sections {
+            if !include(&group) {
+                continue;
+            }
+            let mut entry_points = section
+                .iter()
+                .map(|(name, value)| {
+                    let value = value
+                        .as_deref()
+                        .ok_or_else(|| EntryPointsError::MissingValue {
+                            group: group.clone(),
+                            name: name.clone(),
+                        })?;
+                    EntryPoint::from_value(name, value)
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            entry_points.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+            groups.insert(group, entry_points);
+        }
+
+        Ok(Self(groups))
+    }
+}
+
+impl FromStr for EntryPoints {
+    type Err = EntryPointsError;
+
+    /// Parse the contents of an `entry_points.txt` file.
+    fn from_str(contents: &str) -> Result<Self, Self::Err> {
+        Self::parse(contents, |_| true)
+    }
+}
+
+impl EntryPoint {
+    /// Parse an entry point from its name and object reference, like `foo.bar:main [extra]`.
+    ///
+    /// Spaces are accepted around the colon and the extras, per the specification.
+    pub fn from_value(name: &str, value: &str) -> Result<Self, EntryPointsError> {
+        let invalid = || EntryPointsError::InvalidObjectReference {
+            name: name.to_string(),
+            value: value.to_string(),
+        };
+
+        let (reference, extras) = match value.split_once('[') {
+            Some((reference, extras)) => {
+                let extras = extras.trim_end().strip_suffix(']').ok_or_else(invalid)?;
+                let extras = extras
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|extra| !extra.is_empty())
+                    .map(ToString::to_string)
+                    .collect();
+                (reference, extras)
+            }
+            None => (value, Vec::new()),
+        };
+
+        let (module, object) = match reference.split_once(':') {
+            Some((module, object)) => (module.trim(), Some(object.trim())),
+            None => (reference.trim(), None),
+        };
+        // Like pip, accept dashes, which aren't valid in Python identifiers, but are in use.
+        let is_dotted_name = |name: &str| {
+            !name.is_empty()
+                && name.split('.').all(|part| {
+                    !part.is_empty()
+                        && part
+                            .chars()
+                            .all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+                })
+        };
+        if name.is_empty() || !is_dotted_name(module) || !object.map_or(true, is_dotted_name) {
+            return Err(invalid());
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            module: module.to_string(),
+            object: object.map(ToString::to_string),
+            extras,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use indoc::indoc;
+
+    use super::{EntryPoint, EntryPoints};
+
+    #[test]
+    fn parse() {
+        let entry_points = EntryPoints::from_str(indoc! {"
+            [console_scripts]
+            black = black:patched_main
+            blackd = blackd:patched_main [d]
+
+            # A comment.
+            [pytest11]
+            xdist.looponfail = xdist.looponfail
+            xdist = xdist.plugin : Plugin.hook [ a, b ]
+        "})
+        .unwrap();
+
+        assert_eq!(
+            entry_points.console_scripts(),
+            &[
+                EntryPoint {
+                    name: "black".to_string(),
+                    module: "black".to_string(),
+                    object: Some("patched_main".to_string()),
+                    extras: vec![],
+                },
+                EntryPoint {
+                    name: "blackd".to_string(),
+                    module: "blackd".to_string(),
+                    object: Some("patched_main".to_string()),
+                    extras: vec!["d".to_string()],
+                },
+            ]
+        );
+        assert!(entry_points.gui_scripts().is_empty());
+        assert_eq!(
+            entry_points.group("pytest11"),
+            &[
+                EntryPoint {
+                    name: "xdist".to_string(),
+                    module: "xdist.plugin".to_string(),
+                    object: Some("Plugin.hook".to_string()),
+                    extras: vec!["a".to_string(), "b".to_string()],
+                },
+                EntryPoint {
+                    name: "xdist.looponfail".to_string(),
+                    module: "xdist.looponfail".to_string(),
+                    object: None,
+                    extras: vec![],
+                },
+            ]
+        );
+        assert_eq!(
+            entry_points
+                .groups()
+                .map(|(group, _)| group)
+                .collect::<Vec<_>>(),
+            vec!["console_scripts", "pytest11"]
+        );
+    }
+
+    #[test]
+    fn from_groups() {
+        let contents = indoc! {"
+            [console_scripts]
+            black = black:patched_main
+
+            [pytest11]
+            broken = broken:
+        "};
+
+        // Only the requested groups are validated.
+        let entry_points = EntryPoints::from_groups(contents, &["console_scripts"]).unwrap();
+        assert_eq!(entry_points.console_scripts().len(), 1);
+        assert!(entry_points.group("pytest11").is_empty());
+
+        assert!(EntryPoints::from_str(contents).is_err());
+    }
+
+    #[test]
+    fn invalid() {
+        let err = EntryPoints::from_str("[console_scripts]\nblack\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Entry point `black` in group `console_scripts` must have a value"
+        );
+
+        let err = EntryPoints::from_str("[console_scripts]\nblack = black:\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid object reference for entry point `black`: `black:`"
+        );
+    }
+}
@@ -1,11 +1,15 @@
 pub use base_url::*;
 pub use direct_url::*;
+pub use entry_points::*;
 pub use lenient_requirement::*;
 pub use metadata::*;
+pub use record::*;
 pub use simple_json::*;
 
 mod base_url;
 mod direct_url;
+mod entry_points;
 mod lenient_requirement;
 mod metadata;
+mod record;
 mod simple_json;
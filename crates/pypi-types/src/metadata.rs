@@ -140,6 +140,102 @@ impl Metadata21 {
     }
 }
 
+/// The licensing information declared in a distribution's core metadata.
+///
+/// Unlike [`Metadata21`], this isn't used for dependency resolution, and so is parsed separately,
+/// on demand.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct LicenseMetadata {
+    /// The SPDX license expression from the `License-Expression` field (see PEP 639).
+    pub license_expression: Option<String>,
+    /// The free-form `License` field.
+    pub license: Option<String>,
+    /// The license names from any `License ::` classifiers, like `MIT License`.
+    pub classifiers: Vec<String>,
+}
+
+impl LicenseMetadata {
+    /// Parse the licensing information from metadata bytes.
+    pub fn parse(content: &[u8]) -> Result<Self, Error> {
+        let (headers, _) = mailparse::parse_headers(content)?;
+
+        let get_first_value = |name| {
+            headers.get_first_value(name).and_then(|value| {
+                let value = value.trim();
+                if value.is_empty() || value == "UNKNOWN" {
+                    None
+                } else {
+                    Some(value.to_string())
+                }
+            })
+        };
+
+        let license_expression = get_first_value("License-Expression");
+        let license = get_first_value("License");
+        let classifiers = headers
+            .get_all_values("Classifier")
+            .into_iter()
+            .filter(|classifier| classifier.starts_with("License ::"))
+            .filter_map(|classifier| {
+                classifier
+                    .rsplit(" :: ")
+                    .next()
+                    .map(|name| name.trim().to_string())
+            })
+            // `License :: OSI Approved` alone doesn't identify a license.
+            .filter(|name| name != "OSI Approved")
+            .collect();
+
+        Ok(Self {
+            license_expression,
+            license,
+            classifiers,
+        })
+    }
+
+    /// Returns an SPDX license expression for the distribution, if one can be determined.
+    ///
+    /// Uses the `License-Expression` field if present, and otherwise maps a single well-known
+    /// license classifier to its SPDX identifier.
+    pub fn spdx_expression(&self) -> Option<String> {
+        if let Some(expression) = &self.license_expression {
+            return Some(expression.clone());
+        }
+        let [classifier] = self.classifiers.as_slice() else {
+            return None;
+        };
+        let identifier = match classifier.as_str() {
+            "Apache Software License" => "Apache-2.0",
+            "BSD License" => "BSD-3-Clause",
+            "GNU General Public License v2 (GPLv2)" => "GPL-2.0-only",
+            "GNU General Public License v3 (GPLv3)" => "GPL-3.0-only",
+            "GNU Lesser General Public License v3 (LGPLv3)" => "LGPL-3.0-only",
+            "ISC License (ISCL)" => "ISC",
+            "MIT License" => "MIT",
+            "Mozilla Public License 2.0 (MPL 2.0)" => "MPL-2.0",
+            "Python Software Foundation License" => "PSF-2.0",
+            "The Unlicense (Unlicense)" => "Unlicense",
+            _ => return None,
+        };
+        Some(identifier.to_string())
+    }
+
+    /// Returns a human-readable description of the license, preferring the SPDX expression, then
+    /// the classifiers, then the free-form `License` field (if it fits on a single line).
+    pub fn description(&self) -> Option<String> {
+        if let Some(expression) = &self.license_expression {
+            return Some(expression.clone());
+        }
+        if !self.classifiers.is_empty() {
+            return Some(self.classifiers.join(", "));
+        }
+        self.license
+            .as_ref()
+            .filter(|license| !license.contains('\n'))
+            .cloned()
+    }
+}
+
 impl FromStr for Metadata21 {
     type Err = Error;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -156,7 +252,7 @@ mod tests {
 
     use crate::Error;
 
-    use super::Metadata21;
+    use super::{LicenseMetadata, Metadata21};
 
     #[test]
     fn test_parse_from_str() {
@@ -190,4 +286,25 @@ mod tests {
         let meta = Metadata21::parse(s.as_bytes());
         assert!(matches!(meta, Err(Error::InvalidName(_))));
     }
+
+    #[test]
+    fn test_parse_license() {
+        let s = "Metadata-Version: 2.1\nName: asdf\nVersion: 1.0\nLicense: UNKNOWN\nClassifier: License :: OSI Approved :: MIT License\nClassifier: Programming Language :: Python";
+        let license = LicenseMetadata::parse(s.as_bytes()).unwrap();
+        assert_eq!(license.license, None);
+        assert_eq!(license.classifiers, vec!["MIT License".to_string()]);
+        assert_eq!(license.spdx_expression().as_deref(), Some("MIT"));
+
+        let s = "Metadata-Version: 2.4\nName: asdf\nVersion: 1.0\nLicense-Expression: Apache-2.0 OR BSD-2-Clause\nClassifier: License :: OSI Approved :: MIT License";
+        let license = LicenseMetadata::parse(s.as_bytes()).unwrap();
+        assert_eq!(
+            license.spdx_expression().as_deref(),
+            Some("Apache-2.0 OR BSD-2-Clause")
+        );
+
+        let s = "Metadata-Version: 2.1\nName: asdf\nVersion: 1.0\nLicense: Proprietary";
+        let license = LicenseMetadata::parse(s.as_bytes()).unwrap();
+        assert_eq!(license.spdx_expression(), None);
+        assert_eq!(license.description().as_deref(), Some("Proprietary"));
+    }
 }
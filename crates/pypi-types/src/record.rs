@@ -0,0 +1,40 @@
+use std::io::Read;
+
+use serde::{Deserialize, Serialize};
+
+/// Line in a RECORD file
+/// <https://www.python.org/dev/peps/pep-0376/#record>
+///
+/// ```csv
+/// tqdm/cli.py,sha256=x_c8nmc4Huc-lKEsAXj78ZiyqSJ9hJ71j7vltY67icw,10509
+/// tqdm-4.62.3.dist-info/RECORD,,
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize, PartialOrd, PartialEq, Ord, Eq)]
+pub struct RecordEntry {
+    /// The path to the file, relative to the `site-packages` directory.
+    pub path: String,
+    /// The hash of the file, in the form `<algorithm>=<urlsafe-base64-digest>`, if recorded.
+    pub hash: Option<String>,
+    /// The size of the file in bytes, if recorded.
+    pub size: Option<u64>,
+}
+
+impl RecordEntry {
+    /// Read all entries from a RECORD file.
+    pub fn read_all(record: impl Read) -> Result<Vec<Self>, csv::Error> {
+        csv::ReaderBuilder::new()
+            .has_headers(false)
+            .escape(Some(b'"'))
+            .from_reader(record)
+            .deserialize()
+            .map(|entry| {
+                let entry: Self = entry?;
+                Ok(Self {
+                    // selenium uses absolute paths for some reason
+                    path: entry.path.trim_start_matches('/').to_string(),
+                    ..entry
+                })
+            })
+            .collect()
+    }
+}
@@ -13,6 +13,27 @@ pub struct SimpleJson {
     /// The list of [`File`]s available for download sorted by filename.
     #[serde(deserialize_with = "sorted_simple_json_files")]
     pub files: Vec<File>,
+    /// The response metadata, including the version of the API.
+    #[serde(default)]
+    pub meta: SimpleJsonMeta,
+}
+
+/// The `meta` key of a PEP 691 response.
+///
+/// <https://peps.python.org/pep-0691/#project-detail>
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct SimpleJsonMeta {
+    /// The version of the API, as described in PEP 629 (e.g., `1.0`).
+    pub api_version: Option<String>,
+}
+
+impl SimpleJsonMeta {
+    /// Return the major and minor components of the API version, if present and well-formed.
+    pub fn api_version(&self) -> Option<(u64, u64)> {
+        let (major, minor) = self.api_version.as_deref()?.split_once('.')?;
+        Some((major.parse().ok()?, minor.parse().ok()?))
+    }
 }
 
 /// Deserializes a sequence of "simple" files from `PyPI` and ensures that they
@@ -35,17 +56,14 @@ fn sorted_simple_json_files<'de, D: Deserializer<'de>>(d: D) -> Result<Vec<File>
 ///
 /// <https://peps.python.org/pep-0691/#project-detail>
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "kebab-case")]
+#[serde(from = "RawFile")]
 pub struct File {
-    // Non-PEP 691-compliant alias used by PyPI.
-    #[serde(alias = "data-dist-info-metadata")]
     pub dist_info_metadata: Option<DistInfoMetadata>,
     pub filename: String,
     pub hashes: Hashes,
     /// There are a number of invalid specifiers on pypi, so we first try to parse it into a [`VersionSpecifiers`]
     /// according to spec (PEP 440), then a [`LenientVersionSpecifiers`] with fixup for some common problems and if this
     /// still fails, we skip the file when creating a version map.
-    #[serde(default, deserialize_with = "deserialize_version_specifiers_lenient")]
     pub requires_python: Option<Result<VersionSpecifiers, VersionSpecifiersParseError>>,
     pub size: Option<u64>,
     pub upload_time: Option<DateTime<Utc>>,
@@ -53,6 +71,41 @@ pub struct File {
     pub yanked: Option<Yanked>,
 }
 
+/// The wire format of a [`File`], which may provide the metadata availability under several keys.
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct RawFile {
+    /// The PEP 714 key.
+    core_metadata: Option<DistInfoMetadata>,
+    // Non-PEP 691-compliant alias used by PyPI.
+    #[serde(alias = "data-dist-info-metadata")]
+    dist_info_metadata: Option<DistInfoMetadata>,
+    filename: String,
+    hashes: Hashes,
+    #[serde(default, deserialize_with = "deserialize_version_specifiers_lenient")]
+    requires_python: Option<Result<VersionSpecifiers, VersionSpecifiersParseError>>,
+    size: Option<u64>,
+    upload_time: Option<DateTime<Utc>>,
+    url: String,
+    yanked: Option<Yanked>,
+}
+
+impl From<RawFile> for File {
+    fn from(file: RawFile) -> Self {
+        Self {
+            // Prefer the PEP 714 key, as indexes may stop serving the legacy key.
+            dist_info_metadata: file.core_metadata.or(file.dist_info_metadata),
+            filename: file.filename,
+            hashes: file.hashes,
+            requires_python: file.requires_python,
+            size: file.size,
+            upload_time: file.upload_time,
+            url: file.url,
+            yanked: file.yanked,
+        }
+    }
+}
+
 fn deserialize_version_specifiers_lenient<'de, D>(
     deserializer: D,
 ) -> Result<Option<Result<VersionSpecifiers, VersionSpecifiersParseError>>, D::Error>
@@ -4,25 +4,29 @@
 //!
 //! Supported:
 //!  * [PEP 508 requirements](https://packaging.python.org/en/latest/specifications/dependency-specifiers/)
-//!  * `-r`
-//!  * `-c`
+//!  * `-r`, resolved relative to the including file and recursively
+//!  * `-c`, resolved relative to the including file and recursively
 //!  * `--hash` (postfix)
 //!  * `-e`
+//!  * `--index-url`, `--extra-index-url`, `--find-links`, and `--no-index`
+//!  * `--no-binary` and `--only-binary`
+//!  * `${ENV_VAR}` expansion in paths and URLs
 //!
 //! Unsupported:
 //!  * `<path>`. TBD
 //!  * `<archive_url>`. TBD
-//!  * Options without a requirement, such as `--find-links` or `--index-url`
 //!
 //! Grammar as implemented:
 //!
 //! ```text
 //! file = (statement | empty ('#' any*)? '\n')*
 //! empty = whitespace*
-//! statement = constraint_include | requirements_include | editable_requirement | requirement
+//! statement = constraint_include | requirements_include | editable_requirement | global_option | requirement
 //! constraint_include = '-c' ('=' | wrappable_whitespaces) filepath
 //! requirements_include = '-r' ('=' | wrappable_whitespaces) filepath
 //! editable_requirement = '-e' ('=' | wrappable_whitespaces) requirement
+//! global_option = ('--index-url' | '--extra-index-url' | '--find-links' | '--no-binary' | '--only-binary') ('=' | wrappable_whitespaces) value
+//!     | '--no-index'
 //! # We check whether the line starts with a letter or a number, in that case we assume it's a
 //! # PEP 508 requirement
 //! # https://packaging.python.org/en/latest/specifications/name-normalization/#valid-non-normalized-names
@@ -38,6 +42,7 @@ use std::borrow::Cow;
 use std::fmt::{Display, Formatter};
 use std::io;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
 use serde::{Deserialize, Serialize};
 use tracing::{instrument, warn};
@@ -47,10 +52,10 @@ use uv_warnings::warn_user;
 
 use pep508_rs::{
     expand_path_vars, split_scheme, Extras, Pep508Error, Pep508ErrorSource, Requirement, Scheme,
-    VerbatimUrl,
+    VerbatimUrl, VersionOrUrl,
 };
 use uv_fs::{normalize_url_path, Simplified};
-use uv_normalize::ExtraName;
+use uv_normalize::{ExtraName, PackageName};
 
 /// We emit one of those for each requirements.txt entry
 enum RequirementsTxtStatement {
@@ -70,6 +75,8 @@ enum RequirementsTxtStatement {
     RequirementEntry(RequirementEntry),
     /// `-e`
     EditableRequirement(EditableRequirement),
+    /// A local path whose package name can't be determined without building it
+    UnnamedRequirement(UnnamedRequirement),
     /// `--index-url`
     IndexUrl(VerbatimUrl),
     /// `--extra-index-url`
@@ -78,6 +85,10 @@ enum RequirementsTxtStatement {
     FindLinks(FindLink),
     /// `--no-index`
     NoIndex,
+    /// `--no-binary`
+    NoBinary(Vec<String>),
+    /// `--only-binary`
+    OnlyBinary(Vec<String>),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -104,6 +115,9 @@ impl FindLink {
                     // Transform, e.g., `/C:/Users/ferris/wheel-0.42.0.tar.gz` to `C:\Users\ferris\wheel-0.42.0.tar.gz`.
                     let path = normalize_url_path(path);
 
+                    // Expand environment variables, e.g., `${PROJECT_ROOT}/wheels`.
+                    let path = expand_path_vars(path.as_ref());
+
                     let path = PathBuf::from(path.as_ref());
                     let path = if path.is_absolute() {
                         path
@@ -121,7 +135,7 @@ impl FindLink {
 
                 // Ex) `C:/Users/ferris/wheel-0.42.0.tar.gz`
                 _ => {
-                    let path = PathBuf::from(given);
+                    let path = PathBuf::from(expand_path_vars(given).as_ref());
                     let path = if path.is_absolute() {
                         path
                     } else {
@@ -132,7 +146,7 @@ impl FindLink {
             }
         } else {
             // Ex) `../ferris/`
-            let path = PathBuf::from(given);
+            let path = PathBuf::from(expand_path_vars(given).as_ref());
             let path = if path.is_absolute() {
                 path
             } else {
@@ -268,6 +282,200 @@ impl EditableRequirement {
     }
 }
 
+/// A local path requirement that omits the package name (e.g., `./path/to/project`,
+/// `../wheel-0.42.0-py3-none-any.whl`, or `file:///home/ferris/project`).
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct UnnamedRequirement {
+    pub url: VerbatimUrl,
+    pub extras: Vec<ExtraName>,
+    pub path: PathBuf,
+}
+
+impl UnnamedRequirement {
+    /// Parse a local path requirement that omits the package name.
+    ///
+    /// Returns `None` if the requirement doesn't look like a path or `file://` URL, or doesn't
+    /// refer to an existing local path.
+    pub fn parse(given: &str, working_dir: impl AsRef<Path>) -> Option<Self> {
+        if !is_path_like(given) {
+            return None;
+        }
+
+        // Reuse the editable parser, which handles `file://` URLs, relative paths, and extras.
+        let EditableRequirement { url, extras, path } =
+            EditableRequirement::parse(given, working_dir).ok()?;
+        if !path.exists() {
+            return None;
+        }
+
+        Some(Self { url, extras, path })
+    }
+
+    /// Convert the requirement into a PEP 508 direct reference with the given package name.
+    pub fn into_requirement(self, name: PackageName) -> Requirement {
+        Requirement {
+            name,
+            extras: self.extras,
+            version_or_url: Some(VersionOrUrl::Url(self.url)),
+            marker: None,
+        }
+    }
+}
+
+impl Display for UnnamedRequirement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.url, f)
+    }
+}
+
+/// Returns `true` if the requirement looks like a local path or `file://` URL, as opposed to a
+/// PEP 508 requirement (which may itself contain a path, as in `flask @ ../flask`).
+fn is_path_like(given: &str) -> bool {
+    // Ex) `./project`, `../project`, `/home/ferris/project`, `~/project`, or `file:../project`
+    if given.starts_with(['.', '/', '~']) || given.starts_with("file:") {
+        return true;
+    }
+
+    // Ex) `C:\Users\ferris\project` or `\\server\share\project`
+    if given.starts_with("\\\\")
+        || matches!(given.as_bytes(), [drive, b':', b'/' | b'\\', ..] if drive.is_ascii_alphabetic())
+    {
+        return true;
+    }
+
+    // Ex) `path/to/project`, but not `flask @ https://...` or `flask; sys_platform == "win32"`.
+    let head = given
+        .split(|c: char| c.is_whitespace() || matches!(c, ';' | '@'))
+        .next()
+        .unwrap_or_default();
+    !head.contains("://") && head.contains(['/', '\\'])
+}
+
+/// Rewrite a URL requirement that uses a legacy `#egg=` fragment to specify the package name (e.g.,
+/// `git+https://github.com/pallets/flask.git#egg=flask[dotenv]`) as a PEP 508 direct reference
+/// (e.g., `flask[dotenv] @ git+https://github.com/pallets/flask.git`), retaining any other
+/// fragments (e.g., `subdirectory=`) and trailing markers.
+///
+/// Relative `file:` URLs (e.g., `file:../flask#egg=flask`) are retained as-is, such that they're
+/// resolved against the working directory and written back out in their original form.
+///
+/// Returns `None` if the requirement isn't a URL, or doesn't contain a valid `#egg=` fragment.
+fn normalize_egg_url(given: &str) -> Option<String> {
+    let (url, rest) = given
+        .find(char::is_whitespace)
+        .map_or((given, ""), |index| given.split_at(index));
+    if !url.contains("://") && !url.starts_with("file:") {
+        return None;
+    }
+
+    let (location, fragment) = url.split_once('#')?;
+    let mut name = None;
+    let fragment = fragment
+        .split('&')
+        .filter(|part| {
+            if let Some(egg) = part.strip_prefix("egg=") {
+                name = Some(egg);
+                false
+            } else {
+                true
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("&");
+
+    // Ex) `#egg=flask[dotenv,async]`
+    let (name, extras) = match name?.split_once('[') {
+        Some((name, extras)) => (name, extras.strip_suffix(']')?),
+        None => (name?, ""),
+    };
+    let name = PackageName::from_str(name).ok()?;
+    let extras = extras
+        .split(',')
+        .map(str::trim)
+        .filter(|extra| !extra.is_empty())
+        .map(|extra| ExtraName::from_str(extra).ok())
+        .collect::<Option<Vec<_>>>()?;
+    let name = if extras.is_empty() {
+        name.to_string()
+    } else {
+        format!(
+            "{name}[{}]",
+            extras
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(",")
+        )
+    };
+
+    if fragment.is_empty() {
+        Some(format!("{name} @ {location}{rest}"))
+    } else {
+        Some(format!("{name} @ {location}#{fragment}{rest}"))
+    }
+}
+
+/// Infer the package name of a local wheel, source distribution, or project directory, from the
+/// distribution filename or the static project metadata.
+///
+/// Returns `None` if the package name can't be determined without building the project.
+pub fn infer_package_name(path: &Path) -> Option<PackageName> {
+    if path.is_file() {
+        let filename = path.file_name()?.to_str()?;
+
+        // Ex) `black-23.11.0-py3-none-any.whl`
+        if let Some(stem) = filename.strip_suffix(".whl") {
+            return PackageName::from_str(stem.split('-').next()?).ok();
+        }
+
+        // Ex) `black-23.11.0.tar.gz`
+        let stem = [".tar.gz", ".tar.bz2", ".tar.xz", ".tgz", ".zip"]
+            .iter()
+            .find_map(|extension| filename.strip_suffix(extension))?;
+        let (name, _version) = stem.rsplit_once('-')?;
+        return PackageName::from_str(name).ok();
+    }
+
+    // Ex) A `pyproject.toml` with a `[project]` table.
+    if let Ok(contents) = uv_fs::read_to_string(path.join("pyproject.toml")) {
+        if let Some(name) = contents
+            .parse::<toml::Table>()
+            .ok()
+            .as_ref()
+            .and_then(|table| table.get("project"))
+            .and_then(|project| project.get("name"))
+            .and_then(toml::Value::as_str)
+        {
+            return PackageName::from_str(name).ok();
+        }
+    }
+
+    // Ex) An unpacked source distribution, with a `PKG-INFO` file.
+    if let Ok(contents) = uv_fs::read_to_string(path.join("PKG-INFO")) {
+        if let Some(name) = contents.lines().find_map(|line| line.strip_prefix("Name:")) {
+            return PackageName::from_str(name.trim()).ok();
+        }
+    }
+
+    // Ex) A `setup.cfg` with a `[metadata]` section.
+    if let Ok(contents) = uv_fs::read_to_string(path.join("setup.cfg")) {
+        let mut in_metadata = false;
+        for line in contents.lines().map(str::trim) {
+            if line.starts_with('[') {
+                in_metadata = line == "[metadata]";
+            } else if in_metadata {
+                if let Some((key, value)) = line.split_once('=') {
+                    if key.trim() == "name" {
+                        return PackageName::from_str(value.trim()).ok();
+                    }
+                }
+            }
+        }
+    }
+
+    None
+}
+
 impl Display for EditableRequirement {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         Display::fmt(&self.url, f)
@@ -284,6 +492,8 @@ pub struct RequirementEntry {
     pub hashes: Vec<String>,
     /// Editable installation, see e.g. <https://stackoverflow.com/q/35064426/3549270>
     pub editable: bool,
+    /// The file and line at which the requirement was declared
+    pub origin: RequirementOrigin,
 }
 
 impl Display for RequirementEntry {
@@ -299,6 +509,23 @@ impl Display for RequirementEntry {
     }
 }
 
+/// The location of a requirement within a requirements.txt, used to point users at the offending
+/// line when requirements conflict.
+#[derive(Debug, Deserialize, Clone, Eq, PartialEq, Serialize)]
+pub struct RequirementOrigin {
+    /// The requirements.txt in which the requirement was declared, which may be a file included
+    /// via `-r`
+    pub path: PathBuf,
+    /// The 1-indexed line at which the requirement starts
+    pub line: usize,
+}
+
+impl Display for RequirementOrigin {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.path.simplified_display(), self.line)
+    }
+}
+
 /// Parsed and flattened requirements.txt with requirements and constraints
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
 pub struct RequirementsTxt {
@@ -308,6 +535,8 @@ pub struct RequirementsTxt {
     pub constraints: Vec<Requirement>,
     /// Editables with `-e`.
     pub editables: Vec<EditableRequirement>,
+    /// Local paths whose package name can't be determined without building them.
+    pub unnamed_requirements: Vec<UnnamedRequirement>,
     /// The index URL, specified with `--index-url`.
     pub index_url: Option<VerbatimUrl>,
     /// The extra index URLs, specified with `--extra-index-url`.
@@ -316,6 +545,12 @@ pub struct RequirementsTxt {
     pub find_links: Vec<FindLink>,
     /// Whether to ignore the index, specified with `--no-index`.
     pub no_index: bool,
+    /// The packages for which wheels should not be used, specified with `--no-binary`. Each entry
+    /// is a package name, `:all:`, or `:none:`.
+    pub no_binary: Vec<String>,
+    /// The packages for which source distributions should not be used, specified with
+    /// `--only-binary`. Each entry is a package name, `:all:`, or `:none:`.
+    pub only_binary: Vec<String>,
 }
 
 impl RequirementsTxt {
@@ -333,11 +568,15 @@ impl RequirementsTxt {
 
         let working_dir = working_dir.as_ref();
         let requirements_dir = requirements_txt.as_ref().parent().unwrap_or(working_dir);
-        let data = Self::parse_inner(&content, working_dir, requirements_dir).map_err(|err| {
-            RequirementsTxtFileError {
-                file: requirements_txt.as_ref().to_path_buf(),
-                error: err,
-            }
+        let data = Self::parse_inner(
+            &content,
+            requirements_txt.as_ref(),
+            working_dir,
+            requirements_dir,
+        )
+        .map_err(|err| RequirementsTxtFileError {
+            file: requirements_txt.as_ref().to_path_buf(),
+            error: err,
         })?;
         if data == Self::default() {
             warn_user!(
@@ -355,15 +594,18 @@ impl RequirementsTxt {
     /// the current working directory. However, relative paths to sub-files (e.g., `-r ../requirements.txt`)
     /// are resolved against the directory of the containing `requirements.txt` file, to match
     /// `pip`'s behavior.
+    ///
+    /// The `requirements_txt` path is only used to record the origin of each requirement.
     pub fn parse_inner(
         content: &str,
+        requirements_txt: &Path,
         working_dir: &Path,
         requirements_dir: &Path,
     ) -> Result<Self, RequirementsTxtParserError> {
         let mut s = Scanner::new(content);
 
         let mut data = Self::default();
-        while let Some(statement) = parse_entry(&mut s, content, working_dir)? {
+        while let Some(statement) = parse_entry(&mut s, content, requirements_txt, working_dir)? {
             match statement {
                 RequirementsTxtStatement::Requirements {
                     filename,
@@ -378,6 +620,15 @@ impl RequirementsTxt {
                             end,
                         }
                     })?;
+                    // Only one index URL may be provided across all included files.
+                    if data.index_url.is_some() && sub_requirements.index_url.is_some() {
+                        let (line, column) = calculate_row_column(content, start);
+                        return Err(RequirementsTxtParserError::Parser {
+                            message: "Multiple `--index-url` values provided".to_string(),
+                            line,
+                            column,
+                        });
+                    }
                     // Add each to the correct category
                     data.update_from(sub_requirements);
                 }
@@ -411,6 +662,9 @@ impl RequirementsTxt {
                 RequirementsTxtStatement::EditableRequirement(editable) => {
                     data.editables.push(editable);
                 }
+                RequirementsTxtStatement::UnnamedRequirement(unnamed) => {
+                    data.unnamed_requirements.push(unnamed);
+                }
                 RequirementsTxtStatement::IndexUrl(url) => {
                     if data.index_url.is_some() {
                         let (line, column) = calculate_row_column(content, s.cursor());
@@ -431,6 +685,12 @@ impl RequirementsTxt {
                 RequirementsTxtStatement::NoIndex => {
                     data.no_index = true;
                 }
+                RequirementsTxtStatement::NoBinary(specifiers) => {
+                    data.no_binary.extend(specifiers);
+                }
+                RequirementsTxtStatement::OnlyBinary(specifiers) => {
+                    data.only_binary.extend(specifiers);
+                }
             }
         }
         Ok(data)
@@ -440,6 +700,16 @@ impl RequirementsTxt {
     pub fn update_from(&mut self, other: Self) {
         self.requirements.extend(other.requirements);
         self.constraints.extend(other.constraints);
+        self.editables.extend(other.editables);
+        self.unnamed_requirements.extend(other.unnamed_requirements);
+        if other.index_url.is_some() {
+            self.index_url = other.index_url;
+        }
+        self.extra_index_urls.extend(other.extra_index_urls);
+        self.find_links.extend(other.find_links);
+        self.no_index |= other.no_index;
+        self.no_binary.extend(other.no_binary);
+        self.only_binary.extend(other.only_binary);
     }
 }
 
@@ -450,6 +720,7 @@ impl RequirementsTxt {
 fn parse_entry(
     s: &mut Scanner,
     content: &str,
+    requirements_txt: &Path,
     working_dir: &Path,
 ) -> Result<Option<RequirementsTxtStatement>, RequirementsTxtParserError> {
     // Eat all preceding whitespace, this may run us to the end of file
@@ -508,6 +779,14 @@ fn parse_entry(
         RequirementsTxtStatement::ExtraIndexUrl(url)
     } else if s.eat_if("--no-index") {
         RequirementsTxtStatement::NoIndex
+    } else if s.eat_if("--no-binary") {
+        let given = parse_value(content, s, |c: char| !['\n', '\r', '#'].contains(&c))?;
+        eat_trailing_line(content, s)?;
+        RequirementsTxtStatement::NoBinary(parse_package_specifiers(given))
+    } else if s.eat_if("--only-binary") {
+        let given = parse_value(content, s, |c: char| !['\n', '\r', '#'].contains(&c))?;
+        eat_trailing_line(content, s)?;
+        RequirementsTxtStatement::OnlyBinary(parse_package_specifiers(given))
     } else if s.eat_if("--find-links") || s.eat_if("-f") {
         let path_or_url = parse_value(content, s, |c: char| !['\n', '\r'].contains(&c))?;
         let path_or_url = FindLink::parse(path_or_url, working_dir).map_err(|err| {
@@ -519,13 +798,24 @@ fn parse_entry(
             }
         })?;
         RequirementsTxtStatement::FindLinks(path_or_url)
-    } else if s.at(char::is_ascii_alphanumeric) {
-        let (requirement, hashes) = parse_requirement_and_hashes(s, content, working_dir)?;
-        RequirementsTxtStatement::RequirementEntry(RequirementEntry {
-            requirement,
-            hashes,
-            editable: false,
-        })
+    } else if s.at(char::is_ascii_alphanumeric) || s.at(['.', '/', '~', '\\']) {
+        let (line, _) = calculate_row_column(content, start);
+        match parse_requirement_and_hashes(s, content, working_dir)? {
+            (ParsedRequirement::Named(requirement), hashes) => {
+                RequirementsTxtStatement::RequirementEntry(RequirementEntry {
+                    requirement,
+                    hashes,
+                    editable: false,
+                    origin: RequirementOrigin {
+                        path: requirements_txt.to_path_buf(),
+                        line,
+                    },
+                })
+            }
+            (ParsedRequirement::Unnamed(unnamed), _) => {
+                RequirementsTxtStatement::UnnamedRequirement(unnamed)
+            }
+        }
     } else if let Some(char) = s.peek() {
         let (line, column) = calculate_row_column(content, s.cursor());
         return Err(RequirementsTxtParserError::Parser {
@@ -579,12 +869,18 @@ fn eat_trailing_line(content: &str, s: &mut Scanner) -> Result<(), RequirementsT
     Ok(())
 }
 
+/// A requirement parsed from a requirements file, which may omit the package name.
+enum ParsedRequirement {
+    Named(Requirement),
+    Unnamed(UnnamedRequirement),
+}
+
 /// Parse a PEP 508 requirement with optional trailing hashes
 fn parse_requirement_and_hashes(
     s: &mut Scanner,
     content: &str,
     working_dir: &Path,
-) -> Result<(Requirement, Vec<String>), RequirementsTxtParserError> {
+) -> Result<(ParsedRequirement, Vec<String>), RequirementsTxtParserError> {
     // PEP 508 requirement
     let start = s.cursor();
     // Termination: s.eat() eventually becomes None
@@ -640,24 +936,34 @@ fn parse_requirement_and_hashes(
         }
     }
 
-    // If the requirement looks like an editable requirement (with a missing `-e`), raise an
-    // error.
-    //
-    // Slashes are not allowed in package names, so these would be rejected in the next step anyway.
-    if requirement.contains('/') || requirement.contains('\\') {
-        let path = Path::new(requirement);
-        let path = if path.is_absolute() {
-            Cow::Borrowed(path)
+    // If the requirement is a local path without a package name (e.g., `./path/to/project`),
+    // infer the name from the distribution filename or project metadata. If the name can only be
+    // determined by building the project, leave that to the caller.
+    if let Some(unnamed) = UnnamedRequirement::parse(requirement, working_dir) {
+        let hashes = if has_hashes {
+            let hashes = parse_hashes(content, s)?;
+            eat_trailing_line(content, s)?;
+            hashes
         } else {
-            Cow::Owned(working_dir.join(path))
+            Vec::new()
         };
-        if path.is_dir() {
-            return Err(RequirementsTxtParserError::MissingEditablePrefix(
-                requirement.to_string(),
-            ));
-        }
+        let requirement = match infer_package_name(&unnamed.path) {
+            Some(name) => ParsedRequirement::Named(unnamed.into_requirement(name)),
+            None => ParsedRequirement::Unnamed(unnamed),
+        };
+        return Ok((requirement, hashes));
     }
 
+    // If the requirement is a URL with a legacy `#egg=` fragment, rewrite it as a direct reference
+    // (e.g., `git+https://...#egg=flask` to `flask @ git+https://...`).
+    let normalized = normalize_egg_url(requirement);
+    let requirement = if let Some(normalized) = normalized.as_deref() {
+        warn_user!("Interpreting legacy `#egg=` requirement `{requirement}` as `{normalized}`");
+        normalized
+    } else {
+        requirement
+    };
+
     let requirement =
         Requirement::parse(requirement, working_dir).map_err(|err| match err.message {
             Pep508ErrorSource::String(_) | Pep508ErrorSource::UrlError(_) => {
@@ -682,7 +988,7 @@ fn parse_requirement_and_hashes(
     } else {
         Vec::new()
     };
-    Ok((requirement, hashes))
+    Ok((ParsedRequirement::Named(requirement), hashes))
 }
 
 /// Parse `--hash=... --hash ...` after a requirement
@@ -712,6 +1018,17 @@ fn parse_hashes(content: &str, s: &mut Scanner) -> Result<Vec<String>, Requireme
     Ok(hashes)
 }
 
+/// Split a comma-separated list of package specifiers, like `numpy,scipy` or `:all:`, as provided
+/// to `--no-binary` or `--only-binary`.
+fn parse_package_specifiers(given: &str) -> Vec<String> {
+    given
+        .split(',')
+        .map(str::trim)
+        .filter(|specifier| !specifier.is_empty())
+        .map(ToString::to_string)
+        .collect()
+}
+
 /// In `-<key>=<value>` or `-<key> value`, this parses the part after the key
 fn parse_value<'a, T>(
     content: &str,
@@ -755,7 +1072,6 @@ pub enum RequirementsTxtParserError {
     InvalidEditablePath(String),
     UnsupportedUrl(String),
     MissingRequirementPrefix(String),
-    MissingEditablePrefix(String),
     Parser {
         message: String,
         line: usize,
@@ -798,7 +1114,6 @@ impl RequirementsTxtParserError {
             },
             Self::UnsupportedUrl(url) => Self::UnsupportedUrl(url),
             Self::MissingRequirementPrefix(given) => Self::MissingRequirementPrefix(given),
-            Self::MissingEditablePrefix(given) => Self::MissingEditablePrefix(given),
             Self::Parser {
                 message,
                 line,
@@ -843,12 +1158,6 @@ impl Display for RequirementsTxtParserError {
             Self::MissingRequirementPrefix(given) => {
                 write!(f, "Requirement `{given}` looks like a requirements file but was passed as a package name. Did you mean `-r {given}`?")
             }
-            Self::MissingEditablePrefix(given) => {
-                write!(
-                    f,
-                    "Requirement `{given}` looks like a directory but was passed as a package name. Did you mean `-e {given}`?"
-                )
-            }
             Self::Parser {
                 message,
                 line,
@@ -877,7 +1186,6 @@ impl std::error::Error for RequirementsTxtParserError {
             Self::InvalidEditablePath(_) => None,
             Self::UnsupportedUrl(_) => None,
             Self::MissingRequirementPrefix(_) => None,
-            Self::MissingEditablePrefix(_) => None,
             Self::UnsupportedRequirement { source, .. } => Some(source),
             Self::Pep508 { source, .. } => Some(source),
             Self::Subfile { source, .. } => Some(source.as_ref()),
@@ -918,13 +1226,6 @@ impl Display for RequirementsTxtFileError {
                     self.file.simplified_display(),
                 )
             }
-            RequirementsTxtParserError::MissingEditablePrefix(given) => {
-                write!(
-                    f,
-                    "Requirement `{given}` in `{}` looks like a directory but was passed as a package name. Did you mean `-e {given}`?",
-                    self.file.simplified_display(),
-                )
-            }
             RequirementsTxtParserError::Parser {
                 message,
                 line,
@@ -1022,13 +1323,13 @@ mod test {
     use fs_err as fs;
     use indoc::indoc;
     use itertools::Itertools;
+    use pep508_rs::VersionOrUrl;
     use tempfile::tempdir;
     use test_case::test_case;
     use unscanny::Scanner;
     use uv_fs::Simplified;
 
-    use crate::{calculate_row_column, EditableRequirement, RequirementsTxt};
-
+    use crate::{calculate_row_column, is_path_like, EditableRequirement, RequirementsTxt};
     fn workspace_test_data_dir() -> PathBuf {
         PathBuf::from("./test-data")
     }
@@ -1050,7 +1351,11 @@ mod test {
         let actual = RequirementsTxt::parse(requirements_txt, &working_dir).unwrap();
 
         let snapshot = format!("parse-{}", path.to_string_lossy());
-        insta::assert_debug_snapshot!(snapshot, actual);
+        insta::with_settings!({
+            filters => vec![(r"\\\\", "/")],
+        }, {
+            insta::assert_debug_snapshot!(snapshot, actual);
+        });
     }
 
     #[test_case(Path::new("basic.txt"))]
@@ -1091,7 +1396,13 @@ mod test {
         let actual = RequirementsTxt::parse(&requirements_txt, &working_dir).unwrap();
 
         let snapshot = format!("line-endings-{}", path.to_string_lossy());
-        insta::assert_debug_snapshot!(snapshot, actual);
+        let temp_dir = regex::escape(&temp_dir.path().simplified_display().to_string());
+        let filters = vec![(temp_dir.as_str(), "<TEMP_DIR>"), (r"\\\\", "/")];
+        insta::with_settings!({
+            filters => filters,
+        }, {
+            insta::assert_debug_snapshot!(snapshot, actual);
+        });
     }
 
     #[test]
@@ -1187,6 +1498,59 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn egg_fragment() -> Result<()> {
+        let temp_dir = assert_fs::TempDir::new()?;
+        let requirements_txt = temp_dir.child("requirements.txt");
+        requirements_txt.write_str(indoc! {r#"
+            git+https://github.com/pallets/flask.git@3.0.0#egg=flask
+            https://github.com/foo-labs/foo/archive/master.zip#egg=foo&subdirectory=packages/foo ; python_version >= "3.8"
+            git+https://github.com/pallets/flask.git@3.0.0#egg=flask[dotenv,async]
+        "#})?;
+
+        let requirements_txt = RequirementsTxt::parse(requirements_txt.path(), temp_dir.path())?;
+        let requirements = requirements_txt
+            .requirements
+            .iter()
+            .map(|entry| entry.requirement.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            requirements,
+            vec![
+                "flask @ git+https://github.com/pallets/flask.git@3.0.0",
+                "foo @ https://github.com/foo-labs/foo/archive/master.zip#subdirectory=packages/foo ; python_version >= '3.8'",
+                "flask[dotenv,async] @ git+https://github.com/pallets/flask.git@3.0.0",
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn egg_fragment_relative_file() -> Result<()> {
+        let temp_dir = assert_fs::TempDir::new()?;
+        let requirements_txt = temp_dir.child("requirements.txt");
+        requirements_txt.write_str("file:../foo#egg=foo")?;
+
+        let requirements_txt = RequirementsTxt::parse(requirements_txt.path(), temp_dir.path())?;
+        let [entry] = requirements_txt.requirements.as_slice() else {
+            panic!("Expected a single requirement");
+        };
+        assert_eq!(entry.requirement.name.as_ref(), "foo");
+
+        // The relative URL is resolved against the working directory, but retained verbatim.
+        let Some(VersionOrUrl::Url(url)) = entry.requirement.version_or_url.as_ref() else {
+            panic!("Expected a URL requirement");
+        };
+        assert_eq!(url.given(), Some("file:../foo"));
+        assert_eq!(
+            url.to_file_path().ok(),
+            Some(temp_dir.path().parent().unwrap().join("foo"))
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn invalid_editable_extra() -> Result<()> {
         let temp_dir = assert_fs::TempDir::new()?;
@@ -1299,30 +1663,132 @@ mod test {
         "})?;
 
         let requirements = RequirementsTxt::parse(parent_txt.path(), temp_dir.path()).unwrap();
-        insta::assert_debug_snapshot!(requirements, @r###"
-        RequirementsTxt {
-            requirements: [
-                RequirementEntry {
-                    requirement: Requirement {
-                        name: PackageName(
-                            "flask",
-                        ),
-                        extras: [],
-                        version_or_url: None,
-                        marker: None,
+        let temp_dir = regex::escape(&temp_dir.path().simplified_display().to_string());
+        let filters = vec![(temp_dir.as_str(), "<TEMP_DIR>"), (r"\\\\", "/")];
+        insta::with_settings!({
+            filters => filters,
+        }, {
+            insta::assert_debug_snapshot!(requirements, @r###"
+            RequirementsTxt {
+                requirements: [
+                    RequirementEntry {
+                        requirement: Requirement {
+                            name: PackageName(
+                                "flask",
+                            ),
+                            extras: [],
+                            version_or_url: None,
+                            marker: None,
+                        },
+                        hashes: [],
+                        editable: false,
+                        origin: RequirementOrigin {
+                            path: "<TEMP_DIR>/subdir/sibling.txt",
+                            line: 1,
+                        },
                     },
-                    hashes: [],
-                    editable: false,
-                },
-            ],
-            constraints: [],
-            editables: [],
-            index_url: None,
-            extra_index_urls: [],
-            find_links: [],
-            no_index: false,
-        }
-        "###);
+                ],
+                constraints: [],
+                editables: [],
+                unnamed_requirements: [],
+                index_url: None,
+                extra_index_urls: [],
+                find_links: [],
+                no_index: false,
+                no_binary: [],
+                only_binary: [],
+            }
+            "###);
+        });
+
+        Ok(())
+    }
+
+    #[test]
+    fn nested_include_options() -> Result<()> {
+        let temp_dir = assert_fs::TempDir::new()?;
+
+        let child_txt = temp_dir.child("subdir").child("child.txt");
+        child_txt.write_str(indoc! {"
+            --index-url https://test.pypi.org/simple
+            --find-links ../wheels
+            --no-binary flask
+            -e ../editable
+        "})?;
+
+        let parent_txt = temp_dir.child("parent.txt");
+        parent_txt.write_str(indoc! {"
+            --extra-index-url https://download.pytorch.org/whl/cpu
+            -r subdir/child.txt
+        "})?;
+
+        let requirements = RequirementsTxt::parse(parent_txt.path(), temp_dir.path()).unwrap();
+        assert_eq!(
+            requirements.index_url.map(|url| url.to_string()),
+            Some("https://test.pypi.org/simple".to_string())
+        );
+        assert_eq!(requirements.extra_index_urls.len(), 1);
+        assert_eq!(
+            requirements.find_links,
+            vec![FindLink::Path(temp_dir.path().join("../wheels"))]
+        );
+        assert_eq!(requirements.no_binary, vec!["flask".to_string()]);
+        assert_eq!(requirements.editables.len(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn nested_include_index_url_conflict() -> Result<()> {
+        let temp_dir = assert_fs::TempDir::new()?;
+
+        let child_txt = temp_dir.child("child.txt");
+        child_txt.write_str(indoc! {"
+            --index-url https://test.pypi.org/simple
+        "})?;
+
+        let parent_txt = temp_dir.child("parent.txt");
+        parent_txt.write_str(indoc! {"
+            --index-url https://pypi.org/simple
+            -r child.txt
+        "})?;
+
+        let error = RequirementsTxt::parse(parent_txt.path(), temp_dir.path()).unwrap_err();
+        let errors = anyhow::Error::new(error).chain().join("\n");
+
+        let requirement_txt = regex::escape(&parent_txt.path().simplified_display().to_string());
+        let filters = vec![(requirement_txt.as_str(), "<REQUIREMENTS_TXT>")];
+        insta::with_settings!({
+            filters => filters
+        }, {
+            insta::assert_display_snapshot!(errors, @"Multiple `--index-url` values provided at <REQUIREMENTS_TXT>:2:1");
+        });
+
+        Ok(())
+    }
+
+    #[test]
+    fn binary_options() -> Result<()> {
+        let temp_dir = assert_fs::TempDir::new()?;
+        let requirements_txt = temp_dir.child("requirements.txt");
+        requirements_txt.write_str(indoc! {"
+            --no-binary :all:  # Build everything from source...
+            --only-binary numpy,scipy
+            --no-binary=flask
+            flask
+        "})?;
+
+        let requirements =
+            RequirementsTxt::parse(requirements_txt.path(), temp_dir.path()).unwrap();
+        assert_eq!(
+            requirements.no_binary,
+            vec![":all:".to_string(), "flask".to_string()]
+        );
+        assert_eq!(
+            requirements.only_binary,
+            vec!["numpy".to_string(), "scipy".to_string()]
+        );
+        assert_eq!(requirements.requirements.len(), 1);
 
         Ok(())
     }
@@ -1377,6 +1843,96 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn unnamed_requirements() -> Result<()> {
+        let temp_dir = assert_fs::TempDir::new()?;
+        temp_dir
+            .child("project/pyproject.toml")
+            .write_str(indoc! {r#"
+            [project]
+            name = "Example_Project"
+            version = "0.1.0"
+        "#})?;
+        temp_dir
+            .child("legacy/setup.cfg")
+            .write_str("[metadata]\nname = legacy-project\n")?;
+        temp_dir.child("unknown/setup.py").write_str("")?;
+        temp_dir
+            .child("dist/black-23.11.0-py3-none-any.whl")
+            .write_str("")?;
+        temp_dir.child("dist/tqdm-4.66.1.tar.gz").write_str("")?;
+
+        let requirements_txt = temp_dir.child("requirements.txt");
+        requirements_txt.write_str(indoc! {"
+            ./project[dev]
+            ./legacy
+            ./dist/black-23.11.0-py3-none-any.whl
+            ./dist/tqdm-4.66.1.tar.gz
+        "})?;
+
+        let requirements_txt = RequirementsTxt::parse(requirements_txt.path(), temp_dir.path())?;
+        let names = requirements_txt
+            .requirements
+            .iter()
+            .map(|entry| {
+                format!(
+                    "{}{:?}",
+                    entry.requirement.name,
+                    entry
+                        .requirement
+                        .extras
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>()
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            [
+                "example-project[\"dev\"]",
+                "legacy-project[]",
+                "black[]",
+                "tqdm[]"
+            ]
+        );
+
+        // A directory whose name can't be determined statically is left for the caller to build.
+        let requirements_txt = temp_dir.child("unknown.txt");
+        requirements_txt.write_str("./unknown")?;
+        let requirements_txt = RequirementsTxt::parse(requirements_txt.path(), temp_dir.path())?;
+        assert!(requirements_txt.requirements.is_empty());
+        assert_eq!(requirements_txt.unnamed_requirements.len(), 1);
+        assert_eq!(
+            requirements_txt.unnamed_requirements[0].path,
+            temp_dir.path().join("unknown")
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn path_like() {
+        assert!(is_path_like("./project"));
+        assert!(is_path_like("../project[dev]"));
+        assert!(is_path_like("/home/ferris/project"));
+        assert!(is_path_like("file:///home/ferris/project"));
+        assert!(is_path_like("path/to/project"));
+        assert!(is_path_like(r"C:\Users\ferris\project"));
+        assert!(is_path_like(r"\\server\share\project"));
+
+        assert!(!is_path_like("flask"));
+        assert!(!is_path_like("flask>=2.0"));
+        assert!(!is_path_like("flask @ ../flask"));
+        assert!(!is_path_like(
+            "flask @ https://github.com/pallets/flask/archive/3.0.0.zip"
+        ));
+        assert!(!is_path_like(
+            "https://github.com/pallets/flask/archive/3.0.0.zip"
+        ));
+        assert!(!is_path_like(r#"flask; sys_platform == "linux/amd64""#));
+    }
+
     #[test_case("numpy>=1,<2\n  @-borken\ntqdm", "2:4"; "ASCII Character with LF")]
     #[test_case("numpy>=1,<2\r\n  #-borken\ntqdm", "2:4"; "ASCII Character with CRLF")]
     #[test_case("numpy>=1,<2\n  \n-borken\ntqdm", "3:1"; "ASCII Character LF then LF")]
@@ -24,14 +24,17 @@ use serde::{de, Deserialize, Deserializer, Serialize};
 use tempfile::{tempdir_in, TempDir};
 use thiserror::Error;
 use tokio::process::Command;
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, Semaphore};
 use tracing::{debug, info_span, instrument, Instrument};
 
 use distribution_types::Resolution;
 use pep508_rs::Requirement;
 use uv_fs::Simplified;
 use uv_interpreter::{Interpreter, PythonEnvironment};
-use uv_traits::{BuildContext, BuildKind, ConfigSettings, SetupPyStrategy, SourceBuildTrait};
+use uv_traits::{
+    BuildContext, BuildIsolation, BuildKind, Concurrency, ConfigSettings, SetupPyStrategy,
+    SourceBuildTrait,
+};
 
 /// e.g. `pygraphviz/graphviz_wrap.c:3020:10: fatal error: graphviz/cgraph.h: No such file or directory`
 static MISSING_HEADER_RE: Lazy<Regex> = Lazy::new(|| {
@@ -298,11 +301,29 @@ impl Pep517Backend {
 }
 
 /// Uses an [`Arc`] internally, clone freely
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Clone)]
 pub struct SourceBuildContext {
     /// Cache the first resolution of `pip`, `setuptools` and `wheel` we made for setup.py (and
     /// some PEP 517) builds so we can reuse it.
     setup_py_resolution: Arc<Mutex<Option<Resolution>>>,
+    /// Limits the number of build backend invocations that run concurrently.
+    build_semaphore: Arc<Semaphore>,
+}
+
+impl SourceBuildContext {
+    /// Create a context that runs at most `concurrent_builds` build backend invocations at once.
+    pub fn new(concurrent_builds: usize) -> Self {
+        Self {
+            setup_py_resolution: Arc::default(),
+            build_semaphore: Arc::new(Semaphore::new(concurrent_builds)),
+        }
+    }
+}
+
+impl Default for SourceBuildContext {
+    fn default() -> Self {
+        Self::new(Concurrency::threads())
+    }
 }
 
 /// Holds the state through a series of PEP 517 frontend to backend calls or a single setup.py
@@ -336,6 +357,8 @@ pub struct SourceBuild {
     modified_path: OsString,
     /// Environment variables to be passed in during metadata or wheel building
     environment_variables: FxHashMap<OsString, OsString>,
+    /// Limits the number of build backend invocations that run concurrently, shared across builds.
+    build_semaphore: Arc<Semaphore>,
 }
 
 impl SourceBuild {
@@ -357,6 +380,7 @@ impl SourceBuild {
         mut environment_variables: FxHashMap<OsString, OsString>,
     ) -> Result<Self, Error> {
         let temp_dir = tempdir_in(build_context.cache().root())?;
+        let build_semaphore = source_build_context.build_semaphore.clone();
 
         let metadata = match fs::metadata(source) {
             Ok(metadata) => metadata,
@@ -392,33 +416,62 @@ impl SourceBuild {
             source_root
         };
 
-        let default_backend: Pep517Backend = DEFAULT_BACKEND.clone();
+        let mut default_backend: Pep517Backend = DEFAULT_BACKEND.clone();
 
         // Check if we have a PEP 517 build backend.
-        let pep517_backend = Self::get_pep517_backend(setup_py, &source_tree, &default_backend)
+        let mut pep517_backend = Self::get_pep517_backend(setup_py, &source_tree, &default_backend)
             .map_err(|err| *err)?;
 
-        let venv = uv_virtualenv::create_venv(
-            &temp_dir.path().join(".venv"),
-            interpreter.clone(),
-            uv_virtualenv::Prompt::None,
-            false,
-            Vec::new(),
-        )?;
-
-        // Setup the build environment.
-        let resolved_requirements = Self::get_resolved_requirements(
-            build_context,
-            source_build_context,
-            &default_backend,
-            pep517_backend.as_ref(),
-        )
-        .await?;
+        // Install any additional build requirements provided by the user alongside those of the
+        // backend. (They're the same for every build, so the cached resolution of the default
+        // backend remains valid.)
+        let extra_build_requires = build_context.extra_build_requires();
+        if !extra_build_requires.is_empty() {
+            for backend in iter::once(&mut default_backend).chain(pep517_backend.as_mut()) {
+                backend
+                    .requirements
+                    .extend(extra_build_requires.iter().cloned());
+            }
+        }
 
-        build_context
-            .install(&resolved_requirements, &venv)
-            .await
-            .map_err(|err| Error::RequirementsInstall("build-system.requires (install)", err))?;
+        let venv = match build_context.build_isolation() {
+            BuildIsolation::Isolated => {
+                let venv = uv_virtualenv::create_venv(
+                    &temp_dir.path().join(".venv"),
+                    interpreter.clone(),
+                    uv_virtualenv::Prompt::None,
+                    false,
+                    Vec::new(),
+                )?;
+
+                // Setup the build environment.
+                let resolved_requirements = Self::get_resolved_requirements(
+                    build_context,
+                    source_build_context,
+                    &default_backend,
+                    pep517_backend.as_ref(),
+                )
+                .await?;
+
+                build_context
+                    .install(&resolved_requirements, &venv)
+                    .await
+                    .map_err(|err| {
+                        Error::RequirementsInstall("build-system.requires (install)", err)
+                    })?;
+
+                venv
+            }
+            // Without build isolation, the build dependencies are assumed to already be present in
+            // the shared environment, so we don't install anything.
+            BuildIsolation::Shared(venv) => {
+                debug!(
+                    "Building without isolation in: {}",
+                    venv.root().simplified_display()
+                );
+                venv.clone()
+            }
+        };
 
         // Figure out what the modified path should be
         // Remove the PATH variable from the environment variables if it's there
@@ -450,8 +503,14 @@ impl SourceBuild {
             OsString::from(venv.scripts())
         };
 
-        if let Some(pep517_backend) = &pep517_backend {
+        // Install any extra requirements requested by the build backend (only necessary when
+        // building in isolation).
+        if let Some(pep517_backend) = pep517_backend
+            .as_ref()
+            .filter(|_| build_context.build_isolation().is_isolated())
+        {
             create_pep517_build_environment(
+                &build_semaphore,
                 &source_tree,
                 &venv,
                 pep517_backend,
@@ -476,6 +535,7 @@ impl SourceBuild {
             package_id,
             environment_variables,
             modified_path,
+            build_semaphore,
         })
     }
 
@@ -617,6 +677,7 @@ impl SourceBuild {
             python_version = %self.venv.interpreter().python_version()
         );
         let output = run_python_script(
+            &self.build_semaphore,
             &self.venv,
             &script,
             &self.source_tree,
@@ -687,6 +748,7 @@ impl SourceBuild {
                 script="setup.py bdist_wheel",
                 python_version = %self.venv.interpreter().python_version()
             );
+            let _permit = self.build_semaphore.acquire().await;
             let output = Command::new(python_interpreter)
                 .args(["setup.py", "bdist_wheel"])
                 .current_dir(self.source_tree.simplified())
@@ -755,6 +817,7 @@ impl SourceBuild {
             python_version = %self.venv.interpreter().python_version()
         );
         let output = run_python_script(
+            &self.build_semaphore,
             &self.venv,
             &script,
             &self.source_tree,
@@ -810,6 +873,7 @@ fn escape_path_for_python(path: &Path) -> String {
 /// Not a method because we call it before the builder is completely initialized
 #[allow(clippy::too_many_arguments)]
 async fn create_pep517_build_environment(
+    build_semaphore: &Semaphore,
     source_tree: &Path,
     venv: &PythonEnvironment,
     pep517_backend: &Pep517Backend,
@@ -843,6 +907,7 @@ async fn create_pep517_build_environment(
         python_version = %venv.interpreter().python_version()
     );
     let output = run_python_script(
+        build_semaphore,
         venv,
         &script,
         source_tree,
@@ -907,13 +972,18 @@ async fn create_pep517_build_environment(
 }
 
 /// It is the caller's responsibility to create an informative span.
+///
+/// Waits for a permit from the `build_semaphore` before running the script.
 async fn run_python_script(
+    build_semaphore: &Semaphore,
     venv: &PythonEnvironment,
     script: &str,
     source_tree: &Path,
     environment_variables: &FxHashMap<OsString, OsString>,
     modified_path: &OsString,
 ) -> Result<Output, Error> {
+    // The semaphore is never closed, so acquiring a permit can't fail.
+    let _permit = build_semaphore.acquire().await;
     Command::new(venv.python_executable())
         .args(["-c", script])
         .current_dir(source_tree.simplified())
@@ -0,0 +1,30 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::timestamp::Timestamp;
+
+/// A fingerprint used to detect changes to a file without reading it, consisting of its
+/// [`Timestamp`] and size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub struct FileInfo {
+    pub timestamp: Timestamp,
+    pub size: u64,
+}
+
+impl FileInfo {
+    /// Return the [`FileInfo`] for the given path.
+    pub fn from_path(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let metadata = path.as_ref().metadata()?;
+        Ok(Self {
+            timestamp: Timestamp::from_metadata(&metadata),
+            size: metadata.len(),
+        })
+    }
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct CachedByFileInfo<Data> {
+    pub file_info: FileInfo,
+    pub data: Data,
+}
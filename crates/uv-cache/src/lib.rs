@@ -8,10 +8,12 @@ use std::sync::Arc;
 
 use fs_err as fs;
 use tempfile::{tempdir, TempDir};
+use tracing::debug;
 
-use uv_fs::directories;
+use uv_fs::{directories, LockedFile, Simplified};
 use uv_normalize::PackageName;
 
+pub use crate::by_file_info::{CachedByFileInfo, FileInfo};
 pub use crate::by_timestamp::CachedByTimestamp;
 #[cfg(feature = "clap")]
 pub use crate::cli::CacheArgs;
@@ -20,6 +22,7 @@ pub use crate::timestamp::Timestamp;
 pub use crate::wheel::WheelCache;
 use crate::wheel::WheelCacheKind;
 
+mod by_file_info;
 mod by_timestamp;
 #[cfg(feature = "clap")]
 mod cli;
@@ -138,6 +141,11 @@ impl Cache {
         Self { refresh, ..self }
     }
 
+    /// Return the [`Refresh`] policy for the cache.
+    pub fn refresh(&self) -> &Refresh {
+        &self.refresh
+    }
+
     /// Return the root of the cache.
     pub fn root(&self) -> &Path {
         &self.root
@@ -148,6 +156,29 @@ impl Cache {
         self.root.join(cache_bucket.to_str())
     }
 
+    /// Acquire a shared lock on a cache bucket, to prevent it from being cleared (e.g., by
+    /// `uv cache clean`) while its entries are in use by the current process.
+    pub fn lock_bucket_shared(&self, cache_bucket: CacheBucket) -> io::Result<LockedFile> {
+        LockedFile::acquire_shared(
+            self.bucket_lock_path(cache_bucket),
+            self.bucket(cache_bucket).simplified_display(),
+        )
+    }
+
+    /// Acquire an exclusive lock on a cache bucket, waiting for any other processes that are using
+    /// its entries to finish.
+    pub fn lock_bucket(&self, cache_bucket: CacheBucket) -> io::Result<LockedFile> {
+        LockedFile::acquire(
+            self.bucket_lock_path(cache_bucket),
+            self.bucket(cache_bucket).simplified_display(),
+        )
+    }
+
+    /// The path to the lock file for a cache bucket, alongside the bucket in the cache root.
+    fn bucket_lock_path(&self, cache_bucket: CacheBucket) -> PathBuf {
+        self.root.join(format!("{}.lock", cache_bucket.to_str()))
+    }
+
     /// Compute an entry in the cache.
     pub fn shard(&self, cache_bucket: CacheBucket, dir: impl AsRef<Path>) -> CacheShard {
         CacheShard(self.bucket(cache_bucket).join(dir.as_ref()))
@@ -226,6 +257,16 @@ impl Cache {
         Ok(archive_entry.into_path_buf())
     }
 
+    /// Return the path to the marker that indicates that the files in a persisted unzipped wheel
+    /// were verified against its `RECORD` when it was unzipped.
+    ///
+    /// The marker lives alongside the directory in the artifact store, rather than within it, such
+    /// that it isn't installed along with the wheel. The given path may be a symlink to the
+    /// artifact store (as in the wheel buckets).
+    pub fn record_marker(archive: &Path) -> io::Result<PathBuf> {
+        Ok(fs::canonicalize(archive)?.with_extension("record"))
+    }
+
     /// Initialize a directory for use as a cache.
     fn init(root: impl Into<PathBuf>) -> Result<PathBuf, io::Error> {
         let root = root.into();
@@ -261,7 +302,49 @@ impl Cache {
 
     /// Clear the cache, removing all entries.
     pub fn clear(&self) -> Result<Removal, io::Error> {
-        rm_rf(&self.root)
+        if !self.root.exists() {
+            return Ok(Removal::default());
+        }
+
+        let buckets = [
+            CacheBucket::Wheels,
+            CacheBucket::BuiltWheels,
+            CacheBucket::FlatIndex,
+            CacheBucket::Git,
+            CacheBucket::Interpreter,
+            CacheBucket::Simple,
+            CacheBucket::Resolutions,
+            CacheBucket::Downloads,
+            CacheBucket::Archive,
+        ];
+        let locks = buckets
+            .into_iter()
+            .map(|bucket| self.lock_bucket(bucket))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Remove everything but the lock files, which must remain in place while they're held.
+        let lock_paths = buckets
+            .into_iter()
+            .map(|bucket| self.bucket_lock_path(bucket))
+            .collect::<Vec<_>>();
+        let mut removal = Removal::default();
+        for entry in fs::read_dir(&self.root)? {
+            let path = entry?.path();
+            if !lock_paths.contains(&path) {
+                removal += rm_rf(&path)?;
+            }
+        }
+
+        // Release the locks, then remove the lock files along with the cache root. Another
+        // process may have acquired a lock in the meantime, in which case the lock file (and the
+        // root) can't necessarily be removed, which is fine.
+        drop(locks);
+        match rm_rf(&self.root) {
+            Ok(lock_removal) => removal += lock_removal,
+            Err(err) => debug!("Failed to remove lock files from the cache: {err}"),
+        }
+
+        Ok(removal)
     }
 
     /// Remove a package from the cache.
@@ -275,7 +358,10 @@ impl Cache {
             CacheBucket::Git,
             CacheBucket::Interpreter,
             CacheBucket::Simple,
+            CacheBucket::Resolutions,
+            CacheBucket::Downloads,
         ] {
+            let _lock = self.lock_bucket(bucket)?;
             summary += bucket.remove(self, name)?;
         }
         Ok(summary)
@@ -384,6 +470,10 @@ pub enum CacheBucket {
     ///                 │   └── ...
     ///                 └── flask-3.0.0.dist-info
     ///                     └── ...
+    ///
+    /// Wheels downloaded from an index are accompanied by a `.sha256` file containing the
+    /// hex-encoded SHA-256 digest of the original archive, as computed while downloading. These
+    /// digests are used by `uv cache audit` to compare the cache against the index.
     Wheels,
     /// Wheels built from source distributions, their extracted metadata and the cache policy of
     /// the source distribution.
@@ -467,16 +557,22 @@ pub enum CacheBucket {
     /// without the shim itself changing, we only cache when the path equals `sys.executable`, i.e.
     /// the path we're running is the python executable itself and not a shim.
     ///
-    /// Cache structure: `interpreter-v0/<digest(path)>.msgpack`
+    /// Each entry is invalidated when the timestamp or size of the (canonicalized) executable
+    /// changes, e.g., when the interpreter is upgraded in place.
+    ///
+    /// Cache structure: `interpreter-v1/<digest(path)>.msgpack`
     ///
     /// # Example
     ///
-    /// The contents of each of the MsgPack files has a timestamp field in unix time, the [PEP 508]
-    /// markers and some information from the `sys`/`sysconfig` modules.
+    /// The contents of each of the MsgPack files has the timestamp (in unix time) and size of the
+    /// executable, the [PEP 508] markers and some information from the `sys`/`sysconfig` modules.
     ///
     /// ```json
     /// {
-    ///   "timestamp": 1698047994491,
+    ///   "file_info": {
+    ///     "timestamp": 1698047994491,
+    ///     "size": 6088
+    ///   },
     ///   "data": {
     ///     "markers": {
     ///       "implementation_name": "cpython",
@@ -508,6 +604,23 @@ pub enum CacheBucket {
     ///
     /// The response is parsed into `uv_client::SimpleMetadata` before storage.
     Simple,
+    /// Resolutions from `pip compile`, keyed by a digest of everything that can affect the result
+    /// (the requirements, the existing pins, the indexes, the target environment, etc.).
+    ///
+    /// Cache structure: `resolutions-v0/<digest(inputs)>.json`.
+    ///
+    /// Only resolutions that are reproducible from their inputs alone (e.g., with `--offline` or
+    /// `--exclude-newer`) are stored. Yanks aren't bound by `--exclude-newer`, so a cached
+    /// resolution won't reflect any packages that were yanked after it was stored; use
+    /// `--refresh` to resolve against the current state of the index.
+    Resolutions,
+    /// Archives saved by `pip download`, stored as-is (i.e., without unzipping), keyed by package
+    /// name and URL.
+    ///
+    /// Cache structure: `downloads-v0/<package_name>/<digest(url)>/<filename>`.
+    ///
+    /// Archives are only reused when their SHA-256 digest is known, and matches.
+    Downloads,
     /// A cache of unzipped wheels, stored as directories. This is used internally within the cache.
     /// When other buckets need to store directories, they should persist them to
     /// [`CacheBucket::Archive`], and then symlink them into the appropriate bucket. This ensures
@@ -522,8 +635,10 @@ impl CacheBucket {
             Self::BuiltWheels => "built-wheels-v0",
             Self::FlatIndex => "flat-index-v0",
             Self::Git => "git-v0",
-            Self::Interpreter => "interpreter-v0",
+            Self::Interpreter => "interpreter-v1",
             Self::Simple => "simple-v3",
+            Self::Resolutions => "resolutions-v0",
+            Self::Downloads => "downloads-v0",
             Self::Wheels => "wheels-v0",
             Self::Archive => "archive-v0",
         }
@@ -607,6 +722,16 @@ impl CacheBucket {
                 let root = cache.bucket(self);
                 summary += rm_rf(root)?;
             }
+            Self::Resolutions => {
+                // We can't know which resolutions include a package without reading every entry,
+                // so we just remove the entire bucket.
+                let root = cache.bucket(self);
+                summary += rm_rf(root)?;
+            }
+            Self::Downloads => {
+                // We expect a directory per package (indexed by name).
+                summary += rm_rf(cache.bucket(self).join(name.to_string()))?;
+            }
             Self::Git => {
                 // Nothing to do.
             }
@@ -632,7 +757,8 @@ pub enum ArchiveTimestamp {
     /// The archive consists of a single file with the given modification time.
     Exact(Timestamp),
     /// The archive consists of a directory. The modification time is the latest modification time
-    /// of the `pyproject.toml` or `setup.py` file in the directory.
+    /// of the `pyproject.toml` or `setup.py` file in the directory (or, for a source tree, of any
+    /// file within it).
     Approximate(Timestamp),
 }
 
@@ -685,6 +811,44 @@ impl ArchiveTimestamp {
         }
     }
 
+    /// Return the modification timestamp for a local source tree, which could be a file (like a
+    /// wheel or a zip archive) or a directory containing a Python package.
+    ///
+    /// Unlike [`ArchiveTimestamp::from_path`], which only considers the build entrypoints, this
+    /// takes the most recent modification time of any file or directory within the tree, such
+    /// that any local change (including added or removed files) invalidates previously-built
+    /// artifacts. Version control metadata, virtual environments, bytecode, build outputs, and
+    /// `node_modules` are ignored. Files excluded by a `.gitignore` are still considered, since
+    /// generated sources can be inputs to the build.
+    ///
+    /// If the path is to a directory with no entrypoint (i.e., no `pyproject.toml`, `setup.py`, or
+    /// `setup.cfg`), returns `None`.
+    pub fn from_source_tree(path: impl AsRef<Path>) -> Result<Option<Self>, io::Error> {
+        let Some(entrypoint) = Self::from_path(path.as_ref())? else {
+            return Ok(None);
+        };
+        if matches!(entrypoint, Self::Exact(_)) {
+            return Ok(Some(entrypoint));
+        }
+
+        let mut timestamp = entrypoint.timestamp();
+        let walker = walkdir::WalkDir::new(path.as_ref())
+            .into_iter()
+            .filter_entry(|entry| {
+                entry.depth() == 0
+                    || !entry.file_type().is_dir()
+                    || !entry.file_name().to_str().is_some_and(is_ignored_directory)
+            });
+        for entry in walker {
+            let entry = entry.map_err(io::Error::from)?;
+            timestamp = timestamp.max(Timestamp::from_metadata(
+                &entry.metadata().map_err(io::Error::from)?,
+            ));
+        }
+
+        Ok(Some(Self::Approximate(timestamp)))
+    }
+
     /// Return the modification timestamp for an archive.
     pub fn timestamp(&self) -> Timestamp {
         match self {
@@ -694,6 +858,24 @@ impl ArchiveTimestamp {
     }
 }
 
+/// Returns `true` if a directory within a source tree should be ignored when determining
+/// whether the tree has changed.
+fn is_ignored_directory(name: &str) -> bool {
+    matches!(
+        name,
+        ".git"
+            | ".hg"
+            | ".svn"
+            | ".venv"
+            | ".tox"
+            | ".nox"
+            | "__pycache__"
+            | "build"
+            | "dist"
+            | "node_modules"
+    ) || name.ends_with(".egg-info")
+}
+
 impl PartialOrd for ArchiveTimestamp {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         Some(self.timestamp().cmp(&other.timestamp()))
@@ -47,6 +47,12 @@ impl<'a> WheelCache<'a> {
         }
     }
 
+    /// The root directory for this source, relative to the cache bucket (e.g., `pypi` or
+    /// `index/<digest>`).
+    pub fn root(&self) -> PathBuf {
+        self.bucket()
+    }
+
     /// Metadata of a remote wheel. See [`CacheBucket::Wheels`]
     pub fn remote_wheel_dir(&self, package_name: impl AsRef<Path>) -> PathBuf {
         self.bucket().join(package_name)
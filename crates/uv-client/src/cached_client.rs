@@ -12,7 +12,7 @@ use uv_cache::{CacheEntry, Freshness};
 use uv_fs::write_atomic;
 
 use crate::{
-    httpcache::{AfterResponse, BeforeRequest, CachePolicy, CachePolicyBuilder},
+    httpcache::{AfterResponse, BeforeRequest, CachePolicy, CachePolicyBuilder, Validation},
     rkyvutil::OwnedArchive,
     Error, ErrorKind,
 };
@@ -158,16 +158,35 @@ impl From<Freshness> for CacheControl {
 /// Again unlike `http-cache`, the caller gets full control over the cache key with the assumption
 /// that it's a file.
 #[derive(Debug, Clone)]
-pub struct CachedClient(ClientWithMiddleware);
+pub struct CachedClient {
+    client: ClientWithMiddleware,
+    /// The entity tags that may be used to revalidate cached responses.
+    validation: Validation,
+}
 
 impl CachedClient {
     pub fn new(client: ClientWithMiddleware) -> Self {
-        Self(client)
+        Self {
+            client,
+            validation: Validation::default(),
+        }
+    }
+
+    /// Return a client that also revalidates cached responses with weak entity tags.
+    ///
+    /// A weak match only indicates that two responses are semantically equivalent, so this should
+    /// only be used for index pages, and never for artifacts like wheels or their metadata.
+    #[must_use]
+    pub fn with_weak_etags(&self) -> Self {
+        Self {
+            client: self.client.clone(),
+            validation: Validation::Weak,
+        }
     }
 
     /// The middleware is the retry strategy
     pub fn uncached(&self) -> ClientWithMiddleware {
-        self.0.clone()
+        self.client.clone()
     }
 
     /// Make a cached request with a custom response transformation
@@ -377,7 +396,10 @@ impl CachedClient {
                 );
             }
         }
-        Ok(match cached.cache_policy.before_request(&mut req) {
+        let before_request = cached
+            .cache_policy
+            .before_request(&mut req, self.validation);
+        Ok(match before_request {
             BeforeRequest::Fresh => {
                 debug!("Found fresh response for: {}", req.url());
                 CachedResponse::FreshCache(cached)
@@ -417,17 +439,19 @@ impl CachedClient {
         let url = req.url().clone();
         debug!("Sending revalidation request for: {url}");
         let response = self
-            .0
+            .client
             .execute(req)
             .instrument(info_span!("revalidation_request", url = url.as_str()))
             .await
             .map_err(ErrorKind::from)?
             .error_for_status()
             .map_err(ErrorKind::from)?;
-        match cached
-            .cache_policy
-            .after_response(new_cache_policy_builder, &response)
-        {
+        let after_response = cached.cache_policy.after_response(
+            new_cache_policy_builder,
+            &response,
+            self.validation,
+        );
+        match after_response {
             AfterResponse::NotModified(new_policy) => {
                 debug!("Found not-modified response for: {url}");
                 Ok(CachedResponse::NotModified {
@@ -456,7 +480,7 @@ impl CachedClient {
         trace!("Sending fresh {} request for {}", req.method(), req.url());
         let cache_policy_builder = CachePolicyBuilder::new(&req);
         let response = self
-            .0
+            .client
             .execute(req)
             .await
             .map_err(ErrorKind::from)?
@@ -57,7 +57,7 @@ pub enum ErrorKind {
     #[error(transparent)]
     InstallWheel(#[from] install_wheel_rs::Error),
 
-    #[error("{0} isn't available locally, but making network requests to registries was banned.")]
+    #[error("{0} isn't available in the cache or any `--find-links` location, and index lookups were disabled with `--no-index`")]
     NoIndex(String),
 
     /// The package was not found in the registry.
@@ -138,6 +138,9 @@ pub enum ErrorKind {
     #[error("Unsupported `Content-Type` \"{1}\" for {0}. Expected JSON or HTML.")]
     UnsupportedMediaType(Url, String),
 
+    #[error("Unsupported Simple API version \"{1}\" for {0}. Expected a 1.x version.")]
+    UnsupportedApiVersion(Url, String),
+
     #[error("Reading from cache archive failed: {0}")]
     ArchiveRead(String),
 
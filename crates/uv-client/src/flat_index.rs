@@ -1,6 +1,6 @@
 use std::collections::btree_map::Entry;
 use std::collections::BTreeMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use futures::{FutureExt, StreamExt};
 use reqwest::Response;
@@ -32,6 +32,9 @@ pub enum FlatIndexError {
 
     #[error("Failed to read `--find-links` URL: {0}")]
     FindLinksUrl(Url, #[source] Error),
+
+    #[error("Failed to read `--find-links` HTML file: {0}")]
+    FindLinksHtml(PathBuf, #[source] Error),
 }
 
 #[derive(Debug, Default, Clone)]
@@ -100,6 +103,10 @@ impl<'a> FlatIndexClient<'a> {
         let mut fetches = futures::stream::iter(indexes)
             .map(|index| async move {
                 let entries = match index {
+                    FlatIndexLocation::Path(path) if path.is_file() => {
+                        Self::read_from_html_file(path)
+                            .map_err(|err| FlatIndexError::FindLinksHtml(path.clone(), err))?
+                    }
                     FlatIndexLocation::Path(path) => Self::read_from_directory(path)
                         .map_err(|err| FlatIndexError::FindLinksDirectory(path.clone(), err))?,
                     FlatIndexLocation::Url(url) => self
@@ -183,6 +190,7 @@ impl<'a> FlatIndexClient<'a> {
             .instrument(info_span!("parse_flat_index_html", url = % url))
         };
         let response = cached_client
+            .with_weak_etags()
             .get_serde(
                 flat_index_request,
                 &cache_entry,
@@ -256,6 +264,61 @@ impl<'a> FlatIndexClient<'a> {
         }
         Ok(FlatIndexEntries::from_entries(dists))
     }
+
+    /// Read a flat index from a local `--find-links` HTML file, like a page of links saved from a
+    /// remote index or generated alongside a directory of vendored artifacts.
+    fn read_from_html_file(path: &Path) -> Result<FlatIndexEntries, Error> {
+        // Absolute paths are required for the URL conversion.
+        let path = fs_err::canonicalize(path).map_err(ErrorKind::Io)?;
+        let url = Url::from_file_path(&path).expect("canonicalized paths are absolute");
+        let index_url = IndexUrl::Url(VerbatimUrl::from_path(&path));
+
+        let text = fs_err::read_to_string(&path).map_err(ErrorKind::Io)?;
+        let SimpleHtml { base, files } =
+            SimpleHtml::parse(&text, &url).map_err(|err| Error::from_html_err(err, url.clone()))?;
+        let base = base.into_url();
+
+        let dists = files
+            .into_iter()
+            .filter_map(|file| {
+                let mut file = match File::try_from(file, &base) {
+                    Ok(file) => file,
+                    Err(err) => {
+                        // Ignore files with unparsable version specifiers.
+                        warn!("Skipping file in {}: {err}", path.display());
+                        return None;
+                    }
+                };
+
+                // Read links to local files (e.g., relative links to archives alongside the page)
+                // directly from disk, like the entries in a `--find-links` directory.
+                let resolved = match &file.url {
+                    FileLocation::RelativeUrl(base, url) => {
+                        pypi_types::base_url_join_relative(base, url).ok()
+                    }
+                    FileLocation::AbsoluteUrl(url) => Url::parse(url).ok(),
+                    FileLocation::Path(_) => None,
+                };
+                if let Some(path) = resolved
+                    .filter(|url| url.scheme() == "file")
+                    .and_then(|url| url.to_file_path().ok())
+                {
+                    file.url = FileLocation::Path(path);
+                }
+
+                let Some(filename) = DistFilename::try_from_normalized_filename(&file.filename)
+                else {
+                    debug!(
+                        "Ignoring `--find-links` entry (expected a wheel or source distribution filename): {}",
+                        file.filename
+                    );
+                    return None;
+                };
+                Some((filename, file, index_url.clone()))
+            })
+            .collect();
+        Ok(FlatIndexEntries::from_entries(dists))
+    }
 }
 
 /// A set of [`PrioritizedDist`] from a `--find-links` entry, indexed by [`PackageName`]
@@ -157,9 +157,13 @@ impl SimpleHtml {
         };
 
         // Extract the `data-dist-info-metadata` field, which should be set on
-        // the `data-dist-info-metadata` attribute.
-        let dist_info_metadata = if let Some(dist_info_metadata) =
-            link.attributes().get("data-dist-info-metadata").flatten()
+        // the `data-core-metadata` attribute (PEP 714), or the legacy
+        // `data-dist-info-metadata` attribute.
+        let dist_info_metadata = if let Some(dist_info_metadata) = link
+            .attributes()
+            .get("data-core-metadata")
+            .flatten()
+            .or_else(|| link.attributes().get("data-dist-info-metadata").flatten())
         {
             let dist_info_metadata = std::str::from_utf8(dist_info_metadata.as_bytes())?;
             let dist_info_metadata = html_escape::decode_html_entities(dist_info_metadata);
@@ -909,4 +913,26 @@ mod tests {
         }
         "###);
     }
+
+    #[test]
+    fn parse_core_metadata() {
+        let text = r#"
+<!DOCTYPE html>
+<html>
+  <body>
+    <h1>Links for jinja2</h1>
+    <a href="/whl/Jinja2-3.1.2-py3-none-any.whl#sha256=6088930bfe239f0e6710546ab9c19c9ef35e29792895fed6e6e31a023a182a61" data-core-metadata="true" data-dist-info-metadata="false">Jinja2-3.1.2-py3-none-any.whl</a><br/>
+  </body>
+</html>
+        "#;
+        let base = Url::parse("https://download.pytorch.org/whl/jinja2/").unwrap();
+        let result = SimpleHtml::parse(text, &base).unwrap();
+        insta::assert_debug_snapshot!(result.files[0].dist_info_metadata, @r###"
+        Some(
+            Bool(
+                true,
+            ),
+        )
+        "###);
+    }
 }
@@ -308,7 +308,11 @@ impl ArchivedCachePolicy {
     /// it is suitable as a revalidation request.
     ///
     /// [RFC 9111 S4]: https://www.rfc-editor.org/rfc/rfc9111.html#section-4
-    pub fn before_request(&self, request: &mut reqwest::Request) -> BeforeRequest {
+    pub fn before_request(
+        &self,
+        request: &mut reqwest::Request,
+        validation: Validation,
+    ) -> BeforeRequest {
         let now = SystemTime::now();
         // If the response was never storable, then we just bail out
         // completely.
@@ -353,13 +357,13 @@ impl ArchivedCachePolicy {
                 "request {} does not match cached request because of the 'Vary' header",
                 request.url(),
             );
-            self.set_revalidation_headers(request);
+            self.set_revalidation_headers(request, validation);
             return BeforeRequest::Stale(self.new_cache_policy_builder(request));
         }
         // "the stored response does not contain the no-cache directive, unless
         // it is successfully validated, and..."
         if self.response.headers.cc.no_cache {
-            self.set_revalidation_headers(request);
+            self.set_revalidation_headers(request, validation);
             return BeforeRequest::Stale(self.new_cache_policy_builder(request));
         }
         // "the stored response is one of the following: ..."
@@ -372,7 +376,7 @@ impl ArchivedCachePolicy {
         // "successfully validated."
         //
         // In this case, callers will need to send a revalidation request.
-        self.set_revalidation_headers(request);
+        self.set_revalidation_headers(request, validation);
         BeforeRequest::Stale(self.new_cache_policy_builder(request))
     }
 
@@ -401,9 +405,10 @@ impl ArchivedCachePolicy {
         &self,
         cache_policy_builder: CachePolicyBuilder,
         response: &reqwest::Response,
+        validation: Validation,
     ) -> AfterResponse {
         let mut new_policy = cache_policy_builder.build(response);
-        if self.is_modified(&new_policy) {
+        if self.is_modified(&new_policy, validation) {
             AfterResponse::Modified(new_policy)
         } else {
             new_policy.response.status = self.response.status;
@@ -411,7 +416,7 @@ impl ArchivedCachePolicy {
         }
     }
 
-    fn is_modified(&self, new_policy: &CachePolicy) -> bool {
+    fn is_modified(&self, new_policy: &CachePolicy, validation: Validation) -> bool {
         // From [RFC 9111 S4.3.3],
         //
         // "A 304 (Not Modified) response status code indicates that the stored
@@ -430,9 +435,15 @@ impl ArchivedCachePolicy {
         // [RFC 9111 S4.3.4]: https://www.rfc-editor.org/rfc/rfc9111.html#section-4.3.4
         if let Some(old_etag) = self.response.headers.etag.as_ref() {
             if let Some(new_etag) = new_policy.response.headers.etag.as_ref() {
-                // We don't support weak validators, so only match if they're
-                // both strong.
-                if !old_etag.weak && !new_etag.weak && old_etag.value == new_etag.value {
+                // Unless weak validation is allowed, only match if they're both
+                // strong. Otherwise, as per [RFC 9111 S4.3.4], a weak validator
+                // may also be used to select the stored response, so we use the
+                // weak comparison function (i.e., we ignore the `W/` prefix).
+                let comparable = match validation {
+                    Validation::Strong => !old_etag.weak && !new_etag.weak,
+                    Validation::Weak => true,
+                };
+                if comparable && old_etag.value == new_etag.value {
                     return false;
                 }
             }
@@ -481,15 +492,16 @@ impl ArchivedCachePolicy {
     /// body. That is, it indicates that our cached response is still fresh.
     ///
     /// This will always use a strong etag validator if it's present on the
-    /// cached response. If the given request already has an etag validator
-    /// on it, this routine will add to it and not replace it.
+    /// cached response, and a weak one if permitted by the given
+    /// [`Validation`]. If the given request already has an etag validator on
+    /// it, this routine will add to it and not replace it.
     ///
     /// In contrast, if the request already has the `If-Modified-Since` header
     /// set, then this will not change or replace it. If it's not set, then one
     /// is added if the cached response had a valid `Last-Modified` header.
     ///
     /// [RFC 9111 S4.3.1]: https://www.rfc-editor.org/rfc/rfc9111.html#section-4.3.1
-    fn set_revalidation_headers(&self, request: &mut reqwest::Request) {
+    fn set_revalidation_headers(&self, request: &mut reqwest::Request, validation: Validation) {
         // As per [RFC 9110 13.1.2] and [RFC 9111 S4.3.1], if our stored
         // response has an etag, we should send it back via the `If-None-Match`
         // header. The idea is that the server should only "do" the request if
@@ -499,13 +511,23 @@ impl ArchivedCachePolicy {
         // [RFC 9110 S13.1.2]: https://www.rfc-editor.org/rfc/rfc9110#section-13.1.2
         // [RFC 9111 S4.3.1]: https://www.rfc-editor.org/rfc/rfc9111.html#section-4.3.1
         if let Some(etag) = self.response.headers.etag.as_ref() {
-            // We don't support weak validation principally because we want to
-            // be notified if there was a change in the content. Namely, from
-            // RFC 9110 S13.1.2: "... weak entity tags can be used for cache
-            // validation even if there have been changes to the representation
-            // data."
-            if !etag.weak {
-                if let Ok(header) = HeaderValue::from_bytes(&etag.value) {
+            // By default, we don't support weak validation principally because
+            // we want to be notified if there was a change in the content.
+            // Namely, from RFC 9110 S13.1.2: "... weak entity tags can be used
+            // for cache validation even if there have been changes to the
+            // representation data."
+            //
+            // Servers commonly weaken the etags of index pages, though (e.g.,
+            // when compressing them on the fly), and a weak match indicates
+            // that the representations are semantically equivalent, which
+            // suffices for a page that we parse.
+            if !etag.weak || validation == Validation::Weak {
+                let mut value = Vec::with_capacity(etag.value.len() + 2);
+                if etag.weak {
+                    value.extend_from_slice(b"W/");
+                }
+                value.extend_from_slice(&etag.value);
+                if let Ok(header) = HeaderValue::from_bytes(&value) {
                     request.headers_mut().append("if-none-match", header);
                 }
             }
@@ -949,6 +971,21 @@ impl ArchivedCachePolicy {
     }
 }
 
+/// The entity tags that may be used to revalidate a cached response.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub enum Validation {
+    /// Only strong entity tags, such that a revalidated response is known to be
+    /// identical to the cached response. This is appropriate for artifacts,
+    /// like wheels and their metadata.
+    #[default]
+    Strong,
+    /// Strong or weak entity tags, such that a revalidated response is only
+    /// known to be semantically equivalent to the cached response. This is
+    /// appropriate for index pages, which servers commonly compress on the fly
+    /// (and thus serve with weak entity tags).
+    Weak,
+}
+
 /// The result of calling [`CachePolicy::before_request`].
 ///
 /// This dictates what the caller should do next by indicating whether the
@@ -1197,7 +1234,8 @@ struct ETag {
     /// group representations by some self-determined set of equivalency rather
     /// than unique sequences of data."
     ///
-    /// We don't currently support weak validation.
+    /// Weak validators are only used to revalidate responses for which
+    /// [`Validation::Weak`] is requested.
     ///
     /// [RFC 9110 S8.8.1]: https://www.rfc-editor.org/rfc/rfc9110#section-8.8.1-6
     weak: bool,
@@ -1356,3 +1394,94 @@ fn parse_seconds(value: &[u8]) -> Option<u64> {
     }
     std::str::from_utf8(value).ok()?.parse().ok()
 }
+
+#[cfg(test)]
+mod tests {
+    use url::Url;
+
+    use super::*;
+
+    /// Build a cache policy for a stale response with the given `ETag`.
+    fn stale_policy(etag: &'static str) -> CachePolicy {
+        let request = reqwest::Request::new(
+            http::Method::GET,
+            Url::parse("https://pypi.org/simple/flask/").unwrap(),
+        );
+        let response = reqwest::Response::from(
+            http::Response::builder()
+                .status(200)
+                .header("etag", etag)
+                .header("cache-control", "max-age=0")
+                .body("")
+                .unwrap(),
+        );
+        CachePolicyBuilder::new(&request).build(&response)
+    }
+
+    fn revalidate(
+        policy: &CachePolicy,
+        etag: &'static str,
+        validation: Validation,
+    ) -> (Option<String>, bool) {
+        let mut request = reqwest::Request::new(
+            http::Method::GET,
+            Url::parse("https://pypi.org/simple/flask/").unwrap(),
+        );
+        let BeforeRequest::Stale(builder) = policy
+            .to_archived()
+            .before_request(&mut request, validation)
+        else {
+            panic!("expected a stale response");
+        };
+        let if_none_match = request
+            .headers()
+            .get("if-none-match")
+            .map(|header| header.to_str().unwrap().to_string());
+
+        let response = reqwest::Response::from(
+            http::Response::builder()
+                .status(304)
+                .header("etag", etag)
+                .body("")
+                .unwrap(),
+        );
+        let modified = matches!(
+            policy
+                .to_archived()
+                .after_response(builder, &response, validation),
+            AfterResponse::Modified(_)
+        );
+        (if_none_match, modified)
+    }
+
+    #[test]
+    fn revalidate_strong_etag() {
+        let policy = stale_policy("\"abc\"");
+        let (if_none_match, modified) = revalidate(&policy, "\"abc\"", Validation::Strong);
+        assert_eq!(if_none_match.as_deref(), Some("\"abc\""));
+        assert!(!modified);
+    }
+
+    #[test]
+    fn revalidate_weak_etag() {
+        let policy = stale_policy("W/\"abc\"");
+        let (if_none_match, modified) = revalidate(&policy, "W/\"abc\"", Validation::Weak);
+        assert_eq!(if_none_match.as_deref(), Some("W/\"abc\""));
+        assert!(!modified);
+    }
+
+    #[test]
+    fn revalidate_weak_etag_strong_validation() {
+        let policy = stale_policy("W/\"abc\"");
+        let (if_none_match, modified) = revalidate(&policy, "W/\"abc\"", Validation::Strong);
+        assert_eq!(if_none_match, None);
+        assert!(modified);
+    }
+
+    #[test]
+    fn revalidate_changed_etag() {
+        let policy = stale_policy("W/\"abc\"");
+        let (_, modified) = revalidate(&policy, "W/\"def\"", Validation::Weak);
+        assert!(modified);
+    }
+}
@@ -1,6 +1,7 @@
 pub use cached_client::{CacheControl, CachedClient, CachedClientError, DataWithCachePolicy};
 pub use error::{BetterReqwestError, Error, ErrorKind};
 pub use flat_index::{FlatDistributions, FlatIndex, FlatIndexClient, FlatIndexError};
+pub use osv::{OsvClient, Vulnerability};
 pub use registry_client::{
     Connectivity, RegistryClient, RegistryClientBuilder, SimpleMetadata, SimpleMetadatum,
     VersionFiles,
@@ -13,6 +14,7 @@ mod flat_index;
 mod html;
 mod httpcache;
 mod middleware;
+mod osv;
 mod registry_client;
 mod remote_metadata;
 mod rkyvutil;
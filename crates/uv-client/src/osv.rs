@@ -0,0 +1,230 @@
+use std::str::FromStr;
+
+use futures::{StreamExt, TryStreamExt};
+use serde::Deserialize;
+use tracing::{debug, instrument};
+use url::Url;
+
+use pep440_rs::Version;
+use uv_normalize::PackageName;
+
+use crate::{Error, ErrorKind, RegistryClient};
+
+/// The default base URL of the OSV API.
+const OSV_URL: &str = "https://api.osv.dev/";
+
+/// A client for querying the [OSV](https://osv.dev) vulnerability database, which includes the
+/// advisories from the PyPA Advisory Database.
+#[derive(Debug, Clone)]
+pub struct OsvClient<'a> {
+    client: &'a RegistryClient,
+    url: Url,
+}
+
+impl<'a> OsvClient<'a> {
+    /// Create a new [`OsvClient`] that queries the public OSV API.
+    pub fn new(client: &'a RegistryClient) -> Self {
+        Self {
+            client,
+            url: Url::parse(OSV_URL).unwrap(),
+        }
+    }
+
+    /// Set the base URL of the OSV API (e.g., to query a mirror).
+    #[must_use]
+    pub fn with_url(self, url: Url) -> Self {
+        Self { url, ..self }
+    }
+
+    /// Query the known vulnerabilities for a specific version of a package.
+    #[instrument(skip(self))]
+    pub async fn query(
+        &self,
+        name: &PackageName,
+        version: &Version,
+    ) -> Result<Vec<Vulnerability>, Error> {
+        let url = self.url.join("v1/query").map_err(ErrorKind::from)?;
+        let body = serde_json::json!({
+            "package": {
+                "name": name.to_string(),
+                "ecosystem": "PyPI",
+            },
+            "version": version.to_string(),
+        });
+
+        debug!("Querying OSV for {name}=={version}");
+        let response = self
+            .client
+            .cached_client()
+            .uncached()
+            .post(url.clone())
+            .header(http::header::CONTENT_TYPE, "application/json")
+            .body(body.to_string())
+            .send()
+            .await
+            .map_err(ErrorKind::from)?
+            .error_for_status()
+            .map_err(ErrorKind::from)?;
+        let bytes = response.bytes().await.map_err(ErrorKind::from)?;
+        let response: QueryResponse =
+            serde_json::from_slice(&bytes).map_err(|err| Error::from_json_err(err, url))?;
+        Ok(response.vulns)
+    }
+
+    /// Query the known vulnerabilities for each of the given packages, returning the packages
+    /// with at least one vulnerability.
+    pub async fn query_all<'data>(
+        &self,
+        packages: impl Iterator<Item = (&'data PackageName, &'data Version)>,
+    ) -> Result<Vec<(&'data PackageName, &'data Version, Vec<Vulnerability>)>, Error> {
+        futures::stream::iter(packages)
+            .map(|(name, version)| async move {
+                let vulnerabilities = self.query(name, version).await?;
+                Ok::<_, Error>((name, version, vulnerabilities))
+            })
+            .buffered(16)
+            .try_filter(|(_, _, vulnerabilities)| {
+                futures::future::ready(!vulnerabilities.is_empty())
+            })
+            .try_collect()
+            .await
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct QueryResponse {
+    #[serde(default)]
+    vulns: Vec<Vulnerability>,
+}
+
+/// A vulnerability, as reported by the OSV API.
+///
+/// See: <https://ossf.github.io/osv-schema/>
+#[derive(Debug, Clone, Deserialize)]
+pub struct Vulnerability {
+    /// The identifier of the vulnerability (e.g., `PYSEC-2023-62` or `GHSA-j8r2-6x86-q33q`).
+    pub id: String,
+    /// Other identifiers for the same vulnerability (e.g., `CVE-2023-32681`).
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    /// A one-line summary of the vulnerability.
+    pub summary: Option<String>,
+    #[serde(default)]
+    affected: Vec<Affected>,
+}
+
+impl Vulnerability {
+    /// Return the CVE identifier of the vulnerability, if it has one.
+    pub fn cve(&self) -> Option<&str> {
+        std::iter::once(&self.id)
+            .chain(self.aliases.iter())
+            .find(|id| id.starts_with("CVE-"))
+            .map(String::as_str)
+    }
+
+    /// Return the versions of the given package in which the vulnerability was fixed, in
+    /// ascending order.
+    pub fn fixed_versions(&self, name: &PackageName) -> Vec<Version> {
+        let mut versions = self
+            .affected
+            .iter()
+            .filter(|affected| {
+                affected.package.ecosystem == "PyPI"
+                    && PackageName::from_str(&affected.package.name)
+                        .is_ok_and(|affected| affected == *name)
+            })
+            .flat_map(|affected| affected.ranges.iter())
+            .filter(|range| range.kind == "ECOSYSTEM")
+            .flat_map(|range| range.events.iter())
+            .filter_map(|event| event.fixed.as_deref())
+            .filter_map(|fixed| Version::from_str(fixed).ok())
+            .collect::<Vec<_>>();
+        versions.sort();
+        versions.dedup();
+        versions
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct Affected {
+    package: AffectedPackage,
+    #[serde(default)]
+    ranges: Vec<AffectedRange>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct AffectedPackage {
+    name: String,
+    ecosystem: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct AffectedRange {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    events: Vec<RangeEvent>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct RangeEvent {
+    fixed: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use pep440_rs::Version;
+    use uv_normalize::PackageName;
+
+    use super::QueryResponse;
+
+    #[test]
+    fn parse_query_response() {
+        let response = r#"
+        {
+          "vulns": [
+            {
+              "id": "PYSEC-2023-74",
+              "summary": "Unintended leak of Proxy-Authorization header in requests",
+              "aliases": ["CVE-2023-32681", "GHSA-j8r2-6x86-q33q"],
+              "affected": [
+                {
+                  "package": {"name": "requests", "ecosystem": "PyPI"},
+                  "ranges": [
+                    {
+                      "type": "ECOSYSTEM",
+                      "events": [{"introduced": "2.3.0"}, {"fixed": "2.31.0"}]
+                    },
+                    {
+                      "type": "GIT",
+                      "repo": "https://github.com/psf/requests",
+                      "events": [{"introduced": "0"}, {"fixed": "74ea7cf7a6a27a4eeb2ae24e162bcc942a6706d5"}]
+                    }
+                  ]
+                }
+              ]
+            }
+          ]
+        }
+        "#;
+        let response: QueryResponse = serde_json::from_str(response).unwrap();
+        let vulnerability = &response.vulns[0];
+        assert_eq!(vulnerability.id, "PYSEC-2023-74");
+        assert_eq!(vulnerability.cve(), Some("CVE-2023-32681"));
+        assert_eq!(
+            vulnerability.fixed_versions(&PackageName::from_str("requests").unwrap()),
+            vec![Version::from_str("2.31.0").unwrap()]
+        );
+        assert!(vulnerability
+            .fixed_versions(&PackageName::from_str("urllib3").unwrap())
+            .is_empty());
+    }
+
+    #[test]
+    fn parse_empty_response() {
+        let response: QueryResponse = serde_json::from_str("{}").unwrap();
+        assert!(response.vulns.is_empty());
+    }
+}
@@ -21,7 +21,7 @@ use distribution_filename::{DistFilename, SourceDistFilename, WheelFilename};
 use distribution_types::{BuiltDist, File, FileLocation, IndexUrl, IndexUrls, Name};
 use install_wheel_rs::{find_dist_info, is_metadata_entry};
 use pep440_rs::Version;
-use pypi_types::{Metadata21, SimpleJson};
+use pypi_types::{Metadata21, SimpleJson, SimpleJsonMeta};
 use uv_auth::safe_copy_url_auth;
 use uv_cache::{Cache, CacheBucket, WheelCache};
 use uv_normalize::PackageName;
@@ -286,6 +286,7 @@ impl RegistryClient {
                         let bytes = response.bytes().await.map_err(ErrorKind::from)?;
                         let data: SimpleJson = serde_json::from_slice(bytes.as_ref())
                             .map_err(|err| Error::from_json_err(err, url.clone()))?;
+                        check_api_version(&data.meta, &url)?;
 
                         SimpleMetadata::from_files(data.files, package_name, &url)
                     }
@@ -305,6 +306,7 @@ impl RegistryClient {
         };
         let result = self
             .client
+            .with_weak_etags()
             .get_cacheable(
                 simple_request,
                 &cache_entry,
@@ -756,7 +758,7 @@ impl ArchivedSimpleMetadata {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
 enum MediaType {
     Json,
     Html,
@@ -764,10 +766,17 @@ enum MediaType {
 
 impl MediaType {
     /// Parse a media type from a string, returning `None` if the media type is not supported.
+    ///
+    /// Media types are case-insensitive, and the `latest` alias is treated as equivalent to `v1`,
+    /// since we only support the first major version of the API.
     fn from_str(s: &str) -> Option<Self> {
-        match s {
-            "application/vnd.pypi.simple.v1+json" => Some(Self::Json),
-            "application/vnd.pypi.simple.v1+html" | "text/html" => Some(Self::Html),
+        match s.trim().to_ascii_lowercase().as_str() {
+            "application/vnd.pypi.simple.v1+json" | "application/vnd.pypi.simple.latest+json" => {
+                Some(Self::Json)
+            }
+            "application/vnd.pypi.simple.v1+html"
+            | "application/vnd.pypi.simple.latest+html"
+            | "text/html" => Some(Self::Html),
             _ => None,
         }
     }
@@ -780,6 +789,32 @@ impl MediaType {
     }
 }
 
+/// The latest minor version of the Simple API that we support.
+const SUPPORTED_API_MINOR_VERSION: u64 = 1;
+
+/// Validate the API version of a JSON Simple API response.
+///
+/// Per PEP 629, clients should fail on an unsupported major version, and warn on an unsupported
+/// minor version.
+fn check_api_version(meta: &SimpleJsonMeta, url: &Url) -> Result<(), Error> {
+    let Some((major, minor)) = meta.api_version() else {
+        return Ok(());
+    };
+    if major != 1 {
+        return Err(ErrorKind::UnsupportedApiVersion(
+            url.clone(),
+            meta.api_version.clone().unwrap_or_default(),
+        )
+        .into());
+    }
+    if minor > SUPPORTED_API_MINOR_VERSION {
+        warn_user_once!(
+            "{url} uses Simple API version {major}.{minor}, which is newer than the latest supported version ({major}.{SUPPORTED_API_MINOR_VERSION}); some features may be ignored"
+        );
+    }
+    Ok(())
+}
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum Connectivity {
     /// Allow access to the network.
@@ -795,11 +830,13 @@ mod tests {
 
     use url::Url;
 
-    use pypi_types::{JoinRelativeError, SimpleJson};
+    use pypi_types::{DistInfoMetadata, JoinRelativeError, SimpleJson};
     use uv_normalize::PackageName;
 
     use crate::{html::SimpleHtml, SimpleMetadata, SimpleMetadatum};
 
+    use super::{check_api_version, MediaType};
+
     #[test]
     fn ignore_failing_files() {
         // 1.7.7 has an invalid requires-python field (double comma), 1.7.8 is valid
@@ -892,4 +929,68 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn media_type() {
+        assert_eq!(
+            MediaType::from_str("application/vnd.pypi.simple.v1+json"),
+            Some(MediaType::Json)
+        );
+        assert_eq!(
+            MediaType::from_str("Application/VND.PyPI.Simple.Latest+JSON"),
+            Some(MediaType::Json)
+        );
+        assert_eq!(
+            MediaType::from_str(" application/vnd.pypi.simple.v1+html "),
+            Some(MediaType::Html)
+        );
+        assert_eq!(MediaType::from_str("text/html"), Some(MediaType::Html));
+        assert_eq!(
+            MediaType::from_str("application/vnd.pypi.simple.v2+json"),
+            None
+        );
+    }
+
+    #[test]
+    fn api_version() {
+        let url = Url::parse("https://pypi.org/simple/pyflyby/").unwrap();
+
+        let data: SimpleJson =
+            serde_json::from_str(r#"{"meta": {"api-version": "1.1"}, "files": []}"#).unwrap();
+        assert_eq!(data.meta.api_version(), Some((1, 1)));
+        assert!(check_api_version(&data.meta, &url).is_ok());
+
+        let data: SimpleJson = serde_json::from_str(r#"{"files": []}"#).unwrap();
+        assert_eq!(data.meta.api_version(), None);
+        assert!(check_api_version(&data.meta, &url).is_ok());
+
+        let data: SimpleJson =
+            serde_json::from_str(r#"{"meta": {"api-version": "2.0"}, "files": []}"#).unwrap();
+        assert!(check_api_version(&data.meta, &url).is_err());
+    }
+
+    /// The PEP 714 `core-metadata` key takes precedence over the legacy `data-dist-info-metadata`.
+    #[test]
+    fn core_metadata() {
+        let response = r#"
+        {
+          "files": [
+            {
+              "core-metadata": {"sha256": "4d0a1e4ecb2b6a2a2f2b2d7c4f1c0e4f3e1d1a0b9f6c3e9e7f0f8c1d3b4a5e6f"},
+              "data-dist-info-metadata": false,
+              "filename": "pyflyby-1.7.8.tar.gz",
+              "hashes": {
+                "sha256": "1ee37474f6da8f98653dbcc208793f50b7ace1d9066f49e2707750a5ba5d53c6"
+              },
+              "url": "https://files.pythonhosted.org/packages/ad/39/17180d9806a1c50197bc63b25d0f1266f745fc3b23f11439fccb3d6baa50/pyflyby-1.7.8.tar.gz"
+            }
+          ]
+        }
+        "#;
+        let data: SimpleJson = serde_json::from_str(response).unwrap();
+        assert!(matches!(
+            data.files[0].dist_info_metadata,
+            Some(DistInfoMetadata::Hashes(_))
+        ));
+    }
 }
@@ -19,8 +19,11 @@ use uv_cache::Cache;
 use uv_client::{FlatIndex, RegistryClient};
 use uv_installer::{Downloader, Installer, NoBinary, Plan, Planner, Reinstall, SitePackages};
 use uv_interpreter::{Interpreter, PythonEnvironment};
-use uv_resolver::{InMemoryIndex, Manifest, Options, Resolver};
-use uv_traits::{BuildContext, BuildKind, ConfigSettings, InFlight, NoBuild, SetupPyStrategy};
+use uv_resolver::{InMemoryIndex, Options, ResolverBuilder};
+use uv_traits::{
+    BuildContext, BuildIsolation, BuildKind, Concurrency, ConfigSettings, HashDatabase, InFlight,
+    NoBuild, SetupPyStrategy,
+};
 
 /// The main implementation of [`BuildContext`], used by the CLI, see [`BuildContext`]
 /// documentation.
@@ -33,12 +36,17 @@ pub struct BuildDispatch<'a> {
     index: &'a InMemoryIndex,
     in_flight: &'a InFlight,
     setup_py: SetupPyStrategy,
+    build_isolation: BuildIsolation<'a>,
+    extra_build_requires: Vec<Requirement>,
+    build_constraints: Vec<Requirement>,
+    hash_database: HashDatabase,
     no_build: &'a NoBuild,
     no_binary: &'a NoBinary,
     config_settings: &'a ConfigSettings,
     source_build_context: SourceBuildContext,
     options: Options,
     build_extra_env_vars: FxHashMap<OsString, OsString>,
+    concurrency: Concurrency,
 }
 
 impl<'a> BuildDispatch<'a> {
@@ -65,12 +73,17 @@ impl<'a> BuildDispatch<'a> {
             index,
             in_flight,
             setup_py,
+            build_isolation: BuildIsolation::default(),
+            extra_build_requires: Vec::new(),
+            build_constraints: Vec::new(),
+            hash_database: HashDatabase::default(),
             config_settings,
             no_build,
             no_binary,
             source_build_context: SourceBuildContext::default(),
             options: Options::default(),
             build_extra_env_vars: FxHashMap::default(),
+            concurrency: Concurrency::default(),
         }
     }
 
@@ -80,6 +93,42 @@ impl<'a> BuildDispatch<'a> {
         self
     }
 
+    /// Set whether source distributions should be built in isolated environments.
+    #[must_use]
+    pub fn with_build_isolation(mut self, build_isolation: BuildIsolation<'a>) -> Self {
+        self.build_isolation = build_isolation;
+        self
+    }
+
+    /// Set additional requirements to install into each isolated build environment.
+    #[must_use]
+    pub fn with_extra_build_requires(mut self, extra_build_requires: Vec<Requirement>) -> Self {
+        self.extra_build_requires = extra_build_requires;
+        self
+    }
+
+    /// Set the constraints to apply when resolving the requirements of a build environment.
+    #[must_use]
+    pub fn with_build_constraints(mut self, build_constraints: Vec<Requirement>) -> Self {
+        self.build_constraints = build_constraints;
+        self
+    }
+
+    /// Set the database of expected hashes against which downloaded distributions are verified.
+    #[must_use]
+    pub fn with_hash_database(mut self, hash_database: HashDatabase) -> Self {
+        self.hash_database = hash_database;
+        self
+    }
+
+    /// Set the limits on the number of concurrent downloads, builds, and installs.
+    #[must_use]
+    pub fn with_concurrency(mut self, concurrency: Concurrency) -> Self {
+        self.concurrency = concurrency;
+        self.source_build_context = SourceBuildContext::new(concurrency.builds);
+        self
+    }
+
     /// Set the environment variables to be used when building a source distribution.
     #[must_use]
     pub fn with_build_extra_env_vars<I, K, V>(mut self, sdist_build_env_variables: I) -> Self
@@ -123,26 +172,37 @@ impl<'a> BuildContext for BuildDispatch<'a> {
         self.setup_py
     }
 
+    fn build_isolation(&self) -> BuildIsolation {
+        self.build_isolation
+    }
+
+    fn extra_build_requires(&self) -> &[Requirement] {
+        &self.extra_build_requires
+    }
+
+    fn hash_database(&self) -> &HashDatabase {
+        &self.hash_database
+    }
+
+    fn concurrency(&self) -> Concurrency {
+        self.concurrency
+    }
+
     async fn resolve<'data>(&'data self, requirements: &'data [Requirement]) -> Result<Resolution> {
-        let markers = self.interpreter.markers();
         let tags = self.interpreter.tags()?;
-        let resolver = Resolver::new(
-            Manifest::simple(requirements.to_vec()),
-            self.options,
-            markers,
-            self.interpreter,
-            tags,
-            self.client,
-            self.flat_index,
-            self.index,
-            self,
-        )?;
-        let graph = resolver.resolve().await.with_context(|| {
-            format!(
-                "No solution found when resolving: {}",
-                requirements.iter().map(ToString::to_string).join(", "),
-            )
-        })?;
+        let graph = ResolverBuilder::new(self.client, tags, self.index, self)
+            .requirements(requirements.to_vec())
+            .constraints(self.build_constraints.clone())
+            .options(self.options.clone())
+            .flat_index(self.flat_index)
+            .resolve()
+            .await
+            .with_context(|| {
+                format!(
+                    "No solution found when resolving: {}",
+                    requirements.iter().map(ToString::to_string).join(", "),
+                )
+            })?;
         Ok(Resolution::from(graph))
     }
 
@@ -181,15 +241,17 @@ impl<'a> BuildContext for BuildDispatch<'a> {
                 remote,
                 reinstalls,
                 extraneous,
-            } = Planner::with_requirements(&resolution.requirements()).build(
-                site_packages,
-                &Reinstall::None,
-                &NoBinary::None,
-                self.index_locations,
-                self.cache(),
-                venv,
-                tags,
-            )?;
+            } = Planner::with_requirements(&resolution.requirements())
+                .with_connectivity(self.client.connectivity())
+                .build(
+                    site_packages,
+                    &Reinstall::None,
+                    &NoBinary::None,
+                    self.index_locations,
+                    self.cache(),
+                    venv,
+                    tags,
+                )?;
 
             // Resolve any registry-based requirements.
             let remote = remote
@@ -245,7 +307,10 @@ impl<'a> BuildContext for BuildDispatch<'a> {
                     if wheels.len() == 1 { "" } else { "s" },
                     wheels.iter().map(ToString::to_string).join(", ")
                 );
+                // Mark every package as requested, such that seed packages (like `pip`) aren't
+                // mistaken for orphaned dependencies, e.g., by `uv pip autoremove`.
                 Installer::new(venv)
+                    .with_requested(wheels.iter().map(|wheel| wheel.name().clone()).collect())
                     .install(&wheels)
                     .context("Failed to install build dependencies")?;
             }
@@ -268,20 +333,16 @@ impl<'a> BuildContext for BuildDispatch<'a> {
             NoBuild::All => bail!("Building source distributions is disabled"),
             NoBuild::None => {}
             NoBuild::Packages(packages) => {
+                // We can only prevent builds by name for packages with names, which are unknown
+                // before building editables and local source trees (e.g., a `setup.py` passed to
+                // `pip compile`)
                 if let Some(dist) = dist {
-                    // We can only prevent builds by name for packages with names
-                    // which is unknown before build of editable source distributions
                     if packages.contains(dist.name()) {
                         bail!(
                             "Building source distributions for {} is disabled",
                             dist.name()
                         );
                     }
-                } else {
-                    debug_assert!(
-                        matches!(build_kind, BuildKind::Editable),
-                        "Only editable builds are exempt from 'no build' checks"
-                    );
                 }
             }
         }
@@ -5,21 +5,24 @@ use std::sync::Arc;
 
 use futures::{FutureExt, TryStreamExt};
 use tokio_util::compat::FuturesAsyncReadCompatExt;
-use tracing::{info_span, instrument, Instrument};
+use tracing::{debug, info_span, instrument, Instrument};
 use url::Url;
 
 use distribution_types::{
     BuiltDist, DirectGitUrl, Dist, FileLocation, IndexLocations, LocalEditable, Name, SourceDist,
 };
+use pep440_rs::Version;
 use platform_tags::Tags;
 use pypi_types::Metadata21;
-use uv_cache::{Cache, CacheBucket, Timestamp, WheelCache};
+use uv_cache::{Cache, CacheBucket, CacheEntry, Timestamp, WheelCache};
 use uv_client::{CacheControl, CachedClientError, Connectivity, RegistryClient};
 use uv_fs::metadata_if_exists;
 use uv_git::GitSource;
+use uv_normalize::PackageName;
 use uv_traits::{BuildContext, NoBinary, NoBuild};
 
 use crate::download::{BuiltWheel, UnzippedWheel};
+use crate::hash::{self, HashReader};
 use crate::locks::Locks;
 use crate::reporter::Facade;
 use crate::{DiskWheel, Error, LocalWheel, Reporter, SourceDistCachedBuilder};
@@ -87,6 +90,70 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
         }
     }
 
+    /// Invalidate a cached wheel whose digest is needed, but wasn't recorded alongside it (e.g.,
+    /// because it was cached by an older version of uv), such that it's downloaded (and hashed)
+    /// again, rather than failing verification.
+    async fn invalidate_unhashed(
+        &self,
+        needed: bool,
+        http_entry: &CacheEntry,
+        hash_entry: &CacheEntry,
+    ) -> Result<(), Error> {
+        if !needed || hash_entry.path().is_file() {
+            return Ok(());
+        }
+        debug!(
+            "Missing digest for cached wheel, downloading again: {}",
+            hash_entry.path().display()
+        );
+        match fs_err::tokio::remove_file(http_entry.path()).await {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(Error::CacheWrite(err)),
+        }
+    }
+
+    /// Verify the recorded digest of a downloaded wheel against the hash database, if the package
+    /// is listed.
+    async fn verify_digest(
+        &self,
+        name: &PackageName,
+        version: &Version,
+        hash_path: &Path,
+    ) -> Result<(), Error> {
+        let database = self.build_context.hash_database();
+        if database.expected(name, version).is_none() {
+            return Ok(());
+        }
+        let digest = fs_err::tokio::read_to_string(hash_path)
+            .await
+            .map_err(Error::CacheRead)?;
+        hash::verify_digest(database, name, version, digest.trim())
+    }
+
+    /// Verify the recorded digest of a wheel downloaded from a direct URL against the hash
+    /// database, if the URL is listed.
+    async fn verify_url_digest(&self, url: &Url, hash_path: &Path) -> Result<(), Error> {
+        let database = self.build_context.hash_database();
+        if database.expected_url(url.as_str()).is_none() {
+            return Ok(());
+        }
+        let digest = fs_err::tokio::read_to_string(hash_path)
+            .await
+            .map_err(Error::CacheRead)?;
+        hash::verify_url_digest(database, url, digest.trim())
+    }
+
+    /// Verify the digest of a local archive against the hash database, if its URL is listed.
+    async fn verify_path_digest(&self, url: &Url, path: &Path) -> Result<(), Error> {
+        let database = self.build_context.hash_database();
+        if database.expected_url(url.as_str()).is_none() {
+            return Ok(());
+        }
+        let digest = hash::file_digest(path).await.map_err(Error::CacheRead)?;
+        hash::verify_url_digest(database, url, &digest)
+    }
+
     /// Either fetch the wheel or fetch and build the source distribution
     ///
     /// If `no_remote_wheel` is set, the wheel will be built from a source distribution
@@ -159,24 +226,61 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
                     wheel.filename.stem(),
                 );
                 let http_entry = wheel_entry.with_file(format!("{}.http", wheel.filename.stem()));
+                let hash_entry = wheel_entry.with_file(format!("{}.sha256", wheel.filename.stem()));
 
                 let download = |response: reqwest::Response| {
                     async {
+                        let progress = self.reporter.as_ref().map(|reporter| {
+                            let id = reporter.on_download_start(&dist, response.content_length());
+                            (reporter, id)
+                        });
                         let reader = response
                             .bytes_stream()
+                            .inspect_ok(|chunk| {
+                                if let Some((reporter, id)) = &progress {
+                                    reporter.on_download_progress(*id, chunk.len() as u64);
+                                }
+                            })
                             .map_err(|err| self.handle_response_errors(err))
                             .into_async_read();
 
-                        // Download and unzip the wheel to a temporary directory.
+                        // Download and unzip the wheel to a temporary directory, hashing the
+                        // archive as it streams by.
                         let temp_dir =
                             tempfile::tempdir_in(self.cache.root()).map_err(Error::CacheWrite)?;
-                        uv_extract::stream::unzip(reader.compat(), temp_dir.path()).await?;
+                        let mut reader = HashReader::new(reader.compat());
+                        let files = uv_extract::stream::unzip(&mut reader, temp_dir.path()).await?;
+                        let digest = reader.finish().await.map_err(Error::CacheWrite)?;
+                        if let Some((reporter, id)) = progress {
+                            reporter.on_download_complete(id);
+                        }
+                        let verified = hash::matches_record(temp_dir.path(), &files);
 
                         // Persist the temporary directory to the directory store.
                         let archive = self
                             .cache
                             .persist(temp_dir.into_path(), wheel_entry.path())
                             .map_err(Error::CacheRead)?;
+
+                        // Record that the files were verified against the `RECORD` as they were
+                        // unzipped, such that they needn't be hashed again at install time.
+                        if verified {
+                            fs_err::tokio::write(
+                                Cache::record_marker(&archive).map_err(Error::CacheRead)?,
+                                "",
+                            )
+                            .await
+                            .map_err(Error::CacheWrite)?;
+                        } else {
+                            debug!("Wheel doesn't match its RECORD: {wheel}");
+                        }
+
+                        // Record the digest of the downloaded archive, so that the cache can later
+                        // be audited against the index.
+                        fs_err::tokio::write(hash_entry.path(), digest)
+                            .await
+                            .map_err(Error::CacheWrite)?;
+
                         Ok(archive)
                     }
                     .instrument(info_span!("download", wheel = %wheel))
@@ -192,6 +296,16 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
                     Connectivity::Offline => CacheControl::AllowStale,
                 };
 
+                let database = self.build_context.hash_database();
+                self.invalidate_unhashed(
+                    database
+                        .expected(wheel.name(), &wheel.filename.version)
+                        .is_some(),
+                    &http_entry,
+                    &hash_entry,
+                )
+                .await?;
+
                 let archive = self
                     .client
                     .cached_client()
@@ -202,6 +316,9 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
                         CachedClientError::Client(err) => Error::Client(err),
                     })?;
 
+                self.verify_digest(wheel.name(), &wheel.filename.version, hash_entry.path())
+                    .await?;
+
                 Ok(LocalWheel::Unzipped(UnzippedWheel {
                     dist: dist.clone(),
                     archive,
@@ -221,24 +338,61 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
                     wheel.filename.stem(),
                 );
                 let http_entry = wheel_entry.with_file(format!("{}.http", wheel.filename.stem()));
+                let hash_entry = wheel_entry.with_file(format!("{}.sha256", wheel.filename.stem()));
 
                 let download = |response: reqwest::Response| {
                     async {
+                        let progress = self.reporter.as_ref().map(|reporter| {
+                            let id = reporter.on_download_start(&dist, response.content_length());
+                            (reporter, id)
+                        });
                         let reader = response
                             .bytes_stream()
+                            .inspect_ok(|chunk| {
+                                if let Some((reporter, id)) = &progress {
+                                    reporter.on_download_progress(*id, chunk.len() as u64);
+                                }
+                            })
                             .map_err(|err| self.handle_response_errors(err))
                             .into_async_read();
 
-                        // Download and unzip the wheel to a temporary directory.
+                        // Download and unzip the wheel to a temporary directory, hashing the
+                        // archive as it streams by.
                         let temp_dir =
                             tempfile::tempdir_in(self.cache.root()).map_err(Error::CacheWrite)?;
-                        uv_extract::stream::unzip(reader.compat(), temp_dir.path()).await?;
+                        let mut reader = HashReader::new(reader.compat());
+                        let files = uv_extract::stream::unzip(&mut reader, temp_dir.path()).await?;
+                        let digest = reader.finish().await.map_err(Error::CacheWrite)?;
+                        if let Some((reporter, id)) = progress {
+                            reporter.on_download_complete(id);
+                        }
+                        let verified = hash::matches_record(temp_dir.path(), &files);
 
                         // Persist the temporary directory to the directory store.
                         let archive = self
                             .cache
                             .persist(temp_dir.into_path(), wheel_entry.path())
                             .map_err(Error::CacheRead)?;
+
+                        // Record that the files were verified against the `RECORD` as they were
+                        // unzipped, such that they needn't be hashed again at install time.
+                        if verified {
+                            fs_err::tokio::write(
+                                Cache::record_marker(&archive).map_err(Error::CacheRead)?,
+                                "",
+                            )
+                            .await
+                            .map_err(Error::CacheWrite)?;
+                        } else {
+                            debug!("Wheel doesn't match its RECORD: {wheel}");
+                        }
+
+                        // Record the digest of the downloaded archive, so that it can be verified
+                        // even when the archive is later served from the cache.
+                        fs_err::tokio::write(hash_entry.path(), digest)
+                            .await
+                            .map_err(Error::CacheWrite)?;
+
                         Ok(archive)
                     }
                     .instrument(info_span!("download", wheel = %wheel))
@@ -258,6 +412,18 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
                     ),
                     Connectivity::Offline => CacheControl::AllowStale,
                 };
+
+                let database = self.build_context.hash_database();
+                self.invalidate_unhashed(
+                    database
+                        .expected(wheel.name(), &wheel.filename.version)
+                        .is_some()
+                        || database.expected_url(wheel.url.raw().as_str()).is_some(),
+                    &http_entry,
+                    &hash_entry,
+                )
+                .await?;
+
                 let archive = self
                     .client
                     .cached_client()
@@ -268,6 +434,11 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
                         CachedClientError::Client(err) => Error::Client(err),
                     })?;
 
+                self.verify_digest(wheel.name(), &wheel.filename.version, hash_entry.path())
+                    .await?;
+                self.verify_url_digest(wheel.url.raw(), hash_entry.path())
+                    .await?;
+
                 Ok(LocalWheel::Unzipped(UnzippedWheel {
                     dist: dist.clone(),
                     archive,
@@ -280,6 +451,9 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
                     return Err(Error::NoBinary);
                 }
 
+                self.verify_path_digest(wheel.url.raw(), &wheel.path)
+                    .await?;
+
                 let cache_entry = self.cache.entry(
                     CacheBucket::Wheels,
                     WheelCache::Url(&wheel.url).remote_wheel_dir(wheel.name().as_ref()),
@@ -318,6 +492,15 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
             }
 
             Dist::Source(source_dist) => {
+                // Local source archives are verified on every build, since they may have changed
+                // since they were last built.
+                if let SourceDist::Path(path_source_dist) = source_dist {
+                    if path_source_dist.path.is_file() {
+                        self.verify_path_digest(path_source_dist.url.raw(), &path_source_dist.path)
+                            .await?;
+                    }
+                }
+
                 let lock = self.locks.acquire(&dist).await;
                 let _guard = lock.lock().await;
 
@@ -412,6 +595,18 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
         Ok((LocalWheel::Built(built_wheel), metadata))
     }
 
+    /// Build the metadata for a local source tree (e.g., a directory containing a `setup.py`).
+    pub async fn build_source_tree_metadata(
+        &self,
+        source_tree: &Path,
+        wheel_dir: &Path,
+    ) -> Result<Metadata21, Error> {
+        self.builder
+            .build_source_tree_metadata(source_tree, wheel_dir)
+            .boxed()
+            .await
+    }
+
     /// Given a remote source distribution, return a precise variant, if possible.
     ///
     /// For example, given a Git dependency with a reference to a branch or tag, return a URL
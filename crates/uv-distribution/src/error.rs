@@ -1,7 +1,9 @@
 use tokio::task::JoinError;
+use url::Url;
 use zip::result::ZipError;
 
 use distribution_filename::WheelFilenameError;
+use pep440_rs::Version;
 use uv_client::BetterReqwestError;
 use uv_normalize::PackageName;
 
@@ -57,6 +59,26 @@ pub enum Error {
     #[error("Failed to extract source distribution")]
     Extract(#[from] uv_extract::Error),
 
+    // Hash verification error
+    #[error("Hash mismatch for `{name}=={version}`: expected {}, but the downloaded distribution has `sha256:{actual}`", format_hashes(expected))]
+    HashMismatch {
+        name: PackageName,
+        version: Version,
+        expected: Vec<String>,
+        actual: String,
+    },
+    #[error("`{0}=={1}` is not permitted by the hash database")]
+    HashUnlisted(PackageName, Version),
+    #[error(
+        "Hash mismatch for `{url}`: expected {}, but the distribution has `sha256:{actual}`",
+        format_hashes(expected)
+    )]
+    UrlHashMismatch {
+        url: Url,
+        expected: Vec<String>,
+        actual: String,
+    },
+
     /// Should not occur; only seen when another task panicked.
     #[error("The task executor is broken, did some other task panic?")]
     Join(#[from] JoinError),
@@ -67,3 +89,17 @@ impl From<reqwest::Error> for Error {
         Self::Reqwest(BetterReqwestError::from(error))
     }
 }
+
+/// Format a list of SHA-256 digests for display, e.g., ``one of `sha256:...`, `sha256:...` ``.
+fn format_hashes(hashes: &[String]) -> String {
+    let formatted = hashes
+        .iter()
+        .map(|hash| format!("`sha256:{hash}`"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    if hashes.len() == 1 {
+        formatted
+    } else {
+        format!("one of {formatted}")
+    }
+}
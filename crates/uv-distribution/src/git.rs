@@ -0,0 +1,43 @@
+use std::path::Path;
+
+use url::Url;
+
+use distribution_types::DirectGitUrl;
+use uv_cache::{Cache, CacheBucket};
+use uv_fs::LockedFile;
+use uv_git::{GitSha, GitSource};
+
+use crate::error::Error;
+
+/// Acquire the lock that guards fetches of the Git repository at the given URL into the cache,
+/// to avoid races between processes.
+pub(crate) fn lock_git(git_dir: &Path, url: &Url) -> Result<LockedFile, Error> {
+    let lock_dir = git_dir.join("locks");
+    fs_err::create_dir_all(&lock_dir).map_err(Error::CacheWrite)?;
+    let canonical_url = cache_key::CanonicalUrl::new(url);
+    LockedFile::acquire(
+        lock_dir.join(cache_key::digest(&canonical_url)),
+        &canonical_url,
+    )
+    .map_err(Error::CacheWrite)
+}
+
+/// Resolve the reference of a Git URL (a branch, tag, partial commit, etc.) to the commit it
+/// currently points to, by fetching the repository into the cache.
+///
+/// Blocks the current thread while fetching, holding the same lock as source distribution
+/// fetches of the same URL.
+pub fn resolve_precise(url: &Url, cache: &Cache) -> Result<GitSha, Error> {
+    let DirectGitUrl { url: git, .. } = DirectGitUrl::try_from(url).map_err(Error::Git)?;
+    if let Some(precise) = git.precise() {
+        return Ok(precise);
+    }
+
+    let git_dir = cache.bucket(CacheBucket::Git);
+    let _lock = lock_git(&git_dir, url)?;
+    let fetch = GitSource::new(git, git_dir).fetch().map_err(Error::Git)?;
+    fetch
+        .into_git()
+        .precise()
+        .ok_or_else(|| Error::Git(anyhow::anyhow!("Failed to resolve Git reference: {url}")))
+}
@@ -0,0 +1,154 @@
+use std::path::Path;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use data_encoding::BASE64URL_NOPAD;
+use rustc_hash::FxHashMap;
+use sha2::{Digest, Sha256};
+use tokio::io::{AsyncRead, ReadBuf};
+use url::Url;
+
+use pep440_rs::Version;
+use pypi_types::RecordEntry;
+use uv_extract::stream::ExtractedFile;
+use uv_normalize::PackageName;
+use uv_traits::HashDatabase;
+
+use crate::Error;
+
+/// A reader that computes the SHA-256 digest of the bytes that are read through it.
+pub(crate) struct HashReader<R> {
+    reader: R,
+    hasher: Sha256,
+}
+
+impl<R: AsyncRead + Unpin> HashReader<R> {
+    pub(crate) fn new(reader: R) -> Self {
+        Self {
+            reader,
+            hasher: Sha256::new(),
+        }
+    }
+
+    /// Exhaust the underlying reader, and return the hex-encoded digest of everything read.
+    ///
+    /// Readers like the streaming unzipper may stop before the end of the stream (e.g., before
+    /// the end of the central directory), so the remainder is consumed before finalizing.
+    pub(crate) async fn finish(mut self) -> std::io::Result<String> {
+        tokio::io::copy(&mut self, &mut tokio::io::sink()).await?;
+        Ok(format!("{:x}", self.hasher.finalize()))
+    }
+}
+
+impl<R: AsyncRead + Unpin> AsyncRead for HashReader<R> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        let this = self.get_mut();
+        let start = buf.filled().len();
+        let result = Pin::new(&mut this.reader).poll_read(cx, buf);
+        if let Poll::Ready(Ok(())) = result {
+            this.hasher.update(&buf.filled()[start..]);
+        }
+        result
+    }
+}
+
+/// Verify the SHA-256 digest of a downloaded distribution against the [`HashDatabase`].
+///
+/// Distributions of packages that are absent from the database are always permitted.
+pub(crate) fn verify_digest(
+    database: &HashDatabase,
+    name: &PackageName,
+    version: &Version,
+    digest: &str,
+) -> Result<(), Error> {
+    let Some(expected) = database.expected(name, version) else {
+        return Ok(());
+    };
+    if expected.is_empty() {
+        return Err(Error::HashUnlisted(name.clone(), version.clone()));
+    }
+    if !expected
+        .iter()
+        .any(|expected| expected.eq_ignore_ascii_case(digest))
+    {
+        return Err(Error::HashMismatch {
+            name: name.clone(),
+            version: version.clone(),
+            expected: expected.to_vec(),
+            actual: digest.to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Verify the SHA-256 digest of a direct URL or local archive against the [`HashDatabase`].
+///
+/// Distributions whose URLs are absent from the database are always permitted.
+pub(crate) fn verify_url_digest(
+    database: &HashDatabase,
+    url: &Url,
+    digest: &str,
+) -> Result<(), Error> {
+    let Some(expected) = database.expected_url(url.as_str()) else {
+        return Ok(());
+    };
+    if !expected
+        .iter()
+        .any(|expected| expected.eq_ignore_ascii_case(digest))
+    {
+        return Err(Error::UrlHashMismatch {
+            url: url.clone(),
+            expected: expected.to_vec(),
+            actual: digest.to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Compute the hex-encoded SHA-256 digest of a local file.
+pub(crate) async fn file_digest(path: &Path) -> std::io::Result<String> {
+    let file = fs_err::tokio::File::open(path).await?;
+    HashReader::new(file).finish().await
+}
+
+/// Returns `true` if the files extracted from a wheel match the hashes and sizes in its `RECORD`.
+///
+/// Like the verification at install time, entries without a hash, or with a hash other than
+/// SHA-256, are skipped.
+pub(crate) fn matches_record(target: &Path, files: &[ExtractedFile]) -> bool {
+    let Some(record) = files.iter().find(|file| {
+        file.path
+            .strip_suffix("/RECORD")
+            .is_some_and(|dist_info| dist_info.ends_with(".dist-info") && !dist_info.contains('/'))
+    }) else {
+        return false;
+    };
+    let Ok(reader) = fs_err::File::open(target.join(&record.path)) else {
+        return false;
+    };
+    let Ok(entries) = RecordEntry::read_all(reader) else {
+        return false;
+    };
+
+    let files = files
+        .iter()
+        .map(|file| (file.path.as_str(), file))
+        .collect::<FxHashMap<_, _>>();
+    entries.iter().all(|entry| {
+        let Some(expected) = entry
+            .hash
+            .as_deref()
+            .and_then(|hash| hash.strip_prefix("sha256="))
+        else {
+            return true;
+        };
+        files.get(entry.path.as_str()).is_some_and(|file| {
+            BASE64URL_NOPAD.encode(&file.sha256) == expected
+                && entry.size.map_or(true, |size| size == file.size)
+        })
+    })
+}
@@ -90,7 +90,12 @@ impl<'a> RegistryWheelIndex<'a> {
             })
             .collect();
 
-        for index_url in index_locations.indexes().chain(flat_index_urls.iter()) {
+        // Include the cached wheels from the configured indexes even if `--no-index` is set, such
+        // that previously downloaded distributions can be installed without any index lookups.
+        for index_url in index_locations
+            .cached_indexes()
+            .chain(flat_index_urls.iter())
+        {
             // Index all the wheels that were downloaded directly from the registry.
             let wheel_dir = cache.shard(
                 CacheBucket::Wheels,
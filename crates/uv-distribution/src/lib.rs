@@ -1,6 +1,7 @@
 pub use distribution_database::DistributionDatabase;
 pub use download::{BuiltWheel, DiskWheel, LocalWheel};
 pub use error::Error;
+pub use git::resolve_precise;
 pub use index::{BuiltWheelIndex, RegistryWheelIndex};
 pub use reporter::Reporter;
 pub use source::SourceDistCachedBuilder;
@@ -9,6 +10,8 @@ pub use unzip::Unzip;
 mod distribution_database;
 mod download;
 mod error;
+mod git;
+mod hash;
 mod index;
 mod locks;
 mod reporter;
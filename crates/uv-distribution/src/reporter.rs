@@ -2,7 +2,7 @@ use std::sync::Arc;
 
 use url::Url;
 
-use distribution_types::SourceDist;
+use distribution_types::{Dist, SourceDist};
 
 pub trait Reporter: Send + Sync {
     /// Callback to invoke when a source distribution build is kicked off.
@@ -16,6 +16,15 @@ pub trait Reporter: Send + Sync {
 
     /// Callback to invoke when a repository checkout completes.
     fn on_checkout_complete(&self, url: &Url, rev: &str, index: usize);
+
+    /// Callback to invoke when a wheel download begins, with the size of the wheel, if known.
+    fn on_download_start(&self, dist: &Dist, size: Option<u64>) -> usize;
+
+    /// Callback to invoke when a chunk of a wheel download is received.
+    fn on_download_progress(&self, id: usize, bytes: u64);
+
+    /// Callback to invoke when a wheel download completes.
+    fn on_download_complete(&self, id: usize);
 }
 
 /// A facade for converting from [`Reporter`] to [`uv_git::Reporter`].
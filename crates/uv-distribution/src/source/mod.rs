@@ -28,11 +28,13 @@ use uv_cache::{
 use uv_client::{
     CacheControl, CachedClientError, Connectivity, DataWithCachePolicy, RegistryClient,
 };
-use uv_fs::{write_atomic, LockedFile};
+use uv_fs::write_atomic;
 use uv_git::{Fetch, GitSource};
 use uv_traits::{BuildContext, BuildKind, NoBuild, SourceBuildTrait};
 
 use crate::error::Error;
+use crate::git::lock_git;
+use crate::hash::{verify_digest, verify_url_digest, HashReader};
 use crate::reporter::Facade;
 use crate::source::built_wheel_metadata::BuiltWheelMetadata;
 use crate::source::manifest::Manifest;
@@ -88,10 +90,12 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
                 let DirectArchiveUrl { url, subdirectory } =
                     DirectArchiveUrl::from(direct_url_source_dist.url.raw());
 
-                // For direct URLs, cache directly under the hash of the URL itself.
+                // For direct URLs, cache directly under the hash of the URL itself, including any
+                // `#subdirectory=` fragment, since each subdirectory is a distinct package.
                 let cache_shard = self.build_context.cache().shard(
                     CacheBucket::BuiltWheels,
-                    WheelCache::Url(&url).remote_wheel_dir(direct_url_source_dist.name().as_ref()),
+                    WheelCache::Url(direct_url_source_dist.url.raw())
+                        .remote_wheel_dir(direct_url_source_dist.name().as_ref()),
                 );
 
                 self.url(
@@ -169,10 +173,12 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
                 let DirectArchiveUrl { url, subdirectory } =
                     DirectArchiveUrl::from(direct_url_source_dist.url.raw());
 
-                // For direct URLs, cache directly under the hash of the URL itself.
+                // For direct URLs, cache directly under the hash of the URL itself, including any
+                // `#subdirectory=` fragment, since each subdirectory is a distinct package.
                 let cache_shard = self.build_context.cache().shard(
                     CacheBucket::BuiltWheels,
-                    WheelCache::Url(&url).remote_wheel_dir(direct_url_source_dist.name().as_ref()),
+                    WheelCache::Url(direct_url_source_dist.url.raw())
+                        .remote_wheel_dir(direct_url_source_dist.name().as_ref()),
                 );
 
                 self.url_metadata(
@@ -475,9 +481,12 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
                 .remote_wheel_dir(path_source_dist.name().as_ref()),
         );
 
+        // Determine the path to build, and the subdirectory within it.
+        let (source_path, subdirectory) = local_source(path_source_dist);
+
         // Determine the last-modified time of the source distribution.
         let Some(modified) =
-            ArchiveTimestamp::from_path(&path_source_dist.path).map_err(Error::CacheRead)?
+            ArchiveTimestamp::from_source_tree(&source_path).map_err(Error::CacheRead)?
         else {
             return Err(Error::DirWithoutEntrypoint);
         };
@@ -510,7 +519,12 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
             .map(|reporter| reporter.on_build_start(source_dist));
 
         let (disk_filename, filename, metadata) = self
-            .build_source_dist(source_dist, &path_source_dist.path, None, &cache_shard)
+            .build_source_dist(
+                source_dist,
+                &source_path,
+                subdirectory.as_deref(),
+                &cache_shard,
+            )
             .await?;
 
         if let Some(task) = task {
@@ -547,9 +561,12 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
                 .remote_wheel_dir(path_source_dist.name().as_ref()),
         );
 
+        // Determine the path to build, and the subdirectory within it.
+        let (source_path, subdirectory) = local_source(path_source_dist);
+
         // Determine the last-modified time of the source distribution.
         let Some(modified) =
-            ArchiveTimestamp::from_path(&path_source_dist.path).map_err(Error::CacheRead)?
+            ArchiveTimestamp::from_source_tree(&source_path).map_err(Error::CacheRead)?
         else {
             return Err(Error::DirWithoutEntrypoint);
         };
@@ -586,7 +603,7 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
 
         // If the backend supports `prepare_metadata_for_build_wheel`, use it.
         if let Some(metadata) = self
-            .build_source_dist_metadata(source_dist, &path_source_dist.path, None)
+            .build_source_dist_metadata(source_dist, &source_path, subdirectory.as_deref())
             .boxed()
             .await?
         {
@@ -609,7 +626,12 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
             .map(|reporter| reporter.on_build_start(source_dist));
 
         let (_disk_filename, _filename, metadata) = self
-            .build_source_dist(source_dist, &path_source_dist.path, None, &cache_shard)
+            .build_source_dist(
+                source_dist,
+                &source_path,
+                subdirectory.as_deref(),
+                &cache_shard,
+            )
             .await?;
 
         if let Some(task) = task {
@@ -783,9 +805,34 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
             .bytes_stream()
             .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))
             .into_async_read();
-        uv_extract::stream::archive(reader.compat(), filename, temp_dir.path()).await?;
+        let mut reader = HashReader::new(reader.compat());
+        uv_extract::stream::archive(
+            tokio::io::BufReader::new(&mut reader),
+            filename,
+            temp_dir.path(),
+        )
+        .await?;
+        let digest = reader.finish().await.map_err(Error::CacheWrite)?;
         drop(span);
 
+        // If the package is listed in the hash database, verify the digest of the downloaded
+        // archive before persisting it. The version of a direct URL source distribution isn't
+        // known until it's built, so direct URL source distributions are verified by URL instead.
+        match source_dist {
+            SourceDist::Registry(dist) => {
+                verify_digest(
+                    self.build_context.hash_database(),
+                    &dist.filename.name,
+                    &dist.filename.version,
+                    &digest,
+                )?;
+            }
+            SourceDist::DirectUrl(dist) => {
+                verify_url_digest(self.build_context.hash_database(), dist.url.raw(), &digest)?;
+            }
+            SourceDist::Git(_) | SourceDist::Path(_) => {}
+        }
+
         // Extract the top-level directory.
         let extracted = match uv_extract::strip_component(temp_dir.path()) {
             Ok(top_level) => top_level,
@@ -810,16 +857,7 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
         let git_dir = self.build_context.cache().bucket(CacheBucket::Git);
 
         // Avoid races between different processes, too.
-        let lock_dir = git_dir.join("locks");
-        fs::create_dir_all(&lock_dir)
-            .await
-            .map_err(Error::CacheWrite)?;
-        let canonical_url = cache_key::CanonicalUrl::new(url);
-        let _lock = LockedFile::acquire(
-            lock_dir.join(cache_key::digest(&canonical_url)),
-            &canonical_url,
-        )
-        .map_err(Error::CacheWrite)?;
+        let _lock = lock_git(&git_dir, url)?;
 
         let DirectGitUrl { url, subdirectory } = DirectGitUrl::try_from(url).map_err(Error::Git)?;
 
@@ -976,6 +1014,66 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
         debug!("Finished building (editable): {dist}");
         Ok((dist, disk_filename, filename, metadata))
     }
+
+    /// Build the metadata for a local source tree (e.g., a directory containing a `setup.py`),
+    /// for which the package name isn't known upfront.
+    ///
+    /// If the build backend doesn't support `prepare_metadata_for_build_wheel`, the source tree is
+    /// built into a wheel in `wheel_dir`, and the metadata is read from the built wheel.
+    pub async fn build_source_tree_metadata(
+        &self,
+        source_tree: &Path,
+        wheel_dir: &Path,
+    ) -> Result<Metadata21, Error> {
+        let id = source_tree.display().to_string();
+        debug!("Preparing metadata for source tree: {id}");
+
+        // Setup the builder.
+        let mut builder = self
+            .build_context
+            .setup_build(source_tree, None, &id, None, BuildKind::Wheel)
+            .await
+            .map_err(|err| Error::Build(id.clone(), err))?;
+
+        // Build the metadata, if the backend supports it.
+        if let Some(dist_info) = builder
+            .metadata()
+            .await
+            .map_err(|err| Error::Build(id.clone(), err))?
+        {
+            let content = fs::read(dist_info.join("METADATA"))
+                .await
+                .map_err(Error::CacheRead)?;
+            return Ok(Metadata21::parse(&content)?);
+        }
+
+        // Otherwise, build a wheel and read its metadata.
+        let disk_filename = builder
+            .wheel(wheel_dir)
+            .await
+            .map_err(|err| Error::Build(id.clone(), err))?;
+        let filename = WheelFilename::from_str(&disk_filename)?;
+        let metadata = read_wheel_metadata(&filename, wheel_dir.join(&disk_filename))?;
+
+        debug!("Finished building metadata for source tree: {id}");
+        Ok(metadata)
+    }
+}
+
+/// Determine the path to build for a local source distribution, along with the subdirectory to
+/// build within it, as given by a `#subdirectory=` fragment on its URL.
+///
+/// For a directory, the subdirectory is joined onto the path directly, such that the package's own
+/// `pyproject.toml` (or `setup.py`) is used to determine freshness. For an archive, the subdirectory
+/// is applied after the archive is unpacked.
+fn local_source(path_source_dist: &PathSourceDist) -> (PathBuf, Option<PathBuf>) {
+    let DirectArchiveUrl { subdirectory, .. } = DirectArchiveUrl::from(path_source_dist.url.raw());
+    match subdirectory {
+        Some(subdirectory) if path_source_dist.path.is_dir() => {
+            (path_source_dist.path.join(subdirectory), None)
+        }
+        subdirectory => (path_source_dist.path.clone(), subdirectory),
+    }
 }
 
 /// Read an existing HTTP-cached [`Manifest`], if it exists.
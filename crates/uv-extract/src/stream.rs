@@ -3,30 +3,50 @@ use std::pin::Pin;
 
 use futures::StreamExt;
 use rustc_hash::FxHashSet;
+use sha2::{Digest, Sha256};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio_util::compat::{FuturesAsyncReadCompatExt, TokioAsyncReadCompatExt};
 use tracing::warn;
 
 use crate::Error;
 
+/// The maximum size of the buffer used when writing each extracted file to disk.
+const MAX_BUFFER_SIZE: usize = 1024 * 1024;
+
+/// A file that was extracted from a `.zip` archive.
+#[derive(Debug, Clone)]
+pub struct ExtractedFile {
+    /// The path to the file, relative to the target directory, as given in the archive.
+    pub path: String,
+    /// The size of the file, in bytes.
+    pub size: u64,
+    /// The SHA-256 digest of the file's contents.
+    pub sha256: Vec<u8>,
+}
+
 /// Unzip a `.zip` archive into the target directory, without requiring `Seek`.
 ///
 /// This is useful for unzipping files as they're being downloaded. If the archive
 /// is already fully on disk, consider using `unzip_archive`, which can use multiple
 /// threads to work faster in that case.
+///
+/// Each file is hashed as it's written, such that callers can verify the contents (e.g., against
+/// a wheel's `RECORD`) without reading the files back from disk.
 pub async fn unzip<R: tokio::io::AsyncRead + Unpin>(
     reader: R,
     target: impl AsRef<Path>,
-) -> Result<(), Error> {
+) -> Result<Vec<ExtractedFile>, Error> {
     let target = target.as_ref();
     let mut reader = reader.compat();
     let mut zip = async_zip::base::read::stream::ZipFileReader::new(&mut reader);
 
     let mut directories = FxHashSet::default();
+    let mut files = Vec::new();
 
     while let Some(mut entry) = zip.next_with_entry().await? {
         // Construct the (expected) path to the file on-disk.
-        let path = entry.reader().entry().filename().as_str()?;
-        let path = target.join(path);
+        let name = entry.reader().entry().filename().as_str()?.to_string();
+        let path = target.join(&name);
         let is_dir = entry.reader().entry().dir()?;
 
         // Either create the directory or write the file to disk.
@@ -43,14 +63,30 @@ pub async fn unzip<R: tokio::io::AsyncRead + Unpin>(
 
             // We don't know the file permissions here, because we haven't seen the central directory yet.
             let file = fs_err::tokio::File::create(&path).await?;
-            let mut writer =
-                if let Ok(size) = usize::try_from(entry.reader().entry().uncompressed_size()) {
-                    tokio::io::BufWriter::with_capacity(size, file)
-                } else {
-                    tokio::io::BufWriter::new(file)
-                };
+            // Size the buffer to the file, up to a limit, such that large files (e.g., shared
+            // libraries) are written out as they're decompressed, rather than held in memory.
+            let capacity = usize::try_from(entry.reader().entry().uncompressed_size())
+                .map_or(MAX_BUFFER_SIZE, |size| size.min(MAX_BUFFER_SIZE));
+            let mut writer = tokio::io::BufWriter::with_capacity(capacity, file);
             let mut reader = entry.reader_mut().compat();
-            tokio::io::copy(&mut reader, &mut writer).await?;
+            let mut hasher = Sha256::new();
+            let mut size = 0u64;
+            let mut buf = vec![0; 8 * 1024];
+            loop {
+                let len = reader.read(&mut buf).await?;
+                if len == 0 {
+                    break;
+                }
+                hasher.update(&buf[..len]);
+                writer.write_all(&buf[..len]).await?;
+                size += len as u64;
+            }
+            writer.flush().await?;
+            files.push(ExtractedFile {
+                path: name,
+                size,
+                sha256: hasher.finalize().to_vec(),
+            });
         }
 
         // Close current file to get access to the next one. See docs:
@@ -97,7 +133,7 @@ pub async fn unzip<R: tokio::io::AsyncRead + Unpin>(
         }
     }
 
-    Ok(())
+    Ok(files)
 }
 
 /// Unpack the given tar archive into the destination directory.
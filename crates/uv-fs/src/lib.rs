@@ -218,33 +218,162 @@ pub fn files(path: impl AsRef<Path>) -> impl Iterator<Item = PathBuf> {
 
 /// A file lock that is automatically released when dropped.
 #[derive(Debug)]
-pub struct LockedFile(fs_err::File);
+pub struct LockedFile {
+    file: fs_err::File,
+    /// Whether the lock is held exclusively, in which case the file records the holder's process
+    /// ID.
+    exclusive: bool,
+}
 
 impl LockedFile {
+    /// Acquire an exclusive lock on the file at the given path, waiting for any other process
+    /// that holds a lock on the file to release it.
+    ///
+    /// If `UV_LOCK_TIMEOUT` is set to a number of seconds, returns an error if the lock can't be
+    /// acquired within that time.
     pub fn acquire(path: impl AsRef<Path>, resource: impl Display) -> Result<Self, std::io::Error> {
-        let file = fs_err::File::create(path.as_ref())?;
-        match file.file().try_lock_exclusive() {
-            Ok(()) => Ok(Self(file)),
+        Self::lock(path.as_ref(), resource, false, lock_timeout())
+    }
+
+    /// Acquire a shared lock on the file at the given path, which may be held by multiple
+    /// processes at once, but excludes any exclusive lock.
+    ///
+    /// As with [`LockedFile::acquire`], `UV_LOCK_TIMEOUT` limits the time spent waiting.
+    pub fn acquire_shared(
+        path: impl AsRef<Path>,
+        resource: impl Display,
+    ) -> Result<Self, std::io::Error> {
+        Self::lock(path.as_ref(), resource, true, lock_timeout())
+    }
+
+    fn lock(
+        path: &Path,
+        resource: impl Display,
+        shared: bool,
+        timeout: Option<std::time::Duration>,
+    ) -> Result<Self, std::io::Error> {
+        // Avoid truncating the file, which would discard the process ID of the current holder.
+        let mut file = fs_err::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .open(path)?;
+
+        let try_lock = |file: &fs_err::File| {
+            if shared {
+                file.file().try_lock_shared()
+            } else {
+                file.file().try_lock_exclusive()
+            }
+        };
+
+        match try_lock(&file) {
+            Ok(()) => {}
             Err(err) if err.kind() == std::io::ErrorKind::WouldBlock => {
-                warn_user!(
-                    "Waiting to acquire lock for {} (lockfile: {})",
-                    resource,
-                    path.simplified_display(),
-                );
-                file.file().lock_exclusive()?;
-                Ok(Self(file))
+                // Identify the process holding the lock, if it's held exclusively. (Shared holders
+                // don't record their IDs, since there may be several of them.)
+                let holder = std::io::read_to_string(&mut file)
+                    .ok()
+                    .and_then(|contents| contents.trim().parse::<u32>().ok());
+                if let Some(pid) = holder {
+                    warn_user!(
+                        "Waiting to acquire lock for {} held by process {pid} (lockfile: {})",
+                        resource,
+                        path.simplified_display(),
+                    );
+                } else {
+                    warn_user!(
+                        "Waiting to acquire lock for {} (lockfile: {})",
+                        resource,
+                        path.simplified_display(),
+                    );
+                }
+
+                match timeout {
+                    Some(timeout) => {
+                        let start = std::time::Instant::now();
+                        loop {
+                            match try_lock(&file) {
+                                Ok(()) => break,
+                                Err(err) if err.kind() == std::io::ErrorKind::WouldBlock => {
+                                    if start.elapsed() >= timeout {
+                                        return Err(std::io::Error::new(
+                                            std::io::ErrorKind::TimedOut,
+                                            format!(
+                                                "Timed out after {}s waiting to acquire lock for {} (lockfile: {})",
+                                                timeout.as_secs(),
+                                                resource,
+                                                path.simplified_display(),
+                                            ),
+                                        ));
+                                    }
+                                    std::thread::sleep(std::time::Duration::from_millis(100));
+                                }
+                                Err(err) => return Err(err),
+                            }
+                        }
+                    }
+                    None => {
+                        if shared {
+                            file.file().lock_shared()?;
+                        } else {
+                            file.file().lock_exclusive()?;
+                        }
+                    }
+                }
             }
-            Err(err) => Err(err),
+            Err(err) => return Err(err),
+        }
+
+        // For an exclusive lock, record our process ID, such that any other process waiting on the
+        // lock can report it. For a shared lock, clear the ID of any previous exclusive holder
+        // (e.g., one that exited without releasing the lock cleanly).
+        {
+            use std::io::{Seek, Write};
+
+            file.file().set_len(0)?;
+            if !shared {
+                file.rewind()?;
+                write!(file, "{}", std::process::id())?;
+                file.flush()?;
+            }
+        }
+
+        Ok(Self {
+            file,
+            exclusive: !shared,
+        })
+    }
+}
+
+/// Read the maximum time to wait for a lock from `UV_LOCK_TIMEOUT`, in seconds.
+fn lock_timeout() -> Option<std::time::Duration> {
+    let value = std::env::var("UV_LOCK_TIMEOUT").ok()?;
+    match value.parse::<u64>() {
+        Ok(seconds) => Some(std::time::Duration::from_secs(seconds)),
+        Err(_) => {
+            warn!("Ignoring invalid value for UV_LOCK_TIMEOUT. Expected integer number of seconds, got \"{value}\".");
+            None
         }
     }
 }
 
 impl Drop for LockedFile {
     fn drop(&mut self) {
-        if let Err(err) = self.0.file().unlock() {
+        // Clear our process ID before releasing the lock, such that it isn't reported as the
+        // holder of a lock that's since been acquired by shared holders.
+        if self.exclusive {
+            if let Err(err) = self.file.file().set_len(0) {
+                warn!(
+                    "Failed to clear the lock holder in {}: {err}",
+                    self.file.path().display()
+                );
+            }
+        }
+        if let Err(err) = self.file.file().unlock() {
             error!(
                 "Failed to unlock {}; program may be stuck: {}",
-                self.0.path().display(),
+                self.file.path().display(),
                 err
             );
         }
@@ -261,3 +390,54 @@ pub fn metadata_if_exists(path: impl AsRef<Path>) -> std::io::Result<Option<std:
         Err(err) => Err(err),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::LockedFile;
+
+    #[test]
+    fn locked_file() -> std::io::Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let path = temp_dir.path().join(".lock");
+
+        // An exclusive lock records the process that holds it.
+        let lock = LockedFile::acquire(&path, "test")?;
+        assert_eq!(
+            fs_err::read_to_string(&path)?,
+            std::process::id().to_string()
+        );
+        drop(lock);
+
+        // Releasing the lock clears the holder.
+        assert_eq!(fs_err::read_to_string(&path)?, "");
+
+        // Shared locks can be held concurrently, and don't record a holder. Any stale holder (e.g.,
+        // from a process that was killed) is cleared.
+        fs_err::write(&path, "0")?;
+        let first = LockedFile::acquire_shared(&path, "test")?;
+        let second = LockedFile::acquire_shared(&path, "test")?;
+        assert_eq!(fs_err::read_to_string(&path)?, "");
+        drop((first, second));
+
+        Ok(())
+    }
+
+    #[test]
+    fn locked_file_timeout() -> std::io::Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let path = temp_dir.path().join(".lock");
+
+        // With a timeout, waiting on a held lock fails rather than blocking forever. (Locks are
+        // held per file handle, so a second handle in the same process has to wait, too.)
+        let timeout = Some(std::time::Duration::from_secs(1));
+        let lock = LockedFile::lock(&path, "test", false, None)?;
+        let err = LockedFile::lock(&path, "test", true, timeout).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::TimedOut);
+
+        // Once the lock is released, it can be acquired.
+        drop(lock);
+        LockedFile::lock(&path, "test", true, timeout)?;
+
+        Ok(())
+    }
+}
@@ -930,6 +930,9 @@ pub(crate) fn fetch(
     let mut refspecs = Vec::new();
     let mut tags = false;
     let mut refspec_strategy = RefspecStrategy::All;
+    // Whether the reference can be fetched with a shallow (depth 1) fetch, i.e., whether we know
+    // exactly which commit we need, such that we can avoid fetching the entire history.
+    let mut shallow = true;
     // The `+` symbol on the refspec means to allow a forced (fast-forward)
     // update which is needed if there is ever a force push that requires a
     // fast-forward.
@@ -986,6 +989,7 @@ pub(crate) fn fetch(
                 refspecs.push(String::from("+refs/heads/*:refs/remotes/origin/*"));
                 refspecs.push(String::from("+HEAD:refs/remotes/origin/HEAD"));
                 tags = true;
+                shallow = false;
             }
         }
     }
@@ -994,14 +998,32 @@ pub(crate) fn fetch(
     match strategy {
         FetchStrategy::Cli => {
             let result = match refspec_strategy {
-                RefspecStrategy::All => fetch_with_cli(repo, remote_url, refspecs.as_slice(), tags),
+                RefspecStrategy::All => {
+                    let result =
+                        fetch_with_cli(repo, remote_url, refspecs.as_slice(), tags, shallow);
+                    match (result, reference) {
+                        // Not all servers allow fetching an arbitrary commit by its SHA. If the
+                        // shallow fetch fails, fall back to fetching the full history of all
+                        // branches and tags, and hope that the commit is reachable from one of
+                        // them.
+                        (Err(err), GitReference::FullCommit(rev)) if shallow => {
+                            debug!("Shallow fetch of commit `{rev}` failed, falling back to a full fetch: {err}");
+                            let refspecs = [
+                                String::from("+refs/heads/*:refs/remotes/origin/*"),
+                                String::from("+HEAD:refs/remotes/origin/HEAD"),
+                            ];
+                            fetch_with_cli(repo, remote_url, &refspecs, true, false)
+                        }
+                        (result, _) => result,
+                    }
+                }
                 RefspecStrategy::First => {
                     // Try each refspec
                     let mut errors = refspecs
                         .iter()
                         .map_while(|refspec| {
                             let fetch_result =
-                                fetch_with_cli(repo, remote_url, &[refspec.clone()], tags);
+                                fetch_with_cli(repo, remote_url, &[refspec.clone()], tags, shallow);
 
                             // Stop after the first success and log failures
                             match fetch_result {
@@ -1103,17 +1125,28 @@ pub(crate) fn fetch(
 /// speed and portability of using `libgit2`.
 ///
 /// [1]: https://doc.rust-lang.org/nightly/cargo/reference/config.html#netgit-fetch-with-cli
+///
+/// If `shallow` is set, only the commits referenced by the refspecs are fetched (`--depth=1`),
+/// rather than their entire history, which avoids cloning large repositories in full just to
+/// build a single revision. Otherwise, if the repository was previously fetched shallowly, its
+/// history is completed (`--unshallow`), such that abbreviated commits can be resolved.
 fn fetch_with_cli(
     repo: &mut git2::Repository,
     url: &str,
     refspecs: &[String],
     tags: bool,
+    shallow: bool,
 ) -> Result<()> {
     let mut cmd = ProcessBuilder::new("git");
     cmd.arg("fetch");
     if tags {
         cmd.arg("--tags");
     }
+    if shallow {
+        cmd.arg("--depth=1");
+    } else if repo.is_shallow() {
+        cmd.arg("--unshallow");
+    }
     cmd.arg("--force") // handle force pushes
         .arg("--update-head-ok") // see discussion in #2078
         .arg(url)
@@ -1131,6 +1164,14 @@ fn fetch_with_cli(
         .env_remove("GIT_ALTERNATE_OBJECT_DIRECTORIES")
         .cwd(repo.path());
 
+    // For SSH remotes, authenticate via the SSH agent (or an unencrypted key) rather than
+    // prompting for a passphrase or host key confirmation, since we capture the output and the
+    // prompt would otherwise block indefinitely. Users can opt out by setting `GIT_SSH_COMMAND`
+    // or `GIT_SSH` themselves.
+    if is_ssh(url) && env::var_os("GIT_SSH_COMMAND").is_none() && env::var_os("GIT_SSH").is_none() {
+        cmd.env("GIT_SSH_COMMAND", "ssh -o BatchMode=yes");
+    }
+
     // We capture the output to avoid streaming it to the user's console during clones.
     // The required `on...line` callbacks currently do nothing.
     // The output appears to be included in error messages by default.
@@ -1408,6 +1449,11 @@ fn is_github(url: &Url) -> bool {
     url.host_str() == Some("github.com")
 }
 
+/// Whether a `url` is fetched over SSH (e.g., `ssh://git@github.com/user/repo.git`).
+fn is_ssh(url: &str) -> bool {
+    Url::parse(url).is_ok_and(|url| url.scheme() == "ssh")
+}
+
 /// Whether a `rev` looks like a commit hash (ASCII hex digits).
 fn looks_like_commit_hash(rev: &str) -> bool {
     rev.len() >= 7 && rev.chars().all(|ch| ch.is_ascii_hexdigit())
@@ -1424,7 +1470,85 @@ fn is_short_hash_of(rev: &str, oid: git2::Oid) -> bool {
 
 #[cfg(test)]
 mod tests {
-    use super::absolute_submodule_url;
+    use std::path::Path;
+    use std::process::Command;
+
+    use reqwest::Client;
+    use url::Url;
+
+    use super::{absolute_submodule_url, fetch, is_ssh, FetchStrategy, GitReference};
+
+    /// Run a `git` command in the given directory, returning its trimmed output.
+    fn git(dir: &Path, args: &[&str]) -> String {
+        let output = Command::new("git")
+            .args(["-c", "user.name=uv", "-c", "user.email=uv@example.com"])
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "`git {}` failed", args.join(" "));
+        String::from_utf8(output.stdout).unwrap().trim().to_string()
+    }
+
+    #[test]
+    fn test_shallow_fetch() {
+        // Create a repository with two commits.
+        let temp_dir = tempfile::tempdir().unwrap();
+        let upstream = temp_dir.path().join("upstream");
+        fs_err::create_dir(&upstream).unwrap();
+        git(&upstream, &["init", "--quiet"]);
+        git(
+            &upstream,
+            &["commit", "--quiet", "--allow-empty", "-m", "first"],
+        );
+        let first = git(&upstream, &["rev-parse", "HEAD"]);
+        git(
+            &upstream,
+            &["commit", "--quiet", "--allow-empty", "-m", "second"],
+        );
+        let url = Url::from_directory_path(&upstream).unwrap();
+
+        // Fetching a full commit should fetch that commit alone, without its history.
+        let mut repo = git2::Repository::init_bare(temp_dir.path().join("db")).unwrap();
+        fetch(
+            &mut repo,
+            url.as_str(),
+            &GitReference::FullCommit(first.clone()),
+            FetchStrategy::Cli,
+            &Client::new(),
+        )
+        .unwrap();
+        assert!(repo.is_shallow());
+        assert!(repo
+            .find_commit(git2::Oid::from_str(&first).unwrap())
+            .is_ok());
+        let second = git(&upstream, &["rev-parse", "HEAD"]);
+        assert!(repo
+            .find_commit(git2::Oid::from_str(&second).unwrap())
+            .is_err());
+
+        // Fetching an abbreviated commit requires the full history, so the database should be
+        // unshallowed.
+        fetch(
+            &mut repo,
+            url.as_str(),
+            &GitReference::ShortCommit(first[..7].to_string()),
+            FetchStrategy::Cli,
+            &Client::new(),
+        )
+        .unwrap();
+        assert!(!repo.is_shallow());
+        assert!(repo
+            .find_commit(git2::Oid::from_str(&second).unwrap())
+            .is_ok());
+    }
+
+    #[test]
+    fn test_is_ssh() {
+        assert!(is_ssh("ssh://git@github.com/pallets/flask.git"));
+        assert!(!is_ssh("https://github.com/pallets/flask.git"));
+        assert!(!is_ssh("git@github.com:pallets/flask.git"));
+    }
 
     #[test]
     fn test_absolute_submodule_url() {
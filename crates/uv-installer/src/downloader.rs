@@ -31,11 +31,24 @@ pub enum Error {
     Thread(String),
 }
 
+impl Error {
+    /// Return the distribution that failed to download, if the error concerns a single
+    /// distribution.
+    pub fn dist(&self) -> Option<&Dist> {
+        match self {
+            Self::Unzip(dist, _) | Self::Fetch(dist, _) => Some(dist),
+            Self::Join(_) | Self::Editable(_) | Self::Thread(_) => None,
+        }
+    }
+}
+
 /// Download, build, and unzip a set of distributions.
 pub struct Downloader<'a, Context: BuildContext + Send + Sync> {
     database: DistributionDatabase<'a, Context>,
     cache: &'a Cache,
     reporter: Option<Arc<dyn Reporter>>,
+    /// The maximum number of distributions to fetch concurrently.
+    concurrent_downloads: usize,
 }
 
 impl<'a, Context: BuildContext + Send + Sync> Downloader<'a, Context> {
@@ -49,6 +62,7 @@ impl<'a, Context: BuildContext + Send + Sync> Downloader<'a, Context> {
             database: DistributionDatabase::new(cache, tags, client, build_context),
             reporter: None,
             cache,
+            concurrent_downloads: build_context.concurrency().downloads,
         }
     }
 
@@ -60,6 +74,7 @@ impl<'a, Context: BuildContext + Send + Sync> Downloader<'a, Context> {
             reporter: Some(reporter.clone()),
             database: self.database.with_reporter(Facade::from(reporter.clone())),
             cache: self.cache,
+            concurrent_downloads: self.concurrent_downloads,
         }
     }
 
@@ -77,9 +92,8 @@ impl<'a, Context: BuildContext + Send + Sync> Downloader<'a, Context> {
                 }
                 Ok::<CachedDist, Error>(wheel)
             })
-            // TODO(charlie): The number of concurrent fetches, such that we limit the number of
-            // concurrent builds to the number of cores, while allowing more concurrent downloads.
-            .buffer_unordered(50)
+            // Builds are limited separately, by the build context.
+            .buffer_unordered(self.concurrent_downloads)
     }
 
     /// Download, build, and unzip a set of downloaded wheels.
@@ -133,7 +147,7 @@ impl<'a, Context: BuildContext + Send + Sync> Downloader<'a, Context> {
                 }
                 Ok::<_, Error>((editable, cached_dist, metadata))
             })
-            .buffer_unordered(50);
+            .buffer_unordered(self.concurrent_downloads);
 
         while let Some((editable, wheel, metadata)) = fetches.next().await.transpose()? {
             if let Some(reporter) = self.reporter.as_ref() {
@@ -241,6 +255,15 @@ pub trait Reporter: Send + Sync {
 
     /// Callback to invoke when a repository checkout completes.
     fn on_checkout_complete(&self, url: &Url, rev: &str, index: usize);
+
+    /// Callback to invoke when a wheel download begins, with the size of the wheel, if known.
+    fn on_download_start(&self, dist: &Dist, size: Option<u64>) -> usize;
+
+    /// Callback to invoke when a chunk of a wheel download is received.
+    fn on_download_progress(&self, id: usize, bytes: u64);
+
+    /// Callback to invoke when a wheel download completes.
+    fn on_download_complete(&self, id: usize);
 }
 
 /// A facade for converting from [`Reporter`] to [`uv_git::Reporter`].
@@ -270,4 +293,16 @@ impl uv_distribution::Reporter for Facade {
     fn on_checkout_complete(&self, url: &Url, rev: &str, index: usize) {
         self.reporter.on_checkout_complete(url, rev, index);
     }
+
+    fn on_download_start(&self, dist: &Dist, size: Option<u64>) -> usize {
+        self.reporter.on_download_start(dist, size)
+    }
+
+    fn on_download_progress(&self, id: usize, bytes: u64) {
+        self.reporter.on_download_progress(id, bytes);
+    }
+
+    fn on_download_complete(&self, id: usize) {
+        self.reporter.on_download_complete(id);
+    }
 }
@@ -1,13 +1,23 @@
-use anyhow::{Context, Error, Result};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use anyhow::{anyhow, Context, Error, Result};
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
-use tracing::instrument;
+use tracing::{debug, instrument};
 
-use distribution_types::CachedDist;
+use distribution_types::{CachedDist, Name};
+use uv_cache::Cache;
 use uv_interpreter::PythonEnvironment;
+use uv_normalize::PackageName;
 
 pub struct Installer<'a> {
     venv: &'a PythonEnvironment,
     link_mode: install_wheel_rs::linker::LinkMode,
+    no_zip: Vec<PackageName>,
+    script_naming: install_wheel_rs::ScriptNaming,
+    scripts: bool,
+    requested: Vec<PackageName>,
+    verify_hashes: bool,
+    interrupt: Option<&'a AtomicBool>,
     reporter: Option<Box<dyn Reporter>>,
 }
 
@@ -17,6 +27,12 @@ impl<'a> Installer<'a> {
         Self {
             venv,
             link_mode: install_wheel_rs::linker::LinkMode::default(),
+            no_zip: Vec::new(),
+            script_naming: install_wheel_rs::ScriptNaming::default(),
+            scripts: true,
+            requested: Vec::new(),
+            verify_hashes: false,
+            interrupt: None,
             reporter: None,
         }
     }
@@ -27,6 +43,58 @@ impl<'a> Installer<'a> {
         Self { link_mode, ..self }
     }
 
+    /// Set the packages that should never be installed as zip archives, even when using
+    /// [`LinkMode::Zip`][`install_wheel_rs::linker::LinkMode::Zip`].
+    #[must_use]
+    pub fn with_no_zip(self, no_zip: Vec<PackageName>) -> Self {
+        Self { no_zip, ..self }
+    }
+
+    /// Set the prefix and suffix to apply to the names of installed entrypoint scripts.
+    #[must_use]
+    pub fn with_script_naming(self, script_naming: install_wheel_rs::ScriptNaming) -> Self {
+        Self {
+            script_naming,
+            ..self
+        }
+    }
+
+    /// Set whether to generate launchers for the wheels' entrypoints. Launchers are specific to the
+    /// current platform, so they should be omitted when installing for a different platform.
+    #[must_use]
+    pub fn with_scripts(self, scripts: bool) -> Self {
+        Self { scripts, ..self }
+    }
+
+    /// Set the packages that were requested directly by the user, as opposed to installed as
+    /// dependencies. These packages are marked with a `REQUESTED` file.
+    #[must_use]
+    pub fn with_requested(self, requested: Vec<PackageName>) -> Self {
+        Self { requested, ..self }
+    }
+
+    /// Verify that every file in each wheel's `RECORD` was installed with the expected size and
+    /// hash, once the wheel is fully installed, rather than trusting them.
+    ///
+    /// Wheels whose files were already verified as they were downloaded are skipped.
+    #[must_use]
+    pub fn with_verify_hashes(self, verify_hashes: bool) -> Self {
+        Self {
+            verify_hashes,
+            ..self
+        }
+    }
+
+    /// Set a flag that, once set (e.g., when the process is interrupted), stops the installation
+    /// with an error before any further wheels are installed, such that the caller can roll back.
+    #[must_use]
+    pub fn with_interrupt(self, interrupt: &'a AtomicBool) -> Self {
+        Self {
+            interrupt: Some(interrupt),
+            ..self
+        }
+    }
+
     /// Set the [`Reporter`] to use for this installer.
     #[must_use]
     pub fn with_reporter(self, reporter: impl Reporter + 'static) -> Self {
@@ -40,8 +108,46 @@ impl<'a> Installer<'a> {
     #[instrument(skip_all, fields(num_wheels = %wheels.len()))]
     pub fn install(self, wheels: &[CachedDist]) -> Result<()> {
         let layout = self.venv.interpreter().layout();
+
+        // Determine the link mode that the environment supports once, rather than for every wheel.
+        // In zip mode, this is the mode used for wheels that can't be installed as archives.
+        let resolved = match self.link_mode {
+            install_wheel_rs::linker::LinkMode::Zip => {
+                install_wheel_rs::linker::LinkMode::default()
+            }
+            link_mode => link_mode,
+        };
+        let resolved = wheels.first().map_or(resolved, |wheel| {
+            resolved.resolve(&layout.purelib, wheel.path())
+        });
+
         tokio::task::block_in_place(|| {
             wheels.par_iter().try_for_each(|wheel| {
+                if self
+                    .interrupt
+                    .is_some_and(|interrupt| interrupt.load(Ordering::SeqCst))
+                {
+                    return Err(anyhow!("Installation was interrupted"));
+                }
+
+                let link_mode = match self.link_mode {
+                    install_wheel_rs::linker::LinkMode::Zip
+                        if !self.no_zip.contains(wheel.name()) =>
+                    {
+                        install_wheel_rs::linker::LinkMode::Zip
+                    }
+                    _ => resolved,
+                };
+
+                // The installed files are linked (or copied) unchanged from the cache, so if the
+                // cached files were verified against the `RECORD` as the wheel was downloaded,
+                // there's no need to hash them again.
+                let verify_hashes = self.verify_hashes
+                    && !Cache::record_marker(wheel.path()).is_ok_and(|marker| marker.is_file());
+                if self.verify_hashes && !verify_hashes {
+                    debug!("Reusing hashes verified on download: {wheel}");
+                }
+
                 install_wheel_rs::linker::install_wheel(
                     &layout,
                     wheel.path(),
@@ -53,7 +159,10 @@ impl<'a> Installer<'a> {
                         .transpose()?
                         .as_ref(),
                     Some("uv"),
-                    self.link_mode,
+                    self.requested.contains(wheel.name()),
+                    link_mode,
+                    self.scripts.then_some(&self.script_naming),
+                    verify_hashes,
                 )
                 .with_context(|| format!("Failed to install: {} ({wheel})", wheel.filename()))?;
 
@@ -1,8 +1,10 @@
-pub use downloader::{Downloader, Reporter as DownloadReporter};
+pub use downloader::{Downloader, Error as DownloadError, Reporter as DownloadReporter};
 pub use editable::{is_dynamic, not_modified, BuiltEditable, ResolvedEditable};
 pub use installer::{Installer, Reporter as InstallReporter};
-pub use plan::{Plan, Planner, Reinstall};
-pub use site_packages::SitePackages;
+pub use plan::{Keep, Plan, Planner, Reinstall};
+pub use shared::{verify_shared_environment, SharedEnvironmentError};
+pub use site_packages::{Diagnostic, SitePackages};
+pub use transaction::Transaction;
 pub use uninstall::uninstall;
 pub use uv_traits::NoBinary;
 
@@ -10,5 +12,7 @@ mod downloader;
 mod editable;
 mod installer;
 mod plan;
+mod shared;
 mod site_packages;
+mod transaction;
 mod uninstall;
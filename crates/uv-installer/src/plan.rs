@@ -6,15 +6,17 @@ use std::path::Path;
 use anyhow::{bail, Result};
 use rustc_hash::FxHashMap;
 use tracing::{debug, warn};
+use url::Url;
 
 use distribution_types::{
-    BuiltDist, CachedDirectUrlDist, CachedDist, Dist, IndexLocations, InstalledDirectUrlDist,
-    InstalledDist, InstalledMetadata, InstalledVersion, Name, SourceDist,
+    BuiltDist, CachedDirectUrlDist, CachedDist, DirectUrl, Dist, IndexLocations,
+    InstalledDirectUrlDist, InstalledDist, InstalledMetadata, InstalledVersion, Name, SourceDist,
 };
 use pep508_rs::{Requirement, VersionOrUrl};
 use platform_tags::Tags;
 use uv_cache::{ArchiveTimestamp, Cache, CacheBucket, CacheEntry, Timestamp, WheelCache};
-use uv_distribution::{BuiltWheelIndex, RegistryWheelIndex};
+use uv_client::Connectivity;
+use uv_distribution::{resolve_precise, BuiltWheelIndex, RegistryWheelIndex};
 use uv_fs::Simplified;
 use uv_interpreter::PythonEnvironment;
 use uv_normalize::PackageName;
@@ -27,6 +29,8 @@ use crate::{ResolvedEditable, SitePackages};
 pub struct Planner<'a> {
     requirements: &'a [Requirement],
     editable_requirements: &'a [ResolvedEditable],
+    keep: Keep,
+    connectivity: Connectivity,
 }
 
 impl<'a> Planner<'a> {
@@ -36,6 +40,8 @@ impl<'a> Planner<'a> {
         Self {
             requirements,
             editable_requirements: &[],
+            keep: Keep::None,
+            connectivity: Connectivity::Online,
         }
     }
 
@@ -48,6 +54,23 @@ impl<'a> Planner<'a> {
         }
     }
 
+    /// Set the installed packages that should be retained, even if they aren't included in the
+    /// requirements.
+    #[must_use]
+    pub fn with_keep(self, keep: Keep) -> Self {
+        Self { keep, ..self }
+    }
+
+    /// Set the [`Connectivity`] used to determine whether Git requirements on a branch or tag can
+    /// be resolved to their current commit.
+    #[must_use]
+    pub fn with_connectivity(self, connectivity: Connectivity) -> Self {
+        Self {
+            connectivity,
+            ..self
+        }
+    }
+
     /// Partition a set of requirements into those that should be linked from the cache, those that
     /// need to be downloaded, and those that should be removed.
     ///
@@ -184,21 +207,18 @@ impl<'a> Planner<'a> {
                                 }
                             }
 
-                            // If the requirement comes from a direct URL, check by URL.
+                            // If the requirement comes from a direct URL, check against the
+                            // `direct_url.json` recorded at install time.
                             Some(VersionOrUrl::Url(url)) => {
                                 if let InstalledDist::Url(distribution) = &distribution {
-                                    if &distribution.url == url.raw() {
-                                        // If the requirement came from a local path, check freshness.
-                                        if let Ok(archive) = url.to_file_path() {
-                                            if not_modified_install(distribution, &archive)? {
-                                                debug!("Requirement already satisfied (and up-to-date): {distribution}");
-                                                continue;
-                                            }
-                                        } else {
-                                            // Otherwise, assume the requirement is up-to-date.
-                                            debug!("Requirement already satisfied (assumed up-to-date): {distribution}");
-                                            continue;
-                                        }
+                                    if satisfies_direct_url(
+                                        distribution,
+                                        url.raw(),
+                                        cache,
+                                        self.connectivity,
+                                    )? {
+                                        debug!("Requirement already satisfied (and up-to-date): {distribution}");
+                                        continue;
                                     }
                                 }
                             }
@@ -396,6 +416,11 @@ impl<'a> Planner<'a> {
                     continue;
                 }
 
+                if self.keep.contains(dist_info.name()) {
+                    debug!("Preserving kept package: {dist_info}");
+                    continue;
+                }
+
                 debug!("Unnecessary package: {dist_info}");
                 extraneous.push(dist_info);
             }
@@ -418,6 +443,84 @@ enum Specifier<'a> {
     NonEditable(Option<&'a VersionOrUrl>),
 }
 
+/// Returns `true` if the installed distribution satisfies the given direct URL requirement, based on
+/// the `direct_url.json` recorded when the distribution was installed.
+///
+/// - Local files and directories must match by URL, and must not have been modified since the
+///   distribution was installed.
+/// - Archives must match by URL and subdirectory.
+/// - Git repositories must match by repository and subdirectory, and the requirement must resolve
+///   to the same commit that was installed. A requirement on a branch or tag (or a short commit)
+///   is resolved to its current commit by fetching the repository into the Git cache, and is
+///   never satisfied when offline.
+fn satisfies_direct_url(
+    distribution: &InstalledDirectUrlDist,
+    url: &Url,
+    cache: &Cache,
+    connectivity: Connectivity,
+) -> Result<bool> {
+    let Ok(requested) = DirectUrl::try_from(url) else {
+        return Ok(false);
+    };
+
+    match (requested, distribution.direct_url.as_ref()) {
+        (
+            DirectUrl::LocalFile(requested),
+            pypi_types::DirectUrl::LocalDirectory { url, dir_info },
+        ) => {
+            if dir_info.editable == Some(true) {
+                return Ok(false);
+            }
+            if Url::parse(url).ok().as_ref() != Some(&requested.url) {
+                return Ok(false);
+            }
+            let Ok(archive) = requested.url.to_file_path() else {
+                return Ok(false);
+            };
+            Ok(not_modified_install(distribution, &archive)?)
+        }
+        (
+            DirectUrl::Archive(requested),
+            pypi_types::DirectUrl::ArchiveUrl {
+                url, subdirectory, ..
+            },
+        ) => Ok(Url::parse(url).ok().as_ref() == Some(&requested.url)
+            && subdirectory == &requested.subdirectory),
+        (
+            DirectUrl::Git(requested),
+            pypi_types::DirectUrl::VcsUrl {
+                url: installed_url,
+                vcs_info,
+                subdirectory,
+            },
+        ) => {
+            if vcs_info.vcs != pypi_types::VcsKind::Git {
+                return Ok(false);
+            }
+            if Url::parse(installed_url).ok().as_ref() != Some(requested.url.repository()) {
+                return Ok(false);
+            }
+            if subdirectory != &requested.subdirectory {
+                return Ok(false);
+            }
+            let Some(commit_id) = vcs_info.commit_id.as_deref() else {
+                return Ok(false);
+            };
+            let precise = if let Some(precise) = requested.url.precise() {
+                precise
+            } else if connectivity == Connectivity::Offline {
+                debug!("Unable to resolve Git reference for {distribution} while offline");
+                return Ok(false);
+            } else {
+                // Resolve the branch or tag to the commit it currently points to.
+                tokio::task::block_in_place(|| resolve_precise(url, cache))?
+            };
+            Ok(commit_id == precise.to_string())
+        }
+        _ => Ok(false),
+    }
+}
+
 /// Returns `true` if the cache entry linked to the file at the given [`Path`] is not-modified.
 ///
 /// A cache entry is not modified if it exists and is newer than the file at the given path.
@@ -447,8 +550,8 @@ fn not_modified_install(dist: &InstalledDirectUrlDist, artifact: &Path) -> Resul
     let dist_metadata = fs_err::metadata(dist.path.join("METADATA"))?;
     let dist_timestamp = Timestamp::from_metadata(&dist_metadata);
 
-    // Determine the modification time of the wheel.
-    let Some(artifact_timestamp) = ArchiveTimestamp::from_path(artifact)? else {
+    // Determine the modification time of the wheel or source tree.
+    let Some(artifact_timestamp) = ArchiveTimestamp::from_source_tree(artifact)? else {
         // The artifact doesn't exist, so it's not fresh.
         return Ok(false);
     };
@@ -509,3 +612,39 @@ impl Reinstall {
         matches!(self, Self::All)
     }
 }
+
+/// The installed packages to retain when syncing an environment, even if they aren't included in
+/// the requirements.
+#[derive(Debug, Clone)]
+pub enum Keep {
+    /// Remove all packages that aren't included in the requirements.
+    None,
+
+    /// Retain all installed packages.
+    All,
+
+    /// Retain only the specified packages.
+    Packages(Vec<PackageName>),
+}
+
+impl Keep {
+    /// Determine the packages to retain.
+    pub fn from_args(no_strip: bool, keep: Vec<PackageName>) -> Self {
+        if no_strip {
+            Self::All
+        } else if !keep.is_empty() {
+            Self::Packages(keep)
+        } else {
+            Self::None
+        }
+    }
+
+    /// Returns `true` if the given package should be retained.
+    pub fn contains(&self, package: &PackageName) -> bool {
+        match self {
+            Self::None => false,
+            Self::All => true,
+            Self::Packages(packages) => packages.contains(package),
+        }
+    }
+}
@@ -0,0 +1,159 @@
+//! Verify that an environment can be shared between the members of a Unix group, such that any
+//! member of the group can install, upgrade, and remove packages.
+//!
+//! A shared environment requires that:
+//!
+//! - Every directory in the environment is group-writable, such that any member of the group can
+//!   add and remove files within it.
+//! - Every directory in the environment has the setgid bit set, such that files and directories
+//!   created within it are owned by the environment's group, rather than the primary group of the
+//!   user that created them.
+//! - The current umask preserves group write permissions, such that any directories created during
+//!   the install can be modified by the rest of the group.
+//!
+//! Walking the entire environment on every install would be prohibitively slow, so only the
+//! directories that an install writes to directly are verified: the environment root, the scripts
+//! directory, `site-packages`, and the top-level directories within `site-packages`. Directories
+//! nested deeper than that are created by an install, and so inherit the setgid bit (and, given a
+//! suitable umask, group write permissions) from their parents.
+
+#[cfg(unix)]
+use std::path::Path;
+use std::path::PathBuf;
+
+use uv_fs::Simplified;
+use uv_interpreter::PythonEnvironment;
+
+#[derive(thiserror::Error, Debug)]
+pub enum SharedEnvironmentError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("Shared environments are only supported on Unix")]
+    Unsupported,
+    #[error("The directory `{}` doesn't have the setgid bit set, so files created within it won't be owned by the environment's group (hint: run `find {} -type d -exec chmod g+s {{}} +`)", path.simplified_display(), root.simplified_display())]
+    NotSetgid { path: PathBuf, root: PathBuf },
+    #[error("`{}` isn't group-writable, so other members of the group can't modify it (hint: run `chmod -R g+w` on the environment)", _0.simplified_display())]
+    NotGroupWritable(PathBuf),
+    #[error("The directory `{}` is owned by group {actual}, but the environment is owned by group {expected} (hint: run `chgrp -R {expected}` on the environment)", path.simplified_display())]
+    MismatchedGroup {
+        path: PathBuf,
+        expected: u32,
+        actual: u32,
+    },
+    #[error("The current umask creates directories with mode {mode:o}, which other members of the group can't modify (hint: run `umask 002` before installing into a shared environment)")]
+    Umask { mode: u32 },
+}
+
+/// Verify that the given environment can be shared between the members of its group.
+#[cfg(unix)]
+pub fn verify_shared_environment(venv: &PythonEnvironment) -> Result<(), SharedEnvironmentError> {
+    let interpreter = venv.interpreter();
+    let group = group(interpreter.purelib())?;
+    tracing::debug!(
+        "Verifying shared environment at: {} (group: {group})",
+        venv.root().simplified_display()
+    );
+
+    // The lockfile is created at the root of a virtual environment.
+    let root = venv.root();
+    if interpreter.is_virtualenv() {
+        verify_directory(root, root, group)?;
+    }
+    verify_directory(interpreter.scripts(), root, group)?;
+    verify_site_packages(interpreter.purelib(), root, group)?;
+    if interpreter.platlib() != interpreter.purelib() {
+        verify_site_packages(interpreter.platlib(), root, group)?;
+    }
+
+    // Any existing lockfile must be group-writable, such that other members of the group can
+    // acquire it.
+    let lockfile = venv.root().join(".lock");
+    if interpreter.is_virtualenv() && lockfile.exists() {
+        use std::os::unix::fs::PermissionsExt;
+
+        if fs_err::metadata(&lockfile)?.permissions().mode() & 0o020 == 0 {
+            return Err(SharedEnvironmentError::NotGroupWritable(lockfile));
+        }
+    }
+
+    // Create a directory to verify that the current umask (and the setgid bit) will produce
+    // directories that the rest of the group can modify.
+    let probe = interpreter
+        .purelib()
+        .join(format!(".uv-shared-{}", std::process::id()));
+    fs_err::create_dir(&probe)?;
+    let result = verify_probe(&probe, root, group);
+    fs_err::remove_dir(&probe)?;
+    result
+}
+
+/// Verify that the given environment can be shared between the members of its group.
+#[cfg(not(unix))]
+pub fn verify_shared_environment(_venv: &PythonEnvironment) -> Result<(), SharedEnvironmentError> {
+    Err(SharedEnvironmentError::Unsupported)
+}
+
+/// Return the group that owns the given path.
+#[cfg(unix)]
+fn group(path: &Path) -> Result<u32, SharedEnvironmentError> {
+    use std::os::unix::fs::MetadataExt;
+
+    Ok(fs_err::metadata(path)?.gid())
+}
+
+/// Verify that a directory is owned by the given group, group-writable, and setgid.
+#[cfg(unix)]
+fn verify_directory(path: &Path, root: &Path, group: u32) -> Result<(), SharedEnvironmentError> {
+    use std::os::unix::fs::MetadataExt;
+
+    let metadata = fs_err::metadata(path)?;
+    if metadata.mode() & 0o2000 == 0 {
+        return Err(SharedEnvironmentError::NotSetgid {
+            path: path.to_path_buf(),
+            root: root.to_path_buf(),
+        });
+    }
+    if metadata.mode() & 0o020 == 0 {
+        return Err(SharedEnvironmentError::NotGroupWritable(path.to_path_buf()));
+    }
+    if metadata.gid() != group {
+        return Err(SharedEnvironmentError::MismatchedGroup {
+            path: path.to_path_buf(),
+            expected: group,
+            actual: metadata.gid(),
+        });
+    }
+    Ok(())
+}
+
+/// Verify a `site-packages` directory and its top-level subdirectories (i.e., packages and
+/// `.dist-info` directories).
+#[cfg(unix)]
+fn verify_site_packages(
+    path: &Path,
+    root: &Path,
+    group: u32,
+) -> Result<(), SharedEnvironmentError> {
+    verify_directory(path, root, group)?;
+    for entry in fs_err::read_dir(path)? {
+        let entry = entry?;
+        if entry.file_type()?.is_dir() {
+            verify_directory(&entry.path(), root, group)?;
+        }
+    }
+    Ok(())
+}
+
+/// Verify that a newly created directory is usable by the rest of the group.
+#[cfg(unix)]
+fn verify_probe(path: &Path, root: &Path, group: u32) -> Result<(), SharedEnvironmentError> {
+    use std::os::unix::fs::MetadataExt;
+
+    let metadata = fs_err::metadata(path)?;
+    if metadata.mode() & 0o020 == 0 {
+        return Err(SharedEnvironmentError::Umask {
+            mode: metadata.mode() & 0o777,
+        });
+    }
+    verify_directory(path, root, group)
+}
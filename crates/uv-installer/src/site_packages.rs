@@ -1,18 +1,22 @@
+use std::collections::VecDeque;
 use std::hash::BuildHasherDefault;
 use std::iter::Flatten;
 use std::path::PathBuf;
 
 use anyhow::{Context, Result};
-use fs_err as fs;
 use rustc_hash::{FxHashMap, FxHashSet};
+use tracing::warn;
 use url::Url;
 
-use distribution_types::{InstalledDist, InstalledMetadata, InstalledVersion, Name};
+use distribution_types::{
+    InstalledDist, InstalledDists, InstalledMetadata, InstalledVersion, Name,
+};
 use pep440_rs::{Version, VersionSpecifiers};
-use pep508_rs::{Requirement, VerbatimUrl};
+use pep508_rs::{MarkerEnvironment, Requirement, VerbatimUrl};
+use pypi_types::EntryPoint;
 use requirements_txt::EditableRequirement;
 use uv_interpreter::PythonEnvironment;
-use uv_normalize::PackageName;
+use uv_normalize::{ExtraName, PackageName};
 
 use crate::{is_dynamic, not_modified};
 
@@ -42,33 +46,24 @@ impl<'a> SitePackages<'a> {
         let mut by_url = FxHashMap::default();
 
         // Index all installed packages by name.
-        for entry in fs::read_dir(venv.site_packages())? {
-            let entry = entry?;
-            if entry.file_type()?.is_dir() {
-                let path = entry.path();
-
-                let Some(dist_info) = InstalledDist::try_from_path(&path)
-                    .with_context(|| format!("Failed to read metadata: from {}", path.display()))?
-                else {
-                    continue;
-                };
+        for dist_info in InstalledDists::from_site_packages(venv.site_packages())? {
+            let dist_info = dist_info?;
 
-                let idx = distributions.len();
+            let idx = distributions.len();
 
-                // Index the distribution by name.
-                by_name
-                    .entry(dist_info.name().clone())
-                    .or_insert_with(Vec::new)
-                    .push(idx);
+            // Index the distribution by name.
+            by_name
+                .entry(dist_info.name().clone())
+                .or_insert_with(Vec::new)
+                .push(idx);
 
-                // Index the distribution by URL.
-                if let Some(url) = dist_info.as_editable() {
-                    by_url.entry(url.clone()).or_insert_with(Vec::new).push(idx);
-                }
-
-                // Add the distribution to the database.
-                distributions.push(Some(dist_info));
+            // Index the distribution by URL.
+            if let Some(url) = dist_info.as_editable() {
+                by_url.entry(url.clone()).or_insert_with(Vec::new).push(idx);
             }
+
+            // Add the distribution to the database.
+            distributions.push(Some(dist_info));
         }
 
         Ok(Self {
@@ -147,6 +142,28 @@ impl<'a> SitePackages<'a> {
             .collect()
     }
 
+    /// Returns the entry points in the given group (e.g., `console_scripts`, or a plugin group)
+    /// across all installed distributions, along with the distribution that declares each.
+    ///
+    /// Distributions with a malformed `entry_points.txt` are skipped with a warning, such that a
+    /// single broken distribution doesn't hide the entry points of the rest of the environment.
+    pub fn entry_points(&self, group: &str) -> Vec<(&InstalledDist, EntryPoint)> {
+        let mut entry_points = Vec::new();
+        for dist in self.iter() {
+            let declared = match dist.entry_points() {
+                Ok(declared) => declared,
+                Err(err) => {
+                    warn!("Ignoring the entry points of {dist}: {err:#}");
+                    continue;
+                }
+            };
+            for entry_point in declared.group(group) {
+                entry_points.push((dist, entry_point.clone()));
+            }
+        }
+        entry_points
+    }
+
     /// Returns `true` if there are any installed packages.
     pub fn any(&self) -> bool {
         self.distributions.iter().any(Option::is_some)
@@ -154,6 +171,19 @@ impl<'a> SitePackages<'a> {
 
     /// Validate the installed packages in the virtual environment.
     pub fn diagnostics(&self) -> Result<Vec<Diagnostic>> {
+        self.diagnostics_for(
+            self.venv.interpreter().python_version(),
+            self.venv.interpreter().markers(),
+        )
+    }
+
+    /// Validate the installed packages against the given Python version and markers, e.g., to
+    /// determine whether the environment would remain valid under a different Python version.
+    pub fn diagnostics_for(
+        &self,
+        python_version: &Version,
+        markers: &MarkerEnvironment,
+    ) -> Result<Vec<Diagnostic>> {
         let mut diagnostics = Vec::new();
 
         for (package, indexes) in &self.by_name {
@@ -192,10 +222,10 @@ impl<'a> SitePackages<'a> {
 
                 // Verify that the package is compatible with the current Python version.
                 if let Some(requires_python) = metadata.requires_python.as_ref() {
-                    if !requires_python.contains(self.venv.interpreter().python_version()) {
+                    if !requires_python.contains(python_version) {
                         diagnostics.push(Diagnostic::IncompatiblePythonVersion {
                             package: package.clone(),
-                            version: self.venv.interpreter().python_version().clone(),
+                            version: python_version.clone(),
                             requires_python: requires_python.clone(),
                         });
                     }
@@ -203,7 +233,7 @@ impl<'a> SitePackages<'a> {
 
                 // Verify that the dependencies are installed.
                 for dependency in &metadata.requires_dist {
-                    if !dependency.evaluate_markers(self.venv.interpreter().markers(), &[]) {
+                    if !dependency.evaluate_markers(markers, &[]) {
                         continue;
                     }
 
@@ -246,6 +276,69 @@ impl<'a> SitePackages<'a> {
         Ok(diagnostics)
     }
 
+    /// Returns the names of the packages that are required by at least one other installed
+    /// package, evaluated against the current environment's markers.
+    ///
+    /// Dependencies that are only required by an extra aren't included, as the extras that were
+    /// requested at install time aren't recorded. Packages with unreadable metadata are skipped.
+    pub fn required(&self) -> FxHashSet<PackageName> {
+        let markers = self.venv.interpreter().markers();
+        let mut required = FxHashSet::default();
+        for distribution in self.iter() {
+            let Ok(metadata) = distribution.metadata() else {
+                continue;
+            };
+            for dependency in metadata.requires_dist {
+                if dependency.name != *distribution.name()
+                    && dependency.evaluate_markers(markers, &[])
+                {
+                    required.insert(dependency.name);
+                }
+            }
+        }
+        required
+    }
+
+    /// Returns the installed packages that were only installed as dependencies, and are no longer
+    /// required (directly or transitively) by any requested package.
+    ///
+    /// A package is considered requested if it has a `REQUESTED` marker, or if it lacks an
+    /// `INSTALLER` file, as installers that don't record the latter can't be trusted to record the
+    /// former.
+    pub fn orphans(&self) -> Vec<&InstalledDist> {
+        let markers = self.venv.interpreter().markers();
+
+        // Walk the dependency graph, starting from the requested packages.
+        let mut queue = self
+            .iter()
+            .filter(|dist| dist.is_requested() || matches!(dist.installer(), Ok(None)))
+            .map(|dist| (dist.name().clone(), Vec::new()))
+            .collect::<VecDeque<(PackageName, Vec<ExtraName>)>>();
+        let mut visited = queue.iter().cloned().collect::<FxHashSet<_>>();
+        let mut reachable = FxHashSet::default();
+        while let Some((package, extras)) = queue.pop_front() {
+            for distribution in self.get_packages(&package) {
+                reachable.insert(distribution.path());
+                let Ok(metadata) = distribution.metadata() else {
+                    continue;
+                };
+                for dependency in metadata.requires_dist {
+                    if !dependency.evaluate_markers(markers, &extras) {
+                        continue;
+                    }
+                    let node = (dependency.name, dependency.extras);
+                    if visited.insert(node.clone()) {
+                        queue.push_back(node);
+                    }
+                }
+            }
+        }
+
+        self.iter()
+            .filter(|dist| !reachable.contains(dist.path()))
+            .collect()
+    }
+
     /// Returns `true` if the installed packages satisfy the given requirements.
     pub fn satisfies(
         &self,
@@ -461,6 +554,17 @@ impl Diagnostic {
         }
     }
 
+    /// Returns the [`PackageName`] of the package that this diagnostic concerns.
+    pub fn package(&self) -> &PackageName {
+        match self {
+            Self::IncompletePackage { package, .. } => package,
+            Self::IncompatiblePythonVersion { package, .. } => package,
+            Self::MissingDependency { package, .. } => package,
+            Self::IncompatibleDependency { package, .. } => package,
+            Self::DuplicatePackage { package, .. } => package,
+        }
+    }
+
     /// Returns `true` if the [`PackageName`] is involved in this diagnostic.
     pub fn includes(&self, name: &PackageName) -> bool {
         match self {
@@ -0,0 +1,202 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use fs_err as fs;
+use rustc_hash::FxHashSet;
+use tracing::{debug, warn};
+
+use distribution_types::{CachedDist, InstalledDist, Name};
+use pypi_types::RecordEntry;
+use uv_fs::Simplified;
+use uv_interpreter::PythonEnvironment;
+use uv_normalize::PackageName;
+
+use crate::SitePackages;
+
+/// A journal of the packages removed from an environment during an install, such that the
+/// environment can be restored to its original state if the install fails partway through.
+///
+/// Prior to uninstalling a package, its files are backed up (via hard links, where possible) to a
+/// temporary directory within the environment's `site-packages`, which is created on first use.
+/// If the install fails, any newly installed packages are removed, and the backed-up files are
+/// restored; otherwise, the backups are discarded when the transaction is dropped.
+pub struct Transaction<'a> {
+    venv: &'a PythonEnvironment,
+    /// The directory containing the backed-up files, if any files were backed up.
+    backup: Option<tempfile::TempDir>,
+    /// The original and backup paths of each file that was removed.
+    journal: Vec<(PathBuf, PathBuf)>,
+}
+
+impl<'a> Transaction<'a> {
+    /// Begin a new transaction against the given environment.
+    pub fn new(venv: &'a PythonEnvironment) -> Self {
+        Self {
+            venv,
+            backup: None,
+            journal: Vec::new(),
+        }
+    }
+
+    /// Uninstall a package, backing up its files such that it can be restored on rollback.
+    pub async fn uninstall(&mut self, dist: &InstalledDist) -> Result<install_wheel_rs::Uninstall> {
+        for path in install_wheel_rs::uninstall_wheel_dry_run(dist.path())? {
+            if !path.is_file() {
+                continue;
+            }
+            let backup = self.backup_dir()?.join(self.journal.len().to_string());
+            if fs::hard_link(&path, &backup).is_err() {
+                fs::copy(&path, &backup)?;
+            }
+            self.journal.push((path, backup));
+        }
+
+        crate::uninstall(dist, false).await
+    }
+
+    /// Return the directory for backed-up files, creating it if necessary.
+    fn backup_dir(&mut self) -> Result<&Path> {
+        if self.backup.is_none() {
+            let backup = tempfile::Builder::new()
+                .prefix(".uv-backup-")
+                .tempdir_in(self.venv.interpreter().purelib())
+                .context("Failed to create backup directory")?;
+            self.backup = Some(backup);
+        }
+        Ok(self.backup.as_ref().unwrap().path())
+    }
+
+    /// Roll back the transaction in response to the given error, removing any of the given wheels
+    /// that were (even partially) installed, and restoring all packages that were uninstalled.
+    ///
+    /// Returns the error to report: the given error, or, if the rollback itself failed, the given
+    /// error along with the reason the environment couldn't be restored.
+    pub fn rollback(self, wheels: &[CachedDist], err: anyhow::Error) -> anyhow::Error {
+        let root = self.venv.root().to_path_buf();
+        match self.try_rollback(wheels) {
+            Ok(()) => err,
+            Err(rollback) => err.context(format!(
+                "Failed to restore the environment at {} after the installation failed: {rollback:#}",
+                root.simplified_display()
+            )),
+        }
+    }
+
+    fn try_rollback(self, wheels: &[CachedDist]) -> Result<()> {
+        debug!(
+            "Rolling back installation in: {}",
+            self.venv.root().simplified_display()
+        );
+
+        // Remove any newly installed packages. Any previous installations of these packages were
+        // uninstalled as part of the transaction, so anything that remains is new.
+        let site_packages = SitePackages::from_executable(self.venv)?;
+        let mut partial = Vec::new();
+        for wheel in wheels {
+            let installed = site_packages.get_packages(wheel.name());
+            if installed.is_empty() {
+                partial.push(wheel);
+            }
+            for dist in installed {
+                match install_wheel_rs::uninstall_wheel(dist.path(), false) {
+                    Ok(_) => debug!("Removed partial installation: {dist}"),
+                    Err(err) => {
+                        debug!("Failed to uninstall partial installation of {dist}: {err}");
+                        partial.push(wheel);
+                    }
+                }
+            }
+        }
+
+        // If a wheel was only partially extracted (e.g., before its `.dist-info` directory was
+        // complete), remove whatever it left behind, based on the `RECORD` in the unzipped wheel.
+        // Files that belong to any other installed package are retained.
+        if !partial.is_empty() {
+            let names = wheels.iter().map(Name::name).collect::<FxHashSet<_>>();
+            let owned = owned_files(&site_packages, &names);
+            for wheel in partial {
+                match remove_extracted(wheel, self.venv.site_packages(), &owned) {
+                    Ok(count) => debug!("Removed {count} file(s) extracted from: {wheel}"),
+                    Err(err) => warn!("Failed to remove files extracted from {wheel}: {err}"),
+                }
+            }
+        }
+
+        // Restore the files of any uninstalled packages.
+        for (path, backup) in &self.journal {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::rename(backup, path)?;
+        }
+        debug!("Restored {} file(s) from backup", self.journal.len());
+
+        Ok(())
+    }
+}
+
+/// Collect the files listed in the `RECORD` of every installed package, other than the given
+/// packages.
+fn owned_files(
+    site_packages: &SitePackages,
+    exclude: &FxHashSet<&PackageName>,
+) -> FxHashSet<PathBuf> {
+    site_packages
+        .iter()
+        .filter(|dist| !exclude.contains(dist.name()))
+        .filter_map(|dist| dist.record().ok())
+        .flatten()
+        .map(|entry| PathBuf::from(entry.path))
+        .collect()
+}
+
+/// Remove the files that were extracted from the given wheel into `site-packages`, along with any
+/// directories left empty, skipping any `owned` files.
+///
+/// Returns the number of files that were removed.
+fn remove_extracted(
+    wheel: &CachedDist,
+    site_packages: &Path,
+    owned: &FxHashSet<PathBuf>,
+) -> Result<usize> {
+    let Some(dist_info) = fs::read_dir(wheel.path())?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .find(|path| path.extension().is_some_and(|ext| ext == "dist-info"))
+    else {
+        return Ok(0);
+    };
+    let record = RecordEntry::read_all(fs::File::open(dist_info.join("RECORD"))?)?;
+
+    let mut count = 0;
+    let mut parents = FxHashSet::default();
+    for entry in record {
+        let relative = PathBuf::from(&entry.path);
+        if owned.contains(&relative) {
+            continue;
+        }
+        let path = site_packages.join(&relative);
+        match fs::remove_file(&path) {
+            Ok(()) => count += 1,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(err) => {
+                debug!("Failed to remove extracted file: {err}");
+                continue;
+            }
+        }
+        parents.extend(relative.ancestors().skip(1).map(Path::to_path_buf));
+    }
+
+    // Remove any directories that were left empty, deepest first.
+    let mut parents = parents
+        .into_iter()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .collect::<Vec<_>>();
+    parents.sort_unstable_by_key(|parent| std::cmp::Reverse(parent.components().count()));
+    for parent in parents {
+        // Non-empty directories are left in place.
+        let _ = fs::remove_dir(site_packages.join(parent));
+    }
+
+    Ok(count)
+}
@@ -3,10 +3,15 @@ use anyhow::Result;
 use distribution_types::InstalledDist;
 
 /// Uninstall a package from the specified Python environment.
-pub async fn uninstall(dist: &InstalledDist) -> Result<install_wheel_rs::Uninstall> {
+///
+/// If `check_modified` is set, any files that were modified since installation are reported.
+pub async fn uninstall(
+    dist: &InstalledDist,
+    check_modified: bool,
+) -> Result<install_wheel_rs::Uninstall> {
     let uninstall = tokio::task::spawn_blocking({
         let path = dist.path().to_owned();
-        move || install_wheel_rs::uninstall_wheel(&path)
+        move || install_wheel_rs::uninstall_wheel(&path, check_modified)
     })
     .await??;
 
@@ -15,7 +15,7 @@ use pep440_rs::Version;
 use pep508_rs::MarkerEnvironment;
 use platform_host::Platform;
 use platform_tags::{Tags, TagsError};
-use uv_cache::{Cache, CacheBucket, CachedByTimestamp, Freshness, Timestamp};
+use uv_cache::{Cache, CacheBucket, CachedByFileInfo, FileInfo, Freshness};
 use uv_fs::write_atomic_sync;
 
 use crate::python_environment::{detect_python_executable, detect_virtual_env};
@@ -96,6 +96,24 @@ impl Interpreter {
         }
     }
 
+    /// Return a new [`Interpreter`] that installs packages into the given directory, rather than
+    /// into its own `site-packages`, as with `pip install --target`.
+    #[must_use]
+    pub fn with_target(self, target: &Path) -> Self {
+        Self {
+            sysconfig_paths: SysconfigPaths {
+                purelib: target.to_path_buf(),
+                platlib: target.to_path_buf(),
+                scripts: target.join("bin"),
+                data: target.to_path_buf(),
+                include: target.join("include"),
+                platinclude: target.join("include"),
+                ..self.sysconfig_paths
+            },
+            ..self
+        }
+    }
+
     /// Find the best available Python interpreter to use.
     ///
     /// If no Python version is provided, we will use the first available interpreter.
@@ -187,7 +205,7 @@ impl Interpreter {
         };
 
         // Look for the requested version with by search for `python{major}.{minor}` in `PATH` on
-        // Unix and `py --list-paths` on Windows.
+        // Unix and in the registry (or with `py --list-paths`) on Windows.
         let interpreter = if let Some(python_version) = python_version {
             find_requested_python(&python_version.string, platform, cache)?
         } else {
@@ -427,10 +445,11 @@ impl Interpreter {
             data: self.data().to_path_buf(),
             include: if self.is_virtualenv() {
                 // If the interpreter is a venv, then the `include` directory has a different structure.
+                // Unlike `site-packages`, pip uses `python` in the directory name for all
+                // implementations (including PyPy).
                 // See: https://github.com/pypa/pip/blob/0ad4c94be74cc24874c6feb5bb3c2152c398a18e/src/pip/_internal/locations/_sysconfig.py#L172
                 self.prefix.join("include").join("site").join(format!(
-                    "{}{}.{}",
-                    self.site_packages_python(),
+                    "python{}.{}",
                     self.python_major(),
                     self.python_minor()
                 ))
@@ -552,7 +571,7 @@ impl InterpreterInfo {
     ///
     /// Running a Python script is (relatively) expensive, and the markers won't change
     /// unless the Python executable changes, so we use the executable's last modified
-    /// time as a cache key.
+    /// time and size to invalidate the cache entry.
     pub(crate) fn query_cached(executable: &Path, cache: &Cache) -> Result<Self, Error> {
         let executable_bytes = executable.as_os_str().as_encoded_bytes();
 
@@ -562,7 +581,7 @@ impl InterpreterInfo {
             format!("{}.msgpack", digest(&executable_bytes)),
         );
 
-        let modified = Timestamp::from_path(uv_fs::canonicalize_executable(executable)?)?;
+        let file_info = FileInfo::from_path(uv_fs::canonicalize_executable(executable)?)?;
 
         // Read from the cache.
         if cache
@@ -570,9 +589,9 @@ impl InterpreterInfo {
             .is_ok_and(Freshness::is_fresh)
         {
             if let Ok(data) = fs::read(cache_entry.path()) {
-                match rmp_serde::from_slice::<CachedByTimestamp<Self>>(&data) {
+                match rmp_serde::from_slice::<CachedByFileInfo<Self>>(&data) {
                     Ok(cached) => {
-                        if cached.timestamp == modified {
+                        if cached.file_info == file_info {
                             debug!(
                                 "Cached interpreter info for Python {}, skipping probing: {}",
                                 cached.data.markers.python_full_version,
@@ -612,8 +631,8 @@ impl InterpreterInfo {
             fs::create_dir_all(cache_entry.dir())?;
             write_atomic_sync(
                 cache_entry.path(),
-                rmp_serde::to_vec(&CachedByTimestamp {
-                    timestamp: modified,
+                rmp_serde::to_vec(&CachedByFileInfo {
+                    file_info,
                     data: info.clone(),
                 })?,
             )?;
@@ -632,10 +651,13 @@ mod tests {
     use indoc::{formatdoc, indoc};
     use tempfile::tempdir;
 
+    use cache_key::digest;
     use pep440_rs::Version;
+    use pep508_rs::StringVersion;
     use platform_host::Platform;
-    use uv_cache::Cache;
+    use uv_cache::{Cache, CacheBucket, CachedByFileInfo};
 
+    use crate::interpreter::InterpreterInfo;
     use crate::Interpreter;
 
     #[test]
@@ -711,4 +733,100 @@ mod tests {
             Version::from_str("3.13").unwrap()
         );
     }
+
+    /// The cached interpreter info is invalidated when the size of the executable changes, even if
+    /// its timestamp doesn't (as with in-place upgrades that preserve the modification time, on
+    /// platforms where the timestamp is the modification time).
+    #[test]
+    fn test_cache_invalidation_size() {
+        let mock_dir = tempdir().unwrap();
+        let mocked_interpreter = mock_dir.path().join("python");
+        // The interpreter info is only cached if `sys.executable` is the queried executable.
+        let json = formatdoc! {r##"
+            {{
+                "markers": {{
+                    "implementation_name": "cpython",
+                    "implementation_version": "3.12.0",
+                    "os_name": "posix",
+                    "platform_machine": "x86_64",
+                    "platform_python_implementation": "CPython",
+                    "platform_release": "6.5.0-13-generic",
+                    "platform_system": "Linux",
+                    "platform_version": "#13-Ubuntu SMP PREEMPT_DYNAMIC Fri Nov  3 12:16:05 UTC 2023",
+                    "python_full_version": "3.12.0",
+                    "python_version": "3.12",
+                    "sys_platform": "linux"
+                }},
+                "base_exec_prefix": "/home/ferris/.pyenv/versions/3.12.0",
+                "base_prefix": "/home/ferris/.pyenv/versions/3.12.0",
+                "prefix": "/home/ferris/projects/uv/.venv",
+                "sys_executable": "{}",
+                "sysconfig_paths": {{
+                    "data": "/home/ferris/.pyenv/versions/3.12.0",
+                    "include": "/home/ferris/.pyenv/versions/3.12.0/include",
+                    "platinclude": "/home/ferris/.pyenv/versions/3.12.0/include",
+                    "platlib": "/home/ferris/.pyenv/versions/3.12.0/lib/python3.12/site-packages",
+                    "purelib": "/home/ferris/.pyenv/versions/3.12.0/lib/python3.12/site-packages",
+                    "scripts": "/home/ferris/.pyenv/versions/3.12.0/bin",
+                    "stdlib": "/home/ferris/.pyenv/versions/3.12.0/lib/python3.12",
+                    "platstdlib": "/home/ferris/.pyenv/versions/3.12.0/lib/python3.12"
+                }}
+            }}
+        "##, mocked_interpreter.display()};
+
+        let cache = Cache::temp().unwrap();
+        let platform = Platform::current().unwrap();
+
+        fs::write(
+            &mocked_interpreter,
+            formatdoc! {r##"
+            #!/bin/bash
+            echo '{json}'
+            "##},
+        )
+        .unwrap();
+        fs::set_permissions(
+            &mocked_interpreter,
+            std::os::unix::fs::PermissionsExt::from_mode(0o770),
+        )
+        .unwrap();
+        let interpreter =
+            Interpreter::query(&mocked_interpreter, platform.clone(), &cache).unwrap();
+        assert_eq!(
+            interpreter.markers.python_version.version,
+            Version::from_str("3.12").unwrap()
+        );
+
+        // Since the executable is unchanged on disk, the file info can't be changed directly.
+        // Instead, modify the cache entry, such that a cache hit is distinguishable from a probe.
+        let cache_entry = cache.entry(
+            CacheBucket::Interpreter,
+            "",
+            format!(
+                "{}.msgpack",
+                digest(&mocked_interpreter.as_os_str().as_encoded_bytes())
+            ),
+        );
+        let mut cached: CachedByFileInfo<InterpreterInfo> =
+            rmp_serde::from_slice(&fs::read(cache_entry.path()).unwrap()).unwrap();
+        cached.data.markers.python_version = StringVersion::from_str("3.13").unwrap();
+
+        // If the file info matches, the cache entry is used.
+        fs::write(cache_entry.path(), rmp_serde::to_vec(&cached).unwrap()).unwrap();
+        let interpreter =
+            Interpreter::query(&mocked_interpreter, platform.clone(), &cache).unwrap();
+        assert_eq!(
+            interpreter.markers.python_version.version,
+            Version::from_str("3.13").unwrap()
+        );
+
+        // If only the size differs, the interpreter is probed again.
+        cached.file_info.size += 1;
+        fs::write(cache_entry.path(), rmp_serde::to_vec(&cached).unwrap()).unwrap();
+        let interpreter = Interpreter::query(&mocked_interpreter, platform, &cache).unwrap();
+        assert_eq!(
+            interpreter.markers.python_version.version,
+            Version::from_str("3.12").unwrap()
+        );
+    }
 }
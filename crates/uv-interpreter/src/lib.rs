@@ -7,17 +7,23 @@ use thiserror::Error;
 pub use crate::cfg::PyVenvConfiguration;
 pub use crate::interpreter::Interpreter;
 pub use crate::python_environment::PythonEnvironment;
-pub use crate::python_query::{find_default_python, find_requested_python};
+pub use crate::python_pin::{find_pinned_python, PythonPin, PYTHON_VERSION_FILENAME};
+pub use crate::python_query::{
+    find_all_pythons, find_default_python, find_requested_python, PythonSource,
+};
 pub use crate::python_version::PythonVersion;
 pub use crate::sysconfig::SysconfigPaths;
+pub use crate::target_triple::TargetTriple;
 pub use crate::virtualenv::Virtualenv;
 
 mod cfg;
 mod interpreter;
 mod python_environment;
+mod python_pin;
 mod python_query;
 mod python_version;
 mod sysconfig;
+mod target_triple;
 mod virtualenv;
 
 #[derive(Debug, Error)]
@@ -44,7 +50,7 @@ pub enum Error {
     PyList(#[source] io::Error),
     #[cfg(windows)]
     #[error(
-        "No Python {0} found through `py --list-paths` or in `PATH`. Is Python {0} installed?"
+        "No Python {0} found in the registry, through `py --list-paths`, or in `PATH`. Is Python {0} installed?"
     )]
     NoSuchPython(String),
     #[cfg(unix)]
@@ -53,7 +59,7 @@ pub enum Error {
     #[error("Neither `python` nor `python3` are in `PATH`. Is Python installed?")]
     NoPythonInstalledUnix,
     #[error(
-        "Could not find `python.exe` through `py --list-paths` or in 'PATH'. Is Python installed?"
+        "Could not find `python.exe` in the registry, through `py --list-paths`, or in 'PATH'. Is Python installed?"
     )]
     NoPythonInstalledWindows,
     #[error("{message}:\n--- stdout:\n{stdout}\n--- stderr:\n{stderr}\n---")]
@@ -70,4 +76,6 @@ pub enum Error {
     Cfg(#[from] cfg::Error),
     #[error("Error finding `{}` in PATH", _0.to_string_lossy())]
     WhichError(OsString, #[source] which::Error),
+    #[error("Failed to parse `{}`", _0.display())]
+    PyprojectToml(PathBuf, #[source] Box<toml::de::Error>),
 }
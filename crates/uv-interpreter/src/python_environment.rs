@@ -40,6 +40,20 @@ impl PythonEnvironment {
         })
     }
 
+    /// Create a [`PythonEnvironment`] for the virtual environment at the given root directory.
+    pub fn from_root(root: &Path, platform: Platform, cache: &Cache) -> Result<Self, Error> {
+        if !root.join("pyvenv.cfg").is_file() {
+            return Err(Error::MissingPyVenvCfg(root.to_path_buf()));
+        }
+        let venv = fs_err::canonicalize(root)?;
+        let executable = detect_python_executable(&venv);
+        let interpreter = Interpreter::query(&executable, platform, cache)?;
+        Ok(Self {
+            root: venv,
+            interpreter,
+        })
+    }
+
     /// Create a [`PythonEnvironment`] for a Python interpreter specifier (e.g., a path or a binary name).
     pub fn from_requested_python(
         python: &str,
@@ -85,6 +99,12 @@ impl PythonEnvironment {
         Ok(PyVenvConfiguration::parse(self.root.join("pyvenv.cfg"))?)
     }
 
+    /// Returns `true` if the environment is a Conda environment, i.e., if it contains a
+    /// `conda-meta` directory.
+    pub fn is_conda(&self) -> bool {
+        self.root.join("conda-meta").is_dir()
+    }
+
     /// Returns the location of the Python executable.
     pub fn python_executable(&self) -> &Path {
         self.interpreter.sys_executable()
@@ -102,8 +122,10 @@ impl PythonEnvironment {
 
     /// Lock the virtual environment to prevent concurrent writes.
     pub fn lock(&self) -> Result<LockedFile, std::io::Error> {
-        if self.interpreter.is_virtualenv() {
-            // If the environment a virtualenv, use a virtualenv-specific lock file.
+        if self.interpreter.is_virtualenv() && self.root.join("pyvenv.cfg").is_file() {
+            // If the environment a virtualenv, use a virtualenv-specific lock file. (An
+            // environment that targets some other directory, as with `--target`, shouldn't
+            // receive a lock file.)
             LockedFile::acquire(self.root.join(".lock"), self.root.simplified_display())
         } else {
             // Otherwise, use a global lock file.
@@ -130,7 +152,7 @@ pub(crate) fn detect_virtual_env() -> Result<Option<PathBuf>, Error> {
         }
         (None, Some(dir)) => {
             debug!(
-                "Found a virtualenv through CONDA_PREFIX at: {}",
+                "Found a Conda environment through CONDA_PREFIX at: {}",
                 Path::new(&dir).display()
             );
             return Ok(Some(PathBuf::from(dir)));
@@ -177,8 +199,8 @@ pub(crate) fn detect_python_executable(venv: impl AsRef<Path>) -> PathBuf {
             return executable;
         }
 
-        // Fallback for Conda environments.
-        venv.to_path_buf()
+        // Conda environments place `python.exe` at the root of the environment.
+        venv.join("python.exe")
     } else {
         // Search for `python` in the `bin` directory.
         venv.join("bin").join("python")
@@ -0,0 +1,198 @@
+//! Find the Python interpreter pinned for a project, via a `.python-version` file or the
+//! `[tool.uv] python` setting in a `pyproject.toml`.
+
+use std::path::{Path, PathBuf};
+
+use fs_err as fs;
+use serde::Deserialize;
+use tracing::debug;
+
+use platform_host::Platform;
+use uv_cache::Cache;
+
+use crate::{find_default_python, find_requested_python, Error, Interpreter};
+
+/// The name of the file that pins the Python interpreter for a project.
+pub const PYTHON_VERSION_FILENAME: &str = ".python-version";
+
+/// A Python interpreter request pinned for a project (e.g., `3.11`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PythonPin {
+    /// The request for the Python interpreter, in any format accepted by `--python`.
+    pub request: String,
+    /// The `.python-version` or `pyproject.toml` file from which the request was read.
+    pub path: PathBuf,
+}
+
+impl PythonPin {
+    /// Find the pinned Python request that applies to the given directory.
+    ///
+    /// Searches the directory and each of its parents for a `.python-version` file, or a
+    /// `pyproject.toml` with a `[tool.uv] python` setting, stopping at the first directory that
+    /// contains either. Within a single directory, the `.python-version` file takes precedence.
+    pub fn find(dir: &Path) -> Result<Option<Self>, Error> {
+        for dir in dir.ancestors() {
+            let path = dir.join(PYTHON_VERSION_FILENAME);
+            if let Some(request) = read_python_version(&path)? {
+                // pyenv uses `system` to opt out of any pinned version.
+                if request == "system" {
+                    debug!("Ignoring `system` Python pin in: {}", path.display());
+                    return Ok(None);
+                }
+                debug!("Found pinned Python `{request}` in: {}", path.display());
+                return Ok(Some(Self { request, path }));
+            }
+
+            let path = dir.join("pyproject.toml");
+            if let Some(request) = read_pyproject_toml(&path)? {
+                debug!("Found pinned Python `{request}` in: {}", path.display());
+                return Ok(Some(Self { request, path }));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Write the given request to the `.python-version` file in the given directory.
+    pub fn write(dir: &Path, request: &str) -> Result<Self, Error> {
+        let path = dir.join(PYTHON_VERSION_FILENAME);
+        fs::write(&path, format!("{request}\n"))?;
+        Ok(Self {
+            request: request.to_string(),
+            path,
+        })
+    }
+}
+
+/// Find the Python interpreter pinned for the given directory (see [`PythonPin::find`]), or the
+/// default Python interpreter if there's no pin.
+pub fn find_pinned_python(
+    dir: &Path,
+    platform: &Platform,
+    cache: &Cache,
+) -> Result<Interpreter, Error> {
+    if let Some(pin) = PythonPin::find(dir)? {
+        find_requested_python(&pin.request, platform, cache)?
+            .ok_or_else(|| Error::NoSuchPython(pin.request))
+    } else {
+        find_default_python(platform, cache)
+    }
+}
+
+/// Read the request from a `.python-version` file, i.e., its first non-empty, non-comment line.
+///
+/// Like pyenv, only the first of any whitespace-separated versions on the line is used.
+fn read_python_version(path: &Path) -> Result<Option<String>, Error> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err.into()),
+    };
+    Ok(contents
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !line.starts_with('#'))
+        .and_then(|line| line.split_whitespace().next())
+        .map(ToString::to_string))
+}
+
+/// Read the request from the `[tool.uv] python` setting in a `pyproject.toml`.
+fn read_pyproject_toml(path: &Path) -> Result<Option<String>, Error> {
+    #[derive(Deserialize)]
+    struct PyProjectToml {
+        tool: Option<Tool>,
+    }
+
+    #[derive(Deserialize)]
+    struct Tool {
+        uv: Option<ToolUv>,
+    }
+
+    #[derive(Deserialize)]
+    struct ToolUv {
+        python: Option<String>,
+    }
+
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err.into()),
+    };
+    let pyproject_toml = toml::from_str::<PyProjectToml>(&contents)
+        .map_err(|err| Error::PyprojectToml(path.to_path_buf(), Box::new(err)))?;
+    Ok(pyproject_toml
+        .tool
+        .and_then(|tool| tool.uv)
+        .and_then(|uv| uv.python))
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use fs_err as fs;
+    use indoc::indoc;
+    use tempfile::TempDir;
+
+    use super::{PythonPin, PYTHON_VERSION_FILENAME};
+
+    #[test]
+    fn find() -> Result<()> {
+        let root = TempDir::new()?;
+        let project = root.path().join("project");
+        let nested = project.join("src").join("package");
+        fs::create_dir_all(&nested)?;
+
+        assert_eq!(PythonPin::find(&nested)?, None);
+
+        // A `[tool.uv] python` setting applies to nested directories.
+        fs::write(
+            project.join("pyproject.toml"),
+            indoc! {r#"
+                [project]
+                name = "project"
+
+                [tool.uv]
+                python = "3.11"
+            "#},
+        )?;
+        assert_eq!(
+            PythonPin::find(&nested)?,
+            Some(PythonPin {
+                request: "3.11".to_string(),
+                path: project.join("pyproject.toml"),
+            })
+        );
+
+        // A `.python-version` file takes precedence within the same directory.
+        fs::write(
+            project.join(PYTHON_VERSION_FILENAME),
+            "# Pinned for CI\n\n3.12\n",
+        )?;
+        assert_eq!(
+            PythonPin::find(&nested)?,
+            Some(PythonPin {
+                request: "3.12".to_string(),
+                path: project.join(PYTHON_VERSION_FILENAME),
+            })
+        );
+
+        // The closest pin wins.
+        let pin = PythonPin::write(&nested, "3.10")?;
+        assert_eq!(PythonPin::find(&nested)?, Some(pin));
+
+        // Only the first of multiple pyenv versions is used.
+        fs::write(project.join(PYTHON_VERSION_FILENAME), "3.12.1 3.11.7\n")?;
+        assert_eq!(
+            PythonPin::find(&project)?,
+            Some(PythonPin {
+                request: "3.12.1".to_string(),
+                path: project.join(PYTHON_VERSION_FILENAME),
+            })
+        );
+
+        // A `system` pin opts out of pinning, including any pins in parent directories.
+        fs::write(project.join(PYTHON_VERSION_FILENAME), "system\n")?;
+        assert_eq!(PythonPin::find(&project)?, None);
+
+        Ok(())
+    }
+}
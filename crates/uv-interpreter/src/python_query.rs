@@ -1,11 +1,16 @@
 //! Find a user requested python version/interpreter.
 
 use std::borrow::Cow;
+use std::collections::HashSet;
 use std::env;
-use std::path::PathBuf;
+use std::ffi::OsStr;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
 use tracing::{debug, instrument};
 
+use pep440_rs::Version;
 use platform_host::Platform;
 use uv_cache::Cache;
 use uv_fs::normalize_path;
@@ -15,9 +20,10 @@ use crate::{Error, Interpreter};
 /// Find a python version/interpreter of a specific version.
 ///
 /// Supported formats:
-/// * `-p 3.10` searches for an installed Python 3.10 (`py --list-paths` on Windows, `python3.10` on
+/// * `-p 3.10` searches for an installed Python 3.10 (PEP 514 registry on Windows, `python3.10` on
 ///   Linux/Mac). Specifying a patch version is not supported.
 /// * `-p python3.10` or `-p python.exe` looks for a binary in `PATH`.
+/// * `-p pypy3.10-7.3.13` uses the pyenv or asdf installation with the given name.
 /// * `-p /home/ferris/.local/bin/python3.10` uses this exact Python.
 ///
 /// When the user passes a patch version (e.g. 3.12.1), we currently search for a matching minor
@@ -57,6 +63,18 @@ pub fn find_requested_python(
             _ => unreachable!(),
         }
     } else if !request.contains(std::path::MAIN_SEPARATOR) {
+        // `-p pypy3.10-7.3.13`, as named by pyenv or asdf (e.g., in a `.python-version` file).
+        let installation = if env::var_os("UV_TEST_PYTHON_PATH").is_some() {
+            None
+        } else {
+            managed_installations()
+                .into_iter()
+                .find(|installation| installation.name == request)
+        };
+        if let Some(installation) = installation {
+            return Interpreter::query(&installation.executable, platform.clone(), cache).map(Some);
+        }
+
         // `-p python3.10`; Generally not used on windows because all Python are `python.exe`.
         let Some(executable) = Interpreter::find_executable(request)? else {
             return Ok(None);
@@ -86,6 +104,319 @@ pub fn find_default_python(platform: &Platform, cache: &Cache) -> Result<Interpr
     })
 }
 
+/// The location in which a Python interpreter was discovered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PythonSource {
+    /// An executable in `PATH` (or `UV_TEST_PYTHON_PATH`, if set).
+    SearchPath,
+    /// A pyenv installation, found either through its shims in `PATH` or in `$PYENV_ROOT/versions`.
+    Pyenv,
+    /// An asdf installation, found either through its shims in `PATH` or in
+    /// `$ASDF_DATA_DIR/installs/python`.
+    Asdf,
+    /// An installation registered in the Windows registry (PEP 514).
+    Registry,
+    /// An installation reported by the Windows `py` launcher (`py --list-paths`).
+    PyLauncher,
+}
+
+impl fmt::Display for PythonSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SearchPath => f.write_str("PATH"),
+            Self::Pyenv => f.write_str("pyenv"),
+            Self::Asdf => f.write_str("asdf"),
+            Self::Registry => f.write_str("registry"),
+            Self::PyLauncher => f.write_str("py launcher"),
+        }
+    }
+}
+
+/// Find all Python interpreters that uv can discover, in the order in which they're considered
+/// when searching for a requested version.
+///
+/// Each `PATH` entry is searched for `python`, `python3` and `python3.x` executables, followed by
+/// any pyenv and asdf installations and, on Windows, the installations registered in the registry
+/// or reported by `py --list-paths`. If
+/// `UV_TEST_PYTHON_PATH` is set, only its entries are searched.
+///
+/// Executables that resolve to the same interpreter are only reported once, and executables that
+/// can't be queried (e.g., Python 2 installations) are skipped.
+#[instrument(skip_all)]
+pub fn find_all_pythons(
+    platform: &Platform,
+    cache: &Cache,
+) -> Result<Vec<(PythonSource, Interpreter)>, Error> {
+    debug!("Starting discovery of all Python interpreters");
+
+    #[allow(non_snake_case)]
+    let UV_TEST_PYTHON_PATH = env::var_os("UV_TEST_PYTHON_PATH");
+
+    let override_path = UV_TEST_PYTHON_PATH.is_some();
+    let pyenv_root = pyenv_root();
+    let asdf_root = asdf_root();
+
+    #[allow(non_snake_case)]
+    let PATH = UV_TEST_PYTHON_PATH
+        .or(env::var_os("PATH"))
+        .unwrap_or_default();
+
+    // Determine whether an executable (or directory) belongs to a pyenv or asdf installation.
+    let managed_source = |path: &Path| {
+        if pyenv_root
+            .as_ref()
+            .is_some_and(|root| path.starts_with(root))
+        {
+            Some(PythonSource::Pyenv)
+        } else if asdf_root
+            .as_ref()
+            .is_some_and(|root| path.starts_with(root))
+        {
+            Some(PythonSource::Asdf)
+        } else {
+            None
+        }
+    };
+
+    let mut candidates = Vec::new();
+    for path in env::split_paths(&PATH) {
+        let Ok(entries) = fs_err::read_dir(&path) else {
+            continue;
+        };
+        let mut executables = entries
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|executable| {
+                executable
+                    .file_name()
+                    .and_then(OsStr::to_str)
+                    .map_or(false, is_python_executable_name)
+            })
+            .filter(|executable| !(cfg!(windows) && windows::is_windows_store_shim(executable)))
+            .collect::<Vec<_>>();
+        executables.sort();
+
+        let source = managed_source(&path).unwrap_or(PythonSource::SearchPath);
+        candidates.extend(
+            executables
+                .into_iter()
+                .map(|executable| (source, executable)),
+        );
+    }
+
+    if !override_path {
+        // Look for installations that pyenv and asdf manage, but that aren't selected by their
+        // shims.
+        candidates.extend(
+            managed_installations()
+                .into_iter()
+                .map(|installation| (installation.source, installation.executable)),
+        );
+
+        if cfg!(windows) {
+            let mut installations = windows::registry_installations()
+                .into_iter()
+                .map(|installation| (PythonSource::Registry, installation))
+                .collect::<Vec<_>>();
+            match windows::py_list_installations() {
+                Ok(listed) => installations.extend(
+                    listed
+                        .into_iter()
+                        .map(|installation| (PythonSource::PyLauncher, installation)),
+                ),
+                Err(Error::PyList(error)) => {
+                    if error.kind() == std::io::ErrorKind::NotFound {
+                        debug!("`py` is not installed");
+                    }
+                }
+                Err(error) => return Err(error),
+            }
+
+            // The registry and the `py` launcher also report installations that live in `PATH`
+            // or are managed by pyenv, so label those by where they actually live.
+            let search_path = env::split_paths(&PATH).collect::<Vec<_>>();
+            candidates.extend(
+                installations
+                    .into_iter()
+                    .filter_map(|(source, installation)| match installation {
+                        PythonInstallation::Listed {
+                            executable_path, ..
+                        } => {
+                            let source = managed_source(&executable_path)
+                                .or_else(|| {
+                                    executable_path
+                                        .parent()
+                                        .is_some_and(|parent| {
+                                            search_path.iter().any(|path| path == parent)
+                                        })
+                                        .then_some(PythonSource::SearchPath)
+                                })
+                                .unwrap_or(source);
+                            Some((source, executable_path))
+                        }
+                        PythonInstallation::Interpreter(_) => None,
+                    }),
+            );
+        }
+    }
+
+    // Query each candidate, skipping any that resolve to an interpreter we've already seen (e.g.,
+    // `python3` and `python3.12` are commonly symlinks to the same executable).
+    let mut seen = HashSet::new();
+    let mut pythons = Vec::new();
+    for (source, executable) in candidates {
+        let canonical = fs_err::canonicalize(&executable).unwrap_or_else(|_| executable.clone());
+        if !seen.insert(canonical.clone()) {
+            continue;
+        }
+
+        let interpreter = match Interpreter::query(&executable, platform.clone(), cache) {
+            Ok(interpreter) => interpreter,
+            Err(error) => {
+                debug!("Skipping `{}`: {error}", executable.display());
+                continue;
+            }
+        };
+
+        // Shims (like pyenv's) resolve to an interpreter elsewhere on disk.
+        let sys_executable = interpreter.sys_executable();
+        let sys_executable =
+            fs_err::canonicalize(sys_executable).unwrap_or_else(|_| sys_executable.to_path_buf());
+        if sys_executable != canonical && !seen.insert(sys_executable) {
+            continue;
+        }
+
+        pythons.push((source, interpreter));
+    }
+
+    Ok(pythons)
+}
+
+/// Returns `true` if the file name is that of a Python executable, like `python`, `python3`, or
+/// `python3.12` (with an `.exe` extension on Windows).
+fn is_python_executable_name(name: &str) -> bool {
+    let name = if cfg!(windows) {
+        let Some(name) = name.strip_suffix(".exe") else {
+            return false;
+        };
+        name
+    } else {
+        name
+    };
+    let Some(version) = name.strip_prefix("python") else {
+        return false;
+    };
+    if version.is_empty() {
+        return true;
+    }
+    let mut parts = version.splitn(2, '.');
+    let major = parts.next().unwrap_or_default();
+    let minor = parts.next();
+    !major.is_empty()
+        && major.bytes().all(|byte| byte.is_ascii_digit())
+        && minor.map_or(true, |minor| {
+            !minor.is_empty() && minor.bytes().all(|byte| byte.is_ascii_digit())
+        })
+}
+
+/// Returns the root of the pyenv installation, if any.
+fn pyenv_root() -> Option<PathBuf> {
+    let root = if let Some(root) = env::var_os("PYENV_ROOT") {
+        PathBuf::from(root)
+    } else {
+        let home = env::var_os(if cfg!(windows) { "USERPROFILE" } else { "HOME" })?;
+        if cfg!(windows) {
+            PathBuf::from(home).join(".pyenv").join("pyenv-win")
+        } else {
+            PathBuf::from(home).join(".pyenv")
+        }
+    };
+    root.is_dir().then_some(root)
+}
+
+/// Returns the root of the asdf installation, if any.
+fn asdf_root() -> Option<PathBuf> {
+    let root = if let Some(root) = env::var_os("ASDF_DATA_DIR") {
+        PathBuf::from(root)
+    } else {
+        PathBuf::from(env::var_os("HOME")?).join(".asdf")
+    };
+    root.is_dir().then_some(root)
+}
+
+/// A Python installation managed by pyenv or asdf.
+struct ManagedInstallation {
+    source: PythonSource,
+    /// The name of the installation's directory, which is also the name used to select it in a
+    /// `.python-version` file (e.g., `3.12.1` or `pypy3.10-7.3.13`).
+    name: String,
+    executable: PathBuf,
+}
+
+/// Find the installations in `$PYENV_ROOT/versions` and `$ASDF_DATA_DIR/installs/python`,
+/// ordered by manager and then by name.
+fn managed_installations() -> Vec<ManagedInstallation> {
+    managed_installations_in(pyenv_root().as_deref(), asdf_root().as_deref())
+}
+
+/// Find the installations managed by pyenv and asdf, given their respective roots.
+fn managed_installations_in(
+    pyenv_root: Option<&Path>,
+    asdf_root: Option<&Path>,
+) -> Vec<ManagedInstallation> {
+    let roots = [
+        (
+            PythonSource::Pyenv,
+            pyenv_root.map(|root| root.join("versions")),
+        ),
+        (
+            PythonSource::Asdf,
+            asdf_root.map(|root| root.join("installs").join("python")),
+        ),
+    ];
+
+    let mut installations = Vec::new();
+    for (source, root) in roots {
+        let Some(entries) = root.and_then(|root| fs_err::read_dir(root).ok()) else {
+            continue;
+        };
+        let mut versions = entries
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .collect::<Vec<_>>();
+        versions.sort();
+        for version in versions {
+            let Some(name) = version.file_name().and_then(OsStr::to_str) else {
+                continue;
+            };
+            let executable = if cfg!(windows) {
+                version.join("python.exe")
+            } else {
+                version.join("bin").join("python3")
+            };
+            if executable.is_file() {
+                installations.push(ManagedInstallation {
+                    source,
+                    name: name.to_string(),
+                    executable,
+                });
+            }
+        }
+    }
+    installations
+}
+
+/// Parse the major and minor version from the start of a version string, like `3.12`,
+/// `3.12.1`, or `3.12-arm64`.
+fn parse_major_minor(version: &str) -> Option<(u8, u8)> {
+    let (major, rest) = version.split_once('.')?;
+    let minor = rest
+        .split(|c: char| !c.is_ascii_digit())
+        .next()
+        .filter(|minor| !minor.is_empty())?;
+    Some((major.parse().ok()?, minor.parse().ok()?))
+}
+
 /// Same as [`find_default_python`] but returns `None` if no python is found instead of returning an `Err`.
 pub(crate) fn try_find_default_python(
     platform: &Platform,
@@ -103,7 +434,10 @@ pub(crate) fn try_find_default_python(
 ///   * Major: `pythonx`, `python`
 ///   * Default: `python3`, `python`
 ///   * (windows): For each of the above, test for the existence of `python.bat` shim (pyenv-windows) last.
-/// * (windows): Discover installations using `py --list-paths` (PEP514). Continue if `py` is not installed.
+/// * Search the installations managed by pyenv (`$PYENV_ROOT/versions`) and asdf
+///   (`$ASDF_DATA_DIR/installs/python`), newest first.
+/// * (windows): Discover installations registered in the Windows registry (PEP 514), then using
+///   `py --list-paths`. Continue if `py` is not installed.
 ///
 /// (Windows): Filter out the windows store shim (Enabled in Settings/Apps/Advanced app settings/App execution aliases).
 fn find_python(
@@ -181,8 +515,40 @@ fn find_python(
         }
     }
 
+    if !override_path {
+        // Look for installations that pyenv and asdf manage, but that aren't selected by their
+        // shims, preferring the newest matching version.
+        let mut installations = managed_installations()
+            .into_iter()
+            .filter_map(|installation| {
+                let (major, minor) = parse_major_minor(&installation.name)?;
+                let version = Version::from_str(&installation.name).ok();
+                Some((version, major, minor, installation.executable))
+            })
+            .collect::<Vec<_>>();
+        installations.sort_by(|(a, ..), (b, ..)| b.cmp(a));
+        for (_, major, minor, executable_path) in installations {
+            let installation = PythonInstallation::Listed {
+                major,
+                minor,
+                executable_path,
+            };
+            if let Some(interpreter) = installation.select(selector, platform, cache)? {
+                return Ok(Some(interpreter));
+            }
+        }
+    }
+
     if cfg!(windows) && !override_path {
-        // Use `py` to find the python installation on the system.
+        // Read the installations registered in the Windows registry, which doesn't require the
+        // `py` launcher (or any Python) to be on `PATH`.
+        for installation in windows::registry_installations() {
+            if let Some(interpreter) = installation.select(selector, platform, cache)? {
+                return Ok(Some(interpreter));
+            }
+        }
+
+        // Fall back to `py`, to find installations that aren't registered as expected.
         match windows::py_list_paths(selector, platform, cache) {
             Ok(Some(interpreter)) => return Ok(Some(interpreter)),
             Ok(None) => {}
@@ -200,7 +566,10 @@ fn find_python(
 
 #[derive(Debug, Clone)]
 enum PythonInstallation {
-    PyListPath {
+    /// An installation whose version is known without querying the interpreter, as listed in the
+    /// Windows registry (PEP 514), by `py --list-paths`, or by the name of a pyenv or asdf version
+    /// directory.
+    Listed {
         major: u8,
         minor: u8,
         executable_path: PathBuf,
@@ -211,14 +580,14 @@ enum PythonInstallation {
 impl PythonInstallation {
     fn major(&self) -> u8 {
         match self {
-            Self::PyListPath { major, .. } => *major,
+            Self::Listed { major, .. } => *major,
             Self::Interpreter(interpreter) => interpreter.python_major(),
         }
     }
 
     fn minor(&self) -> u8 {
         match self {
-            Self::PyListPath { minor, .. } => *minor,
+            Self::Listed { minor, .. } => *minor,
             Self::Interpreter(interpreter) => interpreter.python_minor(),
         }
     }
@@ -266,7 +635,7 @@ impl PythonInstallation {
         cache: &Cache,
     ) -> Result<Interpreter, Error> {
         match self {
-            Self::PyListPath {
+            Self::Listed {
                 executable_path, ..
             } => Interpreter::query(&executable_path, platform.clone(), cache),
             Self::Interpreter(interpreter) => Ok(interpreter),
@@ -340,7 +709,7 @@ mod windows {
     use platform_host::Platform;
     use uv_cache::Cache;
 
-    use crate::python_query::{PythonInstallation, PythonVersionSelector};
+    use crate::python_query::{parse_major_minor, PythonInstallation, PythonVersionSelector};
     use crate::{Error, Interpreter};
 
     /// ```text
@@ -352,15 +721,25 @@ mod windows {
         Regex::new(r"(?mR)^ -(?:V:)?(\d).(\d+)-?(?:arm)?\d*\s*\*?\s*(.*)$").unwrap()
     });
 
-    /// Run `py --list-paths` to find the installed pythons.
-    ///
-    /// The command takes 8ms on my machine.
-    /// TODO(konstin): Implement <https://peps.python.org/pep-0514/> to read python installations from the registry instead.
+    /// Find the first python matching the selector in the output of `py --list-paths`.
     pub(super) fn py_list_paths(
         selector: PythonVersionSelector,
         platform: &Platform,
         cache: &Cache,
     ) -> Result<Option<Interpreter>, Error> {
+        for installation in py_list_installations()? {
+            if let Some(interpreter) = installation.select(selector, platform, cache)? {
+                return Ok(Some(interpreter));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Run `py --list-paths` to find the installed pythons.
+    ///
+    /// The command takes 8ms on my machine.
+    pub(super) fn py_list_installations() -> Result<Vec<PythonInstallation>, Error> {
         let output = info_span!("py_list_paths")
             .in_scope(|| Command::new("py").arg("--list-paths").output())
             .map_err(Error::PyList)?;
@@ -377,7 +756,6 @@ mod windows {
             });
         }
 
-        // Find the first python of the version we want in the list
         let stdout =
             String::from_utf8(output.stdout).map_err(|err| Error::PythonSubcommandOutput {
                 message: format!("The stdout of `py --list-paths` isn't UTF-8 encoded: {err}"),
@@ -385,24 +763,108 @@ mod windows {
                 stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
             })?;
 
-        for captures in PY_LIST_PATHS.captures_iter(&stdout) {
-            let (_, [major, minor, path]) = captures.extract();
-
-            if let (Some(major), Some(minor)) = (major.parse::<u8>().ok(), minor.parse::<u8>().ok())
-            {
-                let installation = PythonInstallation::PyListPath {
-                    major,
-                    minor,
+        Ok(PY_LIST_PATHS
+            .captures_iter(&stdout)
+            .filter_map(|captures| {
+                let (_, [major, minor, path]) = captures.extract();
+                Some(PythonInstallation::Listed {
+                    major: major.parse::<u8>().ok()?,
+                    minor: minor.parse::<u8>().ok()?,
                     executable_path: PathBuf::from(path),
-                };
+                })
+            })
+            .collect())
+    }
+
+    /// Read the Python installations registered in the Windows registry, per
+    /// <https://peps.python.org/pep-0514/>.
+    ///
+    /// Installations are read from `HKEY_CURRENT_USER\Software\Python` (e.g., per-user python.org
+    /// and Windows Store installations), `HKEY_LOCAL_MACHINE\Software\Python` (e.g., system-wide
+    /// python.org and Anaconda installations) and its 32-bit view, in that order. Within each key,
+    /// every company (like `PythonCore` or `ContinuumAnalytics`) and tag is considered, with the
+    /// `PyLauncher` key (which describes the launcher itself) excluded.
+    ///
+    /// Entries that are malformed or point to a missing executable are skipped.
+    #[cfg(windows)]
+    pub(super) fn registry_installations() -> Vec<PythonInstallation> {
+        use winreg::enums::{HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE};
+        use winreg::RegKey;
+
+        let roots = [
+            (HKEY_CURRENT_USER, r"Software\Python"),
+            (HKEY_LOCAL_MACHINE, r"Software\Python"),
+            (HKEY_LOCAL_MACHINE, r"Software\WOW6432Node\Python"),
+        ];
 
-                if let Some(interpreter) = installation.select(selector, platform, cache)? {
-                    return Ok(Some(interpreter));
+        let mut installations = Vec::new();
+        for (hive, path) in roots {
+            let Ok(root) = RegKey::predef(hive).open_subkey(path) else {
+                continue;
+            };
+            for company in root.enum_keys().filter_map(Result::ok) {
+                if company == "PyLauncher" {
+                    continue;
+                }
+                let Ok(company_key) = root.open_subkey(&company) else {
+                    continue;
+                };
+                for tag in company_key.enum_keys().filter_map(Result::ok) {
+                    let Ok(tag_key) = company_key.open_subkey(&tag) else {
+                        continue;
+                    };
+                    if let Some(installation) = registry_installation(&tag_key, &tag) {
+                        installations.push(installation);
+                    }
                 }
             }
         }
+        installations
+    }
 
-        Ok(None)
+    /// Read a single installation from its tag key (e.g., `PythonCore\3.12`).
+    #[cfg(windows)]
+    fn registry_installation(tag_key: &winreg::RegKey, tag: &str) -> Option<PythonInstallation> {
+        let install_path = tag_key.open_subkey("InstallPath").ok()?;
+
+        // Prefer the `ExecutablePath` value, falling back to `python.exe` in the installation
+        // directory (the key's default value), as older installers don't set the former.
+        let executable_path = install_path
+            .get_value::<String, _>("ExecutablePath")
+            .ok()
+            .filter(|path| !path.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| {
+                install_path
+                    .get_value::<String, _>("")
+                    .ok()
+                    .map(|dir| PathBuf::from(dir).join("python.exe"))
+            })?;
+        if !executable_path.is_file() || is_windows_store_shim(&executable_path) {
+            return None;
+        }
+        tracing::debug!(
+            "Found `{tag}` in the registry: {}",
+            executable_path.display()
+        );
+
+        // `SysVersion` is optional for companies other than `PythonCore`, whose tags are
+        // conventionally the version itself (e.g., `3.12` or `3.12-32`).
+        let version = tag_key
+            .get_value::<String, _>("SysVersion")
+            .unwrap_or_else(|_| tag.to_string());
+        let (major, minor) = parse_major_minor(&version)?;
+
+        Some(PythonInstallation::Listed {
+            major,
+            minor,
+            executable_path,
+        })
+    }
+
+    #[cfg(not(windows))]
+    pub(super) fn registry_installations() -> Vec<PythonInstallation> {
+        Vec::new()
     }
 
     /// On Windows we might encounter the windows store proxy shim (Enabled in Settings/Apps/Advanced app settings/App execution aliases).
@@ -478,7 +940,10 @@ mod tests {
     use platform_host::Platform;
     use uv_cache::Cache;
 
-    use crate::python_query::find_requested_python;
+    use crate::python_query::{
+        find_requested_python, is_python_executable_name, managed_installations_in,
+        parse_major_minor,
+    };
     use crate::Error;
 
     fn format_err<T: std::fmt::Debug>(err: Result<T, Error>) -> String {
@@ -519,6 +984,70 @@ mod tests {
         );
     }
 
+    #[test]
+    fn python_executable_names() {
+        for name in ["python", "python3", "python3.12"] {
+            assert!(is_python_executable_name(name), "{name}");
+        }
+        for name in [
+            "python3.12-config",
+            "python3-config",
+            "python3.",
+            "pythonw",
+            "python3.12m",
+            "ipython",
+        ] {
+            assert!(!is_python_executable_name(name), "{name}");
+        }
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn managed_installations() -> anyhow::Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let pyenv_root = temp_dir.path().join("pyenv");
+        let asdf_root = temp_dir.path().join("asdf");
+        for executable in [
+            pyenv_root.join("versions/3.12.1/bin/python3"),
+            pyenv_root.join("versions/3.11.7/bin/python3"),
+            asdf_root.join("installs/python/3.10.13/bin/python3"),
+        ] {
+            fs_err::create_dir_all(executable.parent().unwrap())?;
+            fs_err::write(executable, "")?;
+        }
+        // An installation without an executable (e.g., a failed build) is skipped.
+        fs_err::create_dir_all(pyenv_root.join("versions/3.13.0/bin"))?;
+
+        let installations = managed_installations_in(Some(&pyenv_root), Some(&asdf_root))
+            .into_iter()
+            .map(|installation| {
+                assert!(installation.executable.is_file());
+                format!("{} {}", installation.source, installation.name)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            installations,
+            ["pyenv 3.11.7", "pyenv 3.12.1", "asdf 3.10.13"]
+        );
+
+        // A root without any installations is ignored.
+        assert!(managed_installations_in(Some(&temp_dir.path().join("missing")), None).is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn listed_versions() {
+        assert_eq!(parse_major_minor("3.12"), Some((3, 12)));
+        assert_eq!(parse_major_minor("3.12.1"), Some((3, 12)));
+        assert_eq!(parse_major_minor("3.13.0rc1"), Some((3, 13)));
+        assert_eq!(parse_major_minor("3.12-32"), Some((3, 12)));
+        assert_eq!(parse_major_minor("3.9-arm64"), Some((3, 9)));
+        assert_eq!(parse_major_minor("Anaconda3"), None);
+        assert_eq!(parse_major_minor("pypy3.10-7.3.13"), None);
+        assert_eq!(parse_major_minor("3"), None);
+    }
+
     #[test]
     fn no_such_python_path() {
         let result = find_requested_python(
@@ -0,0 +1,108 @@
+use pep508_rs::MarkerEnvironment;
+use platform_host::{Arch, Os, Platform};
+
+/// A platform for which to resolve or install packages, as an alternative to the platform of the
+/// current interpreter (e.g., to collect Linux wheels on macOS for deployment elsewhere).
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum TargetTriple {
+    /// An alias for `x86_64-pc-windows-msvc`, the default target for Windows.
+    Windows,
+
+    /// An alias for `x86_64-unknown-linux-gnu`, the default target for Linux.
+    Linux,
+
+    /// An alias for `aarch64-apple-darwin`, the default target for macOS.
+    Macos,
+
+    /// An x86 Windows target.
+    #[cfg_attr(feature = "clap", value(name = "x86_64-pc-windows-msvc"))]
+    X8664PcWindowsMsvc,
+
+    /// An x86 Linux target, equivalent to `x86_64-manylinux_2_17`.
+    #[cfg_attr(feature = "clap", value(name = "x86_64-unknown-linux-gnu"))]
+    X8664UnknownLinuxGnu,
+
+    /// An ARM Linux target, equivalent to `aarch64-manylinux_2_17`.
+    #[cfg_attr(feature = "clap", value(name = "aarch64-unknown-linux-gnu"))]
+    Aarch64UnknownLinuxGnu,
+
+    /// An x86 Linux target with musl, equivalent to `x86_64-musllinux_1_2`.
+    #[cfg_attr(feature = "clap", value(name = "x86_64-unknown-linux-musl"))]
+    X8664UnknownLinuxMusl,
+
+    /// An ARM-based macOS target, as seen on Apple Silicon devices, targeting macOS 12.0 and later.
+    #[cfg_attr(feature = "clap", value(name = "aarch64-apple-darwin"))]
+    Aarch64AppleDarwin,
+
+    /// An x86 macOS target, targeting macOS 12.0 and later.
+    #[cfg_attr(feature = "clap", value(name = "x86_64-apple-darwin"))]
+    X8664AppleDarwin,
+}
+
+impl TargetTriple {
+    /// Return the [`Platform`] for the target, from which its compatible wheel tags are derived.
+    pub fn platform(self) -> Platform {
+        match self {
+            Self::Windows | Self::X8664PcWindowsMsvc => Platform::new(Os::Windows, Arch::X86_64),
+            Self::Linux | Self::X8664UnknownLinuxGnu => Platform::new(
+                Os::Manylinux {
+                    major: 2,
+                    minor: 17,
+                },
+                Arch::X86_64,
+            ),
+            Self::Aarch64UnknownLinuxGnu => Platform::new(
+                Os::Manylinux {
+                    major: 2,
+                    minor: 17,
+                },
+                Arch::Aarch64,
+            ),
+            Self::X8664UnknownLinuxMusl => {
+                Platform::new(Os::Musllinux { major: 1, minor: 2 }, Arch::X86_64)
+            }
+            Self::Macos | Self::Aarch64AppleDarwin => Platform::new(
+                Os::Macos {
+                    major: 12,
+                    minor: 0,
+                },
+                Arch::Aarch64,
+            ),
+            Self::X8664AppleDarwin => Platform::new(
+                Os::Macos {
+                    major: 12,
+                    minor: 0,
+                },
+                Arch::X86_64,
+            ),
+        }
+    }
+
+    /// Return a [`MarkerEnvironment`] compatible with the target, based on a base
+    /// [`MarkerEnvironment`].
+    ///
+    /// The returned [`MarkerEnvironment`] will preserve the base environment's Python version
+    /// markers, but override its platform markers. Markers that can't be inferred from the target
+    /// alone (`platform_release` and `platform_version`) are left empty.
+    pub fn markers(self, base: &MarkerEnvironment) -> MarkerEnvironment {
+        let (os_name, platform_machine, platform_system, sys_platform) = match self {
+            Self::Windows | Self::X8664PcWindowsMsvc => ("nt", "AMD64", "Windows", "win32"),
+            Self::Linux | Self::X8664UnknownLinuxGnu | Self::X8664UnknownLinuxMusl => {
+                ("posix", "x86_64", "Linux", "linux")
+            }
+            Self::Aarch64UnknownLinuxGnu => ("posix", "aarch64", "Linux", "linux"),
+            Self::Macos | Self::Aarch64AppleDarwin => ("posix", "arm64", "Darwin", "darwin"),
+            Self::X8664AppleDarwin => ("posix", "x86_64", "Darwin", "darwin"),
+        };
+        MarkerEnvironment {
+            os_name: os_name.to_string(),
+            platform_machine: platform_machine.to_string(),
+            platform_system: platform_system.to_string(),
+            sys_platform: sys_platform.to_string(),
+            platform_release: String::new(),
+            platform_version: String::new(),
+            ..base.clone()
+        }
+    }
+}
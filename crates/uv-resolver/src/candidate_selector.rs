@@ -21,7 +21,7 @@ pub(crate) struct CandidateSelector {
 
 impl CandidateSelector {
     /// Return a [`CandidateSelector`] for the given [`Manifest`].
-    pub(crate) fn for_resolution(manifest: &Manifest, options: Options) -> Self {
+    pub(crate) fn for_resolution(manifest: &Manifest, options: &Options) -> Self {
         Self {
             resolution_strategy: ResolutionStrategy::from_mode(options.resolution_mode, manifest),
             prerelease_strategy: PreReleaseStrategy::from_mode(options.prerelease_mode, manifest),
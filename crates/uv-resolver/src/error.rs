@@ -1,20 +1,26 @@
 use std::collections::BTreeSet;
 use std::convert::Infallible;
 use std::fmt::Formatter;
+use std::time::Duration;
 
 use dashmap::{DashMap, DashSet};
 use indexmap::IndexMap;
+use itertools::Itertools;
+use owo_colors::OwoColorize;
 use pubgrub::range::Range;
 use pubgrub::report::{DefaultStringReporter, DerivationTree, Reporter};
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
 
-use distribution_types::{BuiltDist, IndexLocations, PathBuiltDist, PathSourceDist, SourceDist};
+use distribution_types::{
+    BuiltDist, IndexLocations, Name, PathBuiltDist, PathSourceDist, SourceDist,
+};
 use once_map::OnceMap;
 use pep440_rs::Version;
-use pep508_rs::Requirement;
+use pep508_rs::{MarkerTree, Requirement};
 use uv_normalize::PackageName;
 
 use crate::candidate_selector::CandidateSelector;
+use crate::provenance::Provenance;
 use crate::pubgrub::{PubGrubPackage, PubGrubPython, PubGrubReportFormatter};
 use crate::python_requirement::PythonRequirement;
 use crate::resolver::{UnavailablePackage, VersionsResponse};
@@ -86,9 +92,93 @@ pub enum ResolveError {
         version: Box<Version>,
     },
 
+    #[error("Resolution did not complete within {}s{}", .timeout.as_secs(), .conflicts)]
+    Timeout {
+        /// The configured time limit.
+        timeout: Duration,
+        /// The packages most frequently involved in conflicts at the time of the abort.
+        conflicts: ConflictSummary,
+    },
+
+    #[error("Resolution did not complete within {max_steps} steps{conflicts}")]
+    StepLimit {
+        /// The configured step limit.
+        max_steps: usize,
+        /// The packages most frequently involved in conflicts at the time of the abort.
+        conflicts: ConflictSummary,
+    },
+
     /// Something unexpected happened.
     #[error("{0}")]
     Failure(String),
+
+    /// An error involving a requirement that was read from a file, along with the line at which
+    /// the requirement was declared.
+    #[error("{}: `{}`", .0.origin, .0.requirement)]
+    Declared(Box<Provenance>, #[source] Box<ResolveError>),
+}
+
+impl ResolveError {
+    /// Attribute the error to the user-provided requirement from which it arose, if the error
+    /// concerns a single package that was requested in a file.
+    #[must_use]
+    pub fn with_provenance(self, provenance: &[Provenance]) -> Self {
+        let name = match &self {
+            Self::NotFound(requirement) => &requirement.name,
+            Self::NameMismatch { given, .. } => given,
+            Self::Fetch(dist, _) => dist.name(),
+            Self::FetchAndBuild(dist, _) => dist.name(),
+            Self::Read(dist, _) => dist.name(),
+            Self::Build(dist, _) => dist.name(),
+            _ => return self,
+        };
+        match Provenance::find_in_file(provenance, name) {
+            Some(provenance) => Self::Declared(Box::new(provenance.clone()), Box::new(self)),
+            None => self,
+        }
+    }
+}
+
+/// A summary of the packages that were most frequently rejected by the solver, used to provide a
+/// partial explanation when resolution is aborted before completion.
+#[derive(Debug, Default)]
+pub struct ConflictSummary(Vec<(PubGrubPackage, usize)>);
+
+impl ConflictSummary {
+    /// The maximum number of packages to include in the summary.
+    const LIMIT: usize = 5;
+
+    /// Summarize the given per-package conflict counts.
+    pub(crate) fn from_counts(counts: &FxHashMap<PubGrubPackage, usize>) -> Self {
+        let mut counts = counts
+            .iter()
+            .map(|(package, count)| (package.clone(), *count))
+            .collect::<Vec<_>>();
+        counts.sort_by(|(a_package, a_count), (b_package, b_count)| {
+            b_count
+                .cmp(a_count)
+                .then_with(|| a_package.to_string().cmp(&b_package.to_string()))
+        });
+        counts.truncate(Self::LIMIT);
+        Self(counts)
+    }
+}
+
+impl std::fmt::Display for ConflictSummary {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.0.is_empty() {
+            return Ok(());
+        }
+        write!(
+            f,
+            "; the following packages were involved in the most conflicts:"
+        )?;
+        for (package, count) in &self.0 {
+            let s = if *count == 1 { "" } else { "s" };
+            write!(f, "\n- {package} ({count} rejected version{s})")?;
+        }
+        Ok(())
+    }
 }
 
 impl<T> From<tokio::sync::mpsc::error::SendError<T>> for ResolveError {
@@ -122,6 +212,8 @@ impl From<pubgrub::error::PubGrubError<PubGrubPackage, Range<Version>, Infallibl
                     python_requirement: None,
                     index_locations: None,
                     unavailable_packages: FxHashMap::default(),
+                    dependency_markers: FxHashMap::default(),
+                    provenance: Vec::new(),
                 })
             }
             pubgrub::error::PubGrubError::SelfDependency { package, version } => {
@@ -143,6 +235,8 @@ pub struct NoSolutionError {
     python_requirement: Option<PythonRequirement>,
     index_locations: Option<IndexLocations>,
     unavailable_packages: FxHashMap<PackageName, UnavailablePackage>,
+    dependency_markers: FxHashMap<(Option<PackageName>, PackageName), Option<MarkerTree>>,
+    provenance: Vec<Provenance>,
 }
 
 impl std::error::Error for NoSolutionError {}
@@ -168,11 +262,53 @@ impl std::fmt::Display for NoSolutionError {
             write!(f, "\n\n{hint}")?;
         }
 
+        // If the conflicting requirements were read from multiple inputs, point at each of them.
+        if self
+            .provenance
+            .iter()
+            .map(|provenance| provenance.origin.path())
+            .collect::<FxHashSet<_>>()
+            .len()
+            > 1
+        {
+            write!(
+                f,
+                "\n\n{}{} The requirements involved were declared at:",
+                "hint".bold().cyan(),
+                ":".bold(),
+            )?;
+            for provenance in &self.provenance {
+                write!(f, "\n- {provenance}")?;
+            }
+            for (package, parents) in self.transitive_parents() {
+                write!(
+                    f,
+                    "\n- `{package}` (via {})",
+                    parents
+                        .iter()
+                        .map(|parent| format!("`{parent}`"))
+                        .join(", ")
+                )?;
+            }
+        }
+
         Ok(())
     }
 }
 
 impl NoSolutionError {
+    /// Render the derivation tree that led to the resolution failure, with one incompatibility
+    /// per line.
+    ///
+    /// Intended for verbose output, as a complement to the narrative report.
+    pub fn derivation_tree(&self) -> String {
+        let formatter = PubGrubReportFormatter {
+            available_versions: &self.available_versions,
+            python_requirement: self.python_requirement.as_ref(),
+        };
+        formatter.tree(&self.derivation_tree, &self.dependency_markers)
+    }
+
     /// Update the available versions attached to the error using the given package version index.
     ///
     /// Only packages used in the error's derivation tree will be retrieved.
@@ -257,6 +393,82 @@ impl NoSolutionError {
         self
     }
 
+    /// Update the markers under which each dependency was requested, attached to the error.
+    ///
+    /// Only dependencies of packages used in the error's derivation tree will be retained.
+    #[must_use]
+    pub(crate) fn with_dependency_markers(
+        mut self,
+        dependency_markers: &DashMap<(Option<PackageName>, PackageName), Option<MarkerTree>>,
+    ) -> Self {
+        let packages = self
+            .derivation_tree
+            .packages()
+            .into_iter()
+            .filter_map(|package| match package {
+                PubGrubPackage::Package(name, ..) => Some(name.clone()),
+                _ => None,
+            })
+            .collect::<FxHashSet<_>>();
+        let mut new = FxHashMap::default();
+        for entry in dependency_markers {
+            let (source, dependency) = entry.key();
+            if packages.contains(dependency) {
+                new.insert((source.clone(), dependency.clone()), entry.value().clone());
+            }
+        }
+        self.dependency_markers = new;
+        self
+    }
+
+    /// Update the user-provided requirements attached to the error, along with their origins.
+    ///
+    /// Only requirements on packages used in the error's derivation tree will be retained.
+    #[must_use]
+    pub(crate) fn with_provenance(mut self, provenance: &[Provenance]) -> Self {
+        let packages = self
+            .derivation_tree
+            .packages()
+            .into_iter()
+            .filter_map(|package| match package {
+                PubGrubPackage::Package(name, ..) => Some(name),
+                _ => None,
+            })
+            .collect::<FxHashSet<_>>();
+        self.provenance = provenance
+            .iter()
+            .filter(|provenance| packages.contains(&provenance.requirement.name))
+            .cloned()
+            .collect();
+        self
+    }
+
+    /// Return the packages in the derivation tree that weren't requested directly, along with the
+    /// packages that depend on them.
+    fn transitive_parents(&self) -> Vec<(&PackageName, Vec<&PackageName>)> {
+        let mut parents: FxHashMap<&PackageName, Vec<&PackageName>> = FxHashMap::default();
+        for (source, dependency) in self.dependency_markers.keys() {
+            if let Some(source) = source {
+                if self
+                    .provenance
+                    .iter()
+                    .all(|provenance| provenance.requirement.name != *dependency)
+                {
+                    parents.entry(dependency).or_default().push(source);
+                }
+            }
+        }
+        parents
+            .into_iter()
+            .map(|(package, mut parents)| {
+                parents.sort_unstable();
+                parents.dedup();
+                (package, parents)
+            })
+            .sorted_unstable_by(|(a, _), (b, _)| a.cmp(b))
+            .collect()
+    }
+
     /// Update the Python requirements attached to the error.
     #[must_use]
     pub(crate) fn with_python_requirement(
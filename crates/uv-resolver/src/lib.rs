@@ -1,15 +1,24 @@
+//! Resolve a set of requirements into a set of pinned versions.
+//!
+//! The main entrypoint is [`ResolverBuilder`], which takes the requirements to resolve, a
+//! [`RegistryClient`][uv_client::RegistryClient] for fetching package metadata, the platform
+//! [`Tags`][platform_tags::Tags] to resolve for, and a [`BuildContext`][uv_traits::BuildContext]
+//! for building source distributions, and returns a [`ResolutionGraph`].
+
 pub use dependency_mode::DependencyMode;
-pub use error::ResolveError;
+pub use error::{ConflictSummary, ResolveError};
 pub use finder::{DistFinder, Reporter as FinderReporter};
 pub use manifest::Manifest;
+pub use min_release_age::{MinReleaseAge, MinReleaseAgeEntry, MinReleaseAgeError};
 pub use options::{Options, OptionsBuilder};
 pub use prerelease_mode::PreReleaseMode;
+pub use provenance::{Origin, Provenance, ProvenanceKind};
 pub use python_requirement::PythonRequirement;
 pub use resolution::{AnnotationStyle, Diagnostic, DisplayResolutionGraph, ResolutionGraph};
 pub use resolution_mode::ResolutionMode;
 pub use resolver::{
     BuildId, DefaultResolverProvider, InMemoryIndex, PackageVersionsResult,
-    Reporter as ResolverReporter, Resolver, ResolverProvider, VersionsResponse,
+    Reporter as ResolverReporter, Resolver, ResolverBuilder, ResolverProvider, VersionsResponse,
     WheelMetadataResult,
 };
 pub use version_map::VersionMap;
@@ -21,15 +30,18 @@ mod editables;
 mod error;
 mod finder;
 mod manifest;
+mod min_release_age;
 mod options;
 mod overrides;
 mod pins;
 mod prerelease_mode;
+mod provenance;
 mod pubgrub;
 mod python_requirement;
 mod redirect;
 mod resolution;
 mod resolution_mode;
 mod resolver;
+mod suggestions;
 mod version_map;
 mod yanks;
@@ -3,6 +3,8 @@ use pep508_rs::Requirement;
 use pypi_types::Metadata21;
 use uv_normalize::PackageName;
 
+use crate::provenance::Provenance;
+
 /// A manifest of requirements, constraints, and preferences.
 #[derive(Debug)]
 pub struct Manifest {
@@ -12,6 +14,7 @@ pub struct Manifest {
     pub(crate) preferences: Vec<Requirement>,
     pub(crate) project: Option<PackageName>,
     pub(crate) editables: Vec<(LocalEditable, Metadata21)>,
+    pub(crate) provenance: Vec<Provenance>,
 }
 
 impl Manifest {
@@ -30,6 +33,7 @@ impl Manifest {
             preferences,
             project,
             editables,
+            provenance: Vec::new(),
         }
     }
 
@@ -41,6 +45,14 @@ impl Manifest {
             preferences: Vec::new(),
             project: None,
             editables: Vec::new(),
+            provenance: Vec::new(),
         }
     }
+
+    /// Set the origins of the requirements, constraints, and overrides in the manifest, which are
+    /// used to explain resolution failures.
+    #[must_use]
+    pub fn with_provenance(self, provenance: Vec<Provenance>) -> Self {
+        Self { provenance, ..self }
+    }
 }
@@ -0,0 +1,179 @@
+use std::str::FromStr;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use rustc_hash::FxHashMap;
+
+use uv_normalize::PackageName;
+
+/// A policy that excludes any distribution published more recently than a minimum age, to avoid
+/// resolving to a freshly-published (and potentially malicious) release.
+#[derive(Debug, Default, Clone)]
+pub struct MinReleaseAge {
+    /// The minimum age that applies to all packages.
+    default: Option<Duration>,
+    /// Per-package overrides of the minimum age.
+    packages: FxHashMap<PackageName, Duration>,
+}
+
+impl MinReleaseAge {
+    /// Determine the minimum release age policy from the command-line arguments.
+    pub fn from_args(entries: Vec<MinReleaseAgeEntry>) -> Self {
+        let mut policy = Self::default();
+        for entry in entries {
+            match entry {
+                MinReleaseAgeEntry::All(age) => policy.default = Some(age),
+                MinReleaseAgeEntry::Package(package, age) => {
+                    policy.packages.insert(package, age);
+                }
+            }
+        }
+        policy
+    }
+
+    /// Returns `true` if no minimum release age is enforced.
+    pub fn is_none(&self) -> bool {
+        self.default.is_none() && self.packages.is_empty()
+    }
+
+    /// Returns the minimum release age for the given package, if any.
+    fn get(&self, package: &PackageName) -> Option<Duration> {
+        self.packages
+            .get(package)
+            .copied()
+            .or(self.default)
+            .filter(|age| !age.is_zero())
+    }
+
+    /// Returns the upload time after which distributions for the given package should be excluded,
+    /// combining the minimum release age (relative to `now`) with any `--exclude-newer` cutoff.
+    pub(crate) fn exclude_newer(
+        &self,
+        package: &PackageName,
+        exclude_newer: Option<DateTime<Utc>>,
+        now: DateTime<Utc>,
+    ) -> Option<DateTime<Utc>> {
+        let cutoff = self
+            .get(package)
+            .and_then(|age| chrono::Duration::from_std(age).ok())
+            .and_then(|age| now.checked_sub_signed(age));
+        match (cutoff, exclude_newer) {
+            (Some(cutoff), Some(exclude_newer)) => Some(cutoff.min(exclude_newer)),
+            (cutoff, exclude_newer) => cutoff.or(exclude_newer),
+        }
+    }
+}
+
+/// A single `--min-release-age` argument, either for all packages (`7d`) or for a specific package
+/// (`flask=1d`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MinReleaseAgeEntry {
+    All(Duration),
+    Package(PackageName, Duration),
+}
+
+impl FromStr for MinReleaseAgeEntry {
+    type Err = MinReleaseAgeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some((package, age)) = s.split_once('=') {
+            let package = PackageName::from_str(package.trim())
+                .map_err(|_| MinReleaseAgeError::InvalidPackage(package.to_string()))?;
+            Ok(Self::Package(package, parse_age(age.trim())?))
+        } else {
+            Ok(Self::All(parse_age(s.trim())?))
+        }
+    }
+}
+
+/// Parse an age like `7d`, `12h`, `30m`, `2w`, or `3600s`.
+fn parse_age(age: &str) -> Result<Duration, MinReleaseAgeError> {
+    let Some(split) = age.find(|c: char| !c.is_ascii_digit()) else {
+        return Err(MinReleaseAgeError::MissingUnit(age.to_string()));
+    };
+    let (value, unit) = age.split_at(split);
+    let value = value
+        .parse::<u64>()
+        .map_err(|_| MinReleaseAgeError::InvalidAge(age.to_string()))?;
+    let seconds = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 60 * 60 * 24,
+        "w" => 60 * 60 * 24 * 7,
+        _ => return Err(MinReleaseAgeError::InvalidUnit(unit.to_string())),
+    };
+    value
+        .checked_mul(seconds)
+        .map(Duration::from_secs)
+        .ok_or_else(|| MinReleaseAgeError::InvalidAge(age.to_string()))
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum MinReleaseAgeError {
+    #[error("Invalid package name in minimum release age: `{0}`")]
+    InvalidPackage(String),
+    #[error("Invalid minimum release age: `{0}` (expected, e.g., `7d`)")]
+    InvalidAge(String),
+    #[error(
+        "Minimum release age is missing a unit: `{0}` (expected one of: `s`, `m`, `h`, `d`, `w`)"
+    )]
+    MissingUnit(String),
+    #[error(
+        "Invalid unit in minimum release age: `{0}` (expected one of: `s`, `m`, `h`, `d`, `w`)"
+    )]
+    InvalidUnit(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_entries() {
+        assert_eq!(
+            MinReleaseAgeEntry::from_str("7d").unwrap(),
+            MinReleaseAgeEntry::All(Duration::from_secs(7 * 24 * 60 * 60))
+        );
+        assert_eq!(
+            MinReleaseAgeEntry::from_str("Flask=12h").unwrap(),
+            MinReleaseAgeEntry::Package(
+                PackageName::from_str("flask").unwrap(),
+                Duration::from_secs(12 * 60 * 60)
+            )
+        );
+        assert!(MinReleaseAgeEntry::from_str("7").is_err());
+        assert!(MinReleaseAgeEntry::from_str("7y").is_err());
+        assert!(MinReleaseAgeEntry::from_str("d").is_err());
+    }
+
+    #[test]
+    fn package_override() {
+        let policy = MinReleaseAge::from_args(vec![
+            MinReleaseAgeEntry::from_str("7d").unwrap(),
+            MinReleaseAgeEntry::from_str("flask=0d").unwrap(),
+        ]);
+        let now = DateTime::parse_from_rfc3339("2024-03-08T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let week_ago = DateTime::parse_from_rfc3339("2024-03-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        let requests = PackageName::from_str("requests").unwrap();
+        assert_eq!(policy.exclude_newer(&requests, None, now), Some(week_ago));
+
+        // An override of zero exempts the package.
+        let flask = PackageName::from_str("flask").unwrap();
+        assert_eq!(policy.exclude_newer(&flask, None, now), None);
+
+        // The earlier of the two cutoffs wins.
+        let earlier = DateTime::parse_from_rfc3339("2024-01-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(
+            policy.exclude_newer(&requests, Some(earlier), now),
+            Some(earlier)
+        );
+    }
+}
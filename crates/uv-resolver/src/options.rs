@@ -1,14 +1,19 @@
+use std::time::Duration;
+
 use chrono::{DateTime, Utc};
 
-use crate::{DependencyMode, PreReleaseMode, ResolutionMode};
+use crate::{DependencyMode, MinReleaseAge, PreReleaseMode, ResolutionMode};
 
 /// Options for resolving a manifest.
-#[derive(Debug, Default, Copy, Clone)]
+#[derive(Debug, Default, Clone)]
 pub struct Options {
     pub resolution_mode: ResolutionMode,
     pub prerelease_mode: PreReleaseMode,
     pub dependency_mode: DependencyMode,
     pub exclude_newer: Option<DateTime<Utc>>,
+    pub min_release_age: MinReleaseAge,
+    pub timeout: Option<Duration>,
+    pub max_steps: Option<usize>,
 }
 
 /// Builder for [`Options`].
@@ -18,6 +23,9 @@ pub struct OptionsBuilder {
     prerelease_mode: PreReleaseMode,
     dependency_mode: DependencyMode,
     exclude_newer: Option<DateTime<Utc>>,
+    min_release_age: MinReleaseAge,
+    timeout: Option<Duration>,
+    max_steps: Option<usize>,
 }
 
 impl OptionsBuilder {
@@ -54,6 +62,28 @@ impl OptionsBuilder {
         self
     }
 
+    /// Sets the minimum release age policy.
+    #[must_use]
+    pub fn min_release_age(mut self, min_release_age: MinReleaseAge) -> Self {
+        self.min_release_age = min_release_age;
+        self
+    }
+
+    /// Sets the maximum wall-clock time to spend in the solver before giving up.
+    #[must_use]
+    pub fn timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets the maximum number of solver steps (i.e., package decisions, including those that are
+    /// later backtracked) before giving up.
+    #[must_use]
+    pub fn max_steps(mut self, max_steps: Option<usize>) -> Self {
+        self.max_steps = max_steps;
+        self
+    }
+
     /// Builds the options.
     pub fn build(self) -> Options {
         Options {
@@ -61,6 +91,9 @@ impl OptionsBuilder {
             prerelease_mode: self.prerelease_mode,
             dependency_mode: self.dependency_mode,
             exclude_newer: self.exclude_newer,
+            min_release_age: self.min_release_age,
+            timeout: self.timeout,
+            max_steps: self.max_steps,
         }
     }
 }
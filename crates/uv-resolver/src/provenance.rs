@@ -0,0 +1,100 @@
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+
+use pep508_rs::Requirement;
+use uv_fs::Simplified;
+use uv_normalize::PackageName;
+
+/// The input from which a requirement was read.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Origin {
+    /// The requirement was provided on the command line (e.g., `uv pip install flask`).
+    CommandLine,
+    /// The requirement was read from a file (e.g., a `requirements.txt` or `pyproject.toml`),
+    /// along with the line at which it was declared, if known.
+    File { path: PathBuf, line: Option<usize> },
+}
+
+impl Origin {
+    /// Return the file from which the requirement was read, or `None` if it was provided on the
+    /// command line.
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            Self::CommandLine => None,
+            Self::File { path, .. } => Some(path),
+        }
+    }
+}
+
+impl Display for Origin {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::CommandLine => write!(f, "the command line"),
+            Self::File {
+                path,
+                line: Some(line),
+            } => write!(f, "{}:{line}", path.simplified_display()),
+            Self::File { path, line: None } => write!(f, "{}", path.simplified_display()),
+        }
+    }
+}
+
+/// The role that a requirement plays in the resolution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProvenanceKind {
+    /// A requirement that must be included in the resolution.
+    Requirement,
+    /// A constraint on the versions of a package, if it's included in the resolution.
+    Constraint,
+    /// An override, which replaces any other requirements on the package.
+    Override,
+}
+
+/// A user-provided requirement, constraint, or override, along with the input from which it was
+/// read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Provenance {
+    pub requirement: Requirement,
+    pub kind: ProvenanceKind,
+    pub origin: Origin,
+}
+
+impl Provenance {
+    pub fn new(requirement: Requirement, kind: ProvenanceKind, origin: Origin) -> Self {
+        Self {
+            requirement,
+            kind,
+            origin,
+        }
+    }
+
+    /// Return the requirement or constraint on the given package that was read from a file, if
+    /// any, such that errors involving the package can point at the line that declared it.
+    pub fn find_in_file<'a>(provenance: &'a [Self], name: &PackageName) -> Option<&'a Self> {
+        provenance.iter().find(|provenance| {
+            provenance.requirement.name == *name
+                && provenance.kind != ProvenanceKind::Override
+                && matches!(provenance.origin, Origin::File { .. })
+        })
+    }
+}
+
+impl Display for Provenance {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.kind {
+            ProvenanceKind::Requirement => {
+                write!(f, "`{}` (from {})", self.requirement, self.origin)
+            }
+            ProvenanceKind::Constraint => {
+                write!(
+                    f,
+                    "`{}` (constraint from {})",
+                    self.requirement, self.origin
+                )
+            }
+            ProvenanceKind::Override => {
+                write!(f, "`{}` (override from {})", self.requirement, self.origin)
+            }
+        }
+    }
+}
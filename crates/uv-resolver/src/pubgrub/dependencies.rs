@@ -11,7 +11,7 @@ use crate::constraints::Constraints;
 use crate::overrides::Overrides;
 use crate::pubgrub::specifier::PubGrubSpecifier;
 use crate::pubgrub::PubGrubPackage;
-use crate::resolver::Urls;
+use crate::resolver::{is_same_url, Urls};
 use crate::ResolveError;
 
 #[derive(Debug)]
@@ -25,6 +25,7 @@ impl PubGrubDependencies {
         overrides: &Overrides,
         source_name: Option<&PackageName>,
         source_extra: Option<&ExtraName>,
+        source_version: Option<&Version>,
         urls: &Urls,
         env: &MarkerEnvironment,
     ) -> Result<Self, ResolveError> {
@@ -49,17 +50,17 @@ impl PubGrubDependencies {
                     .into_iter()
                     .map(|extra| to_pubgrub(requirement, Some(extra), urls)),
             ) {
-                let (mut package, version) = result?;
+                let (package, version) = result?;
 
                 // Detect self-dependencies.
-                if let PubGrubPackage::Package(name, extra, ..) = &mut package {
-                    if source_name.is_some_and(|source_name| source_name == name) {
-                        // Allow, e.g., `black` to depend on `black[colorama]`.
-                        if source_extra == extra.as_ref() {
-                            warn!("{name} has a dependency on itself");
-                            continue;
-                        }
-                    }
+                if !handle_self_dependency(
+                    &package,
+                    &version,
+                    source_name,
+                    source_extra,
+                    source_version,
+                ) {
+                    continue;
                 }
 
                 dependencies.push((package.clone(), version.clone()));
@@ -83,17 +84,17 @@ impl PubGrubDependencies {
                             .into_iter()
                             .map(|extra| to_pubgrub(constraint, Some(extra), urls)),
                     ) {
-                        let (mut package, version) = result?;
+                        let (package, version) = result?;
 
                         // Detect self-dependencies.
-                        if let PubGrubPackage::Package(name, extra, ..) = &mut package {
-                            if source_name.is_some_and(|source_name| source_name == name) {
-                                // Allow, e.g., `black` to depend on `black[colorama]`.
-                                if source_extra == extra.as_ref() {
-                                    warn!("{name} has a dependency on itself");
-                                    continue;
-                                }
-                            }
+                        if !handle_self_dependency(
+                            &package,
+                            &version,
+                            source_name,
+                            source_extra,
+                            source_version,
+                        ) {
+                            continue;
                         }
 
                         dependencies.push((package.clone(), version.clone()));
@@ -123,6 +124,44 @@ impl From<PubGrubDependencies> for Vec<(PubGrubPackage, Range<Version>)> {
     }
 }
 
+/// Detect a dependency of a package on itself, returning `false` if the dependency should be
+/// dropped.
+///
+/// A package that depends on itself with the same extras (e.g., `black` on `black`, or
+/// `black[dev]` on `black[dev]`) forms a trivial cycle, and the dependency is dropped. A package
+/// may depend on itself with different extras (e.g., `black` on `black[colorama]`, or `black[dev]`
+/// on `black[test]`), in which case the dependency is retained as-is: if its range excludes the
+/// version being resolved, the resolver backtracks (or reports the conflict).
+fn handle_self_dependency(
+    package: &PubGrubPackage,
+    version: &Range<Version>,
+    source_name: Option<&PackageName>,
+    source_extra: Option<&ExtraName>,
+    source_version: Option<&Version>,
+) -> bool {
+    let PubGrubPackage::Package(name, extra, ..) = package else {
+        return true;
+    };
+    if !source_name.is_some_and(|source_name| source_name == name) {
+        return true;
+    }
+
+    if source_extra == extra.as_ref() {
+        warn!("{name} has a dependency on itself");
+        return false;
+    }
+
+    if let Some(source_version) = source_version {
+        if !version.contains(source_version) {
+            warn!(
+                "{name}=={source_version} has a dependency on {package}{version}, which excludes itself"
+            );
+        }
+    }
+
+    true
+}
+
 /// Convert a [`Requirement`] to a `PubGrub`-compatible package and range.
 fn to_pubgrub(
     requirement: &Requirement,
@@ -159,7 +198,7 @@ fn to_pubgrub(
                 ));
             };
 
-            if cache_key::CanonicalUrl::new(allowed) != cache_key::CanonicalUrl::new(url) {
+            if !is_same_url(allowed, url) {
                 return Err(ResolveError::ConflictingUrlsTransitive(
                     requirement.name.clone(),
                     allowed.verbatim().to_string(),
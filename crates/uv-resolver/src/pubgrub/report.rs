@@ -6,18 +6,22 @@ use std::ops::Bound;
 use derivative::Derivative;
 use distribution_types::IndexLocations;
 use indexmap::{IndexMap, IndexSet};
+use itertools::Itertools;
 use owo_colors::OwoColorize;
 use pep440_rs::Version;
 use pubgrub::range::Range;
 use pubgrub::report::{DerivationTree, Derived, External, ReportFormatter};
 use pubgrub::term::Term;
 use pubgrub::type_aliases::Map;
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use pep508_rs::MarkerTree;
 use uv_normalize::PackageName;
 
 use crate::candidate_selector::CandidateSelector;
 use crate::python_requirement::PythonRequirement;
 use crate::resolver::UnavailablePackage;
+use crate::suggestions::Suggestion;
 
 use super::PubGrubPackage;
 
@@ -384,6 +388,22 @@ impl PubGrubReportFormatter<'_> {
                         }
                     }
 
+                    // Check for a package that doesn't exist, which may be a typo or an import
+                    // name.
+                    if let PubGrubPackage::Package(name, ..) = package {
+                        if matches!(
+                            unavailable_packages.get(name),
+                            Some(UnavailablePackage::NotFound)
+                        ) {
+                            if let Some(suggestion) = Suggestion::for_package(name) {
+                                hints.insert(PubGrubHint::NotFound {
+                                    package: package.clone(),
+                                    suggestion,
+                                });
+                            }
+                        }
+                    }
+
                     // Check for no versions due to no `--find-links` flat index
                     if let Some(index_locations) = index_locations {
                         let no_find_links =
@@ -425,6 +445,87 @@ impl PubGrubReportFormatter<'_> {
         }
         hints
     }
+
+    /// Render a [`DerivationTree`] as an indented tree, with one incompatibility per line.
+    ///
+    /// Unlike the default report, which collapses the derivation into a narrative, the tree shows
+    /// the full chain of requirements that led to the conflict, including any markers under which
+    /// a dependency was declared.
+    pub(crate) fn tree(
+        &self,
+        derivation_tree: &DerivationTree<PubGrubPackage, Range<Version>>,
+        dependency_markers: &FxHashMap<(Option<PackageName>, PackageName), Option<MarkerTree>>,
+    ) -> String {
+        let mut output = String::new();
+        let mut seen = FxHashSet::default();
+        self.write_tree(
+            derivation_tree,
+            dependency_markers,
+            0,
+            &mut seen,
+            &mut output,
+        );
+        output
+    }
+
+    fn write_tree(
+        &self,
+        derivation_tree: &DerivationTree<PubGrubPackage, Range<Version>>,
+        dependency_markers: &FxHashMap<(Option<PackageName>, PackageName), Option<MarkerTree>>,
+        depth: usize,
+        seen: &mut FxHashSet<usize>,
+        output: &mut String,
+    ) {
+        let indent = "    ".repeat(depth);
+        match derivation_tree {
+            DerivationTree::External(external) => {
+                let line = self.format_external(external);
+                match external {
+                    External::FromDependencyOf(package, _, dependency, _) => {
+                        let source = match package {
+                            PubGrubPackage::Root(_) => None,
+                            PubGrubPackage::Package(name, ..) => Some(name.clone()),
+                            PubGrubPackage::Python(_) => {
+                                output.push_str(&format!("{indent}{line}\n"));
+                                return;
+                            }
+                        };
+                        let marker = match dependency {
+                            PubGrubPackage::Package(name, ..) => dependency_markers
+                                .get(&(source, name.clone()))
+                                .and_then(Option::as_ref),
+                            _ => None,
+                        };
+                        if let Some(marker) = marker {
+                            output.push_str(&format!(
+                                "{indent}{line} {}\n",
+                                format!("(when `{marker}`)").dimmed()
+                            ));
+                        } else {
+                            output.push_str(&format!("{indent}{line}\n"));
+                        }
+                    }
+                    _ => {
+                        output.push_str(&format!("{indent}{line}\n"));
+                    }
+                }
+            }
+            DerivationTree::Derived(derived) => {
+                let terms = self.format_terms(&derived.terms);
+                if let Some(id) = derived.shared_id {
+                    if !seen.insert(id) {
+                        output.push_str(&format!("{indent}{terms} (see ({id}) above)\n"));
+                        return;
+                    }
+                    output.push_str(&format!("{indent}({id}) {terms}\n"));
+                } else {
+                    output.push_str(&format!("{indent}{terms}\n"));
+                }
+                self.write_tree(&derived.cause1, dependency_markers, depth + 1, seen, output);
+                self.write_tree(&derived.cause2, dependency_markers, depth + 1, seen, output);
+            }
+        }
+    }
 }
 
 #[derive(Derivative, Debug, Clone)]
@@ -450,6 +551,13 @@ pub(crate) enum PubGrubHint {
     NoIndex,
     /// A package was not found in the registry, but
     Offline,
+    /// A package was not found in the registry, but a similarly-named package exists (or the
+    /// requested name is the import name of another package).
+    NotFound {
+        package: PubGrubPackage,
+        #[derivative(PartialEq = "ignore", Hash = "ignore")]
+        suggestion: Suggestion,
+    },
 }
 
 impl std::fmt::Display for PubGrubHint {
@@ -491,6 +599,33 @@ impl std::fmt::Display for PubGrubHint {
                     ":".bold(),
                 )
             }
+            Self::NotFound {
+                package,
+                suggestion: Suggestion::ImportName(name),
+            } => {
+                write!(
+                    f,
+                    "{}{} {} is the import name of a module provided by {} (try: `{}`)",
+                    "hint".bold().cyan(),
+                    ":".bold(),
+                    package.bold(),
+                    name.bold(),
+                    name.bold(),
+                )
+            }
+            Self::NotFound {
+                package,
+                suggestion: Suggestion::SimilarNames(names),
+            } => {
+                write!(
+                    f,
+                    "{}{} {} was not found in the package registry; did you mean {}?",
+                    "hint".bold().cyan(),
+                    ":".bold(),
+                    package.bold(),
+                    names.iter().map(|name| name.bold().to_string()).join(", "),
+                )
+            }
         }
     }
 }
@@ -726,3 +861,72 @@ fn fmt_package(package: &PubGrubPackage) -> String {
         _ => format!("{package}"),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use pubgrub::range::Range;
+    use pubgrub::report::{DerivationTree, External};
+    use rustc_hash::FxHashMap;
+
+    use pep508_rs::MarkerTree;
+    use uv_normalize::PackageName;
+
+    use crate::resolver::combine_markers;
+
+    use super::{IndexMap, PubGrubPackage, PubGrubReportFormatter};
+
+    /// Render the tree for a root requirement on `anyio`, declared under each of the given
+    /// markers in turn.
+    fn tree(markers: &[Option<&str>]) -> String {
+        let anyio = PackageName::from_str("anyio").unwrap();
+        let marker = markers
+            .iter()
+            .map(|marker| marker.map(|marker| MarkerTree::from_str(marker).unwrap()))
+            .reduce(|existing, marker| combine_markers(existing, marker.as_ref()))
+            .flatten();
+        let dependency_markers: FxHashMap<_, _> =
+            [((None, anyio.clone()), marker)].into_iter().collect();
+
+        let derivation_tree = DerivationTree::External(External::FromDependencyOf(
+            PubGrubPackage::Root(None),
+            Range::full(),
+            PubGrubPackage::Package(anyio, None, None),
+            Range::full(),
+        ));
+        let available_versions = IndexMap::default();
+        let formatter = PubGrubReportFormatter {
+            available_versions: &available_versions,
+            python_requirement: None,
+        };
+        let tree = formatter.tree(&derivation_tree, &dependency_markers);
+        anstream::adapter::strip_str(&tree).to_string()
+    }
+
+    #[test]
+    fn marker() {
+        insta::assert_snapshot!(tree(&[Some("python_version >= '3.8'")]), @r###"
+        you require anyio (when `python_version >= '3.8'`)
+        "###);
+    }
+
+    #[test]
+    fn combined_markers() {
+        insta::assert_snapshot!(tree(&[
+            Some("python_version >= '3.8'"),
+            Some("sys_platform == 'darwin'"),
+            Some("python_version >= '3.8'"),
+            Some("sys_platform == 'win32'"),
+        ]), @r###"
+        you require anyio (when `python_version >= '3.8' or sys_platform == 'darwin' or sys_platform == 'win32'`)
+        "###);
+    }
+
+    #[test]
+    fn unconditional() {
+        insta::assert_snapshot!(tree(&[Some("python_version >= '3.8'"), None]), @r###"
+        you require anyio
+        "###);
+    }
+}
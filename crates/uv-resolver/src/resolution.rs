@@ -1,15 +1,19 @@
 use std::borrow::Cow;
+use std::collections::VecDeque;
 use std::hash::BuildHasherDefault;
 
 use anyhow::Result;
 use dashmap::DashMap;
+use itertools::Itertools;
 use owo_colors::OwoColorize;
+use petgraph::graph::NodeIndex;
 use petgraph::visit::EdgeRef;
 use petgraph::Direction;
 use pubgrub::range::Range;
 use pubgrub::solver::{Kind, State};
 use pubgrub::type_aliases::SelectedDependencies;
 use rustc_hash::FxHashMap;
+use tracing::debug;
 use url::Url;
 
 use distribution_types::{Dist, DistributionMetadata, LocalEditable, Name, PackageId, Verbatim};
@@ -45,6 +49,8 @@ pub struct ResolutionGraph {
     petgraph: petgraph::graph::Graph<Dist, Range<Version>, petgraph::Directed>,
     /// The metadata for every distribution in this resolution.
     hashes: FxHashMap<PackageName, Vec<Hashes>>,
+    /// The extras that were activated for each package in this resolution.
+    extras: FxHashMap<PackageName, Vec<ExtraName>>,
     /// The set of editable requirements in this resolution.
     editables: Editables,
     /// Any diagnostics that were encountered while building the graph.
@@ -67,9 +73,11 @@ impl ResolutionGraph {
         let mut petgraph = petgraph::graph::Graph::with_capacity(selection.len(), selection.len());
         let mut hashes =
             FxHashMap::with_capacity_and_hasher(selection.len(), BuildHasherDefault::default());
+        let mut extras: FxHashMap<PackageName, Vec<ExtraName>> = FxHashMap::default();
         let mut diagnostics = Vec::new();
 
-        // Add every package to the graph.
+        // Add every package to the graph. Packages requested with extras (e.g., `black[colorama]`)
+        // are merged into the node for the base package, with the extras tracked separately.
         let mut inverse =
             FxHashMap::with_capacity_and_hasher(selection.len(), BuildHasherDefault::default());
         for (package, version) in selection {
@@ -139,6 +147,7 @@ impl ResolutionGraph {
                                 dist: pinned_package,
                                 extra: extra.clone(),
                             });
+                            continue;
                         }
                     } else {
                         let metadata = distributions.get(&dist.package_id()).unwrap_or_else(|| {
@@ -160,8 +169,14 @@ impl ResolutionGraph {
                                 dist: pinned_package,
                                 extra: extra.clone(),
                             });
+                            continue;
                         }
                     }
+
+                    extras
+                        .entry(package_name.clone())
+                        .or_insert_with(Vec::new)
+                        .push(extra.clone());
                 }
                 PubGrubPackage::Package(package_name, Some(extra), Some(url)) => {
                     // Validate that the `extra` exists.
@@ -176,6 +191,7 @@ impl ResolutionGraph {
                                 dist: pinned_package,
                                 extra: extra.clone(),
                             });
+                            continue;
                         }
                     } else {
                         let metadata = distributions.get(&dist.package_id()).unwrap_or_else(|| {
@@ -196,8 +212,14 @@ impl ResolutionGraph {
                                 dist: pinned_package,
                                 extra: extra.clone(),
                             });
+                            continue;
                         }
                     }
+
+                    extras
+                        .entry(package_name.clone())
+                        .or_insert_with(Vec::new)
+                        .push(extra.clone());
                 }
                 _ => {}
             };
@@ -239,9 +261,19 @@ impl ResolutionGraph {
             }
         }
 
+        // Surface any dependency cycles, including those that involve extras.
+        report_cycles(selection, state);
+
+        // Sort the extras, for deterministic output.
+        for extras in extras.values_mut() {
+            extras.sort_unstable();
+            extras.dedup();
+        }
+
         Ok(Self {
             petgraph,
             hashes,
+            extras,
             editables,
             diagnostics,
         })
@@ -264,6 +296,27 @@ impl ResolutionGraph {
             .any(|index| self.petgraph[index].name() == name)
     }
 
+    /// Return the extras that were activated for the given package, if any.
+    pub fn extras(&self, name: &PackageName) -> &[ExtraName] {
+        self.extras.get(name).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Set the hashes of the distributions for the given package, as for a direct URL
+    /// distribution, whose hashes aren't provided by an index.
+    pub fn insert_hashes(&mut self, name: PackageName, hashes: Vec<Hashes>) {
+        self.hashes.insert(name, hashes);
+    }
+
+    /// Return the hashes of the distributions for the given package, if any.
+    pub fn hashes(&self, name: &PackageName) -> &[Hashes] {
+        self.hashes.get(name).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Returns `true` if the given package was resolved from an editable requirement.
+    pub fn is_editable(&self, name: &PackageName) -> bool {
+        self.editables.get(name).is_some()
+    }
+
     /// Return the [`Diagnostic`]s that were encountered while building the graph.
     pub fn diagnostics(&self) -> &[Diagnostic] {
         &self.diagnostics
@@ -275,6 +328,107 @@ impl ResolutionGraph {
     }
 }
 
+/// Log any dependency cycles in the resolved state, including those that involve extras (e.g.,
+/// `foo[dev]` depending on `foo[test]`, which in turn depends on `foo[dev]`).
+///
+/// Cycles are valid, but can lead to surprising resolutions, so the path of each cycle is included
+/// in the verbose output.
+fn report_cycles(
+    selection: &SelectedDependencies<PubGrubPackage, Version>,
+    state: &State<PubGrubPackage, Range<Version>, PubGrubPriority>,
+) {
+    let mut graph = petgraph::graph::DiGraph::<&PubGrubPackage, ()>::new();
+    let mut indices = FxHashMap::default();
+    for (package, version) in selection {
+        for id in &state.incompatibilities[package] {
+            let Kind::FromDependencyOf(
+                self_package,
+                self_version,
+                dependency_package,
+                dependency_range,
+            ) = &state.incompatibility_store[*id].kind
+            else {
+                continue;
+            };
+
+            // Consider each edge from its source, and only between the selected versions.
+            if self_package != package || !self_version.contains(version) {
+                continue;
+            }
+            if !selection
+                .get(dependency_package)
+                .is_some_and(|version| dependency_range.contains(version))
+            {
+                continue;
+            }
+            if !matches!(dependency_package, PubGrubPackage::Package(..)) {
+                continue;
+            }
+
+            let source = *indices
+                .entry(self_package)
+                .or_insert_with(|| graph.add_node(self_package));
+            let target = *indices
+                .entry(dependency_package)
+                .or_insert_with(|| graph.add_node(dependency_package));
+            graph.update_edge(source, target, ());
+        }
+    }
+
+    for component in petgraph::algo::tarjan_scc(&graph) {
+        if component.len() < 2 {
+            continue;
+        }
+        debug!(
+            "Found a dependency cycle: {}",
+            cycle_path(&graph, &component)
+                .into_iter()
+                .map(|index| graph[index])
+                .join(" -> ")
+        );
+    }
+}
+
+/// Find a path through a strongly connected component of the graph that starts and ends at the
+/// same node, e.g., `foo[dev] -> foo[test] -> foo[dev]`.
+fn cycle_path(
+    graph: &petgraph::graph::DiGraph<&PubGrubPackage, ()>,
+    component: &[NodeIndex],
+) -> Vec<NodeIndex> {
+    // Start from the first node by name, for deterministic output.
+    let start = *component
+        .iter()
+        .min_by_key(|index| graph[**index].to_string())
+        .expect("components are non-empty");
+
+    // Perform a breadth-first search within the component, to find the shortest path back to the
+    // start node.
+    let mut parents = FxHashMap::default();
+    let mut queue = VecDeque::from([start]);
+    while let Some(node) = queue.pop_front() {
+        for next in graph.neighbors(node) {
+            if next == start {
+                let mut path = Vec::new();
+                let mut current = node;
+                while current != start {
+                    path.push(current);
+                    current = parents[&current];
+                }
+                path.push(start);
+                path.reverse();
+                path.push(start);
+                return path;
+            }
+            if component.contains(&next) && !parents.contains_key(&next) {
+                parents.insert(next, node);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    component.to_vec()
+}
+
 /// A [`std::fmt::Display`] implementation for the resolution graph.
 #[derive(Debug)]
 pub struct DisplayResolutionGraph<'a> {
@@ -284,6 +438,8 @@ pub struct DisplayResolutionGraph<'a> {
     no_emit_packages: &'a [PackageName],
     /// Whether to include hashes in the output.
     show_hashes: bool,
+    /// Whether to include extras in the output (e.g., `black[colorama]`).
+    include_extras: bool,
     /// Whether to include annotations in the output, to indicate which dependency or dependencies
     /// requested each package.
     include_annotations: bool,
@@ -294,7 +450,14 @@ pub struct DisplayResolutionGraph<'a> {
 
 impl<'a> From<&'a ResolutionGraph> for DisplayResolutionGraph<'a> {
     fn from(resolution: &'a ResolutionGraph) -> Self {
-        Self::new(resolution, &[], false, true, AnnotationStyle::default())
+        Self::new(
+            resolution,
+            &[],
+            false,
+            false,
+            true,
+            AnnotationStyle::default(),
+        )
     }
 }
 
@@ -304,6 +467,7 @@ impl<'a> DisplayResolutionGraph<'a> {
         underlying: &'a ResolutionGraph,
         no_emit_packages: &'a [PackageName],
         show_hashes: bool,
+        include_extras: bool,
         include_annotations: bool,
         annotation_style: AnnotationStyle,
     ) -> DisplayResolutionGraph<'a> {
@@ -311,6 +475,7 @@ impl<'a> DisplayResolutionGraph<'a> {
             resolution: underlying,
             no_emit_packages,
             show_hashes,
+            include_extras,
             include_annotations,
             annotation_style,
         }
@@ -382,7 +547,18 @@ impl std::fmt::Display for DisplayResolutionGraph<'_> {
         for (index, node) in nodes {
             // Display the node itself.
             let mut line = match node {
-                Node::Distribution(_, dist) => format!("{}", dist.verbatim()),
+                Node::Distribution(name, dist) => {
+                    let extras = self.resolution.extras(name);
+                    if self.include_extras && !extras.is_empty() {
+                        format!(
+                            "{name}[{}]{}",
+                            extras.iter().join(","),
+                            dist.version_or_url().verbatim()
+                        )
+                    } else {
+                        format!("{}", dist.verbatim())
+                    }
+                }
                 Node::Editable(_, editable) => format!("-e {}", editable.verbatim()),
             };
 
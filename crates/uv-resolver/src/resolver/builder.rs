@@ -0,0 +1,153 @@
+use pep508_rs::{MarkerEnvironment, Requirement};
+use platform_tags::Tags;
+use uv_client::{FlatIndex, RegistryClient};
+use uv_interpreter::Interpreter;
+use uv_traits::BuildContext;
+
+use crate::manifest::Manifest;
+use crate::resolution::ResolutionGraph;
+use crate::resolver::{DefaultResolverProvider, InMemoryIndex, Resolver};
+use crate::{Options, Provenance, ResolveError};
+
+/// A builder for a [`Resolver`] backed by the default (network) provider.
+///
+/// The builder requires an index client, the platform tags to resolve for, a shared in-memory
+/// index, and a [`BuildContext`] for building source distributions. Everything else (the
+/// requirements, resolver options, target markers, and `--find-links` entries) is optional, and
+/// defaults to an empty manifest, the default [`Options`], and the markers of the build context's
+/// interpreter.
+///
+/// ```ignore
+/// let graph = ResolverBuilder::new(&client, tags, &index, &build_dispatch)
+///     .requirements(vec![Requirement::from_str("flask")?])
+///     .options(OptionsBuilder::new().resolution_mode(ResolutionMode::Highest).build())
+///     .resolve()
+///     .await?;
+/// ```
+pub struct ResolverBuilder<'a, Context: BuildContext + Send + Sync> {
+    client: &'a RegistryClient,
+    tags: &'a Tags,
+    index: &'a InMemoryIndex,
+    build_context: &'a Context,
+    interpreter: &'a Interpreter,
+    markers: &'a MarkerEnvironment,
+    flat_index: Option<&'a FlatIndex>,
+    manifest: Manifest,
+    options: Options,
+}
+
+impl<'a, Context: BuildContext + Send + Sync> ResolverBuilder<'a, Context> {
+    /// Create a [`ResolverBuilder`] for the given client, platform tags, and build context.
+    pub fn new(
+        client: &'a RegistryClient,
+        tags: &'a Tags,
+        index: &'a InMemoryIndex,
+        build_context: &'a Context,
+    ) -> Self {
+        let interpreter = build_context.interpreter();
+        Self {
+            client,
+            tags,
+            index,
+            build_context,
+            interpreter,
+            markers: interpreter.markers(),
+            flat_index: None,
+            manifest: Manifest::simple(Vec::new()),
+            options: Options::default(),
+        }
+    }
+
+    /// Set the requirements to resolve.
+    #[must_use]
+    pub fn requirements(mut self, requirements: Vec<Requirement>) -> Self {
+        self.manifest.requirements = requirements;
+        self
+    }
+
+    /// Set the constraints to apply to the resolution.
+    #[must_use]
+    pub fn constraints(mut self, constraints: Vec<Requirement>) -> Self {
+        self.manifest.constraints = constraints;
+        self
+    }
+
+    /// Set the overrides to apply to the resolution.
+    #[must_use]
+    pub fn overrides(mut self, overrides: Vec<Requirement>) -> Self {
+        self.manifest.overrides = overrides;
+        self
+    }
+
+    /// Set the preferred versions (e.g., from an existing lockfile).
+    #[must_use]
+    pub fn preferences(mut self, preferences: Vec<Requirement>) -> Self {
+        self.manifest.preferences = preferences;
+        self
+    }
+
+    /// Set the origins of the requirements, constraints, and overrides, which are used to explain
+    /// resolution failures.
+    #[must_use]
+    pub fn provenance(mut self, provenance: Vec<Provenance>) -> Self {
+        self.manifest.provenance = provenance;
+        self
+    }
+
+    /// Replace the full [`Manifest`], including editables and the project name.
+    #[must_use]
+    pub fn manifest(mut self, manifest: Manifest) -> Self {
+        self.manifest = manifest;
+        self
+    }
+
+    /// Set the resolver [`Options`].
+    #[must_use]
+    pub fn options(mut self, options: Options) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Set the interpreter to resolve for, along with its markers.
+    #[must_use]
+    pub fn interpreter(mut self, interpreter: &'a Interpreter) -> Self {
+        self.interpreter = interpreter;
+        self.markers = interpreter.markers();
+        self
+    }
+
+    /// Set the marker environment to resolve for, overriding that of the interpreter.
+    #[must_use]
+    pub fn markers(mut self, markers: &'a MarkerEnvironment) -> Self {
+        self.markers = markers;
+        self
+    }
+
+    /// Set the flat index to use for `--find-links` entries.
+    #[must_use]
+    pub fn flat_index(mut self, flat_index: &'a FlatIndex) -> Self {
+        self.flat_index = Some(flat_index);
+        self
+    }
+
+    /// Build the [`Resolver`].
+    pub fn build(self) -> Result<Resolver<'a, DefaultResolverProvider<'a, Context>>, ResolveError> {
+        let flat_index = FlatIndex::default();
+        Resolver::new(
+            self.manifest,
+            self.options,
+            self.markers,
+            self.interpreter,
+            self.tags,
+            self.client,
+            self.flat_index.unwrap_or(&flat_index),
+            self.index,
+            self.build_context,
+        )
+    }
+
+    /// Build the [`Resolver`] and resolve the requirements into a [`ResolutionGraph`].
+    pub async fn resolve(self) -> Result<ResolutionGraph, ResolveError> {
+        self.build()?.resolve().await
+    }
+}
@@ -2,9 +2,11 @@
 
 use std::borrow::Cow;
 use std::fmt::{Display, Formatter};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use anyhow::Result;
+use dashmap::mapref::entry::Entry;
 use dashmap::{DashMap, DashSet};
 use futures::{FutureExt, StreamExt};
 use itertools::Itertools;
@@ -23,29 +25,31 @@ use distribution_types::{
     VersionOrUrl,
 };
 use pep440_rs::{Version, VersionSpecifiers, MIN_VERSION};
-use pep508_rs::{MarkerEnvironment, Requirement};
+use pep508_rs::{MarkerEnvironment, MarkerTree, Requirement};
 use platform_tags::{IncompatibleTag, Tags};
 use pypi_types::{Metadata21, Yanked};
-pub(crate) use urls::Urls;
+pub(crate) use urls::{is_same_url, Urls};
 use uv_client::{FlatIndex, RegistryClient};
 use uv_distribution::DistributionDatabase;
 use uv_interpreter::Interpreter;
 use uv_normalize::PackageName;
-use uv_traits::BuildContext;
+use uv_traits::{BuildContext, Concurrency};
 
 use crate::candidate_selector::{CandidateDist, CandidateSelector};
 use crate::constraints::Constraints;
 use crate::editables::Editables;
-use crate::error::ResolveError;
+use crate::error::{ConflictSummary, ResolveError};
 use crate::manifest::Manifest;
 use crate::overrides::Overrides;
 use crate::pins::FilePins;
+use crate::provenance::Provenance;
 use crate::pubgrub::{
     PubGrubDependencies, PubGrubDistribution, PubGrubPackage, PubGrubPriorities, PubGrubPython,
     PubGrubSpecifier,
 };
 use crate::python_requirement::PythonRequirement;
 use crate::resolution::ResolutionGraph;
+pub use crate::resolver::builder::ResolverBuilder;
 pub use crate::resolver::index::InMemoryIndex;
 pub use crate::resolver::provider::{
     DefaultResolverProvider, PackageVersionsResult, ResolverProvider, VersionsResponse,
@@ -56,6 +60,7 @@ pub use crate::resolver::reporter::{BuildId, Reporter};
 use crate::yanks::AllowedYanks;
 use crate::{DependencyMode, Options};
 
+mod builder;
 mod index;
 mod provider;
 mod reporter;
@@ -100,6 +105,8 @@ pub struct Resolver<'a, Provider: ResolverProvider> {
     allowed_yanks: AllowedYanks,
     urls: Urls,
     dependency_mode: DependencyMode,
+    timeout: Option<Duration>,
+    max_steps: Option<usize>,
     markers: &'a MarkerEnvironment,
     python_requirement: PythonRequirement,
     selector: CandidateSelector,
@@ -108,6 +115,14 @@ pub struct Resolver<'a, Provider: ResolverProvider> {
     unavailable_packages: DashMap<PackageName, UnavailablePackage>,
     /// The set of all registry-based packages visited during resolution.
     visited: DashSet<PackageName>,
+    /// The markers under which each dependency was requested, keyed by the requesting package
+    /// (or `None`, for the root) and the dependency name, or `None` if it was requested
+    /// unconditionally.
+    dependency_markers: DashMap<(Option<PackageName>, PackageName), Option<MarkerTree>>,
+    /// The origins of the user-provided requirements, constraints, and overrides.
+    provenance: Vec<Provenance>,
+    /// The maximum number of metadata requests to process concurrently.
+    concurrent_downloads: usize,
     reporter: Option<Arc<dyn Reporter>>,
     provider: Provider,
 }
@@ -124,7 +139,7 @@ impl<'a, Context: BuildContext + Send + Sync> Resolver<'a, DefaultResolverProvid
         interpreter: &'a Interpreter,
         tags: &'a Tags,
         client: &'a RegistryClient,
-        flat_index: &'a FlatIndex,
+        flat_index: &FlatIndex,
         index: &'a InMemoryIndex,
         build_context: &'a Context,
     ) -> Result<Self, ResolveError> {
@@ -135,16 +150,20 @@ impl<'a, Context: BuildContext + Send + Sync> Resolver<'a, DefaultResolverProvid
             tags,
             PythonRequirement::new(interpreter, markers),
             options.exclude_newer,
+            options.min_release_age.clone(),
             build_context.no_binary(),
         );
-        Self::new_custom_io(
-            manifest,
-            options,
-            markers,
-            PythonRequirement::new(interpreter, markers),
-            index,
-            provider,
-        )
+        Ok(Self {
+            concurrent_downloads: build_context.concurrency().downloads,
+            ..Self::new_custom_io(
+                manifest,
+                options,
+                markers,
+                PythonRequirement::new(interpreter, markers),
+                index,
+                provider,
+            )?
+        })
     }
 }
 
@@ -158,7 +177,7 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
         index: &'a InMemoryIndex,
         provider: Provider,
     ) -> Result<Self, ResolveError> {
-        let selector = CandidateSelector::for_resolution(&manifest, options);
+        let selector = CandidateSelector::for_resolution(&manifest, &options);
 
         // Determine the allowed yanked package versions
         let allowed_yanks = manifest
@@ -171,17 +190,22 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
             index,
             unavailable_packages: DashMap::default(),
             visited: DashSet::default(),
+            dependency_markers: DashMap::default(),
             selector,
             allowed_yanks,
             dependency_mode: options.dependency_mode,
+            timeout: options.timeout,
+            max_steps: options.max_steps,
             urls: Urls::from_manifest(&manifest, markers)?,
             project: manifest.project,
             requirements: manifest.requirements,
             constraints: Constraints::from_requirements(manifest.constraints),
             overrides: Overrides::from_requirements(manifest.overrides),
             editables: Editables::from_requirements(manifest.editables),
+            provenance: manifest.provenance,
             markers,
             python_requirement,
+            concurrent_downloads: Concurrency::DEFAULT_DOWNLOADS,
             reporter: None,
             provider,
         })
@@ -203,38 +227,60 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
         // A channel to fetch package metadata (e.g., given `flask`, fetch all versions) and version
         // metadata (e.g., given `flask==1.0.0`, fetch the metadata for that version).
         // Channel size is set to the same size as the task buffer for simplicity.
-        let (request_sink, request_stream) = tokio::sync::mpsc::channel(50);
+        let (request_sink, request_stream) = tokio::sync::mpsc::channel(self.concurrent_downloads);
 
         // Run the fetcher.
         let requests_fut = self.fetch(request_stream).fuse();
 
-        // Run the solver.
-        let resolve_fut = self.solve(&request_sink).fuse();
+        // Run the solver, tracking the number of times each package was rejected, to explain an
+        // aborted resolution.
+        let conflicts = Mutex::new(FxHashMap::default());
+        let resolve_fut = self.solve(&request_sink, &conflicts).fuse();
 
-        let resolution = select! {
-            result = requests_fut => {
-                result?;
-                return Err(ResolveError::ChannelClosed);
-            }
-            resolution = resolve_fut => {
-                resolution.map_err(|err| {
-                    // Add version information to improve unsat error messages.
-                    if let ResolveError::NoSolution(err) = err {
-                        ResolveError::NoSolution(
-                            err
-                            .with_available_versions(&self.python_requirement, &self.visited, &self.index.packages)
-                            .with_selector(self.selector.clone())
-                            .with_python_requirement(&self.python_requirement)
-                            .with_index_locations(self.provider.index_locations())
-                            .with_unavailable_packages(&self.unavailable_packages)
-                        )
-                    } else {
-                        err
-                    }
-                })?
+        let resolve_fut = async {
+            select! {
+                result = requests_fut => {
+                    result?;
+                    Err(ResolveError::ChannelClosed)
+                }
+                resolution = resolve_fut => resolution,
             }
         };
 
+        // Enforce the time limit, if any, including any time spent waiting on metadata.
+        let resolution = match self.timeout {
+            Some(timeout) => tokio::time::timeout(timeout, resolve_fut)
+                .await
+                .unwrap_or_else(|_| {
+                    Err(ResolveError::Timeout {
+                        timeout,
+                        conflicts: ConflictSummary::from_counts(&conflicts.lock().unwrap()),
+                    })
+                }),
+            None => resolve_fut.await,
+        };
+
+        let resolution = resolution.map_err(|err| {
+            // Add version information to improve unsat error messages.
+            if let ResolveError::NoSolution(err) = err {
+                ResolveError::NoSolution(
+                    err.with_available_versions(
+                        &self.python_requirement,
+                        &self.visited,
+                        &self.index.packages,
+                    )
+                    .with_selector(self.selector.clone())
+                    .with_python_requirement(&self.python_requirement)
+                    .with_index_locations(self.provider.index_locations())
+                    .with_unavailable_packages(&self.unavailable_packages)
+                    .with_dependency_markers(&self.dependency_markers)
+                    .with_provenance(&self.provenance),
+                )
+            } else {
+                err.with_provenance(&self.provenance)
+            }
+        })?;
+
         self.on_complete();
 
         Ok(resolution)
@@ -245,6 +291,7 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
     async fn solve(
         &self,
         request_sink: &tokio::sync::mpsc::Sender<Request>,
+        conflicts: &Mutex<FxHashMap<PubGrubPackage, usize>>,
     ) -> Result<ResolutionGraph, ResolveError> {
         let root = PubGrubPackage::Root(self.project.clone());
 
@@ -258,12 +305,26 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
             FxHashMap::default();
         let mut next = root;
 
+        // Track the number of solver steps, to enforce the step limit.
+        let mut steps = 0usize;
+
         debug!(
             "Solving with target Python version {}",
             self.python_requirement.target()
         );
 
         loop {
+            // Enforce the step limit, if any. (The time limit is enforced by the caller.)
+            steps += 1;
+            if let Some(max_steps) = self.max_steps {
+                if steps > max_steps {
+                    return Err(ResolveError::StepLimit {
+                        max_steps,
+                        conflicts: ConflictSummary::from_counts(&conflicts.lock().unwrap()),
+                    });
+                }
+            }
+
             // Run unit propagation.
             state.unit_propagation(next)?;
 
@@ -341,6 +402,7 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
                         reason.map(ToString::to_string),
                     );
 
+                    *conflicts.lock().unwrap().entry(next.clone()).or_default() += 1;
                     state.add_incompatibility(inc);
                     continue;
                 }
@@ -349,6 +411,7 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
             let version = match version {
                 ResolverVersion::Available(version) => version,
                 ResolverVersion::Unavailable(version, unavailable) => {
+                    *conflicts.lock().unwrap().entry(next.clone()).or_default() += 1;
                     let reason = match unavailable {
                         UnavailableVersion::RequiresPython(requires_python) => {
                             // Incompatible requires-python versions are special in that we track
@@ -390,6 +453,7 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
                                             IncompatibleTag::Python => "no wheels are available with a matching Python implementation".to_string(),
                                             IncompatibleTag::Abi => "no wheels are available with a matching Python ABI".to_string(),
                                             IncompatibleTag::Platform => "no wheels are available with a matching platform".to_string(),
+                                            IncompatibleTag::Libc { required, detected } => format!("the available wheels require {required} or later, but the target platform has {detected} (consider building from a source distribution, i.e., without `--only-binary`, or using an older version with compatible wheels)"),
                                         }
                                     }
                                 }
@@ -740,6 +804,8 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
     ) -> Result<Dependencies, ResolveError> {
         match package {
             PubGrubPackage::Root(_) => {
+                self.record_markers(None, &self.requirements);
+
                 // Add the root requirements.
                 let constraints = PubGrubDependencies::from_requirements(
                     &self.requirements,
@@ -747,6 +813,7 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
                     &self.overrides,
                     None,
                     None,
+                    None,
                     &self.urls,
                     self.markers,
                 );
@@ -816,12 +883,15 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
 
                 // Determine if the distribution is editable.
                 if let Some((_local, metadata)) = self.editables.get(package_name) {
+                    self.record_markers(Some(package_name), &metadata.requires_dist);
+
                     let mut constraints = PubGrubDependencies::from_requirements(
                         &metadata.requires_dist,
                         &self.constraints,
                         &self.overrides,
                         Some(package_name),
                         extra.as_ref(),
+                        Some(version),
                         &self.urls,
                         self.markers,
                     )?;
@@ -872,12 +942,15 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
                     .await
                     .ok_or(ResolveError::Unregistered)?;
 
+                self.record_markers(Some(package_name), &metadata.requires_dist);
+
                 let mut constraints = PubGrubDependencies::from_requirements(
                     &metadata.requires_dist,
                     &self.constraints,
                     &self.overrides,
                     Some(package_name),
                     extra.as_ref(),
+                    Some(version),
                     &self.urls,
                     self.markers,
                 )?;
@@ -903,6 +976,25 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
         }
     }
 
+    /// Record the markers attached to the given requirements, for use in error reporting.
+    ///
+    /// A dependency may be requested more than once by the same package (e.g., by multiple
+    /// versions, or under different markers), in which case the markers are combined.
+    fn record_markers(&self, source: Option<&PackageName>, requirements: &[Requirement]) {
+        for requirement in requirements {
+            let key = (source.cloned(), requirement.name.clone());
+            match self.dependency_markers.entry(key) {
+                Entry::Occupied(mut entry) => {
+                    let existing = entry.get_mut();
+                    *existing = combine_markers(existing.take(), requirement.marker.as_ref());
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(requirement.marker.clone());
+                }
+            }
+        }
+    }
+
     /// Fetch the metadata for a stream of packages and versions.
     async fn fetch(
         &self,
@@ -910,7 +1002,7 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
     ) -> Result<(), ResolveError> {
         let mut response_stream = ReceiverStream::new(request_stream)
             .map(|request| self.process_request(request).boxed())
-            .buffer_unordered(50);
+            .buffer_unordered(self.concurrent_downloads);
 
         while let Some(response) = response_stream.next().await {
             match response? {
@@ -1164,3 +1256,22 @@ fn uncapitalize<T: AsRef<str>>(string: T) -> String {
         Some(first) => first.to_lowercase().chain(chars).collect(),
     }
 }
+
+/// Combine the markers under which a dependency was requested, where `None` indicates that it was
+/// requested unconditionally.
+pub(crate) fn combine_markers(
+    existing: Option<MarkerTree>,
+    marker: Option<&MarkerTree>,
+) -> Option<MarkerTree> {
+    let (existing, marker) = (existing?, marker?);
+    match existing {
+        MarkerTree::Or(mut markers) => {
+            if !markers.contains(marker) {
+                markers.push(marker.clone());
+            }
+            Some(MarkerTree::Or(markers))
+        }
+        existing if existing == *marker => Some(existing),
+        existing => Some(MarkerTree::Or(vec![existing, marker.clone()])),
+    }
+}
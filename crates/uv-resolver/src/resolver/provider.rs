@@ -12,6 +12,7 @@ use uv_distribution::DistributionDatabase;
 use uv_normalize::PackageName;
 use uv_traits::{BuildContext, NoBinary};
 
+use crate::min_release_age::MinReleaseAge;
 use crate::python_requirement::PythonRequirement;
 use crate::version_map::VersionMap;
 
@@ -67,6 +68,9 @@ pub struct DefaultResolverProvider<'a, Context: BuildContext + Send + Sync> {
     tags: Tags,
     python_requirement: PythonRequirement,
     exclude_newer: Option<DateTime<Utc>>,
+    min_release_age: MinReleaseAge,
+    /// The time at which the resolution started, against which release ages are measured.
+    now: DateTime<Utc>,
     no_binary: NoBinary,
 }
 
@@ -76,10 +80,11 @@ impl<'a, Context: BuildContext + Send + Sync> DefaultResolverProvider<'a, Contex
     pub fn new(
         client: &'a RegistryClient,
         fetcher: DistributionDatabase<'a, Context>,
-        flat_index: &'a FlatIndex,
+        flat_index: &FlatIndex,
         tags: &'a Tags,
         python_requirement: PythonRequirement,
         exclude_newer: Option<DateTime<Utc>>,
+        min_release_age: MinReleaseAge,
         no_binary: &'a NoBinary,
     ) -> Self {
         Self {
@@ -89,6 +94,8 @@ impl<'a, Context: BuildContext + Send + Sync> DefaultResolverProvider<'a, Contex
             tags: tags.clone(),
             python_requirement,
             exclude_newer,
+            min_release_age,
+            now: Utc::now(),
             no_binary: no_binary.clone(),
         }
     }
@@ -104,6 +111,12 @@ impl<'a, Context: BuildContext + Send + Sync> ResolverProvider
     ) -> PackageVersionsResult {
         let result = self.client.simple(package_name).await;
 
+        // Exclude any distributions that are newer than the `--exclude-newer` cutoff, or that
+        // were published more recently than the minimum release age.
+        let exclude_newer =
+            self.min_release_age
+                .exclude_newer(package_name, self.exclude_newer, self.now);
+
         // If the "Simple API" request was successful, convert to `VersionMap` on the Tokio
         // threadpool, since it can be slow.
         match result {
@@ -113,7 +126,7 @@ impl<'a, Context: BuildContext + Send + Sync> ResolverProvider
                 &index,
                 &self.tags,
                 &self.python_requirement,
-                self.exclude_newer.as_ref(),
+                exclude_newer.as_ref(),
                 self.flat_index.get(package_name).cloned(),
                 &self.no_binary,
             ))),
@@ -2,7 +2,7 @@ use std::sync::Arc;
 
 use url::Url;
 
-use distribution_types::{SourceDist, VersionOrUrl};
+use distribution_types::{Dist, SourceDist, VersionOrUrl};
 use uv_normalize::PackageName;
 
 pub type BuildId = usize;
@@ -48,4 +48,14 @@ impl uv_distribution::Reporter for Facade {
     fn on_checkout_complete(&self, url: &Url, rev: &str, index: usize) {
         self.reporter.on_checkout_complete(url, rev, index);
     }
+
+    // Wheels are only downloaded during resolution when their metadata can't be fetched directly,
+    // so their progress isn't reported.
+    fn on_download_start(&self, _dist: &Dist, _size: Option<u64>) -> usize {
+        0
+    }
+
+    fn on_download_progress(&self, _id: usize, _bytes: u64) {}
+
+    fn on_download_complete(&self, _id: usize) {}
 }
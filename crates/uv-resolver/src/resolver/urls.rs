@@ -1,4 +1,5 @@
 use rustc_hash::FxHashMap;
+use url::Url;
 
 use distribution_types::Verbatim;
 use pep508_rs::{MarkerEnvironment, VerbatimUrl};
@@ -28,9 +29,7 @@ impl Urls {
 
             if let Some(pep508_rs::VersionOrUrl::Url(url)) = &requirement.version_or_url {
                 if let Some(previous) = urls.insert(requirement.name.clone(), url.clone()) {
-                    if cache_key::CanonicalUrl::new(previous.raw())
-                        != cache_key::CanonicalUrl::new(url.raw())
-                    {
+                    if !is_same_url(previous.raw(), url.raw()) {
                         return Err(ResolveError::ConflictingUrlsDirect(
                             requirement.name.clone(),
                             previous.verbatim().to_string(),
@@ -44,9 +43,7 @@ impl Urls {
         // Add any editable requirements. If there are any conflicts, return an error.
         for (requirement, metadata) in &manifest.editables {
             if let Some(previous) = urls.insert(metadata.name.clone(), requirement.url.clone()) {
-                if cache_key::CanonicalUrl::new(previous.raw())
-                    != cache_key::CanonicalUrl::new(requirement.raw())
-                {
+                if !is_same_url(previous.raw(), requirement.raw()) {
                     return Err(ResolveError::ConflictingUrlsDirect(
                         metadata.name.clone(),
                         previous.verbatim().to_string(),
@@ -58,9 +55,7 @@ impl Urls {
             for requirement in &metadata.requires_dist {
                 if let Some(pep508_rs::VersionOrUrl::Url(url)) = &requirement.version_or_url {
                     if let Some(previous) = urls.insert(requirement.name.clone(), url.clone()) {
-                        if cache_key::CanonicalUrl::new(previous.raw())
-                            != cache_key::CanonicalUrl::new(url.raw())
-                        {
+                        if !is_same_url(previous.raw(), url.raw()) {
                             return Err(ResolveError::ConflictingUrlsDirect(
                                 requirement.name.clone(),
                                 previous.verbatim().to_string(),
@@ -91,3 +86,28 @@ impl Urls {
         self.0.get(package)
     }
 }
+
+/// Returns `true` if the two URLs refer to the same resource.
+///
+/// Local files and directories are compared by their canonical location on disk (following
+/// symlinks), such that the same resource referenced via different paths isn't considered a
+/// conflict.
+pub(crate) fn is_same_url(previous: &Url, url: &Url) -> bool {
+    if cache_key::CanonicalUrl::new(previous) == cache_key::CanonicalUrl::new(url) {
+        return true;
+    }
+
+    if previous.scheme() != "file" || url.scheme() != "file" {
+        return false;
+    }
+    if previous.fragment() != url.fragment() {
+        return false;
+    }
+    let (Ok(previous), Ok(url)) = (previous.to_file_path(), url.to_file_path()) else {
+        return false;
+    };
+    match (fs_err::canonicalize(previous), fs_err::canonicalize(url)) {
+        (Ok(previous), Ok(url)) => previous == url,
+        _ => false,
+    }
+}
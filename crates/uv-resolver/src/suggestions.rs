@@ -0,0 +1,235 @@
+use std::str::FromStr;
+
+use uv_normalize::PackageName;
+
+/// Import names that differ from the name of the package that provides them, as `(import, package)`
+/// pairs. The import names are normalized, to match against a [`PackageName`].
+static IMPORT_NAMES: &[(&str, &str)] = &[
+    ("attr", "attrs"),
+    ("bs4", "beautifulsoup4"),
+    ("crypto", "pycryptodome"),
+    ("cv2", "opencv-python"),
+    ("dateutil", "python-dateutil"),
+    ("docx", "python-docx"),
+    ("dotenv", "python-dotenv"),
+    ("fitz", "pymupdf"),
+    ("gi", "pygobject"),
+    ("git", "gitpython"),
+    ("google-protobuf", "protobuf"),
+    ("jose", "python-jose"),
+    ("jwt", "pyjwt"),
+    ("ldap", "python-ldap"),
+    ("magic", "python-magic"),
+    ("mpl-toolkits", "matplotlib"),
+    ("multipart", "python-multipart"),
+    ("mysqldb", "mysqlclient"),
+    ("openssl", "pyopenssl"),
+    ("pil", "pillow"),
+    ("psycopg2", "psycopg2-binary"),
+    ("serial", "pyserial"),
+    ("skimage", "scikit-image"),
+    ("sklearn", "scikit-learn"),
+    ("telegram", "python-telegram-bot"),
+    ("usb", "pyusb"),
+    ("win32api", "pywin32"),
+    ("win32com", "pywin32"),
+    ("yaml", "pyyaml"),
+    ("zmq", "pyzmq"),
+];
+
+/// A list of popular packages, used to suggest alternatives for misspelled package names.
+static POPULAR_PACKAGES: &[&str] = &[
+    "aiohttp",
+    "anyio",
+    "attrs",
+    "beautifulsoup4",
+    "black",
+    "boto3",
+    "botocore",
+    "celery",
+    "certifi",
+    "cffi",
+    "charset-normalizer",
+    "click",
+    "colorama",
+    "cryptography",
+    "django",
+    "fastapi",
+    "filelock",
+    "flask",
+    "fsspec",
+    "gunicorn",
+    "h11",
+    "httpx",
+    "idna",
+    "jinja2",
+    "jsonschema",
+    "keras",
+    "lxml",
+    "markupsafe",
+    "matplotlib",
+    "mypy",
+    "networkx",
+    "numpy",
+    "opencv-python",
+    "packaging",
+    "pandas",
+    "pillow",
+    "pip",
+    "platformdirs",
+    "pluggy",
+    "protobuf",
+    "psutil",
+    "psycopg2",
+    "pyarrow",
+    "pydantic",
+    "pygments",
+    "pyjwt",
+    "pytest",
+    "python-dateutil",
+    "python-dotenv",
+    "pytz",
+    "pyyaml",
+    "redis",
+    "requests",
+    "rich",
+    "scikit-learn",
+    "scipy",
+    "setuptools",
+    "six",
+    "sqlalchemy",
+    "starlette",
+    "sympy",
+    "tensorflow",
+    "toml",
+    "tomli",
+    "torch",
+    "tqdm",
+    "transformers",
+    "typing-extensions",
+    "urllib3",
+    "uvicorn",
+    "virtualenv",
+    "werkzeug",
+    "wheel",
+    "zipp",
+];
+
+/// The maximum number of similarly-named packages to suggest.
+const MAX_SUGGESTIONS: usize = 3;
+
+/// A suggested alternative for a package that could not be found.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) enum Suggestion {
+    /// The requested name is the import name of a module provided by a differently-named package
+    /// (e.g., `cv2` is provided by `opencv-python`).
+    ImportName(PackageName),
+    /// The requested name is similar to the names of one or more popular packages, and may be a
+    /// typo.
+    SimilarNames(Vec<PackageName>),
+}
+
+impl Suggestion {
+    /// Suggest alternatives for a package that could not be found in the registry.
+    pub(crate) fn for_package(name: &PackageName) -> Option<Self> {
+        // Check for a known import name.
+        if let Some((_, package)) = IMPORT_NAMES
+            .iter()
+            .find(|(import, _)| *import == name.as_ref())
+        {
+            return PackageName::from_str(package).ok().map(Self::ImportName);
+        }
+
+        // Otherwise, look for popular packages within a small edit distance. Shorter names
+        // tolerate fewer edits, to avoid suggesting unrelated packages.
+        let threshold = (name.as_ref().len() / 4).clamp(1, 3);
+        let mut candidates = POPULAR_PACKAGES
+            .iter()
+            .filter(|package| **package != name.as_ref())
+            .filter_map(|package| {
+                let distance = distance(name.as_ref(), package);
+                (distance <= threshold).then_some((distance, *package))
+            })
+            .collect::<Vec<_>>();
+        if candidates.is_empty() {
+            return None;
+        }
+        candidates.sort_unstable();
+
+        Some(Self::SimilarNames(
+            candidates
+                .into_iter()
+                .take(MAX_SUGGESTIONS)
+                .filter_map(|(_, package)| PackageName::from_str(package).ok())
+                .collect(),
+        ))
+    }
+}
+
+/// Compute the Damerau–Levenshtein distance (with adjacent transpositions) between two strings.
+fn distance(a: &str, b: &str) -> usize {
+    let a = a.chars().collect::<Vec<_>>();
+    let b = b.chars().collect::<Vec<_>>();
+
+    // The rows of the distance matrix for the previous two characters of `a`.
+    let mut prev_prev = vec![0; b.len() + 1];
+    let mut prev = (0..=b.len()).collect::<Vec<_>>();
+    let mut current = vec![0; b.len() + 1];
+
+    for i in 1..=a.len() {
+        current[0] = i;
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            current[j] = (prev[j] + 1)
+                .min(current[j - 1] + 1)
+                .min(prev[j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                current[j] = current[j].min(prev_prev[j - 2] + 1);
+            }
+        }
+        std::mem::swap(&mut prev_prev, &mut prev);
+        std::mem::swap(&mut prev, &mut current);
+    }
+
+    prev[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edit_distance() {
+        assert_eq!(distance("", ""), 0);
+        assert_eq!(distance("flask", "flask"), 0);
+        assert_eq!(distance("flaks", "flask"), 1);
+        assert_eq!(distance("reqeusts", "requests"), 1);
+        assert_eq!(distance("requets", "requests"), 1);
+        assert_eq!(distance("numpyy", "numpy"), 1);
+        assert_eq!(distance("django", "flask"), 5);
+    }
+
+    #[test]
+    fn import_name() {
+        let name = PackageName::from_str("cv2").unwrap();
+        assert_eq!(
+            Suggestion::for_package(&name),
+            Some(Suggestion::ImportName(
+                PackageName::from_str("opencv-python").unwrap()
+            ))
+        );
+    }
+
+    #[test]
+    fn similar_names() {
+        let name = PackageName::from_str("reqeusts").unwrap();
+        let requests = PackageName::from_str("requests").unwrap();
+        assert_eq!(
+            Suggestion::for_package(&name),
+            Some(Suggestion::SimilarNames(vec![requests]))
+        );
+
+        let name = PackageName::from_str("some-private-package").unwrap();
+        assert_eq!(Suggestion::for_package(&name), None);
+    }
+}
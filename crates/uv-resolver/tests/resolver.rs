@@ -21,7 +21,10 @@ use uv_resolver::{
     DisplayResolutionGraph, InMemoryIndex, Manifest, Options, OptionsBuilder, PreReleaseMode,
     ResolutionGraph, ResolutionMode, Resolver,
 };
-use uv_traits::{BuildContext, BuildKind, NoBinary, NoBuild, SetupPyStrategy, SourceBuildTrait};
+use uv_traits::{
+    BuildContext, BuildIsolation, BuildKind, Concurrency, HashDatabase, NoBinary, NoBuild,
+    SetupPyStrategy, SourceBuildTrait,
+};
 
 // Exclude any packages uploaded after this date.
 static EXCLUDE_NEWER: Lazy<DateTime<Utc>> = Lazy::new(|| {
@@ -34,6 +37,7 @@ struct DummyContext {
     cache: Cache,
     interpreter: Interpreter,
     index_locations: IndexLocations,
+    hash_database: HashDatabase,
 }
 
 impl DummyContext {
@@ -42,6 +46,7 @@ impl DummyContext {
             cache,
             interpreter,
             index_locations: IndexLocations::default(),
+            hash_database: HashDatabase::default(),
         }
     }
 }
@@ -69,6 +74,22 @@ impl BuildContext for DummyContext {
         SetupPyStrategy::default()
     }
 
+    fn build_isolation(&self) -> BuildIsolation {
+        BuildIsolation::Isolated
+    }
+
+    fn extra_build_requires(&self) -> &[Requirement] {
+        &[]
+    }
+
+    fn hash_database(&self) -> &HashDatabase {
+        &self.hash_database
+    }
+
+    fn concurrency(&self) -> Concurrency {
+        Concurrency::default()
+    }
+
     fn index_locations(&self) -> &IndexLocations {
         &self.index_locations
     }
@@ -640,6 +661,25 @@ async fn msgraph_sdk() -> Result<()> {
     Ok(())
 }
 
+/// Resolve Black with a step limit that's too low to complete the resolution. The resolver should
+/// abort rather than continuing to search.
+#[tokio::test]
+async fn black_max_steps() -> Result<()> {
+    let manifest = Manifest::simple(vec![Requirement::from_str("black<=23.9.1").unwrap()]);
+    let options = OptionsBuilder::new()
+        .exclude_newer(Some(*EXCLUDE_NEWER))
+        .max_steps(Some(2))
+        .build();
+
+    let err = resolve(manifest, options, &MARKERS_311, &TAGS_311)
+        .await
+        .unwrap_err();
+
+    assert_snapshot!(err, @"Resolution did not complete within 2 steps");
+
+    Ok(())
+}
+
 static MARKERS_311: Lazy<MarkerEnvironment> = Lazy::new(|| {
     MarkerEnvironment {
         implementation_name: "cpython".to_string(),
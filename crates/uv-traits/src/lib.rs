@@ -4,13 +4,15 @@ use std::collections::btree_map::Entry;
 use std::collections::BTreeMap;
 use std::fmt::{Display, Formatter};
 use std::future::Future;
+use std::num::NonZeroUsize;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
 
 use distribution_types::{CachedDist, DistributionId, IndexLocations, Resolution, SourceDist};
 use once_map::OnceMap;
+use pep440_rs::Version;
 use pep508_rs::Requirement;
 use uv_cache::Cache;
 use uv_interpreter::{Interpreter, PythonEnvironment};
@@ -78,6 +80,21 @@ pub trait BuildContext: Sync {
     /// The strategy to use when building source distributions that lack a `pyproject.toml`.
     fn setup_py_strategy(&self) -> SetupPyStrategy;
 
+    /// Whether to build source distributions in isolated environments, or in a shared
+    /// environment that already contains the build dependencies.
+    fn build_isolation(&self) -> BuildIsolation;
+
+    /// Additional requirements to install into every isolated build environment, alongside the
+    /// requirements declared by the build backend.
+    fn extra_build_requires(&self) -> &[Requirement];
+
+    /// The expected hashes of downloaded distributions, independent of any hashes provided in
+    /// requirements files.
+    fn hash_database(&self) -> &HashDatabase;
+
+    /// The limits on the number of concurrent downloads, builds, and installs.
+    fn concurrency(&self) -> Concurrency;
+
     /// Resolve the given requirements into a ready-to-install set of package versions.
     fn resolve<'a>(
         &'a self,
@@ -131,6 +148,40 @@ pub trait SourceBuildTrait {
         -> impl Future<Output = Result<String>> + Send + 'a;
 }
 
+/// Limits on the number of operations to perform concurrently.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Concurrency {
+    /// The maximum number of concurrent downloads.
+    pub downloads: usize,
+    /// The maximum number of concurrent source distribution builds.
+    pub builds: usize,
+    /// The maximum number of threads to use when installing wheels.
+    pub installs: usize,
+}
+
+impl Concurrency {
+    /// The default maximum number of concurrent downloads.
+    pub const DEFAULT_DOWNLOADS: usize = 50;
+
+    /// The default maximum number of concurrent builds and installs, based on the available
+    /// parallelism of the machine.
+    pub fn threads() -> usize {
+        std::thread::available_parallelism()
+            .map(NonZeroUsize::get)
+            .unwrap_or(1)
+    }
+}
+
+impl Default for Concurrency {
+    fn default() -> Self {
+        Self {
+            downloads: Self::DEFAULT_DOWNLOADS,
+            builds: Self::threads(),
+            installs: Self::threads(),
+        }
+    }
+}
+
 #[derive(Default)]
 pub struct InFlight {
     /// The in-flight distribution downloads.
@@ -139,6 +190,7 @@ pub struct InFlight {
 
 /// The strategy to use when building source distributions that lack a `pyproject.toml`.
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum SetupPyStrategy {
     /// Perform a PEP 517 build.
     #[default]
@@ -147,6 +199,25 @@ pub enum SetupPyStrategy {
     Setuptools,
 }
 
+/// Whether to isolate source distribution builds from the target environment.
+#[derive(Copy, Clone, Debug, Default)]
+pub enum BuildIsolation<'a> {
+    /// Build each source distribution in a fresh virtual environment, containing only the
+    /// requirements declared by its build backend.
+    #[default]
+    Isolated,
+    /// Build source distributions directly in the given environment, which is expected to contain
+    /// the build backend and any other build dependencies.
+    Shared(&'a PythonEnvironment),
+}
+
+impl<'a> BuildIsolation<'a> {
+    /// Returns `true` if builds should be isolated.
+    pub fn is_isolated(&self) -> bool {
+        matches!(self, Self::Isolated)
+    }
+}
+
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
 pub enum BuildKind {
     /// A regular PEP 517 wheel build
@@ -250,9 +321,24 @@ impl NoBinary {
     pub fn is_none(&self) -> bool {
         matches!(self, Self::None)
     }
+
+    /// Combine two binary installation strategies, such that any wheel disallowed by either
+    /// strategy is disallowed.
+    #[must_use]
+    pub fn combine(self, other: Self) -> Self {
+        match (self, other) {
+            (Self::All, _) | (_, Self::All) => Self::All,
+            (Self::None, other) | (other, Self::None) => other,
+            (Self::Packages(mut packages), Self::Packages(other)) => {
+                packages.extend(other);
+                Self::Packages(packages)
+            }
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum NoBuild {
     /// Allow building wheels from any source distribution.
     None,
@@ -285,6 +371,20 @@ impl NoBuild {
     pub fn is_none(&self) -> bool {
         matches!(self, Self::None)
     }
+
+    /// Combine two build strategies, such that any build disallowed by either strategy is
+    /// disallowed.
+    #[must_use]
+    pub fn combine(self, other: Self) -> Self {
+        match (self, other) {
+            (Self::All, _) | (_, Self::All) => Self::All,
+            (Self::None, other) | (other, Self::None) => other,
+            (Self::Packages(mut packages), Self::Packages(other)) => {
+                packages.extend(other);
+                Self::Packages(packages)
+            }
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -349,6 +449,165 @@ impl FromIterator<ConfigSettingEntry> for ConfigSettings {
     }
 }
 
+/// A database of the expected hashes for specific versions of packages, consulted whenever a
+/// distribution is downloaded, independent of any hashes provided in requirements files.
+///
+/// The database is read from a `requirements.txt`-like file, in which each entry pins a package to
+/// a version (or to a direct URL) and lists the SHA-256 digests of its permitted artifacts:
+///
+/// ```text
+/// anyio==4.0.0 \
+///     --hash=sha256:cfdb2b588b9fc25ede96d8db56ed50848b0b649dca3dd1df0b11f683bb9e0b5f
+/// idna @ https://files.pythonhosted.org/packages/idna-3.4-py3-none-any.whl \
+///     --hash=sha256:90b77e79eaa3eba6de819a0c442c0b4ceefc341a7a2ab77d7562bf49f425c5c2
+/// ```
+///
+/// Packages that are absent from the database are unrestricted. However, if a package is present,
+/// only the listed versions and artifacts are permitted. Direct URLs (including local archives)
+/// are matched by URL, ignoring any fragment.
+#[derive(Debug, Default, Clone)]
+pub struct HashDatabase {
+    /// The permitted digests for each version of each package.
+    packages: BTreeMap<PackageName, BTreeMap<Version, Vec<String>>>,
+    /// The permitted digests for each direct URL.
+    urls: BTreeMap<String, Vec<String>>,
+}
+
+impl HashDatabase {
+    /// Read a [`HashDatabase`] from the given file.
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self> {
+        let content = fs_err::read_to_string(path.as_ref())?;
+        Self::parse(&content)
+            .with_context(|| format!("Failed to parse hash database: {}", path.as_ref().display()))
+    }
+
+    /// Parse a [`HashDatabase`] from the contents of a `requirements.txt`-like file.
+    pub fn parse(content: &str) -> Result<Self> {
+        let mut database = Self::default();
+
+        // Join any continued lines, as in the output of `pip-compile --generate-hashes`.
+        let content = content.replace("\\\r\n", " ").replace("\\\n", " ");
+        for line in content.lines() {
+            let mut tokens = line
+                .split_whitespace()
+                // Strip any comments. (A `#` within a token, as in a URL fragment, is preserved.)
+                .take_while(|token| !token.starts_with('#'))
+                .peekable();
+            let Some(pin) = tokens.next() else {
+                continue;
+            };
+
+            // Parse a direct URL pin, as in `idna @ https://...`.
+            let (pin, url) = if let Some((name, url)) = pin.split_once('@') {
+                (
+                    name,
+                    if url.is_empty() {
+                        tokens.next()
+                    } else {
+                        Some(url)
+                    },
+                )
+            } else if tokens.next_if_eq(&"@").is_some() {
+                (pin, tokens.next())
+            } else {
+                (pin, None)
+            };
+
+            let mut hashes = Vec::new();
+            while let Some(token) = tokens.next() {
+                let hash = if let Some(hash) = token.strip_prefix("--hash=") {
+                    hash
+                } else if token == "--hash" {
+                    tokens
+                        .next()
+                        .with_context(|| format!("Missing value for `--hash` on `{pin}`"))?
+                } else {
+                    bail!("Unexpected `{token}` on `{pin}` (expected `--hash`)");
+                };
+                hashes.push(parse_digest(hash, pin)?);
+            }
+            if hashes.is_empty() {
+                bail!("No hashes provided for `{pin}`");
+            }
+
+            if let Some(url) = url {
+                // Validate the package name, though direct URLs are matched by URL alone.
+                PackageName::from_str(pin.trim())?;
+                database
+                    .urls
+                    .entry(url_key(url).to_string())
+                    .or_default()
+                    .extend(hashes);
+            } else {
+                let Some((name, version)) = pin.split_once("==") else {
+                    bail!("Expected a pinned requirement (e.g., `flask==3.0.0`), found: `{pin}`");
+                };
+                let name = PackageName::from_str(name)?;
+                let version = Version::from_str(version)?;
+                database
+                    .packages
+                    .entry(name)
+                    .or_default()
+                    .entry(version)
+                    .or_default()
+                    .extend(hashes);
+            }
+        }
+
+        Ok(database)
+    }
+
+    /// Add the permitted hashes (e.g., `sha256:abc123`) for a direct URL, as provided alongside a
+    /// requirement in a `requirements.txt` file.
+    pub fn insert_url(&mut self, url: &str, hashes: &[String]) -> Result<()> {
+        let hashes = hashes
+            .iter()
+            .map(|hash| parse_digest(hash, url))
+            .collect::<Result<Vec<_>>>()?;
+        self.urls
+            .entry(url_key(url).to_string())
+            .or_default()
+            .extend(hashes);
+        Ok(())
+    }
+
+    /// Returns `true` if the database contains no packages.
+    pub fn is_empty(&self) -> bool {
+        self.packages.is_empty() && self.urls.is_empty()
+    }
+
+    /// Return the permitted SHA-256 digests (as hex strings) for the given package version.
+    ///
+    /// Returns `None` if the package is absent from the database, in which case any artifact is
+    /// permitted, and an empty slice if the package is present, but the version is not, in which
+    /// case no artifact is permitted.
+    pub fn expected(&self, name: &PackageName, version: &Version) -> Option<&[String]> {
+        let versions = self.packages.get(name)?;
+        Some(versions.get(version).map_or(&[], Vec::as_slice))
+    }
+
+    /// Return the permitted SHA-256 digests (as hex strings) for the given direct URL.
+    ///
+    /// Returns `None` if the URL is absent from the database, in which case any artifact is
+    /// permitted.
+    pub fn expected_url(&self, url: &str) -> Option<&[String]> {
+        self.urls.get(url_key(url)).map(Vec::as_slice)
+    }
+}
+
+/// Parse a `sha256:<digest>` hash into a lowercase hex digest.
+fn parse_digest(hash: &str, pin: &str) -> Result<String> {
+    let Some(digest) = hash.strip_prefix("sha256:") else {
+        bail!("Unsupported hash `{hash}` on `{pin}` (only `sha256` is supported)");
+    };
+    Ok(digest.to_ascii_lowercase())
+}
+
+/// Strip any fragment (e.g., `#subdirectory=...` or `#sha256=...`) from a URL.
+fn url_key(url: &str) -> &str {
+    url.split_once('#').map_or(url, |(url, _)| url)
+}
+
 #[cfg(feature = "serde")]
 impl ConfigSettings {
     /// Convert the settings to a string that can be passed directly to a PEP 517 build backend.
@@ -439,6 +698,27 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn no_build_combine() -> Result<(), Error> {
+        let foo = PackageName::from_str("foo")?;
+        let bar = PackageName::from_str("bar")?;
+        assert_eq!(NoBuild::None.combine(NoBuild::None), NoBuild::None);
+        assert_eq!(
+            NoBuild::Packages(vec![foo.clone()]).combine(NoBuild::All),
+            NoBuild::All
+        );
+        assert_eq!(
+            NoBuild::None.combine(NoBuild::Packages(vec![foo.clone()])),
+            NoBuild::Packages(vec![foo.clone()])
+        );
+        assert_eq!(
+            NoBuild::Packages(vec![foo.clone()]).combine(NoBuild::Packages(vec![bar.clone()])),
+            NoBuild::Packages(vec![foo, bar])
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn collect_config_settings() {
         let settings: ConfigSettings = vec![
@@ -515,4 +795,74 @@ mod tests {
         );
         assert_eq!(settings.escape_for_python(), r#"{"key":"val\\1 {}ue"}"#);
     }
+
+    #[test]
+    fn hash_database() -> Result<(), Error> {
+        let database = HashDatabase::parse(
+            "# Approved artifacts.\n\
+             anyio==4.0.0 \\\n    \
+                 --hash=sha256:ABC123 \\\n    \
+                 --hash=sha256:def456\n\
+             idna==3.4 --hash sha256:789 # An inline comment.\n",
+        )?;
+
+        let anyio = PackageName::from_str("anyio")?;
+        assert_eq!(
+            database.expected(&anyio, &Version::from_str("4.0.0")?),
+            Some(["abc123".to_string(), "def456".to_string()].as_slice())
+        );
+        assert_eq!(
+            database.expected(&anyio, &Version::from_str("3.7.1")?),
+            Some([].as_slice())
+        );
+        assert_eq!(
+            database.expected(&PackageName::from_str("idna")?, &Version::from_str("3.4")?),
+            Some(["789".to_string()].as_slice())
+        );
+        assert_eq!(
+            database.expected(
+                &PackageName::from_str("flask")?,
+                &Version::from_str("3.0.0")?
+            ),
+            None
+        );
+
+        assert!(HashDatabase::parse("anyio>=4.0.0 --hash=sha256:abc").is_err());
+        assert!(HashDatabase::parse("anyio @ https://example.com/anyio.tar.gz").is_err());
+        assert!(HashDatabase::parse("anyio==4.0.0 --hash=md5:abc").is_err());
+        assert!(HashDatabase::parse("anyio==4.0.0").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn hash_database_url() -> Result<(), Error> {
+        let database = HashDatabase::parse(
+            "anyio @ https://example.com/anyio-4.0.0.tar.gz#sha256=abc \\\n    \
+                 --hash=sha256:ABC\n\
+             idna@file:///tmp/idna-3.4-py3-none-any.whl --hash=sha256:def # A comment.\n",
+        )?;
+
+        assert_eq!(
+            database.expected_url("https://example.com/anyio-4.0.0.tar.gz"),
+            Some(["abc".to_string()].as_slice())
+        );
+        assert_eq!(
+            database.expected_url("file:///tmp/idna-3.4-py3-none-any.whl"),
+            Some(["def".to_string()].as_slice())
+        );
+        assert_eq!(
+            database.expected_url("https://example.com/flask-3.0.0.tar.gz"),
+            None
+        );
+        assert_eq!(
+            database.expected(
+                &PackageName::from_str("anyio")?,
+                &Version::from_str("4.0.0")?
+            ),
+            None
+        );
+
+        Ok(())
+    }
 }
@@ -1,4 +1,5 @@
-use std::sync::atomic::AtomicBool;
+use std::fmt::{Display, Formatter};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Mutex;
 
 // macro hygiene: The user might not have direct dependencies on those crates
@@ -7,14 +8,14 @@ pub use anstream;
 use once_cell::sync::Lazy;
 #[doc(hidden)]
 pub use owo_colors;
-use rustc_hash::FxHashSet;
+use rustc_hash::{FxHashMap, FxHashSet};
 
 /// Whether user-facing warnings are enabled.
 pub static ENABLED: AtomicBool = AtomicBool::new(false);
 
 /// Enable user-facing warnings.
 pub fn enable() {
-    ENABLED.store(true, std::sync::atomic::Ordering::SeqCst);
+    ENABLED.store(true, Ordering::SeqCst);
 }
 
 /// Warn a user, if warnings are enabled.
@@ -52,3 +53,129 @@ macro_rules! warn_user_once {
         }
     };
 }
+
+/// A category of user-facing warning, identified by a stable code.
+///
+/// Unlike uncategorized warnings, categorized warnings can be suppressed (`--silence`) or promoted
+/// to errors (`--warn-as-error`), such that, e.g., CI can enforce a policy for each category.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum WarningCode {
+    /// A yanked distribution was installed.
+    Yanked,
+    /// No hashes were available for a pinned distribution.
+    MissingHashes,
+    /// An entrypoint script overwrote an existing file, e.g., a script from another package.
+    ScriptCollision,
+}
+
+impl WarningCode {
+    /// Return the stable code for the warning, as accepted on the command line.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Yanked => "yanked",
+            Self::MissingHashes => "missing-hashes",
+            Self::ScriptCollision => "script-collision",
+        }
+    }
+}
+
+impl Display for WarningCode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// How a category of warnings should be surfaced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Policy {
+    /// Don't display the warning.
+    Silence,
+    /// Display the warning as an error, and fail the command.
+    Error,
+}
+
+static POLICIES: Lazy<Mutex<FxHashMap<WarningCode, Policy>>> = Lazy::new(Mutex::default);
+
+/// Whether any warnings were promoted to errors.
+static ERRORS: AtomicBool = AtomicBool::new(false);
+
+/// Suppress warnings with the given code.
+pub fn silence(code: WarningCode) {
+    if let Ok(mut policies) = POLICIES.lock() {
+        policies.insert(code, Policy::Silence);
+    }
+}
+
+/// Promote warnings with the given code to errors.
+pub fn warn_as_error(code: WarningCode) {
+    if let Ok(mut policies) = POLICIES.lock() {
+        policies.insert(code, Policy::Error);
+    }
+}
+
+/// Returns `true` if warnings with the given code are promoted to errors.
+pub fn is_error(code: WarningCode) -> bool {
+    POLICIES
+        .lock()
+        .is_ok_and(|policies| policies.get(&code) == Some(&Policy::Error))
+}
+
+/// Returns `true` if any warnings were promoted to errors, in which case the command should fail.
+pub fn has_errors() -> bool {
+    ERRORS.load(Ordering::SeqCst)
+}
+
+/// Display a categorized warning, respecting the policy for its code.
+///
+/// Warnings are displayed once, with uniqueness determined by the content of the message.
+/// Warnings that were promoted to errors are displayed even if warnings are disabled.
+#[doc(hidden)]
+pub fn emit(code: WarningCode, message: &str) {
+    use anstream::eprintln;
+    use owo_colors::OwoColorize;
+
+    let policy = POLICIES
+        .lock()
+        .ok()
+        .and_then(|policies| policies.get(&code).copied());
+    match policy {
+        Some(Policy::Silence) => {}
+        Some(Policy::Error) => {
+            ERRORS.store(true, Ordering::SeqCst);
+            if let Ok(mut states) = WARNINGS.lock() {
+                if states.insert(message.to_string()) {
+                    eprintln!(
+                        "{}{} {}",
+                        format!("error[{code}]").red().bold(),
+                        ":".bold(),
+                        message.bold()
+                    );
+                }
+            }
+        }
+        None => {
+            if ENABLED.load(Ordering::SeqCst) {
+                if let Ok(mut states) = WARNINGS.lock() {
+                    if states.insert(message.to_string()) {
+                        eprintln!(
+                            "{}{} {}",
+                            format!("warning[{code}]").yellow().bold(),
+                            ":".bold(),
+                            message.bold()
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Warn a user with a categorized warning, which can be silenced or promoted to an error via its
+/// [`WarningCode`].
+#[macro_export]
+macro_rules! warn_user_code {
+    ($code:expr, $($arg:tt)*) => {
+        $crate::emit($code, &format!("{}", format_args!($($arg)*)))
+    };
+}
@@ -0,0 +1,254 @@
+use std::fmt::Write;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+
+use anstream::println;
+use anyhow::{Context, Result};
+use owo_colors::OwoColorize;
+use rustc_hash::FxHashMap;
+use serde::Serialize;
+use tracing::debug;
+
+use distribution_types::{IndexLocations, IndexUrl};
+use uv_cache::{Cache, CacheBucket, Refresh, Timestamp, WheelCache};
+use uv_client::{OwnedArchive, RegistryClient, RegistryClientBuilder};
+use uv_fs::Simplified;
+use uv_normalize::PackageName;
+
+use crate::commands::ExitStatus;
+use crate::printer::Printer;
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub(crate) enum AuditFormat {
+    Text,
+    Json,
+}
+
+/// The outcome of auditing a single cached wheel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+enum AuditStatus {
+    /// The cached digest matches the digest advertised by the index.
+    Verified,
+    /// The cached digest differs from the digest advertised by the index.
+    Mismatch,
+    /// The index no longer lists the cached file.
+    Missing,
+    /// The index lists the file, but does not advertise a SHA-256 digest for it.
+    Unverifiable,
+}
+
+#[derive(Debug, Serialize)]
+struct AuditEntry {
+    package: PackageName,
+    index: String,
+    filename: String,
+    status: AuditStatus,
+    cached_sha256: String,
+    index_sha256: Option<String>,
+}
+
+/// Re-check the digests of cached wheels against those currently advertised by the index.
+pub(crate) async fn cache_audit(
+    packages: &[PackageName],
+    index_locations: &IndexLocations,
+    output_format: AuditFormat,
+    cache: &Cache,
+    mut printer: Printer,
+) -> Result<ExitStatus> {
+    if !cache.root().exists() {
+        writeln!(
+            printer,
+            "No cache found at: {}",
+            cache.root().simplified_display().cyan()
+        )?;
+        return Ok(ExitStatus::Success);
+    }
+
+    // Always query the live index, rather than any cached index responses.
+    let cache = cache.clone().with_refresh(Refresh::All(Timestamp::now()));
+    let client = RegistryClientBuilder::new(cache.clone())
+        .index_urls(index_locations.index_urls())
+        .build();
+
+    let mut entries = Vec::new();
+    for index in index_locations.indexes() {
+        let root = cache
+            .bucket(CacheBucket::Wheels)
+            .join(WheelCache::Index(index).root());
+        for (package, digests) in cached_digests(&root, packages)? {
+            entries.extend(audit_package(&client, index, package, digests).await?);
+        }
+    }
+
+    let failed = entries
+        .iter()
+        .any(|entry| matches!(entry.status, AuditStatus::Mismatch));
+
+    match output_format {
+        AuditFormat::Text => {
+            for entry in &entries {
+                match entry.status {
+                    AuditStatus::Verified => {}
+                    AuditStatus::Mismatch => println!(
+                        "{}: {} (cached: {}, index: {})",
+                        "mismatch".red().bold(),
+                        entry.filename.bold(),
+                        entry.cached_sha256,
+                        entry.index_sha256.as_deref().unwrap_or_default()
+                    ),
+                    AuditStatus::Missing => println!(
+                        "{}: {} is no longer listed on {}",
+                        "missing".yellow().bold(),
+                        entry.filename.bold(),
+                        entry.index
+                    ),
+                    AuditStatus::Unverifiable => println!(
+                        "{}: {} has no SHA-256 digest on {}",
+                        "unverifiable".yellow().bold(),
+                        entry.filename.bold(),
+                        entry.index
+                    ),
+                }
+            }
+
+            let count = |status| {
+                entries
+                    .iter()
+                    .filter(|entry| entry.status == status)
+                    .count()
+            };
+            let s = if entries.len() == 1 { "" } else { "s" };
+            writeln!(
+                printer,
+                "{}",
+                format!(
+                    "Audited {} cached wheel{s}: {} verified, {} mismatched, {} missing, {} unverifiable",
+                    entries.len(),
+                    count(AuditStatus::Verified),
+                    count(AuditStatus::Mismatch),
+                    count(AuditStatus::Missing),
+                    count(AuditStatus::Unverifiable),
+                )
+                .dimmed()
+            )?;
+        }
+        AuditFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(&entries)?);
+        }
+    }
+
+    if failed {
+        Ok(ExitStatus::Failure)
+    } else {
+        Ok(ExitStatus::Success)
+    }
+}
+
+/// Read the recorded digests of the wheels cached under the given index directory, grouped by
+/// package.
+///
+/// Returns a list of `(package, {filename: digest})` pairs, sorted by package name.
+fn cached_digests(
+    root: &Path,
+    packages: &[PackageName],
+) -> Result<Vec<(PackageName, FxHashMap<String, String>)>> {
+    let read_dir = match fs_err::read_dir(root) {
+        Ok(read_dir) => read_dir,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err.into()),
+    };
+
+    let mut cached = Vec::new();
+    for entry in read_dir {
+        let entry = entry?;
+        if !entry.file_type()?.is_dir() {
+            continue;
+        }
+        let Some(package) = entry
+            .file_name()
+            .to_str()
+            .and_then(|name| PackageName::from_str(name).ok())
+        else {
+            continue;
+        };
+        if !packages.is_empty() && !packages.contains(&package) {
+            continue;
+        }
+
+        let mut digests = FxHashMap::default();
+        for file in fs_err::read_dir(entry.path())? {
+            let path = file?.path();
+            if path.extension().is_some_and(|ext| ext == "sha256") {
+                let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                    continue;
+                };
+                let digest = fs_err::read_to_string(&path)?;
+                digests.insert(format!("{stem}.whl"), digest.trim().to_string());
+            }
+        }
+        if !digests.is_empty() {
+            cached.push((package, digests));
+        }
+    }
+    cached.sort_by(|(a, _), (b, _)| a.cmp(b));
+    Ok(cached)
+}
+
+/// Compare the recorded digests for a package's cached wheels to those advertised by the index.
+async fn audit_package(
+    client: &RegistryClient,
+    index: &IndexUrl,
+    package: PackageName,
+    digests: FxHashMap<String, String>,
+) -> Result<Vec<AuditEntry>> {
+    // Collect the digests advertised by the index, if the package is still served by it.
+    let advertised = match client.simple(&package).await {
+        Ok((found, metadata)) if found == *index => {
+            let metadata = OwnedArchive::deserialize(&metadata);
+            metadata
+                .iter()
+                .flat_map(|datum| datum.files.wheels.iter())
+                .map(|wheel| {
+                    (
+                        wheel.file.filename.clone(),
+                        wheel.file.hashes.sha256.clone(),
+                    )
+                })
+                .collect::<FxHashMap<_, _>>()
+        }
+        Ok(_) => FxHashMap::default(),
+        Err(err) if matches!(err.kind(), uv_client::ErrorKind::PackageNotFound(_)) => {
+            debug!("Package `{package}` is no longer available on the index");
+            FxHashMap::default()
+        }
+        Err(err) => {
+            return Err(err).with_context(|| format!("Failed to query the index for `{package}`"))
+        }
+    };
+
+    let mut entries = digests
+        .into_iter()
+        .map(|(filename, cached_sha256)| {
+            let (status, index_sha256) = match advertised.get(&filename) {
+                None => (AuditStatus::Missing, None),
+                Some(None) => (AuditStatus::Unverifiable, None),
+                Some(Some(sha256)) if sha256.eq_ignore_ascii_case(&cached_sha256) => {
+                    (AuditStatus::Verified, Some(sha256.clone()))
+                }
+                Some(Some(sha256)) => (AuditStatus::Mismatch, Some(sha256.clone())),
+            };
+            AuditEntry {
+                package: package.clone(),
+                index: index.to_string(),
+                filename,
+                status,
+                cached_sha256,
+                index_sha256,
+            }
+        })
+        .collect::<Vec<_>>();
+    entries.sort_by(|a, b| a.filename.cmp(&b.filename));
+    Ok(entries)
+}
@@ -0,0 +1,626 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anstream::println;
+use anyhow::{Context, Result};
+use itertools::Itertools;
+use owo_colors::OwoColorize;
+use serde::Serialize;
+use url::Url;
+use walkdir::WalkDir;
+
+use distribution_filename::WheelFilename;
+use platform_host::{Os, Platform};
+use platform_tags::{IncompatibleTag, TagCompatibility, Tags};
+use uv_cache::Cache;
+use uv_fs::Simplified;
+use uv_interpreter::{find_all_pythons, PythonEnvironment, PythonPin};
+use uv_traits::Concurrency;
+
+use crate::commands::{format_bytes, ExitStatus};
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub(crate) enum DebugInfoFormat {
+    /// Display the report as human-readable text.
+    Text,
+    /// Display the report as a JSON object.
+    Json,
+}
+
+/// Environment variables that affect uv's behavior, in addition to those prefixed with `UV_`.
+const ENVIRONMENT_VARIABLES: &[&str] = &[
+    "VIRTUAL_ENV",
+    "CONDA_PREFIX",
+    "PYENV_ROOT",
+    "PYENV_VERSION",
+    "ASDF_DATA_DIR",
+    "XDG_CACHE_HOME",
+    "HTTP_PROXY",
+    "HTTPS_PROXY",
+    "ALL_PROXY",
+    "NO_PROXY",
+    "http_proxy",
+    "https_proxy",
+    "all_proxy",
+    "no_proxy",
+    "SSL_CERT_FILE",
+    "SSL_CERT_DIR",
+    "GIT_SSH",
+    "GIT_SSH_COMMAND",
+];
+
+/// A snapshot of the host environment, for inclusion in bug reports.
+#[derive(Debug, Serialize)]
+struct DebugInfo {
+    version: String,
+    platform: Option<PlatformInfo>,
+    /// The active virtual environment, if any.
+    virtualenv: Option<InterpreterInfo>,
+    /// The interpreters that uv can discover, in order of precedence.
+    interpreters: Vec<InterpreterInfo>,
+    /// The pinned Python request for the current directory, if any.
+    python_pin: Option<PythonPinInfo>,
+    /// The interpreter for which wheel compatibility is reported, along with its supported tags.
+    target: Option<TargetInfo>,
+    /// The compatibility of the requested wheel with the target interpreter, if any.
+    wheel: Option<WheelInfo>,
+    cache: CacheInfo,
+    /// The configuration files that were read, in order of precedence.
+    config_files: Vec<String>,
+    configuration: ConfigurationInfo,
+    /// The relevant environment variables, with any credentials redacted.
+    environment: BTreeMap<String, String>,
+    /// Any errors encountered while gathering the report.
+    errors: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct PlatformInfo {
+    os: String,
+    /// The detected C library and its version (e.g., `glibc 2.35`), on Linux.
+    libc: Option<String>,
+    arch: String,
+}
+
+#[derive(Debug, Serialize)]
+struct InterpreterInfo {
+    path: String,
+    version: String,
+    implementation: String,
+    source: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct TargetInfo {
+    #[serde(flatten)]
+    interpreter: InterpreterInfo,
+    /// The supported wheel tags, from the most to the least preferred.
+    tags: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct WheelInfo {
+    filename: String,
+    compatible: bool,
+    /// The most preferred of the supported tags that the wheel matches, if it's compatible.
+    tag: Option<String>,
+    /// The rank of the matching tag among the supported tags, starting from 1.
+    preference: Option<usize>,
+    /// The reason the wheel is incompatible, if it's incompatible.
+    reason: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct PythonPinInfo {
+    request: String,
+    path: String,
+}
+
+#[derive(Debug, Serialize)]
+struct CacheInfo {
+    root: String,
+    /// The size of each cache bucket, in bytes.
+    buckets: BTreeMap<String, u64>,
+    /// The total size of the cache, in bytes.
+    total: u64,
+}
+
+#[derive(Debug, Serialize)]
+struct ConfigurationInfo {
+    concurrent_downloads: usize,
+    concurrent_builds: usize,
+    concurrent_installs: usize,
+}
+
+/// Gather the uv version, platform, discovered interpreters, cache, configuration, and relevant
+/// environment variables into a single report.
+///
+/// If a wheel is provided, the report also explains whether the wheel is compatible with the
+/// target interpreter, and the command fails if it isn't.
+pub(crate) fn debug_info(
+    format: DebugInfoFormat,
+    wheel: Option<&str>,
+    python: Option<&str>,
+    system: bool,
+    concurrency: Concurrency,
+    config_files: &[PathBuf],
+    cache: &Cache,
+) -> Result<ExitStatus> {
+    // Accept either a bare wheel filename or a path to a wheel.
+    let wheel = wheel
+        .map(|wheel| {
+            let filename = Path::new(wheel)
+                .file_name()
+                .and_then(|filename| filename.to_str())
+                .unwrap_or(wheel);
+            WheelFilename::from_str(filename)
+                .with_context(|| format!("Failed to parse wheel filename: {wheel}"))
+        })
+        .transpose()?;
+
+    let mut errors = Vec::new();
+
+    // Errors are recorded in the report, rather than aborting, since the report is most useful
+    // precisely when something is misconfigured.
+    let platform = match Platform::current() {
+        Ok(platform) => Some(platform),
+        Err(err) => {
+            errors.push(format!("Failed to detect platform: {err}"));
+            None
+        }
+    };
+
+    let mut virtualenv = None;
+    let mut target = None;
+    let mut wheel_info = None;
+    let mut interpreters = Vec::new();
+    if let Some(platform) = &platform {
+        let venv = match PythonEnvironment::from_virtualenv(platform.clone(), cache) {
+            Ok(venv) => Some(venv),
+            Err(uv_interpreter::Error::VenvNotFound) => None,
+            Err(err) => {
+                errors.push(format!("Failed to query virtual environment: {err}"));
+                None
+            }
+        };
+        virtualenv = venv.as_ref().map(InterpreterInfo::from);
+
+        // Report compatibility for the requested interpreter, falling back to the active virtual
+        // environment, and then to the default Python.
+        let environment = if let Some(python) = python {
+            PythonEnvironment::from_requested_python(python, platform, cache)
+        } else if let Some(venv) = venv.filter(|_| !system) {
+            Ok(venv)
+        } else {
+            PythonEnvironment::from_default_python(platform, cache)
+        };
+        match environment {
+            Ok(environment) => match environment.interpreter().tags() {
+                Ok(tags) => {
+                    target = Some(TargetInfo {
+                        interpreter: InterpreterInfo::from(&environment),
+                        tags: tags
+                            .ranked()
+                            .into_iter()
+                            .map(|(python_tag, abi_tag, platform_tag)| {
+                                format!("{python_tag}-{abi_tag}-{platform_tag}")
+                            })
+                            .collect(),
+                    });
+                    if let Some(wheel) = &wheel {
+                        wheel_info = Some(explain(wheel, tags));
+                    }
+                }
+                Err(err) => errors.push(format!("Failed to compute supported tags: {err}")),
+            },
+            Err(err) => errors.push(format!("Failed to find target interpreter: {err}")),
+        }
+
+        match find_all_pythons(platform, cache) {
+            Ok(pythons) => {
+                interpreters = pythons
+                    .iter()
+                    .map(|(source, interpreter)| InterpreterInfo {
+                        path: interpreter
+                            .sys_executable()
+                            .simplified_display()
+                            .to_string(),
+                        version: interpreter.python_version().to_string(),
+                        implementation: interpreter.implementation_name().to_string(),
+                        source: Some(source.to_string()),
+                    })
+                    .collect();
+            }
+            Err(err) => errors.push(format!("Failed to discover Python interpreters: {err}")),
+        }
+    }
+
+    let python_pin = match std::env::current_dir().map(|cwd| PythonPin::find(&cwd)) {
+        Ok(Ok(pin)) => pin.map(|pin| PythonPinInfo {
+            request: pin.request,
+            path: pin.path.simplified_display().to_string(),
+        }),
+        Ok(Err(err)) => {
+            errors.push(format!("Failed to read Python pin: {err}"));
+            None
+        }
+        Err(err) => {
+            errors.push(format!("Failed to read current directory: {err}"));
+            None
+        }
+    };
+
+    let buckets = match cache_buckets(cache.root()) {
+        Ok(buckets) => buckets,
+        Err(err) => {
+            errors.push(format!("Failed to read cache: {err}"));
+            BTreeMap::new()
+        }
+    };
+
+    let info = DebugInfo {
+        version: crate::version::version().to_string(),
+        platform: platform.as_ref().map(PlatformInfo::from),
+        virtualenv,
+        interpreters,
+        python_pin,
+        target,
+        wheel: wheel_info,
+        cache: CacheInfo {
+            root: cache.root().simplified_display().to_string(),
+            total: buckets.values().sum(),
+            buckets,
+        },
+        config_files: config_files
+            .iter()
+            .map(|path| path.simplified_display().to_string())
+            .collect(),
+        configuration: ConfigurationInfo {
+            concurrent_downloads: concurrency.downloads,
+            concurrent_builds: concurrency.builds,
+            concurrent_installs: concurrency.installs,
+        },
+        environment: environment(),
+        errors,
+    };
+
+    match format {
+        DebugInfoFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(&info)?);
+        }
+        DebugInfoFormat::Text => print_text(&info),
+    }
+
+    // If the wheel's compatibility couldn't be determined, the error is included in the report.
+    match &info.wheel {
+        Some(wheel) if wheel.compatible => Ok(ExitStatus::Success),
+        Some(_) => Ok(ExitStatus::Failure),
+        None if wheel.is_some() => Ok(ExitStatus::Failure),
+        None => Ok(ExitStatus::Success),
+    }
+}
+
+impl From<&PythonEnvironment> for InterpreterInfo {
+    fn from(environment: &PythonEnvironment) -> Self {
+        let interpreter = environment.interpreter();
+        Self {
+            path: environment
+                .python_executable()
+                .simplified_display()
+                .to_string(),
+            version: interpreter.python_version().to_string(),
+            implementation: interpreter.implementation_name().to_string(),
+            source: None,
+        }
+    }
+}
+
+impl From<&Platform> for PlatformInfo {
+    fn from(platform: &Platform) -> Self {
+        let (os, libc) = match platform.os() {
+            Os::Manylinux { major, minor } => {
+                ("Linux".to_string(), Some(format!("glibc {major}.{minor}")))
+            }
+            Os::Musllinux { major, minor } => {
+                ("Linux".to_string(), Some(format!("musl {major}.{minor}")))
+            }
+            Os::Macos { major, minor } => (format!("macOS {major}.{minor}"), None),
+            Os::Windows => ("Windows".to_string(), None),
+            Os::FreeBsd { release }
+            | Os::NetBsd { release }
+            | Os::OpenBsd { release }
+            | Os::Dragonfly { release }
+            | Os::Illumos { release, .. }
+            | Os::Haiku { release } => (format!("{} {release}", platform.os()), None),
+        };
+        Self {
+            os,
+            libc,
+            arch: platform.arch().to_string(),
+        }
+    }
+}
+
+/// Compute the size of each top-level directory (i.e., bucket) in the cache.
+///
+/// Symlinks aren't followed, so unzipped wheels (which are linked into the archive bucket) are
+/// only counted once.
+fn cache_buckets(root: &Path) -> std::io::Result<BTreeMap<String, u64>> {
+    let mut buckets = BTreeMap::new();
+    if !root.exists() {
+        return Ok(buckets);
+    }
+    for entry in fs_err::read_dir(root)? {
+        let entry = entry?;
+        if !entry.file_type()?.is_dir() {
+            continue;
+        }
+        let size = WalkDir::new(entry.path())
+            .into_iter()
+            .filter_map(Result::ok)
+            .filter_map(|entry| entry.metadata().ok())
+            .filter(std::fs::Metadata::is_file)
+            .map(|metadata| metadata.len())
+            .sum();
+        buckets.insert(entry.file_name().to_string_lossy().to_string(), size);
+    }
+    Ok(buckets)
+}
+
+/// Collect the relevant environment variables, redacting any credentials.
+fn environment() -> BTreeMap<String, String> {
+    std::env::vars_os()
+        .filter_map(|(key, value)| {
+            let key = key.into_string().ok()?;
+            if !(key.starts_with("UV_") || ENVIRONMENT_VARIABLES.contains(&key.as_str())) {
+                return None;
+            }
+            let value = value.to_string_lossy();
+            let value = if is_secret(&key) {
+                "****".to_string()
+            } else {
+                // Values may contain multiple, space-separated URLs (e.g., `UV_EXTRA_INDEX_URL`).
+                value
+                    .split(' ')
+                    .map(redact_url)
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            };
+            Some((key, value))
+        })
+        .collect()
+}
+
+/// Returns `true` if the environment variable is likely to contain a secret in its entirety.
+fn is_secret(key: &str) -> bool {
+    let key = key.to_ascii_uppercase();
+    ["TOKEN", "PASSWORD", "SECRET", "CREDENTIAL"]
+        .iter()
+        .any(|marker| key.contains(marker))
+}
+
+/// Redact the username and password from a URL, if the value is a URL with credentials.
+fn redact_url(value: &str) -> String {
+    match Url::parse(value) {
+        Ok(mut url) if !url.username().is_empty() || url.password().is_some() => {
+            let _ = url.set_username("****");
+            let _ = url.set_password(None);
+            url.to_string()
+        }
+        _ => value.to_string(),
+    }
+}
+
+/// Explain whether the given wheel can be installed with the given tags.
+fn explain(filename: &WheelFilename, tags: &Tags) -> WheelInfo {
+    match filename.compatibility(tags) {
+        TagCompatibility::Compatible(_) => {
+            // Identify the most preferred of the wheel's tags, which is the one that's used to
+            // choose between multiple compatible wheels.
+            let (rank, (python_tag, abi_tag, platform_tag)) = tags
+                .ranked()
+                .into_iter()
+                .find_position(|&(python_tag, abi_tag, platform_tag)| {
+                    filename.python_tag.iter().any(|tag| tag == python_tag)
+                        && filename.abi_tag.iter().any(|tag| tag == abi_tag)
+                        && filename.platform_tag.iter().any(|tag| tag == platform_tag)
+                })
+                .expect("a compatible wheel matches at least one tag");
+            WheelInfo {
+                filename: filename.to_string(),
+                compatible: true,
+                tag: Some(format!("{python_tag}-{abi_tag}-{platform_tag}")),
+                preference: Some(rank + 1),
+                reason: None,
+            }
+        }
+        TagCompatibility::Incompatible(reason) => {
+            let reason = match reason {
+                IncompatibleTag::Invalid => "the wheel has no valid tags".to_string(),
+                IncompatibleTag::Python => format!(
+                    "none of the wheel's Python tags are supported (expected one of: {})",
+                    supported(tags, |(python_tag, ..)| Some(python_tag))
+                ),
+                IncompatibleTag::Abi => format!(
+                    "none of the wheel's ABI tags are supported for its Python tags (expected one of: {})",
+                    supported(tags, |(python_tag, abi_tag, _)| {
+                        filename
+                            .python_tag
+                            .iter()
+                            .any(|tag| tag == python_tag)
+                            .then_some(abi_tag)
+                    })
+                ),
+                IncompatibleTag::Platform => format!(
+                    "none of the wheel's platform tags are supported for its Python and ABI tags (expected one of: {})",
+                    supported(tags, |(python_tag, abi_tag, platform_tag)| {
+                        (filename.python_tag.iter().any(|tag| tag == python_tag)
+                            && filename.abi_tag.iter().any(|tag| tag == abi_tag))
+                        .then_some(platform_tag)
+                    })
+                ),
+                IncompatibleTag::Libc { required, detected } => format!(
+                    "the wheel requires {required} or later, but the current platform has {detected}"
+                ),
+            };
+            WheelInfo {
+                filename: filename.to_string(),
+                compatible: false,
+                tag: None,
+                preference: None,
+                reason: Some(reason),
+            }
+        }
+    }
+}
+
+/// Summarize the distinct values of a component of the supported tags, from the most to the least
+/// preferred.
+fn supported<'a>(
+    tags: &'a Tags,
+    component: impl Fn((&'a str, &'a str, &'a str)) -> Option<&'a str>,
+) -> String {
+    /// The maximum number of values to display.
+    const LIMIT: usize = 10;
+
+    let values = tags
+        .ranked()
+        .into_iter()
+        .filter_map(component)
+        .unique()
+        .collect::<Vec<_>>();
+    if values.len() > LIMIT {
+        format!(
+            "{}, and {} more",
+            values[..LIMIT].join(", "),
+            values.len() - LIMIT
+        )
+    } else {
+        values.join(", ")
+    }
+}
+
+fn print_text(info: &DebugInfo) {
+    println!("{} {}", "uv:".bold(), info.version);
+
+    if let Some(platform) = &info.platform {
+        match &platform.libc {
+            Some(libc) => println!(
+                "{} {} ({libc}) {}",
+                "Platform:".bold(),
+                platform.os,
+                platform.arch
+            ),
+            None => println!("{} {} {}", "Platform:".bold(), platform.os, platform.arch),
+        }
+    }
+
+    match &info.virtualenv {
+        Some(venv) => println!(
+            "{} {} {} at {}",
+            "Virtual environment:".bold(),
+            venv.implementation,
+            venv.version,
+            venv.path.cyan()
+        ),
+        None => println!("{} none", "Virtual environment:".bold()),
+    }
+
+    match &info.python_pin {
+        Some(pin) => println!(
+            "{} {} (from {})",
+            "Python pin:".bold(),
+            pin.request,
+            pin.path.cyan()
+        ),
+        None => println!("{} none", "Python pin:".bold()),
+    }
+
+    println!("{} {}", "Interpreters:".bold(), info.interpreters.len());
+    for interpreter in &info.interpreters {
+        println!(
+            "  {}-{}  {}  {}",
+            interpreter.implementation,
+            interpreter.version,
+            interpreter.path,
+            format!("({})", interpreter.source.as_deref().unwrap_or_default()).dimmed()
+        );
+    }
+
+    if let Some(target) = &info.target {
+        println!(
+            "{} {} {} at {}",
+            "Target interpreter:".bold(),
+            target.interpreter.implementation,
+            target.interpreter.version,
+            target.interpreter.path.cyan()
+        );
+        // When explaining a wheel, the full list of tags is available in the JSON report.
+        if info.wheel.is_none() {
+            println!("{} {}", "Compatible tags:".bold(), target.tags.len());
+            for tag in &target.tags {
+                println!("  {tag}");
+            }
+        }
+    }
+
+    if let Some(wheel) = &info.wheel {
+        println!("{} {}", "Wheel:".bold(), wheel.filename);
+        if let (Some(tag), Some(preference)) = (&wheel.tag, wheel.preference) {
+            println!(
+                "  {} matches `{tag}` (preference {preference} of {})",
+                "Compatible:".green().bold(),
+                info.target.as_ref().map_or(0, |target| target.tags.len())
+            );
+        }
+        if let Some(reason) = &wheel.reason {
+            println!("  {} {reason}", "Incompatible:".red().bold());
+        }
+    }
+
+    println!(
+        "{} {} ({})",
+        "Cache:".bold(),
+        info.cache.root.cyan(),
+        format_bytes(info.cache.total)
+    );
+    for (bucket, size) in &info.cache.buckets {
+        println!("  {bucket}: {}", format_bytes(*size));
+    }
+
+    println!("{}", "Configuration:".bold());
+    if info.config_files.is_empty() {
+        println!("  files: {}", "none".dimmed());
+    }
+    for path in &info.config_files {
+        println!("  file: {}", path.cyan());
+    }
+    println!(
+        "  concurrent-downloads: {}",
+        info.configuration.concurrent_downloads
+    );
+    println!(
+        "  concurrent-builds: {}",
+        info.configuration.concurrent_builds
+    );
+    println!(
+        "  concurrent-installs: {}",
+        info.configuration.concurrent_installs
+    );
+
+    println!("{}", "Environment:".bold());
+    if info.environment.is_empty() {
+        println!("  {}", "none".dimmed());
+    }
+    for (key, value) in &info.environment {
+        println!("  {key}={value}");
+    }
+
+    if !info.errors.is_empty() {
+        println!("{}", "Errors:".bold());
+        for error in &info.errors {
+            println!("  {}", error.red());
+        }
+    }
+}
@@ -1,27 +1,57 @@
 use std::process::ExitCode;
 use std::time::Duration;
 
+pub(crate) use cache_audit::{cache_audit, AuditFormat};
 pub(crate) use cache_clean::cache_clean;
 pub(crate) use cache_dir::cache_dir;
-use distribution_types::InstalledMetadata;
+pub(crate) use debug_info::{debug_info, DebugInfoFormat};
+use distribution_types::{InstalledMetadata, Name};
+pub(crate) use pip_audit::pip_audit;
+pub(crate) use pip_autoremove::pip_autoremove;
+pub(crate) use pip_check::pip_check;
 pub(crate) use pip_compile::{extra_name_with_clap_error, pip_compile, Upgrade};
+pub(crate) use pip_download::pip_download;
 pub(crate) use pip_freeze::pip_freeze;
-pub(crate) use pip_install::pip_install;
-pub(crate) use pip_list::pip_list;
+pub(crate) use pip_install::{install_into, pip_install, UpgradeStrategy};
+pub(crate) use pip_licenses::{pip_licenses, LicensesFormat};
+pub(crate) use pip_list::{pip_list, ListFormat, PackagePattern};
+pub(crate) use pip_sbom::{pip_sbom, SbomFormat};
+pub(crate) use pip_startup::{
+    pip_startup_add, pip_startup_list, pip_startup_remove, pip_startup_toggle,
+};
 pub(crate) use pip_sync::pip_sync;
 pub(crate) use pip_uninstall::pip_uninstall;
-pub(crate) use venv::venv;
+pub(crate) use python_list::{python_list, PythonListFormat};
+pub(crate) use python_pin::python_pin;
+pub(crate) use report_diff::{report_diff, ReportDiffFormat};
+pub(crate) use run::run;
+use uv_installer::DownloadError;
+use uv_resolver::Provenance;
+pub(crate) use venv::{find_or_create_venv, venv};
 pub(crate) use version::version;
 
+mod cache_audit;
 mod cache_clean;
 mod cache_dir;
+mod debug_info;
+mod pip_audit;
+mod pip_autoremove;
+mod pip_check;
 mod pip_compile;
+mod pip_download;
 mod pip_freeze;
 mod pip_install;
+mod pip_licenses;
 mod pip_list;
+mod pip_sbom;
+mod pip_startup;
 mod pip_sync;
 mod pip_uninstall;
+mod python_list;
+mod python_pin;
+mod report_diff;
 mod reporters;
+mod run;
 mod venv;
 mod version;
 
@@ -38,6 +68,9 @@ pub(crate) enum ExitStatus {
     /// The command failed with an unexpected error.
     #[allow(unused)]
     Error,
+
+    /// The command's exit status is propagated from an external command.
+    External(u8),
 }
 
 impl From<ExitStatus> for ExitCode {
@@ -46,10 +79,24 @@ impl From<ExitStatus> for ExitCode {
             ExitStatus::Success => Self::from(0),
             ExitStatus::Failure => Self::from(1),
             ExitStatus::Error => Self::from(2),
+            ExitStatus::External(code) => Self::from(code),
         }
     }
 }
 
+/// Attribute a failure to download a distribution to the requirement that requested it, if the
+/// requirement was read from a file.
+pub(super) fn download_error(err: DownloadError, provenance: &[Provenance]) -> anyhow::Error {
+    let declared = err
+        .dist()
+        .and_then(|dist| Provenance::find_in_file(provenance, dist.name()));
+    match declared {
+        Some(declared) => anyhow::Error::new(err)
+            .context(format!("{}: `{}`", declared.origin, declared.requirement)),
+        None => anyhow::Error::new(err),
+    }
+}
+
 /// Format a duration as a human-readable string, Cargo-style.
 pub(super) fn elapsed(duration: Duration) -> String {
     let secs = duration.as_secs();
@@ -63,6 +110,32 @@ pub(super) fn elapsed(duration: Duration) -> String {
     }
 }
 
+/// Formats a number of bytes into a human readable SI-prefixed size.
+///
+/// Returns a tuple of `(quantity, units)`.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_possible_wrap,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+pub(super) fn human_readable_bytes(bytes: u64) -> (f32, &'static str) {
+    static UNITS: [&str; 7] = ["B", "KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
+    let bytes = bytes as f32;
+    let i = ((bytes.log2() / 10.0) as usize).min(UNITS.len() - 1);
+    (bytes / 1024_f32.powi(i as i32), UNITS[i])
+}
+
+/// Format a number of bytes for display, e.g., `512B` or `1.5MiB`.
+pub(super) fn format_bytes(bytes: u64) -> String {
+    if bytes < 1024 {
+        format!("{bytes}B")
+    } else {
+        let (bytes, unit) = human_readable_bytes(bytes);
+        format!("{bytes:.1}{unit}")
+    }
+}
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
 pub(super) enum ChangeEventKind {
     /// The package was removed from the environment.
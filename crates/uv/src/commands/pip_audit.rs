@@ -0,0 +1,183 @@
+use std::fmt::Write;
+
+use anyhow::Result;
+use itertools::Itertools;
+use owo_colors::OwoColorize;
+use tracing::debug;
+
+use distribution_types::Name;
+use pep440_rs::{Operator, Version};
+use pep508_rs::VersionOrUrl;
+use platform_host::Platform;
+use uv_cache::Cache;
+use uv_client::{Connectivity, OsvClient, RegistryClientBuilder};
+use uv_fs::Simplified;
+use uv_installer::SitePackages;
+use uv_interpreter::PythonEnvironment;
+use uv_normalize::PackageName;
+use uv_warnings::warn_user;
+
+use crate::commands::{elapsed, ExitStatus};
+use crate::printer::Printer;
+use crate::requirements::{RequirementsSource, RequirementsSpecification};
+
+/// Audit a set of packages for known vulnerabilities, using the OSV database.
+///
+/// If requirements files are provided (e.g., the output of `uv pip compile`), their pinned
+/// versions are audited; otherwise, the packages installed in the current environment are.
+pub(crate) async fn pip_audit(
+    sources: &[RequirementsSource],
+    strict: bool,
+    connectivity: Connectivity,
+    python: Option<&str>,
+    system: bool,
+    cache: Cache,
+    mut printer: Printer,
+) -> Result<ExitStatus> {
+    let start = std::time::Instant::now();
+
+    let packages = if sources.is_empty() {
+        // Detect the current Python interpreter.
+        let platform = Platform::current()?;
+        let venv = if let Some(python) = python {
+            PythonEnvironment::from_requested_python(python, &platform, &cache)?
+        } else if system {
+            PythonEnvironment::from_default_python(&platform, &cache)?
+        } else {
+            match PythonEnvironment::from_virtualenv(platform.clone(), &cache) {
+                Ok(venv) => venv,
+                Err(uv_interpreter::Error::VenvNotFound) => {
+                    PythonEnvironment::from_default_python(&platform, &cache)?
+                }
+                Err(err) => return Err(err.into()),
+            }
+        };
+
+        debug!(
+            "Using Python {} environment at {}",
+            venv.interpreter().python_version(),
+            venv.python_executable().simplified_display().cyan()
+        );
+
+        // Audit every installed package, aside from those installed from local directories,
+        // which aren't published.
+        let site_packages = SitePackages::from_executable(&venv)?;
+        site_packages
+            .iter()
+            .filter(|dist| !dist.is_editable())
+            .map(|dist| (dist.name().clone(), dist.version().clone()))
+            .collect::<Vec<_>>()
+    } else {
+        // Audit the pinned versions from the requirements files.
+        let spec = RequirementsSpecification::from_simple_sources(sources)?;
+        let mut packages = Vec::with_capacity(spec.requirements.len());
+        for requirement in spec.requirements {
+            let pinned = match &requirement.version_or_url {
+                Some(VersionOrUrl::VersionSpecifier(specifiers)) => specifiers
+                    .iter()
+                    .find(|specifier| {
+                        matches!(specifier.operator(), Operator::Equal | Operator::ExactEqual)
+                    })
+                    .map(|specifier| specifier.version().clone()),
+                _ => None,
+            };
+            if let Some(version) = pinned {
+                packages.push((requirement.name, version));
+            } else {
+                warn_user!(
+                    "Skipping `{requirement}`, since only pinned (`==`) requirements can be audited"
+                );
+            }
+        }
+        packages
+    };
+    let packages = packages
+        .into_iter()
+        .unique()
+        .sorted_unstable()
+        .collect::<Vec<(PackageName, Version)>>();
+
+    // Query the vulnerability database.
+    let client = RegistryClientBuilder::new(cache.clone())
+        .connectivity(connectivity)
+        .build();
+    let osv = OsvClient::new(&client);
+    let vulnerable = osv
+        .query_all(packages.iter().map(|(name, version)| (name, version)))
+        .await?;
+
+    let s = if packages.len() == 1 { "" } else { "s" };
+    writeln!(
+        printer,
+        "{}",
+        format!(
+            "Audited {} in {}",
+            format!("{} package{s}", packages.len()).bold(),
+            elapsed(start.elapsed())
+        )
+        .dimmed()
+    )?;
+
+    if vulnerable.is_empty() {
+        writeln!(printer, "No known vulnerabilities found.")?;
+        return Ok(ExitStatus::Success);
+    }
+
+    let num_vulnerabilities = vulnerable
+        .iter()
+        .map(|(.., vulnerabilities)| vulnerabilities.len())
+        .sum::<usize>();
+    writeln!(
+        printer,
+        "Found {} in {}:",
+        format!(
+            "{num_vulnerabilities} known vulnerabilit{}",
+            if num_vulnerabilities == 1 { "y" } else { "ies" }
+        )
+        .bold(),
+        format!(
+            "{} package{}",
+            vulnerable.len(),
+            if vulnerable.len() == 1 { "" } else { "s" }
+        )
+        .bold(),
+    )?;
+    for (name, version, vulnerabilities) in &vulnerable {
+        writeln!(
+            printer,
+            " {} {}{}",
+            "-".red(),
+            name.as_ref().bold(),
+            format!("=={version}").dimmed()
+        )?;
+        for vulnerability in vulnerabilities {
+            let id = match vulnerability.cve() {
+                Some(cve) if cve != vulnerability.id => format!("{} ({cve})", vulnerability.id),
+                _ => vulnerability.id.clone(),
+            };
+            let summary = vulnerability
+                .summary
+                .as_deref()
+                .map(|summary| format!(": {summary}"))
+                .unwrap_or_default();
+
+            // Suggest the earliest fixed version that's newer than the audited version.
+            let fix = vulnerability
+                .fixed_versions(name)
+                .into_iter()
+                .find(|fixed| fixed > *version)
+                .map_or_else(
+                    || " (no fix available)".dimmed().to_string(),
+                    |fixed| format!(" (fixed in {})", fixed.to_string().green()),
+                );
+
+            writeln!(printer, "     {}{summary}{fix}", id.yellow())?;
+        }
+    }
+
+    if strict {
+        Ok(ExitStatus::Failure)
+    } else {
+        Ok(ExitStatus::Success)
+    }
+}
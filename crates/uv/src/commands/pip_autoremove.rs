@@ -0,0 +1,135 @@
+use std::fmt::Write;
+
+use anyhow::Result;
+use itertools::Itertools;
+use owo_colors::OwoColorize;
+use tracing::debug;
+
+use distribution_types::{InstalledMetadata, Name};
+use platform_host::Platform;
+use uv_cache::Cache;
+use uv_fs::Simplified;
+use uv_installer::SitePackages;
+use uv_interpreter::PythonEnvironment;
+
+use crate::commands::{elapsed, ExitStatus};
+use crate::printer::Printer;
+
+/// Remove any packages that were installed as dependencies, but are no longer required by any
+/// requested package.
+pub(crate) async fn pip_autoremove(
+    python: Option<String>,
+    system: bool,
+    dry_run: bool,
+    cache: Cache,
+    mut printer: Printer,
+) -> Result<ExitStatus> {
+    let start = std::time::Instant::now();
+
+    // Detect the current Python interpreter.
+    let platform = Platform::current()?;
+    let venv = if let Some(python) = python.as_ref() {
+        PythonEnvironment::from_requested_python(python, &platform, &cache)?
+    } else if system {
+        PythonEnvironment::from_default_python(&platform, &cache)?
+    } else {
+        PythonEnvironment::from_virtualenv(platform, &cache)?
+    };
+    debug!(
+        "Using Python {} environment at {}",
+        venv.interpreter().python_version(),
+        venv.python_executable().simplified_display().cyan(),
+    );
+
+    // If the environment is externally managed, abort.
+    if let Some(externally_managed) = venv.interpreter().is_externally_managed() {
+        return if let Some(error) = externally_managed.into_error() {
+            Err(anyhow::anyhow!(
+                "The interpreter at {} is externally managed, and indicates the following:\n\n{}\n\nConsider creating a virtual environment with `uv venv`.",
+                venv.root().simplified_display().cyan(),
+                textwrap::indent(&error, "  ").green(),
+            ))
+        } else {
+            Err(anyhow::anyhow!(
+                "The interpreter at {} is externally managed. Instead, create a virtual environment with `uv venv`.",
+                venv.root().simplified_display().cyan()
+            ))
+        };
+    }
+
+    let _lock = venv.lock()?;
+
+    // Identify the packages that are no longer required by any requested package.
+    let site_packages = SitePackages::from_executable(&venv)?;
+    let orphans = site_packages
+        .orphans()
+        .into_iter()
+        .sorted_unstable_by(|a, b| a.name().cmp(b.name()).then(a.version().cmp(b.version())))
+        .collect::<Vec<_>>();
+
+    if orphans.is_empty() {
+        writeln!(printer, "No unused packages found.")?;
+        return Ok(ExitStatus::Success);
+    }
+
+    let s = if orphans.len() == 1 { "" } else { "s" };
+
+    // If we're performing a dry run, list the packages that would be removed, and exit.
+    if dry_run {
+        writeln!(
+            printer,
+            "{}",
+            format!(
+                "Would uninstall {}",
+                format!("{} package{s}", orphans.len()).bold(),
+            )
+            .dimmed()
+        )?;
+        for distribution in orphans {
+            writeln!(
+                printer,
+                " {} {}{}",
+                "-".red(),
+                distribution.name().as_ref().bold(),
+                distribution.installed_version().to_string().dimmed()
+            )?;
+        }
+        return Ok(ExitStatus::Success);
+    }
+
+    // Uninstall each package.
+    for distribution in &orphans {
+        let summary = uv_installer::uninstall(distribution, false).await?;
+        debug!(
+            "Uninstalled {} ({} file{}, {} director{})",
+            distribution.name(),
+            summary.file_count,
+            if summary.file_count == 1 { "" } else { "s" },
+            summary.dir_count,
+            if summary.dir_count == 1 { "y" } else { "ies" },
+        );
+    }
+
+    writeln!(
+        printer,
+        "{}",
+        format!(
+            "Uninstalled {} in {}",
+            format!("{} package{s}", orphans.len()).bold(),
+            elapsed(start.elapsed())
+        )
+        .dimmed()
+    )?;
+
+    for distribution in orphans {
+        writeln!(
+            printer,
+            " {} {}{}",
+            "-".red(),
+            distribution.name().as_ref().bold(),
+            distribution.installed_version().to_string().dimmed()
+        )?;
+    }
+
+    Ok(ExitStatus::Success)
+}
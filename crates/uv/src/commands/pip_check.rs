@@ -0,0 +1,224 @@
+use std::fmt::Write;
+
+use anyhow::Result;
+use itertools::Itertools;
+use owo_colors::OwoColorize;
+use tracing::debug;
+
+use distribution_types::{IndexLocations, InstalledDist, InstalledRegistryDist, Name};
+use platform_host::Platform;
+use platform_tags::Tags;
+use uv_cache::Cache;
+use uv_client::{Connectivity, ErrorKind, OwnedArchive, RegistryClient, RegistryClientBuilder};
+use uv_fs::Simplified;
+use uv_installer::{Diagnostic, SitePackages};
+use uv_interpreter::{PythonEnvironment, PythonVersion};
+
+use crate::commands::{elapsed, ExitStatus};
+use crate::printer::Printer;
+
+/// Check that the installed packages have compatible dependencies, or, if a target Python version
+/// is provided, that they would remain installable under that version.
+pub(crate) async fn pip_check(
+    python_version: Option<PythonVersion>,
+    index_locations: IndexLocations,
+    connectivity: Connectivity,
+    python: Option<&str>,
+    system: bool,
+    cache: Cache,
+    mut printer: Printer,
+) -> Result<ExitStatus> {
+    let start = std::time::Instant::now();
+
+    // Detect the current Python interpreter.
+    let platform = Platform::current()?;
+    let venv = if let Some(python) = python {
+        PythonEnvironment::from_requested_python(python, &platform, &cache)?
+    } else if system {
+        PythonEnvironment::from_default_python(&platform, &cache)?
+    } else {
+        match PythonEnvironment::from_virtualenv(platform.clone(), &cache) {
+            Ok(venv) => venv,
+            Err(uv_interpreter::Error::VenvNotFound) => {
+                PythonEnvironment::from_default_python(&platform, &cache)?
+            }
+            Err(err) => return Err(err.into()),
+        }
+    };
+
+    debug!(
+        "Using Python {} environment at {}",
+        venv.interpreter().python_version(),
+        venv.python_executable().simplified_display().cyan()
+    );
+
+    // Build the installed index.
+    let site_packages = SitePackages::from_executable(&venv)?;
+    let num_packages = site_packages.iter().count();
+
+    let Some(python_version) = python_version else {
+        // Validate the environment as-is.
+        let diagnostics = site_packages.diagnostics()?;
+        write_checked(num_packages, start, printer)?;
+
+        if diagnostics.is_empty() {
+            writeln!(printer, "No broken requirements found.")?;
+            return Ok(ExitStatus::Success);
+        }
+
+        let s = if diagnostics.len() == 1 { "y" } else { "ies" };
+        writeln!(
+            printer,
+            "Found {} incompatibilit{s}:",
+            diagnostics.len().to_string().bold()
+        )?;
+        for diagnostic in &diagnostics {
+            writeln!(printer, " {} {}", "-".red(), diagnostic.message())?;
+        }
+        return Ok(ExitStatus::Failure);
+    };
+
+    // Validate the environment against the markers of the target Python version.
+    let interpreter = venv.interpreter();
+    let markers = python_version.clone().markers(interpreter.markers());
+    let mut blockers = site_packages
+        .diagnostics_for(python_version.version(), &markers)?
+        .into_iter()
+        .map(|diagnostic| {
+            let message = match &diagnostic {
+                Diagnostic::IncompatiblePythonVersion {
+                    package,
+                    version,
+                    requires_python,
+                } => format!(
+                    "The package `{package}` requires Python {requires_python}, which excludes `{version}`."
+                ),
+                Diagnostic::MissingDependency {
+                    package,
+                    requirement,
+                } => format!(
+                    "The package `{package}` would require `{requirement}`, but it's not installed."
+                ),
+                _ => diagnostic.message(),
+            };
+            (diagnostic.package().clone(), message)
+        })
+        .collect::<Vec<_>>();
+
+    // Verify that each installed package could be reinstalled from the index under the target
+    // Python version.
+    if index_locations.indexes().next().is_none() {
+        debug!("Skipping wheel availability check due to `--no-index`");
+    } else {
+        let tags = Tags::from_env(
+            interpreter.platform(),
+            (python_version.major(), python_version.minor()),
+            interpreter.implementation_name(),
+            interpreter.implementation_tuple(),
+        )?;
+        let client = RegistryClientBuilder::new(cache.clone())
+            .index_urls(index_locations.index_urls())
+            .connectivity(connectivity)
+            .build();
+        for dist in site_packages.iter() {
+            let InstalledDist::Registry(dist) = dist else {
+                continue;
+            };
+            if let Some(message) = check_availability(&client, &tags, dist, &python_version).await?
+            {
+                blockers.push((dist.name().clone(), message));
+            }
+        }
+    }
+
+    write_checked(num_packages, start, printer)?;
+
+    if blockers.is_empty() {
+        writeln!(
+            printer,
+            "All installed packages are compatible with Python {python_version}."
+        )?;
+        return Ok(ExitStatus::Success);
+    }
+
+    let num_packages = blockers.iter().map(|(package, _)| package).unique().count();
+    let s = if num_packages == 1 { "" } else { "s" };
+    writeln!(
+        printer,
+        "Found {} that would block a switch to Python {python_version}:",
+        format!("{num_packages} package{s}").bold(),
+    )?;
+    for (_, message) in blockers.iter().sorted_by(|(a, _), (b, _)| a.cmp(b)) {
+        writeln!(printer, " {} {message}", "-".red())?;
+    }
+
+    Ok(ExitStatus::Failure)
+}
+
+/// Determine whether the installed version of a package has a wheel compatible with the given
+/// tags, or a source distribution, on the index. Returns a message describing the blocker if not.
+async fn check_availability(
+    client: &RegistryClient,
+    tags: &Tags,
+    dist: &InstalledRegistryDist,
+    python_version: &PythonVersion,
+) -> Result<Option<String>> {
+    let metadata = match client.simple(dist.name()).await {
+        Ok((_, metadata)) => OwnedArchive::deserialize(&metadata),
+        Err(err) => match err.kind() {
+            ErrorKind::PackageNotFound(_) | ErrorKind::NoIndex(_) | ErrorKind::Offline(_) => {
+                debug!("Skipping wheel availability check for {dist}: {err}");
+                return Ok(None);
+            }
+            _ => return Err(err.into()),
+        },
+    };
+
+    let Some(datum) = metadata
+        .into_iter()
+        .find(|datum| datum.version == dist.version)
+    else {
+        debug!("Skipping wheel availability check for {dist}: version not found on the index");
+        return Ok(None);
+    };
+
+    if datum
+        .files
+        .wheels
+        .iter()
+        .any(|wheel| wheel.name.is_compatible(tags))
+    {
+        return Ok(None);
+    }
+
+    if !datum.files.source_dists.is_empty() {
+        debug!("No compatible wheels for {dist}, but a source distribution is available");
+        return Ok(None);
+    }
+
+    Ok(Some(format!(
+        "The package `{}=={}` has no wheels compatible with Python {python_version}, and no source distribution.",
+        dist.name(),
+        dist.version,
+    )))
+}
+
+/// Report the number of packages that were checked.
+fn write_checked(
+    num_packages: usize,
+    start: std::time::Instant,
+    mut printer: Printer,
+) -> Result<()> {
+    let s = if num_packages == 1 { "" } else { "s" };
+    writeln!(
+        printer,
+        "{}",
+        format!(
+            "Checked {} in {}",
+            format!("{num_packages} package{s}").bold(),
+            elapsed(start.elapsed())
+        )
+        .dimmed()
+    )?;
+    Ok(())
+}
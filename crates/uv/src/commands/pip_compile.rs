@@ -5,39 +5,50 @@ use std::io::stdout;
 use std::ops::Deref;
 use std::path::Path;
 use std::str::FromStr;
+use std::time::Duration;
 
 use anstream::{eprint, AutoStream, StripStream};
 use anyhow::{anyhow, Context, Result};
 use chrono::{DateTime, Utc};
+use clap::ValueEnum;
 use itertools::Itertools;
 use owo_colors::OwoColorize;
 use rustc_hash::FxHashSet;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use tempfile::tempdir_in;
 use tracing::debug;
 
-use distribution_types::{IndexLocations, LocalEditable, Verbatim};
-use pep508_rs::Requirement;
+use distribution_types::{
+    BuiltDist, Dist, IndexLocations, LocalEditable, Name, SourceDist, Verbatim,
+};
+use pep508_rs::{MarkerEnvironment, Requirement, VersionOrUrl};
 use platform_host::Platform;
 use platform_tags::Tags;
+use pypi_types::Hashes;
 use requirements_txt::EditableRequirement;
-use uv_cache::Cache;
-use uv_client::{Connectivity, FlatIndex, FlatIndexClient, RegistryClientBuilder};
+use uv_cache::{Cache, CacheBucket, CacheEntry};
+use uv_client::{Connectivity, FlatIndex, FlatIndexClient, RegistryClient, RegistryClientBuilder};
 use uv_dispatch::BuildDispatch;
+use uv_distribution::DistributionDatabase;
 use uv_fs::Simplified;
 use uv_installer::{Downloader, NoBinary};
-use uv_interpreter::{Interpreter, PythonVersion};
+use uv_interpreter::{Interpreter, PythonEnvironment, PythonVersion, TargetTriple};
 use uv_normalize::{ExtraName, PackageName};
 use uv_resolver::{
-    AnnotationStyle, DependencyMode, DisplayResolutionGraph, InMemoryIndex, Manifest,
-    OptionsBuilder, PreReleaseMode, ResolutionMode, Resolver,
+    AnnotationStyle, DependencyMode, Diagnostic, DisplayResolutionGraph, InMemoryIndex, Manifest,
+    MinReleaseAge, OptionsBuilder, PreReleaseMode, ResolutionGraph, ResolutionMode, Resolver,
 };
-use uv_traits::{ConfigSettings, InFlight, NoBuild, SetupPyStrategy};
-use uv_warnings::warn_user;
+use uv_traits::{BuildIsolation, Concurrency, ConfigSettings, InFlight, NoBuild, SetupPyStrategy};
+use uv_warnings::{warn_user, warn_user_code, WarningCode};
 
 use crate::commands::reporters::{DownloadReporter, ResolverReporter};
 use crate::commands::{elapsed, ExitStatus};
 use crate::printer::Printer;
-use crate::requirements::{ExtrasSpecification, RequirementsSource, RequirementsSpecification};
+use crate::requirements::{
+    check_conflicting_pins, name_unnamed_requirements, requirements_from_metadata,
+    ExtrasSpecification, RequirementsSource, RequirementsSpecification,
+};
 
 /// Resolve a set of requirements into a set of pinned versions.
 #[allow(clippy::too_many_arguments, clippy::fn_params_excessive_bools)]
@@ -47,6 +58,7 @@ pub(crate) async fn pip_compile(
     overrides: &[RequirementsSource],
     extras: ExtrasSpecification<'_>,
     output_file: Option<&Path>,
+    check: bool,
     resolution_mode: ResolutionMode,
     prerelease_mode: PreReleaseMode,
     dependency_mode: DependencyMode,
@@ -55,67 +67,95 @@ pub(crate) async fn pip_compile(
     no_emit_packages: Vec<PackageName>,
     include_annotations: bool,
     include_header: bool,
+    custom_header: Option<String>,
     include_index_url: bool,
     include_find_links: bool,
+    include_extras: bool,
     index_locations: IndexLocations,
     setup_py: SetupPyStrategy,
     config_settings: ConfigSettings,
+    no_build_isolation: bool,
+    extra_build_requires: Vec<Requirement>,
+    build_constraints: &[RequirementsSource],
     connectivity: Connectivity,
     no_build: &NoBuild,
     python_version: Option<PythonVersion>,
+    python_platform: Option<TargetTriple>,
     exclude_newer: Option<DateTime<Utc>>,
+    resolution_timeout: Option<Duration>,
+    max_resolution_steps: Option<usize>,
+    min_release_age: MinReleaseAge,
     annotation_style: AnnotationStyle,
     quiet: bool,
+    concurrency: Concurrency,
     cache: Cache,
     mut printer: Printer,
 ) -> Result<ExitStatus> {
     let start = std::time::Instant::now();
 
+    if check && output_file.is_none() {
+        return Err(anyhow!(
+            "`--check` requires an output file to verify (e.g., `--output-file requirements.txt`)"
+        ));
+    }
+
     // If the user requests `extras` but does not provide a pyproject toml source
     if !matches!(extras, ExtrasSpecification::None)
-        && !requirements
-            .iter()
-            .any(|source| matches!(source, RequirementsSource::PyprojectToml(_)))
+        && !requirements.iter().any(|source| {
+            matches!(
+                source,
+                RequirementsSource::PyprojectToml(_)
+                    | RequirementsSource::SetupPy(_)
+                    | RequirementsSource::SetupCfg(_)
+            )
+        })
     {
         return Err(anyhow!(
-            "Requesting extras requires a pyproject.toml input file."
+            "Requesting extras requires a pyproject.toml, setup.cfg, or setup.py input file."
         ));
     }
 
+    // Compute the digest of each input file, to record in the header.
+    let input_digests = input_digests(requirements.iter().chain(constraints).chain(overrides))?;
+
     // Read all requirements from the provided sources.
     let RequirementsSpecification {
-        project,
-        requirements,
+        mut project,
+        mut requirements,
+        provenance,
         constraints,
         overrides,
         editables,
+        source_trees,
+        unnamed_requirements,
         index_url,
         extra_index_urls,
         no_index,
         find_links,
-        extras: used_extras,
+        no_binary: _no_binary,
+        only_binary: specified_only_binary,
+        extras: mut used_extras,
+        url_hashes: _url_hashes,
     } = RequirementsSpecification::from_sources(requirements, constraints, overrides, &extras)?;
 
+    // If the input files pin incompatible versions of the same package, point at the offending
+    // lines, rather than surfacing the conflict from the resolver.
+    check_conflicting_pins(&provenance)?;
+
+    // Read the build constraints, which apply to the resolution of build environments.
+    let build_constraints = RequirementsSpecification::from_build_constraints(build_constraints)?;
+
     // Incorporate any index locations from the provided sources.
     let index_locations =
         index_locations.combine(index_url, extra_index_urls, find_links, no_index);
 
-    // Check that all provided extras are used
-    if let ExtrasSpecification::Some(extras) = extras {
-        let mut unused_extras = extras
-            .iter()
-            .filter(|extra| !used_extras.contains(extra))
-            .collect::<Vec<_>>();
-        if !unused_extras.is_empty() {
-            unused_extras.sort_unstable();
-            unused_extras.dedup();
-            let s = if unused_extras.len() == 1 { "" } else { "s" };
-            return Err(anyhow!(
-                "Requested extra{s} not found: {}",
-                unused_extras.iter().join(", ")
-            ));
-        }
-    }
+    // Incorporate any `--only-binary` options from the provided sources.
+    let no_build = &no_build
+        .clone()
+        .combine(NoBuild::from_args(specified_only_binary, false));
+
+    // With `--upgrade`, skip any cached resolution, since it was computed from the existing pins.
+    let upgrade_requested = !matches!(upgrade, Upgrade::None);
 
     let preferences: Vec<Requirement> = output_file
         // As an optimization, skip reading the lockfile is we're upgrading all packages anyway.
@@ -171,30 +211,121 @@ pub(crate) async fn pip_compile(
     // Create a shared in-memory index.
     let source_index = InMemoryIndex::default();
 
-    // If we're resolving against a different Python version, use a separate index. Source
-    // distributions will be built against the installed version, and so the index may contain
-    // different package priorities than in the top-level resolution.
-    let top_level_index = if python_version.is_some() {
+    // If we're resolving against a different Python version or platform, use a separate index.
+    // Source distributions will be built against the installed version, and so the index may
+    // contain different package priorities than in the top-level resolution.
+    let top_level_index = if python_version.is_some() || python_platform.is_some() {
         InMemoryIndexRef::Owned(InMemoryIndex::default())
     } else {
         InMemoryIndexRef::Borrowed(&source_index)
     };
 
-    // Determine the tags, markers, and interpreter to use for resolution.
-    let tags = if let Some(python_version) = python_version.as_ref() {
-        Cow::Owned(Tags::from_env(
-            interpreter.platform(),
-            (python_version.major(), python_version.minor()),
-            interpreter.implementation_name(),
-            interpreter.implementation_tuple(),
-        )?)
-    } else {
-        Cow::Borrowed(interpreter.tags()?)
-    };
-    let markers = python_version.map_or_else(
-        || Cow::Borrowed(interpreter.markers()),
-        |python_version| Cow::Owned(python_version.markers(interpreter.markers())),
-    );
+    // Determine the tags and markers to use for resolution.
+    let (tags, markers) =
+        resolution_environment(&interpreter, python_version.as_ref(), python_platform)?;
+
+    // Resolutions are only cached when they're reproducible from their inputs alone: the indexes
+    // must be fixed in time via `--exclude-newer`, and the inputs can't reference local or remote
+    // sources that could change between runs. (Offline resolutions aren't fixed in time, since the
+    // versions available in the cache change as the cache is populated.)
+    //
+    // Note that yanks aren't bound by `--exclude-newer`, so a cached resolution may include a
+    // version that was yanked after the resolution was cached, until the cache is refreshed.
+    let reproducible = exclude_newer.is_some()
+        && min_release_age.is_none()
+        && cache.refresh().is_none()
+        && editables.is_empty()
+        && source_trees.is_empty()
+        && unnamed_requirements.is_empty()
+        && index_locations.flat_index().next().is_none()
+        && requirements
+            .iter()
+            .chain(&constraints)
+            .chain(&overrides)
+            .all(|requirement| !matches!(requirement.version_or_url, Some(VersionOrUrl::Url(_))));
+    let resolution_entry = reproducible.then(|| {
+        let key = ResolutionKey {
+            version: crate::version::version().to_string(),
+            input_digests: &input_digests,
+            requirements: to_strings(&requirements),
+            constraints: to_strings(&constraints),
+            overrides: to_strings(&overrides),
+            preferences: to_strings(&preferences),
+            project: project.as_ref(),
+            index_locations: &index_locations,
+            markers: &markers,
+            platform: interpreter.platform(),
+            python_version: python_version.as_ref().map(ToString::to_string),
+            python_platform: python_platform.as_ref().map(value_name),
+            implementation: (
+                interpreter.implementation_name(),
+                interpreter.implementation_tuple(),
+            ),
+            resolution_mode: value_name(&resolution_mode),
+            prerelease_mode: value_name(&prerelease_mode),
+            transitive: dependency_mode.is_transitive(),
+            exclude_newer: exclude_newer.map(|exclude_newer| exclude_newer.to_rfc3339()),
+            no_build,
+            setup_py,
+            config_settings: &config_settings,
+            no_build_isolation,
+            extra_build_requires: to_strings(&extra_build_requires),
+            build_constraints: to_strings(&build_constraints),
+            generate_hashes,
+            include_extras,
+            include_annotations,
+            annotation_style: value_name(&annotation_style),
+            no_emit_packages: &no_emit_packages,
+        };
+        cache.entry(
+            CacheBucket::Resolutions,
+            "",
+            format!("{}.json", key.digest()),
+        )
+    });
+
+    // Reuse the result of a previous resolution with the same inputs, if possible.
+    if let Some(entry) = resolution_entry.as_ref().filter(|_| !upgrade_requested) {
+        if let Some(resolved) = CompiledResolution::read(entry) {
+            debug!(
+                "Using cached resolution from: {}",
+                entry.path().simplified_display()
+            );
+            let s = if resolved.packages == 1 { "" } else { "s" };
+            writeln!(
+                printer,
+                "{}",
+                format!(
+                    "Resolved {} in {}",
+                    format!("{} package{}", resolved.packages, s).bold(),
+                    elapsed(start.elapsed())
+                )
+                .dimmed()
+            )?;
+            for diagnostic in &resolved.diagnostics {
+                writeln!(
+                    printer,
+                    "{}{} {}",
+                    "warning".yellow().bold(),
+                    ":".bold(),
+                    diagnostic.bold()
+                )?;
+            }
+            return write_resolution(
+                &resolved,
+                output_file,
+                check,
+                include_header,
+                custom_header,
+                include_index_url,
+                include_find_links,
+                &index_locations,
+                &input_digests,
+                quiet,
+                printer,
+            );
+        }
+    }
 
     // Instantiate a client.
     let client = RegistryClientBuilder::new(cache.clone())
@@ -212,6 +343,19 @@ pub(crate) async fn pip_compile(
     // Track in-flight downloads, builds, etc., across resolutions.
     let in_flight = InFlight::default();
 
+    // Determine whether to build source distributions in isolation, or in the environment of the
+    // selected interpreter.
+    let environment;
+    let build_isolation = if no_build_isolation {
+        environment = PythonEnvironment::from_interpreter(
+            interpreter.clone(),
+            interpreter.prefix().to_path_buf(),
+        );
+        BuildIsolation::Shared(&environment)
+    } else {
+        BuildIsolation::Isolated
+    };
+
     let build_dispatch = BuildDispatch::new(
         &client,
         &cache,
@@ -225,7 +369,70 @@ pub(crate) async fn pip_compile(
         no_build,
         &NoBinary::None,
     )
-    .with_options(OptionsBuilder::new().exclude_newer(exclude_newer).build());
+    .with_build_isolation(build_isolation)
+    .with_concurrency(concurrency)
+    .with_extra_build_requires(extra_build_requires)
+    .with_build_constraints(build_constraints)
+    .with_options(
+        OptionsBuilder::new()
+            .exclude_newer(exclude_newer)
+            .min_release_age(min_release_age.clone())
+            .build(),
+    );
+
+    // Build the metadata for any local paths that omit a package name, to determine their names.
+    if !unnamed_requirements.is_empty() {
+        let database = DistributionDatabase::new(&cache, &tags, &client, &build_dispatch);
+        let wheel_dir = tempdir_in(cache.root())?;
+        requirements.extend(
+            name_unnamed_requirements(unnamed_requirements, &database, wheel_dir.path()).await?,
+        );
+    }
+
+    // Build the metadata for any source trees (e.g., `setup.py` inputs) to determine their
+    // requirements.
+    if !source_trees.is_empty() {
+        let database = DistributionDatabase::new(&cache, &tags, &client, &build_dispatch);
+        let wheel_dir = tempdir_in(cache.root())?;
+        for source_tree in &source_trees {
+            let metadata = database
+                .build_source_tree_metadata(source_tree, wheel_dir.path())
+                .await
+                .with_context(|| {
+                    format!(
+                        "Failed to build metadata for: {}",
+                        source_tree.simplified_display()
+                    )
+                })?;
+
+            // Use the first project name discovered.
+            if project.is_none() {
+                project = Some(metadata.name.clone());
+            }
+
+            let (source_tree_requirements, source_tree_extras) =
+                requirements_from_metadata(metadata, &extras, &markers);
+            requirements.extend(source_tree_requirements);
+            used_extras.extend(source_tree_extras);
+        }
+    }
+
+    // Check that all provided extras are used
+    if let ExtrasSpecification::Some(extras) = extras {
+        let mut unused_extras = extras
+            .iter()
+            .filter(|extra| !used_extras.contains(extra))
+            .collect::<Vec<_>>();
+        if !unused_extras.is_empty() {
+            unused_extras.sort_unstable();
+            unused_extras.dedup();
+            let s = if unused_extras.len() == 1 { "" } else { "s" };
+            return Err(anyhow!(
+                "Requested extra{s} not found: {}",
+                unused_extras.iter().join(", ")
+            ));
+        }
+    }
 
     // Build the editables and add their requirements
     let editable_metadata = if editables.is_empty() {
@@ -279,13 +486,17 @@ pub(crate) async fn pip_compile(
         preferences,
         project,
         editable_metadata,
-    );
+    )
+    .with_provenance(provenance);
 
     let options = OptionsBuilder::new()
         .resolution_mode(resolution_mode)
         .prerelease_mode(prerelease_mode)
         .dependency_mode(dependency_mode)
         .exclude_newer(exclude_newer)
+        .timeout(resolution_timeout)
+        .max_steps(max_resolution_steps)
+        .min_release_age(min_release_age.clone())
         .build();
 
     // Resolve the dependencies.
@@ -302,11 +513,14 @@ pub(crate) async fn pip_compile(
     )?
     .with_reporter(ResolverReporter::from(printer));
 
-    let resolution = match resolver.resolve().await {
+    let mut resolution = match resolver.resolve().await {
         Err(uv_resolver::ResolveError::NoSolution(err)) => {
             let report = miette::Report::msg(format!("{err}"))
                 .context("No solution found when resolving dependencies:");
             eprint!("{report:?}");
+            if printer == Printer::Verbose {
+                eprint!("\n{}\n{}", "Derivation tree:".bold(), err.derivation_tree());
+            }
             return Ok(ExitStatus::Failure);
         }
         result => result,
@@ -335,8 +549,233 @@ pub(crate) async fn pip_compile(
         )?;
     }
 
-    // Write the resolved dependencies to the output channel.
-    let mut writer = OutputWriter::new(!quiet || output_file.is_none(), output_file)?;
+    // Hash any direct URL and local archives, for which the index doesn't provide hashes.
+    if generate_hashes {
+        let dists = resolution
+            .petgraph()
+            .node_weights()
+            .filter(|dist| !resolution.is_editable(dist.name()))
+            .cloned()
+            .collect::<Vec<_>>();
+        for dist in dists {
+            if let Some(sha256) = archive_hash(&dist, &client).await? {
+                resolution.insert_hashes(
+                    dist.name().clone(),
+                    vec![Hashes {
+                        md5: None,
+                        sha256: Some(sha256),
+                    }],
+                );
+            }
+        }
+    }
+
+    // Notify the user of any distributions that can't be pinned by hash. (Git distributions are
+    // pinned to a specific commit instead.)
+    if generate_hashes {
+        for dist in resolution.petgraph().node_weights() {
+            if resolution.is_editable(dist.name())
+                || matches!(dist, Dist::Source(SourceDist::Git(_)))
+            {
+                continue;
+            }
+            if !resolution
+                .hashes(dist.name())
+                .iter()
+                .any(|hash| hash.to_string().is_some())
+            {
+                warn_user_code!(
+                    WarningCode::MissingHashes,
+                    "No hashes are available for {dist}, so it can't be pinned by hash"
+                );
+            }
+        }
+    }
+
+    // Render the resolution, and store it for subsequent runs with the same inputs.
+    let resolved = CompiledResolution::new(
+        &resolution,
+        &no_emit_packages,
+        generate_hashes,
+        include_extras,
+        include_annotations,
+        annotation_style,
+    );
+    if let Some(entry) = resolution_entry {
+        if let Err(err) = resolved.write(&entry) {
+            warn_user!("Failed to cache the resolution: {err}");
+        }
+    }
+
+    write_resolution(
+        &resolved,
+        output_file,
+        check,
+        include_header,
+        custom_header,
+        include_index_url,
+        include_find_links,
+        &index_locations,
+        &input_digests,
+        quiet,
+        printer,
+    )
+}
+
+/// The inputs that determine the result of a resolution, used to key the resolution cache.
+///
+/// The key is hashed in its serialized form, so every field must serialize deterministically:
+/// requirements are stored in their PEP 508 form, and options in their command-line spelling.
+#[derive(Debug, Serialize)]
+struct ResolutionKey<'a> {
+    version: String,
+    input_digests: &'a [(String, String)],
+    requirements: Vec<String>,
+    constraints: Vec<String>,
+    overrides: Vec<String>,
+    preferences: Vec<String>,
+    project: Option<&'a PackageName>,
+    index_locations: &'a IndexLocations,
+    markers: &'a MarkerEnvironment,
+    platform: &'a Platform,
+    python_version: Option<String>,
+    python_platform: Option<String>,
+    implementation: (&'a str, (u8, u8)),
+    resolution_mode: String,
+    prerelease_mode: String,
+    transitive: bool,
+    exclude_newer: Option<String>,
+    no_build: &'a NoBuild,
+    setup_py: SetupPyStrategy,
+    config_settings: &'a ConfigSettings,
+    no_build_isolation: bool,
+    extra_build_requires: Vec<String>,
+    build_constraints: Vec<String>,
+    generate_hashes: bool,
+    include_extras: bool,
+    include_annotations: bool,
+    annotation_style: String,
+    no_emit_packages: &'a [PackageName],
+}
+
+impl ResolutionKey<'_> {
+    /// Compute a digest of the key, as a hex string.
+    fn digest(&self) -> String {
+        let key = serde_json::to_vec(self).expect("Failed to serialize resolution key");
+        format!("{:x}", Sha256::digest(key))
+    }
+}
+
+/// Render each item in its canonical string form, for inclusion in a [`ResolutionKey`].
+fn to_strings<T: ToString>(items: &[T]) -> Vec<String> {
+    items.iter().map(ToString::to_string).collect()
+}
+
+/// Return the name by which a command-line option value is spelled, for inclusion in a
+/// [`ResolutionKey`].
+fn value_name(value: &impl ValueEnum) -> String {
+    value
+        .to_possible_value()
+        .expect("Option values are never skipped")
+        .get_name()
+        .to_string()
+}
+
+/// A rendered resolution, as stored in the resolution cache.
+#[derive(Debug, Serialize, Deserialize)]
+struct CompiledResolution {
+    /// The number of packages in the resolution.
+    packages: usize,
+    /// The diagnostics that were encountered during resolution, as user-facing messages.
+    diagnostics: Vec<String>,
+    /// The resolved requirements, as written to the output file.
+    requirements: String,
+    /// The packages that were omitted from the output via `--no-emit-package`.
+    excluded: Vec<String>,
+}
+
+impl CompiledResolution {
+    /// Render a [`ResolutionGraph`] with the given output options.
+    fn new(
+        resolution: &ResolutionGraph,
+        no_emit_packages: &[PackageName],
+        generate_hashes: bool,
+        include_extras: bool,
+        include_annotations: bool,
+        annotation_style: AnnotationStyle,
+    ) -> Self {
+        let requirements = DisplayResolutionGraph::new(
+            resolution,
+            no_emit_packages,
+            generate_hashes,
+            include_extras,
+            include_annotations,
+            annotation_style,
+        )
+        .to_string();
+        let excluded = no_emit_packages
+            .iter()
+            .filter(|name| resolution.contains(name))
+            .map(ToString::to_string)
+            .collect();
+        Self {
+            packages: resolution.len(),
+            diagnostics: resolution
+                .diagnostics()
+                .iter()
+                .map(Diagnostic::message)
+                .collect(),
+            requirements,
+            excluded,
+        }
+    }
+
+    /// Read a resolution from the cache, if present.
+    fn read(entry: &CacheEntry) -> Option<Self> {
+        let contents = fs_err::read(entry.path()).ok()?;
+        match serde_json::from_slice(&contents) {
+            Ok(resolved) => Some(resolved),
+            Err(err) => {
+                debug!(
+                    "Ignoring malformed cached resolution at {}: {err}",
+                    entry.path().simplified_display()
+                );
+                None
+            }
+        }
+    }
+
+    /// Write the resolution to the cache.
+    fn write(&self, entry: &CacheEntry) -> Result<()> {
+        fs_err::create_dir_all(entry.dir())?;
+        uv_fs::write_atomic_sync(entry.path(), serde_json::to_vec(self)?)?;
+        Ok(())
+    }
+}
+
+/// Write a resolution to the output channel, or, with `--check`, compare it to the existing
+/// output file.
+#[allow(clippy::too_many_arguments, clippy::fn_params_excessive_bools)]
+fn write_resolution(
+    resolved: &CompiledResolution,
+    output_file: Option<&Path>,
+    check: bool,
+    include_header: bool,
+    custom_header: Option<String>,
+    include_index_url: bool,
+    include_find_links: bool,
+    index_locations: &IndexLocations,
+    input_digests: &[(String, String)],
+    quiet: bool,
+    mut printer: Printer,
+) -> Result<ExitStatus> {
+    // Write the resolved dependencies to the output channel. With `--check`, write to an
+    // in-memory buffer instead, to compare against the existing output file.
+    let mut writer = if check {
+        OutputWriter::buffered()
+    } else {
+        OutputWriter::new(!quiet || output_file.is_none(), output_file)?
+    };
 
     if include_header {
         writeln!(
@@ -344,11 +783,18 @@ pub(crate) async fn pip_compile(
             "{}",
             "# This file was autogenerated by uv via the following command:".green()
         )?;
-        writeln!(
-            writer,
-            "{}",
-            format!("#    {}", cmd(include_index_url, include_find_links,)).green()
-        )?;
+        let command = custom_header.unwrap_or_else(|| cmd(include_index_url, include_find_links));
+        writeln!(writer, "{}", format!("#    {command}").green())?;
+
+        // Record the digest of each input file, such that the output file can be verified against
+        // the inputs from which it was generated.
+        for (path, digest) in input_digests {
+            writeln!(
+                writer,
+                "{}",
+                format!("# input-hash: {path} sha256:{digest}").green()
+            )?;
+        }
     }
 
     // Write the index locations to the output channel.
@@ -379,23 +825,10 @@ pub(crate) async fn pip_compile(
         writeln!(writer)?;
     }
 
-    write!(
-        writer,
-        "{}",
-        DisplayResolutionGraph::new(
-            &resolution,
-            &no_emit_packages,
-            generate_hashes,
-            include_annotations,
-            annotation_style,
-        )
-    )?;
+    write!(writer, "{}", resolved.requirements)?;
 
     // If any "unsafe" packages were excluded, notify the user.
-    let excluded = no_emit_packages
-        .into_iter()
-        .filter(|name| resolution.contains(name))
-        .collect::<Vec<_>>();
+    let excluded = &resolved.excluded;
     if !excluded.is_empty() {
         writeln!(writer)?;
         writeln!(
@@ -408,9 +841,59 @@ pub(crate) async fn pip_compile(
         }
     }
 
+    if let (Some(compiled), Some(output_file)) = (writer.into_buffer(), output_file) {
+        let compiled = String::from_utf8_lossy(&compiled);
+        let compiled = anstream::adapter::strip_str(&compiled).to_string();
+        let existing = match fs_err::read_to_string(output_file) {
+            Ok(existing) => Some(existing),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => None,
+            Err(err) => return Err(err.into()),
+        };
+        if existing.is_some_and(|existing| existing.lines().eq(compiled.lines())) {
+            writeln!(
+                printer,
+                "{} is up to date",
+                output_file.simplified_display().cyan()
+            )?;
+        } else {
+            writeln!(
+                printer,
+                "{} is out of date with respect to its inputs; run without `--check` to update it",
+                output_file.simplified_display().cyan()
+            )?;
+            return Ok(ExitStatus::Failure);
+        }
+    }
+
     Ok(ExitStatus::Success)
 }
 
+/// Compute the SHA-256 digest of each input file, skipping any requirements provided on the
+/// command line or via stdin.
+fn input_digests<'a>(
+    sources: impl Iterator<Item = &'a RequirementsSource>,
+) -> Result<Vec<(String, String)>> {
+    let mut digests = Vec::new();
+    for source in sources {
+        let path = match source {
+            RequirementsSource::RequirementsTxt(path)
+            | RequirementsSource::PyprojectToml(path)
+            | RequirementsSource::SetupPy(path)
+            | RequirementsSource::SetupCfg(path) => path,
+            RequirementsSource::Package(_) | RequirementsSource::Editable(_) => continue,
+        };
+        if path == Path::new("-") {
+            continue;
+        }
+        let contents = fs_err::read(path)?;
+        digests.push((
+            path.simplified_display().to_string(),
+            format!("{:x}", Sha256::digest(contents)),
+        ));
+    }
+    Ok(digests)
+}
+
 /// Format the `uv` command used to generate the output file.
 #[allow(clippy::fn_params_excessive_bools)]
 fn cmd(include_index_url: bool, include_find_links: bool) -> String {
@@ -466,8 +949,16 @@ fn cmd(include_index_url: bool, include_find_links: bool) -> String {
                 return Some(None);
             }
 
-            // Always skip the `--verbose` flag.
-            if arg == "--verbose" || arg == "-v" {
+            // Always skip the `--check` flag, such that the header matches the checked file.
+            if arg == "--check" {
+                *skip_next = None;
+                return Some(None);
+            }
+
+            // Always skip the `--verbose` flag, including repeated short flags (e.g., `-vv`).
+            if arg == "--verbose"
+                || (arg.len() > 1 && arg.starts_with('-') && arg[1..].chars().all(|c| c == 'v'))
+            {
                 *skip_next = None;
                 return Some(None);
             }
@@ -485,6 +976,7 @@ fn cmd(include_index_url: bool, include_find_links: bool) -> String {
 struct OutputWriter {
     stdout: Option<AutoStream<std::io::Stdout>>,
     output_file: Option<StripStream<std::fs::File>>,
+    buffer: Option<Vec<u8>>,
 }
 
 #[allow(clippy::disallowed_types)]
@@ -501,9 +993,24 @@ impl OutputWriter {
         Ok(Self {
             stdout,
             output_file,
+            buffer: None,
         })
     }
 
+    /// Create an output writer that writes to an in-memory buffer, and nowhere else.
+    fn buffered() -> Self {
+        Self {
+            stdout: None,
+            output_file: None,
+            buffer: Some(Vec::new()),
+        }
+    }
+
+    /// Return the contents of the in-memory buffer, if any.
+    fn into_buffer(self) -> Option<Vec<u8>> {
+        self.buffer
+    }
+
     /// Write the given arguments to both the standard output and the output file, if present.
     fn write_fmt(&mut self, args: std::fmt::Arguments<'_>) -> std::io::Result<()> {
         use std::io::Write;
@@ -516,12 +1023,16 @@ impl OutputWriter {
             write!(stdout, "{args}")?;
         }
 
+        if let Some(buffer) = &mut self.buffer {
+            write!(buffer, "{args}")?;
+        }
+
         Ok(())
     }
 }
 
 /// Whether to allow package upgrades.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub(crate) enum Upgrade {
     /// Prefer pinned versions from the existing lockfile, if possible.
     None,
@@ -565,8 +1076,76 @@ pub(crate) fn extra_name_with_clap_error(arg: &str) -> Result<ExtraName> {
     })
 }
 
+/// Compute the SHA-256 digest of a direct URL or local archive, for which (unlike a registry
+/// distribution) the index doesn't provide any hashes.
+///
+/// Returns `None` for Git distributions, which are pinned to a commit instead, and for local
+/// directories, which can't be pinned by hash.
+async fn archive_hash(dist: &Dist, client: &RegistryClient) -> Result<Option<String>> {
+    let url = match dist {
+        Dist::Built(BuiltDist::DirectUrl(wheel)) => wheel.url.raw(),
+        Dist::Source(SourceDist::DirectUrl(sdist)) => sdist.url.raw(),
+        Dist::Built(BuiltDist::Path(wheel)) => {
+            return Ok(Some(format!(
+                "{:x}",
+                Sha256::digest(fs_err::read(&wheel.path)?)
+            )));
+        }
+        Dist::Source(SourceDist::Path(sdist)) if sdist.path.is_file() => {
+            return Ok(Some(format!(
+                "{:x}",
+                Sha256::digest(fs_err::read(&sdist.path)?)
+            )));
+        }
+        _ => return Ok(None),
+    };
+
+    debug!("Downloading {url} to compute its hash");
+    let response = client
+        .cached_client()
+        .uncached()
+        .get(url.clone())
+        .send()
+        .await?
+        .error_for_status()?;
+    let contents = response.bytes().await?;
+    Ok(Some(format!("{:x}", Sha256::digest(&contents))))
+}
+
+/// Determine the tags and markers to use for resolution, targeting the given Python version and
+/// platform in lieu of the interpreter's own.
+pub(crate) fn resolution_environment<'a>(
+    interpreter: &'a Interpreter,
+    python_version: Option<&PythonVersion>,
+    python_platform: Option<TargetTriple>,
+) -> Result<(Cow<'a, Tags>, Cow<'a, MarkerEnvironment>)> {
+    let tags = if python_version.is_some() || python_platform.is_some() {
+        Cow::Owned(Tags::from_env(
+            &python_platform.map_or_else(|| interpreter.platform().clone(), TargetTriple::platform),
+            python_version.map_or_else(
+                || (interpreter.python_major(), interpreter.python_minor()),
+                |python_version| (python_version.major(), python_version.minor()),
+            ),
+            interpreter.implementation_name(),
+            interpreter.implementation_tuple(),
+        )?)
+    } else {
+        Cow::Borrowed(interpreter.tags()?)
+    };
+    let markers = python_version.map_or_else(
+        || Cow::Borrowed(interpreter.markers()),
+        |python_version| Cow::Owned(python_version.markers(interpreter.markers())),
+    );
+    let markers = if let Some(python_platform) = python_platform {
+        Cow::Owned(python_platform.markers(&markers))
+    } else {
+        markers
+    };
+    Ok((tags, markers))
+}
+
 /// An owned or unowned [`InMemoryIndex`].
-enum InMemoryIndexRef<'a> {
+pub(crate) enum InMemoryIndexRef<'a> {
     Owned(InMemoryIndex),
     Borrowed(&'a InMemoryIndex),
 }
@@ -0,0 +1,534 @@
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+use anstream::{eprint, println};
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Utc};
+use futures::{StreamExt, TryStreamExt};
+use itertools::Itertools;
+use owo_colors::OwoColorize;
+use sha2::{Digest, Sha256};
+use tokio::io::AsyncWriteExt;
+use tracing::debug;
+use url::Url;
+
+use cache_key::CanonicalUrl;
+use distribution_types::{
+    BuiltDist, Dist, File, FileLocation, IndexLocations, Name, RemoteSource, SourceDist,
+};
+use pep440_rs::Version;
+use platform_host::Platform;
+use uv_cache::{Cache, CacheBucket};
+use uv_client::{Connectivity, FlatIndex, FlatIndexClient, RegistryClient, RegistryClientBuilder};
+use uv_dispatch::BuildDispatch;
+use uv_fs::Simplified;
+use uv_installer::NoBinary;
+use uv_interpreter::{Interpreter, PythonVersion, TargetTriple};
+use uv_normalize::PackageName;
+use uv_resolver::{
+    DependencyMode, InMemoryIndex, Manifest, OptionsBuilder, PreReleaseMode, ResolutionMode,
+    Resolver,
+};
+use uv_traits::{Concurrency, ConfigSettings, HashDatabase, InFlight, NoBuild, SetupPyStrategy};
+use uv_warnings::warn_user;
+
+use crate::commands::pip_compile::{resolution_environment, InMemoryIndexRef};
+use crate::commands::reporters::ResolverReporter;
+use crate::commands::{elapsed, ExitStatus};
+use crate::printer::Printer;
+use crate::requirements::{ExtrasSpecification, RequirementsSource, RequirementsSpecification};
+
+/// Resolve a set of requirements, and download the resolved wheels and source distributions into
+/// a directory, without installing them.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn pip_download(
+    requirements: &[RequirementsSource],
+    constraints: &[RequirementsSource],
+    overrides: &[RequirementsSource],
+    dest: &Path,
+    hash_database: &HashDatabase,
+    resolution_mode: ResolutionMode,
+    prerelease_mode: PreReleaseMode,
+    dependency_mode: DependencyMode,
+    index_locations: IndexLocations,
+    connectivity: Connectivity,
+    no_build: &NoBuild,
+    python_version: Option<PythonVersion>,
+    python_platform: Option<TargetTriple>,
+    exclude_newer: Option<DateTime<Utc>>,
+    concurrency: Concurrency,
+    cache: Cache,
+    mut printer: Printer,
+) -> Result<ExitStatus> {
+    let start = std::time::Instant::now();
+
+    // Read all requirements from the provided sources.
+    let RequirementsSpecification {
+        project,
+        requirements,
+        provenance: _provenance,
+        constraints,
+        overrides,
+        editables,
+        source_trees,
+        unnamed_requirements,
+        index_url,
+        extra_index_urls,
+        no_index,
+        find_links,
+        no_binary: _no_binary,
+        only_binary: specified_only_binary,
+        extras: _extras,
+        url_hashes,
+    } = RequirementsSpecification::from_sources(
+        requirements,
+        constraints,
+        overrides,
+        &ExtrasSpecification::None,
+    )?;
+
+    // Verify any direct URL requirements against the hashes provided alongside them.
+    let mut hash_database = hash_database.clone();
+    for (url, hashes) in &url_hashes {
+        hash_database.insert_url(url.as_str(), hashes)?;
+    }
+
+    // Editables and local source trees can't be downloaded as archives.
+    if let Some(editable) = editables.first() {
+        return Err(anyhow!(
+            "Editable requirements can't be downloaded: {}",
+            editable.path.simplified_display()
+        ));
+    }
+    if let Some(source_tree) = source_trees
+        .first()
+        .or_else(|| unnamed_requirements.first().map(|unnamed| &unnamed.path))
+    {
+        return Err(anyhow!(
+            "Source trees can't be downloaded: {}",
+            source_tree.simplified_display()
+        ));
+    }
+
+    // Incorporate any index locations from the provided sources.
+    let index_locations =
+        index_locations.combine(index_url, extra_index_urls, find_links, no_index);
+
+    // Incorporate any `--only-binary` options from the provided sources.
+    let no_build = &no_build
+        .clone()
+        .combine(NoBuild::from_args(specified_only_binary, false));
+
+    // Find an interpreter to use for building distributions.
+    let platform = Platform::current()?;
+    let interpreter = Interpreter::find_best(python_version.as_ref(), &platform, &cache)?;
+    debug!(
+        "Using Python {} interpreter at {} for builds",
+        interpreter.python_version(),
+        interpreter.sys_executable().simplified_display().cyan()
+    );
+    if let Some(python_version) = python_version.as_ref() {
+        if no_build.is_none()
+            && (python_version.major() != interpreter.python_major()
+                || python_version.minor() != interpreter.python_minor())
+        {
+            warn_user!(
+                "The requested Python version {} is not available; {} will be used to build dependencies instead.",
+                python_version.version(),
+                interpreter.python_version(),
+            );
+        }
+    }
+
+    // Create a shared in-memory index.
+    let source_index = InMemoryIndex::default();
+
+    // If we're resolving against a different Python version or platform, use a separate index, as
+    // in `pip compile`.
+    let top_level_index = if python_version.is_some() || python_platform.is_some() {
+        InMemoryIndexRef::Owned(InMemoryIndex::default())
+    } else {
+        InMemoryIndexRef::Borrowed(&source_index)
+    };
+
+    // Determine the tags and markers to use for resolution.
+    let (tags, markers) =
+        resolution_environment(&interpreter, python_version.as_ref(), python_platform)?;
+
+    // Instantiate a client.
+    let client = RegistryClientBuilder::new(cache.clone())
+        .index_urls(index_locations.index_urls())
+        .connectivity(connectivity)
+        .build();
+
+    // Resolve the flat indexes from `--find-links`.
+    let flat_index = {
+        let client = FlatIndexClient::new(&client, &cache);
+        let entries = client.fetch(index_locations.flat_index()).await?;
+        FlatIndex::from_entries(entries, &tags)
+    };
+
+    // Track in-flight downloads, builds, etc., across resolutions.
+    let in_flight = InFlight::default();
+
+    let config_settings = ConfigSettings::default();
+    let build_dispatch = BuildDispatch::new(
+        &client,
+        &cache,
+        &interpreter,
+        &index_locations,
+        &flat_index,
+        &source_index,
+        &in_flight,
+        SetupPyStrategy::default(),
+        &config_settings,
+        no_build,
+        &NoBinary::None,
+    )
+    .with_hash_database(hash_database.clone())
+    .with_options(OptionsBuilder::new().exclude_newer(exclude_newer).build())
+    .with_concurrency(concurrency);
+
+    // Resolve the requirements.
+    let manifest = Manifest::new(
+        requirements,
+        constraints,
+        overrides,
+        Vec::new(),
+        project,
+        Vec::new(),
+    );
+
+    let options = OptionsBuilder::new()
+        .resolution_mode(resolution_mode)
+        .prerelease_mode(prerelease_mode)
+        .dependency_mode(dependency_mode)
+        .exclude_newer(exclude_newer)
+        .build();
+
+    let resolver = Resolver::new(
+        manifest,
+        options,
+        &markers,
+        &interpreter,
+        &tags,
+        &client,
+        &flat_index,
+        &top_level_index,
+        &build_dispatch,
+    )?
+    .with_reporter(ResolverReporter::from(printer));
+
+    let resolution = match resolver.resolve().await {
+        Err(uv_resolver::ResolveError::NoSolution(err)) => {
+            let report = miette::Report::msg(format!("{err}"))
+                .context("No solution found when resolving dependencies:");
+            eprint!("{report:?}");
+            return Ok(ExitStatus::Failure);
+        }
+        result => result,
+    }?;
+
+    let s = if resolution.len() == 1 { "" } else { "s" };
+    writeln!(
+        printer,
+        "{}",
+        format!(
+            "Resolved {} in {}",
+            format!("{} package{}", resolution.len(), s).bold(),
+            elapsed(start.elapsed())
+        )
+        .dimmed()
+    )?;
+
+    // Determine the archive to save for each distribution.
+    let mut archives = Vec::new();
+    for dist in resolution
+        .petgraph()
+        .node_weights()
+        .sorted_unstable_by(|a, b| a.name().cmp(b.name()))
+    {
+        match Archive::from_dist(dist, &hash_database)? {
+            Some(archive) => archives.push((dist.filename()?.to_string(), archive)),
+            None => warn_user!("Skipping {dist}, which isn't available as an archive"),
+        }
+    }
+
+    if archives.is_empty() {
+        writeln!(printer, "{}", "No distributions to download".dimmed())?;
+        return Ok(ExitStatus::Success);
+    }
+
+    let start = std::time::Instant::now();
+
+    fs_err::create_dir_all(dest)?;
+
+    let saved = futures::stream::iter(archives)
+        .map(|(filename, archive)| save(filename, archive, dest, &client, &cache))
+        .buffer_unordered(concurrency.downloads)
+        .try_collect::<Vec<_>>()
+        .await?;
+
+    let s = if saved.len() == 1 { "" } else { "s" };
+    writeln!(
+        printer,
+        "{}",
+        format!(
+            "Downloaded {} to {} in {}",
+            format!("{} distribution{}", saved.len(), s).bold(),
+            dest.simplified_display(),
+            elapsed(start.elapsed())
+        )
+        .dimmed()
+    )?;
+
+    for path in saved.into_iter().sorted_unstable() {
+        println!("{}", path.simplified_display());
+    }
+
+    Ok(ExitStatus::Success)
+}
+
+/// A distribution archive to download.
+#[derive(Debug)]
+struct Archive {
+    /// The name of the package to which the archive belongs.
+    name: PackageName,
+    /// The location of the archive.
+    location: Location,
+    /// The permitted SHA-256 digests of the archive. If empty, any archive is permitted.
+    expected: Vec<String>,
+}
+
+/// The location of a distribution archive.
+#[derive(Debug)]
+enum Location {
+    /// A remote archive.
+    Url(Url),
+    /// A local archive.
+    Path(PathBuf),
+}
+
+impl Archive {
+    /// Return the archive for a resolved distribution.
+    ///
+    /// If the distribution is listed in the [`HashDatabase`], the archive must match one of the
+    /// listed digests; otherwise, it must match the digest published by the index, if any.
+    ///
+    /// Returns `None` for Git distributions and local directories, which don't correspond to an
+    /// archive.
+    fn from_dist(dist: &Dist, hash_database: &HashDatabase) -> Result<Option<Self>> {
+        let (location, expected) = match dist {
+            Dist::Built(BuiltDist::Registry(wheel)) => (
+                Location::from_file(&wheel.file)?,
+                Self::expected(
+                    hash_database,
+                    &wheel.filename.name,
+                    &wheel.filename.version,
+                    &wheel.file,
+                )?,
+            ),
+            Dist::Source(SourceDist::Registry(sdist)) => (
+                Location::from_file(&sdist.file)?,
+                Self::expected(
+                    hash_database,
+                    &sdist.filename.name,
+                    &sdist.filename.version,
+                    &sdist.file,
+                )?,
+            ),
+            Dist::Built(BuiltDist::DirectUrl(wheel)) => (
+                Location::Url(wheel.url.raw().clone()),
+                Self::expected_url(hash_database, wheel.url.raw()),
+            ),
+            Dist::Source(SourceDist::DirectUrl(sdist)) => (
+                Location::Url(sdist.url.raw().clone()),
+                Self::expected_url(hash_database, sdist.url.raw()),
+            ),
+            Dist::Built(BuiltDist::Path(wheel)) => (
+                Location::Path(wheel.path.clone()),
+                Self::expected_url(hash_database, wheel.url.raw()),
+            ),
+            Dist::Source(SourceDist::Path(sdist)) if sdist.path.is_file() => (
+                Location::Path(sdist.path.clone()),
+                Self::expected_url(hash_database, sdist.url.raw()),
+            ),
+            Dist::Source(SourceDist::Path(_) | SourceDist::Git(_)) => return Ok(None),
+        };
+        Ok(Some(Self {
+            name: dist.name().clone(),
+            location,
+            expected,
+        }))
+    }
+
+    /// Return the permitted digests for a registry distribution.
+    fn expected(
+        hash_database: &HashDatabase,
+        name: &PackageName,
+        version: &Version,
+        file: &File,
+    ) -> Result<Vec<String>> {
+        match hash_database.expected(name, version) {
+            Some([]) => Err(anyhow!(
+                "`{name}=={version}` is not permitted by the hash database"
+            )),
+            Some(expected) => Ok(expected.to_vec()),
+            None => Ok(file.hashes.sha256.iter().cloned().collect()),
+        }
+    }
+
+    /// Return the permitted digests for a direct URL or local archive.
+    fn expected_url(hash_database: &HashDatabase, url: &Url) -> Vec<String> {
+        hash_database
+            .expected_url(url.as_str())
+            .map(<[String]>::to_vec)
+            .unwrap_or_default()
+    }
+
+    /// Returns `true` if the given digest is permitted for this archive.
+    fn permits(&self, digest: &str) -> bool {
+        self.expected.is_empty()
+            || self
+                .expected
+                .iter()
+                .any(|expected| expected.eq_ignore_ascii_case(digest))
+    }
+
+    /// Verify the digest of the archive at the given source.
+    fn verify(&self, source: &dyn std::fmt::Display, digest: &str) -> Result<()> {
+        if self.permits(digest) {
+            return Ok(());
+        }
+        Err(anyhow!(
+            "Hash mismatch for {source}\n\nExpected:\n{}\n\nComputed:\n  sha256:{digest}",
+            self.expected
+                .iter()
+                .map(|expected| format!("  sha256:{expected}"))
+                .join("\n")
+        ))
+    }
+}
+
+impl Location {
+    /// Return the location of a file listed in a registry or a `--find-links` location.
+    fn from_file(file: &File) -> Result<Self> {
+        match &file.url {
+            FileLocation::RelativeUrl(base, url) => {
+                Ok(Self::Url(pypi_types::base_url_join_relative(base, url)?))
+            }
+            FileLocation::AbsoluteUrl(url) => Ok(Self::Url(Url::parse(url)?)),
+            FileLocation::Path(path) => Ok(Self::Path(path.clone())),
+        }
+    }
+}
+
+/// Save an archive into the destination directory, returning the path to the saved file.
+///
+/// Archives that already exist in the destination directory are left untouched, unless they don't
+/// match the expected digest.
+async fn save(
+    filename: String,
+    archive: Archive,
+    dest: &Path,
+    client: &RegistryClient,
+    cache: &Cache,
+) -> Result<PathBuf> {
+    let target = dest.join(&filename);
+    if target.exists() {
+        if archive.expected.is_empty() || archive.permits(&file_digest(&target)?) {
+            debug!("Skipping existing archive: {}", target.simplified_display());
+            return Ok(target);
+        }
+        debug!(
+            "Replacing existing archive with unexpected hash: {}",
+            target.simplified_display()
+        );
+    }
+
+    let source = match &archive.location {
+        Location::Url(url) => fetch(&filename, url, &archive, client, cache).await?,
+        Location::Path(path) => {
+            if !archive.expected.is_empty() {
+                archive.verify(&path.simplified_display(), &file_digest(path)?)?;
+            }
+            path.clone()
+        }
+    };
+
+    // Copy to a temporary file first, to avoid leaving a partial archive behind.
+    debug!("Copying {}", source.simplified_display());
+    let temp_file = tempfile::NamedTempFile::new_in(dest)?;
+    fs_err::copy(&source, temp_file.path())?;
+    temp_file
+        .persist(&target)
+        .with_context(|| format!("Failed to write: {}", target.simplified_display()))?;
+
+    Ok(target)
+}
+
+/// Download a remote archive into the cache, returning the path to the cached archive.
+///
+/// Previously downloaded archives are reused if their digest is known, and matches; otherwise, the
+/// archive is downloaded again.
+async fn fetch(
+    filename: &str,
+    url: &Url,
+    archive: &Archive,
+    client: &RegistryClient,
+    cache: &Cache,
+) -> Result<PathBuf> {
+    let cache_entry = cache.entry(
+        CacheBucket::Downloads,
+        Path::new(archive.name.as_ref()).join(cache_key::digest(&CanonicalUrl::new(url))),
+        filename,
+    );
+    if !archive.expected.is_empty() && cache_entry.path().is_file() {
+        if archive.permits(&file_digest(cache_entry.path())?) {
+            debug!("Using cached archive for: {url}");
+            return Ok(cache_entry.into_path_buf());
+        }
+        debug!("Ignoring cached archive with unexpected hash: {url}");
+    }
+
+    // Stream the archive into a temporary file in the cache, computing its digest along the way.
+    debug!("Downloading {url}");
+    let response = client
+        .cached_client()
+        .uncached()
+        .get(url.clone())
+        .send()
+        .await?
+        .error_for_status()?;
+    fs_err::tokio::create_dir_all(cache_entry.dir()).await?;
+    let temp_file = tempfile::NamedTempFile::new_in(cache_entry.dir())?;
+    let mut writer =
+        tokio::io::BufWriter::new(fs_err::tokio::File::create(temp_file.path()).await?);
+    let mut hasher = Sha256::new();
+    let mut stream = response.bytes_stream();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk?;
+        hasher.update(&chunk);
+        writer.write_all(&chunk).await?;
+    }
+    writer.flush().await?;
+    drop(writer);
+
+    archive.verify(url, &format!("{:x}", hasher.finalize()))?;
+
+    temp_file.persist(cache_entry.path()).with_context(|| {
+        format!(
+            "Failed to write to the cache: {}",
+            cache_entry.path().simplified_display()
+        )
+    })?;
+
+    Ok(cache_entry.into_path_buf())
+}
+
+/// Compute the hex-encoded SHA-256 digest of a local file.
+fn file_digest(path: &Path) -> Result<String> {
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut fs_err::File::open(path)?, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
@@ -13,11 +13,19 @@ use uv_fs::Simplified;
 use uv_installer::SitePackages;
 use uv_interpreter::PythonEnvironment;
 
-use crate::commands::ExitStatus;
+use crate::commands::{ExitStatus, PackagePattern};
 use crate::printer::Printer;
 
+/// Packages that are omitted from the output unless `--all` is provided, matching `pip freeze`.
+const FREEZE_EXCLUDES: &[&str] = &["pip", "setuptools", "distribute", "wheel"];
+
 /// Enumerate the installed packages in the current environment.
+#[allow(clippy::too_many_arguments, clippy::fn_params_excessive_bools)]
 pub(crate) fn pip_freeze(
+    exclude_editable: bool,
+    exclude: &[PackagePattern],
+    not_required: bool,
+    all: bool,
     strict: bool,
     python: Option<&str>,
     system: bool,
@@ -48,8 +56,20 @@ pub(crate) fn pip_freeze(
 
     // Build the installed index.
     let site_packages = SitePackages::from_executable(&venv)?;
+
+    // Determine the packages that are required by other installed packages.
+    let required = not_required.then(|| site_packages.required());
+
     for dist in site_packages
         .iter()
+        .filter(|dist| all || !FREEZE_EXCLUDES.contains(&dist.name().as_ref()))
+        .filter(|dist| !(exclude_editable && dist.is_editable()))
+        .filter(|dist| !exclude.iter().any(|pattern| pattern.matches(dist.name())))
+        .filter(|dist| {
+            required.as_ref().map_or(true, |required| {
+                dist.is_requested() || !required.contains(dist.name())
+            })
+        })
         .sorted_unstable_by(|a, b| a.name().cmp(b.name()).then(a.version().cmp(b.version())))
     {
         match dist {
@@ -1,46 +1,73 @@
 use std::collections::HashSet;
 use std::fmt::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 use anstream::eprint;
 use anyhow::{anyhow, Context, Result};
 use chrono::{DateTime, Utc};
 use itertools::Itertools;
 use owo_colors::OwoColorize;
+use rayon::prelude::*;
 use tempfile::tempdir_in;
 use tracing::debug;
 
 use distribution_types::{
-    IndexLocations, InstalledMetadata, LocalDist, LocalEditable, Name, Resolution,
+    CachedDist, Dist, IndexLocations, InstalledDist, InstalledMetadata, LocalDist, LocalEditable,
+    Name, Resolution,
 };
 use install_wheel_rs::linker::LinkMode;
+use install_wheel_rs::ScriptNaming;
 use pep508_rs::{MarkerEnvironment, Requirement};
 use platform_host::Platform;
 use platform_tags::Tags;
 use pypi_types::Yanked;
 use requirements_txt::EditableRequirement;
-use uv_cache::Cache;
+use uv_cache::{Cache, CacheBucket};
 use uv_client::{Connectivity, FlatIndex, FlatIndexClient, RegistryClient, RegistryClientBuilder};
 use uv_dispatch::BuildDispatch;
+use uv_distribution::DistributionDatabase;
 use uv_fs::Simplified;
 use uv_installer::{
-    BuiltEditable, Downloader, NoBinary, Plan, Planner, Reinstall, ResolvedEditable, SitePackages,
+    verify_shared_environment, BuiltEditable, Downloader, NoBinary, Plan, Planner, Reinstall,
+    ResolvedEditable, SitePackages,
 };
-use uv_interpreter::{Interpreter, PythonEnvironment};
+use uv_interpreter::{Interpreter, PythonEnvironment, TargetTriple};
 use uv_normalize::PackageName;
 use uv_resolver::{
-    DependencyMode, InMemoryIndex, Manifest, Options, OptionsBuilder, PreReleaseMode,
-    ResolutionGraph, ResolutionMode, Resolver,
+    DependencyMode, InMemoryIndex, Manifest, MinReleaseAge, Options, OptionsBuilder,
+    PreReleaseMode, Provenance, ResolutionGraph, ResolutionMode, Resolver,
 };
-use uv_traits::{ConfigSettings, InFlight, NoBuild, SetupPyStrategy};
+use uv_traits::{
+    BuildIsolation, Concurrency, ConfigSettings, HashDatabase, InFlight, NoBuild, SetupPyStrategy,
+};
+use uv_warnings::{warn_user_code, WarningCode};
 
+use crate::commands::pip_compile::resolution_environment;
 use crate::commands::reporters::{DownloadReporter, InstallReporter, ResolverReporter};
-use crate::commands::{elapsed, ChangeEvent, ChangeEventKind, ExitStatus};
+use crate::commands::{
+    download_error, elapsed, find_or_create_venv, ChangeEvent, ChangeEventKind, ExitStatus,
+};
+use crate::interrupt;
 use crate::printer::Printer;
-use crate::requirements::{ExtrasSpecification, RequirementsSource, RequirementsSpecification};
+use crate::requirements::{
+    check_conflicting_pins, name_unnamed_requirements, ExtrasSpecification, RequirementsSource,
+    RequirementsSpecification, SOURCE_TREES_UNSUPPORTED,
+};
 
 use super::Upgrade;
 
+/// The strategy to use when upgrading packages with `--upgrade`.
+#[derive(Debug, Default, Clone, Copy, clap::ValueEnum)]
+pub(crate) enum UpgradeStrategy {
+    /// Upgrade the requested packages and all of their dependencies.
+    Eager,
+    /// Upgrade the requested packages, but only upgrade their dependencies if the installed
+    /// versions no longer satisfy the requirements.
+    #[default]
+    OnlyIfNeeded,
+}
+
 /// Install packages into the current environment.
 #[allow(clippy::too_many_arguments)]
 pub(crate) async fn pip_install(
@@ -52,18 +79,36 @@ pub(crate) async fn pip_install(
     prerelease_mode: PreReleaseMode,
     dependency_mode: DependencyMode,
     upgrade: Upgrade,
+    upgrade_strategy: UpgradeStrategy,
     index_locations: IndexLocations,
     reinstall: &Reinstall,
     link_mode: LinkMode,
+    no_zip: &[PackageName],
+    script_naming: &ScriptNaming,
+    verify_hashes: bool,
+    hash_database: &HashDatabase,
     setup_py: SetupPyStrategy,
     connectivity: Connectivity,
     config_settings: &ConfigSettings,
+    no_build_isolation: bool,
+    extra_build_requires: &[Requirement],
+    build_constraints: &[RequirementsSource],
     no_build: &NoBuild,
     no_binary: &NoBinary,
     strict: bool,
     exclude_newer: Option<DateTime<Utc>>,
+    resolution_timeout: Option<Duration>,
+    max_resolution_steps: Option<usize>,
+    min_release_age: MinReleaseAge,
     python: Option<String>,
     system: bool,
+    auto_create: bool,
+    shared: bool,
+    allow_conda: bool,
+    venvs: Vec<PathBuf>,
+    target: Option<PathBuf>,
+    python_platform: Option<TargetTriple>,
+    concurrency: Concurrency,
     cache: Cache,
     mut printer: Printer,
 ) -> Result<ExitStatus> {
@@ -72,21 +117,44 @@ pub(crate) async fn pip_install(
     // Read all requirements from the provided sources.
     let RequirementsSpecification {
         project,
-        requirements,
+        mut requirements,
+        provenance,
         constraints,
         overrides,
         editables,
+        source_trees: _,
+        unnamed_requirements,
         index_url,
         extra_index_urls,
         no_index,
         find_links,
+        no_binary: specified_no_binary,
+        only_binary: specified_only_binary,
         extras: used_extras,
+        url_hashes,
     } = specification(requirements, constraints, overrides, extras)?;
 
+    // Verify any direct URL requirements against the hashes provided alongside them.
+    let mut hash_database = hash_database.clone();
+    for (url, hashes) in &url_hashes {
+        hash_database.insert_url(url.as_str(), hashes)?;
+    }
+
+    // Read the build constraints, which apply to the resolution of build environments.
+    let build_constraints = RequirementsSpecification::from_build_constraints(build_constraints)?;
+
     // Incorporate any index locations from the provided sources.
     let index_locations =
         index_locations.combine(index_url, extra_index_urls, find_links, no_index);
 
+    // Incorporate any `--no-binary` and `--only-binary` options from the provided sources.
+    let no_binary = &no_binary
+        .clone()
+        .combine(NoBinary::from_args(specified_no_binary));
+    let no_build = &no_build
+        .clone()
+        .combine(NoBuild::from_args(specified_only_binary, false));
+
     // Check that all provided extras are used
     if let ExtrasSpecification::Some(extras) = extras {
         let mut unused_extras = extras
@@ -106,47 +174,135 @@ pub(crate) async fn pip_install(
 
     // Detect the current Python interpreter.
     let platform = Platform::current()?;
-    let venv = if let Some(python) = python.as_ref() {
-        PythonEnvironment::from_requested_python(python, &platform, &cache)?
-    } else if system {
-        PythonEnvironment::from_default_python(&platform, &cache)?
+    let venvs = if venvs.is_empty() {
+        vec![if let Some(python) = python.as_ref() {
+            PythonEnvironment::from_requested_python(python, &platform, &cache)?
+        } else if system {
+            PythonEnvironment::from_default_python(&platform, &cache)?
+        } else {
+            find_or_create_venv(platform, auto_create, &cache, printer)?
+        }]
     } else {
-        PythonEnvironment::from_virtualenv(platform, &cache)?
+        venvs
+            .iter()
+            .map(|root| PythonEnvironment::from_root(root, platform.clone(), &cache))
+            .collect::<Result<Vec<_>, _>>()?
     };
-    debug!(
-        "Using Python {} environment at {}",
-        venv.interpreter().python_version(),
-        venv.python_executable().simplified_display().cyan()
-    );
 
-    // If the environment is externally managed, abort.
-    if let Some(externally_managed) = venv.interpreter().is_externally_managed() {
-        return if let Some(error) = externally_managed.into_error() {
-            Err(anyhow::anyhow!(
-                "The interpreter at {} is externally managed, and indicates the following:\n\n{}\n\nConsider creating a virtual environment with `uv venv`.",
+    // If a target directory was provided, install into it, rather than into the environment.
+    let venvs = if let Some(target) = target.as_ref() {
+        fs_err::create_dir_all(target)?;
+        let target = fs_err::canonicalize(target)?;
+        venvs
+            .into_iter()
+            .map(|venv| {
+                PythonEnvironment::from_interpreter(
+                    venv.interpreter().clone().with_target(&target),
+                    target.clone(),
+                )
+            })
+            .collect::<Vec<_>>()
+    } else {
+        venvs
+    };
+    for venv in &venvs {
+        debug!(
+            "Using Python {} environment at {}",
+            venv.interpreter().python_version(),
+            venv.python_executable().simplified_display().cyan()
+        );
+    }
+
+    // The first environment is used for resolution, so the others must be equivalent to it.
+    let venv = &venvs[0];
+    for other in &venvs[1..] {
+        if other.interpreter().markers() != venv.interpreter().markers() {
+            return Err(anyhow!(
+                "All target environments must share the same Python version and platform, but {} uses Python {} and {} uses Python {}",
                 venv.root().simplified_display().cyan(),
-                textwrap::indent(&error, "  ").green(),
-            ))
-        } else {
-            Err(anyhow::anyhow!(
-                "The interpreter at {} is externally managed. Instead, create a virtual environment with `uv venv`.",
+                venv.interpreter().python_version(),
+                other.root().simplified_display().cyan(),
+                other.interpreter().python_version(),
+            ));
+        }
+    }
+
+    // If any environment is externally managed, abort. (A target directory is never managed by
+    // the interpreter.)
+    for venv in venvs.iter().filter(|_| target.is_none()) {
+        if let Some(externally_managed) = venv.interpreter().is_externally_managed() {
+            return if let Some(error) = externally_managed.into_error() {
+                Err(anyhow::anyhow!(
+                    "The interpreter at {} is externally managed, and indicates the following:\n\n{}\n\nConsider creating a virtual environment with `uv venv`.",
+                    venv.root().simplified_display().cyan(),
+                    textwrap::indent(&error, "  ").green(),
+                ))
+            } else {
+                Err(anyhow::anyhow!(
+                    "The interpreter at {} is externally managed. Instead, create a virtual environment with `uv venv`.",
+                    venv.root().simplified_display().cyan()
+                ))
+            };
+        }
+    }
+
+    // Packages installed into a Conda environment aren't tracked by `conda`, so require an
+    // explicit opt-in.
+    if !allow_conda {
+        if let Some(venv) = venvs
+            .iter()
+            .filter(|_| target.is_none())
+            .find(|venv| venv.is_conda())
+        {
+            return Err(anyhow::anyhow!(
+                "The environment at {} is a Conda environment. Packages installed by uv aren't tracked by `conda`; pass `--allow-conda` to install into it anyway.",
                 venv.root().simplified_display().cyan()
-            ))
-        };
+            ));
+        }
     }
 
-    let _lock = venv.lock()?;
+    // In a shared environment, verify that the rest of the group will be able to modify anything
+    // we install. Packages are copied, rather than linked, such that they're owned by the
+    // environment's group rather than that of the cache.
+    let link_mode = if shared {
+        for venv in &venvs {
+            verify_shared_environment(venv)?;
+        }
+        LinkMode::Copy
+    } else {
+        link_mode
+    };
+
+    let _locks = venvs
+        .iter()
+        .map(PythonEnvironment::lock)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // Prevent the cached wheels (which may be linked into the environment) from being cleared
+    // while we're installing.
+    let _cache_locks = [CacheBucket::Wheels, CacheBucket::Archive]
+        .into_iter()
+        .map(|bucket| cache.lock_bucket_shared(bucket))
+        .collect::<Result<Vec<_>, _>>()?;
 
-    // Determine the set of installed packages.
-    let site_packages =
-        SitePackages::from_executable(&venv).context("Failed to list installed packages")?;
+    // Determine the set of installed packages in each environment.
+    let site_packages = venvs
+        .iter()
+        .map(|venv| {
+            SitePackages::from_executable(venv).context("Failed to list installed packages")
+        })
+        .collect::<Result<Vec<_>>>()?;
 
     // If the requirements are already satisfied, we're done. Ideally, the resolver would be fast
     // enough to let us remove this check. But right now, for large environments, it's an order of
     // magnitude faster to validate the environment than to resolve the requirements.
     if reinstall.is_none()
         && upgrade.is_none()
-        && site_packages.satisfies(&requirements, &editables, &constraints)?
+        && unnamed_requirements.is_empty()
+        && site_packages
+            .iter()
+            .map(|site_packages| site_packages.satisfies(&requirements, &editables, &constraints))
+            .process_results(|mut satisfied| satisfied.all(|satisfied| satisfied))?
     {
         let num_requirements = requirements.len() + editables.len();
         let s = if num_requirements == 1 { "" } else { "s" };
@@ -165,8 +321,7 @@ pub(crate) async fn pip_install(
 
     // Determine the tags, markers, and interpreter to use for resolution.
     let interpreter = venv.interpreter().clone();
-    let tags = venv.interpreter().tags()?;
-    let markers = venv.interpreter().markers();
+    let (tags, markers) = resolution_environment(&interpreter, None, python_platform)?;
 
     // Instantiate a client.
     let client = RegistryClientBuilder::new(cache.clone())
@@ -178,7 +333,7 @@ pub(crate) async fn pip_install(
     let flat_index = {
         let client = FlatIndexClient::new(&client, &cache);
         let entries = client.fetch(index_locations.flat_index()).await?;
-        FlatIndex::from_entries(entries, tags)
+        FlatIndex::from_entries(entries, &tags)
     };
 
     // Create a shared in-memory index.
@@ -187,6 +342,13 @@ pub(crate) async fn pip_install(
     // Track in-flight downloads, builds, etc., across resolutions.
     let in_flight = InFlight::default();
 
+    // Determine whether to build source distributions in isolation, or in the target environment.
+    let build_isolation = if no_build_isolation {
+        BuildIsolation::Shared(venv)
+    } else {
+        BuildIsolation::Isolated
+    };
+
     let resolve_dispatch = BuildDispatch::new(
         &client,
         &cache,
@@ -200,7 +362,26 @@ pub(crate) async fn pip_install(
         no_build,
         no_binary,
     )
-    .with_options(OptionsBuilder::new().exclude_newer(exclude_newer).build());
+    .with_build_isolation(build_isolation)
+    .with_concurrency(concurrency)
+    .with_extra_build_requires(extra_build_requires.to_vec())
+    .with_build_constraints(build_constraints.clone())
+    .with_hash_database(hash_database.clone())
+    .with_options(
+        OptionsBuilder::new()
+            .exclude_newer(exclude_newer)
+            .min_release_age(min_release_age.clone())
+            .build(),
+    );
+
+    // Build the metadata for any local paths that omit a package name, to determine their names.
+    if !unnamed_requirements.is_empty() {
+        let database = DistributionDatabase::new(&cache, &tags, &client, &resolve_dispatch);
+        let wheel_dir = tempdir_in(cache.root())?;
+        requirements.extend(
+            name_unnamed_requirements(unnamed_requirements, &database, wheel_dir.path()).await?,
+        );
+    }
 
     // Build all editable distributions. The editables are shared between resolution and
     // installation, and should live for the duration of the command. If an editable is already
@@ -214,7 +395,7 @@ pub(crate) async fn pip_install(
             &editables,
             editable_wheel_dir.path(),
             &cache,
-            tags,
+            &tags,
             &client,
             &resolve_dispatch,
             printer,
@@ -222,11 +403,22 @@ pub(crate) async fn pip_install(
         .await?
     };
 
+    // Track the packages that were requested directly, as opposed to those that are installed as
+    // dependencies, such that they can be marked as `REQUESTED`.
+    let requested = requirements
+        .iter()
+        .map(|requirement| requirement.name.clone())
+        .chain(editables.iter().map(|built| built.metadata.name.clone()))
+        .collect::<Vec<_>>();
+
     let options = OptionsBuilder::new()
         .resolution_mode(resolution_mode)
         .prerelease_mode(prerelease_mode)
         .dependency_mode(dependency_mode)
         .exclude_newer(exclude_newer)
+        .timeout(resolution_timeout)
+        .max_steps(max_resolution_steps)
+        .min_release_age(min_release_age.clone())
         .build();
 
     // Resolve the requirements.
@@ -234,14 +426,16 @@ pub(crate) async fn pip_install(
         requirements,
         constraints,
         overrides,
+        provenance.clone(),
         project,
         &editables,
-        &site_packages,
+        &site_packages[0],
         reinstall,
         &upgrade,
+        upgrade_strategy,
         &interpreter,
-        tags,
-        markers,
+        &tags,
+        &markers,
         &client,
         &flat_index,
         &index,
@@ -256,6 +450,9 @@ pub(crate) async fn pip_install(
             let report = miette::Report::msg(format!("{err}"))
                 .context("No solution found when resolving dependencies:");
             eprint!("{report:?}");
+            if printer == Printer::Verbose {
+                eprint!("\n{}\n{}", "Derivation tree:".bold(), err.derivation_tree());
+            }
             return Ok(ExitStatus::Failure);
         }
         Err(err) => return Err(err.into()),
@@ -282,36 +479,182 @@ pub(crate) async fn pip_install(
             no_build,
             no_binary,
         )
-        .with_options(OptionsBuilder::new().exclude_newer(exclude_newer).build())
+        .with_build_isolation(build_isolation)
+        .with_concurrency(concurrency)
+        .with_extra_build_requires(extra_build_requires.to_vec())
+        .with_build_constraints(build_constraints)
+        .with_hash_database(hash_database.clone())
+        .with_options(
+            OptionsBuilder::new()
+                .exclude_newer(exclude_newer)
+                .min_release_age(min_release_age.clone())
+                .build(),
+        )
     };
 
-    // Sync the environment.
-    install(
-        &resolution,
-        editables,
-        site_packages,
-        reinstall,
-        no_binary,
-        link_mode,
-        &index_locations,
-        tags,
-        &client,
-        &in_flight,
-        &install_dispatch,
-        &cache,
-        &venv,
-        printer,
-    )
-    .await?;
+    // Prepare each environment in turn. Distributions are downloaded (and built) once, into the
+    // cache, and shared across environments.
+    let mut prepared = Vec::with_capacity(venvs.len());
+    for (venv, site_packages) in venvs.iter().zip(site_packages) {
+        if interrupt::interrupted() {
+            let mut err = anyhow!("Installation was interrupted");
+            for environment in prepared {
+                err = environment.transaction.rollback(&[], err);
+            }
+            return Err(err);
+        }
+        match prepare(
+            &resolution,
+            editables.clone(),
+            site_packages,
+            reinstall,
+            no_binary,
+            &provenance,
+            &index_locations,
+            &tags,
+            &client,
+            &in_flight,
+            &install_dispatch,
+            &cache,
+            venv,
+            printer,
+        )
+        .await
+        {
+            Ok(environment) => prepared.push(environment),
+            Err(err) => {
+                // Restore any packages that were already removed from the other environments.
+                let mut err = anyhow::Error::from(err);
+                for environment in prepared {
+                    err = environment.transaction.rollback(&[], err);
+                }
+                return Err(err);
+            }
+        }
+    }
+
+    // Link the distributions into each environment, in parallel.
+    let start = std::time::Instant::now();
+    let results = if let [environment] = prepared.as_slice() {
+        vec![link(
+            environment,
+            link_mode,
+            no_zip,
+            script_naming,
+            python_platform.is_none(),
+            verify_hashes,
+            &requested,
+            Some(printer),
+        )]
+    } else {
+        prepared
+            .par_iter()
+            .map(|environment| {
+                link(
+                    environment,
+                    link_mode,
+                    no_zip,
+                    script_naming,
+                    python_platform.is_none(),
+                    verify_hashes,
+                    &requested,
+                    None,
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+    let duration = start.elapsed();
+
+    // Report the changes to each environment, rolling back any environment that failed.
+    let mut failure = None;
+    for (environment, result) in prepared.into_iter().zip(results) {
+        if let Err(err) = result {
+            let err = environment.transaction.rollback(&environment.wheels, err);
+            failure.get_or_insert(err);
+            continue;
+        }
+
+        if venvs.len() > 1 {
+            writeln!(
+                printer,
+                "Installing into: {}",
+                environment.venv.root().simplified_display().cyan()
+            )?;
+        }
+        report(&environment, &resolution, duration, printer)?;
 
-    // Validate the environment.
-    if strict {
-        validate(&resolution, &venv, printer)?;
+        // Validate the environment.
+        if strict {
+            validate(&resolution, environment.venv, printer)?;
+        }
+    }
+    if let Some(err) = failure {
+        return Err(err);
     }
 
     Ok(ExitStatus::Success)
 }
 
+/// Install packages into the given virtual environment, with the default installation settings.
+///
+/// Used to populate the ephemeral environments created by `uv run`, which only expose the index,
+/// network, and concurrency options.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn install_into(
+    venv: &Path,
+    requirements: &[RequirementsSource],
+    index_locations: IndexLocations,
+    connectivity: Connectivity,
+    exclude_newer: Option<DateTime<Utc>>,
+    concurrency: Concurrency,
+    cache: &Cache,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    pip_install(
+        requirements,
+        &[],
+        &[],
+        &ExtrasSpecification::None,
+        ResolutionMode::default(),
+        PreReleaseMode::default(),
+        DependencyMode::Transitive,
+        Upgrade::None,
+        UpgradeStrategy::default(),
+        index_locations,
+        &Reinstall::None,
+        LinkMode::default(),
+        &[],
+        &ScriptNaming::default(),
+        false,
+        &HashDatabase::default(),
+        SetupPyStrategy::default(),
+        connectivity,
+        &ConfigSettings::default(),
+        false,
+        &[],
+        &[],
+        &NoBuild::None,
+        &NoBinary::None,
+        false,
+        exclude_newer,
+        None,
+        None,
+        MinReleaseAge::default(),
+        None,
+        false,
+        false,
+        false,
+        false,
+        vec![venv.to_path_buf()],
+        None,
+        None,
+        concurrency,
+        cache.clone(),
+        printer,
+    )
+    .await
+}
+
 /// Consolidate the requirements for an installation.
 fn specification(
     requirements: &[RequirementsSource],
@@ -332,6 +675,14 @@ fn specification(
     let spec =
         RequirementsSpecification::from_sources(requirements, constraints, overrides, extras)?;
 
+    // Fail fast if the requirements and constraints pin incompatible versions of a package.
+    check_conflicting_pins(&spec.provenance)?;
+
+    // Determining the requirements of a source tree requires building it.
+    if !spec.source_trees.is_empty() {
+        return Err(anyhow!(SOURCE_TREES_UNSUPPORTED).into());
+    }
+
     // Check that all provided extras are used
     if let ExtrasSpecification::Some(extras) = extras {
         let mut unused_extras = extras
@@ -408,11 +759,13 @@ async fn resolve(
     requirements: Vec<Requirement>,
     constraints: Vec<Requirement>,
     overrides: Vec<Requirement>,
+    provenance: Vec<Provenance>,
     project: Option<PackageName>,
     editables: &[BuiltEditable],
     site_packages: &SitePackages<'_>,
     reinstall: &Reinstall,
     upgrade: &Upgrade,
+    upgrade_strategy: UpgradeStrategy,
     interpreter: &Interpreter,
     tags: &Tags,
     markers: &MarkerEnvironment,
@@ -425,7 +778,9 @@ async fn resolve(
 ) -> Result<ResolutionGraph, Error> {
     let start = std::time::Instant::now();
 
-    let preferences = if upgrade.is_all() || reinstall.is_all() {
+    let preferences = if reinstall.is_all()
+        || (upgrade.is_all() && matches!(upgrade_strategy, UpgradeStrategy::Eager))
+    {
         vec![]
     } else {
         // Combine upgrade and reinstall lists
@@ -435,9 +790,15 @@ async fn resolve(
         } else {
             HashSet::default()
         };
-        if let Upgrade::Packages(packages) = upgrade {
-            exclusions.extend(packages);
-        };
+        match upgrade {
+            Upgrade::None => {}
+            // Upgrade the requested packages, but only upgrade their dependencies if the installed
+            // versions no longer satisfy the requirements.
+            Upgrade::All => {
+                exclusions.extend(requirements.iter().map(|requirement| &requirement.name))
+            }
+            Upgrade::Packages(packages) => exclusions.extend(packages),
+        }
 
         // Prefer current site packages, unless in the upgrade or reinstall lists
         site_packages
@@ -465,7 +826,8 @@ async fn resolve(
         preferences,
         project,
         editables,
-    );
+    )
+    .with_provenance(provenance);
 
     // Resolve the dependencies.
     let resolver = Resolver::new(
@@ -497,26 +859,41 @@ async fn resolve(
     Ok(resolution)
 }
 
-/// Install a set of requirements into the current environment.
+/// The changes to apply to an environment, with any missing distributions downloaded into the
+/// cache, and any distributions that are being replaced already removed.
+struct Prepared<'a> {
+    venv: &'a PythonEnvironment,
+    /// The distributions to link into the environment.
+    wheels: Vec<CachedDist>,
+    /// The distributions that were removed from the environment, to be replaced.
+    reinstalls: Vec<InstalledDist>,
+    /// The distributions that had to be downloaded, as resolved.
+    remote: Vec<Dist>,
+    /// The journal of the removals, such that they can be rolled back if linking fails.
+    transaction: uv_installer::Transaction<'a>,
+    /// Defers cancellation on interrupt until the transaction is committed or rolled back.
+    _shield: interrupt::ShieldGuard,
+}
+
+/// Prepare to install a set of requirements into an environment, by downloading any missing
+/// distributions and removing any existing installations that are to be replaced.
 #[allow(clippy::too_many_arguments)]
-async fn install(
+async fn prepare<'a>(
     resolution: &Resolution,
     built_editables: Vec<BuiltEditable>,
     site_packages: SitePackages<'_>,
     reinstall: &Reinstall,
     no_binary: &NoBinary,
-    link_mode: LinkMode,
+    provenance: &[Provenance],
     index_urls: &IndexLocations,
     tags: &Tags,
     client: &RegistryClient,
     in_flight: &InFlight,
     build_dispatch: &BuildDispatch<'_>,
     cache: &Cache,
-    venv: &PythonEnvironment,
+    venv: &'a PythonEnvironment,
     mut printer: Printer,
-) -> Result<(), Error> {
-    let start = std::time::Instant::now();
-
+) -> Result<Prepared<'a>, Error> {
     let requirements = resolution.requirements();
 
     // Map the built editables to their resolved form.
@@ -534,6 +911,7 @@ async fn install(
         extraneous: _,
     } = Planner::with_requirements(&requirements)
         .with_editable_requirements(&editables)
+        .with_connectivity(client.connectivity())
         .build(
             site_packages,
             reinstall,
@@ -545,23 +923,6 @@ async fn install(
         )
         .context("Failed to determine installation plan")?;
 
-    // Nothing to do.
-    if remote.is_empty() && local.is_empty() && reinstalls.is_empty() {
-        let s = if resolution.len() == 1 { "" } else { "s" };
-        writeln!(
-            printer,
-            "{}",
-            format!(
-                "Audited {} in {}",
-                format!("{} package{}", resolution.len(), s).bold(),
-                elapsed(start.elapsed())
-            )
-            .dimmed()
-        )?;
-
-        return Ok(());
-    }
-
     // Map any registry-based requirements back to those returned by the resolver.
     let remote = remote
         .iter()
@@ -585,6 +946,7 @@ async fn install(
         let wheels = downloader
             .download(remote.clone(), in_flight)
             .await
+            .map_err(|err| download_error(err, provenance))
             .context("Failed to download distributions")?;
 
         let s = if wheels.len() == 1 { "" } else { "s" };
@@ -602,51 +964,130 @@ async fn install(
         wheels
     };
 
-    // Remove any existing installations.
-    if !reinstalls.is_empty() {
-        for dist_info in &reinstalls {
-            let summary = uv_installer::uninstall(dist_info).await?;
-            debug!(
-                "Uninstalled {} ({} file{}, {} director{})",
-                dist_info.name(),
-                summary.file_count,
-                if summary.file_count == 1 { "" } else { "s" },
-                summary.dir_count,
-                if summary.dir_count == 1 { "y" } else { "ies" },
-            );
+    // If yanked distributions are treated as errors, fail before modifying the environment, rather
+    // than leaving them installed.
+    if uv_warnings::is_error(WarningCode::Yanked) {
+        warn_yanked(&remote);
+        if uv_warnings::has_errors() {
+            return Err(anyhow!("Refusing to install yanked distributions").into());
         }
     }
 
-    // Install the resolved distributions.
-    let wheels = wheels.into_iter().chain(local).collect::<Vec<_>>();
-    if !wheels.is_empty() {
-        let start = std::time::Instant::now();
-        uv_installer::Installer::new(venv)
-            .with_link_mode(link_mode)
-            .with_reporter(InstallReporter::from(printer).with_length(wheels.len() as u64))
-            .install(&wheels)?;
+    // Remove any existing installations, journaling the removals such that they can be rolled back
+    // if the installation fails (or is interrupted).
+    let shield = interrupt::shield();
+    let mut transaction = uv_installer::Transaction::new(venv);
+    for dist_info in &reinstalls {
+        if interrupt::interrupted() {
+            let err = anyhow!("Installation was interrupted");
+            return Err(transaction.rollback(&[], err).into());
+        }
+        let summary = match transaction.uninstall(dist_info).await {
+            Ok(summary) => summary,
+            Err(err) => return Err(transaction.rollback(&[], err).into()),
+        };
+        debug!(
+            "Uninstalled {} ({} file{}, {} director{})",
+            dist_info.name(),
+            summary.file_count,
+            if summary.file_count == 1 { "" } else { "s" },
+            summary.dir_count,
+            if summary.dir_count == 1 { "y" } else { "ies" },
+        );
+    }
 
-        let s = if wheels.len() == 1 { "" } else { "s" };
+    Ok(Prepared {
+        venv,
+        wheels: wheels.into_iter().chain(local).collect(),
+        reinstalls,
+        remote,
+        transaction,
+        _shield: shield,
+    })
+}
+
+/// Link the prepared distributions into their environment.
+#[allow(clippy::too_many_arguments)]
+fn link(
+    prepared: &Prepared,
+    link_mode: LinkMode,
+    no_zip: &[PackageName],
+    script_naming: &ScriptNaming,
+    scripts: bool,
+    verify_hashes: bool,
+    requested: &[PackageName],
+    printer: Option<Printer>,
+) -> Result<()> {
+    if prepared.wheels.is_empty() {
+        return Ok(());
+    }
+
+    let installer = uv_installer::Installer::new(prepared.venv)
+        .with_link_mode(link_mode)
+        .with_no_zip(no_zip.to_vec())
+        .with_script_naming(script_naming.clone())
+        .with_scripts(scripts)
+        .with_verify_hashes(verify_hashes)
+        .with_requested(requested.to_vec())
+        .with_interrupt(interrupt::flag());
+
+    // Progress bars for concurrent installs would overwrite one another, so only report progress
+    // when installing into a single environment.
+    if let Some(printer) = printer {
+        installer
+            .with_reporter(InstallReporter::from(printer).with_length(prepared.wheels.len() as u64))
+            .install(&prepared.wheels)
+    } else {
+        installer.install(&prepared.wheels)
+    }
+}
+
+/// Report the changes that were made to an environment.
+fn report(
+    prepared: &Prepared,
+    resolution: &Resolution,
+    duration: Duration,
+    mut printer: Printer,
+) -> Result<(), Error> {
+    // Nothing to do.
+    if prepared.wheels.is_empty() && prepared.reinstalls.is_empty() {
+        let s = if resolution.len() == 1 { "" } else { "s" };
+        writeln!(
+            printer,
+            "{}",
+            format!(
+                "Audited {} in {}",
+                format!("{} package{}", resolution.len(), s).bold(),
+                elapsed(duration)
+            )
+            .dimmed()
+        )?;
+        return Ok(());
+    }
+
+    if !prepared.wheels.is_empty() {
+        let s = if prepared.wheels.len() == 1 { "" } else { "s" };
         writeln!(
             printer,
             "{}",
             format!(
                 "Installed {} in {}",
-                format!("{} package{}", wheels.len(), s).bold(),
-                elapsed(start.elapsed())
+                format!("{} package{}", prepared.wheels.len(), s).bold(),
+                elapsed(duration)
             )
             .dimmed()
         )?;
     }
 
-    for event in reinstalls
-        .into_iter()
+    for event in prepared
+        .reinstalls
+        .iter()
         .map(|distribution| ChangeEvent {
-            dist: LocalDist::from(distribution),
+            dist: LocalDist::from(distribution.clone()),
             kind: ChangeEventKind::Removed,
         })
-        .chain(wheels.into_iter().map(|distribution| ChangeEvent {
-            dist: LocalDist::from(distribution),
+        .chain(prepared.wheels.iter().map(|distribution| ChangeEvent {
+            dist: LocalDist::from(distribution.clone()),
             kind: ChangeEventKind::Added,
         }))
         .sorted_unstable_by(|a, b| {
@@ -679,34 +1120,34 @@ async fn install(
         }
     }
 
+    warn_yanked(&prepared.remote);
+
+    Ok(())
+}
+
+/// Warn about any yanked distributions that were downloaded for installation.
+///
+/// Warnings are only emitted once, so it's safe to call this both before and after installing.
+fn warn_yanked(remote: &[Dist]) {
     // TODO(konstin): Also check the cache whether any cached or installed dist is already known to
     // have been yanked, we currently don't show this message on the second run anymore
-    for dist in &remote {
+    for dist in remote {
         let Some(file) = dist.file() else {
             continue;
         };
         match &file.yanked {
             None | Some(Yanked::Bool(false)) => {}
             Some(Yanked::Bool(true)) => {
-                writeln!(
-                    printer,
-                    "{}{} {dist} is yanked.",
-                    "warning".yellow().bold(),
-                    ":".bold(),
-                )?;
+                warn_user_code!(WarningCode::Yanked, "{dist} is yanked.");
             }
             Some(Yanked::Reason(reason)) => {
-                writeln!(
-                    printer,
-                    "{}{} {dist} is yanked (reason: \"{reason}\").",
-                    "warning".yellow().bold(),
-                    ":".bold(),
-                )?;
+                warn_user_code!(
+                    WarningCode::Yanked,
+                    "{dist} is yanked (reason: \"{reason}\")."
+                );
             }
         }
     }
-
-    Ok(())
 }
 
 /// Validate the installed packages in the virtual environment.
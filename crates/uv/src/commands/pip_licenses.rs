@@ -0,0 +1,222 @@
+use std::fmt::Write;
+
+use anstream::{print, println};
+use anyhow::Result;
+use itertools::Itertools;
+use owo_colors::OwoColorize;
+use serde::Serialize;
+use tracing::debug;
+
+use distribution_types::{InstalledDist, Name};
+use platform_host::Platform;
+use pypi_types::LicenseMetadata;
+use uv_cache::Cache;
+use uv_fs::Simplified;
+use uv_installer::SitePackages;
+use uv_interpreter::PythonEnvironment;
+
+use crate::commands::ExitStatus;
+use crate::printer::Printer;
+
+/// The label used for packages that don't declare a license.
+const UNKNOWN: &str = "UNKNOWN";
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub(crate) enum LicensesFormat {
+    /// Display the packages grouped by license.
+    Grouped,
+    /// Display the packages as a JSON array.
+    Json,
+    /// Display the packages as CSV, with one row per package.
+    Csv,
+}
+
+/// An installed package, as reported by `--format json` or `--format csv`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+struct LicenseEntry {
+    name: String,
+    version: String,
+    /// The license under which the package is grouped: an SPDX expression, if one can be
+    /// determined, and otherwise a description of the declared license.
+    license: String,
+    /// The `License-Expression` field, as declared in the package metadata.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    license_expression: Option<String>,
+    /// The `License` classifiers, as declared in the package metadata, separated by `; `.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    classifiers: Option<String>,
+}
+
+/// Report the licenses of the installed packages in the current environment.
+pub(crate) fn pip_licenses(
+    format: LicensesFormat,
+    fail_on: &[String],
+    python: Option<&str>,
+    system: bool,
+    cache: &Cache,
+    mut printer: Printer,
+) -> Result<ExitStatus> {
+    // Detect the current Python interpreter.
+    let platform = Platform::current()?;
+    let venv = if let Some(python) = python {
+        PythonEnvironment::from_requested_python(python, &platform, cache)?
+    } else if system {
+        PythonEnvironment::from_default_python(&platform, cache)?
+    } else {
+        match PythonEnvironment::from_virtualenv(platform.clone(), cache) {
+            Ok(venv) => venv,
+            Err(uv_interpreter::Error::VenvNotFound) => {
+                PythonEnvironment::from_default_python(&platform, cache)?
+            }
+            Err(err) => return Err(err.into()),
+        }
+    };
+
+    debug!(
+        "Using Python {} environment at {}",
+        venv.interpreter().python_version(),
+        venv.python_executable().simplified_display().cyan()
+    );
+
+    // Build the installed index.
+    let site_packages = SitePackages::from_executable(&venv)?;
+
+    // Read the licensing information for each package; always sort by name.
+    let packages = site_packages
+        .iter()
+        .sorted_unstable_by(|a, b| a.name().cmp(b.name()).then(a.version().cmp(b.version())))
+        .map(|dist| {
+            let metadata = dist.license_metadata().unwrap_or_else(|err| {
+                debug!("Failed to read license for {}: {err}", dist.name());
+                LicenseMetadata::default()
+            });
+            let license = license_label(&metadata);
+            (dist, metadata, license)
+        })
+        .collect_vec();
+
+    match format {
+        LicensesFormat::Json => {
+            let entries = packages
+                .iter()
+                .map(|(dist, metadata, license)| entry(dist, metadata, license))
+                .collect_vec();
+            println!("{}", serde_json::to_string_pretty(&entries)?);
+        }
+        LicensesFormat::Csv => {
+            // Write the records directly (rather than serializing `LicenseEntry`), such that
+            // every row has the same columns, even if some fields are missing.
+            let mut writer = csv::Writer::from_writer(Vec::new());
+            writer.write_record([
+                "name",
+                "version",
+                "license",
+                "license_expression",
+                "classifiers",
+            ])?;
+            for (dist, metadata, license) in &packages {
+                let entry = entry(dist, metadata, license);
+                writer.write_record([
+                    entry.name,
+                    entry.version,
+                    entry.license,
+                    entry.license_expression.unwrap_or_default(),
+                    entry.classifiers.unwrap_or_default(),
+                ])?;
+            }
+            print!("{}", String::from_utf8(writer.into_inner()?)?);
+        }
+        LicensesFormat::Grouped => {
+            // List the most common licenses first.
+            let groups = packages
+                .iter()
+                .into_group_map_by(|(.., license)| license.as_str())
+                .into_iter()
+                .sorted_unstable_by(|(a, a_packages), (b, b_packages)| {
+                    b_packages.len().cmp(&a_packages.len()).then(a.cmp(b))
+                });
+            for (license, packages) in groups {
+                let s = if packages.len() == 1 { "" } else { "s" };
+                println!(
+                    "{} {}",
+                    license.bold(),
+                    format!("({} package{s})", packages.len()).dimmed()
+                );
+                for (dist, ..) in packages {
+                    println!(
+                        "  {}{}",
+                        dist.name(),
+                        format!("=={}", dist.version()).dimmed()
+                    );
+                }
+            }
+        }
+    }
+
+    // Check the packages against the denylist.
+    let denied = packages
+        .iter()
+        .filter(|(.., license)| fail_on.iter().any(|denied| references(license, denied)))
+        .collect_vec();
+    if denied.is_empty() {
+        return Ok(ExitStatus::Success);
+    }
+
+    let s = if denied.len() == 1 { "" } else { "s" };
+    writeln!(
+        printer,
+        "{}{} Found {} with a denied license:",
+        "error".red().bold(),
+        ":".bold(),
+        format!("{} package{s}", denied.len()).bold()
+    )?;
+    for (dist, _, license) in denied {
+        writeln!(
+            printer,
+            " {} {}{} ({})",
+            "-".red(),
+            dist.name().as_ref().bold(),
+            format!("=={}", dist.version()).dimmed(),
+            license
+        )?;
+    }
+
+    Ok(ExitStatus::Failure)
+}
+
+/// Return the label under which a package is grouped: its SPDX expression, if one can be
+/// determined, and otherwise a description of its declared license.
+fn license_label(metadata: &LicenseMetadata) -> String {
+    metadata
+        .spdx_expression()
+        .or_else(|| metadata.description())
+        .unwrap_or_else(|| UNKNOWN.to_string())
+}
+
+fn entry(dist: &InstalledDist, metadata: &LicenseMetadata, license: &str) -> LicenseEntry {
+    LicenseEntry {
+        name: dist.name().to_string(),
+        version: dist.version().to_string(),
+        license: license.to_string(),
+        license_expression: metadata.license_expression.clone(),
+        classifiers: (!metadata.classifiers.is_empty()).then(|| metadata.classifiers.join("; ")),
+    }
+}
+
+/// Returns `true` if the license is, or references, the denied license expression.
+///
+/// For example, `GPL-3.0-only` is referenced by both `GPL-3.0-only` and `MIT OR GPL-3.0-only`,
+/// since a package that _may_ be used under a denied license should be reviewed. Comparisons are
+/// case-insensitive, as in SPDX.
+fn references(license: &str, denied: &str) -> bool {
+    let denied = denied.trim();
+    if license.eq_ignore_ascii_case(denied) {
+        return true;
+    }
+    license
+        .split(|c: char| c.is_whitespace() || c == '(' || c == ')')
+        .filter(|token| !token.is_empty())
+        .filter(|token| !matches!(*token, "AND" | "OR" | "WITH"))
+        .any(|token| token.eq_ignore_ascii_case(denied))
+}
@@ -1,14 +1,19 @@
 use std::cmp::max;
 use std::fmt::Write;
+use std::path::Path;
+use std::str::FromStr;
 
 use anstream::println;
 use anyhow::Result;
 use itertools::Itertools;
 use owo_colors::OwoColorize;
+use rustc_hash::FxHashMap;
+use serde::Serialize;
 use tracing::debug;
 use unicode_width::UnicodeWidthStr;
 
-use distribution_types::Name;
+use distribution_types::{InstalledDist, Name};
+use install_wheel_rs::DiskUsage;
 use platform_host::Platform;
 use uv_cache::Cache;
 use uv_fs::Simplified;
@@ -16,16 +21,46 @@ use uv_installer::SitePackages;
 use uv_interpreter::PythonEnvironment;
 use uv_normalize::PackageName;
 
-use crate::commands::ExitStatus;
+use crate::commands::{format_bytes, ExitStatus};
 use crate::printer::Printer;
 
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub(crate) enum ListFormat {
+    /// Display the packages in a human-readable table.
+    Columns,
+    /// Display the packages as a JSON array.
+    Json,
+}
+
+/// An installed package, as reported by `--format json`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+struct ListEntry {
+    name: String,
+    version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    editable_project_location: Option<String>,
+    /// The disk usage of the package, in bytes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    size: Option<u64>,
+    /// The portion of `size` that's hardlinked from outside the environment, in bytes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    linked_size: Option<u64>,
+}
+
 /// Enumerate the installed packages in the current environment.
 #[allow(clippy::too_many_arguments, clippy::fn_params_excessive_bools)]
 pub(crate) fn pip_list(
     strict: bool,
     editable: bool,
     exclude_editable: bool,
-    exclude: &[PackageName],
+    include: &[PackagePattern],
+    exclude: &[PackagePattern],
+    installer: Option<&str>,
+    location: Option<&Path>,
+    not_required: bool,
+    sizes: bool,
+    format: ListFormat,
     python: Option<&str>,
     system: bool,
     cache: &Cache,
@@ -56,56 +91,141 @@ pub(crate) fn pip_list(
     // Build the installed index.
     let site_packages = SitePackages::from_executable(&venv)?;
 
+    // Resolve the `--location` filter, such that it can be compared against the installed paths.
+    let location = location.map(fs_err::canonicalize).transpose()?;
+
+    // Determine the packages that are required by other installed packages.
+    let required = not_required.then(|| site_packages.required());
+
     // Filter if `--editable` is specified; always sort by name.
     let results = site_packages
         .iter()
         .filter(|f| (!f.is_editable() && !editable) || (f.is_editable() && !exclude_editable))
-        .filter(|f| !exclude.contains(f.name()))
+        .filter(|f| include.is_empty() || include.iter().any(|pattern| pattern.matches(f.name())))
+        .filter(|f| !exclude.iter().any(|pattern| pattern.matches(f.name())))
+        .filter(|f| installer.map_or(true, |installer| installed_by(f, installer)))
+        .filter(|f| {
+            location
+                .as_deref()
+                .map_or(true, |location| in_location(f, location))
+        })
+        .filter(|f| {
+            required.as_ref().map_or(true, |required| {
+                f.is_requested() || !required.contains(f.name())
+            })
+        })
         .sorted_unstable_by(|a, b| a.name().cmp(b.name()).then(a.version().cmp(b.version())))
         .collect_vec();
-    if results.is_empty() {
-        return Ok(ExitStatus::Success);
-    }
 
-    // The package name and version are always present.
-    let mut columns = vec![
-        Column {
-            header: String::from("Package"),
-            rows: results.iter().map(|f| f.name().to_string()).collect_vec(),
-        },
-        Column {
-            header: String::from("Version"),
-            rows: results
+    // If requested, measure the disk usage of each package. Usage is computed across the entire
+    // environment, such that files shared between packages are attributed correctly, even if some
+    // packages are filtered out.
+    let usage: Option<FxHashMap<&Path, DiskUsage>> = if sizes {
+        let installed = site_packages.iter().collect_vec();
+        let usage = install_wheel_rs::disk_usage(installed.iter().map(|f| f.path()))?;
+        Some(
+            installed
                 .iter()
-                .map(|f| f.version().to_string())
-                .collect_vec(),
-        },
-    ];
-
-    // Editable column is only displayed if at least one editable package is found.
-    if results.iter().any(|f| f.is_editable()) {
-        columns.push(Column {
-            header: String::from("Editable project location"),
-            rows: results
+                .map(|f| f.path())
+                .zip(usage)
+                .collect::<FxHashMap<_, _>>(),
+        )
+    } else {
+        None
+    };
+    let size_of = |dist: &InstalledDist| -> Option<DiskUsage> {
+        usage
+            .as_ref()
+            .and_then(|usage| usage.get(dist.path()).copied())
+    };
+
+    // When reporting sizes, list the largest packages first.
+    let results = if usage.is_some() {
+        results
+            .into_iter()
+            .sorted_by_key(|f| std::cmp::Reverse(size_of(f).map(|usage| usage.size)))
+            .collect_vec()
+    } else {
+        results
+    };
+
+    match format {
+        ListFormat::Json => {
+            let entries = results
                 .iter()
-                .map(|f| f.as_editable())
-                .map(|e| {
-                    if let Some(url) = e {
-                        url.to_file_path()
-                            .unwrap()
-                            .into_os_string()
-                            .into_string()
-                            .unwrap()
-                    } else {
-                        String::new()
-                    }
+                .map(|f| ListEntry {
+                    name: f.name().to_string(),
+                    version: f.version().to_string(),
+                    editable_project_location: editable_location(f),
+                    size: size_of(f).map(|usage| usage.size),
+                    linked_size: size_of(f).map(|usage| usage.linked),
                 })
-                .collect_vec(),
-        });
-    }
+                .collect_vec();
+            println!("{}", serde_json::to_string_pretty(&entries)?);
+        }
+        ListFormat::Columns if results.is_empty() => {}
+        ListFormat::Columns => {
+            // The package name and version are always present.
+            let mut columns = vec![
+                Column {
+                    header: String::from("Package"),
+                    rows: results.iter().map(|f| f.name().to_string()).collect_vec(),
+                },
+                Column {
+                    header: String::from("Version"),
+                    rows: results
+                        .iter()
+                        .map(|f| f.version().to_string())
+                        .collect_vec(),
+                },
+            ];
 
-    for elems in Multizip(columns.iter().map(Column::fmt_padded).collect_vec()) {
-        println!("{0}", elems.join(" "));
+            // Editable column is only displayed if at least one editable package is found.
+            if results.iter().any(|f| f.is_editable()) {
+                columns.push(Column {
+                    header: String::from("Editable project location"),
+                    rows: results
+                        .iter()
+                        .map(|f| editable_location(f).unwrap_or_default())
+                        .collect_vec(),
+                });
+            }
+
+            // Size column is only displayed if `--sizes` is specified.
+            if usage.is_some() {
+                columns.push(Column {
+                    header: String::from("Size"),
+                    rows: results
+                        .iter()
+                        .map(|f| {
+                            size_of(f)
+                                .map(|usage| format_bytes(usage.size))
+                                .unwrap_or_default()
+                        })
+                        .collect_vec(),
+                });
+            }
+
+            for elems in Multizip(columns.iter().map(Column::fmt_padded).collect_vec()) {
+                println!("{0}", elems.join(" "));
+            }
+
+            // Summarize the total disk usage of the listed packages.
+            if usage.is_some() {
+                let (size, linked) = results
+                    .iter()
+                    .filter_map(|f| size_of(f))
+                    .fold((0, 0), |(size, linked), usage| {
+                        (size + usage.size, linked + usage.linked)
+                    });
+                writeln!(
+                    printer,
+                    "Total: {} ({} shared via hardlinks)",
+                    format_bytes(size).bold(),
+                    format_bytes(linked)
+                )?;
+            }
+        }
     }
 
     // Validate that the environment is consistent.
@@ -124,6 +244,77 @@ pub(crate) fn pip_list(
     Ok(ExitStatus::Success)
 }
 
+/// Returns the location of the project, if the distribution is an editable install.
+fn editable_location(dist: &InstalledDist) -> Option<String> {
+    dist.as_editable().map(|url| {
+        url.to_file_path()
+            .unwrap()
+            .into_os_string()
+            .into_string()
+            .unwrap()
+    })
+}
+
+/// Returns `true` if the distribution was installed by the given installer (e.g., `uv` or `pip`),
+/// as recorded in its `INSTALLER` file.
+fn installed_by(dist: &InstalledDist, installer: &str) -> bool {
+    match dist.installer() {
+        Ok(Some(recorded)) => recorded.trim().eq_ignore_ascii_case(installer.trim()),
+        Ok(None) => false,
+        Err(err) => {
+            debug!("Failed to read `INSTALLER` for {}: {err}", dist.name());
+            false
+        }
+    }
+}
+
+/// Returns `true` if the distribution is installed within the given directory.
+fn in_location(dist: &InstalledDist, location: &Path) -> bool {
+    if dist.path().starts_with(location) {
+        return true;
+    }
+    // The `site-packages` directory may be reached via a symlink (e.g., `lib64`).
+    fs_err::canonicalize(dist.path()).is_ok_and(|path| path.starts_with(location))
+}
+
+/// A pattern to match against package names, like `boto*` or `types-?ython-*`.
+///
+/// Patterns use glob syntax (`*`, `?`, and `[...]`) and are matched against the normalized package
+/// name, such that `Django_*` and `django-*` are equivalent. A pattern without any wildcards
+/// matches a single package by name.
+#[derive(Debug, Clone)]
+pub(crate) struct PackagePattern(glob::Pattern);
+
+impl PackagePattern {
+    /// Returns `true` if the pattern matches the given package name.
+    pub(crate) fn matches(&self, name: &PackageName) -> bool {
+        self.0.matches(name.as_ref())
+    }
+}
+
+impl FromStr for PackagePattern {
+    type Err = glob::PatternError;
+
+    fn from_str(pattern: &str) -> Result<Self, Self::Err> {
+        // Normalize the pattern in the same way as package names (per PEP 503), while preserving
+        // any wildcards.
+        let mut normalized = String::with_capacity(pattern.len());
+        let mut last = None;
+        for char in pattern.trim().chars() {
+            if matches!(char, '-' | '_' | '.') {
+                if last != Some('-') {
+                    normalized.push('-');
+                }
+                last = Some('-');
+            } else {
+                normalized.push(char.to_ascii_lowercase());
+                last = Some(char);
+            }
+        }
+        glob::Pattern::new(&normalized).map(Self)
+    }
+}
+
 #[derive(Debug)]
 struct Column {
     /// The header of the column.
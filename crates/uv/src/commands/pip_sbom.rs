@@ -0,0 +1,328 @@
+use std::path::Path;
+
+use anstream::println;
+use anyhow::Result;
+use chrono::{SecondsFormat, Utc};
+use owo_colors::OwoColorize;
+use rustc_hash::{FxHashMap, FxHashSet};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use tracing::debug;
+
+use distribution_types::{InstalledDist, Name};
+use platform_host::Platform;
+use pypi_types::{DirectUrl, LicenseMetadata};
+use uv_cache::Cache;
+use uv_fs::Simplified;
+use uv_installer::SitePackages;
+use uv_interpreter::PythonEnvironment;
+use uv_normalize::PackageName;
+
+use crate::commands::ExitStatus;
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub(crate) enum SbomFormat {
+    /// A CycloneDX 1.5 document, in JSON.
+    CyclonedxJson,
+    /// An SPDX 2.3 document, in JSON.
+    SpdxJson,
+}
+
+/// An installed package, along with the information reported in the SBOM.
+struct Component<'a> {
+    dist: &'a InstalledDist,
+    /// The Package URL (purl) of the package, like `pkg:pypi/flask@3.0.0`.
+    purl: String,
+    /// The SHA-256 digest of the archive from which the package was installed, as a hex string, if
+    /// known (i.e., if it was recorded in `direct_url.json`).
+    archive_hash: Option<String>,
+    /// The SHA-256 digest of the package's installed `RECORD`, which in turn lists the hash of
+    /// every installed file, as a hex string.
+    ///
+    /// This identifies the installed files, not the archive, so it's reported separately from the
+    /// archive's hash.
+    record_hash: Option<String>,
+    license: LicenseMetadata,
+    /// The installed packages that this package depends on.
+    dependencies: Vec<&'a PackageName>,
+}
+
+/// Generate a software bill of materials (SBOM) for the packages installed in the current
+/// environment.
+pub(crate) fn pip_sbom(
+    format: SbomFormat,
+    python: Option<&str>,
+    system: bool,
+    cache: &Cache,
+) -> Result<ExitStatus> {
+    // Detect the current Python interpreter.
+    let platform = Platform::current()?;
+    let venv = if let Some(python) = python {
+        PythonEnvironment::from_requested_python(python, &platform, cache)?
+    } else if system {
+        PythonEnvironment::from_default_python(&platform, cache)?
+    } else {
+        match PythonEnvironment::from_virtualenv(platform.clone(), cache) {
+            Ok(venv) => venv,
+            Err(uv_interpreter::Error::VenvNotFound) => {
+                PythonEnvironment::from_default_python(&platform, cache)?
+            }
+            Err(err) => return Err(err.into()),
+        }
+    };
+
+    debug!(
+        "Using Python {} environment at {}",
+        venv.interpreter().python_version(),
+        venv.python_executable().simplified_display().cyan()
+    );
+
+    // Build the installed index.
+    let site_packages = SitePackages::from_executable(&venv)?;
+    let installed = site_packages
+        .iter()
+        .map(Name::name)
+        .collect::<FxHashSet<_>>();
+
+    let mut components = Vec::new();
+    for dist in site_packages.iter() {
+        let dependencies = match dist.metadata() {
+            Ok(metadata) => {
+                let mut dependencies = metadata
+                    .requires_dist
+                    .iter()
+                    .filter(|requirement| {
+                        requirement.evaluate_markers(venv.interpreter().markers(), &[])
+                    })
+                    .filter_map(|requirement| installed.get(&requirement.name).copied())
+                    .filter(|name| *name != dist.name())
+                    .collect::<Vec<_>>();
+                dependencies.sort_unstable();
+                dependencies.dedup();
+                dependencies
+            }
+            Err(err) => {
+                debug!("Failed to read metadata for {}: {err}", dist.name());
+                Vec::new()
+            }
+        };
+        let license = dist.license_metadata().unwrap_or_else(|err| {
+            debug!("Failed to read license for {}: {err}", dist.name());
+            LicenseMetadata::default()
+        });
+        components.push(Component {
+            dist,
+            purl: purl(dist),
+            archive_hash: archive_hash(dist),
+            record_hash: record_hash(dist.path()),
+            license,
+            dependencies,
+        });
+    }
+    components.sort_unstable_by(|a, b| {
+        a.dist
+            .name()
+            .cmp(b.dist.name())
+            .then(a.dist.version().cmp(b.dist.version()))
+    });
+
+    let name = venv.root().file_name().map_or_else(
+        || "environment".to_string(),
+        |name| name.to_string_lossy().to_string(),
+    );
+    let timestamp = Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true);
+
+    let document = match format {
+        SbomFormat::CyclonedxJson => cyclonedx(&components, &timestamp),
+        SbomFormat::SpdxJson => spdx(&components, &name, &timestamp),
+    };
+    println!("{}", serde_json::to_string_pretty(&document)?);
+
+    Ok(ExitStatus::Success)
+}
+
+/// Render the components as a CycloneDX document.
+///
+/// See: <https://cyclonedx.org/docs/1.5/json/>
+fn cyclonedx(components: &[Component], timestamp: &str) -> Value {
+    let purls = components
+        .iter()
+        .map(|component| (component.dist.name(), component.purl.as_str()))
+        .collect::<FxHashMap<_, _>>();
+
+    json!({
+        "bomFormat": "CycloneDX",
+        "specVersion": "1.5",
+        "version": 1,
+        "metadata": {
+            "timestamp": timestamp,
+            "tools": [{
+                "name": "uv",
+                "version": env!("CARGO_PKG_VERSION"),
+            }],
+        },
+        "components": components.iter().map(|component| {
+            let mut value = json!({
+                "type": "library",
+                "bom-ref": component.purl,
+                "name": component.dist.name().to_string(),
+                "version": component.dist.version().to_string(),
+                "purl": component.purl,
+            });
+            if let Some(hash) = &component.archive_hash {
+                value["hashes"] = json!([{ "alg": "SHA-256", "content": hash }]);
+            }
+            if let Some(hash) = &component.record_hash {
+                value["properties"] = json!([{ "name": "uv:record-sha256", "value": hash }]);
+            }
+            let licenses = if let Some(expression) = &component.license.license_expression {
+                vec![json!({ "expression": expression })]
+            } else if !component.license.classifiers.is_empty() {
+                component
+                    .license
+                    .classifiers
+                    .iter()
+                    .map(|name| json!({ "license": { "name": name } }))
+                    .collect()
+            } else if let Some(license) = component.license.description() {
+                vec![json!({ "license": { "name": license } })]
+            } else {
+                Vec::new()
+            };
+            if !licenses.is_empty() {
+                value["licenses"] = Value::Array(licenses);
+            }
+            if let Some(url) = download_location(component.dist) {
+                value["externalReferences"] = json!([{ "type": "distribution", "url": url }]);
+            }
+            value
+        }).collect::<Vec<_>>(),
+        "dependencies": components.iter().map(|component| {
+            json!({
+                "ref": component.purl,
+                "dependsOn": component
+                    .dependencies
+                    .iter()
+                    .filter_map(|name| purls.get(name))
+                    .collect::<Vec<_>>(),
+            })
+        }).collect::<Vec<_>>(),
+    })
+}
+
+/// Render the components as an SPDX document.
+///
+/// See: <https://spdx.github.io/spdx-spec/v2.3/>
+fn spdx(components: &[Component], name: &str, timestamp: &str) -> Value {
+    let spdx_id = |name: &PackageName| format!("SPDXRef-Package-{name}");
+
+    let mut relationships = Vec::new();
+    for component in components {
+        relationships.push(json!({
+            "spdxElementId": "SPDXRef-DOCUMENT",
+            "relationshipType": "DESCRIBES",
+            "relatedSpdxElement": spdx_id(component.dist.name()),
+        }));
+        for dependency in &component.dependencies {
+            relationships.push(json!({
+                "spdxElementId": spdx_id(component.dist.name()),
+                "relationshipType": "DEPENDS_ON",
+                "relatedSpdxElement": spdx_id(dependency),
+            }));
+        }
+    }
+
+    json!({
+        "spdxVersion": "SPDX-2.3",
+        "dataLicense": "CC0-1.0",
+        "SPDXID": "SPDXRef-DOCUMENT",
+        "name": name,
+        "documentNamespace": format!("https://spdx.org/spdxdocs/uv-{name}-{timestamp}"),
+        "creationInfo": {
+            "created": timestamp,
+            "creators": [format!("Tool: uv-{}", env!("CARGO_PKG_VERSION"))],
+        },
+        "packages": components.iter().map(|component| {
+            let mut value = json!({
+                "SPDXID": spdx_id(component.dist.name()),
+                "name": component.dist.name().to_string(),
+                "versionInfo": component.dist.version().to_string(),
+                "downloadLocation": download_location(component.dist).unwrap_or_else(|| "NOASSERTION".to_string()),
+                "filesAnalyzed": false,
+                "licenseConcluded": "NOASSERTION",
+                "licenseDeclared": component.license.spdx_expression().unwrap_or_else(|| "NOASSERTION".to_string()),
+                "copyrightText": "NOASSERTION",
+                "externalRefs": [{
+                    "referenceCategory": "PACKAGE-MANAGER",
+                    "referenceType": "purl",
+                    "referenceLocator": component.purl,
+                }],
+            });
+            if let Some(hash) = &component.archive_hash {
+                value["checksums"] = json!([{ "algorithm": "SHA256", "checksumValue": hash }]);
+            }
+            if let Some(hash) = &component.record_hash {
+                value["annotations"] = json!([{
+                    "annotationType": "OTHER",
+                    "annotator": format!("Tool: uv-{}", env!("CARGO_PKG_VERSION")),
+                    "annotationDate": timestamp,
+                    "comment": format!("SHA-256 of the installed RECORD: {hash}"),
+                }]);
+            }
+            value
+        }).collect::<Vec<_>>(),
+        "relationships": relationships,
+    })
+}
+
+/// Return the Package URL (purl) for an installed distribution.
+///
+/// See: <https://github.com/package-url/purl-spec/blob/master/PURL-TYPES.rst#pypi>
+fn purl(dist: &InstalledDist) -> String {
+    // The `+` in a local version must be percent-encoded.
+    let version = dist.version().to_string().replace('+', "%2B");
+    format!("pkg:pypi/{}@{version}", dist.name())
+}
+
+/// Return the location from which a distribution was downloaded, if it was installed from a
+/// remote URL (rather than a registry or local directory).
+fn download_location(dist: &InstalledDist) -> Option<String> {
+    match dist.direct_url()? {
+        DirectUrl::LocalDirectory { .. } => None,
+        DirectUrl::ArchiveUrl { url, .. } => Some(url.clone()),
+        DirectUrl::VcsUrl { url, vcs_info, .. } => Some(match &vcs_info.commit_id {
+            Some(commit_id) => format!("{}+{url}@{commit_id}", vcs_info.vcs),
+            None => format!("{}+{url}", vcs_info.vcs),
+        }),
+    }
+}
+
+/// Return the SHA-256 digest of the archive from which a distribution was installed, as recorded
+/// in its `direct_url.json`.
+fn archive_hash(dist: &InstalledDist) -> Option<String> {
+    let DirectUrl::ArchiveUrl { archive_info, .. } = dist.direct_url()? else {
+        return None;
+    };
+    archive_info
+        .hashes
+        .as_ref()
+        .and_then(|hashes| hashes.get("sha256").cloned())
+        .or_else(|| {
+            archive_info
+                .hash
+                .as_deref()
+                .and_then(|hash| hash.strip_prefix("sha256="))
+                .map(ToString::to_string)
+        })
+}
+
+/// Compute the SHA-256 digest of the `RECORD` file in a `.dist-info` directory, as a hex string.
+fn record_hash(dist_info: &Path) -> Option<String> {
+    match fs_err::read(dist_info.join("RECORD")) {
+        Ok(contents) => Some(format!("{:x}", Sha256::digest(contents))),
+        Err(err) => {
+            debug!("Failed to read RECORD: {err}");
+            None
+        }
+    }
+}
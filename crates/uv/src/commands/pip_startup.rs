@@ -0,0 +1,198 @@
+use std::fmt::Write;
+
+use anstream::println;
+use anyhow::Result;
+use owo_colors::OwoColorize;
+use tracing::debug;
+
+use platform_host::Platform;
+use uv_cache::Cache;
+use uv_fs::Simplified;
+use uv_interpreter::PythonEnvironment;
+use uv_normalize::PackageName;
+
+use crate::commands::ExitStatus;
+use crate::printer::Printer;
+
+/// Install a startup hook into the current environment.
+pub(crate) fn pip_startup_add(
+    name: &PackageName,
+    code: &str,
+    python: Option<&str>,
+    system: bool,
+    cache: &Cache,
+    mut printer: Printer,
+) -> Result<ExitStatus> {
+    let venv = mutable_environment(python, system, cache)?;
+    let _lock = venv.lock()?;
+
+    let hook = install_wheel_rs::install_startup_hook(venv.site_packages(), name, code, "uv")?;
+    writeln!(
+        printer,
+        "Installed startup hook {} (owned by {})",
+        hook.name.as_ref().bold(),
+        hook.dist_name().dimmed()
+    )?;
+
+    Ok(ExitStatus::Success)
+}
+
+/// Enable or disable a startup hook in the current environment.
+pub(crate) fn pip_startup_toggle(
+    name: &PackageName,
+    enabled: bool,
+    python: Option<&str>,
+    system: bool,
+    cache: &Cache,
+    mut printer: Printer,
+) -> Result<ExitStatus> {
+    let venv = mutable_environment(python, system, cache)?;
+    let _lock = venv.lock()?;
+
+    let Some(hook) =
+        install_wheel_rs::set_startup_hook_enabled(venv.site_packages(), name, enabled)?
+    else {
+        writeln!(
+            printer,
+            "{}{} No startup hook named {} is installed.",
+            "warning".yellow().bold(),
+            ":".bold(),
+            name.as_ref().bold()
+        )?;
+        return Ok(ExitStatus::Failure);
+    };
+
+    writeln!(
+        printer,
+        "{} startup hook {}",
+        if hook.enabled { "Enabled" } else { "Disabled" },
+        hook.name.as_ref().bold()
+    )?;
+
+    Ok(ExitStatus::Success)
+}
+
+/// Remove a startup hook from the current environment, along with the distribution that owns it.
+pub(crate) fn pip_startup_remove(
+    name: &PackageName,
+    python: Option<&str>,
+    system: bool,
+    cache: &Cache,
+    mut printer: Printer,
+) -> Result<ExitStatus> {
+    let venv = mutable_environment(python, system, cache)?;
+    let _lock = venv.lock()?;
+
+    let Some(hook) = install_wheel_rs::find_startup_hook(venv.site_packages(), name)? else {
+        writeln!(
+            printer,
+            "{}{} No startup hook named {} is installed.",
+            "warning".yellow().bold(),
+            ":".bold(),
+            name.as_ref().bold()
+        )?;
+        return Ok(ExitStatus::Failure);
+    };
+
+    let summary = install_wheel_rs::uninstall_wheel(&hook.dist_info, false)?;
+    debug!(
+        "Uninstalled {} ({} file{})",
+        hook.dist_name(),
+        summary.file_count,
+        if summary.file_count == 1 { "" } else { "s" },
+    );
+
+    writeln!(
+        printer,
+        "Removed startup hook {}",
+        hook.name.as_ref().bold()
+    )?;
+
+    Ok(ExitStatus::Success)
+}
+
+/// List the startup hooks installed in the current environment.
+pub(crate) fn pip_startup_list(
+    python: Option<&str>,
+    system: bool,
+    cache: &Cache,
+    mut printer: Printer,
+) -> Result<ExitStatus> {
+    // Detect the current Python interpreter.
+    let platform = Platform::current()?;
+    let venv = if let Some(python) = python {
+        PythonEnvironment::from_requested_python(python, &platform, cache)?
+    } else if system {
+        PythonEnvironment::from_default_python(&platform, cache)?
+    } else {
+        match PythonEnvironment::from_virtualenv(platform.clone(), cache) {
+            Ok(venv) => venv,
+            Err(uv_interpreter::Error::VenvNotFound) => {
+                PythonEnvironment::from_default_python(&platform, cache)?
+            }
+            Err(err) => return Err(err.into()),
+        }
+    };
+
+    let hooks = install_wheel_rs::find_startup_hooks(venv.site_packages())?;
+    if hooks.is_empty() {
+        writeln!(printer, "No startup hooks installed.")?;
+        return Ok(ExitStatus::Success);
+    }
+
+    for hook in hooks {
+        let code = hook.code.lines().collect::<Vec<_>>().join("; ");
+        if hook.enabled {
+            println!("{}: {}", hook.name.as_ref().bold(), code);
+        } else {
+            println!(
+                "{} {}: {}",
+                hook.name.as_ref().bold(),
+                "(disabled)".dimmed(),
+                code
+            );
+        }
+    }
+
+    Ok(ExitStatus::Success)
+}
+
+/// Detect the environment to modify, refusing to modify externally managed environments.
+fn mutable_environment(
+    python: Option<&str>,
+    system: bool,
+    cache: &Cache,
+) -> Result<PythonEnvironment> {
+    // Detect the current Python interpreter.
+    let platform = Platform::current()?;
+    let venv = if let Some(python) = python {
+        PythonEnvironment::from_requested_python(python, &platform, cache)?
+    } else if system {
+        PythonEnvironment::from_default_python(&platform, cache)?
+    } else {
+        PythonEnvironment::from_virtualenv(platform, cache)?
+    };
+    debug!(
+        "Using Python {} environment at {}",
+        venv.interpreter().python_version(),
+        venv.python_executable().simplified_display().cyan(),
+    );
+
+    // If the environment is externally managed, abort.
+    if let Some(externally_managed) = venv.interpreter().is_externally_managed() {
+        return if let Some(error) = externally_managed.into_error() {
+            Err(anyhow::anyhow!(
+                "The interpreter at {} is externally managed, and indicates the following:\n\n{}\n\nConsider creating a virtual environment with `uv venv`.",
+                venv.root().simplified_display().cyan(),
+                textwrap::indent(&error, "  ").green(),
+            ))
+        } else {
+            Err(anyhow::anyhow!(
+                "The interpreter at {} is externally managed. Instead, create a virtual environment with `uv venv`.",
+                venv.root().simplified_display().cyan()
+            ))
+        };
+    }
+
+    Ok(venv)
+}
@@ -1,48 +1,71 @@
 use std::fmt::Write;
 
-use anyhow::{Context, Result};
+use anyhow::{anyhow, Context, Result};
 use itertools::Itertools;
 use owo_colors::OwoColorize;
 use tracing::debug;
 
-use distribution_types::{IndexLocations, InstalledMetadata, LocalDist, LocalEditable, Name};
+use distribution_types::{Dist, IndexLocations, InstalledMetadata, LocalDist, LocalEditable, Name};
 use install_wheel_rs::linker::LinkMode;
+use install_wheel_rs::ScriptNaming;
+use pep508_rs::Requirement;
 use platform_host::Platform;
 use platform_tags::Tags;
 use pypi_types::Yanked;
 use requirements_txt::EditableRequirement;
-use uv_cache::Cache;
+use uv_cache::{Cache, CacheBucket};
 use uv_client::{Connectivity, FlatIndex, FlatIndexClient, RegistryClient, RegistryClientBuilder};
 use uv_dispatch::BuildDispatch;
 use uv_fs::Simplified;
 use uv_installer::{
-    is_dynamic, not_modified, Downloader, NoBinary, Plan, Planner, Reinstall, ResolvedEditable,
-    SitePackages,
+    is_dynamic, not_modified, verify_shared_environment, Downloader, Keep, NoBinary, Plan, Planner,
+    Reinstall, ResolvedEditable, SitePackages,
 };
 use uv_interpreter::PythonEnvironment;
+use uv_normalize::PackageName;
 use uv_resolver::InMemoryIndex;
-use uv_traits::{ConfigSettings, InFlight, NoBuild, SetupPyStrategy};
+use uv_traits::{
+    BuildIsolation, Concurrency, ConfigSettings, HashDatabase, InFlight, NoBuild, SetupPyStrategy,
+};
+use uv_warnings::{warn_user_code, WarningCode};
 
 use crate::commands::reporters::{DownloadReporter, FinderReporter, InstallReporter};
-use crate::commands::{elapsed, ChangeEvent, ChangeEventKind, ExitStatus};
+use crate::commands::{
+    download_error, elapsed, find_or_create_venv, ChangeEvent, ChangeEventKind, ExitStatus,
+};
+use crate::interrupt;
 use crate::printer::Printer;
-use crate::requirements::{RequirementsSource, RequirementsSpecification};
+use crate::requirements::{
+    RequirementsSource, RequirementsSpecification, SOURCE_TREES_UNSUPPORTED,
+};
 
 /// Install a set of locked requirements into the current Python environment.
 #[allow(clippy::too_many_arguments)]
 pub(crate) async fn pip_sync(
     sources: &[RequirementsSource],
     reinstall: &Reinstall,
+    keep: Keep,
     link_mode: LinkMode,
+    no_zip: &[PackageName],
+    script_naming: &ScriptNaming,
+    verify_hashes: bool,
+    hash_database: &HashDatabase,
     index_locations: IndexLocations,
     setup_py: SetupPyStrategy,
     connectivity: Connectivity,
     config_settings: &ConfigSettings,
+    no_build_isolation: bool,
+    extra_build_requires: &[Requirement],
+    build_constraints: &[RequirementsSource],
     no_build: &NoBuild,
     no_binary: &NoBinary,
     strict: bool,
     python: Option<String>,
     system: bool,
+    auto_create: bool,
+    shared: bool,
+    allow_conda: bool,
+    concurrency: Concurrency,
     cache: Cache,
     mut printer: Printer,
 ) -> Result<ExitStatus> {
@@ -52,16 +75,44 @@ pub(crate) async fn pip_sync(
     let RequirementsSpecification {
         project: _project,
         requirements,
+        provenance,
         constraints: _constraints,
         overrides: _overrides,
         editables,
+        source_trees,
+        unnamed_requirements,
         index_url,
         extra_index_urls,
         no_index,
         find_links,
+        no_binary: specified_no_binary,
+        only_binary: specified_only_binary,
         extras: _extras,
+        url_hashes,
     } = RequirementsSpecification::from_simple_sources(sources)?;
 
+    // Verify any direct URL requirements against the hashes provided alongside them.
+    let mut hash_database = hash_database.clone();
+    for (url, hashes) in &url_hashes {
+        hash_database.insert_url(url.as_str(), hashes)?;
+    }
+
+    // Determining the requirements of a source tree requires building it.
+    if !source_trees.is_empty() {
+        return Err(anyhow!(SOURCE_TREES_UNSUPPORTED));
+    }
+
+    // Determining the name of a local path that omits it requires building it.
+    if let Some(unnamed) = unnamed_requirements.first() {
+        let path = unnamed.path.simplified_display();
+        return Err(anyhow!(
+            "The package name of `{path}` could not be determined without building it; specify it as `<name> @ {path}`"
+        ));
+    }
+
+    // Read the build constraints, which apply to the resolution of build environments.
+    let build_constraints = RequirementsSpecification::from_build_constraints(build_constraints)?;
+
     let num_requirements = requirements.len() + editables.len();
     if num_requirements == 0 {
         writeln!(printer, "No requirements found")?;
@@ -72,6 +123,14 @@ pub(crate) async fn pip_sync(
     let index_locations =
         index_locations.combine(index_url, extra_index_urls, find_links, no_index);
 
+    // Incorporate any `--no-binary` and `--only-binary` options from the provided sources.
+    let no_binary = &no_binary
+        .clone()
+        .combine(NoBinary::from_args(specified_no_binary));
+    let no_build = &no_build
+        .clone()
+        .combine(NoBuild::from_args(specified_only_binary, false));
+
     // Detect the current Python interpreter.
     let platform = Platform::current()?;
     let venv = if let Some(python) = python.as_ref() {
@@ -79,7 +138,7 @@ pub(crate) async fn pip_sync(
     } else if system {
         PythonEnvironment::from_default_python(&platform, &cache)?
     } else {
-        PythonEnvironment::from_virtualenv(platform, &cache)?
+        find_or_create_venv(platform, auto_create, &cache, printer)?
     };
     debug!(
         "Using Python {} environment at {}",
@@ -103,8 +162,34 @@ pub(crate) async fn pip_sync(
         };
     }
 
+    // Packages installed into a Conda environment aren't tracked by `conda`, so require an
+    // explicit opt-in.
+    if venv.is_conda() && !allow_conda {
+        return Err(anyhow::anyhow!(
+            "The environment at {} is a Conda environment. Packages installed by uv aren't tracked by `conda`; pass `--allow-conda` to install into it anyway.",
+            venv.root().simplified_display().cyan()
+        ));
+    }
+
+    // In a shared environment, verify that the rest of the group will be able to modify anything
+    // we install. Packages are copied, rather than linked, such that they're owned by the
+    // environment's group rather than that of the cache.
+    let link_mode = if shared {
+        verify_shared_environment(&venv)?;
+        LinkMode::Copy
+    } else {
+        link_mode
+    };
+
     let _lock = venv.lock()?;
 
+    // Prevent the cached wheels (which may be linked into the environment) from being cleared
+    // while we're installing.
+    let _cache_locks = [CacheBucket::Wheels, CacheBucket::Archive]
+        .into_iter()
+        .map(|bucket| cache.lock_bucket_shared(bucket))
+        .collect::<Result<Vec<_>, _>>()?;
+
     // Determine the current environment markers.
     let tags = venv.interpreter().tags()?;
 
@@ -127,6 +212,13 @@ pub(crate) async fn pip_sync(
     // Track in-flight downloads, builds, etc., across resolutions.
     let in_flight = InFlight::default();
 
+    // Determine whether to build source distributions in isolation, or in the target environment.
+    let build_isolation = if no_build_isolation {
+        BuildIsolation::Shared(&venv)
+    } else {
+        BuildIsolation::Isolated
+    };
+
     // Prep the build context.
     let build_dispatch = BuildDispatch::new(
         &client,
@@ -140,7 +232,12 @@ pub(crate) async fn pip_sync(
         config_settings,
         no_build,
         no_binary,
-    );
+    )
+    .with_build_isolation(build_isolation)
+    .with_concurrency(concurrency)
+    .with_extra_build_requires(extra_build_requires.to_vec())
+    .with_build_constraints(build_constraints)
+    .with_hash_database(hash_database);
 
     // Determine the set of installed packages.
     let site_packages =
@@ -169,6 +266,8 @@ pub(crate) async fn pip_sync(
         extraneous,
     } = Planner::with_requirements(&requirements)
         .with_editable_requirements(&resolved_editables.editables)
+        .with_keep(keep)
+        .with_connectivity(connectivity)
         .build(
             site_packages,
             reinstall,
@@ -206,7 +305,10 @@ pub(crate) async fn pip_sync(
         let wheel_finder =
             uv_resolver::DistFinder::new(tags, &client, venv.interpreter(), &flat_index, no_binary)
                 .with_reporter(FinderReporter::from(printer).with_length(remote.len() as u64));
-        let resolution = wheel_finder.resolve(&remote).await?;
+        let resolution = wheel_finder
+            .resolve(&remote)
+            .await
+            .map_err(|err| err.with_provenance(&provenance))?;
 
         let s = if resolution.len() == 1 { "" } else { "s" };
         writeln!(
@@ -235,6 +337,7 @@ pub(crate) async fn pip_sync(
         let wheels = downloader
             .download(remote.clone(), &in_flight)
             .await
+            .map_err(|err| download_error(err, &provenance))
             .context("Failed to download distributions")?;
 
         let s = if wheels.len() == 1 { "" } else { "s" };
@@ -252,12 +355,31 @@ pub(crate) async fn pip_sync(
         wheels
     };
 
-    // Remove any unnecessary packages.
+    // If yanked distributions are treated as errors, fail before modifying the environment, rather
+    // than leaving them installed.
+    if uv_warnings::is_error(WarningCode::Yanked) {
+        warn_yanked(&remote);
+        if uv_warnings::has_errors() {
+            return Err(anyhow!("Refusing to install yanked distributions"));
+        }
+    }
+
+    // Remove any unnecessary packages, journaling the removals such that they can be rolled back
+    // if the installation fails (or is interrupted).
+    let _shield = interrupt::shield();
+    let mut transaction = uv_installer::Transaction::new(&venv);
     if !extraneous.is_empty() || !reinstalls.is_empty() {
         let start = std::time::Instant::now();
 
         for dist_info in extraneous.iter().chain(reinstalls.iter()) {
-            let summary = uv_installer::uninstall(dist_info).await?;
+            if interrupt::interrupted() {
+                let err = anyhow!("Installation was interrupted");
+                return Err(transaction.rollback(&[], err));
+            }
+            let summary = match transaction.uninstall(dist_info).await {
+                Ok(summary) => summary,
+                Err(err) => return Err(transaction.rollback(&[], err)),
+            };
             debug!(
                 "Uninstalled {} ({} file{}, {} director{})",
                 dist_info.name(),
@@ -289,10 +411,19 @@ pub(crate) async fn pip_sync(
     let wheels = wheels.into_iter().chain(local).collect::<Vec<_>>();
     if !wheels.is_empty() {
         let start = std::time::Instant::now();
-        uv_installer::Installer::new(&venv)
+        if let Err(err) = uv_installer::Installer::new(&venv)
             .with_link_mode(link_mode)
+            .with_no_zip(no_zip.to_vec())
+            .with_script_naming(script_naming.clone())
+            .with_verify_hashes(verify_hashes)
+            .with_interrupt(interrupt::flag())
+            // Like `pip install -r`, every package listed in the requirements is requested.
+            .with_requested(wheels.iter().map(|wheel| wheel.name().clone()).collect())
             .with_reporter(InstallReporter::from(printer).with_length(wheels.len() as u64))
-            .install(&wheels)?;
+            .install(&wheels)
+        {
+            return Err(transaction.rollback(&wheels, err));
+        }
 
         let s = if wheels.len() == 1 { "" } else { "s" };
         writeln!(
@@ -363,34 +494,37 @@ pub(crate) async fn pip_sync(
         }
     }
 
+    warn_yanked(&remote);
+
+    Ok(ExitStatus::Success)
+}
+
+/// Warn about any yanked distributions that were downloaded for installation.
+///
+/// Warnings are only emitted once, so it's safe to call this both before and after installing.
+fn warn_yanked(remote: &[Dist]) {
     // TODO(konstin): Also check the cache whether any cached or installed dist is already known to
     // have been yanked, we currently don't show this message on the second run anymore
-    for dist in &remote {
+    for dist in remote {
         let Some(file) = dist.file() else {
             continue;
         };
         match &file.yanked {
             None | Some(Yanked::Bool(false)) => {}
             Some(Yanked::Bool(true)) => {
-                writeln!(
-                    printer,
-                    "{}{} {dist} is yanked. Refresh your lockfile to pin an un-yanked version.",
-                    "warning".yellow().bold(),
-                    ":".bold(),
-                )?;
+                warn_user_code!(
+                    WarningCode::Yanked,
+                    "{dist} is yanked. Refresh your lockfile to pin an un-yanked version."
+                );
             }
             Some(Yanked::Reason(reason)) => {
-                writeln!(
-                    printer,
-                    "{}{} {dist} is yanked (reason: \"{reason}\"). Refresh your lockfile to pin an un-yanked version.",
-                    "warning".yellow().bold(),
-                    ":".bold(),
-                )?;
+                warn_user_code!(
+                    WarningCode::Yanked,
+                    "{dist} is yanked (reason: \"{reason}\"). Refresh your lockfile to pin an un-yanked version."
+                );
             }
         }
     }
-
-    Ok(ExitStatus::Success)
 }
 
 #[derive(Debug)]
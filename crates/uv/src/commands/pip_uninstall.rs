@@ -1,6 +1,6 @@
 use std::fmt::Write;
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use owo_colors::OwoColorize;
 use tracing::debug;
 
@@ -9,16 +9,21 @@ use platform_host::Platform;
 use uv_cache::Cache;
 use uv_fs::Simplified;
 use uv_interpreter::PythonEnvironment;
+use uv_warnings::warn_user;
 
 use crate::commands::{elapsed, ExitStatus};
 use crate::printer::Printer;
-use crate::requirements::{RequirementsSource, RequirementsSpecification};
+use crate::requirements::{
+    RequirementsSource, RequirementsSpecification, SOURCE_TREES_UNSUPPORTED,
+};
 
 /// Uninstall packages from the current environment.
 pub(crate) async fn pip_uninstall(
     sources: &[RequirementsSource],
     python: Option<String>,
     system: bool,
+    dry_run: bool,
+    check_modified: bool,
     cache: Cache,
     mut printer: Printer,
 ) -> Result<ExitStatus> {
@@ -28,16 +33,35 @@ pub(crate) async fn pip_uninstall(
     let RequirementsSpecification {
         project: _project,
         requirements,
+        provenance: _provenance,
         constraints: _constraints,
         overrides: _overrides,
         editables,
+        source_trees,
+        unnamed_requirements,
         index_url: _index_url,
         extra_index_urls: _extra_index_urls,
         no_index: _no_index,
         find_links: _find_links,
         extras: _extras,
+        no_binary: _no_binary,
+        only_binary: _only_binary,
+        url_hashes: _url_hashes,
     } = RequirementsSpecification::from_simple_sources(sources)?;
 
+    // Determining the requirements of a source tree requires building it.
+    if !source_trees.is_empty() {
+        return Err(anyhow!(SOURCE_TREES_UNSUPPORTED));
+    }
+
+    // Determining the name of a local path that omits it requires building it.
+    if let Some(unnamed) = unnamed_requirements.first() {
+        let path = unnamed.path.simplified_display();
+        return Err(anyhow!(
+            "The package name of `{path}` could not be determined without building it; specify it as `<name> @ {path}`"
+        ));
+    }
+
     // Detect the current Python interpreter.
     let platform = Platform::current()?;
     let venv = if let Some(python) = python.as_ref() {
@@ -148,17 +172,70 @@ pub(crate) async fn pip_uninstall(
         return Ok(ExitStatus::Success);
     }
 
+    // If we're performing a dry run, list the files that would be removed, and exit.
+    if dry_run {
+        writeln!(
+            printer,
+            "{}",
+            format!(
+                "Would uninstall {}",
+                format!(
+                    "{} package{}",
+                    distributions.len(),
+                    if distributions.len() == 1 { "" } else { "s" }
+                )
+                .bold(),
+            )
+            .dimmed()
+        )?;
+        for distribution in distributions {
+            writeln!(
+                printer,
+                " {} {}{}",
+                "-".red(),
+                distribution.name().as_ref().bold(),
+                distribution.installed_version().to_string().dimmed()
+            )?;
+            for path in install_wheel_rs::uninstall_wheel_dry_run(distribution.path())? {
+                writeln!(printer, "     {}", path.simplified_display())?;
+            }
+        }
+        return Ok(ExitStatus::Success);
+    }
+
     // Uninstall each package.
     for distribution in &distributions {
-        let summary = uv_installer::uninstall(distribution).await?;
+        let summary = uv_installer::uninstall(distribution, check_modified).await?;
         debug!(
-            "Uninstalled {} ({} file{}, {} director{})",
+            "Uninstalled {} ({} file{}, {} director{}; including {} script{} and {} `.pth` file{}, plus {} bytecode file{})",
             distribution.name(),
             summary.file_count,
             if summary.file_count == 1 { "" } else { "s" },
             summary.dir_count,
             if summary.dir_count == 1 { "y" } else { "ies" },
+            summary.script_count,
+            if summary.script_count == 1 { "" } else { "s" },
+            summary.pth_count,
+            if summary.pth_count == 1 { "" } else { "s" },
+            summary.bytecode_count,
+            if summary.bytecode_count == 1 { "" } else { "s" },
         );
+
+        // Like pip, report any files that were modified or removed since installation.
+        for path in &summary.modified {
+            warn_user!(
+                "Removed file from {} that was modified since installation: {}",
+                distribution.name(),
+                path.simplified_display()
+            );
+        }
+        for path in &summary.missing {
+            warn_user!(
+                "File from {} was already missing: {}",
+                distribution.name(),
+                path.simplified_display()
+            );
+        }
     }
 
     writeln!(
@@ -0,0 +1,82 @@
+use std::fmt::Write;
+
+use anstream::println;
+use anyhow::Result;
+use itertools::Itertools;
+use owo_colors::OwoColorize;
+use serde::Serialize;
+
+use platform_host::Platform;
+use uv_cache::Cache;
+use uv_fs::Simplified;
+use uv_interpreter::find_all_pythons;
+
+use crate::commands::ExitStatus;
+use crate::printer::Printer;
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub(crate) enum PythonListFormat {
+    /// Display the interpreters in a human-readable table.
+    Text,
+    /// Display the interpreters as a JSON array.
+    Json,
+}
+
+/// A discovered interpreter, as reported by `--format json`.
+#[derive(Debug, Serialize)]
+struct PythonListEntry {
+    path: String,
+    version: String,
+    implementation: String,
+    arch: String,
+    source: String,
+}
+
+/// List the Python interpreters that uv can discover, in order of precedence.
+pub(crate) fn python_list(
+    format: PythonListFormat,
+    cache: &Cache,
+    mut printer: Printer,
+) -> Result<ExitStatus> {
+    let platform = Platform::current()?;
+    let pythons = find_all_pythons(&platform, cache)?;
+
+    let entries = pythons
+        .iter()
+        .map(|(source, interpreter)| PythonListEntry {
+            path: interpreter
+                .sys_executable()
+                .simplified_display()
+                .to_string(),
+            version: interpreter.python_version().to_string(),
+            implementation: interpreter.implementation_name().to_string(),
+            arch: interpreter.markers().platform_machine.clone(),
+            source: source.to_string(),
+        })
+        .collect_vec();
+
+    match format {
+        PythonListFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(&entries)?);
+        }
+        PythonListFormat::Text if entries.is_empty() => {
+            writeln!(printer, "No Python interpreters found.")?;
+        }
+        PythonListFormat::Text => {
+            let keys = entries
+                .iter()
+                .map(|entry| format!("{}-{}-{}", entry.implementation, entry.version, entry.arch))
+                .collect_vec();
+            let width = keys.iter().map(String::len).max().unwrap_or_default();
+            for (key, entry) in keys.iter().zip(&entries) {
+                println!(
+                    "{key:width$}  {}  {}",
+                    entry.path,
+                    format!("({})", entry.source).dimmed()
+                );
+            }
+        }
+    }
+
+    Ok(ExitStatus::Success)
+}
@@ -0,0 +1,53 @@
+use std::fmt::Write;
+
+use anstream::println;
+use anyhow::Result;
+use owo_colors::OwoColorize;
+
+use platform_host::Platform;
+use uv_cache::Cache;
+use uv_fs::Simplified;
+use uv_interpreter::{find_requested_python, Error, PythonPin};
+
+use crate::commands::ExitStatus;
+use crate::printer::Printer;
+
+/// Pin the Python interpreter for the project in the current directory, or display the current
+/// pin if no request is provided.
+pub(crate) fn python_pin(
+    request: Option<&str>,
+    cache: &Cache,
+    mut printer: Printer,
+) -> Result<ExitStatus> {
+    let cwd = std::env::current_dir()?;
+
+    let Some(request) = request else {
+        return match PythonPin::find(&cwd)? {
+            Some(pin) => {
+                println!("{}", pin.request);
+                Ok(ExitStatus::Success)
+            }
+            None => {
+                writeln!(printer, "No pinned Python version found.")?;
+                Ok(ExitStatus::Failure)
+            }
+        };
+    };
+
+    // Validate that the request can be satisfied before pinning it.
+    let platform = Platform::current()?;
+    let interpreter = find_requested_python(request, &platform, cache)?
+        .ok_or_else(|| Error::NoSuchPython(request.to_string()))?;
+
+    let pin = PythonPin::write(&cwd, request)?;
+    writeln!(
+        printer,
+        "Pinned `{}` to `{}` (Python {} at: {})",
+        pin.path.simplified_display().cyan(),
+        pin.request.bold(),
+        interpreter.python_version(),
+        interpreter.sys_executable().simplified_display().cyan()
+    )?;
+
+    Ok(ExitStatus::Success)
+}
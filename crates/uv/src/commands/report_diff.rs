@@ -0,0 +1,293 @@
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+use std::path::Path;
+
+use anstream::println;
+use anyhow::{Context, Result};
+use owo_colors::OwoColorize;
+use serde::Deserialize;
+use url::Url;
+
+use pep440_rs::Version;
+use uv_fs::Simplified;
+use uv_normalize::PackageName;
+
+use crate::commands::ExitStatus;
+use crate::printer::Printer;
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub(crate) enum ReportDiffFormat {
+    /// Display the changes as a human-readable list.
+    Text,
+    /// Display the changes as a Markdown table, e.g., for use in a pull request comment.
+    Markdown,
+}
+
+/// An installation report, in the format produced by `pip install --report`.
+///
+/// Only the fields relevant to diffing are deserialized; see:
+/// <https://pip.pypa.io/en/stable/reference/installation-report/>
+#[derive(Debug, Deserialize)]
+struct InstallationReport {
+    #[serde(default)]
+    install: Vec<InstallationReportItem>,
+}
+
+#[derive(Debug, Deserialize)]
+struct InstallationReportItem {
+    metadata: InstallationReportMetadata,
+    download_info: Option<DownloadInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+struct InstallationReportMetadata {
+    name: PackageName,
+    version: Version,
+}
+
+/// The PEP 610 `direct_url.json` data for an item, describing where it was installed from.
+#[derive(Debug, Deserialize)]
+struct DownloadInfo {
+    url: String,
+    archive_info: Option<ArchiveInfo>,
+    vcs_info: Option<VcsInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ArchiveInfo {
+    hash: Option<String>,
+    #[serde(default)]
+    hashes: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct VcsInfo {
+    commit_id: String,
+}
+
+/// A package, as pinned in an installation report.
+#[derive(Debug)]
+struct Pin {
+    version: Version,
+    /// The URL from which the package was installed, if known.
+    url: Option<String>,
+    /// The hash of the installed archive or the Git commit, if known.
+    digest: Option<String>,
+}
+
+impl From<InstallationReportItem> for Pin {
+    fn from(item: InstallationReportItem) -> Self {
+        let digest = item.download_info.as_ref().and_then(|info| {
+            if let Some(vcs_info) = &info.vcs_info {
+                return Some(vcs_info.commit_id.clone());
+            }
+            let archive_info = info.archive_info.as_ref()?;
+            // Prefer the `hashes` field, falling back to the legacy `hash` field (`<algorithm>=<digest>`).
+            if let Some(sha256) = archive_info.hashes.get("sha256") {
+                return Some(format!("sha256:{sha256}"));
+            }
+            if let Some((algorithm, digest)) = archive_info
+                .hashes
+                .iter()
+                .next()
+                .map(|(algorithm, digest)| (algorithm.as_str(), digest.as_str()))
+                .or_else(|| archive_info.hash.as_deref()?.split_once('='))
+            {
+                return Some(format!("{algorithm}:{digest}"));
+            }
+            None
+        });
+        Self {
+            version: item.metadata.version,
+            url: item.download_info.map(|info| info.url),
+            digest,
+        }
+    }
+}
+
+/// A change to a package between two installation reports.
+#[derive(Debug)]
+enum Change {
+    Added(Pin),
+    Removed(Pin),
+    /// The package version, source, or hash changed.
+    Modified {
+        old: Pin,
+        new: Pin,
+    },
+}
+
+impl Change {
+    /// A short label describing the change.
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Added(_) => "added",
+            Self::Removed(_) => "removed",
+            Self::Modified { old, new } => match new.version.cmp(&old.version) {
+                Ordering::Greater => "upgraded",
+                Ordering::Less => "downgraded",
+                Ordering::Equal => "changed",
+            },
+        }
+    }
+
+    /// Notes on any changes to the package's source or hash.
+    fn notes(&self) -> Vec<String> {
+        let Self::Modified { old, new } = self else {
+            return Vec::new();
+        };
+        let mut notes = Vec::new();
+        // For a new version, the URL is expected to change, so only flag a change of host (e.g.,
+        // from PyPI to a private index).
+        let source_changed = if old.version == new.version {
+            old.url != new.url
+        } else {
+            old.url.as_deref().map(origin) != new.url.as_deref().map(origin)
+        };
+        if source_changed {
+            notes.push(format!(
+                "source changed from `{}` to `{}`",
+                old.url.as_deref().unwrap_or("unknown"),
+                new.url.as_deref().unwrap_or("unknown")
+            ));
+        }
+        // Only flag a hash change for the same version, since a new version implies a new hash.
+        if old.version == new.version && old.digest != new.digest {
+            notes.push(format!(
+                "hash changed from `{}` to `{}`",
+                old.digest.as_deref().unwrap_or("unknown"),
+                new.digest.as_deref().unwrap_or("unknown")
+            ));
+        }
+        notes
+    }
+}
+
+/// Compare two installation reports, and display the added, removed, and modified packages.
+pub(crate) fn report_diff(
+    old: &Path,
+    new: &Path,
+    format: ReportDiffFormat,
+    mut printer: Printer,
+) -> Result<ExitStatus> {
+    let mut old = read_report(old)?;
+    let mut new = read_report(new)?;
+
+    // Pair up the packages across both reports, in alphabetical order.
+    let names = old
+        .keys()
+        .chain(new.keys())
+        .cloned()
+        .collect::<BTreeSet<_>>();
+    let changes = names
+        .into_iter()
+        .filter_map(|name| {
+            let change = match (old.remove(&name), new.remove(&name)) {
+                (None, Some(new)) => Change::Added(new),
+                (Some(old), None) => Change::Removed(old),
+                (Some(old), Some(new)) => {
+                    if old.version == new.version && old.url == new.url && old.digest == new.digest
+                    {
+                        return None;
+                    }
+                    Change::Modified { old, new }
+                }
+                (None, None) => return None,
+            };
+            Some((name, change))
+        })
+        .collect::<Vec<_>>();
+
+    if changes.is_empty() {
+        writeln!(printer, "{}", "No changes".dimmed())?;
+        return Ok(ExitStatus::Success);
+    }
+
+    match format {
+        ReportDiffFormat::Text => {
+            for (name, change) in &changes {
+                match change {
+                    Change::Added(pin) => {
+                        println!(
+                            " {} {}{}",
+                            "+".green(),
+                            name.as_ref().bold(),
+                            format!("=={}", pin.version).dimmed()
+                        );
+                    }
+                    Change::Removed(pin) => {
+                        println!(
+                            " {} {}{}",
+                            "-".red(),
+                            name.as_ref().bold(),
+                            format!("=={}", pin.version).dimmed()
+                        );
+                    }
+                    Change::Modified { old, new } => {
+                        if old.version == new.version {
+                            println!(
+                                " {} {}{}",
+                                "~".yellow(),
+                                name.as_ref().bold(),
+                                format!("=={}", new.version).dimmed()
+                            );
+                        } else {
+                            println!(
+                                " {} {} {} -> {} ({})",
+                                "~".yellow(),
+                                name.as_ref().bold(),
+                                old.version,
+                                new.version,
+                                change.label()
+                            );
+                        }
+                    }
+                }
+                for note in change.notes() {
+                    println!("   {}", note.dimmed());
+                }
+            }
+        }
+        ReportDiffFormat::Markdown => {
+            println!("| Package | Change | Old | New | Notes |");
+            println!("| --- | --- | --- | --- | --- |");
+            for (name, change) in &changes {
+                let (old, new) = match change {
+                    Change::Added(pin) => (None, Some(pin)),
+                    Change::Removed(pin) => (Some(pin), None),
+                    Change::Modified { old, new } => (Some(old), Some(new)),
+                };
+                println!(
+                    "| `{name}` | {} | {} | {} | {} |",
+                    change.label(),
+                    old.map(|pin| pin.version.to_string()).unwrap_or_default(),
+                    new.map(|pin| pin.version.to_string()).unwrap_or_default(),
+                    change.notes().join("<br>")
+                );
+            }
+        }
+    }
+
+    Ok(ExitStatus::Success)
+}
+
+/// Return the scheme and host of a URL, or the URL itself if it can't be parsed.
+fn origin(url: &str) -> String {
+    match Url::parse(url) {
+        Ok(url) => format!("{}://{}", url.scheme(), url.host_str().unwrap_or_default()),
+        Err(_) => url.to_string(),
+    }
+}
+
+/// Read the packages pinned in an installation report, keyed by name.
+fn read_report(path: &Path) -> Result<BTreeMap<PackageName, Pin>> {
+    let contents = fs_err::read_to_string(path)?;
+    let report: InstallationReport = serde_json::from_str(&contents)
+        .with_context(|| format!("Failed to parse report: {}", path.simplified_display()))?;
+    Ok(report
+        .install
+        .into_iter()
+        .map(|item| (item.metadata.name.clone(), Pin::from(item)))
+        .collect())
+}
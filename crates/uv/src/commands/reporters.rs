@@ -10,7 +10,9 @@ use distribution_types::{
 };
 use uv_normalize::PackageName;
 
+use crate::interrupt;
 use crate::printer::Printer;
+use crate::progress::{Phase, ProgressEvent};
 
 #[derive(Debug)]
 pub(crate) struct FinderReporter {
@@ -24,6 +26,7 @@ impl From<Printer> for FinderReporter {
             ProgressStyle::with_template("{bar:20} [{pos}/{len}] {wide_msg:.dim}").unwrap(),
         );
         progress.set_message("Resolving dependencies...");
+        interrupt::track(&progress, printer, "Resolved");
         Self { progress }
     }
 }
@@ -40,10 +43,15 @@ impl uv_resolver::FinderReporter for FinderReporter {
     fn on_progress(&self, dist: &Dist) {
         self.progress.set_message(format!("{dist}"));
         self.progress.inc(1);
+        ProgressEvent::progress(Phase::Resolve, &dist.to_string(), &self.progress).emit();
     }
 
     fn on_complete(&self) {
         self.progress.finish_and_clear();
+        ProgressEvent::Complete {
+            phase: Phase::Resolve,
+        }
+        .emit();
     }
 }
 
@@ -64,6 +72,7 @@ impl From<Printer> for DownloadReporter {
             ProgressStyle::with_template("{bar:20} [{pos}/{len}] {wide_msg:.dim}").unwrap(),
         );
         progress.set_message("Fetching packages...");
+        interrupt::track(&progress, printer, "Fetched");
 
         Self {
             printer,
@@ -83,7 +92,8 @@ impl DownloadReporter {
 }
 
 impl DownloadReporter {
-    fn on_any_build_start(&self, color_string: &str) -> usize {
+    fn on_any_build_start(&self, package: &str, color_string: &str) -> usize {
+        ProgressEvent::BuildStart { package }.emit();
         let progress = self.multi_progress.insert_before(
             &self.progress,
             ProgressBar::with_draw_target(None, self.printer.target()),
@@ -97,40 +107,65 @@ impl DownloadReporter {
         bars.len() - 1
     }
 
-    fn on_any_build_complete(&self, color_string: &str, id: usize) {
+    fn on_any_build_complete(&self, package: &str, color_string: &str, id: usize) {
+        ProgressEvent::BuildComplete { package }.emit();
         let bars = self.bars.lock().unwrap();
         let progress = &bars[id];
         progress.finish_with_message(format!("   {} {}", "Built".bold().green(), color_string));
     }
 }
 
+impl DownloadReporter {
+    /// The interval, in bytes, at which download progress events are emitted.
+    const EVENT_INTERVAL: u64 = 1024 * 1024;
+
+    fn emit_download_progress(progress: &ProgressBar) {
+        ProgressEvent::DownloadProgress {
+            package: &progress.message(),
+            downloaded: progress.position(),
+            total: progress.length(),
+        }
+        .emit();
+    }
+}
+
 impl uv_installer::DownloadReporter for DownloadReporter {
     fn on_progress(&self, dist: &CachedDist) {
         self.progress.set_message(format!("{dist}"));
         self.progress.inc(1);
+        ProgressEvent::progress(Phase::Download, &dist.to_string(), &self.progress).emit();
     }
 
     fn on_complete(&self) {
         self.progress.finish_and_clear();
+        ProgressEvent::Complete {
+            phase: Phase::Download,
+        }
+        .emit();
     }
 
     fn on_build_start(&self, dist: &SourceDist) -> usize {
-        self.on_any_build_start(&dist.to_color_string())
+        self.on_any_build_start(&dist.to_string(), &dist.to_color_string())
     }
 
     fn on_build_complete(&self, dist: &SourceDist, index: usize) {
-        self.on_any_build_complete(&dist.to_color_string(), index);
+        self.on_any_build_complete(&dist.to_string(), &dist.to_color_string(), index);
     }
 
     fn on_editable_build_start(&self, dist: &LocalEditable) -> usize {
-        self.on_any_build_start(&dist.to_color_string())
+        self.on_any_build_start(&dist.to_string(), &dist.to_color_string())
     }
 
     fn on_editable_build_complete(&self, dist: &LocalEditable, id: usize) {
-        self.on_any_build_complete(&dist.to_color_string(), id);
+        self.on_any_build_complete(&dist.to_string(), &dist.to_color_string(), id);
     }
 
     fn on_checkout_start(&self, url: &Url, rev: &str) -> usize {
+        ProgressEvent::CheckoutStart {
+            url: url.as_str(),
+            rev,
+        }
+        .emit();
         let progress = self.multi_progress.insert_before(
             &self.progress,
             ProgressBar::with_draw_target(None, self.printer.target()),
@@ -151,6 +186,11 @@ impl uv_installer::DownloadReporter for DownloadReporter {
     }
 
     fn on_checkout_complete(&self, url: &Url, rev: &str, index: usize) {
+        ProgressEvent::CheckoutComplete {
+            url: url.as_str(),
+            rev,
+        }
+        .emit();
         let bars = self.bars.lock().unwrap();
         let progress = &bars[index];
         progress.finish_with_message(format!(
@@ -160,6 +200,41 @@ impl uv_installer::DownloadReporter for DownloadReporter {
             rev.dimmed()
         ));
     }
+
+    fn on_download_start(&self, dist: &Dist, size: Option<u64>) -> usize {
+        let progress = self.multi_progress.insert_before(
+            &self.progress,
+            ProgressBar::with_draw_target(size, self.printer.target()),
+        );
+
+        progress.set_style(
+            ProgressStyle::with_template("{msg:.dim} {bar:20} {binary_bytes}/{binary_total_bytes}")
+                .unwrap(),
+        );
+        progress.set_message(dist.to_string());
+        Self::emit_download_progress(&progress);
+
+        let mut bars = self.bars.lock().unwrap();
+        bars.push(progress);
+        bars.len() - 1
+    }
+
+    fn on_download_progress(&self, id: usize, bytes: u64) {
+        let bars = self.bars.lock().unwrap();
+        let progress = &bars[id];
+        let before = progress.position();
+        progress.inc(bytes);
+        if before / Self::EVENT_INTERVAL != progress.position() / Self::EVENT_INTERVAL {
+            Self::emit_download_progress(progress);
+        }
+    }
+
+    fn on_download_complete(&self, id: usize) {
+        let bars = self.bars.lock().unwrap();
+        let progress = &bars[id];
+        Self::emit_download_progress(progress);
+        progress.finish_and_clear();
+    }
 }
 
 #[derive(Debug)]
@@ -174,6 +249,7 @@ impl From<Printer> for InstallReporter {
             ProgressStyle::with_template("{bar:20} [{pos}/{len}] {wide_msg:.dim}").unwrap(),
         );
         progress.set_message("Installing wheels...");
+        interrupt::track(&progress, printer, "Installed");
         Self { progress }
     }
 }
@@ -190,10 +266,15 @@ impl uv_installer::InstallReporter for InstallReporter {
     fn on_install_progress(&self, wheel: &CachedDist) {
         self.progress.set_message(format!("{wheel}"));
         self.progress.inc(1);
+        ProgressEvent::progress(Phase::Install, &wheel.to_string(), &self.progress).emit();
     }
 
     fn on_install_complete(&self) {
         self.progress.finish_and_clear();
+        ProgressEvent::Complete {
+            phase: Phase::Install,
+        }
+        .emit();
     }
 }
 
@@ -217,6 +298,7 @@ impl From<Printer> for ResolverReporter {
                 .tick_strings(&["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"]),
         );
         progress.set_message("Resolving dependencies...");
+        interrupt::track(&progress, printer, "Resolved");
 
         Self {
             printer,
@@ -229,21 +311,29 @@ impl From<Printer> for ResolverReporter {
 
 impl uv_resolver::ResolverReporter for ResolverReporter {
     fn on_progress(&self, name: &PackageName, version_or_url: VersionOrUrl) {
-        match version_or_url {
-            VersionOrUrl::Version(version) => {
-                self.progress.set_message(format!("{name}=={version}"));
-            }
-            VersionOrUrl::Url(url) => {
-                self.progress.set_message(format!("{name} @ {url}"));
-            }
-        }
+        let package = match version_or_url {
+            VersionOrUrl::Version(version) => format!("{name}=={version}"),
+            VersionOrUrl::Url(url) => format!("{name} @ {url}"),
+        };
+        self.progress.inc(1);
+        ProgressEvent::progress(Phase::Resolve, &package, &self.progress).emit();
+        self.progress.set_message(package);
     }
 
     fn on_complete(&self) {
         self.progress.finish_and_clear();
+        ProgressEvent::Complete {
+            phase: Phase::Resolve,
+        }
+        .emit();
     }
 
     fn on_build_start(&self, dist: &SourceDist) -> usize {
+        ProgressEvent::BuildStart {
+            package: &dist.to_string(),
+        }
+        .emit();
+
         let progress = self.multi_progress.insert_before(
             &self.progress,
             ProgressBar::with_draw_target(None, self.printer.target()),
@@ -262,6 +352,10 @@ impl uv_resolver::ResolverReporter for ResolverReporter {
     }
 
     fn on_build_complete(&self, dist: &SourceDist, index: usize) {
+        ProgressEvent::BuildComplete {
+            package: &dist.to_string(),
+        }
+        .emit();
         let bars = self.bars.lock().unwrap();
         let progress = &bars[index];
         progress.finish_with_message(format!(
@@ -272,6 +366,11 @@ impl uv_resolver::ResolverReporter for ResolverReporter {
     }
 
     fn on_checkout_start(&self, url: &Url, rev: &str) -> usize {
+        ProgressEvent::CheckoutStart {
+            url: url.as_str(),
+            rev,
+        }
+        .emit();
         let progress = self.multi_progress.insert_before(
             &self.progress,
             ProgressBar::with_draw_target(None, self.printer.target()),
@@ -292,6 +391,11 @@ impl uv_resolver::ResolverReporter for ResolverReporter {
     }
 
     fn on_checkout_complete(&self, url: &Url, rev: &str, index: usize) {
+        ProgressEvent::CheckoutComplete {
+            url: url.as_str(),
+            rev,
+        }
+        .emit();
         let bars = self.bars.lock().unwrap();
         let progress = &bars[index];
         progress.finish_with_message(format!(
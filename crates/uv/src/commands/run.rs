@@ -0,0 +1,418 @@
+use std::ffi::OsString;
+use std::path::Path;
+
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Utc};
+use owo_colors::OwoColorize;
+use serde::Deserialize;
+use tempfile::tempdir_in;
+use tokio::process::Command;
+use tracing::debug;
+
+use distribution_types::IndexLocations;
+use platform_host::Platform;
+use uv_cache::Cache;
+use uv_client::Connectivity;
+use uv_fs::Simplified;
+use uv_installer::SitePackages;
+use uv_interpreter::{
+    find_pinned_python, find_requested_python, Error, Interpreter, PythonEnvironment,
+};
+use uv_traits::Concurrency;
+use uv_warnings::warn_user;
+
+use crate::commands::{install_into, ExitStatus};
+use crate::interrupt;
+use crate::printer::Printer;
+use crate::requirements::RequirementsSource;
+
+/// Run a command in the project's virtual environment, or in an ephemeral environment.
+///
+/// An ephemeral environment is used if any requirements are provided, or if `isolated` is set (in
+/// which case the requirements declared by the script being run, or else those of the project in
+/// the current directory, if any, are installed). It's created in the cache directory (such that
+/// packages can be linked from the cache), and removed once the command exits.
+///
+/// Any `with` requirements are installed into an ephemeral environment that's overlaid on the
+/// project's virtual environment (if any), such that the packages in both are available.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn run(
+    command: &[OsString],
+    requirements: Vec<RequirementsSource>,
+    with: Vec<RequirementsSource>,
+    isolated: bool,
+    python: Option<&str>,
+    index_locations: IndexLocations,
+    connectivity: Connectivity,
+    exclude_newer: Option<DateTime<Utc>>,
+    concurrency: Concurrency,
+    cache: &Cache,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    let (program, args) = command
+        .split_first()
+        .context("No command provided to `uv run`")?;
+
+    let platform = Platform::current()?;
+
+    // Run Python scripts with the environment's interpreter, and any other command as-is.
+    let script = Path::new(program)
+        .extension()
+        .is_some_and(|extension| extension == "py");
+
+    // Find the project's virtual environment, unless it's to be replaced by an ephemeral one.
+    let base = if isolated || !requirements.is_empty() || python.is_some() {
+        None
+    } else {
+        match PythonEnvironment::from_virtualenv(platform.clone(), cache) {
+            Ok(venv) => Some(venv),
+            Err(Error::VenvNotFound) if !with.is_empty() => None,
+            Err(Error::VenvNotFound) => {
+                return Err(anyhow!(
+                    "No virtual environment found; run `{}` to create an environment, or pass `{}` to run in an ephemeral one",
+                    "uv venv".green(),
+                    "--isolated".green()
+                ));
+            }
+            Err(err) => return Err(err.into()),
+        }
+    };
+
+    // Hold the ephemeral environment (if any) until the command exits.
+    let (venv, base, temp_dir) = match base {
+        Some(base) if with.is_empty() => (base, None, None),
+        base => {
+            let temp_dir = tempdir_in(cache.root())?;
+
+            // An overlay must use the same interpreter as the environment it's overlaid on.
+            let interpreter = if let Some(base) = base.as_ref() {
+                base.interpreter().clone()
+            } else {
+                ephemeral_interpreter(python, &platform, cache)?
+            };
+            debug!(
+                "Creating ephemeral environment with Python {} at: {}",
+                interpreter.python_version(),
+                temp_dir.path().simplified_display()
+            );
+            let venv = uv_virtualenv::create_venv(
+                temp_dir.path(),
+                interpreter,
+                uv_virtualenv::Prompt::None,
+                false,
+                Vec::new(),
+            )?;
+
+            // An isolated environment replaces the project's environment, so it needs the
+            // script's requirements, or else the project's.
+            let project = if isolated {
+                let declared = if script {
+                    script_requirements(Path::new(program))?
+                } else {
+                    None
+                };
+                match declared {
+                    Some(declared) => declared,
+                    None => project_requirements()?.into_iter().collect(),
+                }
+            } else {
+                Vec::new()
+            };
+
+            let requirements = requirements
+                .into_iter()
+                .chain(project)
+                .chain(with)
+                .collect::<Vec<_>>();
+            if !requirements.is_empty() {
+                let status = install_into(
+                    venv.root(),
+                    &requirements,
+                    index_locations,
+                    connectivity,
+                    exclude_newer,
+                    concurrency,
+                    cache,
+                    printer,
+                )
+                .await?;
+                if !matches!(status, ExitStatus::Success) {
+                    return Ok(status);
+                }
+            }
+
+            if let Some(base) = base.as_ref() {
+                overlay(&venv, base)?;
+            }
+
+            (venv, base, Some(temp_dir))
+        }
+    };
+
+    let mut process = if script {
+        let mut process = Command::new(venv.python_executable());
+        process.arg(program);
+        process
+    } else {
+        Command::new(program)
+    };
+    process.args(args);
+
+    // Activate the environment, by prepending its scripts directory (and that of the environment
+    // it's overlaid on, if any) to the `PATH`.
+    let path = std::env::var_os("PATH").unwrap_or_default();
+    let path = std::env::join_paths(
+        std::iter::once(venv.scripts().to_path_buf())
+            .chain(base.as_ref().map(|base| base.scripts().to_path_buf()))
+            .chain(std::env::split_paths(&path)),
+    )?;
+    process
+        .env("PATH", path)
+        .env("VIRTUAL_ENV", venv.root())
+        .env_remove("PYTHONHOME");
+
+    debug!(
+        "Running `{}` in: {}",
+        command
+            .iter()
+            .map(|arg| arg.to_string_lossy())
+            .collect::<Vec<_>>()
+            .join(" "),
+        venv.root().simplified_display()
+    );
+    // Like a shell, leave `Ctrl-C` to the command (e.g., to interrupt a REPL), pass on `SIGTERM`,
+    // and wait for it to exit, such that the ephemeral environment is removed afterwards.
+    let guard = interrupt::defer_to_child();
+    let mut child = match process.spawn() {
+        Ok(child) => child,
+        Err(err) => {
+            // If the command doesn't exist, suggest the commands that the environment provides.
+            let scripts = if err.kind() == std::io::ErrorKind::NotFound {
+                console_scripts(std::iter::once(&venv).chain(base.as_ref()))
+            } else {
+                Vec::new()
+            };
+            let message = if scripts.is_empty() {
+                format!("Failed to run: `{}`", program.to_string_lossy())
+            } else {
+                format!(
+                    "Failed to run: `{}` (the environment provides: {})",
+                    program.to_string_lossy(),
+                    scripts.join(", ")
+                )
+            };
+            return Err(anyhow::Error::new(err).context(message));
+        }
+    };
+    guard.forward_to(child.id());
+
+    let status = child
+        .wait()
+        .await
+        .with_context(|| format!("Failed to run: `{}`", program.to_string_lossy()))?;
+    drop(guard);
+
+    // Remove the ephemeral environment, if any.
+    if let Some(temp_dir) = temp_dir {
+        let path = temp_dir.path().to_path_buf();
+        if let Err(err) = temp_dir.close() {
+            warn_user!(
+                "Failed to remove ephemeral environment at {}: {err}",
+                path.simplified_display()
+            );
+        }
+    }
+
+    // Forward the command's exit status. If the command was terminated by a signal, exit with
+    // the status a shell would report for it (e.g., 130 for `SIGINT`).
+    #[cfg(unix)]
+    if let Some(signal) = std::os::unix::process::ExitStatusExt::signal(&status) {
+        return Ok(ExitStatus::External(
+            u8::try_from(128 + signal).unwrap_or(u8::MAX),
+        ));
+    }
+    Ok(match status.code() {
+        Some(0) => ExitStatus::Success,
+        Some(code) => ExitStatus::External(u8::try_from(code).unwrap_or(1)),
+        None => ExitStatus::Failure,
+    })
+}
+
+/// Find the requirements of the project in the current directory (or any parent directory), as
+/// declared in its `pyproject.toml`, if any.
+fn project_requirements() -> Result<Option<RequirementsSource>> {
+    let cwd = std::env::current_dir()?;
+    let Some(pyproject_toml) = cwd
+        .ancestors()
+        .map(|dir| dir.join("pyproject.toml"))
+        .find(|path| path.is_file())
+    else {
+        debug!("No project found in: {}", cwd.simplified_display());
+        return Ok(None);
+    };
+    debug!(
+        "Installing project requirements from: {}",
+        pyproject_toml.simplified_display()
+    );
+    Ok(Some(RequirementsSource::PyprojectToml(pyproject_toml)))
+}
+
+/// The inline metadata of a Python script, per PEP 723.
+#[derive(Debug, Deserialize)]
+struct ScriptMetadata {
+    #[serde(default)]
+    dependencies: Vec<String>,
+}
+
+/// Find the requirements declared in the inline metadata (i.e., the `# /// script` block) of the
+/// given Python script, if any.
+fn script_requirements(path: &Path) -> Result<Option<Vec<RequirementsSource>>> {
+    // If the script doesn't exist, let the interpreter report it.
+    let Ok(contents) = fs_err::read_to_string(path) else {
+        return Ok(None);
+    };
+    let Some(metadata) = parse_script_metadata(&contents).with_context(|| {
+        format!(
+            "Failed to parse the inline metadata of: {}",
+            path.simplified_display()
+        )
+    })?
+    else {
+        return Ok(None);
+    };
+    debug!(
+        "Installing script requirements from: {}",
+        path.simplified_display()
+    );
+    Ok(Some(
+        metadata
+            .dependencies
+            .into_iter()
+            .map(RequirementsSource::Package)
+            .collect(),
+    ))
+}
+
+/// Parse the `# /// script` block of a Python script, if any.
+///
+/// Each line of the block is a comment, the contents of which (after the `#` and an optional
+/// space) form a TOML document.
+fn parse_script_metadata(contents: &str) -> Result<Option<ScriptMetadata>> {
+    let mut lines = contents
+        .lines()
+        .skip_while(|line| line.trim_end() != "# /// script");
+    if lines.next().is_none() {
+        return Ok(None);
+    }
+
+    let mut toml = String::new();
+    for line in lines {
+        if line.trim_end() == "# ///" {
+            return Ok(Some(toml::from_str(&toml)?));
+        }
+        let Some(line) = line.strip_prefix('#') else {
+            break;
+        };
+        toml.push_str(line.strip_prefix(' ').unwrap_or(line));
+        toml.push('\n');
+    }
+
+    Err(anyhow!("Unterminated `# /// script` block"))
+}
+
+/// Return the names of the console scripts declared by the packages installed in the given
+/// environments, in sorted order.
+fn console_scripts<'a>(envs: impl Iterator<Item = &'a PythonEnvironment>) -> Vec<String> {
+    let mut scripts = envs
+        .filter_map(|venv| SitePackages::from_executable(venv).ok())
+        .flat_map(|site_packages| {
+            site_packages
+                .entry_points("console_scripts")
+                .into_iter()
+                .map(|(_, entry_point)| entry_point.name)
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    scripts.sort_unstable();
+    scripts.dedup();
+    scripts
+}
+
+/// Find the interpreter for an ephemeral environment, respecting any pin.
+fn ephemeral_interpreter(
+    python: Option<&str>,
+    platform: &Platform,
+    cache: &Cache,
+) -> Result<Interpreter> {
+    if let Some(python) = python {
+        Ok(find_requested_python(python, platform, cache)?
+            .ok_or_else(|| Error::NoSuchPython(python.to_string()))?)
+    } else {
+        let cwd = std::env::current_dir()?;
+        Ok(find_pinned_python(&cwd, platform, cache)?)
+    }
+}
+
+/// Overlay an ephemeral environment on a base environment, by way of a `.pth` file that adds the
+/// base environment's site packages (including any `.pth` files therein) to the `sys.path`.
+///
+/// The overlay's own site packages take precedence over those of the base environment.
+fn overlay(venv: &PythonEnvironment, base: &PythonEnvironment) -> Result<()> {
+    // A JSON string is also a valid Python string literal.
+    let site_packages = serde_json::to_string(&base.site_packages().to_string_lossy())?;
+    fs_err::write(
+        venv.site_packages().join("_uv_overlay.pth"),
+        format!("import site; site.addsitedir({site_packages})\n"),
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_script_metadata;
+
+    #[test]
+    fn script_metadata() {
+        let contents = indoc::indoc! {r#"
+            #!/usr/bin/env python
+            # /// script
+            # requires-python = ">=3.11"
+            # dependencies = [
+            #   "requests<3",
+            #   "rich",
+            # ]
+            #
+            # [tool.uv]
+            # ///
+
+            import requests
+        "#};
+        let metadata = parse_script_metadata(contents).unwrap().unwrap();
+        assert_eq!(metadata.dependencies, vec!["requests<3", "rich"]);
+    }
+
+    #[test]
+    fn script_metadata_missing() {
+        let contents = "import sys\n# ///\n";
+        assert!(parse_script_metadata(contents).unwrap().is_none());
+    }
+
+    #[test]
+    fn script_metadata_no_dependencies() {
+        let contents = "# /// script\n# requires-python = \">=3.11\"\n# ///\n";
+        let metadata = parse_script_metadata(contents).unwrap().unwrap();
+        assert!(metadata.dependencies.is_empty());
+    }
+
+    #[test]
+    fn script_metadata_unterminated() {
+        let contents = "# /// script\n# dependencies = []\nimport sys\n";
+        assert!(parse_script_metadata(contents).is_err());
+    }
+
+    #[test]
+    fn script_metadata_invalid() {
+        let contents = "# /// script\n# dependencies = [\n# ///\n";
+        assert!(parse_script_metadata(contents).is_err());
+    }
+}
@@ -1,11 +1,12 @@
 use std::fmt::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::vec;
 
 use anstream::eprint;
 use anyhow::Result;
 use chrono::{DateTime, Utc};
+use console::Term;
 use itertools::Itertools;
 use miette::{Diagnostic, IntoDiagnostic};
 use owo_colors::OwoColorize;
@@ -18,12 +19,16 @@ use uv_cache::Cache;
 use uv_client::{Connectivity, FlatIndex, FlatIndexClient, RegistryClientBuilder};
 use uv_dispatch::BuildDispatch;
 use uv_fs::Simplified;
-use uv_installer::NoBinary;
-use uv_interpreter::{find_default_python, find_requested_python, Error};
+use uv_installer::{NoBinary, SitePackages};
+use uv_interpreter::{
+    find_default_python, find_pinned_python, find_requested_python, Error, PythonEnvironment,
+};
 use uv_resolver::{InMemoryIndex, OptionsBuilder};
-use uv_traits::{BuildContext, ConfigSettings, InFlight, NoBuild, SetupPyStrategy};
+use uv_traits::{BuildContext, Concurrency, ConfigSettings, InFlight, NoBuild, SetupPyStrategy};
+use uv_warnings::warn_user;
 
 use crate::commands::ExitStatus;
+use crate::confirm;
 use crate::printer::Printer;
 
 /// Create a virtual environment.
@@ -36,7 +41,9 @@ pub(crate) async fn venv(
     system_site_packages: bool,
     connectivity: Connectivity,
     seed: bool,
+    clone: Option<&Path>,
     exclude_newer: Option<DateTime<Utc>>,
+    concurrency: Concurrency,
     cache: &Cache,
     printer: Printer,
 ) -> Result<ExitStatus> {
@@ -48,7 +55,9 @@ pub(crate) async fn venv(
         system_site_packages,
         connectivity,
         seed,
+        clone,
         exclude_newer,
+        concurrency,
         cache,
         printer,
     )
@@ -72,6 +81,14 @@ enum VenvError {
     #[diagnostic(code(uv::venv::seed))]
     Seed(#[source] anyhow::Error),
 
+    #[error("Failed to clone virtualenv")]
+    #[diagnostic(code(uv::venv::clone))]
+    Clone(#[source] anyhow::Error),
+
+    #[error("Cannot clone a virtualenv into itself: `{}`", _0.simplified_display())]
+    #[diagnostic(code(uv::venv::clone_into_self))]
+    CloneIntoSelf(PathBuf),
+
     #[error("Failed to extract interpreter tags")]
     #[diagnostic(code(uv::venv::tags))]
     Tags(#[source] platform_tags::TagsError),
@@ -91,17 +108,41 @@ async fn venv_impl(
     system_site_packages: bool,
     connectivity: Connectivity,
     seed: bool,
+    clone: Option<&Path>,
     exclude_newer: Option<DateTime<Utc>>,
+    concurrency: Concurrency,
     cache: &Cache,
     mut printer: Printer,
 ) -> miette::Result<ExitStatus> {
-    // Locate the Python interpreter.
     let platform = Platform::current().into_diagnostic()?;
+
+    // If cloning, snapshot the source environment before creating the target.
+    let source = clone
+        .map(|clone| PythonEnvironment::from_root(clone, platform.clone(), cache))
+        .transpose()
+        .into_diagnostic()?;
+
+    // Creating the target would remove the source, so refuse to clone an environment into itself.
+    if let Some(source) = source.as_ref() {
+        if let (Ok(target), Ok(root)) = (
+            fs_err::canonicalize(path),
+            fs_err::canonicalize(source.root()),
+        ) {
+            if target == root {
+                return Err(VenvError::CloneIntoSelf(path.to_path_buf()).into());
+            }
+        }
+    }
+
+    // Locate the Python interpreter.
     let interpreter = if let Some(python_request) = python_request {
         find_requested_python(python_request, &platform, cache)
             .into_diagnostic()?
             .ok_or(Error::NoSuchPython(python_request.to_string()))
             .into_diagnostic()?
+    } else if let Some(source) = source.as_ref() {
+        // Default to the interpreter of the environment being cloned.
+        source.interpreter().clone()
     } else {
         find_default_python(&platform, cache).into_diagnostic()?
     };
@@ -174,7 +215,8 @@ async fn venv_impl(
             &NoBuild::All,
             &NoBinary::None,
         )
-        .with_options(OptionsBuilder::new().exclude_newer(exclude_newer).build());
+        .with_options(OptionsBuilder::new().exclude_newer(exclude_newer).build())
+        .with_concurrency(concurrency);
 
         // Resolve the seed packages.
         let mut requirements = vec![Requirement::from_str("pip").unwrap()];
@@ -210,6 +252,106 @@ async fn venv_impl(
         }
     }
 
+    // Re-install the packages from the source environment.
+    if let Some(source) = source {
+        let site_packages = SitePackages::from_executable(&source).map_err(VenvError::Clone)?;
+
+        // Editable installs point at a local source tree, which can't be recreated from the cache.
+        let editables = site_packages
+            .iter()
+            .filter(|dist| dist.is_editable())
+            .map(|dist| dist.name().to_string())
+            .sorted()
+            .collect::<Vec<_>>();
+        if !editables.is_empty() {
+            warn_user!(
+                "Skipping editable packages, which are not cloned: {}",
+                editables.join(", ")
+            );
+        }
+
+        // Pin every installed distribution to its exact version (or direct URL).
+        let requirements = site_packages
+            .iter()
+            .zip(site_packages.requirements())
+            .filter(|(dist, _)| !dist.is_editable())
+            .map(|(_, requirement)| requirement)
+            .collect::<Vec<_>>();
+
+        if !requirements.is_empty() {
+            let interpreter = venv.interpreter();
+
+            // Instantiate a client.
+            let client = RegistryClientBuilder::new(cache.clone())
+                .index_urls(index_locations.index_urls())
+                .connectivity(connectivity)
+                .build();
+
+            // Resolve the flat indexes from `--find-links`.
+            let flat_index = {
+                let tags = interpreter.tags().map_err(VenvError::Tags)?;
+                let client = FlatIndexClient::new(&client, cache);
+                let entries = client
+                    .fetch(index_locations.flat_index())
+                    .await
+                    .map_err(VenvError::FlatIndex)?;
+                FlatIndex::from_entries(entries, tags)
+            };
+
+            // Create a shared in-memory index.
+            let index = InMemoryIndex::default();
+
+            // Track in-flight downloads, builds, etc., across resolutions.
+            let in_flight = InFlight::default();
+
+            // Use the default settings, as in the source environment.
+            let config_settings = ConfigSettings::default();
+
+            // Prep the build context. Unlike the seed packages, source distributions may need to
+            // be built, since the source environment may have been populated from them.
+            let build_dispatch = BuildDispatch::new(
+                &client,
+                cache,
+                interpreter,
+                index_locations,
+                &flat_index,
+                &index,
+                &in_flight,
+                SetupPyStrategy::default(),
+                &config_settings,
+                &NoBuild::None,
+                &NoBinary::None,
+            )
+            .with_options(OptionsBuilder::new().exclude_newer(exclude_newer).build())
+            .with_concurrency(concurrency);
+
+            // Since every package is pinned, the resolution reproduces the source environment.
+            let resolution = build_dispatch
+                .resolve(&requirements)
+                .await
+                .map_err(VenvError::Clone)?;
+
+            // Install into the environment.
+            build_dispatch
+                .install(&resolution, &venv)
+                .await
+                .map_err(VenvError::Clone)?;
+
+            for distribution in resolution.distributions().sorted_unstable_by(|a, b| {
+                a.name().cmp(b.name()).then(a.version().cmp(&b.version()))
+            }) {
+                writeln!(
+                    printer,
+                    " {} {}{}",
+                    "+".green(),
+                    distribution.name().as_ref().bold(),
+                    distribution.version_or_url().dimmed()
+                )
+                .into_diagnostic()?;
+            }
+        }
+    }
+
     if cfg!(windows) {
         writeln!(
             printer,
@@ -236,3 +378,62 @@ async fn venv_impl(
 
     Ok(ExitStatus::Success)
 }
+
+/// Find the virtual environment for the current directory, creating one at `.venv` if none exists
+/// and the user opts in, either via `--auto-create` or by accepting an interactive prompt.
+///
+/// The new virtual environment uses the pinned Python interpreter (see `uv python pin`), if any.
+pub(crate) fn find_or_create_venv(
+    platform: Platform,
+    auto_create: bool,
+    cache: &Cache,
+    mut printer: Printer,
+) -> Result<PythonEnvironment> {
+    match PythonEnvironment::from_virtualenv(platform.clone(), cache) {
+        Err(Error::VenvNotFound) => {}
+        result => return Ok(result?),
+    }
+
+    let cwd = std::env::current_dir()?;
+    let path = cwd.join(".venv");
+
+    if !auto_create {
+        let term = Term::stderr();
+        if !term.is_term() {
+            return Err(Error::VenvNotFound.into());
+        }
+        let prompt = format!(
+            "No virtual environment found. Create one at `{}`?",
+            path.simplified_display()
+        );
+        if !confirm::confirm(&prompt, &term, true)? {
+            return Err(Error::VenvNotFound.into());
+        }
+    }
+
+    // Locate the Python interpreter, respecting any pin.
+    let interpreter = find_pinned_python(&cwd, &platform, cache)?;
+
+    writeln!(
+        printer,
+        "Using Python {} interpreter at: {}",
+        interpreter.python_version(),
+        interpreter.sys_executable().simplified_display().cyan()
+    )?;
+    writeln!(
+        printer,
+        "Creating virtualenv at: {}",
+        path.simplified_display().cyan()
+    )?;
+
+    // Extra cfg for pyvenv.cfg to specify uv version
+    let extra_cfg = vec![("uv".to_string(), env!("CARGO_PKG_VERSION").to_string())];
+
+    Ok(uv_virtualenv::create_venv(
+        &path,
+        interpreter,
+        uv_virtualenv::Prompt::None,
+        false,
+        extra_cfg,
+    )?)
+}
@@ -27,12 +27,6 @@ pub(crate) struct PipCompileCompatArgs {
     #[clap(long, hide = true)]
     no_reuse_hashes: bool,
 
-    #[clap(long, hide = true)]
-    build_isolation: bool,
-
-    #[clap(long, hide = true)]
-    no_build_isolation: bool,
-
     #[clap(long, hide = true)]
     resolver: Option<Resolver>,
 
@@ -72,12 +66,6 @@ pub(crate) struct PipCompileCompatArgs {
     #[clap(long, hide = true)]
     no_emit_options: bool,
 
-    #[clap(long, hide = true)]
-    strip_extras: bool,
-
-    #[clap(long, hide = true)]
-    no_strip_extras: bool,
-
     #[clap(long, hide = true)]
     pip_args: Option<String>,
 }
@@ -87,7 +75,7 @@ impl CompatArgs for PipCompileCompatArgs {
     ///
     /// This method will warn when an argument is passed that has no effect but matches uv's
     /// behavior. If an argument is passed that does _not_ match uv's behavior (e.g.,
-    /// `--no-build-isolation`), this method will return an error.
+    /// `--reuse-hashes`), this method will return an error.
     fn validate(&self) -> Result<()> {
         if self.allow_unsafe {
             warn_user!(
@@ -111,18 +99,6 @@ impl CompatArgs for PipCompileCompatArgs {
             );
         }
 
-        if self.build_isolation {
-            warn_user!(
-                "pip-compile's `--build-isolation` has no effect (uv always uses build isolation)."
-            );
-        }
-
-        if self.no_build_isolation {
-            return Err(anyhow!(
-                "pip-compile's `--no-build-isolation` is unsupported (uv always uses build isolation)."
-            ));
-        }
-
         if let Some(resolver) = self.resolver {
             match resolver {
                 Resolver::Backtracking => {
@@ -202,16 +178,6 @@ impl CompatArgs for PipCompileCompatArgs {
             warn_user!("pip-compile's `--no-emit-options` has no effect (uv never emits options).");
         }
 
-        if self.strip_extras {
-            warn_user!("pip-compile's `--strip-extras` has no effect (uv always strips extras).");
-        }
-
-        if self.no_strip_extras {
-            return Err(anyhow!(
-                "pip-compile's `--no-strip-extras` is unsupported (uv always strips extras)."
-            ));
-        }
-
         if self.pip_args.is_some() {
             return Err(anyhow!(
                 "pip-compile's `--pip-args` is unsupported (try passing arguments to uv directly)."
@@ -0,0 +1,193 @@
+use anstream::println;
+use anyhow::Result;
+use clap::builder::PossibleValue;
+use clap::{Arg, ArgAction, Command, CommandFactory};
+use serde::Serialize;
+
+use crate::Cli;
+
+/// The `--help-json` flag, which is handled before the command line is otherwise parsed.
+pub(crate) fn arg() -> Arg {
+    Arg::new("help_json")
+        .long("help-json")
+        .global(true)
+        .action(ArgAction::SetTrue)
+        .help("Print a machine-readable description of the command's arguments, as JSON")
+}
+
+/// A machine-readable description of a command, as emitted by `--help-json`.
+#[derive(Debug, Serialize)]
+struct CommandSchema {
+    /// The full invocation of the command, e.g., `uv pip install`.
+    command: String,
+    about: Option<String>,
+    aliases: Vec<String>,
+    arguments: Vec<ArgumentSchema>,
+    subcommands: Vec<CommandSchema>,
+}
+
+#[derive(Debug, Serialize)]
+struct ArgumentSchema {
+    id: String,
+    long: Option<String>,
+    short: Option<char>,
+    aliases: Vec<String>,
+    help: Option<String>,
+    value_names: Vec<String>,
+    /// Whether the argument accepts a value, as opposed to being a flag.
+    takes_value: bool,
+    /// Whether the argument can be provided multiple times.
+    multiple: bool,
+    required: bool,
+    positional: bool,
+    /// Whether the argument is inherited by all subcommands.
+    global: bool,
+    default: Vec<String>,
+    /// The environment variable that can be used in lieu of the argument.
+    env: Option<String>,
+    possible_values: Vec<PossibleValueSchema>,
+}
+
+#[derive(Debug, Serialize)]
+struct PossibleValueSchema {
+    name: String,
+    help: Option<String>,
+}
+
+impl CommandSchema {
+    fn from_command(command: &Command, parent: Option<&str>) -> Self {
+        let invocation = match parent {
+            Some(parent) => format!("{parent} {}", command.get_name()),
+            None => command.get_name().to_string(),
+        };
+        Self {
+            about: command.get_about().map(ToString::to_string),
+            aliases: command
+                .get_visible_aliases()
+                .map(ToString::to_string)
+                .collect(),
+            arguments: command
+                .get_arguments()
+                .filter(|arg| !arg.is_hide_set())
+                .map(ArgumentSchema::from_arg)
+                .collect(),
+            subcommands: command
+                .get_subcommands()
+                .filter(|subcommand| !subcommand.is_hide_set())
+                .map(|subcommand| Self::from_command(subcommand, Some(&invocation)))
+                .collect(),
+            command: invocation,
+        }
+    }
+}
+
+impl ArgumentSchema {
+    fn from_arg(arg: &Arg) -> Self {
+        let action = arg.get_action();
+        Self {
+            id: arg.get_id().to_string(),
+            long: arg.get_long().map(ToString::to_string),
+            short: arg.get_short(),
+            aliases: arg
+                .get_visible_aliases()
+                .unwrap_or_default()
+                .into_iter()
+                .map(ToString::to_string)
+                .collect(),
+            help: arg
+                .get_long_help()
+                .or_else(|| arg.get_help())
+                .map(ToString::to_string),
+            value_names: arg
+                .get_value_names()
+                .unwrap_or_default()
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            takes_value: action.takes_values(),
+            multiple: matches!(action, ArgAction::Append | ArgAction::Count)
+                || arg
+                    .get_num_args()
+                    .is_some_and(|num_args| num_args.max_values() > 1),
+            required: arg.is_required_set(),
+            positional: arg.is_positional(),
+            global: arg.is_global_set(),
+            default: arg
+                .get_default_values()
+                .iter()
+                .map(|value| value.to_string_lossy().to_string())
+                .collect(),
+            env: arg.get_env().map(|env| env.to_string_lossy().to_string()),
+            possible_values: if action.takes_values() {
+                arg.get_possible_values()
+                    .iter()
+                    .filter(|value| !value.is_hide_set())
+                    .map(PossibleValueSchema::from_possible_value)
+                    .collect()
+            } else {
+                Vec::new()
+            },
+        }
+    }
+}
+
+impl PossibleValueSchema {
+    fn from_possible_value(value: &PossibleValue) -> Self {
+        Self {
+            name: value.get_name().to_string(),
+            help: value.get_help().map(ToString::to_string),
+        }
+    }
+}
+
+/// If `--help-json` was passed, return the (sub)command whose description was requested.
+///
+/// The command line is parsed leniently, such that `--help-json` can be passed to a command without
+/// its required arguments (e.g., `uv pip install --help-json`).
+pub(crate) fn requested() -> Option<(Command, Vec<String>)> {
+    let matches = Cli::command().ignore_errors(true).try_get_matches().ok()?;
+
+    // Identify the innermost subcommand.
+    let mut path = Vec::new();
+    let mut matches = &matches;
+    while let Some((name, submatches)) = matches.subcommand() {
+        path.push(name.to_string());
+        matches = submatches;
+    }
+
+    let requested = matches
+        .try_get_one::<bool>("help_json")
+        .ok()
+        .flatten()
+        .copied()
+        .unwrap_or(false);
+    if !requested {
+        return None;
+    }
+
+    // Build the command, to propagate global arguments to each subcommand.
+    let mut command = Cli::command();
+    command.build();
+    Some((command, path))
+}
+
+/// Print a machine-readable description of the (sub)command at the given path.
+pub(crate) fn print(command: &Command, path: &[String]) -> Result<()> {
+    let mut invocation = vec![command.get_name()];
+    let mut command = command;
+    for name in path {
+        let Some(subcommand) = command.find_subcommand(name) else {
+            break;
+        };
+        invocation.push(subcommand.get_name());
+        command = subcommand;
+    }
+
+    // The command's own name is added when describing it.
+    invocation.pop();
+    let parent = (!invocation.is_empty()).then(|| invocation.join(" "));
+
+    let schema = CommandSchema::from_command(command, parent.as_deref());
+    println!("{}", serde_json::to_string_pretty(&schema)?);
+    Ok(())
+}
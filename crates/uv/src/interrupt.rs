@@ -0,0 +1,292 @@
+use std::fmt::Write;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, AtomicI32, AtomicU32, AtomicUsize, Ordering};
+use std::sync::{Mutex, PoisonError};
+
+use anyhow::Result;
+use console::Term;
+use indicatif::{ProgressBar, WeakProgressBar};
+use tokio::sync::Notify;
+
+use crate::printer::Printer;
+
+/// The progress bars that should be cleaned up if the process is interrupted.
+static PROGRESS: Mutex<Vec<TrackedProgress>> = Mutex::new(Vec::new());
+
+/// Whether the process has been interrupted.
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+/// The signal that interrupted the process, if any.
+static SIGNAL: AtomicI32 = AtomicI32::new(0);
+
+/// Notified when the process is interrupted, and when a [`shield`] is dropped.
+static NOTIFY: Notify = Notify::const_new();
+
+/// The number of sections that must run to completion (or clean up after themselves) before the
+/// running command can be cancelled (see [`shield`]).
+static SHIELDS: AtomicUsize = AtomicUsize::new(0);
+
+/// Whether `Ctrl-C` is currently being left to a child process (see [`defer_to_child`]).
+static DEFERRED: AtomicBool = AtomicBool::new(false);
+
+/// The process ID of the child process that `SIGTERM` is forwarded to, if any.
+static CHILD: AtomicU32 = AtomicU32::new(0);
+
+struct TrackedProgress {
+    progress: WeakProgressBar,
+    printer: Printer,
+    /// The verb used to summarize the completed work (e.g., "Installed").
+    verb: &'static str,
+}
+
+/// Install a handler for `SIGINT` and `SIGTERM` that cancels the running command (see [`run`]).
+///
+/// A second signal terminates the process immediately.
+#[cfg(unix)]
+pub(crate) fn install() -> Result<()> {
+    use nix::sys::signal::Signal;
+    use nix::unistd::Pid;
+    use tokio::signal::unix::{signal, SignalKind};
+
+    // Wait for signals on a dedicated thread, such that they're handled promptly even if the main
+    // runtime is busy.
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_io()
+        .build()?;
+    let (mut sigint, mut sigterm) = {
+        let _guard = runtime.enter();
+        (
+            signal(SignalKind::interrupt())?,
+            signal(SignalKind::terminate())?,
+        )
+    };
+    std::thread::Builder::new()
+        .name("uv-interrupt".to_string())
+        .spawn(move || loop {
+            let signal = runtime.block_on(async {
+                loop {
+                    tokio::select! {
+                        _ = sigint.recv() => {
+                            // The child process receives the same `SIGINT` (as part of the
+                            // foreground process group), and decides whether to exit.
+                            if !DEFERRED.load(Ordering::SeqCst) {
+                                break Signal::SIGINT;
+                            }
+                        },
+                        _ = sigterm.recv() => {
+                            // Unlike `SIGINT`, `SIGTERM` is sent to uv alone, so pass it on to the
+                            // child process, and report its exit status once it exits.
+                            #[allow(clippy::cast_possible_wrap)]
+                            let child = CHILD.load(Ordering::SeqCst) as i32;
+                            if DEFERRED.load(Ordering::SeqCst) && child != 0 {
+                                nix::sys::signal::kill(Pid::from_raw(child), Signal::SIGTERM).ok();
+                            } else {
+                                break Signal::SIGTERM;
+                            }
+                        },
+                    }
+                }
+            });
+            if INTERRUPTED.load(Ordering::SeqCst) {
+                restore();
+                reraise(signal as i32);
+            }
+            SIGNAL.store(signal as i32, Ordering::SeqCst);
+            INTERRUPTED.store(true, Ordering::SeqCst);
+            NOTIFY.notify_one();
+        })?;
+    Ok(())
+}
+
+/// Install a handler for `Ctrl-C` that cancels the running command (see [`run`]).
+///
+/// A second `Ctrl-C` terminates the process immediately.
+#[cfg(windows)]
+pub(crate) fn install() -> Result<()> {
+    ctrlc::set_handler(|| {
+        // The child process receives the same `Ctrl-C`, and decides whether to exit.
+        if DEFERRED.load(Ordering::SeqCst) {
+            return;
+        }
+
+        if INTERRUPTED.swap(true, Ordering::SeqCst) {
+            restore();
+            reraise(0);
+        }
+        NOTIFY.notify_one();
+    })?;
+    Ok(())
+}
+
+/// Run a command until it completes or the process is interrupted.
+///
+/// If the process is interrupted, the command is dropped (such that any destructors run, e.g., to
+/// remove temporary directories) once it's outside of any [`shield`]ed section. Either way, if the
+/// process was interrupted, the terminal is then restored and the process terminates with the
+/// conventional status for the signal (e.g., 130 for `SIGINT`, or 143 for `SIGTERM`).
+pub(crate) async fn run<T>(command: impl Future<Output = T>) -> T {
+    let result = tokio::select! {
+        result = command => Some(result),
+        () = cancelled() => None,
+    };
+    match result {
+        Some(result) if !interrupted() => result,
+        _ => {
+            restore();
+            reraise(SIGNAL.load(Ordering::SeqCst));
+        }
+    }
+}
+
+/// Wait until the process is interrupted, outside of any [`shield`]ed section.
+async fn cancelled() {
+    loop {
+        let notified = NOTIFY.notified();
+        if INTERRUPTED.load(Ordering::SeqCst) && SHIELDS.load(Ordering::SeqCst) == 0 {
+            return;
+        }
+        notified.await;
+    }
+}
+
+/// Returns a flag that's set once the process is interrupted, for code within a [`shield`]ed
+/// section to stop at a point where it can clean up after itself.
+pub(crate) fn flag() -> &'static AtomicBool {
+    &INTERRUPTED
+}
+
+/// Returns `true` if the process has been interrupted.
+pub(crate) fn interrupted() -> bool {
+    INTERRUPTED.load(Ordering::SeqCst)
+}
+
+/// Prevent the running command from being cancelled until the returned guard is dropped.
+///
+/// Used for sections that leave the environment in an inconsistent state if dropped partway
+/// through (e.g., between uninstalling packages and rolling back the uninstall). Such sections
+/// are responsible for checking [`interrupted`] and returning early.
+pub(crate) fn shield() -> ShieldGuard {
+    SHIELDS.fetch_add(1, Ordering::SeqCst);
+    ShieldGuard(())
+}
+
+/// Allows the running command to be cancelled when dropped (see [`shield`]).
+#[must_use]
+pub(crate) struct ShieldGuard(());
+
+impl Drop for ShieldGuard {
+    fn drop(&mut self) {
+        SHIELDS.fetch_sub(1, Ordering::SeqCst);
+        if INTERRUPTED.load(Ordering::SeqCst) {
+            NOTIFY.notify_one();
+        }
+    }
+}
+
+/// Terminate the process by re-raising the given signal with its default disposition.
+#[cfg(unix)]
+fn reraise(signal: i32) -> ! {
+    use nix::sys::signal::{SigHandler, Signal};
+
+    if let Ok(signal) = Signal::try_from(signal) {
+        // SAFETY: The default disposition doesn't run any code in the signal context.
+        #[allow(unsafe_code)]
+        let reset = unsafe { nix::sys::signal::signal(signal, SigHandler::SigDfl) };
+        if reset.is_ok() {
+            nix::sys::signal::raise(signal).ok();
+        }
+    }
+
+    // If the signal couldn't be re-raised, exit with the status a shell would report for it.
+    #[allow(clippy::exit)]
+    std::process::exit(128 + signal);
+}
+
+/// Terminate the process with the status reported for `Ctrl-C` (`STATUS_CONTROL_C_EXIT`).
+#[cfg(windows)]
+fn reraise(_signal: i32) -> ! {
+    #[allow(clippy::exit, clippy::cast_possible_wrap)]
+    std::process::exit(0xC000_013A_u32 as i32);
+}
+
+/// Leave `Ctrl-C` to a child process (e.g., the command run by `uv run`) until the returned guard
+/// is dropped, rather than cancelling the running command (and orphaning the child).
+///
+/// Like a shell, uv then waits for the child to exit, and reports its exit status.
+pub(crate) fn defer_to_child() -> DeferGuard {
+    DEFERRED.store(true, Ordering::SeqCst);
+    DeferGuard(())
+}
+
+/// Restores the default handling of `Ctrl-C` when dropped (see [`defer_to_child`]).
+#[must_use]
+pub(crate) struct DeferGuard(());
+
+impl DeferGuard {
+    /// Forward `SIGTERM` to the child process with the given ID, once it's been spawned.
+    pub(crate) fn forward_to(&self, pid: Option<u32>) {
+        CHILD.store(pid.unwrap_or(0), Ordering::SeqCst);
+    }
+}
+
+impl Drop for DeferGuard {
+    fn drop(&mut self) {
+        DEFERRED.store(false, Ordering::SeqCst);
+        CHILD.store(0, Ordering::SeqCst);
+    }
+}
+
+/// Track a progress bar, such that it's cleared and its completed work is summarized if the
+/// process is interrupted.
+pub(crate) fn track(progress: &ProgressBar, printer: Printer, verb: &'static str) {
+    let mut tracked = PROGRESS.lock().unwrap_or_else(PoisonError::into_inner);
+    // Drop any progress bars that have since been dropped.
+    tracked.retain(|tracked| tracked.progress.upgrade().is_some());
+    tracked.push(TrackedProgress {
+        progress: progress.downgrade(),
+        printer,
+        verb,
+    });
+}
+
+/// Clear any in-flight progress bars, summarize their completed work, and show the cursor.
+fn restore() {
+    let tracked = std::mem::take(&mut *PROGRESS.lock().unwrap_or_else(PoisonError::into_inner));
+
+    let mut summaries = Vec::new();
+    for TrackedProgress {
+        progress,
+        printer,
+        verb,
+    } in tracked
+    {
+        let Some(progress) = progress.upgrade() else {
+            continue;
+        };
+        if progress.is_finished() {
+            continue;
+        }
+
+        let position = progress.position();
+        let length = progress.length();
+        progress.finish_and_clear();
+
+        // Progress bars without a length (e.g., spinners) have no meaningful work to summarize.
+        if let Some(length) = length {
+            summaries.push((printer, verb, position, length));
+        }
+    }
+
+    for (mut printer, verb, position, length) in summaries {
+        let s = if length == 1 { "" } else { "s" };
+        writeln!(
+            printer,
+            "{verb} {position} of {length} package{s} before being interrupted"
+        )
+        .ok();
+    }
+
+    let term = Term::stderr();
+    term.show_cursor().ok();
+    term.flush().ok();
+}
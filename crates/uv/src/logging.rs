@@ -16,22 +16,73 @@ pub(crate) enum Level {
     Default,
     /// Show debug messages by default (overridable by `RUST_LOG`).
     Verbose,
+    /// Show trace messages by default (overridable by `RUST_LOG`).
+    ExtraVerbose,
+    /// Show trace messages for all crates, including dependencies (overridable by `RUST_LOG`).
+    TraceAll,
 }
 
-/// Configure `tracing` based on the given [`Level`], taking into account the `RUST_LOG` environment
-/// variable.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub(crate) enum LogFormat {
+    /// Write human-readable log messages.
+    #[default]
+    Text,
+    /// Write newline-delimited JSON log records.
+    Json,
+}
+
+/// Configure `tracing` based on the given [`Level`] and [`LogFormat`], taking into account the
+/// `RUST_LOG` environment variable.
 ///
 /// The [`Level`] is used to dictate the default filters (which can be overridden by the `RUST_LOG`
 /// environment variable) along with the formatting of the output. For example, [`Level::Verbose`]
 /// includes targets and timestamps, along with all `uv=debug` messages by default.
-pub(crate) fn setup_logging(level: Level, duration: impl Layer<Registry> + Send + Sync) {
-    match level {
-        Level::Default => {
-            // Show nothing, but allow `RUST_LOG` to override.
-            let filter = EnvFilter::builder()
-                .with_default_directive(LevelFilter::OFF.into())
-                .from_env_lossy();
+///
+/// With [`LogFormat::Json`], each message is written to stderr as a JSON record, regardless of
+/// the [`Level`].
+///
+/// If `ansi` is `false`, the output is written without any ANSI escape codes (e.g., colors).
+pub(crate) fn setup_logging(
+    level: Level,
+    format: LogFormat,
+    duration: impl Layer<Registry> + Send + Sync,
+    ansi: bool,
+) {
+    let filter = match level {
+        // Show nothing, but allow `RUST_LOG` to override.
+        Level::Default => EnvFilter::builder()
+            .with_default_directive(LevelFilter::OFF.into())
+            .from_env_lossy(),
+        // Show `DEBUG` messages from uv's crates, but allow `RUST_LOG` to override.
+        Level::Verbose => EnvFilter::try_from_default_env()
+            .or_else(|_| EnvFilter::try_new("uv=debug"))
+            .unwrap(),
+        // Show `TRACE` messages from uv's crates, but allow `RUST_LOG` to override.
+        Level::ExtraVerbose => EnvFilter::try_from_default_env()
+            .or_else(|_| EnvFilter::try_new("uv=trace"))
+            .unwrap(),
+        // Show `TRACE` messages from all crates, but allow `RUST_LOG` to override.
+        Level::TraceAll => EnvFilter::try_from_default_env()
+            .or_else(|_| EnvFilter::try_new("trace"))
+            .unwrap(),
+    };
 
+    match (level, format) {
+        (_, LogFormat::Json) => {
+            // Include the enclosing spans, such that records can be correlated.
+            tracing_subscriber::registry()
+                .with(duration)
+                .with(filter)
+                .with(
+                    tracing_subscriber::fmt::layer()
+                        .json()
+                        .with_current_span(true)
+                        .with_span_list(true)
+                        .with_writer(std::io::stderr),
+                )
+                .init();
+        }
+        (Level::Default, LogFormat::Text) => {
             // Regardless of the tracing level, show messages without any adornment.
             tracing_subscriber::registry()
                 .with(duration)
@@ -40,16 +91,12 @@ pub(crate) fn setup_logging(level: Level, duration: impl Layer<Registry> + Send
                     tracing_subscriber::fmt::layer()
                         .without_time()
                         .with_target(false)
+                        .with_ansi(ansi)
                         .with_writer(std::io::sink),
                 )
                 .init();
         }
-        Level::Verbose => {
-            // Show `DEBUG` messages from the CLI crate, but allow `RUST_LOG` to override.
-            let filter = EnvFilter::try_from_default_env()
-                .or_else(|_| EnvFilter::try_new("uv=debug"))
-                .unwrap();
-
+        (Level::Verbose | Level::ExtraVerbose | Level::TraceAll, LogFormat::Text) => {
             // Regardless of the tracing level, include the uptime and target for each message.
             tracing_subscriber::registry()
                 .with(duration)
@@ -58,6 +105,7 @@ pub(crate) fn setup_logging(level: Level, duration: impl Layer<Registry> + Send
                     HierarchicalLayer::default()
                         .with_targets(true)
                         .with_timer(Uptime::default())
+                        .with_ansi(ansi)
                         .with_writer(std::io::stderr),
                 )
                 .init();
@@ -1,32 +1,48 @@
 use std::env;
+use std::ffi::OsString;
 use std::io::stdout;
+use std::num::NonZeroUsize;
 use std::path::PathBuf;
 use std::process::ExitCode;
 use std::str::FromStr;
+use std::time::Duration;
 
 use anstream::eprintln;
 use anyhow::Result;
 use chrono::{DateTime, Days, NaiveDate, NaiveTime, Utc};
 use clap::error::{ContextKind, ContextValue};
-use clap::{Args, CommandFactory, Parser, Subcommand};
+use clap::{Args, CommandFactory, FromArgMatches, Parser, Subcommand};
 use owo_colors::OwoColorize;
-use tracing::instrument;
+use tracing::{debug, instrument};
 
 use distribution_types::{FlatIndexLocation, IndexLocations, IndexUrl};
+use pep508_rs::Requirement;
 use requirements::ExtrasSpecification;
 use uv_cache::{Cache, CacheArgs, Refresh};
 use uv_client::Connectivity;
-use uv_installer::{NoBinary, Reinstall};
-use uv_interpreter::PythonVersion;
+use uv_fs::Simplified;
+use uv_installer::{Keep, NoBinary, Reinstall};
+use uv_interpreter::{PythonPin, PythonVersion, TargetTriple};
 use uv_normalize::{ExtraName, PackageName};
-use uv_resolver::{AnnotationStyle, DependencyMode, PreReleaseMode, ResolutionMode};
+use uv_resolver::{
+    AnnotationStyle, DependencyMode, MinReleaseAge, MinReleaseAgeEntry, PreReleaseMode,
+    ResolutionMode,
+};
 use uv_traits::{
-    ConfigSettingEntry, ConfigSettings, NoBuild, PackageNameSpecifier, SetupPyStrategy,
+    Concurrency, ConfigSettingEntry, ConfigSettings, HashDatabase, NoBuild, PackageNameSpecifier,
+    SetupPyStrategy,
 };
+use uv_warnings::{warn_user, WarningCode};
 
-use crate::commands::{extra_name_with_clap_error, ExitStatus, Upgrade, VersionFormat};
+use crate::commands::{
+    extra_name_with_clap_error, AuditFormat, ExitStatus, LicensesFormat, ListFormat,
+    PackagePattern, PythonListFormat, ReportDiffFormat, SbomFormat, Upgrade, UpgradeStrategy,
+    VersionFormat,
+};
 use crate::compat::CompatArgs;
+use crate::progress::ProgressMode;
 use crate::requirements::RequirementsSource;
+use crate::settings::LoadedSettings;
 
 #[cfg(target_os = "windows")]
 #[global_allocator]
@@ -47,9 +63,13 @@ static GLOBAL: tikv_jemallocator::Jemalloc = tikv_jemallocator::Jemalloc;
 mod commands;
 mod compat;
 mod confirm;
+mod help_json;
+mod interrupt;
 mod logging;
 mod printer;
+mod progress;
 mod requirements;
+mod settings;
 mod version;
 
 const DEFAULT_VENV_NAME: &str = ".venv";
@@ -57,6 +77,7 @@ const DEFAULT_VENV_NAME: &str = ".venv";
 #[derive(Parser)]
 #[command(author, version, long_version = crate::version::version(), about)]
 #[command(propagate_version = true)]
+#[command(arg = help_json::arg())]
 #[allow(clippy::struct_excessive_bools)]
 struct Cli {
     #[command(subcommand)]
@@ -67,14 +88,33 @@ struct Cli {
     quiet: bool,
 
     /// Use verbose output.
-    #[arg(global = true, long, short, conflicts_with = "quiet")]
-    verbose: bool,
+    ///
+    /// Repeat to increase the verbosity: `-v` shows debug messages from uv, `-vv` shows trace
+    /// messages from uv, and `-vvv` shows trace messages from all dependencies.
+    #[arg(global = true, long, short, action = clap::ArgAction::Count, conflicts_with = "quiet")]
+    verbose: u8,
+
+    /// The format in which to write log messages.
+    ///
+    /// `json` writes newline-delimited JSON records to stderr, e.g., for ingestion by log
+    /// aggregation systems in CI.
+    #[arg(
+        global = true,
+        long,
+        value_enum,
+        default_value = "text",
+        env = "UV_LOG_FORMAT"
+    )]
+    log_format: logging::LogFormat,
 
     /// Disable colors; provided for compatibility with `pip`.
     #[arg(global = true, long, hide = true, conflicts_with = "color")]
     no_color: bool,
 
     /// Control colors in output.
+    ///
+    /// By default, colors are enabled only when writing to a terminal, and can be disabled by
+    /// setting the `NO_COLOR` environment variable.
     #[arg(
         global = true,
         long,
@@ -84,6 +124,67 @@ struct Cli {
     )]
     color: ColorChoice,
 
+    /// Control how progress is reported.
+    ///
+    /// `json` emits newline-delimited JSON events to stderr (e.g., for display by an IDE), in
+    /// lieu of progress bars. Other human-readable output on stderr, including warnings, is
+    /// suppressed, such that every line is an event; errors are still reported.
+    #[arg(
+        global = true,
+        long,
+        value_enum,
+        default_value = "auto",
+        env = "UV_PROGRESS"
+    )]
+    progress: ProgressMode,
+
+    /// Treat warnings with the given code as errors, failing the command if any are emitted.
+    ///
+    /// May be provided multiple times.
+    #[arg(global = true, long, value_enum, value_name = "CODE")]
+    warn_as_error: Vec<WarningCode>,
+
+    /// Suppress warnings with the given code.
+    ///
+    /// May be provided multiple times. If a code is also passed to `--warn-as-error`, the warnings
+    /// are treated as errors.
+    #[arg(global = true, long, value_enum, value_name = "CODE")]
+    silence: Vec<WarningCode>,
+
+    /// The maximum number of in-flight concurrent downloads.
+    ///
+    /// Defaults to 50.
+    #[arg(global = true, long, env = "UV_CONCURRENT_DOWNLOADS")]
+    concurrent_downloads: Option<NonZeroUsize>,
+
+    /// The maximum number of source distributions to build concurrently.
+    ///
+    /// Defaults to the number of available CPU cores.
+    #[arg(global = true, long, env = "UV_CONCURRENT_BUILDS")]
+    concurrent_builds: Option<NonZeroUsize>,
+
+    /// The number of threads to use when installing and unzipping wheels.
+    ///
+    /// Defaults to the number of available CPU cores.
+    #[arg(global = true, long, env = "UV_CONCURRENT_INSTALLS")]
+    concurrent_installs: Option<NonZeroUsize>,
+
+    /// The path to a `uv.toml` file to use for configuration.
+    ///
+    /// By default, uv reads the nearest `uv.toml` or `pyproject.toml` with a `[tool.uv]` table in
+    /// the current directory or its parents, along with the user-level `uv.toml`.
+    #[arg(global = true, long, env = "UV_CONFIG_FILE")]
+    config_file: Option<PathBuf>,
+
+    /// Avoid reading any configuration files.
+    #[arg(
+        global = true,
+        long,
+        env = "UV_NO_CONFIG",
+        conflicts_with = "config_file"
+    )]
+    no_config: bool,
+
     #[command(flatten)]
     cache_args: CacheArgs,
 }
@@ -118,8 +219,16 @@ enum Commands {
     /// Create a virtual environment.
     #[clap(alias = "virtualenv", alias = "v")]
     Venv(VenvArgs),
+    /// Run a command in the project's virtual environment, or in an ephemeral environment.
+    Run(RunArgs),
     /// Manage the cache.
     Cache(CacheNamespace),
+    /// Manage Python interpreters.
+    Python(PythonNamespace),
+    /// Inspect installation reports.
+    Report(ReportNamespace),
+    /// Gather diagnostic information for bug reports.
+    Debug(DebugNamespace),
     /// Remove all items from the cache.
     #[clap(hide = true)]
     Clean(CleanArgs),
@@ -145,6 +254,119 @@ enum CacheCommand {
     Clean(CleanArgs),
     /// Show the cache directory.
     Dir,
+    /// Verify cached wheels against the digests currently advertised by the index.
+    Audit(AuditArgs),
+}
+
+#[derive(Args)]
+struct PythonNamespace {
+    #[clap(subcommand)]
+    command: PythonCommand,
+}
+
+#[derive(Subcommand)]
+enum PythonCommand {
+    /// List the Python interpreters that uv can discover, in order of precedence.
+    List(PythonListArgs),
+    /// Pin the Python interpreter for the project in the current directory.
+    ///
+    /// Writes the request to a `.python-version` file, which `uv venv` (and `uv pip install` or
+    /// `uv pip sync` with `--system`) will respect when `--python` isn't provided.
+    Pin(PythonPinArgs),
+}
+
+#[derive(Args)]
+struct PythonListArgs {
+    /// The format in which to list the interpreters.
+    #[arg(long, value_enum, default_value = "text")]
+    format: PythonListFormat,
+}
+
+#[derive(Args)]
+struct PythonPinArgs {
+    /// The Python interpreter to pin, in any format accepted by `--python` (e.g., `3.11`).
+    ///
+    /// If omitted, the currently pinned request is displayed instead.
+    request: Option<String>,
+}
+
+#[derive(Args)]
+struct DebugNamespace {
+    #[clap(subcommand)]
+    command: DebugCommand,
+}
+
+#[derive(Subcommand)]
+enum DebugCommand {
+    /// Display the uv version, platform, discovered interpreters, supported wheel tags, cache,
+    /// configuration, and relevant environment variables, for inclusion in bug reports.
+    ///
+    /// Credentials in environment variables are redacted.
+    Info(DebugInfoArgs),
+}
+
+#[derive(Args)]
+struct DebugInfoArgs {
+    /// The format in which to display the report.
+    #[arg(long, value_enum, default_value = "text")]
+    format: DebugInfoFormat,
+
+    /// Explain whether the given wheel is compatible with the target interpreter, rather than
+    /// listing the supported tags.
+    ///
+    /// Accepts either a wheel filename (e.g., `numpy-1.26.4-cp312-cp312-manylinux_2_17_x86_64.whl`)
+    /// or a path to a wheel.
+    #[clap(long, value_name = "WHEEL")]
+    wheel: Option<String>,
+
+    /// The Python interpreter for which the supported tags should be reported.
+    ///
+    /// By default, `uv` uses the currently activated virtual environment, or a virtual environment
+    /// (`.venv`) located in the current working directory or any parent directory, falling back to
+    /// the system Python if no virtual environment is found.
+    ///
+    /// Supported formats:
+    /// - `3.10` looks for an installed Python 3.10 in the registry on Windows (see PEP 514), or
+    ///   `python3.10` on Linux and macOS.
+    /// - `python3.10` or `python.exe` looks for a binary with the given name in `PATH`.
+    /// - `/home/ferris/.local/bin/python3.10` uses the exact Python at the given path.
+    #[clap(long, short, verbatim_doc_comment, conflicts_with = "system")]
+    python: Option<String>,
+
+    /// Report the supported tags for the system Python.
+    ///
+    /// WARNING: `--system` is intended for use in continuous integration (CI) environments and
+    /// should be used with caution.
+    #[clap(long, conflicts_with = "python")]
+    system: bool,
+}
+
+#[derive(Args)]
+struct ReportNamespace {
+    #[clap(subcommand)]
+    command: ReportCommand,
+}
+
+#[derive(Subcommand)]
+enum ReportCommand {
+    /// Compare two installation reports, listing the packages that were added, removed, upgraded,
+    /// or downgraded, along with any changes to their sources or hashes.
+    ///
+    /// Reports are read in the format produced by `pip install --report`.
+    Diff(ReportDiffArgs),
+}
+
+#[derive(Args)]
+struct ReportDiffArgs {
+    /// The installation report to compare against.
+    old: PathBuf,
+
+    /// The installation report to compare.
+    new: PathBuf,
+
+    /// The format in which to display the changes.
+    #[arg(long, value_enum, default_value = "text")]
+    format: ReportDiffFormat,
 }
 
 #[derive(Args)]
@@ -154,6 +376,26 @@ struct CleanArgs {
     package: Vec<PackageName>,
 }
 
+#[derive(Args)]
+struct AuditArgs {
+    /// The packages to audit. By default, all cached packages are audited.
+    package: Vec<PackageName>,
+
+    /// The URL of the Python package index (by default: <https://pypi.org/simple>).
+    ///
+    /// Only wheels cached from the given index (or extra indexes) are audited.
+    #[clap(long, short, env = "UV_INDEX_URL", value_parser = parse_index_url)]
+    index_url: Option<Maybe<IndexUrl>>,
+
+    /// Extra URLs of package indexes to audit against, in addition to `--index-url`.
+    #[clap(long, env = "UV_EXTRA_INDEX_URL", value_delimiter = ' ', value_parser = parse_index_url)]
+    extra_index_url: Vec<Maybe<IndexUrl>>,
+
+    /// The format in which to report the audit results.
+    #[arg(long, value_enum, default_value = "text")]
+    output_format: AuditFormat,
+}
+
 #[derive(Args)]
 #[allow(clippy::struct_excessive_bools)]
 struct DirArgs {
@@ -175,12 +417,50 @@ enum PipCommand {
     Sync(PipSyncArgs),
     /// Install packages into the current environment.
     Install(PipInstallArgs),
+    /// Download packages and their dependencies into a directory, without installing them.
+    Download(PipDownloadArgs),
     /// Uninstall packages from the current environment.
     Uninstall(PipUninstallArgs),
+    /// Uninstall packages that were installed as dependencies, but are no longer required by any
+    /// requested package.
+    Autoremove(PipAutoremoveArgs),
     /// Enumerate the installed packages in the current environment.
     Freeze(PipFreezeArgs),
     /// Enumerate the installed packages in the current environment.
     List(PipListArgs),
+    /// Verify that the installed packages have compatible dependencies, or would remain
+    /// installable under a different Python version.
+    Check(PipCheckArgs),
+    /// Generate a software bill of materials (SBOM) for the packages installed in the current
+    /// environment.
+    Sbom(PipSbomArgs),
+    /// Report the licenses of the packages installed in the current environment.
+    Licenses(PipLicensesArgs),
+    /// Audit packages for known vulnerabilities, using the OSV database (which includes the PyPA
+    /// Advisory Database).
+    Audit(PipAuditArgs),
+    /// Manage startup hooks, which run code whenever the interpreter starts.
+    Startup(PipStartupNamespace),
+}
+
+#[derive(Args)]
+struct PipStartupNamespace {
+    #[clap(subcommand)]
+    command: PipStartupCommand,
+}
+
+#[derive(Subcommand)]
+enum PipStartupCommand {
+    /// Install a startup hook, replacing any existing hook with the same name.
+    Add(PipStartupAddArgs),
+    /// Remove a startup hook.
+    Remove(PipStartupArgs),
+    /// Enable a previously disabled startup hook.
+    Enable(PipStartupArgs),
+    /// Disable a startup hook, without removing it.
+    Disable(PipStartupArgs),
+    /// List the startup hooks installed in the current environment.
+    List(PipStartupListArgs),
 }
 
 /// Clap parser for the union of date and datetime
@@ -230,11 +510,67 @@ fn parse_index_url(input: &str) -> Result<Maybe<IndexUrl>, String> {
     }
 }
 
+/// Parse a script name prefix or suffix, which must not contain a path separator.
+fn parse_script_affix(input: &str) -> Result<String, String> {
+    if input.contains(['/', '\\']) {
+        Err("must not contain a path separator".to_string())
+    } else {
+        Ok(input.to_string())
+    }
+}
+
+/// Determine the Python interpreter to use, falling back to the interpreter pinned for the current
+/// directory (e.g., via `uv python pin`) if none was requested.
+///
+/// The pin is only respected if `system` is set, i.e., if the command would otherwise use the
+/// first Python interpreter found in the `PATH`.
+fn pinned_python(python: Option<String>, system: bool) -> Result<Option<String>> {
+    if python.is_some() || !system {
+        return Ok(python);
+    }
+    let Some(pin) = PythonPin::find(&std::env::current_dir()?)? else {
+        return Ok(None);
+    };
+    debug!(
+        "Using pinned Python `{}` from: {}",
+        pin.request,
+        pin.path.display()
+    );
+    Ok(Some(pin.request))
+}
+
+/// The options that control the environments in which source distributions are built, shared by
+/// `pip compile`, `pip sync`, and `pip install`.
+#[derive(Args)]
+struct BuildIsolationArgs {
+    /// Disable isolation when building source distributions.
+    ///
+    /// Assumes that the build dependencies specified by PEP 518 (along with any other packages
+    /// required at build time) are already installed in the target environment.
+    #[clap(long, overrides_with = "build_isolation")]
+    no_build_isolation: bool,
+
+    /// Build source distributions in isolated environments (the default).
+    #[clap(long, overrides_with = "no_build_isolation", hide = true)]
+    build_isolation: bool,
+
+    /// Install the given requirement(s) into each isolated build environment, alongside the
+    /// requirements declared by the build backend.
+    ///
+    /// Useful for source distributions that import packages (like `torch`) at build time without
+    /// declaring them as build requirements.
+    #[clap(long, conflicts_with = "no_build_isolation")]
+    build_requirement: Vec<Requirement>,
+}
+
 #[derive(Args)]
 #[allow(clippy::struct_excessive_bools)]
 struct PipCompileArgs {
     /// Include all packages listed in the given `requirements.in` files.
     ///
+    /// If a `pyproject.toml`, `setup.py`, or `setup.cfg` file is provided, uv will
+    /// extract the requirements for the relevant project.
+    ///
     /// When the path is `-`, then requirements are read from stdin.
     #[clap(required(true))]
     src_file: Vec<PathBuf>,
@@ -287,6 +623,24 @@ struct PipCompileArgs {
     #[clap(long, short)]
     output_file: Option<PathBuf>,
 
+    /// Compile each input file to its own output file, in order, constraining each file by the
+    /// pins of the files before it.
+    ///
+    /// Each `.in` file is compiled to the `.txt` file alongside it. For example,
+    /// `uv pip compile --layered requirements.in dev-requirements.in` writes `requirements.txt`,
+    /// then writes a `dev-requirements.txt` that's guaranteed to agree with its pins.
+    #[clap(long, conflicts_with = "output_file")]
+    layered: bool,
+
+    /// Verify that the output file is up-to-date with respect to its inputs, rather than
+    /// rewriting it.
+    ///
+    /// Re-resolves the inputs and exits with a non-zero status if the result differs from the
+    /// existing output file (e.g., to gate changes to the inputs in CI). Requires `--output-file`
+    /// or `--layered`.
+    #[clap(long, conflicts_with_all = ["upgrade", "upgrade_package"])]
+    check: bool,
+
     /// Exclude comment annotations indicating the source of each package.
     #[clap(long)]
     no_annotate: bool,
@@ -295,6 +649,16 @@ struct PipCompileArgs {
     #[clap(long)]
     no_header: bool,
 
+    /// The command to display in the header of the generated output file, in lieu of the actual
+    /// invocation (e.g., `make requirements.txt`).
+    #[clap(
+        long,
+        alias = "custom-compile-command",
+        env = "UV_CUSTOM_COMPILE_COMMAND",
+        conflicts_with = "no_header"
+    )]
+    custom_header: Option<String>,
+
     /// Run offline, i.e., without accessing the network.
     #[arg(
         global = true,
@@ -343,7 +707,8 @@ struct PipCompileArgs {
     /// Locations to search for candidate distributions, beyond those found in the indexes.
     ///
     /// If a path, the target must be a directory that contains package as wheel files (`.whl`) or
-    /// source distributions (`.tar.gz` or `.zip`) at the top level.
+    /// source distributions (`.tar.gz` or `.zip`) at the top level, or an HTML file that contains
+    /// a flat list of links to package files (which may be relative to the HTML file).
     ///
     /// If a URL, the page must contain a flat list of links to package files.
     #[clap(long, short)]
@@ -392,6 +757,19 @@ struct PipCompileArgs {
     #[clap(long, short = 'C', alias = "config-settings")]
     config_setting: Vec<ConfigSettingEntry>,
 
+    #[command(flatten)]
+    isolation: BuildIsolationArgs,
+
+    /// Constrain build dependencies using the given requirements files when building source
+    /// distributions.
+    ///
+    /// Constraints files are `requirements.txt`-like files that only control the _version_ of a
+    /// requirement that's installed. Build constraints apply to the isolated environments used to
+    /// build source distributions (e.g., pinning `setuptools` or `cython`), independently of the
+    /// constraints applied to the packages being installed.
+    #[clap(long, short = 'b', conflicts_with = "no_build_isolation")]
+    build_constraint: Vec<PathBuf>,
+
     /// The minimum Python version that should be supported by the compiled requirements (e.g.,
     /// `3.7` or `3.7.9`).
     ///
@@ -400,13 +778,47 @@ struct PipCompileArgs {
     #[arg(long, short)]
     python_version: Option<PythonVersion>,
 
+    /// The platform for which requirements should be resolved (e.g., `x86_64-unknown-linux-gnu`),
+    /// rather than the platform of the current interpreter.
+    ///
+    /// Represented as a "target triple", a string that describes the target platform in terms of
+    /// its CPU, vendor, and operating system name, like `x86_64-unknown-linux-gnu` or
+    /// `aarch64-apple-darwin`.
+    #[arg(long, value_enum)]
+    python_platform: Option<TargetTriple>,
+
     /// Limit candidate packages to those that were uploaded prior to the given date.
     ///
     /// Accepts both RFC 3339 timestamps (e.g., `2006-12-02T02:07:43Z`) and UTC dates in the same
     /// format (e.g., `2006-12-02`).
+    ///
+    /// Resolutions made with an upload cutoff are cached, and reused until the inputs change or
+    /// the cache is refreshed; as such, packages that are yanked after a resolution is cached are
+    /// not excluded from it.
     #[arg(long, value_parser = date_or_datetime, hide = true)]
     exclude_newer: Option<DateTime<Utc>>,
 
+    /// The maximum time to spend resolving dependencies, in seconds, before giving up. This
+    /// includes time spent waiting on package metadata.
+    ///
+    /// When exceeded, resolution fails with a summary of the packages that were involved in the
+    /// most conflicts.
+    #[arg(long, env = "UV_RESOLUTION_TIMEOUT")]
+    resolution_timeout: Option<u64>,
+
+    /// The maximum number of resolution steps (i.e., package version decisions, including those
+    /// that are later backtracked) before giving up.
+    #[arg(long)]
+    max_resolution_steps: Option<usize>,
+
+    /// Exclude any distributions published more recently than the given age (e.g., `7d`).
+    ///
+    /// Accepts an age in seconds (`s`), minutes (`m`), hours (`h`), days (`d`), or weeks (`w`).
+    /// May also be provided for a specific package (e.g., `--min-release-age flask=1d`), which
+    /// takes precedence over the default; an age of zero exempts the package.
+    #[arg(long, env = "UV_MIN_RELEASE_AGE", value_delimiter = ' ')]
+    min_release_age: Vec<MinReleaseAgeEntry>,
+
     /// Specify a package to omit from the output resolution. Its dependencies will still be
     /// included in the resolution. Equivalent to pip-compile's `--unsafe-package` option.
     #[clap(long, alias = "unsafe-package")]
@@ -420,6 +832,18 @@ struct PipCompileArgs {
     #[clap(long, hide = true)]
     emit_find_links: bool,
 
+    /// Include extras in the output file.
+    ///
+    /// By default, `uv` strips extras, as any packages pulled in by the extras are already included
+    /// as dependencies in the output file directly. Further, output files generated with
+    /// `--no-strip-extras` cannot be used as constraints files in `install` and `sync` invocations.
+    #[clap(long, overrides_with("strip_extras"))]
+    no_strip_extras: bool,
+
+    /// Strip extras from the output file (the default).
+    #[clap(long, overrides_with("no_strip_extras"), hide = true)]
+    strip_extras: bool,
+
     /// Choose the style of the annotation comments, which indicate the source of each package.
     #[clap(long, default_value_t=AnnotationStyle::Split, value_enum)]
     annotation_style: AnnotationStyle,
@@ -443,6 +867,17 @@ struct PipSyncArgs {
     #[clap(long)]
     reinstall_package: Vec<PackageName>,
 
+    /// Retain the given installed package, even if it isn't included in the requirements.
+    ///
+    /// Useful for preserving development tooling (e.g., debuggers) that was installed separately.
+    #[clap(long)]
+    keep: Vec<PackageName>,
+
+    /// Retain all installed packages, even if they aren't included in the requirements, such that
+    /// packages are installed and upgraded, but never removed.
+    #[clap(long, conflicts_with = "keep")]
+    no_strip: bool,
+
     /// Run offline, i.e., without accessing the network.
     #[arg(
         global = true,
@@ -464,6 +899,37 @@ struct PipSyncArgs {
     #[clap(long, value_enum, default_value_t = install_wheel_rs::linker::LinkMode::default())]
     link_mode: install_wheel_rs::linker::LinkMode,
 
+    /// Don't install the given package as a zip archive, even when using `--link-mode zip`.
+    #[clap(long)]
+    no_zip: Vec<PackageName>,
+
+    /// A prefix to add to the names of installed console and GUI scripts (e.g., `proj-`).
+    #[clap(long, value_parser = parse_script_affix)]
+    script_prefix: Option<String>,
+
+    /// A suffix to add to the names of installed console and GUI scripts (e.g., `-3.12`).
+    #[clap(long, value_parser = parse_script_affix)]
+    script_suffix: Option<String>,
+
+    /// Verify the installed files against the sizes and hashes in each wheel's `RECORD`.
+    ///
+    /// By default, the hashes in the `RECORD` are reused as-is, since the files are installed
+    /// unchanged from the wheel. With this flag, every file listed in the `RECORD` is checked for
+    /// existence, size, and hash once the wheel is fully installed, catching truncated
+    /// extractions and filesystem errors at install time. Wheels that were verified as they were
+    /// downloaded aren't checked again.
+    #[clap(long)]
+    verify_hashes: bool,
+
+    /// Verify downloaded distributions against the expected hashes in the given file.
+    ///
+    /// The hash database is a `requirements.txt`-like file in which each entry pins a package to
+    /// a version, followed by the `--hash=sha256:...` digests of its permitted distributions. If a
+    /// package is listed, only the listed versions and distributions may be downloaded; packages
+    /// that aren't listed are unrestricted.
+    #[clap(long, env = "UV_HASH_DATABASE")]
+    hash_database: Option<PathBuf>,
+
     /// The URL of the Python package index (by default: <https://pypi.org/simple>).
     ///
     /// The index given by this flag is given lower priority than all other
@@ -490,7 +956,8 @@ struct PipSyncArgs {
     /// Locations to search for candidate distributions, beyond those found in the indexes.
     ///
     /// If a path, the target must be a directory that contains package as wheel files (`.whl`) or
-    /// source distributions (`.tar.gz` or `.zip`) at the top level.
+    /// source distributions (`.tar.gz` or `.zip`) at the top level, or an HTML file that contains
+    /// a flat list of links to package files (which may be relative to the HTML file).
     ///
     /// If a URL, the page must contain a flat list of links to package files.
     #[clap(long, short)]
@@ -509,7 +976,7 @@ struct PipSyncArgs {
     /// workflows.
     ///
     /// Supported formats:
-    /// - `3.10` looks for an installed Python 3.10 using `py --list-paths` on Windows, or
+    /// - `3.10` looks for an installed Python 3.10 in the registry on Windows (see PEP 514), or
     ///   `python3.10` on Linux and macOS.
     /// - `python3.10` or `python.exe` looks for a binary with the given name in `PATH`.
     /// - `/home/ferris/.local/bin/python3.10` uses the exact Python at the given path.
@@ -527,6 +994,30 @@ struct PipSyncArgs {
     #[clap(long, conflicts_with = "python")]
     system: bool,
 
+    /// Create a virtual environment at `.venv` if none is found, without prompting.
+    ///
+    /// By default, `uv` prompts to create a virtual environment when none is found and the
+    /// terminal is interactive. The new virtual environment uses the Python interpreter pinned
+    /// for the current directory, if any, or the first Python found in the system `PATH`.
+    #[clap(long, conflicts_with_all = ["python", "system"])]
+    auto_create: bool,
+
+    /// Install into an environment that's shared between the members of a Unix group.
+    ///
+    /// Verifies that every directory in the environment is owned by the environment's group,
+    /// group-writable, and setgid, and that the current umask preserves group write permissions,
+    /// such that any member of the group can later upgrade or remove the installed packages.
+    /// Packages are always copied into a shared environment, rather than linked from the cache.
+    #[clap(long)]
+    shared: bool,
+
+    /// Allow installing into a Conda environment (e.g., as detected through `CONDA_PREFIX`).
+    ///
+    /// Packages installed by `uv` aren't tracked by `conda`, and may conflict with the packages
+    /// that `conda` manages, so installing into a Conda environment requires an explicit opt-in.
+    #[clap(long, env = "UV_ALLOW_CONDA")]
+    allow_conda: bool,
+
     /// Use legacy `setuptools` behavior when building source distributions without a
     /// `pyproject.toml`.
     #[clap(long)]
@@ -567,6 +1058,19 @@ struct PipSyncArgs {
     #[clap(long, short = 'C', alias = "config-settings")]
     config_setting: Vec<ConfigSettingEntry>,
 
+    #[command(flatten)]
+    isolation: BuildIsolationArgs,
+
+    /// Constrain build dependencies using the given requirements files when building source
+    /// distributions.
+    ///
+    /// Constraints files are `requirements.txt`-like files that only control the _version_ of a
+    /// requirement that's installed. Build constraints apply to the isolated environments used to
+    /// build source distributions (e.g., pinning `setuptools` or `cython`), independently of the
+    /// constraints applied to the packages being installed.
+    #[clap(long, short = 'b', conflicts_with = "no_build_isolation")]
+    build_constraint: Vec<PathBuf>,
+
     /// Validate the virtual environment after completing the installation, to detect packages with
     /// missing dependencies or other issues.
     #[clap(long)]
@@ -626,6 +1130,14 @@ struct PipInstallArgs {
     #[clap(long, short = 'U')]
     upgrade: bool,
 
+    /// The strategy to use when upgrading packages with `--upgrade`.
+    ///
+    /// With `only-if-needed`, the requested packages are upgraded, but their dependencies are only
+    /// upgraded if the installed versions no longer satisfy the requirements. With `eager`, all
+    /// dependencies are upgraded to the latest compatible versions.
+    #[clap(long, value_enum, default_value_t = UpgradeStrategy::default())]
+    upgrade_strategy: UpgradeStrategy,
+
     /// Allow upgrade of a specific package.
     #[clap(long, short = 'P')]
     upgrade_package: Vec<PackageName>,
@@ -664,6 +1176,37 @@ struct PipInstallArgs {
     #[clap(long, value_enum, default_value_t = install_wheel_rs::linker::LinkMode::default())]
     link_mode: install_wheel_rs::linker::LinkMode,
 
+    /// Don't install the given package as a zip archive, even when using `--link-mode zip`.
+    #[clap(long)]
+    no_zip: Vec<PackageName>,
+
+    /// A prefix to add to the names of installed console and GUI scripts (e.g., `proj-`).
+    #[clap(long, value_parser = parse_script_affix)]
+    script_prefix: Option<String>,
+
+    /// A suffix to add to the names of installed console and GUI scripts (e.g., `-3.12`).
+    #[clap(long, value_parser = parse_script_affix)]
+    script_suffix: Option<String>,
+
+    /// Verify the installed files against the sizes and hashes in each wheel's `RECORD`.
+    ///
+    /// By default, the hashes in the `RECORD` are reused as-is, since the files are installed
+    /// unchanged from the wheel. With this flag, every file listed in the `RECORD` is checked for
+    /// existence, size, and hash once the wheel is fully installed, catching truncated
+    /// extractions and filesystem errors at install time. Wheels that were verified as they were
+    /// downloaded aren't checked again.
+    #[clap(long)]
+    verify_hashes: bool,
+
+    /// Verify downloaded distributions against the expected hashes in the given file.
+    ///
+    /// The hash database is a `requirements.txt`-like file in which each entry pins a package to
+    /// a version, followed by the `--hash=sha256:...` digests of its permitted distributions. If a
+    /// package is listed, only the listed versions and distributions may be downloaded; packages
+    /// that aren't listed are unrestricted.
+    #[clap(long, env = "UV_HASH_DATABASE")]
+    hash_database: Option<PathBuf>,
+
     #[clap(long, value_enum, default_value_t = ResolutionMode::default())]
     resolution: ResolutionMode,
 
@@ -703,7 +1246,8 @@ struct PipInstallArgs {
     /// Locations to search for candidate distributions, beyond those found in the indexes.
     ///
     /// If a path, the target must be a directory that contains package as wheel files (`.whl`) or
-    /// source distributions (`.tar.gz` or `.zip`) at the top level.
+    /// source distributions (`.tar.gz` or `.zip`) at the top level, or an HTML file that contains
+    /// a flat list of links to package files (which may be relative to the HTML file).
     ///
     /// If a URL, the page must contain a flat list of links to package files.
     #[clap(long, short)]
@@ -722,7 +1266,7 @@ struct PipInstallArgs {
     /// workflows.
     ///
     /// Supported formats:
-    /// - `3.10` looks for an installed Python 3.10 using `py --list-paths` on Windows, or
+    /// - `3.10` looks for an installed Python 3.10 in the registry on Windows (see PEP 514), or
     ///   `python3.10` on Linux and macOS.
     /// - `python3.10` or `python.exe` looks for a binary with the given name in `PATH`.
     /// - `/home/ferris/.local/bin/python3.10` uses the exact Python at the given path.
@@ -740,6 +1284,60 @@ struct PipInstallArgs {
     #[clap(long, conflicts_with = "python")]
     system: bool,
 
+    /// Create a virtual environment at `.venv` if none is found, without prompting.
+    ///
+    /// By default, `uv` prompts to create a virtual environment when none is found and the
+    /// terminal is interactive. The new virtual environment uses the Python interpreter pinned
+    /// for the current directory, if any, or the first Python found in the system `PATH`.
+    #[clap(long, conflicts_with_all = ["python", "system"])]
+    auto_create: bool,
+
+    /// Install into an environment that's shared between the members of a Unix group.
+    ///
+    /// Verifies that every directory in the environment is owned by the environment's group,
+    /// group-writable, and setgid, and that the current umask preserves group write permissions,
+    /// such that any member of the group can later upgrade or remove the installed packages.
+    /// Packages are always copied into a shared environment, rather than linked from the cache.
+    #[clap(long)]
+    shared: bool,
+
+    /// Allow installing into a Conda environment (e.g., as detected through `CONDA_PREFIX`).
+    ///
+    /// Packages installed by `uv` aren't tracked by `conda`, and may conflict with the packages
+    /// that `conda` manages, so installing into a Conda environment requires an explicit opt-in.
+    #[clap(long, env = "UV_ALLOW_CONDA")]
+    allow_conda: bool,
+
+    /// Install packages into the virtual environment at the given path.
+    ///
+    /// May be provided multiple times to apply the same resolution to several environments, which
+    /// must share the same Python version and platform. The requirements are resolved and
+    /// downloaded once, against the first environment, and then installed into each.
+    #[clap(long = "venv", conflicts_with = "python", conflicts_with = "system")]
+    venvs: Vec<PathBuf>,
+
+    /// Install packages into the given directory, rather than into the environment's
+    /// `site-packages`.
+    ///
+    /// The directory is created if it doesn't exist. Packages are installed as they would be into
+    /// `site-packages`, such that the directory can be added to `PYTHONPATH` (or deployed
+    /// alongside an application, as in a serverless function).
+    #[clap(long, conflicts_with = "venvs", conflicts_with = "shared")]
+    target: Option<PathBuf>,
+
+    /// The platform for which packages should be installed (e.g., `x86_64-unknown-linux-gnu`),
+    /// rather than the platform of the current interpreter.
+    ///
+    /// Represented as a "target triple", a string that describes the target platform in terms of
+    /// its CPU, vendor, and operating system name, like `x86_64-unknown-linux-gnu` or
+    /// `aarch64-apple-darwin`.
+    ///
+    /// Requires `--target`, since the packages can't be used by the current interpreter. Launchers
+    /// for the packages' entrypoints are not generated. Source distributions are built for the
+    /// current platform, so `--only-binary :all:` is recommended.
+    #[clap(long, value_enum, requires = "target")]
+    python_platform: Option<TargetTriple>,
+
     /// Use legacy `setuptools` behavior when building source distributions without a
     /// `pyproject.toml`.
     #[clap(long)]
@@ -780,6 +1378,19 @@ struct PipInstallArgs {
     #[clap(long, short = 'C', alias = "config-settings")]
     config_setting: Vec<ConfigSettingEntry>,
 
+    #[command(flatten)]
+    isolation: BuildIsolationArgs,
+
+    /// Constrain build dependencies using the given requirements files when building source
+    /// distributions.
+    ///
+    /// Constraints files are `requirements.txt`-like files that only control the _version_ of a
+    /// requirement that's installed. Build constraints apply to the isolated environments used to
+    /// build source distributions (e.g., pinning `setuptools` or `cython`), independently of the
+    /// constraints applied to the packages being installed.
+    #[clap(long, short = 'b', conflicts_with = "no_build_isolation")]
+    build_constraint: Vec<PathBuf>,
+
     /// Validate the virtual environment after completing the installation, to detect packages with
     /// missing dependencies or other issues.
     #[clap(long)]
@@ -791,55 +1402,282 @@ struct PipInstallArgs {
     /// format (e.g., `2006-12-02`).
     #[arg(long, value_parser = date_or_datetime, hide = true)]
     exclude_newer: Option<DateTime<Utc>>,
+
+    /// The maximum time to spend resolving dependencies, in seconds, before giving up. This
+    /// includes time spent waiting on package metadata.
+    ///
+    /// When exceeded, resolution fails with a summary of the packages that were involved in the
+    /// most conflicts.
+    #[arg(long, env = "UV_RESOLUTION_TIMEOUT")]
+    resolution_timeout: Option<u64>,
+
+    /// The maximum number of resolution steps (i.e., package version decisions, including those
+    /// that are later backtracked) before giving up.
+    #[arg(long)]
+    max_resolution_steps: Option<usize>,
+
+    /// Exclude any distributions published more recently than the given age (e.g., `7d`).
+    ///
+    /// Accepts an age in seconds (`s`), minutes (`m`), hours (`h`), days (`d`), or weeks (`w`).
+    /// May also be provided for a specific package (e.g., `--min-release-age flask=1d`), which
+    /// takes precedence over the default; an age of zero exempts the package.
+    #[arg(long, env = "UV_MIN_RELEASE_AGE", value_delimiter = ' ')]
+    min_release_age: Vec<MinReleaseAgeEntry>,
 }
 
 #[derive(Args)]
 #[allow(clippy::struct_excessive_bools)]
 #[command(group = clap::ArgGroup::new("sources").required(true).multiple(true))]
-struct PipUninstallArgs {
-    /// Uninstall all listed packages.
+struct PipDownloadArgs {
+    /// Download all listed packages.
     #[clap(group = "sources")]
     package: Vec<String>,
 
-    /// Uninstall all packages listed in the given requirements files.
+    /// Download all packages listed in the given requirements files.
     #[clap(long, short, group = "sources")]
     requirement: Vec<PathBuf>,
 
-    /// Uninstall the editable package based on the provided local file path.
-    #[clap(long, short, group = "sources")]
-    editable: Vec<String>,
-
-    /// The Python interpreter from which packages should be uninstalled.
+    /// Constrain versions using the given requirements files.
     ///
-    /// By default, `uv` uninstalls from the virtual environment in the current working directory or
-    /// any parent directory. The `--python` option allows you to specify a different interpreter,
-    /// which is intended for use in continuous integration (CI) environments or other automated
-    /// workflows.
+    /// Constraints files are `requirements.txt`-like files that only control the _version_ of a
+    /// requirement that's installed. However, including a package in a constraints file will _not_
+    /// trigger the installation of that package.
     ///
-    /// Supported formats:
-    /// - `3.10` looks for an installed Python 3.10 using `py --list-paths` on Windows, or
-    ///   `python3.10` on Linux and macOS.
-    /// - `python3.10` or `python.exe` looks for a binary with the given name in `PATH`.
-    /// - `/home/ferris/.local/bin/python3.10` uses the exact Python at the given path.
-    #[clap(long, short, verbatim_doc_comment, conflicts_with = "system")]
-    python: Option<String>,
+    /// This is equivalent to pip's `--constraint` option.
+    #[clap(long, short)]
+    constraint: Vec<PathBuf>,
 
-    /// Use the system Python to uninstall packages.
+    /// Override versions using the given requirements files.
     ///
-    /// By default, `uv` uninstalls from the virtual environment in the current working directory or
-    /// any parent directory. The `--system` option instructs `uv` to instead use the first Python
-    /// found in the system `PATH`.
+    /// Overrides files are `requirements.txt`-like files that force a specific version of a
+    /// requirement to be installed, regardless of the requirements declared by any constituent
+    /// package, and regardless of whether this would be considered an invalid resolution.
+    #[clap(long)]
+    r#override: Vec<PathBuf>,
+
+    /// The directory into which the wheels and source distributions should be downloaded.
     ///
-    /// WARNING: `--system` is intended for use in continuous integration (CI) environments and
-    /// should be used with caution, as it can modify the system Python installation.
-    #[clap(long, conflicts_with = "python")]
-    system: bool,
-}
+    /// Archives that already exist in the directory are not downloaded again, unless they fail to
+    /// match the expected hash.
+    #[clap(long, short, default_value = ".")]
+    dest: PathBuf,
 
-#[derive(Args)]
-#[allow(clippy::struct_excessive_bools)]
-struct PipFreezeArgs {
-    /// Validate the virtual environment, to detect packages with missing dependencies or other
+    /// Ignore package dependencies, instead only downloading those packages explicitly listed on
+    /// the command line or in the requirements files.
+    #[clap(long)]
+    no_deps: bool,
+
+    #[clap(long, value_enum, default_value_t = ResolutionMode::default())]
+    resolution: ResolutionMode,
+
+    #[clap(long, value_enum, default_value_t = PreReleaseMode::default(), conflicts_with = "pre")]
+    prerelease: PreReleaseMode,
+
+    #[clap(long, hide = true, conflicts_with = "prerelease")]
+    pre: bool,
+
+    /// Run offline, i.e., without accessing the network.
+    #[arg(
+        global = true,
+        long,
+        conflicts_with = "refresh",
+        conflicts_with = "refresh_package"
+    )]
+    offline: bool,
+
+    /// Refresh all cached data.
+    #[clap(long)]
+    refresh: bool,
+
+    /// Refresh cached data for a specific package.
+    #[clap(long)]
+    refresh_package: Vec<PackageName>,
+
+    /// Verify downloaded distributions against the expected hashes in the given file.
+    ///
+    /// The hash database is a `requirements.txt`-like file in which each entry pins a package to
+    /// a version, followed by the `--hash=sha256:...` digests of its permitted distributions. If a
+    /// package is listed, only the listed versions and distributions may be downloaded; packages
+    /// that aren't listed are unrestricted.
+    #[clap(long, env = "UV_HASH_DATABASE")]
+    hash_database: Option<PathBuf>,
+
+    /// The URL of the Python package index (by default: <https://pypi.org/simple>).
+    ///
+    /// The index given by this flag is given lower priority than all other
+    /// indexes specified via the `--extra-index-url` flag.
+    #[clap(long, short, env = "UV_INDEX_URL", value_parser = parse_index_url)]
+    index_url: Option<Maybe<IndexUrl>>,
+
+    /// Extra URLs of package indexes to use, in addition to `--index-url`.
+    ///
+    /// All indexes given via this flag take priority over the index
+    /// in `--index-url` (which defaults to PyPI). And when multiple
+    /// `--extra-index-url` flags are given, earlier values take priority.
+    #[clap(long, env = "UV_EXTRA_INDEX_URL", value_delimiter = ' ', value_parser = parse_index_url)]
+    extra_index_url: Vec<Maybe<IndexUrl>>,
+
+    /// Ignore the registry index (e.g., PyPI), instead relying on direct URL dependencies and those
+    /// discovered via `--find-links`.
+    #[clap(long, conflicts_with = "index_url", conflicts_with = "extra_index_url")]
+    no_index: bool,
+
+    /// Locations to search for candidate distributions, beyond those found in the indexes.
+    ///
+    /// If a path, the target must be a directory that contains package as wheel files (`.whl`) or
+    /// source distributions (`.tar.gz` or `.zip`) at the top level, or an HTML file that contains
+    /// a flat list of links to package files (which may be relative to the HTML file).
+    ///
+    /// If a URL, the page must contain a flat list of links to package files.
+    #[clap(long, short)]
+    find_links: Vec<FlatIndexLocation>,
+
+    /// Don't build source distributions.
+    ///
+    /// When enabled, resolving will not run arbitrary code, and only wheels will be downloaded.
+    ///
+    /// Alias for `--only-binary :all:`.
+    #[clap(long, conflicts_with = "only_binary")]
+    no_build: bool,
+
+    /// Only use pre-built wheels; don't build or download source distributions.
+    ///
+    /// Multiple packages may be provided. Disable binaries for all packages with `:all:`.
+    /// Clear previously specified packages with `:none:`.
+    #[clap(long, conflicts_with = "no_build")]
+    only_binary: Vec<PackageNameSpecifier>,
+
+    /// The Python version for which packages should be downloaded (e.g., `3.7` or `3.7.9`),
+    /// rather than the version of the current interpreter.
+    ///
+    /// If a patch version is omitted, the most recent known patch version for that minor version
+    /// is assumed. For example, `3.7` is mapped to `3.7.17`.
+    #[arg(long, short)]
+    python_version: Option<PythonVersion>,
+
+    /// The platform for which packages should be downloaded (e.g., `x86_64-unknown-linux-gnu`),
+    /// rather than the platform of the current interpreter.
+    ///
+    /// Represented as a "target triple", a string that describes the target platform in terms of
+    /// its CPU, vendor, and operating system name, like `x86_64-unknown-linux-gnu` or
+    /// `aarch64-apple-darwin`.
+    #[arg(long, value_enum)]
+    python_platform: Option<TargetTriple>,
+
+    /// Limit candidate packages to those that were uploaded prior to the given date.
+    ///
+    /// Accepts both RFC 3339 timestamps (e.g., `2006-12-02T02:07:43Z`) and UTC dates in the same
+    /// format (e.g., `2006-12-02`).
+    #[arg(long, value_parser = date_or_datetime, hide = true)]
+    exclude_newer: Option<DateTime<Utc>>,
+}
+
+#[derive(Args)]
+#[allow(clippy::struct_excessive_bools)]
+#[command(group = clap::ArgGroup::new("sources").required(true).multiple(true))]
+struct PipUninstallArgs {
+    /// Uninstall all listed packages.
+    #[clap(group = "sources")]
+    package: Vec<String>,
+
+    /// Uninstall all packages listed in the given requirements files.
+    #[clap(long, short, group = "sources")]
+    requirement: Vec<PathBuf>,
+
+    /// Uninstall the editable package based on the provided local file path.
+    #[clap(long, short, group = "sources")]
+    editable: Vec<String>,
+
+    /// The Python interpreter from which packages should be uninstalled.
+    ///
+    /// By default, `uv` uninstalls from the virtual environment in the current working directory or
+    /// any parent directory. The `--python` option allows you to specify a different interpreter,
+    /// which is intended for use in continuous integration (CI) environments or other automated
+    /// workflows.
+    ///
+    /// Supported formats:
+    /// - `3.10` looks for an installed Python 3.10 in the registry on Windows (see PEP 514), or
+    ///   `python3.10` on Linux and macOS.
+    /// - `python3.10` or `python.exe` looks for a binary with the given name in `PATH`.
+    /// - `/home/ferris/.local/bin/python3.10` uses the exact Python at the given path.
+    #[clap(long, short, verbatim_doc_comment, conflicts_with = "system")]
+    python: Option<String>,
+
+    /// Use the system Python to uninstall packages.
+    ///
+    /// By default, `uv` uninstalls from the virtual environment in the current working directory or
+    /// any parent directory. The `--system` option instructs `uv` to instead use the first Python
+    /// found in the system `PATH`.
+    ///
+    /// WARNING: `--system` is intended for use in continuous integration (CI) environments and
+    /// should be used with caution, as it can modify the system Python installation.
+    #[clap(long, conflicts_with = "python")]
+    system: bool,
+
+    /// List the files that would be removed, without uninstalling anything.
+    #[clap(long)]
+    dry_run: bool,
+
+    /// Hash each file before removing it, and warn about any files whose contents no longer
+    /// match the hash recorded at installation time.
+    #[clap(long)]
+    check_modified: bool,
+}
+
+#[derive(Args)]
+struct PipAutoremoveArgs {
+    /// The Python interpreter from which packages should be removed.
+    ///
+    /// By default, `uv` removes packages from the virtual environment in the current working
+    /// directory or any parent directory. The `--python` option allows you to specify a different
+    /// interpreter, which is intended for use in continuous integration (CI) environments or other
+    /// automated workflows.
+    ///
+    /// Supported formats:
+    /// - `3.10` looks for an installed Python 3.10 in the registry on Windows (see PEP 514), or
+    ///   `python3.10` on Linux and macOS.
+    /// - `python3.10` or `python.exe` looks for a binary with the given name in `PATH`.
+    /// - `/home/ferris/.local/bin/python3.10` uses the exact Python at the given path.
+    #[clap(long, short, verbatim_doc_comment, conflicts_with = "system")]
+    python: Option<String>,
+
+    /// Use the system Python to remove packages.
+    ///
+    /// WARNING: `--system` is intended for use in continuous integration (CI) environments and
+    /// should be used with caution, as it can modify the system Python installation.
+    #[clap(long, conflicts_with = "python")]
+    system: bool,
+
+    /// List the packages that would be removed, without uninstalling anything.
+    #[clap(long)]
+    dry_run: bool,
+}
+
+#[derive(Args)]
+#[allow(clippy::struct_excessive_bools)]
+struct PipFreezeArgs {
+    /// Exclude any editable packages from output.
+    #[clap(long)]
+    exclude_editable: bool,
+
+    /// Exclude packages whose names match the given pattern(s), like `types-*`, from the output.
+    #[clap(long, value_name = "PATTERN")]
+    r#exclude: Vec<PackagePattern>,
+
+    /// Only include packages that were requested directly, omitting those that were installed as
+    /// dependencies of other packages.
+    ///
+    /// Packages are considered requested if they were marked with a `REQUESTED` file at install
+    /// time, or if no other installed package depends on them.
+    #[clap(long)]
+    not_required: bool,
+
+    /// Include `pip`, `setuptools`, `distribute`, and `wheel` in the output, which are omitted by
+    /// default.
+    #[clap(long)]
+    all: bool,
+
+    /// Validate the virtual environment, to detect packages with missing dependencies or other
     /// issues.
     #[clap(long)]
     strict: bool,
@@ -851,7 +1689,7 @@ struct PipFreezeArgs {
     /// falling back to the system Python if no virtual environment is found.
     ///
     /// Supported formats:
-    /// - `3.10` looks for an installed Python 3.10 using `py --list-paths` on Windows, or
+    /// - `3.10` looks for an installed Python 3.10 in the registry on Windows (see PEP 514), or
     ///   `python3.10` on Linux and macOS.
     /// - `python3.10` or `python.exe` looks for a binary with the given name in `PATH`.
     /// - `/home/ferris/.local/bin/python3.10` uses the exact Python at the given path.
@@ -874,6 +1712,13 @@ struct PipFreezeArgs {
 #[derive(Args)]
 #[allow(clippy::struct_excessive_bools)]
 struct PipListArgs {
+    /// Only include packages whose names match the given pattern(s), like `boto*`.
+    ///
+    /// Patterns support glob syntax (`*`, `?`, and `[...]`) and are matched against the normalized
+    /// package name.
+    #[clap(value_name = "PATTERN")]
+    packages: Vec<PackagePattern>,
+
     /// Validate the virtual environment, to detect packages with missing dependencies or other
     /// issues.
     #[clap(long)]
@@ -887,9 +1732,44 @@ struct PipListArgs {
     #[clap(long)]
     exclude_editable: bool,
 
-    /// Exclude the specified package(s) from the output.
+    /// Only include packages whose names match the given pattern(s), like `boto*`.
+    ///
+    /// Equivalent to passing the pattern(s) as positional arguments.
+    #[clap(long, value_name = "PATTERN")]
+    include: Vec<PackagePattern>,
+
+    /// Exclude packages whose names match the given pattern(s), like `types-*`, from the output.
+    #[clap(long, value_name = "PATTERN")]
+    r#exclude: Vec<PackagePattern>,
+
+    /// Only include packages installed by the given installer (e.g., `uv` or `pip`), as recorded
+    /// in each package's `INSTALLER` file.
     #[clap(long)]
-    r#exclude: Vec<PackageName>,
+    installer: Option<String>,
+
+    /// Only include packages installed within the given directory (e.g., a specific
+    /// `site-packages` directory).
+    #[clap(long)]
+    location: Option<PathBuf>,
+
+    /// Only include packages that were requested directly, omitting those that were installed as
+    /// dependencies of other packages.
+    ///
+    /// Packages are considered requested if they were marked with a `REQUESTED` file at install
+    /// time, or if no other installed package depends on them.
+    #[clap(long)]
+    not_required: bool,
+
+    /// Report the disk usage of each package, computed from the files listed in its `RECORD`, and
+    /// sort the output from largest to smallest.
+    ///
+    /// Files that are shared between packages are divided evenly among them.
+    #[clap(long)]
+    sizes: bool,
+
+    /// The format in which to list packages.
+    #[arg(long, value_enum, default_value = "columns")]
+    format: ListFormat,
 
     /// The Python interpreter for which packages should be listed.
     ///
@@ -898,7 +1778,7 @@ struct PipListArgs {
     /// falling back to the system Python if no virtual environment is found.
     ///
     /// Supported formats:
-    /// - `3.10` looks for an installed Python 3.10 using `py --list-paths` on Windows, or
+    /// - `3.10` looks for an installed Python 3.10 in the registry on Windows (see PEP 514), or
     ///   `python3.10` on Linux and macOS.
     /// - `python3.10` or `python.exe` looks for a binary with the given name in `PATH`.
     /// - `/home/ferris/.local/bin/python3.10` uses the exact Python at the given path.
@@ -918,13 +1798,242 @@ struct PipListArgs {
     system: bool,
 }
 
+#[derive(Args)]
+struct PipSbomArgs {
+    /// The format of the SBOM.
+    #[arg(long, value_enum, default_value = "cyclonedx-json")]
+    format: SbomFormat,
+
+    /// The Python interpreter for which the SBOM should be generated.
+    ///
+    /// By default, `uv` reports packages in the currently activated virtual environment, or a
+    /// virtual environment (`.venv`) located in the current working directory or any parent
+    /// directory, falling back to the system Python if no virtual environment is found.
+    ///
+    /// Supported formats:
+    /// - `3.10` looks for an installed Python 3.10 in the registry on Windows (see PEP 514), or
+    ///   `python3.10` on Linux and macOS.
+    /// - `python3.10` or `python.exe` looks for a binary with the given name in `PATH`.
+    /// - `/home/ferris/.local/bin/python3.10` uses the exact Python at the given path.
+    #[clap(long, short, verbatim_doc_comment, conflicts_with = "system")]
+    python: Option<String>,
+
+    /// Generate the SBOM for the system Python.
+    ///
+    /// WARNING: `--system` is intended for use in continuous integration (CI) environments and
+    /// should be used with caution.
+    #[clap(long, conflicts_with = "python")]
+    system: bool,
+}
+
+#[derive(Args)]
+struct PipLicensesArgs {
+    /// Select the output format.
+    #[arg(long, value_enum, default_value = "grouped")]
+    format: LicensesFormat,
+
+    /// Exit with a non-zero status if any package is distributed under the given license (e.g.,
+    /// `GPL-3.0-only`).
+    ///
+    /// Packages with a compound license expression that references the given license (e.g., `MIT
+    /// OR GPL-3.0-only`) are also reported. May be provided multiple times.
+    #[clap(long, value_name = "LICENSE")]
+    fail_on: Vec<String>,
+
+    /// The Python interpreter whose packages should be reported.
+    ///
+    /// By default, `uv` reports packages in the currently activated virtual environment, or a
+    /// virtual environment (`.venv`) located in the current working directory or any parent
+    /// directory, falling back to the system Python if no virtual environment is found.
+    ///
+    /// Supported formats:
+    /// - `3.10` looks for an installed Python 3.10 in the registry on Windows (see PEP 514), or
+    ///   `python3.10` on Linux and macOS.
+    /// - `python3.10` or `python.exe` looks for a binary with the given name in `PATH`.
+    /// - `/home/ferris/.local/bin/python3.10` uses the exact Python at the given path.
+    #[clap(long, short, verbatim_doc_comment, conflicts_with = "system")]
+    python: Option<String>,
+
+    /// Report packages in the system Python.
+    ///
+    /// WARNING: `--system` is intended for use in continuous integration (CI) environments and
+    /// should be used with caution.
+    #[clap(long, conflicts_with = "python")]
+    system: bool,
+}
+
+#[derive(Args)]
+struct PipAuditArgs {
+    /// Audit the pinned packages listed in the given requirements files (e.g., the output of
+    /// `uv pip compile`), rather than the packages installed in the current environment.
+    #[clap(long, short)]
+    requirement: Vec<PathBuf>,
+
+    /// Exit with a non-zero status if any known vulnerabilities are found.
+    #[clap(long)]
+    strict: bool,
+
+    /// Run offline, i.e., without accessing the network.
+    #[arg(long)]
+    offline: bool,
+
+    /// The Python interpreter whose packages should be audited.
+    ///
+    /// By default, `uv` audits packages in the currently activated virtual environment, or a
+    /// virtual environment (`.venv`) located in the current working directory or any parent
+    /// directory, falling back to the system Python if no virtual environment is found.
+    ///
+    /// Supported formats:
+    /// - `3.10` looks for an installed Python 3.10 in the registry on Windows (see PEP 514), or
+    ///   `python3.10` on Linux and macOS.
+    /// - `python3.10` or `python.exe` looks for a binary with the given name in `PATH`.
+    /// - `/home/ferris/.local/bin/python3.10` uses the exact Python at the given path.
+    #[clap(
+        long,
+        short,
+        verbatim_doc_comment,
+        conflicts_with = "system",
+        conflicts_with = "requirement"
+    )]
+    python: Option<String>,
+
+    /// Audit packages in the system Python.
+    ///
+    /// WARNING: `--system` is intended for use in continuous integration (CI) environments and
+    /// should be used with caution.
+    #[clap(long, conflicts_with = "python", conflicts_with = "requirement")]
+    system: bool,
+}
+
+#[derive(Args)]
+struct PipCheckArgs {
+    /// Check whether the installed packages would remain installable under the given Python
+    /// version (e.g., `3.13`), rather than the version of the current interpreter.
+    ///
+    /// Reports any packages that would block an upgrade (or downgrade) to the given version:
+    /// packages whose `Requires-Python` excludes it, dependencies that its markers would newly
+    /// require, and packages with neither compatible wheels nor a source distribution on the index.
+    ///
+    /// If a patch version is omitted, the most recent known patch version for that minor version
+    /// is assumed. For example, `3.7` is mapped to `3.7.17`.
+    #[arg(long)]
+    python_version: Option<PythonVersion>,
+
+    /// Run offline, i.e., without accessing the network.
+    #[arg(long)]
+    offline: bool,
+
+    /// The URL of the Python package index (by default: <https://pypi.org/simple>).
+    ///
+    /// Only used to check for compatible wheels with `--python-version`.
+    #[clap(long, short, env = "UV_INDEX_URL", value_parser = parse_index_url)]
+    index_url: Option<Maybe<IndexUrl>>,
+
+    /// Extra URLs of package indexes to use, in addition to `--index-url`.
+    ///
+    /// Only used to check for compatible wheels with `--python-version`.
+    #[clap(long, env = "UV_EXTRA_INDEX_URL", value_delimiter = ' ', value_parser = parse_index_url)]
+    extra_index_url: Vec<Maybe<IndexUrl>>,
+
+    /// Ignore the registry index (e.g., PyPI), skipping the check for compatible wheels.
+    #[clap(long, conflicts_with = "index_url", conflicts_with = "extra_index_url")]
+    no_index: bool,
+
+    /// The Python interpreter for which packages should be checked.
+    ///
+    /// By default, `uv` checks packages in the currently activated virtual environment, or a
+    /// virtual environment (`.venv`) located in the current working directory or any parent
+    /// directory, falling back to the system Python if no virtual environment is found.
+    ///
+    /// Supported formats:
+    /// - `3.10` looks for an installed Python 3.10 in the registry on Windows (see PEP 514), or
+    ///   `python3.10` on Linux and macOS.
+    /// - `python3.10` or `python.exe` looks for a binary with the given name in `PATH`.
+    /// - `/home/ferris/.local/bin/python3.10` uses the exact Python at the given path.
+    #[clap(long, short, verbatim_doc_comment, conflicts_with = "system")]
+    python: Option<String>,
+
+    /// Check packages for the system Python.
+    ///
+    /// By default, `uv` checks packages in the currently activated virtual environment, or a
+    /// virtual environment (`.venv`) located in the current working directory or any parent
+    /// directory, falling back to the system Python if no virtual environment is found. The
+    /// `--system` option instructs `uv` to use the first Python found in the system `PATH`.
+    ///
+    /// WARNING: `--system` is intended for use in continuous integration (CI) environments and
+    /// should be used with caution.
+    #[clap(long, conflicts_with = "python")]
+    system: bool,
+}
+
+#[derive(Args)]
+struct PipStartupAddArgs {
+    /// The name of the startup hook.
+    name: PackageName,
+
+    /// The code to run at startup, e.g., `import coverage; coverage.process_startup()`.
+    ///
+    /// Every line must begin with `import`, since the interpreter ignores any other lines in a
+    /// `.pth` file.
+    code: String,
+
+    /// The Python interpreter into which the startup hook should be installed.
+    ///
+    /// By default, `uv` installs into the virtual environment in the current working directory or
+    /// any parent directory.
+    #[clap(long, short, conflicts_with = "system")]
+    python: Option<String>,
+
+    /// Install the startup hook into the system Python.
+    ///
+    /// WARNING: `--system` is intended for use in continuous integration (CI) environments and
+    /// should be used with caution, as it can modify the system Python installation.
+    #[clap(long, conflicts_with = "python")]
+    system: bool,
+}
+
+#[derive(Args)]
+struct PipStartupArgs {
+    /// The name of the startup hook.
+    name: PackageName,
+
+    /// The Python interpreter whose startup hook should be modified.
+    ///
+    /// By default, `uv` modifies the virtual environment in the current working directory or any
+    /// parent directory.
+    #[clap(long, short, conflicts_with = "system")]
+    python: Option<String>,
+
+    /// Modify the startup hook in the system Python.
+    ///
+    /// WARNING: `--system` is intended for use in continuous integration (CI) environments and
+    /// should be used with caution, as it can modify the system Python installation.
+    #[clap(long, conflicts_with = "python")]
+    system: bool,
+}
+
+#[derive(Args)]
+struct PipStartupListArgs {
+    /// The Python interpreter for which startup hooks should be listed.
+    ///
+    /// By default, `uv` lists startup hooks in the currently activated virtual environment, or a
+    /// virtual environment (`.venv`) located in the current working directory or any parent
+    /// directory, falling back to the system Python if no virtual environment is found.
+    #[clap(long, short, conflicts_with = "system")]
+    python: Option<String>,
+
+    /// List startup hooks for the system Python.
+    #[clap(long, conflicts_with = "python")]
+    system: bool,
+}
+
 #[derive(Args)]
 #[allow(clippy::struct_excessive_bools)]
 struct VenvArgs {
     /// The Python interpreter to use for the virtual environment.
     ///
     /// Supported formats:
-    /// - `3.10` looks for an installed Python 3.10 using `py --list-paths` on Windows, or
+    /// - `3.10` looks for an installed Python 3.10 in the registry on Windows (see PEP 514), or
     ///   `python3.10` on Linux and macOS.
     /// - `python3.10` or `python.exe` looks for a binary with the given name in `PATH`.
     /// - `/home/ferris/.local/bin/python3.10` uses the exact Python at the given path.
@@ -949,6 +2058,14 @@ struct VenvArgs {
     #[clap(long)]
     seed: bool,
 
+    /// Clone the packages installed in an existing virtual environment into the new one.
+    ///
+    /// Each package is re-installed (from the cache, where possible) at the same version or
+    /// direct URL as in the source environment. Editable installs are not cloned. Unless
+    /// `--python` is provided, the source environment's interpreter is used.
+    #[clap(long, conflicts_with = "seed")]
+    clone: Option<PathBuf>,
+
     /// The path to the virtual environment to create.
     #[clap(default_value = DEFAULT_VENV_NAME)]
     name: PathBuf,
@@ -1018,6 +2135,91 @@ struct VenvArgs {
     compat_args: compat::VenvCompatArgs,
 }
 
+#[derive(Args)]
+#[allow(clippy::struct_excessive_bools)]
+#[command(group = clap::ArgGroup::new("ephemeral").multiple(true))]
+struct RunArgs {
+    /// The command to run, followed by its arguments (e.g., `pytest -x`).
+    ///
+    /// If the command is a Python script (i.e., a `.py` file), it's run with the environment's
+    /// Python interpreter.
+    #[clap(required = true, trailing_var_arg = true, allow_hyphen_values = true)]
+    command: Vec<OsString>,
+
+    /// Run in an ephemeral environment with the packages listed in the given files installed,
+    /// rather than in the project's virtual environment.
+    ///
+    /// The following formats are supported: `requirements.txt` and `pyproject.toml`.
+    #[clap(long, short, group = "ephemeral")]
+    requirement: Vec<PathBuf>,
+
+    /// Run with the given package(s) installed, in an ephemeral environment that's overlaid on the
+    /// project's virtual environment (if any), e.g., `--with requests --with rich`.
+    ///
+    /// Packages installed with `--with` take precedence over those in the project's virtual
+    /// environment, which is left unmodified.
+    #[clap(long, group = "ephemeral")]
+    with: Vec<String>,
+
+    /// Run in an ephemeral environment, ignoring the project's virtual environment.
+    ///
+    /// The requirements declared in the script's inline metadata (i.e., a `# /// script` block),
+    /// or else in the project's `pyproject.toml` (if any), are installed into an environment
+    /// that's created in the cache directory, and removed once the command exits, such that the
+    /// command isn't affected by the state of the project's virtual environment.
+    #[clap(long, group = "ephemeral")]
+    isolated: bool,
+
+    /// The Python interpreter to use for the ephemeral environment.
+    ///
+    /// See `uv venv --python` for the supported formats. Defaults to the pinned Python
+    /// interpreter (see `uv python pin`), if any. When provided, the project's virtual environment
+    /// is ignored.
+    #[clap(long, short, requires = "ephemeral")]
+    python: Option<String>,
+
+    /// The URL of the Python package index (by default: <https://pypi.org/simple>).
+    ///
+    /// The index given by this flag is given lower priority than all other
+    /// indexes specified via the `--extra-index-url` flag.
+    #[clap(long, short, env = "UV_INDEX_URL", value_parser = parse_index_url)]
+    index_url: Option<Maybe<IndexUrl>>,
+
+    /// Extra URLs of package indexes to use, in addition to `--index-url`.
+    ///
+    /// All indexes given via this flag take priority over the index
+    /// in `--index-url` (which defaults to PyPI). And when multiple
+    /// `--extra-index-url` flags are given, earlier values take priority.
+    #[clap(long, env = "UV_EXTRA_INDEX_URL", value_delimiter = ' ', value_parser = parse_index_url)]
+    extra_index_url: Vec<Maybe<IndexUrl>>,
+
+    /// Ignore the registry index (e.g., PyPI), instead relying on direct URL dependencies and those
+    /// discovered via `--find-links`.
+    #[clap(long, conflicts_with = "index_url", conflicts_with = "extra_index_url")]
+    no_index: bool,
+
+    /// Locations to search for candidate distributions, beyond those found in the indexes.
+    ///
+    /// If a path, the target must be a directory that contains package as wheel files (`.whl`) or
+    /// source distributions (`.tar.gz` or `.zip`) at the top level, or an HTML file that contains
+    /// a flat list of links to package files (which may be relative to the HTML file).
+    ///
+    /// If a URL, the page must contain a flat list of links to package files.
+    #[clap(long, short)]
+    find_links: Vec<FlatIndexLocation>,
+
+    /// Run offline, i.e., without accessing the network.
+    #[arg(global = true, long)]
+    offline: bool,
+
+    /// Limit candidate packages to those that were uploaded prior to the given date.
+    ///
+    /// Accepts both RFC 3339 timestamps (e.g., `2006-12-02T02:07:43Z`) and UTC dates in the same
+    /// format (e.g., `2006-12-02`).
+    #[arg(long, value_parser = date_or_datetime)]
+    exclude_newer: Option<DateTime<Utc>>,
+}
+
 #[derive(Args)]
 #[allow(clippy::struct_excessive_bools)]
 struct AddArgs {
@@ -1034,7 +2236,21 @@ struct RemoveArgs {
 
 #[instrument] // Anchor span to check for overhead
 async fn run() -> Result<ExitStatus> {
-    let cli = match Cli::try_parse() {
+    // Handle `--help-json` ahead of parsing, since the described command may be missing its
+    // required arguments.
+    if let Some((command, path)) = help_json::requested() {
+        help_json::print(&command, &path)?;
+        return Ok(ExitStatus::Success);
+    }
+
+    // Load the persistent settings, which provide the defaults for any omitted arguments.
+    let settings = LoadedSettings::load()?;
+    let cli = match settings
+        .settings
+        .apply(Cli::command())
+        .try_get_matches()
+        .and_then(|matches| Cli::from_arg_matches(&matches))
+    {
         Ok(cli) => cli,
         Err(mut err) => {
             if let Some(ContextValue::String(subcommand)) = err.get(ContextKind::InvalidSubcommand)
@@ -1083,43 +2299,75 @@ async fn run() -> Result<ExitStatus> {
         }
     };
 
+    // Configure colors for all output streams, including logs, progress bars, and errors.
+    if cli.no_color {
+        anstream::ColorChoice::write_global(anstream::ColorChoice::Never);
+    } else {
+        anstream::ColorChoice::write_global(cli.color.into());
+    }
+
+    // Resolve the color choice for each stream (e.g., `auto` disables colors when piped, or when
+    // `NO_COLOR` is set), for the libraries that don't write through `anstream`.
+    let stdout_color = !matches!(
+        anstream::AutoStream::choice(&std::io::stdout()),
+        anstream::ColorChoice::Never
+    );
+    let stderr_color = !matches!(
+        anstream::AutoStream::choice(&std::io::stderr()),
+        anstream::ColorChoice::Never
+    );
+    console::set_colors_enabled(stdout_color);
+    console::set_colors_enabled_stderr(stderr_color);
+
     // Configure the `tracing` crate, which controls internal logging.
     #[cfg(feature = "tracing-durations-export")]
     let (duration_layer, _duration_guard) = logging::setup_duration();
     #[cfg(not(feature = "tracing-durations-export"))]
     let duration_layer = None::<tracing_subscriber::layer::Identity>;
     logging::setup_logging(
-        if cli.verbose {
-            logging::Level::Verbose
-        } else {
-            logging::Level::Default
+        match cli.verbose {
+            0 => logging::Level::Default,
+            1 => logging::Level::Verbose,
+            2 => logging::Level::ExtraVerbose,
+            _ => logging::Level::TraceAll,
         },
+        cli.log_format,
         duration_layer,
+        stderr_color,
     );
 
-    // Configure the `Printer`, which controls user-facing output in the CLI.
-    let printer = if cli.quiet {
+    // Configure the `Printer`, which controls user-facing output in the CLI. When reporting
+    // progress as JSON, stderr is reserved for progress events (and errors), so the human-readable
+    // output is suppressed.
+    let quiet = cli.quiet || cli.progress == ProgressMode::Json;
+    let printer = if quiet {
         printer::Printer::Quiet
-    } else if cli.verbose {
+    } else if cli.verbose > 0 {
         printer::Printer::Verbose
     } else {
         printer::Printer::Default
     };
 
+    progress::set_mode(cli.progress);
+
     // Configure the `warn!` macros, which control user-facing warnings in the CLI.
-    if !cli.quiet {
+    if !quiet {
         uv_warnings::enable();
     }
-
-    if cli.no_color {
-        anstream::ColorChoice::write_global(anstream::ColorChoice::Never);
-    } else {
-        anstream::ColorChoice::write_global(cli.color.into());
+    for code in cli.silence {
+        uv_warnings::silence(code);
+    }
+    for code in cli.warn_as_error {
+        uv_warnings::warn_as_error(code);
+    }
+    for warning in &settings.warnings {
+        warn_user!("{warning}");
     }
 
-    miette::set_hook(Box::new(|_| {
+    miette::set_hook(Box::new(move |_| {
         Box::new(
             miette::MietteHandlerOpts::new()
+                .color(stderr_color)
                 .break_words(false)
                 .word_separator(textwrap::WordSeparator::AsciiSpace)
                 .word_splitter(textwrap::WordSplitter::NoHyphenation)
@@ -1128,25 +2376,63 @@ async fn run() -> Result<ExitStatus> {
         )
     }))?;
 
+    // Restore the terminal state if the process is interrupted (e.g., mid-progress).
+    if let Err(err) = interrupt::install() {
+        debug!("Failed to install interrupt handler: {err}");
+    }
+
     let cache = Cache::try_from(cli.cache_args)?;
 
-    match cli.command {
+    // Wheels are installed (and local wheels unzipped) on the global `rayon` thread pool. Unless a
+    // limit was requested, leave the pool to size itself (e.g., respecting `RAYON_NUM_THREADS`).
+    if let Some(installs) = cli.concurrent_installs {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(installs.get())
+            .build_global()?;
+    }
+
+    // Configure the limits on concurrent downloads, builds, and installs.
+    let concurrency = Concurrency {
+        downloads: cli
+            .concurrent_downloads
+            .map_or(Concurrency::DEFAULT_DOWNLOADS, NonZeroUsize::get),
+        builds: cli
+            .concurrent_builds
+            .map_or_else(Concurrency::threads, NonZeroUsize::get),
+        installs: rayon::current_num_threads(),
+    };
+
+    let status = match cli.command {
         Commands::Pip(PipNamespace {
             command: PipCommand::Compile(args),
-        }) => {
-            args.compat_args.validate()?;
-
-            let cache = cache.with_refresh(Refresh::from_args(args.refresh, args.refresh_package));
-            let requirements = args
-                .src_file
-                .into_iter()
-                .map(RequirementsSource::from_path)
-                .collect::<Vec<_>>();
-            let constraints = args
-                .constraint
-                .into_iter()
-                .map(RequirementsSource::from_path)
-                .collect::<Vec<_>>();
+        }) => {
+            args.compat_args.validate()?;
+
+            let cache = cache.with_refresh(Refresh::from_args(args.refresh, args.refresh_package));
+            // Each layer is compiled from its own inputs, to its own output. With `--layered`, the
+            // outputs of the preceding layers constrain each subsequent layer.
+            let layers = if args.layered {
+                let mut layers = Vec::with_capacity(args.src_file.len());
+                let mut constraints = args.constraint;
+                for src_file in args.src_file {
+                    if src_file.extension().map_or(true, |ext| ext != "in") {
+                        anyhow::bail!(
+                            "`--layered` requires `.in` input files, but found: {}",
+                            src_file.simplified_display()
+                        );
+                    }
+                    let output_file = src_file.with_extension("txt");
+                    layers.push((
+                        vec![src_file],
+                        constraints.clone(),
+                        Some(output_file.clone()),
+                    ));
+                    constraints.push(output_file);
+                }
+                layers
+            } else {
+                vec![(args.src_file, args.constraint, args.output_file)]
+            };
             let overrides = args
                 .r#override
                 .into_iter()
@@ -1186,39 +2472,71 @@ async fn run() -> Result<ExitStatus> {
                 SetupPyStrategy::Pep517
             };
             let config_settings = args.config_setting.into_iter().collect::<ConfigSettings>();
-            commands::pip_compile(
-                &requirements,
-                &constraints,
-                &overrides,
-                extras,
-                args.output_file.as_deref(),
-                args.resolution,
-                prerelease,
-                dependency_mode,
-                upgrade,
-                args.generate_hashes,
-                args.no_emit_package,
-                !args.no_annotate,
-                !args.no_header,
-                args.emit_index_url,
-                args.emit_find_links,
-                index_urls,
-                setup_py,
-                config_settings,
-                if args.offline {
-                    Connectivity::Offline
-                } else {
-                    Connectivity::Online
-                },
-                &no_build,
-                args.python_version,
-                args.exclude_newer,
-                args.annotation_style,
-                cli.quiet,
-                cache,
-                printer,
-            )
-            .await
+            let build_constraints = args
+                .build_constraint
+                .into_iter()
+                .map(RequirementsSource::from_path)
+                .collect::<Vec<_>>();
+            let min_release_age = MinReleaseAge::from_args(args.min_release_age);
+            let mut status = ExitStatus::Success;
+            for (src_files, constraints, output_file) in layers {
+                let requirements = src_files
+                    .into_iter()
+                    .map(RequirementsSource::from_path)
+                    .collect::<Vec<_>>();
+                let constraints = constraints
+                    .into_iter()
+                    .map(RequirementsSource::from_path)
+                    .collect::<Vec<_>>();
+                status = commands::pip_compile(
+                    &requirements,
+                    &constraints,
+                    &overrides,
+                    extras.clone(),
+                    output_file.as_deref(),
+                    args.check,
+                    args.resolution,
+                    prerelease,
+                    dependency_mode,
+                    upgrade.clone(),
+                    args.generate_hashes,
+                    args.no_emit_package.clone(),
+                    !args.no_annotate,
+                    !args.no_header,
+                    args.custom_header.clone(),
+                    args.emit_index_url,
+                    args.emit_find_links,
+                    args.no_strip_extras,
+                    index_urls.clone(),
+                    setup_py,
+                    config_settings.clone(),
+                    args.isolation.no_build_isolation,
+                    args.isolation.build_requirement.clone(),
+                    &build_constraints,
+                    if args.offline {
+                        Connectivity::Offline
+                    } else {
+                        Connectivity::Online
+                    },
+                    &no_build,
+                    args.python_version.clone(),
+                    args.python_platform,
+                    args.exclude_newer,
+                    args.resolution_timeout.map(Duration::from_secs),
+                    args.max_resolution_steps,
+                    min_release_age.clone(),
+                    args.annotation_style,
+                    cli.quiet,
+                    concurrency,
+                    cache.clone(),
+                    printer,
+                )
+                .await?;
+                if !matches!(status, ExitStatus::Success) {
+                    break;
+                }
+            }
+            Ok(status)
         }
         Commands::Pip(PipNamespace {
             command: PipCommand::Sync(args),
@@ -1241,6 +2559,7 @@ async fn run() -> Result<ExitStatus> {
                 .map(RequirementsSource::from_path)
                 .collect::<Vec<_>>();
             let reinstall = Reinstall::from_args(args.reinstall, args.reinstall_package);
+            let keep = Keep::from_args(args.no_strip, args.keep);
             let no_binary = NoBinary::from_args(args.no_binary);
             let no_build = NoBuild::from_args(args.only_binary, args.no_build);
             let setup_py = if args.legacy_setup_py {
@@ -1249,11 +2568,29 @@ async fn run() -> Result<ExitStatus> {
                 SetupPyStrategy::Pep517
             };
             let config_settings = args.config_setting.into_iter().collect::<ConfigSettings>();
+            let build_constraints = args
+                .build_constraint
+                .into_iter()
+                .map(RequirementsSource::from_path)
+                .collect::<Vec<_>>();
+            let hash_database = args
+                .hash_database
+                .map(HashDatabase::from_path)
+                .transpose()?
+                .unwrap_or_default();
 
             commands::pip_sync(
                 &sources,
                 &reinstall,
+                keep,
                 args.link_mode,
+                &args.no_zip,
+                &install_wheel_rs::ScriptNaming {
+                    prefix: args.script_prefix,
+                    suffix: args.script_suffix,
+                },
+                args.verify_hashes,
+                &hash_database,
                 index_urls,
                 setup_py,
                 if args.offline {
@@ -1262,11 +2599,18 @@ async fn run() -> Result<ExitStatus> {
                     Connectivity::Online
                 },
                 &config_settings,
+                args.isolation.no_build_isolation,
+                &args.isolation.build_requirement,
+                &build_constraints,
                 &no_build,
                 &no_binary,
                 args.strict,
-                args.python,
+                pinned_python(args.python, args.system)?,
                 args.system,
+                args.auto_create,
+                args.shared,
+                args.allow_conda,
+                concurrency,
                 cache,
                 printer,
             )
@@ -1333,6 +2677,16 @@ async fn run() -> Result<ExitStatus> {
                 SetupPyStrategy::Pep517
             };
             let config_settings = args.config_setting.into_iter().collect::<ConfigSettings>();
+            let build_constraints = args
+                .build_constraint
+                .into_iter()
+                .map(RequirementsSource::from_path)
+                .collect::<Vec<_>>();
+            let hash_database = args
+                .hash_database
+                .map(HashDatabase::from_path)
+                .transpose()?
+                .unwrap_or_default();
 
             commands::pip_install(
                 &requirements,
@@ -1343,9 +2697,17 @@ async fn run() -> Result<ExitStatus> {
                 prerelease,
                 dependency_mode,
                 upgrade,
+                args.upgrade_strategy,
                 index_urls,
                 &reinstall,
                 args.link_mode,
+                &args.no_zip,
+                &install_wheel_rs::ScriptNaming {
+                    prefix: args.script_prefix,
+                    suffix: args.script_suffix,
+                },
+                args.verify_hashes,
+                &hash_database,
                 setup_py,
                 if args.offline {
                     Connectivity::Offline
@@ -1353,12 +2715,99 @@ async fn run() -> Result<ExitStatus> {
                     Connectivity::Online
                 },
                 &config_settings,
+                args.isolation.no_build_isolation,
+                &args.isolation.build_requirement,
+                &build_constraints,
                 &no_build,
                 &no_binary,
                 args.strict,
                 args.exclude_newer,
-                args.python,
+                args.resolution_timeout.map(Duration::from_secs),
+                args.max_resolution_steps,
+                MinReleaseAge::from_args(args.min_release_age),
+                pinned_python(args.python, args.system)?,
                 args.system,
+                args.auto_create,
+                args.shared,
+                args.allow_conda,
+                args.venvs,
+                args.target,
+                args.python_platform,
+                concurrency,
+                cache,
+                printer,
+            )
+            .await
+        }
+        Commands::Pip(PipNamespace {
+            command: PipCommand::Download(args),
+        }) => {
+            let cache = cache.with_refresh(Refresh::from_args(args.refresh, args.refresh_package));
+            let requirements = args
+                .package
+                .into_iter()
+                .map(RequirementsSource::from_package)
+                .chain(
+                    args.requirement
+                        .into_iter()
+                        .map(RequirementsSource::from_path),
+                )
+                .collect::<Vec<_>>();
+            let constraints = args
+                .constraint
+                .into_iter()
+                .map(RequirementsSource::from_path)
+                .collect::<Vec<_>>();
+            let overrides = args
+                .r#override
+                .into_iter()
+                .map(RequirementsSource::from_path)
+                .collect::<Vec<_>>();
+            let index_urls = IndexLocations::new(
+                args.index_url.and_then(Maybe::into_option),
+                args.extra_index_url
+                    .into_iter()
+                    .filter_map(Maybe::into_option)
+                    .collect(),
+                args.find_links,
+                args.no_index,
+            );
+            let no_build = NoBuild::from_args(args.only_binary, args.no_build);
+            let dependency_mode = if args.no_deps {
+                DependencyMode::Direct
+            } else {
+                DependencyMode::Transitive
+            };
+            let prerelease = if args.pre {
+                PreReleaseMode::Allow
+            } else {
+                args.prerelease
+            };
+            let hash_database = args
+                .hash_database
+                .map(HashDatabase::from_path)
+                .transpose()?
+                .unwrap_or_default();
+            commands::pip_download(
+                &requirements,
+                &constraints,
+                &overrides,
+                &args.dest,
+                &hash_database,
+                args.resolution,
+                prerelease,
+                dependency_mode,
+                index_urls,
+                if args.offline {
+                    Connectivity::Offline
+                } else {
+                    Connectivity::Online
+                },
+                &no_build,
+                args.python_version,
+                args.python_platform,
+                args.exclude_newer,
+                concurrency,
                 cache,
                 printer,
             )
@@ -1378,11 +2827,29 @@ async fn run() -> Result<ExitStatus> {
                         .map(RequirementsSource::from_path),
                 )
                 .collect::<Vec<_>>();
-            commands::pip_uninstall(&sources, args.python, args.system, cache, printer).await
+            commands::pip_uninstall(
+                &sources,
+                args.python,
+                args.system,
+                args.dry_run,
+                args.check_modified,
+                cache,
+                printer,
+            )
+            .await
+        }
+        Commands::Pip(PipNamespace {
+            command: PipCommand::Autoremove(args),
+        }) => {
+            commands::pip_autoremove(args.python, args.system, args.dry_run, cache, printer).await
         }
         Commands::Pip(PipNamespace {
             command: PipCommand::Freeze(args),
         }) => commands::pip_freeze(
+            args.exclude_editable,
+            &args.exclude,
+            args.not_required,
+            args.all,
             args.strict,
             args.python.as_deref(),
             args.system,
@@ -1395,12 +2862,142 @@ async fn run() -> Result<ExitStatus> {
             args.strict,
             args.editable,
             args.exclude_editable,
+            &args
+                .packages
+                .into_iter()
+                .chain(args.include)
+                .collect::<Vec<_>>(),
             &args.exclude,
+            args.installer.as_deref(),
+            args.location.as_deref(),
+            args.not_required,
+            args.sizes,
+            args.format,
+            args.python.as_deref(),
+            args.system,
+            &cache,
+            printer,
+        ),
+        Commands::Pip(PipNamespace {
+            command: PipCommand::Sbom(args),
+        }) => commands::pip_sbom(args.format, args.python.as_deref(), args.system, &cache),
+        Commands::Pip(PipNamespace {
+            command: PipCommand::Licenses(args),
+        }) => commands::pip_licenses(
+            args.format,
+            &args.fail_on,
+            args.python.as_deref(),
+            args.system,
+            &cache,
+            printer,
+        ),
+        Commands::Pip(PipNamespace {
+            command: PipCommand::Audit(args),
+        }) => {
+            let sources = args
+                .requirement
+                .into_iter()
+                .map(RequirementsSource::from_path)
+                .collect::<Vec<_>>();
+            commands::pip_audit(
+                &sources,
+                args.strict,
+                if args.offline {
+                    Connectivity::Offline
+                } else {
+                    Connectivity::Online
+                },
+                args.python.as_deref(),
+                args.system,
+                cache,
+                printer,
+            )
+            .await
+        }
+        Commands::Pip(PipNamespace {
+            command: PipCommand::Check(args),
+        }) => {
+            let index_urls = IndexLocations::new(
+                args.index_url.and_then(Maybe::into_option),
+                args.extra_index_url
+                    .into_iter()
+                    .filter_map(Maybe::into_option)
+                    .collect(),
+                Vec::new(),
+                args.no_index,
+            );
+            commands::pip_check(
+                args.python_version,
+                index_urls,
+                if args.offline {
+                    Connectivity::Offline
+                } else {
+                    Connectivity::Online
+                },
+                args.python.as_deref(),
+                args.system,
+                cache,
+                printer,
+            )
+            .await
+        }
+        Commands::Pip(PipNamespace {
+            command:
+                PipCommand::Startup(PipStartupNamespace {
+                    command: PipStartupCommand::Add(args),
+                }),
+        }) => commands::pip_startup_add(
+            &args.name,
+            &args.code,
+            args.python.as_deref(),
+            args.system,
+            &cache,
+            printer,
+        ),
+        Commands::Pip(PipNamespace {
+            command:
+                PipCommand::Startup(PipStartupNamespace {
+                    command: PipStartupCommand::Remove(args),
+                }),
+        }) => commands::pip_startup_remove(
+            &args.name,
+            args.python.as_deref(),
+            args.system,
+            &cache,
+            printer,
+        ),
+        Commands::Pip(PipNamespace {
+            command:
+                PipCommand::Startup(PipStartupNamespace {
+                    command: PipStartupCommand::Enable(args),
+                }),
+        }) => commands::pip_startup_toggle(
+            &args.name,
+            true,
+            args.python.as_deref(),
+            args.system,
+            &cache,
+            printer,
+        ),
+        Commands::Pip(PipNamespace {
+            command:
+                PipCommand::Startup(PipStartupNamespace {
+                    command: PipStartupCommand::Disable(args),
+                }),
+        }) => commands::pip_startup_toggle(
+            &args.name,
+            false,
             args.python.as_deref(),
             args.system,
             &cache,
             printer,
         ),
+        Commands::Pip(PipNamespace {
+            command:
+                PipCommand::Startup(PipStartupNamespace {
+                    command: PipStartupCommand::List(args),
+                }),
+        }) => commands::pip_startup_list(args.python.as_deref(), args.system, &cache, printer),
         Commands::Cache(CacheNamespace {
             command: CacheCommand::Clean(args),
         })
@@ -1411,6 +3008,27 @@ async fn run() -> Result<ExitStatus> {
             commands::cache_dir(&cache);
             Ok(ExitStatus::Success)
         }
+        Commands::Cache(CacheNamespace {
+            command: CacheCommand::Audit(args),
+        }) => {
+            let index_locations = IndexLocations::new(
+                args.index_url.and_then(Maybe::into_option),
+                args.extra_index_url
+                    .into_iter()
+                    .filter_map(Maybe::into_option)
+                    .collect(),
+                vec![],
+                false,
+            );
+            commands::cache_audit(
+                &args.package,
+                &index_locations,
+                args.output_format,
+                &cache,
+                printer,
+            )
+            .await
+        }
         Commands::Venv(args) => {
             args.compat_args.validate()?;
 
@@ -1434,9 +3052,16 @@ async fn run() -> Result<ExitStatus> {
                 }
             });
 
+            // Respect the pinned Python interpreter, unless cloning an existing environment.
+            let python = if args.clone.is_none() {
+                pinned_python(args.python, true)?
+            } else {
+                args.python
+            };
+
             commands::venv(
                 &args.name,
-                args.python.as_deref(),
+                python.as_deref(),
                 &index_locations,
                 uv_virtualenv::Prompt::from_args(prompt),
                 args.system_site_packages,
@@ -1446,12 +3071,75 @@ async fn run() -> Result<ExitStatus> {
                     Connectivity::Online
                 },
                 args.seed,
+                args.clone.as_deref(),
+                args.exclude_newer,
+                concurrency,
+                &cache,
+                printer,
+            )
+            .await
+        }
+        Commands::Run(args) => {
+            let requirements = args
+                .requirement
+                .into_iter()
+                .map(RequirementsSource::from_path)
+                .collect::<Vec<_>>();
+            let index_locations = IndexLocations::new(
+                args.index_url.and_then(Maybe::into_option),
+                args.extra_index_url
+                    .into_iter()
+                    .filter_map(Maybe::into_option)
+                    .collect(),
+                args.find_links,
+                args.no_index,
+            );
+
+            let with = args
+                .with
+                .into_iter()
+                .map(RequirementsSource::from_package)
+                .collect::<Vec<_>>();
+
+            commands::run(
+                &args.command,
+                requirements,
+                with,
+                args.isolated,
+                args.python.as_deref(),
+                index_locations,
+                if args.offline {
+                    Connectivity::Offline
+                } else {
+                    Connectivity::Online
+                },
                 args.exclude_newer,
+                concurrency,
                 &cache,
                 printer,
             )
             .await
         }
+        Commands::Python(PythonNamespace {
+            command: PythonCommand::List(args),
+        }) => commands::python_list(args.format, &cache, printer),
+        Commands::Python(PythonNamespace {
+            command: PythonCommand::Pin(args),
+        }) => commands::python_pin(args.request.as_deref(), &cache, printer),
+        Commands::Report(ReportNamespace {
+            command: ReportCommand::Diff(args),
+        }) => commands::report_diff(&args.old, &args.new, args.format, printer),
+        Commands::Debug(DebugNamespace {
+            command: DebugCommand::Info(args),
+        }) => commands::debug_info(
+            args.format,
+            args.wheel.as_deref(),
+            args.python.as_deref(),
+            args.system,
+            concurrency,
+            &settings.sources,
+            &cache,
+        ),
         Commands::Version { output_format } => {
             commands::version(output_format, &mut stdout())?;
             Ok(ExitStatus::Success)
@@ -1460,7 +3148,14 @@ async fn run() -> Result<ExitStatus> {
             shell.generate(&mut Cli::command(), &mut stdout());
             Ok(ExitStatus::Success)
         }
+    }?;
+
+    // Fail if any warnings were promoted to errors, even if the command itself succeeded.
+    if matches!(status, ExitStatus::Success) && uv_warnings::has_errors() {
+        return Ok(ExitStatus::Failure);
     }
+
+    Ok(status)
 }
 
 fn main() -> ExitCode {
@@ -1475,7 +3170,7 @@ fn main() -> ExitCode {
                 .thread_stack_size(stack_size)
                 .build()
                 .expect("Failed building the Runtime")
-                .block_on(run())
+                .block_on(interrupt::run(run()))
         };
         std::thread::Builder::new()
             .stack_size(stack_size)
@@ -1488,7 +3183,7 @@ fn main() -> ExitCode {
             .enable_all()
             .build()
             .expect("Failed building the Runtime")
-            .block_on(run())
+            .block_on(interrupt::run(run()))
     };
 
     match result {
@@ -1,6 +1,8 @@
 use anstream::eprint;
 use indicatif::ProgressDrawTarget;
 
+use crate::progress::{self, ProgressMode};
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum Printer {
     /// A printer that prints to standard streams (e.g., stdout).
@@ -14,6 +16,10 @@ pub(crate) enum Printer {
 impl Printer {
     /// Return the [`ProgressDrawTarget`] for this printer.
     pub(crate) fn target(self) -> ProgressDrawTarget {
+        // Progress bars are replaced by structured events (or hidden entirely) in other modes.
+        if progress::mode() != ProgressMode::Auto {
+            return ProgressDrawTarget::hidden();
+        }
         match self {
             Self::Default => ProgressDrawTarget::stderr(),
             Self::Quiet => ProgressDrawTarget::hidden(),
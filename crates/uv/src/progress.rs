@@ -0,0 +1,97 @@
+use std::io::Write;
+use std::sync::OnceLock;
+
+use indicatif::ProgressBar;
+use serde::Serialize;
+
+/// How progress should be reported.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub(crate) enum ProgressMode {
+    /// Display progress bars when writing to a terminal.
+    #[default]
+    Auto,
+    /// Never display progress.
+    Never,
+    /// Emit newline-delimited JSON progress events to stderr, in lieu of progress bars and other
+    /// human-readable output.
+    Json,
+}
+
+static MODE: OnceLock<ProgressMode> = OnceLock::new();
+
+/// Set the global progress mode. Can only be called once.
+pub(crate) fn set_mode(mode: ProgressMode) {
+    MODE.set(mode).ok();
+}
+
+/// Return the global progress mode.
+pub(crate) fn mode() -> ProgressMode {
+    MODE.get().copied().unwrap_or_default()
+}
+
+/// The phase of an operation to which a progress event belongs.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum Phase {
+    Resolve,
+    Download,
+    Install,
+}
+
+/// A structured progress event, as emitted by `--progress json`.
+#[derive(Debug, Serialize)]
+#[serde(tag = "event", rename_all = "kebab-case")]
+pub(crate) enum ProgressEvent<'a> {
+    /// A package was processed (e.g., resolved, fetched, or installed).
+    Progress {
+        phase: Phase,
+        package: &'a str,
+        /// The number of packages processed so far, in this phase.
+        completed: u64,
+        /// The total number of packages to process in this phase, if known.
+        total: Option<u64>,
+    },
+    /// All packages in the phase were processed.
+    Complete { phase: Phase },
+    /// Bytes of a distribution were downloaded.
+    DownloadProgress {
+        package: &'a str,
+        /// The number of bytes downloaded so far.
+        downloaded: u64,
+        /// The size of the distribution, in bytes, if known.
+        total: Option<u64>,
+    },
+    /// A source distribution or editable started building.
+    BuildStart { package: &'a str },
+    /// A source distribution or editable finished building.
+    BuildComplete { package: &'a str },
+    /// A Git repository started being fetched.
+    CheckoutStart { url: &'a str, rev: &'a str },
+    /// A Git repository finished being fetched.
+    CheckoutComplete { url: &'a str, rev: &'a str },
+}
+
+impl<'a> ProgressEvent<'a> {
+    /// Create a [`ProgressEvent::Progress`] event from the state of a progress bar.
+    pub(crate) fn progress(phase: Phase, package: &'a str, bar: &ProgressBar) -> Self {
+        Self::Progress {
+            phase,
+            package,
+            completed: bar.position(),
+            total: bar.length(),
+        }
+    }
+
+    /// Emit the event to stderr, if JSON progress reporting is enabled.
+    pub(crate) fn emit(&self) {
+        if mode() != ProgressMode::Json {
+            return;
+        }
+        let Ok(event) = serde_json::to_string(self) else {
+            return;
+        };
+        // Write the event in a single call, such that concurrent events aren't interleaved.
+        let mut stderr = std::io::stderr().lock();
+        writeln!(stderr, "{event}").ok();
+    }
+}
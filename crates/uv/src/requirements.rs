@@ -9,16 +9,27 @@ use indexmap::IndexMap;
 use rustc_hash::FxHashSet;
 
 use distribution_types::{FlatIndexLocation, IndexUrl};
-use pep508_rs::Requirement;
-use requirements_txt::{EditableRequirement, FindLink, RequirementsTxt};
-use tracing::{instrument, Level};
+use pep440_rs::{Operator, VersionSpecifier};
+use pep508_rs::{MarkerEnvironment, Requirement, VersionOrUrl};
+use pypi_types::Metadata21;
+use requirements_txt::{EditableRequirement, FindLink, RequirementsTxt, UnnamedRequirement};
+use tracing::{debug, instrument, Level};
+use url::Url;
+use uv_distribution::DistributionDatabase;
 use uv_fs::Simplified;
 use uv_normalize::{ExtraName, PackageName};
+use uv_resolver::{Origin, Provenance, ProvenanceKind};
+use uv_traits::{BuildContext, PackageNameSpecifier};
 
 use crate::confirm;
 
 use uv_warnings::warn_user;
 
+/// The error raised by commands that can't read requirements from a source tree, since doing so
+/// requires building it.
+pub(crate) const SOURCE_TREES_UNSUPPORTED: &str =
+    "Reading requirements from `setup.py` and `setup.cfg` files is only supported by `uv pip compile`";
+
 #[derive(Debug)]
 pub(crate) enum RequirementsSource {
     /// A package was provided on the command line (e.g., `pip install flask`).
@@ -29,6 +40,10 @@ pub(crate) enum RequirementsSource {
     RequirementsTxt(PathBuf),
     /// Dependencies were provided via a `pyproject.toml` file (e.g., `pip-compile pyproject.toml`).
     PyprojectToml(PathBuf),
+    /// Dependencies were provided via a `setup.py` file (e.g., `pip-compile setup.py`).
+    SetupPy(PathBuf),
+    /// Dependencies were provided via a `setup.cfg` file (e.g., `pip-compile setup.cfg`).
+    SetupCfg(PathBuf),
 }
 
 impl RequirementsSource {
@@ -36,6 +51,10 @@ impl RequirementsSource {
     pub(crate) fn from_path(path: PathBuf) -> Self {
         if path.ends_with("pyproject.toml") {
             Self::PyprojectToml(path)
+        } else if path.ends_with("setup.py") {
+            Self::SetupPy(path)
+        } else if path.ends_with("setup.cfg") {
+            Self::SetupCfg(path)
         } else {
             Self::RequirementsTxt(path)
         }
@@ -43,8 +62,8 @@ impl RequirementsSource {
 
     /// Parse a [`RequirementsSource`] from a user-provided string, assumed to be a package.
     ///
-    /// If the user provided a value that appears to be a `requirements.txt` file or a local
-    /// directory, prompt them to correct it (if the terminal is interactive).
+    /// If the user provided a value that appears to be a `requirements.txt` file, prompt them to
+    /// correct it (if the terminal is interactive).
     pub(crate) fn from_package(name: String) -> Self {
         // If the user provided a `requirements.txt` file without `-r` (as in
         // `uv pip install requirements.txt`), prompt them to correct it.
@@ -62,20 +81,6 @@ impl RequirementsSource {
             }
         }
 
-        // If the user provided a path to a local directory without `-e` (as in
-        // `uv pip install ../flask`), prompt them to correct it.
-        if (name.contains('/') || name.contains('\\')) && Path::new(&name).is_dir() {
-            let term = Term::stderr();
-            if term.is_term() {
-                let prompt =
-                    format!("`{name}` looks like a local directory but was passed as a package name. Did you mean `-e {name}`?");
-                let confirmation = confirm::confirm(&prompt, &term, true).unwrap();
-                if confirmation {
-                    return Self::RequirementsTxt(name.into());
-                }
-            }
-        }
-
         Self::Package(name)
     }
 }
@@ -84,7 +89,10 @@ impl std::fmt::Display for RequirementsSource {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Editable(path) => write!(f, "-e {path}"),
-            Self::RequirementsTxt(path) | Self::PyprojectToml(path) => {
+            Self::RequirementsTxt(path)
+            | Self::PyprojectToml(path)
+            | Self::SetupPy(path)
+            | Self::SetupCfg(path) => {
                 write!(f, "{}", path.display())
             }
             Self::Package(package) => write!(f, "{package}"),
@@ -117,12 +125,21 @@ pub(crate) struct RequirementsSpecification {
     pub(crate) project: Option<PackageName>,
     /// The requirements for the project.
     pub(crate) requirements: Vec<Requirement>,
+    /// The requirements, constraints, and overrides, along with the input from which each was
+    /// read.
+    pub(crate) provenance: Vec<Provenance>,
     /// The constraints for the project.
     pub(crate) constraints: Vec<Requirement>,
     /// The overrides for the project.
     pub(crate) overrides: Vec<Requirement>,
     /// Package to install as editable installs
     pub(crate) editables: Vec<EditableRequirement>,
+    /// The source trees (e.g., directories containing a `setup.py`) whose requirements can only
+    /// be determined by building their metadata.
+    pub(crate) source_trees: Vec<PathBuf>,
+    /// The local paths (e.g., `./path/to/project`) whose package names can only be determined by
+    /// building their metadata.
+    pub(crate) unnamed_requirements: Vec<UnnamedRequirement>,
     /// The extras used to collect requirements.
     pub(crate) extras: FxHashSet<ExtraName>,
     /// The index URL to use for fetching packages.
@@ -133,6 +150,12 @@ pub(crate) struct RequirementsSpecification {
     pub(crate) no_index: bool,
     /// The `--find-links` locations to use for fetching packages.
     pub(crate) find_links: Vec<FlatIndexLocation>,
+    /// The packages for which wheels should not be used, from `--no-binary`.
+    pub(crate) no_binary: Vec<PackageNameSpecifier>,
+    /// The packages for which source distributions should not be used, from `--only-binary`.
+    pub(crate) only_binary: Vec<PackageNameSpecifier>,
+    /// The hashes provided for direct URL requirements (e.g., `flask @ https://... --hash=...`).
+    pub(crate) url_hashes: Vec<(Url, Vec<String>)>,
 }
 
 impl RequirementsSpecification {
@@ -144,19 +167,45 @@ impl RequirementsSpecification {
     ) -> Result<Self> {
         Ok(match source {
             RequirementsSource::Package(name) => {
-                let requirement = Requirement::parse(name, std::env::current_dir()?)
-                    .with_context(|| format!("Failed to parse `{name}`"))?;
+                // Allow local paths without a package name (e.g., `uv pip install ./project`). If
+                // the name can only be determined by building the project, defer to the caller.
+                let requirement = if let Some(unnamed) =
+                    UnnamedRequirement::parse(name, std::env::current_dir()?)
+                {
+                    match requirements_txt::infer_package_name(&unnamed.path) {
+                        Some(name) => unnamed.into_requirement(name),
+                        None => {
+                            return Ok(Self {
+                                unnamed_requirements: vec![unnamed],
+                                ..Self::default()
+                            })
+                        }
+                    }
+                } else {
+                    Requirement::parse(name, std::env::current_dir()?)
+                        .with_context(|| format!("Failed to parse `{name}`"))?
+                };
                 Self {
                     project: None,
+                    provenance: vec![Provenance::new(
+                        requirement.clone(),
+                        ProvenanceKind::Requirement,
+                        Origin::CommandLine,
+                    )],
                     requirements: vec![requirement],
                     constraints: vec![],
                     overrides: vec![],
                     editables: vec![],
+                    source_trees: vec![],
+                    unnamed_requirements: vec![],
                     extras: FxHashSet::default(),
                     index_url: None,
                     extra_index_urls: vec![],
                     no_index: false,
                     find_links: vec![],
+                    no_binary: vec![],
+                    only_binary: vec![],
+                    url_hashes: vec![],
                 }
             }
             RequirementsSource::Editable(name) => {
@@ -165,27 +214,60 @@ impl RequirementsSpecification {
                 Self {
                     project: None,
                     requirements: vec![],
+                    provenance: vec![],
                     constraints: vec![],
                     overrides: vec![],
                     editables: vec![requirement],
+                    source_trees: vec![],
+                    unnamed_requirements: vec![],
                     extras: FxHashSet::default(),
                     index_url: None,
                     extra_index_urls: vec![],
                     no_index: false,
                     find_links: vec![],
+                    no_binary: vec![],
+                    only_binary: vec![],
+                    url_hashes: vec![],
                 }
             }
             RequirementsSource::RequirementsTxt(path) => {
                 let requirements_txt = RequirementsTxt::parse(path, std::env::current_dir()?)?;
+                let url_hashes = requirements_txt
+                    .requirements
+                    .iter()
+                    .filter(|entry| !entry.hashes.is_empty())
+                    .filter_map(|entry| match &entry.requirement.version_or_url {
+                        Some(VersionOrUrl::Url(url)) => {
+                            Some((url.raw().clone(), entry.hashes.clone()))
+                        }
+                        _ => None,
+                    })
+                    .collect();
                 Self {
                     project: None,
                     requirements: requirements_txt
+                        .requirements
+                        .iter()
+                        .map(|entry| entry.requirement.clone())
+                        .collect(),
+                    provenance: requirements_txt
                         .requirements
                         .into_iter()
-                        .map(|entry| entry.requirement)
+                        .map(|entry| {
+                            Provenance::new(
+                                entry.requirement,
+                                ProvenanceKind::Requirement,
+                                Origin::File {
+                                    path: entry.origin.path,
+                                    line: Some(entry.origin.line),
+                                },
+                            )
+                        })
                         .collect(),
                     constraints: requirements_txt.constraints,
                     editables: requirements_txt.editables,
+                    source_trees: vec![],
+                    unnamed_requirements: requirements_txt.unnamed_requirements,
                     overrides: vec![],
                     extras: FxHashSet::default(),
                     index_url: requirements_txt.index_url.map(IndexUrl::from),
@@ -203,6 +285,39 @@ impl RequirementsSpecification {
                             FindLink::Path(path) => FlatIndexLocation::Path(path),
                         })
                         .collect(),
+                    no_binary: parse_specifiers(&requirements_txt.no_binary, path)?,
+                    only_binary: parse_specifiers(&requirements_txt.only_binary, path)?,
+                    url_hashes,
+                }
+            }
+            RequirementsSource::SetupPy(path) | RequirementsSource::SetupCfg(path) => {
+                let source_tree = std::env::current_dir()?
+                    .join(path)
+                    .parent()
+                    .map(Path::to_path_buf)
+                    .with_context(|| {
+                        format!(
+                            "Failed to determine the source tree of `{}`",
+                            path.display()
+                        )
+                    })?;
+                Self {
+                    project: None,
+                    requirements: vec![],
+                    provenance: vec![],
+                    constraints: vec![],
+                    overrides: vec![],
+                    editables: vec![],
+                    source_trees: vec![source_tree],
+                    unnamed_requirements: vec![],
+                    extras: FxHashSet::default(),
+                    index_url: None,
+                    extra_index_urls: vec![],
+                    no_index: false,
+                    find_links: vec![],
+                    no_binary: vec![],
+                    only_binary: vec![],
+                    url_hashes: vec![],
                 }
             }
             RequirementsSource::PyprojectToml(path) => {
@@ -220,6 +335,24 @@ impl RequirementsSpecification {
                             format!("Invalid `project.name` in {}", path.simplified_display())
                         })?;
 
+                    // Dynamic dependencies can only be determined by building the project, so
+                    // they're omitted from the resolution.
+                    let dynamic = project.dynamic.as_deref().unwrap_or_default();
+                    if dynamic.iter().any(|field| field == "dependencies") {
+                        warn_user!(
+                            "`{}` declares `project.dependencies` as dynamic; only statically declared dependencies are included",
+                            path.simplified_display()
+                        );
+                    }
+                    if !matches!(extras, ExtrasSpecification::None)
+                        && dynamic.iter().any(|field| field == "optional-dependencies")
+                    {
+                        warn_user!(
+                            "`{}` declares `project.optional-dependencies` as dynamic; only statically declared extras are included",
+                            path.simplified_display()
+                        );
+                    }
+
                     // Include the default dependencies.
                     requirements.extend(project.dependencies.unwrap_or_default());
 
@@ -258,15 +391,33 @@ impl RequirementsSpecification {
 
                 Self {
                     project: project_name,
+                    provenance: requirements
+                        .iter()
+                        .map(|requirement| {
+                            Provenance::new(
+                                requirement.clone(),
+                                ProvenanceKind::Requirement,
+                                Origin::File {
+                                    path: path.clone(),
+                                    line: None,
+                                },
+                            )
+                        })
+                        .collect(),
                     requirements,
                     constraints: vec![],
                     overrides: vec![],
                     editables: vec![],
+                    source_trees: vec![],
+                    unnamed_requirements: vec![],
                     extras: used_extras,
                     index_url: None,
                     extra_index_urls: vec![],
                     no_index: false,
                     find_links: vec![],
+                    no_binary: vec![],
+                    only_binary: vec![],
+                    url_hashes: vec![],
                 }
             }
         })
@@ -287,10 +438,15 @@ impl RequirementsSpecification {
         for source in requirements {
             let source = Self::from_source(source, extras)?;
             spec.requirements.extend(source.requirements);
+            spec.provenance.extend(source.provenance);
             spec.constraints.extend(source.constraints);
             spec.overrides.extend(source.overrides);
             spec.extras.extend(source.extras);
             spec.editables.extend(source.editables);
+            spec.source_trees.extend(source.source_trees);
+            spec.unnamed_requirements
+                .extend(source.unnamed_requirements);
+            spec.url_hashes.extend(source.url_hashes);
 
             // Use the first project name discovered.
             if spec.project.is_none() {
@@ -308,14 +464,29 @@ impl RequirementsSpecification {
             spec.no_index |= source.no_index;
             spec.extra_index_urls.extend(source.extra_index_urls);
             spec.find_links.extend(source.find_links);
+            spec.no_binary.extend(source.no_binary);
+            spec.only_binary.extend(source.only_binary);
         }
 
         // Read all constraints, treating _everything_ as a constraint.
         for source in constraints {
+            if matches!(
+                source,
+                RequirementsSource::SetupPy(_) | RequirementsSource::SetupCfg(_)
+            ) {
+                return Err(anyhow::anyhow!(
+                    "Constraints can't be read from `{source}`; only requirements can be read from `setup.py` and `setup.cfg` files"
+                ));
+            }
             let source = Self::from_source(source, extras)?;
             spec.constraints.extend(source.requirements);
             spec.constraints.extend(source.constraints);
             spec.constraints.extend(source.overrides);
+            spec.provenance
+                .extend(source.provenance.into_iter().map(|provenance| Provenance {
+                    kind: ProvenanceKind::Constraint,
+                    ..provenance
+                }));
 
             if let Some(url) = source.index_url {
                 if let Some(existing) = spec.index_url {
@@ -328,14 +499,29 @@ impl RequirementsSpecification {
             spec.no_index |= source.no_index;
             spec.extra_index_urls.extend(source.extra_index_urls);
             spec.find_links.extend(source.find_links);
+            spec.no_binary.extend(source.no_binary);
+            spec.only_binary.extend(source.only_binary);
         }
 
         // Read all overrides, treating both requirements _and_ constraints as overrides.
         for source in overrides {
+            if matches!(
+                source,
+                RequirementsSource::SetupPy(_) | RequirementsSource::SetupCfg(_)
+            ) {
+                return Err(anyhow::anyhow!(
+                    "Overrides can't be read from `{source}`; only requirements can be read from `setup.py` and `setup.cfg` files"
+                ));
+            }
             let source = Self::from_source(source, extras)?;
             spec.overrides.extend(source.requirements);
             spec.overrides.extend(source.constraints);
             spec.overrides.extend(source.overrides);
+            spec.provenance
+                .extend(source.provenance.into_iter().map(|provenance| Provenance {
+                    kind: ProvenanceKind::Override,
+                    ..provenance
+                }));
 
             if let Some(url) = source.index_url {
                 if let Some(existing) = spec.index_url {
@@ -348,6 +534,8 @@ impl RequirementsSpecification {
             spec.no_index |= source.no_index;
             spec.extra_index_urls.extend(source.extra_index_urls);
             spec.find_links.extend(source.find_links);
+            spec.no_binary.extend(source.no_binary);
+            spec.only_binary.extend(source.only_binary);
         }
 
         Ok(spec)
@@ -357,6 +545,111 @@ impl RequirementsSpecification {
     pub(crate) fn from_simple_sources(requirements: &[RequirementsSource]) -> Result<Self> {
         Self::from_sources(requirements, &[], &[], &ExtrasSpecification::None)
     }
+
+    /// Read the build constraints from a set of sources, treating _everything_ as a constraint.
+    pub(crate) fn from_build_constraints(
+        build_constraints: &[RequirementsSource],
+    ) -> Result<Vec<Requirement>> {
+        Ok(
+            Self::from_sources(&[], build_constraints, &[], &ExtrasSpecification::None)?
+                .constraints,
+        )
+    }
+}
+
+/// Parse the `--no-binary` or `--only-binary` package specifiers from a `requirements.txt` file.
+fn parse_specifiers(specifiers: &[String], path: &Path) -> Result<Vec<PackageNameSpecifier>> {
+    specifiers
+        .iter()
+        .map(|specifier| {
+            PackageNameSpecifier::from_str(specifier).with_context(|| {
+                format!(
+                    "Invalid package name `{specifier}` in `{}`",
+                    path.simplified_display()
+                )
+            })
+        })
+        .collect()
+}
+
+/// Determine the requirements of a source tree from its built metadata, including those of any
+/// requested extras.
+///
+/// The markers on each requirement are evaluated against the target environment, since the
+/// `extra` markers in the metadata would otherwise exclude the requirements of the requested
+/// extras. Returns the requirements, along with the extras that were activated.
+pub(crate) fn requirements_from_metadata(
+    metadata: Metadata21,
+    extras: &ExtrasSpecification,
+    markers: &MarkerEnvironment,
+) -> (Vec<Requirement>, Vec<ExtraName>) {
+    let Metadata21 {
+        name,
+        requires_dist,
+        provides_extras,
+        ..
+    } = metadata;
+
+    // Determine the active extras, including any that are activated through references to the
+    // project itself (e.g., `project[test] ; extra == 'dev'`).
+    let mut active = provides_extras
+        .into_iter()
+        .filter(|extra| extras.contains(extra))
+        .collect::<Vec<_>>();
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for requirement in &requires_dist {
+            if requirement.name == name && requirement.evaluate_markers(markers, &active) {
+                for extra in &requirement.extras {
+                    if !active.contains(extra) {
+                        active.push(extra.clone());
+                        changed = true;
+                    }
+                }
+            }
+        }
+    }
+
+    let requirements = requires_dist
+        .into_iter()
+        .filter(|requirement| requirement.name != name)
+        .filter(|requirement| requirement.evaluate_markers(markers, &active))
+        .map(|requirement| Requirement {
+            marker: None,
+            ..requirement
+        })
+        .collect();
+    (requirements, active)
+}
+
+/// Determine the package names of any local paths that omit them (e.g., `./path/to/project`) by
+/// building their metadata via the PEP 517 frontend, returning the corresponding direct
+/// references.
+pub(crate) async fn name_unnamed_requirements(
+    unnamed_requirements: Vec<UnnamedRequirement>,
+    database: &DistributionDatabase<'_, impl BuildContext + Send + Sync>,
+    wheel_dir: &Path,
+) -> Result<Vec<Requirement>> {
+    let mut requirements = Vec::with_capacity(unnamed_requirements.len());
+    for unnamed in unnamed_requirements {
+        let metadata = database
+            .build_source_tree_metadata(&unnamed.path, wheel_dir)
+            .await
+            .with_context(|| {
+                format!(
+                    "Failed to build metadata for: {}",
+                    unnamed.path.simplified_display()
+                )
+            })?;
+        debug!(
+            "Inferred package name `{}` for: {}",
+            metadata.name,
+            unnamed.path.simplified_display()
+        );
+        requirements.push(unnamed.into_requirement(metadata.name));
+    }
+    Ok(requirements)
 }
 
 /// Given an extra in a project that may contain references to the project
@@ -426,3 +719,59 @@ fn flatten_extra(
         &mut FxHashSet::default(),
     )
 }
+
+/// Return an error if any two requirements or constraints are incompatible, e.g., `flask==2.0.0`
+/// in one input file and `flask==3.0.0` (or a `flask<3` constraint) in another.
+///
+/// Only unconditional `==` pins are considered, since the resolver reports any other conflicts.
+/// Packages with an override are skipped, since the override replaces any other requirements.
+pub(crate) fn check_conflicting_pins(provenance: &[Provenance]) -> Result<()> {
+    let overridden = provenance
+        .iter()
+        .filter(|provenance| provenance.kind == ProvenanceKind::Override)
+        .map(|provenance| &provenance.requirement.name)
+        .collect::<FxHashSet<_>>();
+    let requirements = provenance
+        .iter()
+        .filter(|provenance| {
+            matches!(
+                provenance.kind,
+                ProvenanceKind::Requirement | ProvenanceKind::Constraint
+            ) && provenance.requirement.marker.is_none()
+                && !overridden.contains(&provenance.requirement.name)
+        })
+        .collect::<Vec<_>>();
+    for (index, pinned) in requirements.iter().enumerate() {
+        let Some(VersionOrUrl::VersionSpecifier(specifiers)) = &pinned.requirement.version_or_url
+        else {
+            continue;
+        };
+        let Some(version) = specifiers
+            .iter()
+            .find(|specifier| {
+                matches!(specifier.operator(), Operator::Equal | Operator::ExactEqual)
+            })
+            .map(VersionSpecifier::version)
+        else {
+            continue;
+        };
+
+        for (other_index, other) in requirements.iter().enumerate() {
+            if other_index == index || other.requirement.name != pinned.requirement.name {
+                continue;
+            }
+            let Some(VersionOrUrl::VersionSpecifier(other_specifiers)) =
+                &other.requirement.version_or_url
+            else {
+                continue;
+            };
+            if !other_specifiers.contains(version) {
+                return Err(anyhow::anyhow!(
+                    "Conflicting requirements for `{}`:\n  {pinned}\n  {other}",
+                    pinned.requirement.name
+                ));
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,378 @@
+//! Persistent settings, read from `uv.toml` files and the `[tool.uv]` table in `pyproject.toml`.
+//!
+//! Settings are layered, in order of precedence:
+//! 1. Command-line arguments.
+//! 2. Environment variables.
+//! 3. The project-level configuration (i.e., the nearest `uv.toml` or `pyproject.toml` with a
+//!    `[tool.uv]` table, searching from the current directory upwards).
+//! 4. The user-level configuration (e.g., `~/.config/uv/uv.toml`).
+//!
+//! Settings are applied by overriding the defaults of the corresponding command-line arguments,
+//! such that any explicit argument or environment variable takes precedence.
+
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::{bail, Context, Result};
+use clap::{Command, ValueEnum};
+use serde::Deserialize;
+use tracing::debug;
+
+use distribution_types::FlatIndexLocation;
+use install_wheel_rs::linker::LinkMode;
+use uv_fs::Simplified;
+use uv_resolver::{PreReleaseMode, ResolutionMode};
+
+/// The name of the uv-specific configuration file.
+const CONFIG_FILENAME: &str = "uv.toml";
+
+/// The settings that can be persisted to a configuration file.
+///
+/// Each setting is named after the command-line argument it provides a default for.
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct Settings {
+    index_url: Option<String>,
+    extra_index_url: Option<Vec<String>>,
+    find_links: Option<Vec<String>>,
+    no_index: Option<bool>,
+    cache_dir: Option<PathBuf>,
+    no_cache: Option<bool>,
+    resolution: Option<String>,
+    prerelease: Option<String>,
+    link_mode: Option<String>,
+    exclude_newer: Option<String>,
+}
+
+/// The [`Settings`] that apply to the current invocation, along with the files they were read from.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct LoadedSettings {
+    pub(crate) settings: Settings,
+    /// The configuration files that were read, in order of precedence.
+    pub(crate) sources: Vec<PathBuf>,
+    /// The reasons any discovered configuration files were ignored, to be displayed once warnings
+    /// are enabled.
+    pub(crate) warnings: Vec<String>,
+}
+
+impl LoadedSettings {
+    /// Load the settings for the current invocation, respecting `--config-file` and `--no-config`.
+    ///
+    /// Invalid discovered configuration files are ignored with a warning, such that, e.g., a
+    /// malformed `pyproject.toml` in a parent directory doesn't break every command. An invalid
+    /// `--config-file` is an error.
+    pub(crate) fn load() -> Result<Self> {
+        let (config_file, no_config) = config_args(std::env::args_os().skip(1));
+        let config_file = config_file.or_else(|| {
+            std::env::var_os("UV_CONFIG_FILE")
+                .filter(|value| !value.is_empty())
+                .map(PathBuf::from)
+        });
+        let no_config = no_config
+            || std::env::var("UV_NO_CONFIG").is_ok_and(|value| {
+                !matches!(
+                    value.to_ascii_lowercase().as_str(),
+                    "" | "0" | "n" | "no" | "f" | "false" | "off"
+                )
+            });
+
+        if no_config {
+            debug!("Ignoring configuration files due to `--no-config`");
+            Ok(Self::default())
+        } else if let Some(config_file) = config_file {
+            Self::from_file(&config_file)
+        } else {
+            Ok(Self::discover(&std::env::current_dir()?))
+        }
+    }
+
+    /// Load the settings from the given configuration file, ignoring any other configuration.
+    pub(crate) fn from_file(path: &Path) -> Result<Self> {
+        let settings = read(path)?.with_context(|| {
+            format!(
+                "Configuration file does not contain any uv settings: {}",
+                path.simplified_display()
+            )
+        })?;
+        Ok(Self {
+            settings,
+            sources: vec![path.to_path_buf()],
+            warnings: Vec::new(),
+        })
+    }
+
+    /// Discover and layer the project-level and user-level settings.
+    ///
+    /// The search for project-level settings stops at the nearest configuration file, even if
+    /// that file is invalid.
+    pub(crate) fn discover(cwd: &Path) -> Self {
+        let mut loaded = Self::default();
+
+        for dir in cwd.ancestors() {
+            match find_in(dir) {
+                Ok(Some((path, settings))) => {
+                    loaded.push(settings, path);
+                    break;
+                }
+                Ok(None) => {}
+                Err(err) => {
+                    loaded
+                        .warnings
+                        .push(format!("Ignoring invalid configuration: {err:#}"));
+                    break;
+                }
+            }
+        }
+
+        if let Some(path) = user_config() {
+            match read(&path) {
+                Ok(Some(settings)) => loaded.push(settings, path),
+                Ok(None) => {}
+                Err(err) => loaded
+                    .warnings
+                    .push(format!("Ignoring invalid configuration: {err:#}")),
+            }
+        }
+
+        loaded
+    }
+
+    /// Add a layer of settings, with a lower precedence than any existing layer.
+    fn push(&mut self, settings: Settings, path: PathBuf) {
+        debug!("Loaded settings from: {}", path.simplified_display());
+        self.settings = std::mem::take(&mut self.settings).combine(settings);
+        self.sources.push(path);
+    }
+}
+
+impl Settings {
+    /// Combine two layers of settings, preferring the values in `self`.
+    fn combine(self, other: Self) -> Self {
+        Self {
+            index_url: self.index_url.or(other.index_url),
+            extra_index_url: self.extra_index_url.or(other.extra_index_url),
+            find_links: self.find_links.or(other.find_links),
+            no_index: self.no_index.or(other.no_index),
+            cache_dir: self.cache_dir.or(other.cache_dir),
+            no_cache: self.no_cache.or(other.no_cache),
+            resolution: self.resolution.or(other.resolution),
+            prerelease: self.prerelease.or(other.prerelease),
+            link_mode: self.link_mode.or(other.link_mode),
+            exclude_newer: self.exclude_newer.or(other.exclude_newer),
+        }
+    }
+
+    /// Validate the settings, and resolve any relative paths against the directory containing
+    /// the configuration file.
+    fn validate(mut self, path: &Path) -> Result<Self> {
+        let root = path.parent().unwrap_or(Path::new(""));
+
+        for url in self
+            .index_url
+            .iter()
+            .chain(self.extra_index_url.iter().flatten())
+        {
+            crate::parse_index_url(url).map_err(|err| invalid("index-url", url, &err))?;
+        }
+        if let Some(find_links) = self.find_links.as_mut() {
+            for entry in find_links {
+                match FlatIndexLocation::from_str(entry)
+                    .map_err(|err| invalid("find-links", entry, &err.to_string()))?
+                {
+                    FlatIndexLocation::Path(path) if path.is_relative() => {
+                        *entry = root.join(path).to_string_lossy().to_string();
+                    }
+                    FlatIndexLocation::Path(_) | FlatIndexLocation::Url(_) => {}
+                }
+            }
+        }
+        if let Some(cache_dir) = self.cache_dir.as_mut() {
+            if cache_dir.is_relative() {
+                *cache_dir = root.join(&*cache_dir);
+            }
+        }
+        if let Some(resolution) = &self.resolution {
+            <ResolutionMode as ValueEnum>::from_str(resolution, false)
+                .map_err(|err| invalid("resolution", resolution, &err))?;
+        }
+        if let Some(prerelease) = &self.prerelease {
+            <PreReleaseMode as ValueEnum>::from_str(prerelease, false)
+                .map_err(|err| invalid("prerelease", prerelease, &err))?;
+        }
+        if let Some(link_mode) = &self.link_mode {
+            <LinkMode as ValueEnum>::from_str(link_mode, false)
+                .map_err(|err| invalid("link-mode", link_mode, &err))?;
+        }
+        if let Some(exclude_newer) = &self.exclude_newer {
+            crate::date_or_datetime(exclude_newer)
+                .map_err(|err| invalid("exclude-newer", exclude_newer, &err))?;
+        }
+
+        Ok(self)
+    }
+
+    /// Return the default values for each command-line argument, keyed by argument ID.
+    fn defaults(&self) -> Vec<(&'static str, Vec<String>)> {
+        let mut defaults = Vec::new();
+        if let Some(index_url) = &self.index_url {
+            defaults.push(("index_url", vec![index_url.clone()]));
+        }
+        if let Some(extra_index_url) = &self.extra_index_url {
+            defaults.push(("extra_index_url", extra_index_url.clone()));
+        }
+        if let Some(find_links) = &self.find_links {
+            defaults.push(("find_links", find_links.clone()));
+        }
+        if let Some(no_index) = self.no_index {
+            defaults.push(("no_index", vec![no_index.to_string()]));
+        }
+        if let Some(cache_dir) = &self.cache_dir {
+            defaults.push(("cache_dir", vec![cache_dir.to_string_lossy().to_string()]));
+        }
+        if let Some(no_cache) = self.no_cache {
+            defaults.push(("no_cache", vec![no_cache.to_string()]));
+        }
+        if let Some(resolution) = &self.resolution {
+            defaults.push(("resolution", vec![resolution.clone()]));
+        }
+        if let Some(prerelease) = &self.prerelease {
+            defaults.push(("prerelease", vec![prerelease.clone()]));
+        }
+        if let Some(link_mode) = &self.link_mode {
+            defaults.push(("link_mode", vec![link_mode.clone()]));
+        }
+        if let Some(exclude_newer) = &self.exclude_newer {
+            defaults.push(("exclude_newer", vec![exclude_newer.clone()]));
+        }
+        defaults
+    }
+
+    /// Apply the settings to the given command, as the defaults for the corresponding arguments
+    /// (in the command itself, and in all of its subcommands).
+    pub(crate) fn apply(&self, mut command: Command) -> Command {
+        let defaults = self.defaults();
+        if !defaults.is_empty() {
+            apply_defaults(&mut command, &defaults);
+        }
+        command
+    }
+}
+
+fn apply_defaults(command: &mut Command, defaults: &[(&'static str, Vec<String>)]) {
+    for (id, values) in defaults {
+        if command.get_arguments().any(|arg| arg.get_id() == *id) {
+            let taken = std::mem::replace(command, Command::new(""));
+            *command = taken.mut_arg(*id, |arg| arg.default_values(values.clone()));
+        }
+    }
+    for subcommand in command.get_subcommands_mut() {
+        apply_defaults(subcommand, defaults);
+    }
+}
+
+fn invalid(setting: &str, value: &str, err: &str) -> anyhow::Error {
+    anyhow::anyhow!("Invalid value for `{setting}` (`{value}`): {err}")
+}
+
+/// Return the path to the configuration file in the given directory, if any, along with its
+/// settings.
+///
+/// A `uv.toml` takes precedence over a `pyproject.toml`, which is only considered if it contains
+/// a `[tool.uv]` table.
+fn find_in(dir: &Path) -> Result<Option<(PathBuf, Settings)>> {
+    let path = dir.join(CONFIG_FILENAME);
+    if path.is_file() {
+        let settings = read(&path)?.unwrap_or_default();
+        return Ok(Some((path, settings)));
+    }
+    let path = dir.join("pyproject.toml");
+    if path.is_file() {
+        if let Some(settings) = read(&path)? {
+            return Ok(Some((path, settings)));
+        }
+    }
+    Ok(None)
+}
+
+/// Extract `--config-file` and `--no-config` from the command-line arguments.
+///
+/// The settings must be loaded before the command line is parsed, since they provide the defaults
+/// for its arguments; so these (global) flags are located directly, rather than via a lenient
+/// parse of the full command line. Any arguments after a `--` separator are ignored.
+fn config_args(args: impl IntoIterator<Item = OsString>) -> (Option<PathBuf>, bool) {
+    let mut config_file = None;
+    let mut no_config = false;
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let Some(arg) = arg.to_str() else {
+            continue;
+        };
+        if arg == "--" {
+            break;
+        } else if arg == "--no-config" {
+            no_config = true;
+        } else if arg == "--config-file" {
+            config_file = args.next().map(PathBuf::from);
+        } else if let Some(value) = arg.strip_prefix("--config-file=") {
+            config_file = Some(PathBuf::from(value));
+        }
+    }
+
+    (config_file, no_config)
+}
+
+/// Return the path to the user-level configuration file, if it exists.
+fn user_config() -> Option<PathBuf> {
+    // Prefer `$XDG_CONFIG_HOME` (or `~/.config`) on all Unix platforms, including macOS.
+    let dir = if cfg!(unix) {
+        std::env::var_os("XDG_CONFIG_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| directories::BaseDirs::new().map(|dirs| dirs.home_dir().join(".config")))
+    } else {
+        directories::BaseDirs::new().map(|dirs| dirs.config_dir().to_path_buf())
+    }?;
+    let path = dir.join("uv").join(CONFIG_FILENAME);
+    path.is_file().then_some(path)
+}
+
+/// Read the settings from a `uv.toml` or `pyproject.toml` file.
+///
+/// Returns `None` for a `pyproject.toml` without a `[tool.uv]` table.
+fn read(path: &Path) -> Result<Option<Settings>> {
+    #[derive(Deserialize)]
+    struct PyProjectToml {
+        tool: Option<Tool>,
+    }
+
+    #[derive(Deserialize)]
+    struct Tool {
+        uv: Option<Settings>,
+    }
+
+    let contents = fs_err::read_to_string(path)?;
+    let settings = if path
+        .file_name()
+        .is_some_and(|name| name == "pyproject.toml")
+    {
+        let pyproject_toml: PyProjectToml = toml::from_str(&contents)
+            .with_context(|| format!("Failed to parse: {}", path.simplified_display()))?;
+        let Some(settings) = pyproject_toml.tool.and_then(|tool| tool.uv) else {
+            return Ok(None);
+        };
+        settings
+    } else {
+        toml::from_str(&contents)
+            .with_context(|| format!("Failed to parse: {}", path.simplified_display()))?
+    };
+
+    match settings.validate(path) {
+        Ok(settings) => Ok(Some(settings)),
+        Err(err) => bail!(
+            "Invalid configuration in {}: {err}",
+            path.simplified_display()
+        ),
+    }
+}
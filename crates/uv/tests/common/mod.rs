@@ -40,6 +40,15 @@ pub const INSTA_FILTERS: &[(&str, &str)] = &[
     ),
 ];
 
+/// The standard filters, along with a filter for the digests of the input files in the
+/// `pip compile` header, which vary with the contents of each input file.
+pub fn compile_filters() -> Vec<(&'static str, &'static str)> {
+    [(r"(?m)^# input-hash: .*\n", "")]
+        .into_iter()
+        .chain(INSTA_FILTERS.iter().copied())
+        .collect()
+}
+
 #[derive(Debug)]
 pub struct TestContext {
     pub temp_dir: assert_fs::TempDir,
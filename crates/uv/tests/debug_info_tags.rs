@@ -0,0 +1,133 @@
+#![cfg(feature = "python")]
+
+use std::process::Command;
+
+use anyhow::Result;
+use assert_cmd::prelude::*;
+
+use crate::common::{get_bin, TestContext};
+
+mod common;
+
+/// Create a `debug info` command with options shared across scenarios.
+fn command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("debug")
+        .arg("info")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .current_dir(&context.temp_dir);
+    command
+}
+
+#[test]
+fn list_tags() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let output = command(&context)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let output = String::from_utf8(output)?;
+
+    assert!(output.contains("Target interpreter:"));
+    assert!(output.contains("Compatible tags:"));
+    assert!(output.lines().any(|line| line == "  cp312-none-any"));
+    assert!(output.lines().any(|line| line == "  py3-none-any"));
+
+    Ok(())
+}
+
+#[test]
+fn compatible_wheel() {
+    let context = TestContext::new("3.12");
+
+    command(&context)
+        .arg("--wheel")
+        .arg("iniconfig-2.0.0-py3-none-any.whl")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "Compatible: matches `py3-none-any`",
+        ));
+}
+
+#[test]
+fn incompatible_python() {
+    let context = TestContext::new("3.12");
+
+    command(&context)
+        .arg("--wheel")
+        .arg("dist/example-1.0.0-cp27-cp27mu-any.whl")
+        .assert()
+        .failure()
+        .stdout(predicates::str::contains(
+            "Incompatible: none of the wheel's Python tags are supported",
+        ));
+}
+
+/// The explanation is included in the JSON report.
+#[test]
+fn compatible_wheel_json() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let output = command(&context)
+        .arg("--format")
+        .arg("json")
+        .arg("--wheel")
+        .arg("iniconfig-2.0.0-py3-none-any.whl")
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let report: serde_json::Value = serde_json::from_slice(&output)?;
+
+    assert_eq!(report["wheel"]["compatible"], true);
+    assert_eq!(report["wheel"]["tag"], "py3-none-any");
+    assert!(report["target"]["tags"]
+        .as_array()
+        .is_some_and(|tags| tags.iter().any(|tag| tag == "cp312-none-any")));
+
+    Ok(())
+}
+
+#[test]
+fn invalid_wheel() {
+    let context = TestContext::new("3.12");
+
+    command(&context)
+        .arg("--wheel")
+        .arg("example-1.0.0.tar.gz")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("Failed to parse wheel filename"));
+}
+
+/// Override the detected glibc version with `UV_MANYLINUX_COMPATIBLE`.
+#[test]
+#[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+fn manylinux_compatible_override() {
+    let context = TestContext::new("3.12");
+
+    command(&context)
+        .arg("--wheel")
+        .arg("example-1.0.0-py3-none-manylinux_2_17_x86_64.whl")
+        .env("UV_MANYLINUX_COMPATIBLE", "2.12")
+        .assert()
+        .failure()
+        .stdout(predicates::str::contains(
+            "Incompatible: the wheel requires glibc 2.17 or later, but the current platform has glibc 2.12",
+        ));
+
+    command(&context)
+        .arg("--wheel")
+        .arg("example-1.0.0-py3-none-manylinux_2_17_x86_64.whl")
+        .env("UV_MANYLINUX_COMPATIBLE", "manylinux2014")
+        .assert()
+        .success();
+}
@@ -0,0 +1,74 @@
+use std::process::Command;
+
+use anyhow::Result;
+use assert_cmd::prelude::*;
+use serde_json::Value;
+
+use crate::common::get_bin;
+
+mod common;
+
+/// Run `uv` with the given arguments, and parse the JSON description from its output.
+fn help_json(args: &[&str]) -> Result<Value> {
+    let output = Command::new(get_bin())
+        .args(args)
+        .arg("--help-json")
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    Ok(serde_json::from_slice(&output)?)
+}
+
+/// Find the argument with the given long name in a command description.
+fn argument<'a>(command: &'a Value, long: &str) -> Option<&'a Value> {
+    command["arguments"]
+        .as_array()?
+        .iter()
+        .find(|arg| arg["long"] == long)
+}
+
+#[test]
+fn root() -> Result<()> {
+    let schema = help_json(&[])?;
+
+    assert_eq!(schema["command"], "uv");
+    let subcommands = schema["subcommands"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|subcommand| subcommand["command"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    assert!(subcommands.contains(&"uv pip"));
+    assert!(subcommands.contains(&"uv venv"));
+
+    Ok(())
+}
+
+/// Describe a subcommand, without providing its required arguments.
+#[test]
+fn subcommand() -> Result<()> {
+    let schema = help_json(&["pip", "compile"])?;
+
+    assert_eq!(schema["command"], "uv pip compile");
+
+    let index_url = argument(&schema, "index-url").unwrap();
+    assert_eq!(index_url["env"], "UV_INDEX_URL");
+    assert_eq!(index_url["takes_value"], true);
+
+    let resolution = argument(&schema, "resolution").unwrap();
+    assert_eq!(resolution["default"], serde_json::json!(["highest"]));
+    assert!(resolution["possible_values"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|value| value["name"] == "lowest-direct"));
+
+    // Global arguments are propagated to each subcommand.
+    let quiet = argument(&schema, "quiet").unwrap();
+    assert_eq!(quiet["global"], true);
+    assert_eq!(quiet["takes_value"], false);
+
+    Ok(())
+}
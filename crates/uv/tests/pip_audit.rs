@@ -0,0 +1,108 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::process::Command;
+
+use anyhow::Result;
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use predicates::prelude::predicate;
+
+use crate::common::{get_bin, uv_snapshot, TestContext};
+
+mod common;
+
+/// Create a `pip audit` command with options shared across scenarios.
+fn command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("pip")
+        .arg("audit")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .current_dir(&context.temp_dir);
+    command
+}
+
+/// Unpinned requirements are skipped, since there's no single version to audit.
+#[test]
+fn audit_unpinned() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("anyio>=4.0.0")?;
+
+    uv_snapshot!(command(&context)
+        .arg("-r")
+        .arg("requirements.txt")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: Skipping `anyio>=4.0.0`, since only pinned (`==`) requirements can be audited
+    Audited 0 packages in [TIME]
+    No known vulnerabilities found.
+    "###
+    );
+
+    Ok(())
+}
+
+/// Audit the pins in a requirements file, failing on a known vulnerability with `--strict`.
+#[test]
+fn audit_requirements_strict() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("requests==2.19.1")?;
+
+    // `requests` 2.19.1 leaks the `Authorization` header on redirects (CVE-2018-18074), which was
+    // fixed in 2.20.0.
+    command(&context)
+        .arg("-r")
+        .arg("requirements.txt")
+        .arg("--strict")
+        .assert()
+        .failure()
+        .code(1)
+        .stderr(predicate::str::contains("Audited 1 package"))
+        .stderr(predicate::str::contains("CVE-2018-18074"))
+        .stderr(predicate::str::contains("fixed in 2.20.0"));
+
+    // Without `--strict`, vulnerabilities are reported, but the command succeeds.
+    command(&context)
+        .arg("-r")
+        .arg("requirements.txt")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("CVE-2018-18074"));
+
+    Ok(())
+}
+
+/// Audit the packages installed in the current environment.
+#[test]
+fn audit_installed() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("requests==2.19.1")?;
+
+    Command::new(get_bin())
+        .arg("pip")
+        .arg("sync")
+        .arg(requirements_txt.path())
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .assert()
+        .success();
+
+    command(&context)
+        .arg("--strict")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("requests==2.19.1"))
+        .stderr(predicate::str::contains("CVE-2018-18074"));
+
+    Ok(())
+}
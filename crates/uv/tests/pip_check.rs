@@ -0,0 +1,123 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::process::Command;
+
+use anyhow::Result;
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+
+use crate::common::{get_bin, uv_snapshot, TestContext};
+
+mod common;
+
+/// Create a `pip check` command with options shared across scenarios.
+fn command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("pip")
+        .arg("check")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .current_dir(&context.temp_dir);
+    command
+}
+
+/// Install the given requirements into the test environment with `pip sync`.
+fn sync(context: &TestContext, requirements: &str) -> Result<()> {
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str(requirements)?;
+
+    Command::new(get_bin())
+        .arg("pip")
+        .arg("sync")
+        .arg(requirements_txt.path())
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .assert()
+        .success();
+
+    Ok(())
+}
+
+/// Check an environment in which all dependencies are satisfied.
+#[test]
+fn check_compatible() -> Result<()> {
+    let context = TestContext::new("3.12");
+    sync(&context, "anyio==4.0.0\nidna==3.4\nsniffio==1.3.0")?;
+
+    uv_snapshot!(command(&context), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Checked 3 packages in [TIME]
+    No broken requirements found.
+    "###
+    );
+
+    Ok(())
+}
+
+/// Check an environment in which dependencies are missing.
+#[test]
+fn check_missing_dependency() -> Result<()> {
+    let context = TestContext::new("3.12");
+    sync(&context, "anyio==4.0.0\nidna==3.4")?;
+
+    uv_snapshot!(command(&context), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    Checked 2 packages in [TIME]
+    Found 1 incompatibility:
+     - The package `anyio` requires `sniffio >=1.1`, but it's not installed.
+    "###
+    );
+
+    Ok(())
+}
+
+/// Check whether an environment would remain installable under an older Python version.
+#[test]
+fn check_python_version() -> Result<()> {
+    let context = TestContext::new("3.12");
+    sync(&context, "anyio==4.0.0\nidna==3.4\nsniffio==1.3.0")?;
+
+    // `anyio` 4.0.0 requires Python 3.8 or later, along with additional dependencies on older
+    // Python versions.
+    command(&context)
+        .arg("--python-version")
+        .arg("3.7")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "Found 1 package that would block a switch to Python 3.7:",
+        ))
+        .stderr(predicates::str::contains(
+            "The package `anyio` requires Python >=3.8, which excludes `3.7`.",
+        ))
+        .stderr(predicates::str::contains(
+            "The package `anyio` would require `exceptiongroup >=1.0.2",
+        ));
+
+    // All of the packages remain installable under a newer Python version.
+    uv_snapshot!(command(&context)
+        .arg("--python-version")
+        .arg("3.12"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Checked 3 packages in [TIME]
+    All installed packages are compatible with Python 3.12.
+    "###
+    );
+
+    Ok(())
+}
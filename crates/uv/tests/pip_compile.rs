@@ -6,12 +6,13 @@ use std::path::PathBuf;
 use std::process::Command;
 
 use anyhow::{bail, Context, Result};
+use assert_cmd::prelude::*;
 use assert_fs::prelude::*;
 use assert_fs::TempDir;
 use indoc::indoc;
 use url::Url;
 
-use common::{uv_snapshot, TestContext, INSTA_FILTERS};
+use common::{compile_filters, uv_snapshot, TestContext};
 use uv_fs::Simplified;
 
 use crate::common::{get_bin, EXCLUDE_NEWER};
@@ -25,7 +26,7 @@ fn compile_requirements_in() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("django==5.0b1")?;
 
-    uv_snapshot!(context
+    uv_snapshot!(compile_filters(), context
         .compile()
         .arg("requirements.in"), @r###"
     success: true
@@ -53,7 +54,7 @@ fn compile_requirements_in_annotation_line() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("django==5.0b1")?;
 
-    uv_snapshot!(context
+    uv_snapshot!(compile_filters(), context
         .compile()
         .arg("--annotation-style=line")
         .arg("requirements.in"), @r###"
@@ -81,7 +82,7 @@ fn compile_requirements_in_stdin() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("django==5.0b1")?;
 
-    uv_snapshot!(context
+    uv_snapshot!(compile_filters(), context
         .compile()
         .stdin(fs::File::open(requirements_in)?)
         .arg("-"), @r###"
@@ -108,7 +109,7 @@ fn missing_requirements_in() {
     let context = TestContext::new("3.12");
     let requirements_in = context.temp_dir.child("requirements.in");
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in"), @r###"
     success: false
     exit_code: 2
@@ -129,7 +130,7 @@ fn missing_venv() -> Result<()> {
     let cache_dir = TempDir::new()?;
     let venv = temp_dir.child(".venv");
 
-    uv_snapshot!(Command::new(get_bin())
+    uv_snapshot!(compile_filters(), Command::new(get_bin())
             .arg("pip")
             .arg("compile")
             .arg("requirements.in")
@@ -169,7 +170,7 @@ dependencies = [
 "#,
     )?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("pyproject.toml"), @r###"
     success: true
     exit_code: 0
@@ -190,6 +191,200 @@ dependencies = [
     Ok(())
 }
 
+/// Warn when a `pyproject.toml` file declares its dependencies as dynamic.
+#[test]
+fn compile_pyproject_toml_dynamic_dependencies() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"[build-system]
+requires = ["setuptools", "wheel"]
+
+[project]
+name = "project"
+dynamic = ["dependencies"]
+"#,
+    )?;
+
+    uv_snapshot!(compile_filters(), context.compile()
+            .arg("pyproject.toml"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z pyproject.toml
+
+    ----- stderr -----
+    warning: `pyproject.toml` declares `project.dependencies` as dynamic; only statically declared dependencies are included
+    Resolved 0 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Resolve the requirements of a `setup.py` file, including an extra, by building its metadata.
+#[test]
+fn compile_setup_py() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let setup_py = context.temp_dir.child("setup.py");
+    setup_py.write_str(
+        r#"from setuptools import setup
+
+setup(
+    name="project",
+    version="0.1.0",
+    install_requires=["anyio==4.0.0"],
+    extras_require={"dev": ["iniconfig==2.0.0"]},
+)
+"#,
+    )?;
+
+    uv_snapshot!(compile_filters(), context.compile()
+            .arg("setup.py")
+            .arg("--extra")
+            .arg("dev"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z setup.py --extra dev
+    anyio==4.0.0
+    idna==3.4
+        # via anyio
+    iniconfig==2.0.0
+    sniffio==1.3.0
+        # via anyio
+
+    ----- stderr -----
+    Resolved 4 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Resolve the requirements of a `setup.cfg` file, by building its metadata.
+#[test]
+fn compile_setup_cfg() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let setup_cfg = context.temp_dir.child("setup.cfg");
+    setup_cfg.write_str(indoc! {r"
+        [metadata]
+        name = project
+        version = 0.1.0
+
+        [options]
+        install_requires =
+            anyio==4.0.0
+    "})?;
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(indoc! {r#"
+        [build-system]
+        requires = ["setuptools"]
+        build-backend = "setuptools.build_meta"
+    "#})?;
+
+    uv_snapshot!(compile_filters(), context.compile()
+            .arg("setup.cfg"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z setup.cfg
+    anyio==4.0.0
+    idna==3.4
+        # via anyio
+    sniffio==1.3.0
+        # via anyio
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Resolve a package whose extras depend on each other.
+#[test]
+fn compile_self_dependency_cycle() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let project = context.temp_dir.child("example");
+    project.child("pyproject.toml").write_str(indoc! {r#"
+        [project]
+        name = "example"
+        version = "0.1.0"
+
+        [project.optional-dependencies]
+        dev = ["example[test]"]
+        test = ["example[dev]", "iniconfig"]
+
+        [build-system]
+        requires = ["setuptools>=42"]
+        build-backend = "setuptools.build_meta"
+    "#})?;
+
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str(&format!(
+        "example[dev] @ {}",
+        Url::from_file_path(project.path()).unwrap()
+    ))?;
+
+    context
+        .compile()
+        .arg("requirements.in")
+        .arg("--verbose")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("iniconfig==2.0.0"))
+        .stderr(predicates::str::contains(
+            "Found a dependency cycle: example[dev] -> example[test] -> example[dev]",
+        ));
+
+    Ok(())
+}
+
+/// Resolve a package whose extras depend on each other, with a specifier that excludes the
+/// package's own version. The specifier should be respected, such that resolution fails.
+#[test]
+fn compile_self_dependency_excluded() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let project = context.temp_dir.child("example");
+    project.child("pyproject.toml").write_str(indoc! {r#"
+        [project]
+        name = "example"
+        version = "0.1.0"
+
+        [project.optional-dependencies]
+        dev = ["example[test]>=1.0"]
+        test = ["iniconfig"]
+
+        [build-system]
+        requires = ["setuptools>=42"]
+        build-backend = "setuptools.build_meta"
+    "#})?;
+
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str(&format!(
+        "example[dev] @ {}",
+        Url::from_file_path(project.path()).unwrap()
+    ))?;
+
+    context
+        .compile()
+        .arg("requirements.in")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "No solution found when resolving dependencies",
+        ));
+
+    Ok(())
+}
+
 /// Resolve a package from a `requirements.in` file, with a `constraints.txt` file.
 #[test]
 fn compile_constraints_txt() -> Result<()> {
@@ -200,7 +395,7 @@ fn compile_constraints_txt() -> Result<()> {
     let constraints_txt = context.temp_dir.child("constraints.txt");
     constraints_txt.write_str("sqlparse<0.4.4")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in")
             .arg("--constraint")
             .arg("constraints.txt"), @r###"
@@ -234,7 +429,7 @@ fn compile_constraints_inline() -> Result<()> {
     let constraints_txt = context.temp_dir.child("constraints.txt");
     constraints_txt.write_str("sqlparse<0.4.4")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in"), @r###"
     success: true
     exit_code: 0
@@ -264,7 +459,7 @@ fn compile_constraints_markers() -> Result<()> {
     constraints_txt.write_str("sniffio==1.2.0;python_version<='3.7'")?;
     constraints_txt.write_str("sniffio==1.3.0;python_version>'3.7'")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in")
             .arg("--constraint")
             .arg("constraints.txt"), @r###"
@@ -305,7 +500,7 @@ optional-dependencies.foo = [
 "#,
     )?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("pyproject.toml")
             .arg("--extra")
             .arg("foo"), @r###"
@@ -346,7 +541,7 @@ optional-dependencies."FrIeNdLy-._.-bArD" = [
 "#,
     )?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("pyproject.toml")
             .arg("--extra")
             .arg("FRiENDlY-...-_-BARd"), @r###"
@@ -387,7 +582,7 @@ optional-dependencies.foo = [
 "#,
     )?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("pyproject.toml")
             .arg("--extra")
             .arg("bar"), @r###"
@@ -426,7 +621,7 @@ build-backend = "poetry.core.masonry.api"
 "#,
     )?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("pyproject.toml"), @r###"
     success: true
     exit_code: 0
@@ -461,7 +656,7 @@ optional-dependencies.foo = [
 "#,
     )?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("pyproject.toml")
             .arg("--extra")
             .arg("foo")
@@ -488,7 +683,7 @@ fn compile_requirements_file_extra() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("django==5.0b1")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in")
             .arg("--all-extras"),
             @r###"
@@ -497,7 +692,7 @@ fn compile_requirements_file_extra() -> Result<()> {
     ----- stdout -----
 
     ----- stderr -----
-    error: Requesting extras requires a pyproject.toml input file.
+    error: Requesting extras requires a pyproject.toml, setup.cfg, or setup.py input file.
     "###
     );
 
@@ -522,7 +717,7 @@ optional-dependencies.foo = [
 "#,
     )?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("pyproject.toml")
             .arg("--extra")
             .arg("invalid name!"), @r###"
@@ -547,7 +742,7 @@ fn compile_python_312() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("black==23.10.1")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in")
             .arg("--python-version")
             .arg("3.12"), @r###"
@@ -576,6 +771,45 @@ fn compile_python_312() -> Result<()> {
     Ok(())
 }
 
+/// Resolve a specific version of Black for Windows with `--python-platform`, which should
+/// include Windows-only dependencies (like `colorama`).
+#[test]
+fn compile_python_platform_windows() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("black==23.10.1")?;
+
+    uv_snapshot!(compile_filters(), context.compile()
+            .arg("requirements.in")
+            .arg("--python-platform")
+            .arg("windows"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --python-platform windows
+    black==23.10.1
+    click==8.1.7
+        # via black
+    colorama==0.4.6
+        # via click
+    mypy-extensions==1.0.0
+        # via black
+    packaging==23.2
+        # via black
+    pathspec==0.11.2
+        # via black
+    platformdirs==4.0.0
+        # via black
+
+    ----- stderr -----
+    Resolved 7 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
 /// Resolve a specific version of Black at Python 3.12 with `--annotation-style=line`.
 #[test]
 fn compile_python_312_annotation_line() -> Result<()> {
@@ -583,7 +817,7 @@ fn compile_python_312_annotation_line() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("black==23.10.1")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("--annotation-style=line")
             .arg("requirements.in")
             .arg("--python-version")
@@ -615,7 +849,7 @@ fn compile_python_312_no_deps() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("black==23.10.1")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in")
             .arg("--no-deps")
             .arg("--python-version")
@@ -650,7 +884,7 @@ fn compile_python_37() -> Result<()> {
         ),
     ]
         .into_iter()
-        .chain(INSTA_FILTERS.to_vec())
+        .chain(compile_filters())
         .collect();
 
     uv_snapshot!(filters, context.compile()
@@ -681,7 +915,7 @@ fn compile_sdist_resolution_lowest() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("anyio @ https://files.pythonhosted.org/packages/2d/b8/7333d87d5f03247215d86a86362fd3e324111788c6cdd8d2e6196a6ba833/anyio-4.2.0.tar.gz")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in")
             .arg("--resolution=lowest-direct")
             .arg("--python-version")
@@ -712,7 +946,7 @@ fn compile_python_invalid_version() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("black==23.10.1")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in")
             .arg("--python-version")
             .arg("3.7.x"), @r###"
@@ -737,7 +971,7 @@ fn compile_python_dev_version() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("black==23.10.1")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in")
             .arg("--python-version")
             .arg("3.7-dev"), @r###"
@@ -764,7 +998,7 @@ fn compile_numpy_py38() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("numpy")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in")
             .arg("--no-build"), @r###"
     success: true
@@ -789,7 +1023,7 @@ fn compile_wheel_url_dependency() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("flask @ https://files.pythonhosted.org/packages/36/42/015c23096649b908c809c69388a805a571a3bea44362fe87e33fc3afa01f/flask-3.0.0-py3-none-any.whl")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in"), @r###"
     success: true
     exit_code: 0
@@ -820,6 +1054,34 @@ fn compile_wheel_url_dependency() -> Result<()> {
     Ok(())
 }
 
+/// Resolve a specific Flask wheel via a URL dependency with `--generate-hashes`, which should
+/// include the hash of the downloaded wheel.
+#[test]
+fn compile_wheel_url_dependency_hashes() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("flask @ https://files.pythonhosted.org/packages/36/42/015c23096649b908c809c69388a805a571a3bea44362fe87e33fc3afa01f/flask-3.0.0-py3-none-any.whl")?;
+
+    uv_snapshot!(compile_filters(), context.compile()
+            .arg("requirements.in")
+            .arg("--no-deps")
+            .arg("--generate-hashes"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --no-deps --generate-hashes
+    flask @ https://files.pythonhosted.org/packages/36/42/015c23096649b908c809c69388a805a571a3bea44362fe87e33fc3afa01f/flask-3.0.0-py3-none-any.whl \
+        --hash=sha256:21128f47e4e3b9d597a3e8521a329bf56909b690fcc3fa3e477725aa81367638
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
 /// Resolve a specific Flask source distribution via a URL dependency.
 ///
 /// Exercises the `prepare_metadata_for_build_wheel` hooks.
@@ -829,7 +1091,7 @@ fn compile_sdist_url_dependency() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("flask @ https://files.pythonhosted.org/packages/d8/09/c1a7354d3925a3c6c8cfdebf4245bae67d633ffda1ba415add06ffc839c5/flask-3.0.0.tar.gz")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in"), @r###"
     success: true
     exit_code: 0
@@ -871,7 +1133,7 @@ fn compile_git_https_dependency() -> Result<()> {
     // In addition to the standard filters, remove the `main` commit, which will change frequently.
     let filters: Vec<_> = [(r"@(\d|\w){40}", "@[COMMIT]")]
         .into_iter()
-        .chain(INSTA_FILTERS.to_vec())
+        .chain(compile_filters())
         .collect();
 
     uv_snapshot!(filters, context.compile()
@@ -912,7 +1174,7 @@ fn compile_git_branch_https_dependency() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("flask @ git+https://github.com/pallets/flask.git@1.0.x")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in"), @r###"
     success: true
     exit_code: 0
@@ -949,7 +1211,7 @@ fn compile_git_tag_https_dependency() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("flask @ git+https://github.com/pallets/flask.git@3.0.0")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in"), @r###"
     success: true
     exit_code: 0
@@ -990,7 +1252,7 @@ fn compile_git_long_commit_https_dependency() -> Result<()> {
         "flask @ git+https://github.com/pallets/flask.git@d92b64aa275841b0c9aea3903aba72fbc4275d91",
     )?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in"), @r###"
     success: true
     exit_code: 0
@@ -1027,7 +1289,7 @@ fn compile_git_short_commit_https_dependency() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("flask @ git+https://github.com/pallets/flask.git@d92b64a")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in"), @r###"
     success: true
     exit_code: 0
@@ -1065,7 +1327,7 @@ fn compile_git_refs_https_dependency() -> Result<()> {
     requirements_in
         .write_str("flask @ git+https://github.com/pallets/flask.git@refs/pull/5313/head")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in"), @r###"
     success: true
     exit_code: 0
@@ -1104,7 +1366,7 @@ fn compile_git_subdirectory_dependency() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("example-pkg-a @ git+https://github.com/pypa/sample-namespace-packages.git@df7530eeb8fa0cb7dbb8ecb28363e8e36bfa2f45#subdirectory=pkg_resources/pkg_a")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in"), @r###"
     success: true
     exit_code: 0
@@ -1121,6 +1383,31 @@ fn compile_git_subdirectory_dependency() -> Result<()> {
     Ok(())
 }
 
+/// Resolve a direct URL dependency on an archive, with a subdirectory.
+#[test]
+fn compile_archive_subdirectory_dependency() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("example-pkg-a @ https://github.com/pypa/sample-namespace-packages/archive/df7530eeb8fa0cb7dbb8ecb28363e8e36bfa2f45.zip#subdirectory=pkg_resources/pkg_a\nexample-pkg-b @ https://github.com/pypa/sample-namespace-packages/archive/df7530eeb8fa0cb7dbb8ecb28363e8e36bfa2f45.zip#subdirectory=pkg_resources/pkg_b")?;
+
+    uv_snapshot!(compile_filters(), context.compile()
+            .arg("requirements.in"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
+    example-pkg-a @ https://github.com/pypa/sample-namespace-packages/archive/df7530eeb8fa0cb7dbb8ecb28363e8e36bfa2f45.zip#subdirectory=pkg_resources/pkg_a
+    example-pkg-b @ https://github.com/pypa/sample-namespace-packages/archive/df7530eeb8fa0cb7dbb8ecb28363e8e36bfa2f45.zip#subdirectory=pkg_resources/pkg_b
+
+    ----- stderr -----
+    Resolved 2 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
 /// Resolve two packages from a `requirements.in` file with the same Git HTTPS dependency.
 #[test]
 #[cfg(feature = "git")]
@@ -1130,7 +1417,7 @@ fn compile_git_concurrent_access() -> Result<()> {
     requirements_in
         .write_str("example-pkg-a @ git+https://github.com/pypa/sample-namespace-packages.git@df7530eeb8fa0cb7dbb8ecb28363e8e36bfa2f45#subdirectory=pkg_resources/pkg_a\nexample-pkg-b @ git+https://github.com/pypa/sample-namespace-packages.git@df7530eeb8fa0cb7dbb8ecb28363e8e36bfa2f45#subdirectory=pkg_resources/pkg_b")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in"), @r###"
     success: true
     exit_code: 0
@@ -1157,14 +1444,15 @@ fn compile_git_mismatched_name() -> Result<()> {
     requirements_in
         .write_str("flask @ git+https://github.com/pallets/flask.git@2.0.0\ndask @ git+https://github.com/pallets/flask.git@3.0.0")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in"), @r###"
     success: false
     exit_code: 2
     ----- stdout -----
 
     ----- stderr -----
-    error: Failed to download and build: dask @ git+https://github.com/pallets/flask.git@3.0.0
+    error: requirements.in:2: `dask @ git+https://github.com/pallets/flask.git@3.0.0`
+      Caused by: Failed to download and build: dask @ git+https://github.com/pallets/flask.git@3.0.0
       Caused by: Package metadata name `flask` does not match given name `dask`
     "###
     );
@@ -1180,7 +1468,7 @@ fn mixed_url_dependency() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("flask==3.0.0\nwerkzeug @ https://files.pythonhosted.org/packages/c3/fc/254c3e9b5feb89ff5b9076a23218dafbc99c96ac5941e900b71206e6313b/werkzeug-3.0.1-py3-none-any.whl")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in"), @r###"
     success: true
     exit_code: 0
@@ -1219,7 +1507,7 @@ fn conflicting_direct_url_dependency() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("werkzeug==3.0.0\nwerkzeug @ https://files.pythonhosted.org/packages/ff/1d/960bb4017c68674a1cb099534840f18d3def3ce44aed12b5ed8b78e0153e/Werkzeug-2.0.0-py3-none-any.whl")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in"), @r###"
     success: false
     exit_code: 1
@@ -1244,7 +1532,7 @@ fn compatible_direct_url_dependency() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("werkzeug==2.0.0\nwerkzeug @ https://files.pythonhosted.org/packages/ff/1d/960bb4017c68674a1cb099534840f18d3def3ce44aed12b5ed8b78e0153e/Werkzeug-2.0.0-py3-none-any.whl")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in"), @r###"
     success: true
     exit_code: 0
@@ -1268,7 +1556,7 @@ fn conflicting_repeated_url_dependency_version_mismatch() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("werkzeug @ https://files.pythonhosted.org/packages/bd/24/11c3ea5a7e866bf2d97f0501d0b4b1c9bbeade102bb4b588f0d2919a5212/Werkzeug-2.0.1-py3-none-any.whl\nwerkzeug @ https://files.pythonhosted.org/packages/ff/1d/960bb4017c68674a1cb099534840f18d3def3ce44aed12b5ed8b78e0153e/Werkzeug-2.0.0-py3-none-any.whl")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in"), @r###"
     success: false
     exit_code: 2
@@ -1295,7 +1583,7 @@ fn conflicting_repeated_url_dependency_markers() -> Result<()> {
         werkzeug @ https://files.pythonhosted.org/packages/ff/1d/960bb4017c68674a1cb099534840f18d3def3ce44aed12b5ed8b78e0153e/Werkzeug-2.0.0-py3-none-any.whl ; python_version < '3.10'
     "})?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in"), @r###"
     success: true
     exit_code: 0
@@ -1312,6 +1600,67 @@ fn conflicting_repeated_url_dependency_markers() -> Result<()> {
     Ok(())
 }
 
+/// Request incompatible versions of Werkzeug across two input files. The error should point at
+/// the conflicting line in each file.
+#[test]
+fn conflicting_pins_across_files() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str(indoc! {r"
+        flask
+        werkzeug==2.0.0
+    "})?;
+    let dev_in = context.temp_dir.child("dev.in");
+    dev_in.write_str("werkzeug==3.0.0")?;
+
+    uv_snapshot!(compile_filters(), context.compile()
+            .arg("requirements.in")
+            .arg("dev.in"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Conflicting requirements for `werkzeug`:
+      `werkzeug==2.0.0` (from requirements.in:2)
+      `werkzeug==3.0.0` (from dev.in:1)
+    "###
+    );
+
+    Ok(())
+}
+
+/// Pin a package in a requirements file, and constrain it to an incompatible version in a
+/// constraints file. The conflict should be attributed to both files.
+#[test]
+fn conflicting_pin_and_constraint() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str(indoc! {r"
+        flask
+        werkzeug==2.0.0
+    "})?;
+    let constraints_txt = context.temp_dir.child("constraints.txt");
+    constraints_txt.write_str("werkzeug==3.0.0")?;
+
+    uv_snapshot!(compile_filters(), context.compile()
+            .arg("requirements.in")
+            .arg("--constraint")
+            .arg("constraints.txt"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Conflicting requirements for `werkzeug`:
+      `werkzeug==2.0.0` (from requirements.in:2)
+      `werkzeug==3.0.0` (constraint from constraints.txt:1)
+    "###
+    );
+
+    Ok(())
+}
+
 /// Request Werkzeug via two different URLs at the same version. Despite mapping to the same
 /// version, it should still result in a conflict.
 #[test]
@@ -1321,7 +1670,7 @@ fn conflicting_repeated_url_dependency_version_match() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("werkzeug @ git+https://github.com/pallets/werkzeug.git@2.0.0\nwerkzeug @ https://files.pythonhosted.org/packages/ff/1d/960bb4017c68674a1cb099534840f18d3def3ce44aed12b5ed8b78e0153e/Werkzeug-2.0.0-py3-none-any.whl")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in"), @r###"
     success: false
     exit_code: 2
@@ -1344,7 +1693,7 @@ fn conflicting_transitive_url_dependency() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("flask==3.0.0\nwerkzeug @ https://files.pythonhosted.org/packages/ff/1d/960bb4017c68674a1cb099534840f18d3def3ce44aed12b5ed8b78e0153e/Werkzeug-2.0.0-py3-none-any.whl")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in"), @r###"
     success: false
     exit_code: 1
@@ -1369,7 +1718,7 @@ fn compatible_repeated_url_dependency() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("werkzeug @ git+https://github.com/pallets/werkzeug.git@2.0.0\nwerkzeug @ git+https://github.com/pallets/werkzeug@2.0.0")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in"), @r###"
     success: true
     exit_code: 0
@@ -1395,7 +1744,7 @@ fn disallowed_transitive_url_dependency() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("transitive_url_dependency @ https://github.com/astral-sh/ruff/files/14078476/transitive_url_dependency.zip")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in"), @r###"
     success: false
     exit_code: 2
@@ -1421,7 +1770,7 @@ fn allowed_transitive_url_dependency() -> Result<()> {
     let constraints_txt = context.temp_dir.child("constraints.txt");
     constraints_txt.write_str("werkzeug @ git+https://github.com/pallets/werkzeug@2.0.0")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in")
             .arg("--constraint")
             .arg("constraints.txt"), @r###"
@@ -1455,7 +1804,7 @@ fn allowed_transitive_canonical_url_dependency() -> Result<()> {
     let constraints_txt = context.temp_dir.child("constraints.txt");
     constraints_txt.write_str("werkzeug @ git+https://github.com/pallets/werkzeug.git@2.0.0")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in")
             .arg("--constraint")
             .arg("constraints.txt"), @r###"
@@ -1497,7 +1846,7 @@ optional-dependencies.bar = [
 "#,
     )?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("pyproject.toml")
             .arg("--all-extras"), @r###"
     success: true
@@ -1552,7 +1901,7 @@ optional-dependencies.bar = [
 "#,
     )?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("--annotation-style=line")
             .arg("pyproject.toml")
             .arg("--all-extras"), @r###"
@@ -1600,7 +1949,7 @@ optional-dependencies.bar = [
 "#,
     )?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("pyproject.toml")
             .arg("--all-extras")
             .arg("--extra")
@@ -1637,7 +1986,7 @@ dependencies = ["django==5.0b1", "django==5.0a1"]
 "#,
     )?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("pyproject.toml"), @r###"
     success: false
     exit_code: 1
@@ -1669,7 +2018,7 @@ dependencies = ["django==300.1.4"]
 "#,
     )?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("pyproject.toml"), @r###"
     success: false
     exit_code: 1
@@ -1693,7 +2042,7 @@ fn compile_exclude_newer() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("tqdm")?;
 
-    uv_snapshot!(Command::new(get_bin())
+    uv_snapshot!(compile_filters(), Command::new(get_bin())
             .arg("pip")
             .arg("compile")
             .arg("requirements.in")
@@ -1719,7 +2068,7 @@ fn compile_exclude_newer() -> Result<()> {
 
     // Use a date as input instead.
     // We interpret a date as including this day
-    uv_snapshot!(Command::new(get_bin())
+    uv_snapshot!(compile_filters(), Command::new(get_bin())
             .arg("pip")
             .arg("compile")
             .arg("requirements.in")
@@ -1742,7 +2091,7 @@ fn compile_exclude_newer() -> Result<()> {
     );
 
     // Check the error message for invalid datetime
-    uv_snapshot!(Command::new(get_bin())
+    uv_snapshot!(compile_filters(), Command::new(get_bin())
             .arg("pip")
             .arg("compile")
             .arg("requirements.in")
@@ -1766,6 +2115,68 @@ fn compile_exclude_newer() -> Result<()> {
     Ok(())
 }
 
+/// Resolve a local path dependency that's referenced via two different paths (one of which is a
+/// symlink), which should be treated as the same distribution rather than conflicting URLs.
+#[test]
+#[cfg(unix)]
+fn compile_wheel_path_dependency_symlink() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // Download a wheel.
+    let response = reqwest::blocking::get("https://files.pythonhosted.org/packages/36/42/015c23096649b908c809c69388a805a571a3bea44362fe87e33fc3afa01f/flask-3.0.0-py3-none-any.whl")?;
+    let dist = context.temp_dir.child("dist");
+    dist.create_dir_all()?;
+    let flask_wheel = dist.child("flask-3.0.0-py3-none-any.whl");
+    let mut flask_wheel_file = fs::File::create(&flask_wheel)?;
+    std::io::copy(&mut response.bytes()?.as_ref(), &mut flask_wheel_file)?;
+
+    // Link to the directory containing the wheel.
+    let link = context.temp_dir.child("link");
+    std::os::unix::fs::symlink(dist.path(), link.path())?;
+
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str(&format!(
+        "flask @ {}\nflask @ {}",
+        Url::from_file_path(flask_wheel.path()).unwrap(),
+        Url::from_file_path(link.child("flask-3.0.0-py3-none-any.whl").path()).unwrap()
+    ))?;
+
+    // In addition to the standard filters, remove the temporary directory from the snapshot.
+    let filters: Vec<_> = [(r"file://.*/", "file://[TEMP_DIR]/")]
+        .into_iter()
+        .chain(compile_filters())
+        .collect();
+
+    uv_snapshot!(filters, context.compile()
+            .arg("requirements.in"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
+    blinker==1.7.0
+        # via flask
+    click==8.1.7
+        # via flask
+    flask @ file://[TEMP_DIR]/flask-3.0.0-py3-none-any.whl
+    itsdangerous==2.1.2
+        # via flask
+    jinja2==3.1.2
+        # via flask
+    markupsafe==2.1.3
+        # via
+        #   jinja2
+        #   werkzeug
+    werkzeug==3.0.1
+        # via flask
+
+    ----- stderr -----
+    Resolved 7 packages in [TIME]
+    "###);
+
+    Ok(())
+}
+
 /// Resolve a local path dependency on a specific wheel.
 #[test]
 fn compile_wheel_path_dependency() -> Result<()> {
@@ -1786,7 +2197,7 @@ fn compile_wheel_path_dependency() -> Result<()> {
     // In addition to the standard filters, remove the temporary directory from the snapshot.
     let filters: Vec<_> = [(r"file://.*/", "file://[TEMP_DIR]/")]
         .into_iter()
-        .chain(INSTA_FILTERS.to_vec())
+        .chain(compile_filters())
         .collect();
 
     uv_snapshot!(filters, context.compile()
@@ -1820,7 +2231,7 @@ fn compile_wheel_path_dependency() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("flask @ file:flask-3.0.0-py3-none-any.whl")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in"), @r###"
     success: true
     exit_code: 0
@@ -1852,7 +2263,7 @@ fn compile_wheel_path_dependency() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("flask @ file://flask-3.0.0-py3-none-any.whl")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in"), @r###"
     success: true
     exit_code: 0
@@ -1884,7 +2295,7 @@ fn compile_wheel_path_dependency() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("flask @ ./flask-3.0.0-py3-none-any.whl")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in"), @r###"
     success: true
     exit_code: 0
@@ -1920,7 +2331,7 @@ fn compile_wheel_path_dependency() -> Result<()> {
     let filter_path = regex::escape(&flask_wheel.simplified_display().to_string());
     let filters: Vec<_> = [(filter_path.as_str(), "/[TEMP_DIR]/")]
         .into_iter()
-        .chain(INSTA_FILTERS.to_vec())
+        .chain(compile_filters())
         .collect();
 
     uv_snapshot!(filters, context.compile()
@@ -1973,7 +2384,7 @@ fn compile_source_distribution_path_dependency() -> Result<()> {
     // In addition to the standard filters, remove the temporary directory from the snapshot.
     let filters: Vec<_> = [(r"file://.*/", "file://[TEMP_DIR]/")]
         .into_iter()
-        .chain(INSTA_FILTERS.to_vec())
+        .chain(compile_filters())
         .collect();
 
     uv_snapshot!(filters, context.compile()
@@ -2016,7 +2427,7 @@ fn compile_wheel_path_dependency_missing() -> Result<()> {
     // In addition to the standard filters, remove the temporary directory from the snapshot.
     let filters: Vec<_> = [(r"file://.*/", "file://[TEMP_DIR]/")]
         .into_iter()
-        .chain(INSTA_FILTERS.to_vec())
+        .chain(compile_filters())
         .collect();
 
     uv_snapshot!(filters, context.compile()
@@ -2039,7 +2450,7 @@ fn compile_yanked_version_direct() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("attrs==21.1.0")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in"), @r###"
     success: true
     exit_code: 0
@@ -2063,7 +2474,7 @@ fn compile_yanked_version_indirect() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("attrs>20.3.0,<21.2.0")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in"), @r###"
     success: false
     exit_code: 1
@@ -2097,7 +2508,7 @@ fn override_dependency() -> Result<()> {
     let overrides_txt = context.temp_dir.child("overrides.txt");
     overrides_txt.write_str("werkzeug==2.3.0")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in")
             .arg("--override")
             .arg("overrides.txt"), @r###"
@@ -2143,7 +2554,7 @@ fn override_multi_dependency() -> Result<()> {
         "tomli>=1.1.0; python_version >= '3.11'\ntomli<1.0.0; python_version < '3.11'",
     )?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in")
             .arg("--override")
             .arg("overrides.txt"), @r###"
@@ -2181,7 +2592,7 @@ fn missing_registry_extra() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("black[tensorboard]==23.10.1")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in"), @r###"
     success: true
     exit_code: 0
@@ -2216,7 +2627,7 @@ fn missing_url_extra() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("flask[tensorboard] @ https://files.pythonhosted.org/packages/36/42/015c23096649b908c809c69388a805a571a3bea44362fe87e33fc3afa01f/flask-3.0.0-py3-none-any.whl")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in"), @r###"
     success: true
     exit_code: 0
@@ -2256,7 +2667,7 @@ fn preserve_url() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("flask @ https://files.PYTHONHOSTED.org/packages/36/42/015c23096649b908c809c69388a805a571a3bea44362fe87e33fc3afa01f/flask-3.0.0-py3-none-any.whl")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in"), @r###"
     success: true
     exit_code: 0
@@ -2301,7 +2712,7 @@ fn preserve_env_var() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("flask @ file://${PROJECT_ROOT}/flask-3.0.0-py3-none-any.whl")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in"), @r###"
     success: true
     exit_code: 0
@@ -2348,7 +2759,7 @@ fn compile_editable() -> Result<()> {
     let filter_path = regex::escape(&requirements_in.simplified_display().to_string());
     let filters: Vec<_> = [(filter_path.as_str(), "requirements.in")]
         .into_iter()
-        .chain(INSTA_FILTERS.to_vec())
+        .chain(compile_filters())
         .collect();
 
     uv_snapshot!(filters, Command::new(get_bin())
@@ -2409,7 +2820,7 @@ fn recursive_extras_direct_url() -> Result<()> {
     let filter_path = regex::escape(&requirements_in.simplified_display().to_string());
     let filters: Vec<_> = [(filter_path.as_str(), "requirements.in")]
         .into_iter()
-        .chain(INSTA_FILTERS.to_vec())
+        .chain(compile_filters())
         .collect();
 
     let mut command = Command::new(get_bin());
@@ -2472,7 +2883,7 @@ fn compile_editable_url_requirement() -> Result<()> {
     let filter_path = regex::escape(&requirements_in.simplified_display().to_string());
     let filters: Vec<_> = [(filter_path.as_str(), "requirements.in")]
         .into_iter()
-        .chain(INSTA_FILTERS.to_vec())
+        .chain(compile_filters())
         .collect();
 
     uv_snapshot!(filters, Command::new(get_bin())
@@ -2521,7 +2932,7 @@ fn cache_errors_are_non_fatal() -> Result<()> {
     let interpreter_cache = context
         .cache_dir
         .path()
-        .join("interpreter-v0")
+        .join("interpreter-v1")
         .read_dir()?
         .next()
         .context("Expected a python interpreter cache file")??
@@ -2537,7 +2948,7 @@ fn cache_errors_are_non_fatal() -> Result<()> {
     ];
 
     let check = || {
-        uv_snapshot!(context.compile()
+        uv_snapshot!(compile_filters(), context.compile()
                 .arg("pip")
                 .arg("compile")
                 .arg(requirements_in.path())
@@ -2600,7 +3011,7 @@ fn compile_html() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("jinja2<=3.1.2")?;
 
-    uv_snapshot!(Command::new(get_bin())
+    uv_snapshot!(compile_filters(), Command::new(get_bin())
             .arg("pip")
             .arg("compile")
             .arg("requirements.in")
@@ -2634,7 +3045,7 @@ fn trailing_slash() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("jinja2")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in")
             .arg("--index-url")
             .arg("https://test.pypi.org/simple"), @r###"
@@ -2652,7 +3063,7 @@ fn trailing_slash() -> Result<()> {
     "###
     );
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in")
             .arg("--index-url")
             .arg("https://test.pypi.org/simple/"), @r###"
@@ -2680,7 +3091,7 @@ fn compile_legacy_sdist_pep_517() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("flake8 @ https://files.pythonhosted.org/packages/66/53/3ad4a3b74d609b3b9008a10075c40e7c8909eae60af53623c3888f7a529a/flake8-6.0.0.tar.gz")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in"), @r###"
     success: true
     exit_code: 0
@@ -2710,7 +3121,7 @@ fn compile_legacy_sdist_setuptools() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("flake8 @ https://files.pythonhosted.org/packages/66/53/3ad4a3b74d609b3b9008a10075c40e7c8909eae60af53623c3888f7a529a/flake8-6.0.0.tar.gz")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in")
             .arg("--legacy-setup-py"), @r###"
     success: true
@@ -2757,7 +3168,7 @@ fn generate_hashes() -> Result<()> {
         vec![]
     }
     .into_iter()
-    .chain(INSTA_FILTERS.to_vec())
+    .chain(compile_filters())
     .collect();
 
     uv_snapshot!(filters, context.compile()
@@ -2886,7 +3297,7 @@ fn find_links_directory() -> Result<()> {
         ),
     ]
     .into_iter()
-    .chain(INSTA_FILTERS.to_vec())
+    .chain(compile_filters())
     .collect();
 
     uv_snapshot!(filters, context.compile()
@@ -2911,6 +3322,51 @@ fn find_links_directory() -> Result<()> {
     Ok(())
 }
 
+/// Compile using `--find-links` with a local HTML file that links to an archive alongside it.
+#[test]
+fn find_links_html_file() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("tqdm")?;
+
+    let project_root = fs_err::canonicalize(std::env::current_dir()?.join("..").join(".."))?;
+    let links = context.temp_dir.child("links");
+    links.create_dir_all()?;
+    fs_err::copy(
+        project_root
+            .join("scripts")
+            .join("wheels")
+            .join("tqdm-1000.0.0-py3-none-any.whl"),
+        links.child("tqdm-1000.0.0-py3-none-any.whl"),
+    )?;
+    links.child("index.html").write_str(indoc! {r#"
+        <html>
+          <body>
+            <a href="tqdm-1000.0.0-py3-none-any.whl">tqdm-1000.0.0-py3-none-any.whl</a>
+          </body>
+        </html>
+    "#})?;
+
+    uv_snapshot!(compile_filters(), context.compile()
+            .arg("requirements.in")
+            .arg("--no-index")
+            .arg("--find-links")
+            .arg("links/index.html"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --no-index
+    tqdm==1000.0.0
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
 /// Compile using `--find-links` with a URL by resolving `tqdm` from the `PyTorch` wheels index.
 #[test]
 fn find_links_url() -> Result<()> {
@@ -2918,7 +3374,7 @@ fn find_links_url() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("tqdm")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in")
             .arg("--no-index")
             .arg("--find-links")
@@ -2946,7 +3402,7 @@ fn find_links_requirements_txt() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("-f https://download.pytorch.org/whl/torch_stable.html\ntqdm")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in")
             .arg("--no-index")
             .arg("--emit-find-links"), @r###"
@@ -2990,7 +3446,7 @@ fn upgrade_none() -> Result<()> {
             # via black
     "})?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
         .arg("requirements.in")
         .arg("--output-file")
         .arg("requirements.txt"), @r###"
@@ -3044,7 +3500,7 @@ fn upgrade_all() -> Result<()> {
             # via black
     "})?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
         .arg("requirements.in")
         .arg("--output-file")
         .arg("requirements.txt")
@@ -3099,7 +3555,7 @@ fn upgrade_package() -> Result<()> {
             # via black
     "})?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in")
             .arg("--output-file")
             .arg("requirements.txt")
@@ -3143,7 +3599,7 @@ fn missing_path_requirement() -> Result<()> {
 
     let filters: Vec<_> = [(r"/C:/", "/")]
         .into_iter()
-        .chain(INSTA_FILTERS.to_vec())
+        .chain(compile_filters())
         .collect();
 
     uv_snapshot!(filters, context.compile()
@@ -3173,7 +3629,7 @@ fn missing_editable_requirement() -> Result<()> {
         (r" [A-Z]:\\.*\\", " /[TEMP_DIR]/"),
     ]
     .into_iter()
-    .chain(INSTA_FILTERS.to_vec())
+    .chain(compile_filters())
     .collect::<Vec<_>>();
 
     uv_snapshot!(filters, context.compile()
@@ -3198,7 +3654,7 @@ fn missing_package_name() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("https://files.pythonhosted.org/packages/36/42/015c23096649b908c809c69388a805a571a3bea44362fe87e33fc3afa01f/flask-3.0.0-py3-none-any.whl")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in"), @r###"
     success: false
     exit_code: 2
@@ -3222,7 +3678,7 @@ fn no_annotate() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("black==23.10.1")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in")
             .arg("--no-annotate"), @r###"
     success: true
@@ -3252,7 +3708,7 @@ fn no_header() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("black==23.10.1")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in")
             .arg("--no-header"), @r###"
     success: true
@@ -3285,7 +3741,7 @@ fn allow_unsafe() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("werkzeug==3.0.1")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in")
             .arg("--allow-unsafe"), @r###"
     success: true
@@ -3313,7 +3769,7 @@ fn resolver_legacy() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("werkzeug==3.0.1")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in")
             .arg("--resolver=legacy"), @r###"
     success: false
@@ -3336,7 +3792,7 @@ fn emit_index_urls() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("black==23.10.1")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in")
             .arg("--emit-index-url")
             .arg("--index-url")
@@ -3378,7 +3834,7 @@ fn emit_find_links() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("black==23.10.1")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in")
             .arg("--emit-find-links")
             .arg("--find-links")
@@ -3417,7 +3873,7 @@ fn no_index_requirements_txt() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("--no-index\ntqdm")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in"), @r###"
     success: false
     exit_code: 1
@@ -3446,7 +3902,7 @@ fn index_url_requirements_txt() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("--index-url https://google.com\ntqdm")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in")
             .arg("--index-url")
             .arg("https://pypi.org/simple"), @r###"
@@ -3475,7 +3931,7 @@ fn conflicting_index_urls_requirements_txt() -> Result<()> {
     let constraints_in = context.temp_dir.child("constraints.in");
     constraints_in.write_str("--index-url https://wikipedia.org\nflask")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in")
             .arg("--constraint")
             .arg("constraints.in"), @r###"
@@ -3499,7 +3955,7 @@ fn offline() -> Result<()> {
     requirements_in.write_str("black==23.10.1")?;
 
     // Resolve with `--offline` with an empty cache.
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in")
             .arg("--offline"), @r###"
     success: false
@@ -3516,7 +3972,7 @@ fn offline() -> Result<()> {
     );
 
     // Populate the cache.
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in"), @r###"
     success: true
     exit_code: 0
@@ -3541,7 +3997,7 @@ fn offline() -> Result<()> {
     );
 
     // Resolve with `--offline` with a populated cache.
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in")
             .arg("--offline"), @r###"
     success: true
@@ -3580,7 +4036,7 @@ fn offline_find_links() -> Result<()> {
     // Resolve with `--offline` and `--find-links`. We indicate that the network was disabled,
     // since both the `--find-links` and the registry lookups fail (but, importantly, we don't error
     // when failing to fetch the `--find-links` URL).
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in")
             .arg("--find-links")
             .arg("https://download.pytorch.org/whl/torch_stable.html")
@@ -3599,7 +4055,7 @@ fn offline_find_links() -> Result<()> {
     );
 
     // Resolve with `--offline`, `--find-links`, and `--no-index`.
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in")
             .arg("--find-links")
             .arg("https://download.pytorch.org/whl/torch_stable.html")
@@ -3635,7 +4091,7 @@ fn compile_relative_subfile() -> Result<()> {
     let requirements_dev_in = subdir.child("requirements-dev.in");
     requirements_dev_in.write_str("anyio")?;
 
-    uv_snapshot!(context
+    uv_snapshot!(compile_filters(), context
         .compile()
         .arg("requirements.in"), @r###"
     success: true
@@ -3663,7 +4119,7 @@ fn compile_none_extra() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("entrypoints==0.3")?;
 
-    uv_snapshot!(context
+    uv_snapshot!(compile_filters(), context
         .compile()
         .arg("requirements.in"), @r###"
     success: true
@@ -3692,7 +4148,7 @@ fn compile_types_pytz() -> Result<()> {
     let requirements_txt = context.temp_dir.child("requirements.txt");
     requirements_txt.write_str("types-pytz==2021.1")?;
 
-    uv_snapshot!(context
+    uv_snapshot!(compile_filters(), context
         .compile()
         .arg("requirements.in")
         .arg("-o")
@@ -3722,7 +4178,7 @@ fn compile_constraints_compatible_url() -> Result<()> {
     let constraints_txt = context.temp_dir.child("constraints.txt");
     constraints_txt.write_str("anyio @ https://files.pythonhosted.org/packages/bf/cd/d6d9bb1dadf73e7af02d18225cbd2c93f8552e13130484f1c8dcfece292b/anyio-4.2.0-py3-none-any.whl")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in")
             .arg("--constraint")
             .arg("constraints.txt"), @r###"
@@ -3756,7 +4212,7 @@ fn compile_constraints_compatible_url_version() -> Result<()> {
     let constraints_txt = context.temp_dir.child("constraints.txt");
     constraints_txt.write_str("anyio>4")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in")
             .arg("--constraint")
             .arg("constraints.txt"), @r###"
@@ -3790,7 +4246,7 @@ fn compile_constraints_incompatible_url() -> Result<()> {
     let constraints_txt = context.temp_dir.child("constraints.txt");
     constraints_txt.write_str("anyio @ https://files.pythonhosted.org/packages/bf/cd/d6d9bb1dadf73e7af02d18225cbd2c93f8552e13130484f1c8dcfece292b/anyio-4.2.0-py3-none-any.whl")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in")
             .arg("--constraint")
             .arg("constraints.txt"), @r###"
@@ -3802,6 +4258,12 @@ fn compile_constraints_incompatible_url() -> Result<()> {
       × No solution found when resolving dependencies:
       ╰─▶ Because only anyio>=4 is available and you require anyio<4, we can
           conclude that the requirements are unsatisfiable.
+
+          hint: The requirements involved were declared at:
+          - `anyio<4` (from requirements.in:1)
+          - `anyio @
+          https://files.pythonhosted.org/packages/bf/cd/d6d9bb1dadf73e7af02d18225cbd2c93f8552e13130484f1c8dcfece292b/anyio-4.2.0-py3-none-any.whl`
+          (constraint from constraints.txt:1)
     "###
     );
 
@@ -3816,7 +4278,7 @@ fn index_url_in_requirements() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("--index-url https://download.pytorch.org/whl\nanyio<4")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in"), @r###"
     success: false
     exit_code: 1
@@ -3840,7 +4302,7 @@ fn index_url_from_command_line() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("--index-url https://download.pytorch.org/whl\nanyio<4")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in")
             .arg("--index-url")
             .arg("https://pypi.org/simple"), @r###"
@@ -3871,7 +4333,7 @@ fn unsupported_scheme() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("anyio @ bzr+https://example.com/anyio")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in"), @r###"
     success: false
     exit_code: 2
@@ -3892,7 +4354,7 @@ fn no_deps_valid_extra() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("flask[dotenv]")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in")
             .arg("--no-deps"), @r###"
     success: true
@@ -3917,7 +4379,7 @@ fn no_deps_invalid_extra() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("flask[empty]")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in")
             .arg("--no-deps"), @r###"
     success: true
@@ -3936,6 +4398,51 @@ fn no_deps_invalid_extra() -> Result<()> {
     Ok(())
 }
 
+/// Resolve a package requested both with and without an extra, which should be merged into a
+/// single pin. With `--no-strip-extras`, the activated extras are included in the output.
+#[test]
+fn no_strip_extras() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str(indoc! {r"
+        flask
+        flask[dotenv]
+    "})?;
+
+    uv_snapshot!(compile_filters(), context.compile()
+            .arg("requirements.in")
+            .arg("--no-deps"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --no-deps
+    flask==3.0.0
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    uv_snapshot!(compile_filters(), context.compile()
+            .arg("requirements.in")
+            .arg("--no-deps")
+            .arg("--no-strip-extras"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --no-deps --no-strip-extras
+    flask[dotenv]==3.0.0
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
 /// Resolve an editable package with an invalid extra.
 #[test]
 fn editable_invalid_extra() -> Result<()> {
@@ -3949,7 +4456,7 @@ fn editable_invalid_extra() -> Result<()> {
         (requirements_path.as_str(), "requirements.in"),
     ]
     .into_iter()
-    .chain(INSTA_FILTERS.to_vec())
+    .chain(compile_filters())
     .collect();
 
     uv_snapshot!(filters, Command::new(get_bin())
@@ -3988,7 +4495,7 @@ fn compile_constraints_compatible_version() -> Result<()> {
     let constraints_txt = context.temp_dir.child("constraints.txt");
     constraints_txt.write_str("filelock==3.8.0")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in")
             .arg("--constraint")
             .arg("constraints.txt"), @r###"
@@ -4024,7 +4531,7 @@ fn compile_constraints_incompatible_version() -> Result<()> {
     let constraints_txt = context.temp_dir.child("constraints.txt");
     constraints_txt.write_str("filelock==3.8.0")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in")
             .arg("--constraint")
             .arg("constraints.txt"), @r###"
@@ -4036,6 +4543,10 @@ fn compile_constraints_incompatible_version() -> Result<()> {
       × No solution found when resolving dependencies:
       ╰─▶ Because you require filelock==1.0.0 and you require filelock==3.8.0, we
           can conclude that the requirements are unsatisfiable.
+
+          hint: The requirements involved were declared at:
+          - `filelock==1.0.0` (from requirements.in:1)
+          - `filelock==3.8.0` (constraint from constraints.txt:1)
     "###
     );
 
@@ -4053,7 +4564,7 @@ fn conflicting_url_markers() -> Result<()> {
     let constraints_txt = context.temp_dir.child("constraints.txt");
     constraints_txt.write_str("filelock==3.8.0")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in")
             .arg("--constraint")
             .arg("constraints.txt"), @r###"
@@ -4065,6 +4576,10 @@ fn conflicting_url_markers() -> Result<()> {
       × No solution found when resolving dependencies:
       ╰─▶ Because you require filelock==1.0.0 and you require filelock==3.8.0, we
           can conclude that the requirements are unsatisfiable.
+
+          hint: The requirements involved were declared at:
+          - `filelock==1.0.0` (from requirements.in:1)
+          - `filelock==3.8.0` (constraint from constraints.txt:1)
     "###
     );
 
@@ -4086,7 +4601,7 @@ fn editable_override() -> Result<()> {
     let overrides_txt = context.temp_dir.child("overrides.txt");
     overrides_txt.write_str("-e file://../../scripts/editable-installs/black_editable")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in")
             .arg("--override")
             .arg("overrides.txt"), @r###"
@@ -4134,7 +4649,7 @@ fn override_editable() -> Result<()> {
         (overrides_path.as_str(), "overrides.txt"),
     ]
     .into_iter()
-    .chain(INSTA_FILTERS.to_vec())
+    .chain(compile_filters())
     .collect();
 
     uv_snapshot!(filters, Command::new(get_bin())
@@ -4177,7 +4692,7 @@ fn override_with_compatible_constraint() -> Result<()> {
     let overrides_txt = context.temp_dir.child("overrides.txt");
     overrides_txt.write_str("anyio>=3.0.0")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in")
             .arg("--constraint")
             .arg("constraints.txt")
@@ -4216,7 +4731,7 @@ fn override_with_incompatible_constraint() -> Result<()> {
     let overrides_txt = context.temp_dir.child("overrides.txt");
     overrides_txt.write_str("anyio>=3.0.0")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in")
             .arg("--constraint")
             .arg("constraints.txt")
@@ -4230,6 +4745,11 @@ fn override_with_incompatible_constraint() -> Result<()> {
       × No solution found when resolving dependencies:
       ╰─▶ Because you require anyio>=3.0.0 and you require anyio<3.0.0, we can
           conclude that the requirements are unsatisfiable.
+
+          hint: The requirements involved were declared at:
+          - `anyio` (from requirements.in:1)
+          - `anyio<3.0.0` (constraint from constraints.txt:1)
+          - `anyio>=3.0.0` (override from overrides.txt:1)
     "###
     );
 
@@ -4243,7 +4763,7 @@ fn unsafe_package() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("flask")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in")
             .arg("--unsafe-package")
             .arg("jinja2")
@@ -4287,7 +4807,7 @@ fn pre_release_upper_bound_exclude() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("flask<2.0.0")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in")
             .arg("--prerelease=allow"), @r###"
     success: true
@@ -4323,7 +4843,7 @@ fn pre_release_upper_bound_include() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("flask<2.0.0rc4")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in")
             .arg("--prerelease=allow"), @r###"
     success: true
@@ -4360,7 +4880,7 @@ fn pre_alias() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("flask<2.0.0")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in")
             .arg("--pre"), @r###"
     success: true
@@ -4398,7 +4918,7 @@ fn pre_release_constraint() -> Result<()> {
     let constraints_txt = context.temp_dir.child("constraints.txt");
     constraints_txt.write_str("flask<=2.0.0rc2")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in")
             .arg("--constraint")
             .arg("constraints.txt"), @r###"
@@ -4454,7 +4974,7 @@ dev = [
 "#,
     )?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("pyproject.toml")
             .arg("--extra")
             .arg("dev"), @r###"
@@ -4487,7 +5007,7 @@ fn editable_direct_dependency() -> Result<()> {
         (requirements_path.as_str(), "requirements.in"),
     ]
     .into_iter()
-    .chain(INSTA_FILTERS.to_vec())
+    .chain(compile_filters())
     .collect();
 
     uv_snapshot!(filters, Command::new(get_bin())
@@ -4525,7 +5045,7 @@ fn empty_index_url_env_var() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("anyio")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in")
             .arg("--emit-index-url")
             .env("UV_INDEX_URL", ""), @r###"
@@ -4557,7 +5077,7 @@ fn empty_extra_index_url_env_var() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("anyio")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in")
             .arg("--emit-index-url")
             .env("EXTRA_UV_INDEX_URL", ""), @r###"
@@ -4590,7 +5110,7 @@ fn empty_index_url_env_var_override() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("--index-url https://test.pypi.org/simple\nidna")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in")
             .arg("--emit-index-url")
             .env("UV_INDEX_URL", ""), @r###"
@@ -4618,7 +5138,7 @@ fn index_url_env_var_override() -> Result<()> {
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("--index-url https://pypi.org/simple\nidna")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in")
             .arg("--emit-index-url")
             .env("UV_INDEX_URL", "https://test.pypi.org/simple"), @r###"
@@ -4650,7 +5170,7 @@ fn expand_env_var_requirements_txt() -> Result<()> {
     let requirements_dev_in = context.temp_dir.child("requirements-dev.in");
     requirements_dev_in.write_str("anyio")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(compile_filters(), context.compile()
             .arg("requirements.in"), @r###"
     success: true
     exit_code: 0
@@ -4670,3 +5190,306 @@ fn expand_env_var_requirements_txt() -> Result<()> {
 
     Ok(())
 }
+
+/// Record the digest of each input file in the header.
+#[test]
+fn compile_header_provenance() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("anyio==3.7.0")?;
+
+    uv_snapshot!(context.compile()
+        .arg("requirements.in"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
+    # input-hash: requirements.in sha256:fc63225074011c35efe32d137a975da4b84d55b05afdc35a28a68c06e15dd57a
+    anyio==3.7.0
+    idna==3.4
+        # via anyio
+    sniffio==1.3.0
+        # via anyio
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Display a custom command in the header, in lieu of the actual invocation.
+#[test]
+fn compile_custom_header() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("anyio==3.7.0")?;
+
+    uv_snapshot!(compile_filters(), context.compile()
+        .arg("requirements.in")
+        .arg("--custom-header")
+        .arg("make requirements.txt"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    make requirements.txt
+    anyio==3.7.0
+    idna==3.4
+        # via anyio
+    sniffio==1.3.0
+        # via anyio
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Compile a development layer on top of a production layer, such that the development pins
+/// agree with the production pins.
+#[test]
+fn compile_layered() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("werkzeug<3")?;
+    let dev_requirements_in = context.temp_dir.child("dev-requirements.in");
+    dev_requirements_in.write_str("werkzeug")?;
+
+    context
+        .compile()
+        .arg("--layered")
+        .arg("requirements.in")
+        .arg("dev-requirements.in")
+        .assert()
+        .success();
+
+    // Unconstrained, the development layer would resolve to `werkzeug==3.0.1`.
+    let requirements_txt = fs_err::read_to_string(context.temp_dir.child("requirements.txt"))?;
+    assert!(requirements_txt
+        .lines()
+        .any(|line| line == "werkzeug==2.3.8"));
+    let dev_requirements_txt =
+        fs_err::read_to_string(context.temp_dir.child("dev-requirements.txt"))?;
+    assert!(dev_requirements_txt
+        .lines()
+        .any(|line| line == "werkzeug==2.3.8"));
+
+    Ok(())
+}
+
+/// Each layer must be an `.in` file, to avoid overwriting the input with the output.
+#[test]
+fn compile_layered_txt_input() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("werkzeug")?;
+
+    uv_snapshot!(compile_filters(), context.compile()
+            .arg("--layered")
+            .arg("requirements.txt"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: `--layered` requires `.in` input files, but found: requirements.txt
+    "###
+    );
+
+    Ok(())
+}
+
+/// Verify an output file against its inputs with `--check`, without rewriting it.
+#[test]
+fn compile_check() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("markupsafe==2.1.3")?;
+
+    context
+        .compile()
+        .arg("requirements.in")
+        .arg("--output-file")
+        .arg("requirements.txt")
+        .assert()
+        .success();
+
+    uv_snapshot!(compile_filters(), context.compile()
+            .arg("requirements.in")
+            .arg("--output-file")
+            .arg("requirements.txt")
+            .arg("--check"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    requirements.txt is up to date
+    "###
+    );
+
+    // Changing the inputs invalidates the output file.
+    requirements_in.write_str("markupsafe==2.1.2")?;
+    let before = fs_err::read_to_string(context.temp_dir.child("requirements.txt"))?;
+
+    uv_snapshot!(compile_filters(), context.compile()
+            .arg("requirements.in")
+            .arg("--output-file")
+            .arg("requirements.txt")
+            .arg("--check"), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    requirements.txt is out of date with respect to its inputs; run without `--check` to update it
+    "###
+    );
+
+    let after = fs_err::read_to_string(context.temp_dir.child("requirements.txt"))?;
+    assert_eq!(before, after);
+
+    Ok(())
+}
+
+/// Reuse a cached resolution when the inputs are unchanged, and bypass it with `--upgrade`.
+#[test]
+fn compile_cached_resolution() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("markupsafe==2.1.3")?;
+
+    context.compile().arg("requirements.in").assert().success();
+
+    // Tamper with the cached resolution, to observe that it's reused.
+    let entries = fs_err::read_dir(context.cache_dir.join("resolutions-v0"))?
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(entries.len(), 1);
+    let entry = entries[0].path();
+    let mut cached: serde_json::Value = serde_json::from_str(&fs_err::read_to_string(&entry)?)?;
+    cached["requirements"] = "markupsafe==0.0.0\n".into();
+    fs_err::write(&entry, serde_json::to_string(&cached)?)?;
+
+    uv_snapshot!(compile_filters(), context.compile()
+            .arg("requirements.in"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
+    markupsafe==0.0.0
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    uv_snapshot!(compile_filters(), context.compile()
+            .arg("requirements.in")
+            .arg("--upgrade"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
+    markupsafe==2.1.3
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Don't cache offline resolutions without `--exclude-newer`, since the versions available in the
+/// cache can change between runs.
+#[test]
+fn compile_offline_not_cached() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("markupsafe")?;
+
+    let compile = || {
+        let mut command = Command::new(get_bin());
+        command
+            .arg("pip")
+            .arg("compile")
+            .arg("requirements.in")
+            .arg("--cache-dir")
+            .arg(context.cache_dir.path())
+            .env("VIRTUAL_ENV", context.venv.as_os_str())
+            .current_dir(context.temp_dir.path());
+        command
+    };
+
+    // Populate the cache, then resolve offline.
+    compile().assert().success();
+    compile().arg("--offline").assert().success();
+    assert!(!context.cache_dir.join("resolutions-v0").exists());
+
+    // Remove the cached index response, such that no versions are available offline.
+    fs_err::remove_file(
+        context
+            .cache_dir
+            .join("simple-v3")
+            .join("pypi")
+            .join("markupsafe.rkyv"),
+    )?;
+
+    uv_snapshot!(compile_filters(), compile()
+            .arg("--offline"), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+      × No solution found when resolving dependencies:
+      ╰─▶ Because markupsafe was not found in the cache and you require markupsafe, we can
+          conclude that the requirements are unsatisfiable.
+
+          hint: Packages were unavailable because the network was disabled
+    "###
+    );
+
+    Ok(())
+}
+
+/// Abort a resolution that exceeds `--resolution-timeout` while waiting on an index that never
+/// responds.
+#[test]
+fn compile_resolution_timeout() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("markupsafe")?;
+
+    // Accept connections (via the listen backlog), but never respond to them.
+    let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+    let index_url = format!("http://{}/simple", listener.local_addr()?);
+
+    uv_snapshot!(compile_filters(), context.compile()
+            .arg("requirements.in")
+            .arg("--index-url")
+            .arg(&index_url)
+            .arg("--resolution-timeout")
+            .arg("1"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Resolution did not complete within 1s
+    "###
+    );
+
+    drop(listener);
+
+    Ok(())
+}
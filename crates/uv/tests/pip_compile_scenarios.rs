@@ -13,7 +13,7 @@ use assert_cmd::assert::OutputAssertExt;
 use assert_fs::fixture::{FileWriteStr, PathChild};
 use predicates::prelude::predicate;
 
-use common::{create_bin_with_executables, get_bin, uv_snapshot, TestContext, INSTA_FILTERS};
+use common::{compile_filters, create_bin_with_executables, get_bin, uv_snapshot, TestContext};
 
 mod common;
 
@@ -69,7 +69,7 @@ fn requires_incompatible_python_version_compatible_override() -> Result<()> {
     let python_versions = &[];
 
     // In addition to the standard filters, swap out package names for more realistic messages
-    let mut filters = INSTA_FILTERS.to_vec();
+    let mut filters = compile_filters();
     filters.push((r"a-3f4ac9b2", "albatross"));
     filters.push((r"-3f4ac9b2", ""));
 
@@ -122,7 +122,7 @@ fn requires_compatible_python_version_incompatible_override() -> Result<()> {
     let python_versions = &[];
 
     // In addition to the standard filters, swap out package names for more realistic messages
-    let mut filters = INSTA_FILTERS.to_vec();
+    let mut filters = compile_filters();
     filters.push((r"a-fd6db412", "albatross"));
     filters.push((r"-fd6db412", ""));
 
@@ -172,7 +172,7 @@ fn requires_incompatible_python_version_compatible_override_no_wheels() -> Resul
     let python_versions = &[];
 
     // In addition to the standard filters, swap out package names for more realistic messages
-    let mut filters = INSTA_FILTERS.to_vec();
+    let mut filters = compile_filters();
     filters.push((r"a-3521037f", "albatross"));
     filters.push((r"-3521037f", ""));
 
@@ -228,7 +228,7 @@ fn requires_incompatible_python_version_compatible_override_no_wheels_available_
     let python_versions = &["3.11"];
 
     // In addition to the standard filters, swap out package names for more realistic messages
-    let mut filters = INSTA_FILTERS.to_vec();
+    let mut filters = compile_filters();
     filters.push((r"a-c68bcf5c", "albatross"));
     filters.push((r"-c68bcf5c", ""));
 
@@ -283,7 +283,7 @@ fn requires_incompatible_python_version_compatible_override_no_compatible_wheels
     let python_versions = &[];
 
     // In addition to the standard filters, swap out package names for more realistic messages
-    let mut filters = INSTA_FILTERS.to_vec();
+    let mut filters = compile_filters();
     filters.push((r"a-d7b25a2d", "albatross"));
     filters.push((r"-d7b25a2d", ""));
 
@@ -340,7 +340,7 @@ fn requires_incompatible_python_version_compatible_override_other_wheel() -> Res
     let python_versions = &[];
 
     // In addition to the standard filters, swap out package names for more realistic messages
-    let mut filters = INSTA_FILTERS.to_vec();
+    let mut filters = compile_filters();
     filters.push((r"a-a9179f0c", "albatross"));
     filters.push((r"-a9179f0c", ""));
 
@@ -400,7 +400,7 @@ fn requires_python_patch_version_override_no_patch() -> Result<()> {
     let python_versions = &[];
 
     // In addition to the standard filters, swap out package names for more realistic messages
-    let mut filters = INSTA_FILTERS.to_vec();
+    let mut filters = compile_filters();
     filters.push((r"a-e1884826", "albatross"));
     filters.push((r"-e1884826", ""));
 
@@ -450,7 +450,7 @@ fn requires_python_patch_version_override_patch_compatible() -> Result<()> {
     let python_versions = &[];
 
     // In addition to the standard filters, swap out package names for more realistic messages
-    let mut filters = INSTA_FILTERS.to_vec();
+    let mut filters = compile_filters();
     filters.push((r"a-91b4bcfc", "albatross"));
     filters.push((r"-91b4bcfc", ""));
 
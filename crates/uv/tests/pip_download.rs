@@ -0,0 +1,162 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::process::Command;
+
+use anyhow::Result;
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+
+use crate::common::{get_bin, TestContext};
+
+mod common;
+
+/// Create a `pip download` command with options shared across scenarios.
+fn command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("pip")
+        .arg("download")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .arg("--exclude-newer")
+        .arg("2024-03-01T00:00:00Z")
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .current_dir(&context.temp_dir);
+    command
+}
+
+/// Download a package and its dependencies into a directory.
+#[test]
+fn download_dependencies() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    command(&context)
+        .arg("anyio==4.0.0")
+        .arg("--dest")
+        .arg("wheels")
+        .assert()
+        .success();
+
+    let wheels = context.temp_dir.child("wheels");
+    wheels
+        .child("anyio-4.0.0-py3-none-any.whl")
+        .assert(predicates::path::is_file());
+    wheels
+        .child("idna-3.6-py3-none-any.whl")
+        .assert(predicates::path::is_file());
+    wheels
+        .child("sniffio-1.3.1-py3-none-any.whl")
+        .assert(predicates::path::is_file());
+
+    // Nothing should be installed into the environment.
+    context.assert_command("import anyio").failure();
+
+    Ok(())
+}
+
+/// Download the wheels for a foreign platform.
+#[test]
+fn download_python_platform() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    command(&context)
+        .arg("markupsafe==2.1.3")
+        .arg("--python-platform")
+        .arg("windows")
+        .arg("--only-binary")
+        .arg(":all:")
+        .arg("-d")
+        .arg("wheels")
+        .assert()
+        .success();
+
+    context
+        .temp_dir
+        .child("wheels")
+        .child("MarkupSafe-2.1.3-cp312-cp312-win_amd64.whl")
+        .assert(predicates::path::is_file());
+
+    Ok(())
+}
+
+/// Editable requirements can't be downloaded.
+#[test]
+fn download_editable() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("-e ./editable")?;
+
+    command(&context)
+        .arg("-r")
+        .arg("requirements.txt")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "Editable requirements can't be downloaded",
+        ));
+
+    Ok(())
+}
+
+/// Archives are reused from the cache, and existing archives that don't match the expected hash
+/// are replaced.
+#[test]
+fn download_cached() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    command(&context)
+        .arg("iniconfig==2.0.0")
+        .arg("--dest")
+        .arg("wheels")
+        .assert()
+        .success();
+
+    // Corrupt the downloaded archive.
+    let wheel = context
+        .temp_dir
+        .child("wheels")
+        .child("iniconfig-2.0.0-py3-none-any.whl");
+    wheel.write_str("corrupted")?;
+
+    // Downloading again, offline, should restore the archive from the cache.
+    command(&context)
+        .arg("iniconfig==2.0.0")
+        .arg("--dest")
+        .arg("wheels")
+        .arg("--offline")
+        .assert()
+        .success();
+    assert!(fs_err::read(wheel.path())?.starts_with(b"PK"));
+
+    Ok(())
+}
+
+/// Archives that don't match the hash database are rejected.
+#[test]
+fn download_hash_database_mismatch() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let hashes_txt = context.temp_dir.child("hashes.txt");
+    hashes_txt.write_str(
+        "iniconfig==2.0.0 --hash=sha256:0000000000000000000000000000000000000000000000000000000000000000",
+    )?;
+
+    command(&context)
+        .arg("iniconfig==2.0.0")
+        .arg("--dest")
+        .arg("wheels")
+        .arg("--hash-database")
+        .arg("hashes.txt")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("Hash mismatch"));
+
+    context
+        .temp_dir
+        .child("wheels")
+        .child("iniconfig-2.0.0-py3-none-any.whl")
+        .assert(predicates::path::missing());
+
+    Ok(())
+}
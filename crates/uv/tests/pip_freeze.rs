@@ -5,8 +5,9 @@ use std::process::Command;
 use anyhow::Result;
 use assert_cmd::prelude::*;
 use assert_fs::prelude::*;
+use url::Url;
 
-use crate::common::{get_bin, uv_snapshot, TestContext};
+use crate::common::{get_bin, uv_snapshot, TestContext, EXCLUDE_NEWER, INSTA_FILTERS};
 
 mod common;
 
@@ -62,7 +63,7 @@ fn freeze_many() -> Result<()> {
 #[test]
 #[cfg(unix)]
 fn freeze_duplicate() -> Result<()> {
-    use crate::common::{copy_dir_all, INSTA_FILTERS};
+    use crate::common::copy_dir_all;
 
     // Sync a version of `pip` into a virtual environment.
     let context1 = TestContext::new("3.12");
@@ -122,7 +123,7 @@ fn freeze_duplicate() -> Result<()> {
         .copied()
         .collect::<Vec<_>>();
 
-    uv_snapshot!(filters, command(&context1).arg("--strict"), @r###"
+    uv_snapshot!(filters, command(&context1).arg("--strict").arg("--all"), @r###"
     success: true
     exit_code: 0
     ----- stdout -----
@@ -175,3 +176,130 @@ fn freeze_url() -> Result<()> {
 
     Ok(())
 }
+
+/// List an editable package in a virtual environment, with and without `--exclude-editable`.
+#[test]
+fn freeze_editable() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let current_dir = std::env::current_dir()?;
+    let workspace_dir = regex::escape(
+        Url::from_directory_path(current_dir.join("..").join("..").canonicalize()?)
+            .unwrap()
+            .as_str(),
+    );
+
+    let filters = [(workspace_dir.as_str(), "file://[WORKSPACE_DIR]/")]
+        .into_iter()
+        .chain(INSTA_FILTERS.to_vec())
+        .collect::<Vec<_>>();
+
+    // Install the editable package.
+    Command::new(get_bin())
+        .arg("pip")
+        .arg("install")
+        .arg("-e")
+        .arg("../../scripts/editable-installs/poetry_editable")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .env(
+            "CARGO_TARGET_DIR",
+            "../../../target/target_install_editable",
+        )
+        .assert()
+        .success();
+
+    // Run `pip freeze`.
+    uv_snapshot!(filters, command(&context), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    numpy==1.26.2
+    -e file://[WORKSPACE_DIR]/scripts/editable-installs/poetry_editable
+
+    ----- stderr -----
+    "###
+    );
+
+    // Run `pip freeze --exclude-editable`.
+    uv_snapshot!(filters, command(&context).arg("--exclude-editable"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    numpy==1.26.2
+
+    ----- stderr -----
+    "###
+    );
+
+    Ok(())
+}
+
+/// Exclude packages that match a pattern with `--exclude`.
+#[test]
+fn freeze_exclude() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("MarkupSafe==2.1.3\ntomli==2.0.1")?;
+
+    // Run `pip sync`.
+    Command::new(get_bin())
+        .arg("pip")
+        .arg("sync")
+        .arg(requirements_txt.path())
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .assert()
+        .success();
+
+    // Run `pip freeze --exclude`, with a pattern that's normalized before matching.
+    uv_snapshot!(command(&context)
+        .arg("--exclude")
+        .arg("Markup_*"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    tomli==2.0.1
+
+    ----- stderr -----
+    "###
+    );
+
+    Ok(())
+}
+
+/// Omit packages that were installed as dependencies with `--not-required`.
+#[test]
+fn freeze_not_required() {
+    let context = TestContext::new("3.12");
+
+    // Install `anyio`, which depends on `idna` and `sniffio`.
+    Command::new(get_bin())
+        .arg("pip")
+        .arg("install")
+        .arg("anyio==4.0.0")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .assert()
+        .success();
+
+    // Run `pip freeze --not-required`.
+    uv_snapshot!(command(&context)
+        .arg("--not-required"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    anyio==4.0.0
+
+    ----- stderr -----
+    "###
+    );
+}
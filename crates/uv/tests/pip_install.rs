@@ -8,6 +8,7 @@ use assert_fs::prelude::*;
 use base64::{prelude::BASE64_STANDARD as base64, Engine};
 use indoc::indoc;
 use itertools::Itertools;
+use predicates::prelude::PredicateBooleanExt;
 use url::Url;
 
 use common::{uv_snapshot, TestContext, EXCLUDE_NEWER, INSTA_FILTERS};
@@ -139,6 +140,31 @@ fn no_solution() {
     "###);
 }
 
+/// Request a version of a package that conflicts with a constraint, which should fail before
+/// resolving.
+#[test]
+fn conflicting_pin_and_constraint() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let constraints_txt = context.temp_dir.child("constraints.txt");
+    constraints_txt.write_str("werkzeug==3.0.0")?;
+
+    uv_snapshot!(command(&context)
+        .arg("werkzeug==2.0.0")
+        .arg("--constraint")
+        .arg("constraints.txt"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Conflicting requirements for `werkzeug`:
+      `werkzeug==2.0.0` (from the command line)
+      `werkzeug==3.0.0` (constraint from constraints.txt:1)
+    "###);
+
+    Ok(())
+}
+
 /// Install a package from the command line into a virtual environment.
 #[test]
 fn install_package() {
@@ -587,6 +613,259 @@ fn install_editable() -> Result<()> {
     Ok(())
 }
 
+/// Install a local project directory, without a package name and without `-e`.
+#[test]
+fn install_local_directory() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("example/pyproject.toml");
+    pyproject_toml.write_str(indoc::indoc! {r#"
+        [project]
+        name = "example"
+        version = "0.1.0"
+        dependencies = []
+
+        [build-system]
+        requires = ["flit_core>=3.4,<4"]
+        build-backend = "flit_core.buildapi"
+    "#})?;
+    context
+        .temp_dir
+        .child("example/example/__init__.py")
+        .write_str("\"\"\"An example project.\"\"\"\n__version__ = \"0.1.0\"\n")?;
+
+    let filters: Vec<_> = [(r"file://.*/", "file://[TEMP_DIR]/")]
+        .into_iter()
+        .chain(INSTA_FILTERS.to_vec())
+        .collect();
+
+    uv_snapshot!(filters, command(&context)
+        .arg("./example")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + example==0.1.0 (from file://[TEMP_DIR]/example)
+    "###
+    );
+
+    context.assert_installed("example", "0.1.0");
+
+    Ok(())
+}
+
+/// Install a local project directory whose package name can only be determined by building its
+/// metadata (here, a `setup.py` without a `setup.cfg` or `pyproject.toml`).
+#[test]
+fn install_local_directory_setup_py() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    context
+        .temp_dir
+        .child("legacy/setup.py")
+        .write_str(indoc::indoc! {r#"
+            from setuptools import setup
+
+            setup(name="legacy", version="0.1.0", py_modules=[])
+        "#})?;
+
+    let filters: Vec<_> = [(r"file://.*/", "file://[TEMP_DIR]/")]
+        .into_iter()
+        .chain(INSTA_FILTERS.to_vec())
+        .collect();
+
+    uv_snapshot!(filters, command(&context)
+        .arg("./legacy")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + legacy==0.1.0 (from file://[TEMP_DIR]/legacy)
+    "###
+    );
+
+    context.assert_installed("legacy", "0.1.0");
+
+    Ok(())
+}
+
+/// Install a local project without build isolation, using the build backend that's already
+/// present in the environment.
+#[test]
+fn install_no_build_isolation() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("example/pyproject.toml");
+    pyproject_toml.write_str(indoc::indoc! {r#"
+        [project]
+        name = "example"
+        version = "0.1.0"
+        dependencies = []
+
+        [build-system]
+        requires = ["flit_core>=3.4,<4"]
+        build-backend = "flit_core.buildapi"
+    "#})?;
+    context
+        .temp_dir
+        .child("example/example/__init__.py")
+        .write_str("\"\"\"An example project.\"\"\"\n__version__ = \"0.1.0\"\n")?;
+
+    let filters: Vec<_> = [(r"file://.*/", "file://[TEMP_DIR]/")]
+        .into_iter()
+        .chain(INSTA_FILTERS.to_vec())
+        .collect();
+
+    // Install the build backend into the environment.
+    uv_snapshot!(command(&context)
+        .arg("flit_core==3.9.0"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + flit-core==3.9.0
+    "###
+    );
+
+    // Build the project in the environment itself.
+    uv_snapshot!(filters, command(&context)
+        .arg("./example")
+        .arg("--no-build-isolation")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + example==0.1.0 (from file://[TEMP_DIR]/example)
+    "###
+    );
+
+    context.assert_installed("example", "0.1.0");
+
+    Ok(())
+}
+
+/// Install a local project whose `setup.py` imports a package that isn't declared as a build
+/// requirement, by providing it with `--build-requirement`.
+#[test]
+fn install_build_requirement() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    context
+        .temp_dir
+        .child("example/pyproject.toml")
+        .write_str(indoc::indoc! {r#"
+            [project]
+            name = "example"
+            version = "0.1.0"
+            dependencies = []
+
+            [build-system]
+            requires = ["setuptools>=42"]
+            build-backend = "setuptools.build_meta"
+        "#})?;
+    context
+        .temp_dir
+        .child("example/setup.py")
+        .write_str(indoc::indoc! {r"
+            import iniconfig
+            from setuptools import setup
+
+            setup(py_modules=[])
+        "})?;
+
+    // The build fails without the undeclared build requirement.
+    command(&context).arg("./example").assert().failure();
+
+    let filters: Vec<_> = [(r"file://.*/", "file://[TEMP_DIR]/")]
+        .into_iter()
+        .chain(INSTA_FILTERS.to_vec())
+        .collect();
+
+    uv_snapshot!(filters, command(&context)
+        .arg("./example")
+        .arg("--build-requirement")
+        .arg("iniconfig==2.0.0"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + example==0.1.0 (from file://[TEMP_DIR]/example)
+    "###
+    );
+
+    context.assert_installed("example", "0.1.0");
+
+    // The build requirement is only installed into the build environment.
+    context.assert_command("import iniconfig").failure();
+
+    Ok(())
+}
+
+/// Apply a build constraint that conflicts with the project's build requirements.
+#[test]
+fn install_build_constraint_conflict() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("example/pyproject.toml");
+    pyproject_toml.write_str(indoc::indoc! {r#"
+        [project]
+        name = "example"
+        version = "0.1.0"
+        dependencies = []
+
+        [build-system]
+        requires = ["flit_core>=3.4,<4"]
+        build-backend = "flit_core.buildapi"
+    "#})?;
+    context
+        .temp_dir
+        .child("example/example/__init__.py")
+        .write_str("\"\"\"An example project.\"\"\"\n__version__ = \"0.1.0\"\n")?;
+
+    let constraints_txt = context.temp_dir.child("build-constraints.txt");
+    constraints_txt.write_str("flit_core<3.4")?;
+
+    command(&context)
+        .arg("./example")
+        .arg("--build-constraint")
+        .arg("build-constraints.txt")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "Failed to install requirements from build-system.requires (resolve)",
+        ));
+
+    // Without the build constraint, the project builds successfully.
+    command(&context).arg("./example").assert().success();
+
+    context.assert_installed("example", "0.1.0");
+
+    Ok(())
+}
+
 #[test]
 fn install_editable_and_registry() -> Result<()> {
     let context = TestContext::new("3.12");
@@ -1308,6 +1587,246 @@ fn install_executable_hardlink() {
     Command::new(executable).arg("--version").assert().success();
 }
 
+/// Install a package with a prefix and suffix applied to the names of its console scripts.
+#[test]
+#[cfg(unix)]
+fn install_script_prefix_suffix() {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(command(&context)
+        .arg("isort==5.12.0")
+        .arg("--script-prefix")
+        .arg("proj-")
+        .arg("--script-suffix")
+        .arg("-3.12"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + isort==5.12.0
+    "###
+    );
+
+    let bin = context.venv.join("bin");
+    assert!(!bin.join("isort").exists());
+    Command::new(bin.join("proj-isort-3.12"))
+        .arg("--version")
+        .assert()
+        .success();
+
+    // The renamed scripts are recorded, such that they're removed on uninstall.
+    let record = fs_err::read_to_string(
+        context
+            .venv
+            .join("lib/python3.12/site-packages/isort-5.12.0.dist-info/RECORD"),
+    )
+    .unwrap();
+    assert!(record.contains("bin/proj-isort-3.12,"));
+    assert!(record.contains("bin/proj-isort-identify-imports-3.12,"));
+}
+
+/// Script prefixes and suffixes can't contain path separators.
+#[test]
+fn install_script_prefix_separator() {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(command(&context)
+        .arg("isort==5.12.0")
+        .arg("--script-prefix")
+        .arg("../"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: invalid value '../' for '--script-prefix <SCRIPT_PREFIX>': must not contain a path separator
+
+    For more information, try '--help'.
+    "###
+    );
+}
+
+/// Installing into a shared environment requires that its directories are group-writable and
+/// setgid.
+#[test]
+#[cfg(unix)]
+fn install_shared() -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(context.filters(), command(&context)
+        .arg("iniconfig==2.0.0")
+        .arg("--shared"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: The directory `[VENV]` doesn't have the setgid bit set, so files created within it won't be owned by the environment's group (hint: run `find [VENV] -type d -exec chmod g+s {} +`)
+    "###
+    );
+
+    // Make every directory in the environment group-writable and setgid.
+    fn share(path: &std::path::Path) -> std::io::Result<()> {
+        let mode = fs_err::metadata(path)?.permissions().mode();
+        fs_err::set_permissions(path, std::fs::Permissions::from_mode(mode | 0o2070))?;
+        for entry in fs_err::read_dir(path)? {
+            let entry = entry?;
+            if entry.file_type()?.is_dir() {
+                share(&entry.path())?;
+            }
+        }
+        Ok(())
+    }
+    share(&context.venv)?;
+
+    // Run the install with a umask that preserves group write permissions, such that the result
+    // doesn't depend on the umask of the test runner.
+    let mut install = command(&context);
+    install.arg("iniconfig==2.0.0").arg("--shared");
+    let mut shell = Command::new("sh");
+    shell
+        .arg("-c")
+        .arg("umask 002 && exec \"$@\"")
+        .arg("sh")
+        .arg(install.get_program())
+        .args(install.get_args())
+        .current_dir(&context.temp_dir);
+    for (key, value) in install.get_envs() {
+        if let Some(value) = value {
+            shell.env(key, value);
+        } else {
+            shell.env_remove(key);
+        }
+    }
+    shell.assert().success();
+
+    context.assert_command("import iniconfig").success();
+
+    Ok(())
+}
+
+/// Install pure-Python packages as zip archives, excluding any packages passed to `--no-zip`.
+#[test]
+#[cfg(unix)]
+fn install_zip() {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(command(&context)
+        .arg("iniconfig==2.0.0")
+        .arg("tomli==2.0.1")
+        .arg("--link-mode")
+        .arg("zip")
+        .arg("--no-zip")
+        .arg("tomli"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 2 packages in [TIME]
+    Downloaded 2 packages in [TIME]
+    Installed 2 packages in [TIME]
+     + iniconfig==2.0.0
+     + tomli==2.0.1
+    "###
+    );
+
+    let site_packages = context.venv.join("lib/python3.12/site-packages");
+    assert!(site_packages.join("iniconfig-2.0.0.zip").is_file());
+    assert!(site_packages.join("iniconfig-2.0.0.pth").is_file());
+    assert!(!site_packages.join("iniconfig").exists());
+    assert!(site_packages.join("tomli").is_dir());
+    assert!(!site_packages.join("tomli-2.0.1.zip").exists());
+
+    context
+        .assert_command("import iniconfig; assert '.zip' in iniconfig.__file__")
+        .success();
+    context.assert_command("import tomli").success();
+
+    // Uninstalling removes the archive and the `.pth` file.
+    Command::new(get_bin())
+        .arg("pip")
+        .arg("uninstall")
+        .arg("iniconfig")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .current_dir(&context.temp_dir)
+        .assert()
+        .success();
+    assert!(!site_packages.join("iniconfig-2.0.0.zip").exists());
+    assert!(!site_packages.join("iniconfig-2.0.0.pth").exists());
+    assert!(!site_packages.join("iniconfig-2.0.0.dist-info").exists());
+}
+
+/// Verify the installed files against the hashes in the wheel's `RECORD`.
+#[test]
+fn install_verify_hashes() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(command(&context)
+        .arg("black==23.10.1")
+        .arg("--verify-hashes"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 6 packages in [TIME]
+    Downloaded 6 packages in [TIME]
+    Installed 6 packages in [TIME]
+     + black==23.10.1
+     + click==8.1.7
+     + mypy-extensions==1.0.0
+     + packaging==23.2
+     + pathspec==0.11.2
+     + platformdirs==4.0.0
+    "###
+    );
+
+    context.assert_command("import black").success();
+
+    // The wheels were verified against their `RECORD` as they were downloaded, which is recorded
+    // alongside each wheel in the cache.
+    let markers = fs_err::read_dir(context.cache_dir.join("archive-v0"))?
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "record"))
+        .count();
+    assert_eq!(markers, 6);
+
+    Ok(())
+}
+
+/// Reject a yanked distribution with `--warn-as-error yanked`, before it's installed.
+#[test]
+fn warn_as_error_on_yanked_version() {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(command(&context)
+        .arg("colorama==0.4.2")
+        .arg("--warn-as-error")
+        .arg("yanked"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    error[yanked]: colorama==0.4.2 is yanked (reason: "Bad build, missing files, will not install").
+    error: Refusing to install yanked distributions
+    "###
+    );
+
+    context.assert_command("import colorama").failure();
+}
+
 /// Install a package from the command line into a virtual environment, ignoring its dependencies.
 #[test]
 fn no_deps() {
@@ -1433,6 +1952,68 @@ fn install_upgrade() {
     );
 }
 
+/// With `--upgrade`, only upgrade the dependencies of the requested packages when using
+/// `--upgrade-strategy eager`.
+#[test]
+fn install_upgrade_strategy() {
+    let context = TestContext::new("3.12");
+
+    // Install an old version of anyio and idna.
+    uv_snapshot!(command(&context)
+        .arg("anyio==3.6.2")
+        .arg("idna==3.3")
+        .arg("sniffio==1.3.0"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    Downloaded 3 packages in [TIME]
+    Installed 3 packages in [TIME]
+     + anyio==3.6.2
+     + idna==3.3
+     + sniffio==1.3.0
+    "###
+    );
+
+    // By default, upgrade anyio, but retain the installed version of idna.
+    uv_snapshot!(command(&context)
+        .arg("anyio")
+        .arg("--upgrade"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     - anyio==3.6.2
+     + anyio==4.0.0
+    "###
+    );
+
+    // With the eager strategy, upgrade idna too.
+    uv_snapshot!(command(&context)
+        .arg("anyio")
+        .arg("--upgrade")
+        .arg("--upgrade-strategy")
+        .arg("eager"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     - idna==3.3
+     + idna==3.4
+    "###
+    );
+}
+
 /// Install a package from a `requirements.txt` file, with a `constraints.txt` file.
 #[test]
 fn install_constraints_txt() -> Result<()> {
@@ -2098,3 +2679,344 @@ dependencies = {file = ["requirements.txt"]}
 
     Ok(())
 }
+
+/// Install a package into a target directory, rather than the virtual environment.
+#[test]
+fn install_target() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let target = context.temp_dir.child("target");
+
+    uv_snapshot!(command(&context)
+        .arg("iniconfig==2.0.0")
+        .arg("--target")
+        .arg(target.path()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + iniconfig==2.0.0
+    "###
+    );
+
+    // The package should be installed into the target directory, and not the environment.
+    target
+        .child("iniconfig")
+        .child("__init__.py")
+        .assert(predicates::path::is_file());
+    context.assert_command("import iniconfig").failure();
+
+    Ok(())
+}
+
+/// Install a Git dependency with a declared name that differs from the true name of the package.
+/// The failure should point at the line of the requirements file that declared it.
+#[test]
+#[cfg(feature = "git")]
+fn install_git_mismatched_name_declared() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str(indoc! {r"
+        iniconfig
+        dask @ git+https://github.com/pallets/flask.git@3.0.0
+    "})?;
+
+    uv_snapshot!(command(&context)
+        .arg("-r")
+        .arg("requirements.txt"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: requirements.txt:2: `dask @ git+https://github.com/pallets/flask.git@3.0.0`
+      Caused by: Failed to download and build: dask @ git+https://github.com/pallets/flask.git@3.0.0
+      Caused by: Package metadata name `flask` does not match given name `dask`
+    "###
+    );
+
+    Ok(())
+}
+
+/// Install a package with platform-specific wheels for a different platform.
+#[test]
+fn install_python_platform() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let target = context.temp_dir.child("target");
+
+    uv_snapshot!(command(&context)
+        .arg("markupsafe==2.1.3")
+        .arg("--only-binary")
+        .arg(":all:")
+        .arg("--target")
+        .arg(target.path())
+        .arg("--python-platform")
+        .arg("windows"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + markupsafe==2.1.3
+    "###
+    );
+
+    // The Windows extension module should be installed.
+    target
+        .child("markupsafe")
+        .child("_speedups.cp312-win_amd64.pyd")
+        .assert(predicates::path::is_file());
+
+    Ok(())
+}
+
+/// `--python-platform` requires `--target`, since the packages can't be used by the current
+/// interpreter.
+#[test]
+fn install_python_platform_without_target() {
+    let context = TestContext::new("3.12");
+
+    command(&context)
+        .arg("markupsafe==2.1.3")
+        .arg("--python-platform")
+        .arg("windows")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("--target <TARGET>"));
+}
+
+/// Install into multiple environments at once, with `--venv`. The resolution and downloads are
+/// shared, and the packages are linked into each environment.
+#[test]
+fn install_multiple_venvs() {
+    let context = TestContext::new("3.12");
+
+    // Create a second environment with the same interpreter.
+    let other = context.temp_dir.child(".venv-other");
+    Command::new(get_bin())
+        .arg("venv")
+        .arg(other.path())
+        .arg("--python")
+        .arg(common::venv_to_interpreter(&context.venv))
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .current_dir(&context.temp_dir)
+        .assert()
+        .success();
+
+    command(&context)
+        .arg("--venv")
+        .arg(context.venv.as_os_str())
+        .arg("--venv")
+        .arg(other.path())
+        .arg("iniconfig==2.0.0")
+        .assert()
+        .success();
+
+    // The package should be installed into both environments.
+    for venv in [context.venv.as_path(), other.path()] {
+        Command::new(common::venv_to_interpreter(venv))
+            .arg("-c")
+            .arg(
+                "import importlib.metadata; print(importlib.metadata.version('iniconfig'), end='')",
+            )
+            .assert()
+            .success()
+            .stdout("2.0.0");
+    }
+}
+
+/// Exclude recently published distributions with `--min-release-age`, and exempt a package with a
+/// per-package age of zero.
+#[test]
+fn install_min_release_age() {
+    let context = TestContext::new("3.12");
+
+    // No version of `iniconfig` is a thousand weeks old.
+    command(&context)
+        .arg("iniconfig")
+        .arg("--min-release-age")
+        .arg("1000w")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("no versions of iniconfig"));
+
+    // The same policy can be provided via the environment.
+    command(&context)
+        .arg("iniconfig")
+        .env("UV_MIN_RELEASE_AGE", "1000w")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("no versions of iniconfig"));
+
+    // A per-package age takes precedence over the default.
+    command(&context)
+        .arg("iniconfig")
+        .arg("--min-release-age")
+        .arg("1000w")
+        .arg("--min-release-age")
+        .arg("iniconfig=0d")
+        .assert()
+        .success();
+
+    context.assert_command("import iniconfig").success();
+
+    // Ages require a known unit.
+    command(&context)
+        .arg("iniconfig")
+        .arg("--min-release-age")
+        .arg("7x")
+        .assert()
+        .code(2)
+        .stderr(predicates::str::contains(
+            "Invalid unit in minimum release age: `x`",
+        ));
+}
+
+/// Respect `--color` when writing errors, even when stderr isn't a terminal.
+#[test]
+fn install_color() {
+    let context = TestContext::new("3.12");
+
+    // By default, colors are disabled when stderr isn't a terminal.
+    command(&context)
+        .arg("-r")
+        .arg("missing.txt")
+        .assert()
+        .failure()
+        .stderr(
+            predicates::str::contains("error: ").and(predicates::str::contains("\u{1b}[").not()),
+        );
+
+    // `--color always` forces colors.
+    command(&context)
+        .arg("--color")
+        .arg("always")
+        .arg("-r")
+        .arg("missing.txt")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("\u{1b}["));
+
+    // `--color never` disables colors, even with `FORCE_COLOR` set.
+    command(&context)
+        .arg("--color")
+        .arg("never")
+        .arg("-r")
+        .arg("missing.txt")
+        .env("FORCE_COLOR", "1")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("\u{1b}[").not());
+}
+
+/// If linking fails partway through a reinstall, the environment should be restored to its
+/// original state.
+#[test]
+#[cfg(unix)]
+fn install_rollback() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let site_packages = context.venv.join("lib/python3.12/site-packages");
+
+    command(&context).arg("iniconfig==1.1.1").assert().success();
+
+    // Block one of the files that the new version installs with a (non-empty) directory.
+    fs_err::create_dir_all(site_packages.join("iniconfig/_parse.py/blocker"))?;
+    fs_err::write(site_packages.join("iniconfig/_parse.py/blocker/file"), "")?;
+
+    command(&context)
+        .arg("iniconfig==2.0.0")
+        .arg("--link-mode")
+        .arg("copy")
+        .assert()
+        .failure();
+
+    // The original version should be restored, without any files from the new version.
+    context
+        .assert_command(
+            "import importlib.metadata; print(importlib.metadata.version('iniconfig'), end='')",
+        )
+        .success()
+        .stdout("1.1.1");
+    assert!(site_packages.join("iniconfig/__init__.py").is_file());
+    assert!(!site_packages.join("iniconfig/exceptions.py").exists());
+    assert!(!site_packages.join("iniconfig-2.0.0.dist-info").exists());
+
+    // The backups should be removed.
+    assert!(!fs_err::read_dir(&site_packages)?
+        .filter_map(Result::ok)
+        .any(|entry| entry
+            .file_name()
+            .to_string_lossy()
+            .starts_with(".uv-backup-")));
+
+    Ok(())
+}
+
+/// After a successful reinstall, the backups of the replaced files should be removed.
+#[test]
+fn install_removes_backups() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let site_packages = context.venv.join("lib/python3.12/site-packages");
+
+    command(&context).arg("iniconfig==1.1.1").assert().success();
+
+    command(&context).arg("iniconfig==2.0.0").assert().success();
+
+    context.assert_installed("iniconfig", "2.0.0");
+    assert!(!fs_err::read_dir(&site_packages)?
+        .filter_map(Result::ok)
+        .any(|entry| entry
+            .file_name()
+            .to_string_lossy()
+            .starts_with(".uv-backup-")));
+
+    Ok(())
+}
+
+/// Install a wheel with headers and data files (`pybind11-global` ships its headers under both
+/// `.data/headers` and `.data/data/include`), and uninstall it again.
+#[test]
+#[cfg(unix)]
+fn install_data_headers() {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(command(&context)
+        .arg("pybind11-global==2.11.1"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + pybind11-global==2.11.1
+    "###
+    );
+
+    // Headers are installed into a directory named after the distribution, as with pip.
+    let headers = context.venv.join("include/site/python3.12/pybind11_global");
+    assert!(headers.is_dir());
+    assert!(fs_err::read_dir(&headers).unwrap().next().is_some());
+
+    // Data files are installed relative to the environment's prefix.
+    let data = context.venv.join("include/pybind11/pybind11.h");
+    assert!(data.is_file());
+    assert!(!context
+        .venv
+        .join("lib/python3.12/site-packages/pybind11_global-2.11.1.data")
+        .exists());
+
+    uninstall_command(&context)
+        .arg("pybind11-global")
+        .assert()
+        .success();
+    assert!(!data.exists());
+}
@@ -0,0 +1,149 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::process::Command;
+
+use anyhow::Result;
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use serde_json::Value;
+
+use crate::common::{get_bin, TestContext};
+
+mod common;
+
+/// Create a `pip licenses` command with options shared across scenarios.
+fn command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("pip")
+        .arg("licenses")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .current_dir(&context.temp_dir);
+    command
+}
+
+/// Sync `anyio` and its dependencies into the test environment.
+fn sync(context: &TestContext) -> Result<()> {
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("anyio==4.0.0\nidna==3.4\nsniffio==1.3.0")?;
+
+    Command::new(get_bin())
+        .arg("pip")
+        .arg("sync")
+        .arg(requirements_txt.path())
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .assert()
+        .success();
+
+    Ok(())
+}
+
+#[test]
+fn grouped() -> Result<()> {
+    let context = TestContext::new("3.12");
+    sync(&context)?;
+
+    let output = command(&context)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let output = String::from_utf8(output)?;
+
+    // `anyio` declares the `MIT License` classifier, which maps to an SPDX identifier.
+    let lines = output.lines().collect::<Vec<_>>();
+    let mit = lines
+        .iter()
+        .position(|line| line.starts_with("MIT ("))
+        .expect("Expected an `MIT` group");
+    assert!(lines[mit + 1..]
+        .iter()
+        .take_while(|line| line.starts_with("  "))
+        .any(|line| *line == "  anyio==4.0.0"));
+
+    Ok(())
+}
+
+#[test]
+fn json() -> Result<()> {
+    let context = TestContext::new("3.12");
+    sync(&context)?;
+
+    let output = command(&context)
+        .arg("--format")
+        .arg("json")
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let packages: Value = serde_json::from_slice(&output)?;
+    let packages = packages.as_array().unwrap();
+
+    let names = packages
+        .iter()
+        .map(|package| package["name"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec!["anyio", "idna", "sniffio"]);
+
+    let anyio = &packages[0];
+    assert_eq!(anyio["version"], "4.0.0");
+    assert_eq!(anyio["license"], "MIT");
+    assert_eq!(anyio["classifiers"], "MIT License");
+
+    Ok(())
+}
+
+#[test]
+fn csv() -> Result<()> {
+    let context = TestContext::new("3.12");
+    sync(&context)?;
+
+    let output = command(&context)
+        .arg("--format")
+        .arg("csv")
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let output = String::from_utf8(output)?;
+
+    let lines = output.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 4);
+    assert_eq!(
+        lines[0],
+        "name,version,license,license_expression,classifiers"
+    );
+    assert_eq!(lines[1], "anyio,4.0.0,MIT,,MIT License");
+
+    Ok(())
+}
+
+#[test]
+fn fail_on() -> Result<()> {
+    let context = TestContext::new("3.12");
+    sync(&context)?;
+
+    // `anyio` is MIT-licensed.
+    command(&context)
+        .arg("--fail-on")
+        .arg("mit")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("anyio==4.0.0 (MIT)"));
+
+    // No package is GPL-licensed.
+    command(&context)
+        .arg("--fail-on")
+        .arg("GPL-3.0-only")
+        .assert()
+        .success();
+
+    Ok(())
+}
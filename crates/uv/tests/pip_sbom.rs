@@ -0,0 +1,122 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::process::Command;
+
+use anyhow::Result;
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use serde_json::Value;
+
+use crate::common::{get_bin, TestContext};
+
+mod common;
+
+/// Create a `pip sbom` command with options shared across scenarios.
+fn command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("pip")
+        .arg("sbom")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .current_dir(&context.temp_dir);
+    command
+}
+
+/// Sync `anyio` and its dependencies into the test environment.
+fn sync(context: &TestContext) -> Result<()> {
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("anyio==4.0.0\nidna==3.4\nsniffio==1.3.0")?;
+
+    Command::new(get_bin())
+        .arg("pip")
+        .arg("sync")
+        .arg(requirements_txt.path())
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .assert()
+        .success();
+
+    Ok(())
+}
+
+/// Run the given command, and parse its output as JSON.
+fn output(command: &mut Command) -> Result<Value> {
+    let output = command.assert().success().get_output().stdout.clone();
+    Ok(serde_json::from_slice(&output)?)
+}
+
+#[test]
+fn cyclonedx() -> Result<()> {
+    let context = TestContext::new("3.12");
+    sync(&context)?;
+
+    let sbom = output(&mut command(&context))?;
+    assert_eq!(sbom["bomFormat"], "CycloneDX");
+    assert_eq!(sbom["specVersion"], "1.5");
+
+    let components = sbom["components"].as_array().unwrap();
+    let names = components
+        .iter()
+        .map(|component| component["name"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec!["anyio", "idna", "sniffio"]);
+
+    let anyio = &components[0];
+    assert_eq!(anyio["version"], "4.0.0");
+    assert_eq!(anyio["purl"], "pkg:pypi/anyio@4.0.0");
+    // The archive's digest isn't recorded for registry installs, but the installed `RECORD` is
+    // reported as a property.
+    assert!(anyio["hashes"].is_null());
+    assert_eq!(anyio["properties"][0]["name"], "uv:record-sha256");
+    assert_eq!(anyio["properties"][0]["value"].as_str().unwrap().len(), 64);
+    assert_eq!(anyio["licenses"][0]["license"]["name"], "MIT License");
+
+    // `anyio` depends on both `idna` and `sniffio`.
+    assert_eq!(sbom["dependencies"][0]["ref"], "pkg:pypi/anyio@4.0.0");
+    assert_eq!(
+        sbom["dependencies"][0]["dependsOn"],
+        serde_json::json!(["pkg:pypi/idna@3.4", "pkg:pypi/sniffio@1.3.0"])
+    );
+
+    Ok(())
+}
+
+#[test]
+fn spdx() -> Result<()> {
+    let context = TestContext::new("3.12");
+    sync(&context)?;
+
+    let sbom = output(command(&context).arg("--format").arg("spdx-json"))?;
+    assert_eq!(sbom["spdxVersion"], "SPDX-2.3");
+    assert_eq!(sbom["SPDXID"], "SPDXRef-DOCUMENT");
+
+    let packages = sbom["packages"].as_array().unwrap();
+    assert_eq!(packages.len(), 3);
+
+    let anyio = &packages[0];
+    assert_eq!(anyio["SPDXID"], "SPDXRef-Package-anyio");
+    assert_eq!(anyio["versionInfo"], "4.0.0");
+    assert_eq!(anyio["licenseDeclared"], "MIT");
+    assert_eq!(anyio["downloadLocation"], "NOASSERTION");
+    assert!(anyio["checksums"].is_null());
+    assert!(anyio["annotations"][0]["comment"]
+        .as_str()
+        .unwrap()
+        .starts_with("SHA-256 of the installed RECORD: "));
+    assert_eq!(
+        anyio["externalRefs"][0]["referenceLocator"],
+        "pkg:pypi/anyio@4.0.0"
+    );
+
+    let relationships = sbom["relationships"].as_array().unwrap();
+    assert!(relationships.contains(&serde_json::json!({
+        "spdxElementId": "SPDXRef-Package-anyio",
+        "relationshipType": "DEPENDS_ON",
+        "relatedSpdxElement": "SPDXRef-Package-idna",
+    })));
+
+    Ok(())
+}
@@ -0,0 +1,133 @@
+#![cfg(feature = "python")]
+
+use std::process::Command;
+
+use anyhow::Result;
+
+use crate::common::{get_bin, uv_snapshot, TestContext};
+
+mod common;
+
+/// Create a `pip startup` command with options shared across scenarios.
+fn command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("pip")
+        .arg("startup")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .current_dir(&context.temp_dir);
+    command
+}
+
+/// Install, disable, re-enable, and remove a startup hook.
+#[test]
+fn startup_hook() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(context.filters(), command(&context)
+        .arg("add")
+        .arg("marker")
+        .arg("import os; os.environ['UV_STARTUP_MARKER'] = 'set'"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Installed startup hook marker (owned by uv-startup-marker)
+    "###
+    );
+
+    context
+        .assert_command("import os; print(os.environ.get('UV_STARTUP_MARKER'), end='')")
+        .success()
+        .stdout("set");
+
+    uv_snapshot!(context.filters(), command(&context)
+        .arg("disable")
+        .arg("marker"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Disabled startup hook marker
+    "###
+    );
+
+    context
+        .assert_command("import os; print(os.environ.get('UV_STARTUP_MARKER'), end='')")
+        .success()
+        .stdout("None");
+
+    uv_snapshot!(context.filters(), command(&context)
+        .arg("list"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    marker (disabled): import os; os.environ['UV_STARTUP_MARKER'] = 'set'
+
+    ----- stderr -----
+    "###
+    );
+
+    uv_snapshot!(context.filters(), command(&context)
+        .arg("enable")
+        .arg("marker"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Enabled startup hook marker
+    "###
+    );
+
+    uv_snapshot!(context.filters(), command(&context)
+        .arg("remove")
+        .arg("marker"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Removed startup hook marker
+    "###
+    );
+
+    let site_packages = if cfg!(windows) {
+        context.venv.join("Lib").join("site-packages")
+    } else {
+        context
+            .venv
+            .join("lib")
+            .join("python3.12")
+            .join("site-packages")
+    };
+    assert!(!site_packages.join("uv_startup_marker.pth").exists());
+    assert!(!site_packages
+        .join("uv_startup_marker-0.0.0.dist-info")
+        .exists());
+
+    Ok(())
+}
+
+/// Reject startup hooks that the interpreter would silently ignore.
+#[test]
+fn startup_hook_requires_import() {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(context.filters(), command(&context)
+        .arg("add")
+        .arg("greeting")
+        .arg("print('hello')"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Invalid startup hook: `print('hello')` does not begin with `import`, so it would not be executed at startup
+    "###
+    );
+}
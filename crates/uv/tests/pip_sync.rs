@@ -2,7 +2,7 @@
 
 use fs_err as fs;
 use std::env::consts::EXE_SUFFIX;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 use anyhow::Result;
@@ -176,6 +176,44 @@ fn install() -> Result<()> {
     Ok(())
 }
 
+/// Install packages with every concurrency limit set to one.
+#[test]
+fn install_sequential() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str(indoc! {r"
+        anyio==4.0.0
+        idna==3.6
+        sniffio==1.3.1
+    "})?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--concurrent-downloads")
+        .arg("1")
+        .arg("--concurrent-builds")
+        .arg("1")
+        .env("UV_CONCURRENT_INSTALLS", "1"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    Downloaded 3 packages in [TIME]
+    Installed 3 packages in [TIME]
+     + anyio==4.0.0
+     + idna==3.6
+     + sniffio==1.3.1
+    "###
+    );
+
+    context.assert_command("import anyio").success();
+
+    Ok(())
+}
+
 /// Install a package into a virtual environment using copy semantics.
 #[test]
 fn install_copy() -> Result<()> {
@@ -248,6 +286,45 @@ fn install_hardlink() -> Result<()> {
     Ok(())
 }
 
+/// Install a package into a virtual environment using symlink semantics.
+#[test]
+#[cfg(unix)] // Windows does not allow symlinks by default
+fn install_symlink() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.touch()?;
+    requirements_txt.write_str("MarkupSafe==2.1.3")?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--link-mode")
+        .arg("symlink")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + markupsafe==2.1.3
+    "###
+    );
+
+    context.assert_command("import markupsafe").success();
+
+    // Removing the cache _should_ invalidate the virtual environment.
+    fs::remove_dir_all(context.cache_dir.path())?;
+
+    context
+        .assert_command("from markupsafe import Markup")
+        .failure();
+
+    Ok(())
+}
+
 /// Install multiple packages into a virtual environment.
 #[test]
 fn install_many() -> Result<()> {
@@ -280,6 +357,47 @@ fn install_many() -> Result<()> {
     Ok(())
 }
 
+/// Report progress as newline-delimited JSON events.
+#[test]
+fn progress_json() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.touch()?;
+    requirements_txt.write_str("MarkupSafe==2.1.3")?;
+
+    // The size of the wheel depends on the platform.
+    let filters: Vec<_> = [
+        (r#""total":\d{3,}"#, r#""total":[SIZE]"#),
+        (r#""downloaded":[1-9]\d*"#, r#""downloaded":[SIZE]"#),
+    ]
+    .into_iter()
+    .chain(INSTA_FILTERS.to_vec())
+    .collect();
+
+    uv_snapshot!(filters, command(&context)
+        .arg("requirements.txt")
+        .arg("--progress")
+        .arg("json"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    {"event":"progress","phase":"resolve","package":"markupsafe==2.1.3","completed":1,"total":1}
+    {"event":"complete","phase":"resolve"}
+    {"event":"download-progress","package":"markupsafe==2.1.3","downloaded":0,"total":[SIZE]}
+    {"event":"download-progress","package":"markupsafe==2.1.3","downloaded":[SIZE],"total":[SIZE]}
+    {"event":"progress","phase":"download","package":"markupsafe==2.1.3","completed":1,"total":1}
+    {"event":"complete","phase":"download"}
+    {"event":"progress","phase":"install","package":"markupsafe==2.1.3","completed":1,"total":1}
+    {"event":"complete","phase":"install"}
+    "###
+    );
+
+    Ok(())
+}
+
 /// Attempt to install an already-installed package into a virtual environment.
 #[test]
 fn noop() -> Result<()> {
@@ -418,6 +536,64 @@ fn add_remove() -> Result<()> {
     Ok(())
 }
 
+/// Sync with a different requirements file, retaining installed packages passed to `--keep`, or
+/// all installed packages with `--no-strip`.
+#[test]
+fn add_remove_keep() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("MarkupSafe==2.1.3\niniconfig==2.0.0")?;
+
+    command(&context).arg("requirements.txt").assert().success();
+
+    requirements_txt.write_str("tomli==2.0.1")?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--keep")
+        .arg("markupsafe"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Uninstalled 1 package in [TIME]
+    Installed 1 package in [TIME]
+     - iniconfig==2.0.0
+     + tomli==2.0.1
+    "###
+    );
+
+    context.assert_command("import tomli").success();
+    context.assert_command("import markupsafe").success();
+    context.assert_command("import iniconfig").failure();
+
+    requirements_txt.write_str("iniconfig==2.0.0")?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--no-strip"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + iniconfig==2.0.0
+    "###
+    );
+
+    context.assert_command("import tomli").success();
+    context.assert_command("import markupsafe").success();
+    context.assert_command("import iniconfig").success();
+
+    Ok(())
+}
+
 /// Install a package into a virtual environment, then install a second package into the same
 /// virtual environment.
 #[test]
@@ -561,6 +737,69 @@ fn install_git_commit() -> Result<()> {
     Ok(())
 }
 
+/// Re-syncing a Git requirement pinned to the installed commit should be a no-op, even if the URL
+/// is spelled differently (e.g., with an `#egg=` fragment).
+#[test]
+#[cfg(feature = "git")]
+fn install_git_commit_satisfied() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("werkzeug @ git+https://github.com/pallets/werkzeug.git@af160e0b6b7ddd81c22f1652c728ff5ac72d5c74")?;
+
+    command(&context)
+        .arg("requirements.txt")
+        .arg("--strict")
+        .assert()
+        .success();
+
+    requirements_txt.write_str("werkzeug @ git+https://github.com/pallets/werkzeug.git@af160e0b6b7ddd81c22f1652c728ff5ac72d5c74#egg=werkzeug")?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Audited 1 package in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Re-syncing a Git requirement on a tag that resolves to the installed commit should be a no-op.
+#[test]
+#[cfg(feature = "git")]
+fn install_git_tag_satisfied() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("werkzeug @ git+https://github.com/pallets/werkzeug.git@2.0.0")?;
+
+    command(&context)
+        .arg("requirements.txt")
+        .arg("--strict")
+        .assert()
+        .success();
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Audited 1 package in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
 /// Install a package into a virtual environment from a Git repository.
 #[test]
 #[cfg(feature = "git")]
@@ -842,7 +1081,7 @@ fn install_no_index() -> Result<()> {
     ----- stdout -----
 
     ----- stderr -----
-    error: markupsafe isn't available locally, but making network requests to registries was banned.
+    error: markupsafe isn't available in the cache or any `--find-links` location, and index lookups were disabled with `--no-index`
     "###
     );
 
@@ -851,8 +1090,8 @@ fn install_no_index() -> Result<()> {
     Ok(())
 }
 
-/// Attempt to install a package without using a remote index
-/// after a previous successful installation.
+/// Install a package without using a remote index after a previous successful installation, by
+/// reusing the cached wheel.
 #[test]
 fn install_no_index_cached() -> Result<()> {
     let context = TestContext::new("3.12");
@@ -887,16 +1126,17 @@ fn install_no_index_cached() -> Result<()> {
         .arg("requirements.txt")
         .arg("--no-index")
         .arg("--strict"), @r###"
-    success: false
-    exit_code: 2
+    success: true
+    exit_code: 0
     ----- stdout -----
 
     ----- stderr -----
-    error: markupsafe isn't available locally, but making network requests to registries was banned.
+    Installed 1 package in [TIME]
+     + markupsafe==2.1.3
     "###
     );
 
-    context.assert_command("import markupsafe").failure();
+    context.assert_command("import markupsafe").success();
 
     Ok(())
 }
@@ -923,7 +1163,64 @@ fn warn_on_yanked_version() -> Result<()> {
     Downloaded 1 package in [TIME]
     Installed 1 package in [TIME]
      + colorama==0.4.2
-    warning: colorama==0.4.2 is yanked (reason: "Bad build, missing files, will not install"). Refresh your lockfile to pin an un-yanked version.
+    warning[yanked]: colorama==0.4.2 is yanked (reason: "Bad build, missing files, will not install"). Refresh your lockfile to pin an un-yanked version.
+    "###
+    );
+
+    Ok(())
+}
+
+/// Promote the yanked-version warning to an error with `--warn-as-error`.
+#[test]
+fn warn_as_error_on_yanked_version() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_in = context.temp_dir.child("requirements.txt");
+    requirements_in.write_str("colorama==0.4.2")?;
+
+    uv_snapshot!(INSTA_FILTERS, windows_filters=false, command(&context)
+        .arg("requirements.txt")
+        .arg("--warn-as-error")
+        .arg("yanked"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    error[yanked]: colorama==0.4.2 is yanked (reason: "Bad build, missing files, will not install"). Refresh your lockfile to pin an un-yanked version.
+    error: Refusing to install yanked distributions
+    "###
+    );
+
+    // The yanked distribution is rejected before it's installed.
+    context.assert_command("import colorama").failure();
+
+    Ok(())
+}
+
+/// Suppress the yanked-version warning with `--silence`.
+#[test]
+fn silence_yanked_version() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_in = context.temp_dir.child("requirements.txt");
+    requirements_in.write_str("colorama==0.4.2")?;
+
+    uv_snapshot!(INSTA_FILTERS, windows_filters=false, command(&context)
+        .arg("requirements.txt")
+        .arg("--silence")
+        .arg("yanked"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + colorama==0.4.2
     "###
     );
 
@@ -2893,3 +3190,418 @@ requires-python = ">=3.8"
 
     Ok(())
 }
+
+/// Changes within `node_modules` shouldn't invalidate a local source tree, but changes to files
+/// that are excluded by a `.gitignore` should, since they may be inputs to the build.
+#[test]
+fn ignore_untracked_changes() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // Create an editable package with an ignored directory, and a `node_modules` directory.
+    let editable_dir = assert_fs::TempDir::new()?;
+    editable_dir.child("pyproject.toml").write_str(
+        r#"[project]
+name = "example"
+version = "0.0.0"
+requires-python = ">=3.8"
+
+[tool.setuptools]
+packages = []
+"#,
+    )?;
+    editable_dir.child(".gitignore").write_str("generated/\n")?;
+    editable_dir.child("generated").create_dir_all()?;
+    editable_dir.child("node_modules").create_dir_all()?;
+
+    // Write to a requirements file.
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str(&format!("-e {}", editable_dir.path().display()))?;
+
+    let filters = [(r"\(from file://.*\)", "(from [WORKSPACE_DIR])")]
+        .into_iter()
+        .chain(INSTA_FILTERS.to_vec())
+        .collect::<Vec<_>>();
+
+    uv_snapshot!(filters, command(&context)
+        .arg("requirements.in"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Built 1 editable in [TIME]
+    Installed 1 package in [TIME]
+     + example==0.0.0 (from [WORKSPACE_DIR])
+    "###
+    );
+
+    // Modify the `node_modules` directory.
+    editable_dir
+        .child("node_modules")
+        .child("index.js")
+        .write_str("...")?;
+
+    // Re-installing should be a no-op.
+    uv_snapshot!(filters, command(&context)
+        .arg("requirements.in"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Audited 1 package in [TIME]
+    "###
+    );
+
+    // Modify the ignored directory.
+    editable_dir
+        .child("generated")
+        .child("version.py")
+        .write_str("__version__ = '0.0.0'")?;
+
+    // Re-installing should rebuild the package.
+    uv_snapshot!(filters, command(&context)
+        .arg("requirements.in"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Built 1 editable in [TIME]
+    Uninstalled 1 package in [TIME]
+    Installed 1 package in [TIME]
+     - example==0.0.0 (from [WORKSPACE_DIR])
+     + example==0.0.0 (from [WORKSPACE_DIR])
+    "###
+    );
+
+    Ok(())
+}
+
+/// Audit the cached wheels against the digests advertised by the index.
+#[test]
+fn cache_audit() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("MarkupSafe==2.1.3")?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + markupsafe==2.1.3
+    "###
+    );
+
+    uv_snapshot!(Command::new(get_bin())
+        .arg("cache")
+        .arg("audit")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Audited 1 cached wheel: 1 verified, 0 mismatched, 0 missing, 0 unverifiable
+    "###
+    );
+
+    Ok(())
+}
+
+/// Create a virtual environment on demand with `--auto-create`, respecting the pinned interpreter.
+#[test]
+fn auto_create() -> Result<()> {
+    let temp_dir = assert_fs::TempDir::new()?;
+    let cache_dir = assert_fs::TempDir::new()?;
+    let bin = create_bin_with_executables(&temp_dir, &["3.12"]).expect("Failed to create bin dir");
+    temp_dir.child(".python-version").write_str("3.12\n")?;
+
+    let requirements_txt = temp_dir.child("requirements.txt");
+    requirements_txt.write_str("MarkupSafe==2.1.3")?;
+
+    // Without `--auto-create`, a non-interactive invocation fails.
+    uv_snapshot!(Command::new(get_bin())
+        .arg("pip")
+        .arg("sync")
+        .arg("requirements.txt")
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .env_remove("VIRTUAL_ENV")
+        .env_remove("CONDA_PREFIX")
+        .env("UV_TEST_PYTHON_PATH", bin.clone())
+        .current_dir(&temp_dir), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Failed to locate a virtualenv or Conda environment (checked: `VIRTUAL_ENV`, `CONDA_PREFIX`, and `.venv`). Run `uv venv` to create a virtualenv.
+    "###
+    );
+
+    let filters = [
+        (
+            r"Using Python 3\.12\.\d+ interpreter at: .+",
+            "Using Python 3.12.[X] interpreter at: [PATH]",
+        ),
+        (
+            r"Creating virtualenv at: .+",
+            "Creating virtualenv at: .venv",
+        ),
+    ]
+    .into_iter()
+    .chain(INSTA_FILTERS.to_vec())
+    .collect::<Vec<_>>();
+    uv_snapshot!(filters, Command::new(get_bin())
+        .arg("pip")
+        .arg("sync")
+        .arg("requirements.txt")
+        .arg("--auto-create")
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .env_remove("VIRTUAL_ENV")
+        .env_remove("CONDA_PREFIX")
+        .env("UV_TEST_PYTHON_PATH", bin)
+        .current_dir(&temp_dir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Using Python 3.12.[X] interpreter at: [PATH]
+    Creating virtualenv at: .venv
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + markupsafe==2.1.3
+    "###
+    );
+
+    check_command(
+        &temp_dir.child(".venv"),
+        "import markupsafe",
+        temp_dir.path(),
+    );
+
+    Ok(())
+}
+
+/// Reject a downloaded distribution whose digest doesn't match the hash database.
+#[test]
+fn hash_database_mismatch() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("iniconfig==2.0.0")?;
+
+    let hashes_txt = context.temp_dir.child("hashes.txt");
+    hashes_txt.write_str(indoc! {r"
+        # The expected digest is deliberately incorrect.
+        iniconfig==2.0.0 \
+            --hash=sha256:0000000000000000000000000000000000000000000000000000000000000000
+    "})?;
+
+    command(&context)
+        .arg("requirements.txt")
+        .arg("--hash-database")
+        .arg("hashes.txt")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "Hash mismatch for `iniconfig==2.0.0`: expected `sha256:0000000000000000000000000000000000000000000000000000000000000000`",
+        ));
+
+    context.assert_command("import iniconfig").failure();
+
+    Ok(())
+}
+
+/// Re-download a cached wheel whose digest wasn't recorded (e.g., by an older version of uv), rather
+/// than failing to verify it against the hash database.
+#[test]
+fn hash_database_missing_digest() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("iniconfig==2.0.0")?;
+
+    let hashes_txt = context.temp_dir.child("hashes.txt");
+    hashes_txt.write_str(indoc! {r"
+        iniconfig==2.0.0 \
+            --hash=sha256:b6a85871a79d2e3b22d2d1b94ac2824226a63c6b741c88f7ae975f18b6778374
+    "})?;
+
+    command(&context).arg("requirements.txt").assert().success();
+
+    // Remove the recorded digest from the cache.
+    let digest = context
+        .cache_dir
+        .join("wheels-v0/pypi/iniconfig/iniconfig-2.0.0-py3-none-any.sha256");
+    assert!(digest.is_file());
+    fs::remove_file(&digest)?;
+
+    command(&context)
+        .arg("requirements.txt")
+        .arg("--reinstall")
+        .arg("--refresh")
+        .arg("--hash-database")
+        .arg("hashes.txt")
+        .assert()
+        .success();
+
+    assert!(digest.is_file());
+    context.assert_command("import iniconfig").success();
+
+    Ok(())
+}
+
+/// Reject a direct URL distribution whose digest doesn't match the hash provided alongside it in
+/// the requirements file.
+#[test]
+fn url_hash_mismatch() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str(indoc! {r"
+        iniconfig @ https://files.pythonhosted.org/packages/ef/a6/62565a6e1cf69e10f5727360368e451d4b7f58beeac6173dc9db836a5b46/iniconfig-2.0.0-py3-none-any.whl \
+            --hash=sha256:0000000000000000000000000000000000000000000000000000000000000000
+    "})?;
+
+    command(&context)
+        .arg("requirements.txt")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "Hash mismatch for `https://files.pythonhosted.org/packages/ef/a6/62565a6e1cf69e10f5727360368e451d4b7f58beeac6173dc9db836a5b46/iniconfig-2.0.0-py3-none-any.whl`",
+        ));
+
+    context.assert_command("import iniconfig").failure();
+
+    Ok(())
+}
+
+/// Re-download a cached direct URL wheel whose digest wasn't recorded, rather than failing to
+/// verify it against the hash provided alongside it in the requirements file.
+#[test]
+fn url_hash_missing_digest() -> Result<()> {
+    /// Find the first recorded digest in the given directory.
+    fn find_digest(dir: &Path) -> Option<PathBuf> {
+        fs::read_dir(dir)
+            .ok()?
+            .filter_map(Result::ok)
+            .find_map(|entry| {
+                let path = entry.path();
+                if path.is_dir() {
+                    find_digest(&path)
+                } else if path.extension().is_some_and(|ext| ext == "sha256") {
+                    Some(path)
+                } else {
+                    None
+                }
+            })
+    }
+
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("iniconfig @ https://files.pythonhosted.org/packages/ef/a6/62565a6e1cf69e10f5727360368e451d4b7f58beeac6173dc9db836a5b46/iniconfig-2.0.0-py3-none-any.whl")?;
+
+    command(&context).arg("requirements.txt").assert().success();
+
+    // Remove the recorded digest from the cache.
+    let digest = find_digest(&context.cache_dir.join("wheels-v0/url")).unwrap();
+    fs::remove_file(&digest)?;
+
+    requirements_txt.write_str(indoc! {r"
+        iniconfig @ https://files.pythonhosted.org/packages/ef/a6/62565a6e1cf69e10f5727360368e451d4b7f58beeac6173dc9db836a5b46/iniconfig-2.0.0-py3-none-any.whl \
+            --hash=sha256:b6a85871a79d2e3b22d2d1b94ac2824226a63c6b741c88f7ae975f18b6778374
+    "})?;
+
+    command(&context)
+        .arg("requirements.txt")
+        .arg("--reinstall")
+        .arg("--refresh")
+        .assert()
+        .success();
+
+    assert!(digest.is_file());
+    context.assert_command("import iniconfig").success();
+
+    Ok(())
+}
+
+/// Reject a version of a package that's listed in the hash database, but not at that version.
+#[test]
+fn hash_database_unlisted_version() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("iniconfig==2.0.0")?;
+
+    let hashes_txt = context.temp_dir.child("hashes.txt");
+    hashes_txt.write_str(
+        "iniconfig==1.1.1 --hash=sha256:011e24c64b7f47f6ebd835bb12a743f2fbe9a26d4cecaa7f53bc4f35ee9da8b3",
+    )?;
+
+    command(&context)
+        .arg("requirements.txt")
+        .env("UV_HASH_DATABASE", hashes_txt.path())
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "`iniconfig==2.0.0` is not permitted by the hash database",
+        ));
+
+    Ok(())
+}
+
+/// Install into a Conda environment, which requires an explicit opt-in.
+#[test]
+fn install_conda_environment() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // Mark the virtual environment as a Conda environment.
+    fs::create_dir(context.venv.join("conda-meta"))?;
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("MarkupSafe==2.1.3")?;
+
+    uv_snapshot!(context.filters(), command(&context)
+        .arg("requirements.txt"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: The environment at [TEMP_DIR]/.venv is a Conda environment. Packages installed by uv aren't tracked by `conda`; pass `--allow-conda` to install into it anyway.
+    "###
+    );
+
+    uv_snapshot!(context.filters(), command(&context)
+        .arg("requirements.txt")
+        .arg("--allow-conda"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + markupsafe==2.1.3
+    "###
+    );
+
+    context.assert_command("import markupsafe").success();
+
+    Ok(())
+}
@@ -8,7 +8,9 @@ use url::Url;
 use common::{uv_snapshot, INSTA_FILTERS};
 use uv_fs::Simplified;
 
-use crate::common::{get_bin, venv_to_interpreter, TestContext};
+use crate::common::{
+    create_bin_with_executables, get_bin, venv_to_interpreter, TestContext, EXCLUDE_NEWER,
+};
 
 mod common;
 
@@ -362,6 +364,102 @@ fn missing_record() -> Result<()> {
     Ok(())
 }
 
+/// Uninstalling a package whose files were modified (with `--check-modified`) or removed since
+/// installation should warn.
+#[test]
+#[cfg(unix)]
+fn uninstall_modified() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("MarkupSafe==2.1.3")?;
+
+    Command::new(get_bin())
+        .arg("pip")
+        .arg("sync")
+        .arg("requirements.txt")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .current_dir(&context.temp_dir)
+        .assert()
+        .success();
+
+    // Modify one file (replacing it, rather than writing through any link into the cache), and
+    // remove another.
+    let package = context
+        .venv
+        .join("lib")
+        .join("python3.12")
+        .join("site-packages")
+        .join("markupsafe");
+    fs_err::remove_file(package.join("__init__.py"))?;
+    fs_err::write(package.join("__init__.py"), "# Modified\n")?;
+    fs_err::remove_file(package.join("py.typed"))?;
+
+    uv_snapshot!(context.filters(), Command::new(get_bin())
+        .arg("pip")
+        .arg("uninstall")
+        .arg("MarkupSafe")
+        .arg("--check-modified")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .current_dir(&context.temp_dir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: Removed file from markupsafe that was modified since installation: [VENV]/lib/python3.12/site-packages/markupsafe/__init__.py
+    warning: File from markupsafe was already missing: [VENV]/lib/python3.12/site-packages/markupsafe/py.typed
+    Uninstalled 1 package in [TIME]
+     - markupsafe==2.1.3
+    "###
+    );
+
+    Ok(())
+}
+
+#[test]
+fn uninstall_dry_run() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("MarkupSafe==2.1.3")?;
+
+    Command::new(get_bin())
+        .arg("pip")
+        .arg("sync")
+        .arg("requirements.txt")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .current_dir(&context.temp_dir)
+        .assert()
+        .success();
+
+    Command::new(get_bin())
+        .arg("pip")
+        .arg("uninstall")
+        .arg("MarkupSafe")
+        .arg("--dry-run")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .current_dir(&context.temp_dir)
+        .assert()
+        .success()
+        .stderr(predicates::str::contains("Would uninstall 1 package"))
+        .stderr(predicates::str::contains("__init__.py"))
+        .stderr(predicates::str::contains("RECORD"));
+
+    // Nothing should have been removed.
+    context.assert_command("import markupsafe").success();
+
+    Ok(())
+}
+
 #[test]
 fn uninstall_editable_by_name() -> Result<()> {
     let context = TestContext::new("3.12");
@@ -626,3 +724,133 @@ fn uninstall_duplicate() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn autoremove() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // Install `anyio` (and its dependencies) alongside `tomli`.
+    Command::new(get_bin())
+        .arg("pip")
+        .arg("install")
+        .arg("anyio==4.0.0")
+        .arg("tomli==2.0.1")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .assert()
+        .success();
+
+    // While `anyio` is installed, its dependencies are still required.
+    uv_snapshot!(Command::new(get_bin())
+        .arg("pip")
+        .arg("autoremove")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .current_dir(&context.temp_dir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    No unused packages found.
+    "###
+    );
+
+    Command::new(get_bin())
+        .arg("pip")
+        .arg("uninstall")
+        .arg("anyio")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .assert()
+        .success();
+
+    uv_snapshot!(Command::new(get_bin())
+        .arg("pip")
+        .arg("autoremove")
+        .arg("--dry-run")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .current_dir(&context.temp_dir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Would uninstall 2 packages
+     - idna==3.4
+     - sniffio==1.3.0
+    "###
+    );
+
+    // The dependencies of `anyio` are removed, but `tomli` was requested directly.
+    uv_snapshot!(Command::new(get_bin())
+        .arg("pip")
+        .arg("autoremove")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .current_dir(&context.temp_dir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Uninstalled 2 packages in [TIME]
+     - idna==3.4
+     - sniffio==1.3.0
+    "###
+    );
+
+    context.assert_command("import tomli").success();
+
+    Ok(())
+}
+
+/// Seed packages are requested at creation time, and so aren't removed as unused dependencies.
+#[test]
+fn autoremove_seed() -> Result<()> {
+    let temp_dir = assert_fs::TempDir::new()?;
+    let cache_dir = assert_fs::TempDir::new()?;
+    let bin = create_bin_with_executables(&temp_dir, &["3.12"]).expect("Failed to create bin dir");
+    let venv = temp_dir.child(".venv");
+
+    Command::new(get_bin())
+        .arg("venv")
+        .arg(venv.as_os_str())
+        .arg("--seed")
+        .arg("--python")
+        .arg("3.12")
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .env("UV_TEST_PYTHON_PATH", bin)
+        .current_dir(&temp_dir)
+        .assert()
+        .success();
+
+    uv_snapshot!(Command::new(get_bin())
+        .arg("pip")
+        .arg("autoremove")
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .env("VIRTUAL_ENV", venv.as_os_str())
+        .current_dir(&temp_dir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    No unused packages found.
+    "###
+    );
+
+    Ok(())
+}
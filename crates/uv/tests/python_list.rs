@@ -0,0 +1,155 @@
+#![cfg(feature = "python")]
+
+use std::process::Command;
+
+use anyhow::Result;
+use assert_fs::prelude::*;
+
+use crate::common::{create_bin_with_executables, get_bin, uv_snapshot};
+
+mod common;
+
+#[test]
+fn list() -> Result<()> {
+    let temp_dir = assert_fs::TempDir::new()?;
+    let cache_dir = assert_fs::TempDir::new()?;
+    let bin = create_bin_with_executables(&temp_dir, &["3.12"]).expect("Failed to create bin dir");
+
+    let filters = &[(
+        r"cpython-3\.12\.\d+-\S+\s+\S+",
+        "cpython-3.12.[X]-[ARCH]  [PATH]",
+    )];
+    uv_snapshot!(filters, Command::new(get_bin())
+        .arg("python")
+        .arg("list")
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .env("UV_TEST_PYTHON_PATH", bin)
+        .current_dir(&temp_dir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    cpython-3.12.[X]-[ARCH]  [PATH]  (PATH)
+
+    ----- stderr -----
+    "###
+    );
+
+    Ok(())
+}
+
+#[test]
+fn list_empty() -> Result<()> {
+    let temp_dir = assert_fs::TempDir::new()?;
+    let cache_dir = assert_fs::TempDir::new()?;
+    let bin = temp_dir.child("bin");
+    bin.create_dir_all()?;
+
+    uv_snapshot!(Command::new(get_bin())
+        .arg("python")
+        .arg("list")
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .env("UV_TEST_PYTHON_PATH", bin.path())
+        .current_dir(&temp_dir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    No Python interpreters found.
+    "###
+    );
+
+    uv_snapshot!(Command::new(get_bin())
+        .arg("python")
+        .arg("list")
+        .arg("--format")
+        .arg("json")
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .env("UV_TEST_PYTHON_PATH", bin.path())
+        .current_dir(&temp_dir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    []
+
+    ----- stderr -----
+    "###
+    );
+
+    Ok(())
+}
+
+/// Interpreters in a pyenv or asdf installation are attributed to the manager.
+#[test]
+#[cfg(unix)]
+fn list_managed() -> Result<()> {
+    let temp_dir = assert_fs::TempDir::new()?;
+    let cache_dir = assert_fs::TempDir::new()?;
+    let bin = create_bin_with_executables(&temp_dir, &["3.12"]).expect("Failed to create bin dir");
+    let python = std::env::split_paths(&bin)
+        .map(|dir| dir.join("python3.12"))
+        .find(|python| python.exists())
+        .expect("Failed to find Python 3.12");
+
+    // Mirror the layout of each manager's installations.
+    let pyenv_root = temp_dir.child("pyenv");
+    let pyenv_bin = pyenv_root.child("versions").child("3.12.1").child("bin");
+    pyenv_bin.create_dir_all()?;
+    fs_err::os::unix::fs::symlink(&python, pyenv_bin.child("python3.12"))?;
+
+    let asdf_root = temp_dir.child("asdf");
+    let asdf_bin = asdf_root
+        .child("installs")
+        .child("python")
+        .child("3.12.1")
+        .child("bin");
+    asdf_bin.create_dir_all()?;
+    fs_err::os::unix::fs::symlink(&python, asdf_bin.child("python3.12"))?;
+
+    let filters = &[(
+        r"cpython-3\.12\.\d+-\S+\s+\S+",
+        "cpython-3.12.[X]-[ARCH]  [PATH]",
+    )];
+
+    uv_snapshot!(filters, Command::new(get_bin())
+        .arg("python")
+        .arg("list")
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .env("UV_TEST_PYTHON_PATH", pyenv_bin.path())
+        .env("PYENV_ROOT", pyenv_root.path())
+        .env_remove("ASDF_DATA_DIR")
+        .current_dir(&temp_dir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    cpython-3.12.[X]-[ARCH]  [PATH]  (pyenv)
+
+    ----- stderr -----
+    "###
+    );
+
+    // A non-existent `PYENV_ROOT` is ignored.
+    uv_snapshot!(filters, Command::new(get_bin())
+        .arg("python")
+        .arg("list")
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .env("UV_TEST_PYTHON_PATH", asdf_bin.path())
+        .env("PYENV_ROOT", temp_dir.child("missing").path())
+        .env("ASDF_DATA_DIR", asdf_root.path())
+        .current_dir(&temp_dir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    cpython-3.12.[X]-[ARCH]  [PATH]  (asdf)
+
+    ----- stderr -----
+    "###
+    );
+
+    Ok(())
+}
@@ -0,0 +1,141 @@
+#![cfg(all(feature = "python", unix))]
+
+use std::process::Command;
+
+use anyhow::Result;
+use assert_fs::prelude::*;
+
+use crate::common::{create_bin_with_executables, get_bin, uv_snapshot, EXCLUDE_NEWER};
+
+mod common;
+
+#[test]
+fn pin() -> Result<()> {
+    let temp_dir = assert_fs::TempDir::new()?;
+    let cache_dir = assert_fs::TempDir::new()?;
+    let bin = create_bin_with_executables(&temp_dir, &["3.12"]).expect("Failed to create bin dir");
+
+    // Without a pin, there's nothing to display.
+    uv_snapshot!(Command::new(get_bin())
+        .arg("python")
+        .arg("pin")
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .env("UV_TEST_PYTHON_PATH", bin.clone())
+        .current_dir(&temp_dir), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    No pinned Python version found.
+    "###
+    );
+
+    // Pin a discoverable interpreter.
+    let filters = &[(
+        r"\(Python 3\.12\.\d+ at: .+\)",
+        "(Python 3.12.[X] at: [PATH])",
+    )];
+    uv_snapshot!(filters, Command::new(get_bin())
+        .arg("python")
+        .arg("pin")
+        .arg("3.12")
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .env("UV_TEST_PYTHON_PATH", bin.clone())
+        .current_dir(&temp_dir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Pinned `.python-version` to `3.12` (Python 3.12.[X] at: [PATH])
+    "###
+    );
+
+    temp_dir
+        .child(".python-version")
+        .assert(predicates::str::diff("3.12\n"));
+
+    // The pin applies to nested directories.
+    let nested = temp_dir.child("nested");
+    nested.create_dir_all()?;
+    uv_snapshot!(Command::new(get_bin())
+        .arg("python")
+        .arg("pin")
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .env("UV_TEST_PYTHON_PATH", bin)
+        .current_dir(&nested), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    3.12
+
+    ----- stderr -----
+    "###
+    );
+
+    Ok(())
+}
+
+#[test]
+fn pin_not_found() -> Result<()> {
+    let temp_dir = assert_fs::TempDir::new()?;
+    let cache_dir = assert_fs::TempDir::new()?;
+    let bin = create_bin_with_executables(&temp_dir, &["3.12"]).expect("Failed to create bin dir");
+
+    uv_snapshot!(Command::new(get_bin())
+        .arg("python")
+        .arg("pin")
+        .arg("3.1000")
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .env("UV_TEST_PYTHON_PATH", bin)
+        .current_dir(&temp_dir), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: No Python 3.1000 In `PATH`. Is Python 3.1000 installed?
+    "###
+    );
+
+    temp_dir
+        .child(".python-version")
+        .assert(predicates::path::missing());
+
+    Ok(())
+}
+
+/// `uv venv` respects the pinned interpreter when `--python` isn't provided.
+#[test]
+fn venv_respects_pin() -> Result<()> {
+    let temp_dir = assert_fs::TempDir::new()?;
+    let cache_dir = assert_fs::TempDir::new()?;
+    let bin = create_bin_with_executables(&temp_dir, &["3.12"]).expect("Failed to create bin dir");
+    temp_dir.child(".python-version").write_str("3.1000\n")?;
+
+    uv_snapshot!(Command::new(get_bin())
+        .arg("venv")
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .env("UV_TEST_PYTHON_PATH", bin)
+        .current_dir(&temp_dir), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+      × No Python 3.1000 In `PATH`. Is Python 3.1000 installed?
+    "###
+    );
+
+    temp_dir.child(".venv").assert(predicates::path::missing());
+
+    Ok(())
+}
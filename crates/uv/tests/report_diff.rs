@@ -0,0 +1,143 @@
+use std::process::Command;
+
+use anyhow::Result;
+use assert_fs::prelude::*;
+use serde_json::json;
+
+use common::uv_snapshot;
+
+use crate::common::get_bin;
+
+mod common;
+
+/// Create a `report diff` command with options shared across scenarios.
+fn command(temp_dir: &assert_fs::TempDir) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("report")
+        .arg("diff")
+        .arg("old.json")
+        .arg("new.json")
+        .current_dir(temp_dir);
+    command
+}
+
+/// Write an installation report with the given `(name, version, sha256)` entries, in the format
+/// produced by `pip install --report`.
+fn write_report(
+    file: &assert_fs::fixture::ChildPath,
+    packages: &[(&str, &str, &str)],
+) -> Result<()> {
+    let install = packages
+        .iter()
+        .map(|(name, version, sha256)| {
+            json!({
+                "download_info": {
+                    "url": format!("https://files.pythonhosted.org/packages/{name}-{version}-py3-none-any.whl"),
+                    "archive_info": {
+                        "hashes": { "sha256": sha256 }
+                    }
+                },
+                "is_direct": false,
+                "requested": true,
+                "metadata": {
+                    "metadata_version": "2.1",
+                    "name": name,
+                    "version": version
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    file.write_str(&serde_json::to_string_pretty(&json!({
+        "version": "1",
+        "pip_version": "24.0",
+        "install": install
+    }))?)?;
+    Ok(())
+}
+
+fn write_reports(temp_dir: &assert_fs::TempDir) -> Result<()> {
+    write_report(
+        &temp_dir.child("old.json"),
+        &[
+            ("anyio", "3.7.1", "aaa"),
+            ("exceptiongroup", "1.2.0", "bbb"),
+            ("idna", "3.4", "ccc"),
+            ("sniffio", "1.3.0", "ddd"),
+        ],
+    )?;
+    write_report(
+        &temp_dir.child("new.json"),
+        &[
+            ("anyio", "4.0.0", "eee"),
+            ("certifi", "2024.2.2", "fff"),
+            ("idna", "3.4", "ggg"),
+            ("sniffio", "1.3.0", "ddd"),
+        ],
+    )?;
+    Ok(())
+}
+
+#[test]
+fn diff_text() -> Result<()> {
+    let temp_dir = assert_fs::TempDir::new()?;
+    write_reports(&temp_dir)?;
+
+    uv_snapshot!(command(&temp_dir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+     ~ anyio 3.7.1 -> 4.0.0 (upgraded)
+     + certifi==2024.2.2
+     - exceptiongroup==1.2.0
+     ~ idna==3.4
+       hash changed from `sha256:ccc` to `sha256:ggg`
+
+    ----- stderr -----
+    "###
+    );
+
+    Ok(())
+}
+
+#[test]
+fn diff_markdown() -> Result<()> {
+    let temp_dir = assert_fs::TempDir::new()?;
+    write_reports(&temp_dir)?;
+
+    uv_snapshot!(command(&temp_dir).arg("--format").arg("markdown"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    | Package | Change | Old | New | Notes |
+    | --- | --- | --- | --- | --- |
+    | `anyio` | upgraded | 3.7.1 | 4.0.0 |  |
+    | `certifi` | added |  | 2024.2.2 |  |
+    | `exceptiongroup` | removed | 1.2.0 |  |  |
+    | `idna` | changed | 3.4 | 3.4 | hash changed from `sha256:ccc` to `sha256:ggg` |
+
+    ----- stderr -----
+    "###
+    );
+
+    Ok(())
+}
+
+#[test]
+fn diff_unchanged() -> Result<()> {
+    let temp_dir = assert_fs::TempDir::new()?;
+    write_report(&temp_dir.child("old.json"), &[("idna", "3.4", "ccc")])?;
+    write_report(&temp_dir.child("new.json"), &[("idna", "3.4", "ccc")])?;
+
+    uv_snapshot!(command(&temp_dir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    No changes
+    "###
+    );
+
+    Ok(())
+}
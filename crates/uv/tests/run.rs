@@ -0,0 +1,219 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::process::Command;
+
+use anyhow::Result;
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+
+use crate::common::{get_bin, uv_snapshot, TestContext, EXCLUDE_NEWER};
+
+mod common;
+
+/// Create a `uv run` command with options shared across scenarios.
+fn command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("run")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .current_dir(&context.temp_dir);
+    command
+}
+
+/// Run a Python script in the project's virtual environment, forwarding its exit code.
+#[test]
+fn run_script() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let script = context.temp_dir.child("script.py");
+    script.write_str(indoc::indoc! {r"
+        import os
+        import sys
+
+        print(sys.version_info[:2])
+        print(os.path.samefile(sys.prefix, os.environ['VIRTUAL_ENV']))
+        sys.exit(3)
+    "})?;
+
+    uv_snapshot!(command(&context).arg("script.py"), @r###"
+    success: false
+    exit_code: 3
+    ----- stdout -----
+    (3, 12)
+    True
+
+    ----- stderr -----
+    "###
+    );
+
+    Ok(())
+}
+
+/// Run a command from the environment's scripts directory, passing through its arguments.
+#[test]
+fn run_command() {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(command(&context).arg("python").arg("-c").arg("import sys; print(sys.argv[1:])").arg("--flag"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    ['--flag']
+
+    ----- stderr -----
+    "###
+    );
+}
+
+/// Run a command in an ephemeral environment with the given requirements, leaving the project's
+/// virtual environment untouched.
+#[test]
+fn run_requirements() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("iniconfig==2.0.0")?;
+
+    command(&context)
+        .arg("--requirement")
+        .arg("requirements.txt")
+        .arg("python")
+        .arg("-c")
+        .arg("import importlib.metadata; print(importlib.metadata.version('iniconfig'))")
+        .assert()
+        .success()
+        .stdout("2.0.0\n");
+
+    // The package isn't available in the project's virtual environment.
+    command(&context)
+        .arg("python")
+        .arg("-c")
+        .arg("import iniconfig")
+        .assert()
+        .failure();
+
+    Ok(())
+}
+
+/// Run a command with additional packages, overlaid on the project's virtual environment.
+#[test]
+fn run_with() {
+    let context = TestContext::new("3.12");
+
+    // Install a package into the project's virtual environment.
+    Command::new(get_bin())
+        .arg("pip")
+        .arg("install")
+        .arg("iniconfig==2.0.0")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .current_dir(&context.temp_dir)
+        .assert()
+        .success();
+
+    // Both the overlaid package and the project's packages are available.
+    command(&context)
+        .arg("--with")
+        .arg("tomli==2.0.1")
+        .arg("python")
+        .arg("-c")
+        .arg("import importlib.metadata as m; print(m.version('tomli'), m.version('iniconfig'))")
+        .assert()
+        .success()
+        .stdout("2.0.1 2.0.0\n");
+
+    // The project's virtual environment is left unmodified.
+    command(&context)
+        .arg("python")
+        .arg("-c")
+        .arg("import tomli")
+        .assert()
+        .failure();
+}
+
+/// Run a command in an isolated environment with the project's requirements, ignoring the
+/// project's virtual environment, and remove the environment once the command exits.
+#[test]
+fn run_isolated() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(indoc::indoc! {r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        dependencies = ["tomli==2.0.1"]
+    "#})?;
+
+    // Install a package into the project's virtual environment.
+    Command::new(get_bin())
+        .arg("pip")
+        .arg("install")
+        .arg("iniconfig==2.0.0")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .current_dir(&context.temp_dir)
+        .assert()
+        .success();
+
+    // The project's requirements are available, but the packages in its virtual environment
+    // aren't.
+    command(&context)
+        .arg("--isolated")
+        .arg("python")
+        .arg("-c")
+        .arg("import importlib.util as u; print(u.find_spec('tomli') is not None, u.find_spec('iniconfig') is not None)")
+        .assert()
+        .success()
+        .stdout("True False\n");
+
+    // The ephemeral environment was removed.
+    let leftover = fs_err::read_dir(context.cache_dir.path())?
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with(".tmp"))
+        .count();
+    assert_eq!(leftover, 0);
+
+    Ok(())
+}
+
+/// Run a script in an isolated environment with the requirements declared in its inline metadata,
+/// in lieu of the project's requirements.
+#[test]
+fn run_isolated_script() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(indoc::indoc! {r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        dependencies = ["tomli==2.0.1"]
+    "#})?;
+    let script = context.temp_dir.child("script.py");
+    script.write_str(indoc::indoc! {r#"
+        # /// script
+        # dependencies = [
+        #   "iniconfig==2.0.0",
+        # ]
+        # ///
+        import importlib.util as u
+
+        print(u.find_spec('iniconfig') is not None, u.find_spec('tomli') is not None)
+    "#})?;
+
+    command(&context)
+        .arg("--isolated")
+        .arg("script.py")
+        .assert()
+        .success()
+        .stdout("True False\n");
+
+    Ok(())
+}
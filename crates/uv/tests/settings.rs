@@ -0,0 +1,203 @@
+use std::process::Command;
+
+use anyhow::Result;
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+
+use crate::common::{get_bin, uv_snapshot};
+
+mod common;
+
+/// Create a `cache dir` command, isolated from any user-level configuration.
+fn cache_dir(temp_dir: &assert_fs::TempDir) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("cache")
+        .arg("dir")
+        .env_remove("UV_CACHE_DIR")
+        .env_remove("UV_CONFIG_FILE")
+        .env_remove("UV_NO_CONFIG")
+        .env("XDG_CONFIG_HOME", temp_dir.child("config").path())
+        .current_dir(temp_dir);
+    command
+}
+
+/// Read the cache directory from a `uv.toml`, resolved relative to the file.
+#[test]
+fn uv_toml() -> Result<()> {
+    let temp_dir = assert_fs::TempDir::new()?;
+    temp_dir
+        .child("uv.toml")
+        .write_str(r#"cache-dir = "custom-cache""#)?;
+    let nested = temp_dir.child("nested");
+    nested.create_dir_all()?;
+
+    let output = cache_dir(&temp_dir)
+        .current_dir(&nested)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let output = String::from_utf8(output)?;
+    assert!(output.trim_end().ends_with("custom-cache"));
+    // The path is relative to the `uv.toml`, rather than the current directory.
+    assert!(!output.contains("nested"));
+
+    Ok(())
+}
+
+/// Read the cache directory from the `[tool.uv]` table in a `pyproject.toml`, and let the
+/// command line take precedence.
+#[test]
+fn pyproject_toml() -> Result<()> {
+    let temp_dir = assert_fs::TempDir::new()?;
+    temp_dir.child("pyproject.toml").write_str(
+        r#"
+        [project]
+        name = "project"
+
+        [tool.uv]
+        cache-dir = "custom-cache"
+        "#,
+    )?;
+
+    let output = cache_dir(&temp_dir)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    assert!(String::from_utf8(output)?
+        .trim_end()
+        .ends_with("custom-cache"));
+
+    let output = cache_dir(&temp_dir)
+        .arg("--cache-dir")
+        .arg("override-cache")
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    assert!(String::from_utf8(output)?
+        .trim_end()
+        .ends_with("override-cache"));
+
+    // With `--no-config`, the `pyproject.toml` is ignored.
+    let output = cache_dir(&temp_dir)
+        .arg("--no-config")
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    assert!(!String::from_utf8(output)?.contains("custom-cache"));
+
+    Ok(())
+}
+
+/// The user-level configuration applies when no project-level configuration is found.
+#[test]
+#[cfg(unix)]
+fn user_config() -> Result<()> {
+    let temp_dir = assert_fs::TempDir::new()?;
+    temp_dir
+        .child("config")
+        .child("uv")
+        .child("uv.toml")
+        .write_str(r#"cache-dir = "/tmp/user-cache""#)?;
+
+    let output = cache_dir(&temp_dir)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    assert!(String::from_utf8(output)?
+        .trim_end()
+        .ends_with("user-cache"));
+
+    Ok(())
+}
+
+/// An invalid discovered configuration file is ignored with a warning.
+#[test]
+fn invalid_value() -> Result<()> {
+    let temp_dir = assert_fs::TempDir::new()?;
+    temp_dir
+        .child("uv.toml")
+        .write_str(r#"resolution = "newest""#)?;
+
+    let filters = [
+        (r"in .*uv.toml", "in [TEMP_DIR]/uv.toml"),
+        (r"(?m)^.*cache.*$", "[CACHE_DIR]"),
+    ];
+    uv_snapshot!(filters, cache_dir(&temp_dir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    [CACHE_DIR]
+
+    ----- stderr -----
+    warning: Ignoring invalid configuration: Invalid configuration in [TEMP_DIR]/uv.toml: Invalid value for `resolution` (`newest`): invalid variant: newest
+    "###
+    );
+
+    Ok(())
+}
+
+/// An invalid configuration file passed via `--config-file` is an error.
+#[test]
+fn invalid_config_file() -> Result<()> {
+    let temp_dir = assert_fs::TempDir::new()?;
+    temp_dir
+        .child("custom.toml")
+        .write_str(r#"resolution = "newest""#)?;
+
+    let filters = [(r"in .*custom.toml", "in [TEMP_DIR]/custom.toml")];
+    uv_snapshot!(filters, cache_dir(&temp_dir)
+        .arg("--config-file")
+        .arg("custom.toml"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Invalid configuration in [TEMP_DIR]/custom.toml: Invalid value for `resolution` (`newest`): invalid variant: newest
+    "###
+    );
+
+    Ok(())
+}
+
+/// A malformed `pyproject.toml` in a parent directory doesn't prevent `--help` or `--version`.
+#[test]
+fn malformed_pyproject_toml() -> Result<()> {
+    let temp_dir = assert_fs::TempDir::new()?;
+    temp_dir.child("pyproject.toml").write_str("[project")?;
+    let nested = temp_dir.child("nested");
+    nested.create_dir_all()?;
+
+    cache_dir(&temp_dir)
+        .current_dir(&nested)
+        .assert()
+        .success()
+        .stderr(predicates::str::contains(
+            "warning: Ignoring invalid configuration: Failed to parse:",
+        ));
+
+    Command::new(get_bin())
+        .arg("--version")
+        .current_dir(&nested)
+        .assert()
+        .success();
+
+    Command::new(get_bin())
+        .arg("--help")
+        .current_dir(&nested)
+        .assert()
+        .success();
+
+    Ok(())
+}
@@ -531,6 +531,135 @@ fn non_empty_dir_exists() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn clone_into_self() -> Result<()> {
+    let temp_dir = assert_fs::TempDir::new()?;
+    let cache_dir = assert_fs::TempDir::new()?;
+    let bin = create_bin_with_executables(&temp_dir, &["3.12"]).expect("Failed to create bin dir");
+    let venv = temp_dir.child(".venv");
+
+    Command::new(get_bin())
+        .arg("venv")
+        .arg(venv.as_os_str())
+        .arg("--python")
+        .arg("3.12")
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .env("UV_TEST_PYTHON_PATH", bin.clone())
+        .current_dir(&temp_dir)
+        .assert()
+        .success();
+
+    // Cloning a virtualenv into itself should fail, rather than removing the source.
+    let filter_venv = regex::escape(&venv.simplified_display().to_string());
+    let filters = &[(filter_venv.as_str(), "/home/ferris/project/.venv")];
+    uv_snapshot!(filters, Command::new(get_bin())
+        .arg("venv")
+        .arg(venv.as_os_str())
+        .arg("--clone")
+        .arg(".venv")
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .env("UV_NO_WRAP", "1")
+        .env("UV_TEST_PYTHON_PATH", bin)
+        .current_dir(&temp_dir), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    uv::venv::clone_into_self
+
+      × Cannot clone a virtualenv into itself: `/home/ferris/project/.venv`
+    "###
+    );
+
+    venv.child("pyvenv.cfg").assert(predicates::path::is_file());
+
+    Ok(())
+}
+
+/// Clone a virtualenv with installed packages, which should be reinstalled into the new
+/// environment, leaving the source environment unchanged.
+#[test]
+fn clone_with_packages() -> Result<()> {
+    let temp_dir = assert_fs::TempDir::new()?;
+    let cache_dir = assert_fs::TempDir::new()?;
+    let bin = create_bin_with_executables(&temp_dir, &["3.12"]).expect("Failed to create bin dir");
+    let source = temp_dir.child(".venv");
+    let target = temp_dir.child("clone");
+
+    Command::new(get_bin())
+        .arg("venv")
+        .arg(source.as_os_str())
+        .arg("--python")
+        .arg("3.12")
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .env("UV_TEST_PYTHON_PATH", bin.clone())
+        .current_dir(&temp_dir)
+        .assert()
+        .success();
+
+    Command::new(get_bin())
+        .arg("pip")
+        .arg("install")
+        .arg("markupsafe==2.1.3")
+        .arg("tomli==2.0.1")
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .env("VIRTUAL_ENV", source.as_os_str())
+        .current_dir(&temp_dir)
+        .assert()
+        .success();
+
+    let freeze = |venv: &std::path::Path| -> Result<String> {
+        let output = Command::new(get_bin())
+            .arg("pip")
+            .arg("freeze")
+            .arg("--cache-dir")
+            .arg(cache_dir.path())
+            .env("VIRTUAL_ENV", venv.as_os_str())
+            .current_dir(&temp_dir)
+            .output()?;
+        assert!(output.status.success());
+        Ok(String::from_utf8(output.stdout)?)
+    };
+    let before = freeze(&source)?;
+    assert_eq!(before, "markupsafe==2.1.3\ntomli==2.0.1\n");
+
+    Command::new(get_bin())
+        .arg("venv")
+        .arg(target.as_os_str())
+        .arg("--clone")
+        .arg(source.as_os_str())
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .env("UV_TEST_PYTHON_PATH", bin)
+        .current_dir(&temp_dir)
+        .assert()
+        .success();
+
+    // The clone has the same packages as the source, which is unchanged.
+    assert_eq!(freeze(&target)?, before);
+    assert_eq!(freeze(&source)?, before);
+    source
+        .child("pyvenv.cfg")
+        .assert(predicates::path::is_file());
+
+    Ok(())
+}
+
 #[test]
 #[cfg(windows)]
 fn windows_shims() -> Result<()> {
@@ -731,3 +860,42 @@ fn verify_nested_pyvenv_cfg() -> Result<()> {
 
     Ok(())
 }
+
+/// With `UV_LOG_FORMAT=json`, verbose log messages are written as JSON records.
+#[test]
+fn verbose_json_logs() -> Result<()> {
+    let temp_dir = assert_fs::TempDir::new()?;
+    let cache_dir = assert_fs::TempDir::new()?;
+    let bin = create_bin_with_executables(&temp_dir, &["3.12"]).expect("Failed to create bin dir");
+    let venv = temp_dir.child(".venv");
+
+    let output = Command::new(get_bin())
+        .arg("venv")
+        .arg(venv.as_os_str())
+        .arg("--python")
+        .arg("3.12")
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .arg("-v")
+        .env("UV_LOG_FORMAT", "json")
+        .env("UV_TEST_PYTHON_PATH", bin)
+        .current_dir(&temp_dir)
+        .assert()
+        .success()
+        .get_output()
+        .stderr
+        .clone();
+    let stderr = String::from_utf8(output)?;
+
+    // Every log record should be valid JSON, and at least one should be a debug message.
+    let records = stderr
+        .lines()
+        .filter(|line| line.starts_with('{'))
+        .map(serde_json::from_str::<serde_json::Value>)
+        .collect::<Result<Vec<_>, _>>()?;
+    assert!(records.iter().any(|record| record["level"] == "DEBUG"));
+
+    Ok(())
+}